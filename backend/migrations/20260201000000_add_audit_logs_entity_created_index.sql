@@ -0,0 +1,15 @@
+-- sqlx:no-transaction
+-- Composite index for entity audit history lookups
+-- get_audit_logs_by_entity filters on (entity_type, entity_id) and orders by created_at DESC.
+-- The existing idx_audit_logs_entity covers the filter but still requires a sort; this
+-- index serves both the filter and the ordering directly.
+--
+-- On stock PostgreSQL this would be CREATE INDEX CONCURRENTLY so the build doesn't hold
+-- a write lock on audit_logs while production traffic keeps inserting rows.
+-- DSQL doesn't support CONCURRENTLY; its CREATE INDEX ASYNC is the equivalent - the index
+-- is built in the background without blocking writes. Like CONCURRENTLY, it can't run
+-- inside a transaction, hence the no-transaction directive above.
+--
+-- The user_id filter in get_audit_logs is already covered by idx_audit_logs_user_id
+-- (create_audit migration), so no additional index is needed for it.
+CREATE INDEX ASYNC audit_logs_entity_idx ON audit_logs(entity_type, entity_id, created_at DESC);
@@ -0,0 +1,14 @@
+-- sqlx:no-transaction
+-- Trigram search index for items.name (DSQL-compatible)
+-- list_items searches with `name ILIKE '%term%'`. The leading wildcard means the B-tree
+-- idx_items_name (create_hierarchy migration) can't be used, so search is a sequential scan.
+--
+-- On stock PostgreSQL the right fix is a GIN trigram index:
+--   CREATE EXTENSION IF NOT EXISTS pg_trgm;
+--   CREATE INDEX CONCURRENTLY items_name_trgm_idx ON items USING gin(name gin_trgm_ops);
+--
+-- DSQL supports neither extensions nor GIN indexes, and doesn't run PL/pgSQL DO blocks,
+-- so a migration that tries the extension and falls back at runtime isn't possible here.
+-- The B-tree fallback on name already exists (idx_items_name), so there is nothing to add.
+-- This migration is kept for migration history but does nothing on DSQL
+SELECT 1;
@@ -0,0 +1,3 @@
+-- sqlx:no-transaction
+-- Index for per-IP rate limiting of contact submissions
+CREATE INDEX ASYNC idx_contact_submissions_ip_created_at ON contact_submissions(ip_address, created_at);
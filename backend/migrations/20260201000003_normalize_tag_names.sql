@@ -0,0 +1,18 @@
+-- Normalize existing tag names: trim, collapse internal whitespace, and lowercase.
+-- Matches normalize_tag_name() in the application.
+-- When several tags normalize to the same name, only the oldest is renamed so the
+-- UNIQUE(name) constraint isn't violated; the rest are left as-is for manual merging.
+UPDATE tags
+SET name = LOWER(REGEXP_REPLACE(TRIM(name), '\s+', ' ', 'g'))
+WHERE name <> LOWER(REGEXP_REPLACE(TRIM(name), '\s+', ' ', 'g'))
+  AND NOT EXISTS (
+    SELECT 1 FROM tags other
+    WHERE other.name = LOWER(REGEXP_REPLACE(TRIM(tags.name), '\s+', ' ', 'g'))
+  )
+  AND id = (
+    SELECT dup.id FROM tags dup
+    WHERE LOWER(REGEXP_REPLACE(TRIM(dup.name), '\s+', ' ', 'g'))
+        = LOWER(REGEXP_REPLACE(TRIM(tags.name), '\s+', ' ', 'g'))
+    ORDER BY dup.created_at, dup.id
+    LIMIT 1
+  );
@@ -0,0 +1,9 @@
+-- sqlx:no-transaction
+-- Case-insensitive barcode uniqueness (DSQL-compatible)
+-- Barcode lookups compare LOWER(barcode) so Code128/Code39 barcodes match regardless of case.
+--
+-- A unique index on LOWER(barcode) would need an expression index, which DSQL doesn't
+-- support, so uniqueness is enforced in application code instead: routes/items.rs
+-- returns 409 when another item already has the barcode, in any case.
+-- This migration is kept for migration history but does nothing on DSQL
+SELECT 1;
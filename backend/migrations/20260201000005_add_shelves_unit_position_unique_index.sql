@@ -0,0 +1,21 @@
+-- sqlx:no-transaction
+-- Enforce unique shelf positions within a shelving unit. This is a unique index rather
+-- than a constraint because DSQL rejects ADD CONSTRAINT on existing tables. Shelves
+-- without a position never collide, since NULLs are distinct in a unique index.
+--
+-- Shelves already sharing a position are moved after the last shelf of their unit
+-- first, keeping the oldest one in place, so the index can be built.
+UPDATE shelves
+SET position = renumbered.position
+FROM (
+    SELECT id,
+        MAX(position) OVER (PARTITION BY shelving_unit_id)
+            + ROW_NUMBER() OVER (PARTITION BY shelving_unit_id ORDER BY position, created_at, id) AS position,
+        ROW_NUMBER() OVER (PARTITION BY shelving_unit_id, position ORDER BY created_at, id) AS rank
+    FROM shelves
+    WHERE position IS NOT NULL
+) renumbered
+WHERE shelves.id = renumbered.id
+  AND renumbered.rank > 1;
+
+CREATE UNIQUE INDEX ASYNC idx_shelves_unit_position_unique ON shelves(shelving_unit_id, position);
@@ -0,0 +1,21 @@
+-- sqlx:no-transaction
+-- Label batches: who generated each batch of labels, and why
+-- Note: No foreign key constraints for DSQL compatibility
+CREATE TABLE label_batches (
+    id UUID PRIMARY KEY,
+    label_count INTEGER NOT NULL,
+    template VARCHAR(50) NOT NULL,
+    purpose TEXT,
+    created_by UUID, -- References users(id) - enforced in application
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX ASYNC idx_label_batches_created_at ON label_batches(created_at DESC);
+
+-- Backfill batches generated before this table existed. The creator and
+-- purpose were never recorded, and avery_18660 was the only template.
+INSERT INTO label_batches (id, label_count, template, created_at)
+SELECT batch_id, COUNT(*), 'avery_18660', MIN(created_at)
+FROM labels
+WHERE batch_id IS NOT NULL
+GROUP BY batch_id;
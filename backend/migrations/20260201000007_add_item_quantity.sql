@@ -0,0 +1,14 @@
+-- sqlx:no-transaction
+-- Stock tracking: how many of an item are on hand, and when to flag it as low
+-- Existing items count as a single unit
+--
+-- DSQL rejects ADD COLUMN ... DEFAULT, so quantity is added nullable and existing rows
+-- are backfilled here. The application reads a NULL quantity as 1.
+--
+-- DSQL rejects ADD CONSTRAINT on existing tables, so quantities are kept non-negative by
+-- the application-side checks (quantities_valid and the guarded UPDATE in
+-- adjust_item_quantity).
+ALTER TABLE items ADD COLUMN quantity INTEGER;
+ALTER TABLE items ADD COLUMN min_quantity INTEGER;
+
+UPDATE items SET quantity = 1 WHERE quantity IS NULL;
@@ -0,0 +1,6 @@
+-- sqlx:no-transaction
+-- Soft delete for items: DELETE /api/items/:id sets deleted_at and the item moves to the
+-- trash, from where it can be restored or purged. Live queries filter on deleted_at IS NULL.
+ALTER TABLE items ADD COLUMN deleted_at TIMESTAMPTZ;
+
+CREATE INDEX ASYNC idx_items_deleted_at ON items(deleted_at);
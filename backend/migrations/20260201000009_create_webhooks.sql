@@ -0,0 +1,32 @@
+-- sqlx:no-transaction
+-- Webhooks: POST a signed JSON body to a URL whenever a matching audit log entry is written
+-- Note: No foreign key constraints for DSQL compatibility
+CREATE TABLE webhooks (
+    id UUID PRIMARY KEY,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL, -- HMAC-SHA256 key for the X-Inventory-Signature header
+    -- JSON array of "<entity_type>.<action>" patterns, e.g. ["item.create", "container.*"].
+    -- Stored as TEXT because DSQL has no JSON/JSONB column types.
+    event_types TEXT NOT NULL,
+    active BOOLEAN NOT NULL,
+    created_by UUID NOT NULL, -- References users(id) - enforced in application
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX ASYNC idx_webhooks_active ON webhooks(active);
+
+-- One row per delivery attempt
+CREATE TABLE webhook_deliveries (
+    id UUID PRIMARY KEY,
+    webhook_id UUID NOT NULL, -- References webhooks(id) - enforced in application
+    audit_log_id UUID NOT NULL, -- References audit_logs(id) - enforced in application
+    event VARCHAR(100) NOT NULL,
+    attempt INTEGER NOT NULL,
+    succeeded BOOLEAN NOT NULL,
+    status_code INTEGER, -- NULL when no response was received
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX ASYNC idx_webhook_deliveries_webhook_created ON webhook_deliveries(webhook_id, created_at DESC);
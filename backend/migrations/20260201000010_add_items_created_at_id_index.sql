@@ -0,0 +1,4 @@
+-- sqlx:no-transaction
+-- Supports keyset pagination of GET /api/items: the cursor condition
+-- (created_at, id) < ($1, $2) with ORDER BY created_at DESC, id DESC reads straight off this index
+CREATE INDEX ASYNC idx_items_created_at_id ON items(created_at DESC, id DESC);
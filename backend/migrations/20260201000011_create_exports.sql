@@ -0,0 +1,17 @@
+-- sqlx:no-transaction
+-- Inventory exports: a ZIP of inventory.json and every photo, built in the background
+-- Note: No foreign key constraints for DSQL compatibility
+CREATE TABLE exports (
+    id UUID PRIMARY KEY,
+    status VARCHAR(20) NOT NULL, -- pending, complete or failed
+    s3_key TEXT, -- Set once the archive is uploaded
+    download_url TEXT, -- Presigned, valid until download_url_expires_at
+    download_url_expires_at TIMESTAMPTZ,
+    photo_count INTEGER,
+    error TEXT,
+    created_by UUID NOT NULL, -- References users(id) - enforced in application
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX ASYNC idx_exports_created_at ON exports(created_at DESC);
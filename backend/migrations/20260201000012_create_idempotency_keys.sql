@@ -0,0 +1,9 @@
+-- sqlx:no-transaction
+-- Responses to requests sent with an X-Idempotency-Key header, replayed for 24 hours
+CREATE TABLE idempotency_keys (
+    key TEXT PRIMARY KEY, -- "<user_id> <METHOD> <path> <key>"
+    response_body TEXT, -- NULL until the first request with the key succeeds
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX ASYNC idx_idempotency_keys_created_at ON idempotency_keys(created_at);
@@ -0,0 +1,13 @@
+-- sqlx:no-transaction
+-- Trigram search index for tags.name (DSQL-compatible)
+-- list_tags (?q=) and the tag autocomplete endpoint search with `name ILIKE '%term%'`,
+-- which can't use the B-tree idx_tags_name (create_tags migration).
+--
+-- On stock PostgreSQL the right fix is a GIN trigram index:
+--   CREATE EXTENSION IF NOT EXISTS pg_trgm;
+--   CREATE INDEX CONCURRENTLY tags_name_trgm_idx ON tags USING gin(name gin_trgm_ops);
+--
+-- As with add_items_name_search_index, DSQL supports neither extensions nor GIN indexes.
+-- Tags are few enough that the scan stays cheap.
+-- This migration is kept for migration history but does nothing on DSQL
+SELECT 1;
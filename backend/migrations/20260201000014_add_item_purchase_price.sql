@@ -0,0 +1,9 @@
+-- sqlx:no-transaction
+-- What an item cost, for the inventory valuation report
+-- currency is an ISO 4217 code; the application fills in USD when a price is set
+-- without one
+--
+-- DSQL rejects ADD CONSTRAINT on existing tables, so prices and currencies are checked
+-- in the application (parse_currency and purchase_price_valid).
+ALTER TABLE items ADD COLUMN purchase_price NUMERIC(12, 2);
+ALTER TABLE items ADD COLUMN currency VARCHAR(3);
@@ -0,0 +1,12 @@
+-- sqlx:no-transaction
+-- Triage state for contact submissions: unread, read, replied or archived
+--
+-- DSQL rejects ADD COLUMN ... DEFAULT, so status is added nullable and existing rows
+-- are backfilled here; new rows always get a status from the application.
+ALTER TABLE contact_submissions ADD COLUMN status VARCHAR(20);
+ALTER TABLE contact_submissions ADD COLUMN admin_notes TEXT;
+ALTER TABLE contact_submissions ADD COLUMN replied_at TIMESTAMPTZ;
+
+UPDATE contact_submissions SET status = 'unread' WHERE status IS NULL;
+
+CREATE INDEX ASYNC idx_contact_submissions_status ON contact_submissions(status);
@@ -0,0 +1,26 @@
+-- sqlx:no-transaction
+-- User-defined label sheet layouts, alongside the built-in Avery templates
+-- Note: No foreign key constraints for DSQL compatibility
+CREATE TABLE label_templates (
+    id UUID PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    sheet_width_mm DOUBLE PRECISION NOT NULL,
+    sheet_height_mm DOUBLE PRECISION NOT NULL,
+    label_width_mm DOUBLE PRECISION NOT NULL,
+    label_height_mm DOUBLE PRECISION NOT NULL,
+    cols INTEGER NOT NULL,
+    rows INTEGER NOT NULL,
+    top_margin_mm DOUBLE PRECISION NOT NULL,
+    left_margin_mm DOUBLE PRECISION NOT NULL,
+    col_spacing_mm DOUBLE PRECISION NOT NULL,
+    row_spacing_mm DOUBLE PRECISION NOT NULL,
+    created_by UUID, -- References users(id) - enforced in application
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX ASYNC idx_label_templates_created_at ON label_templates(created_at DESC);
+
+-- The layout each batch was generated with, so reprints match even after a custom
+-- template is edited or deleted. JSON in a TEXT column because DSQL has no JSON/JSONB
+-- column types; NULL for batches generated before this column existed.
+ALTER TABLE label_batches ADD COLUMN template_config TEXT;
@@ -0,0 +1,7 @@
+-- sqlx:no-transaction
+-- Order of containers among their siblings on a shelf or inside a parent container.
+-- Existing containers stay NULL and sort after positioned ones, oldest first.
+ALTER TABLE containers ADD COLUMN position INTEGER;
+
+CREATE INDEX ASYNC idx_containers_shelf_position ON containers(shelf_id, position);
+CREATE INDEX ASYNC idx_containers_parent_position ON containers(parent_container_id, position);
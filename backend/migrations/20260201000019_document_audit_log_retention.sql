@@ -0,0 +1,5 @@
+-- sqlx:no-transaction
+-- Records the audit log retention policy on the table itself, pg_partman style, so
+-- it shows up in \d+ for anyone inspecting the schema. Nothing enforces it here: an
+-- admin runs DELETE /api/admin/audit-logs/purge, which deletes in batches of 1000.
+COMMENT ON TABLE audit_logs IS 'retention: rows older than the requested older_than_days are removed by DELETE /api/admin/audit-logs/purge (admin only, batches of 1000 by created_at); no automatic partitioning or expiry';
@@ -0,0 +1,6 @@
+-- sqlx:no-transaction
+-- Perceptual hash of each photo, for finding near-duplicates. Stored as 16 hex
+-- digits rather than BIT(64) because DSQL has no bit string type; Hamming distances
+-- are computed in the application. Filled in in the background after a photo is
+-- created, so it stays NULL for photos that aren't images or couldn't be decoded.
+ALTER TABLE photos ADD COLUMN phash VARCHAR(16);
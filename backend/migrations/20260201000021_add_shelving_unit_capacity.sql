@@ -0,0 +1,6 @@
+-- sqlx:no-transaction
+-- Optional capacity of a shelving unit. max_shelves is enforced when shelves are
+-- added or moved in; max_weight_kg is informational only. Both stay NULL (no
+-- limit) for existing units.
+ALTER TABLE shelving_units ADD COLUMN max_shelves INTEGER;
+ALTER TABLE shelving_units ADD COLUMN max_weight_kg DOUBLE PRECISION;
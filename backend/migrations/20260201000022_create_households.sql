@@ -0,0 +1,64 @@
+-- sqlx:no-transaction
+-- Households: each inventory belongs to one household, and users see the inventory of
+-- the household they belong to. Invites let an owner bring other users in.
+-- Note: No foreign key constraints for DSQL compatibility
+CREATE TABLE households (
+    id UUID PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    created_by UUID, -- References users(id) - enforced in application; NULL for the default household
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE household_memberships (
+    household_id UUID NOT NULL, -- References households(id) - enforced in application
+    user_id UUID NOT NULL, -- References users(id) - enforced in application
+    role VARCHAR(20) NOT NULL, -- 'owner' or 'member'
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (household_id, user_id)
+);
+
+CREATE INDEX ASYNC idx_household_memberships_user ON household_memberships(user_id, joined_at DESC);
+
+-- Single-use invite links. Only the SHA-256 of the token is stored.
+CREATE TABLE invites (
+    id UUID PRIMARY KEY,
+    household_id UUID NOT NULL, -- References households(id) - enforced in application
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_by UUID NOT NULL, -- References users(id) - enforced in application
+    expires_at TIMESTAMPTZ NOT NULL,
+    accepted_by UUID, -- References users(id) - enforced in application
+    accepted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Owning household of every top-level and located entity. Tags stay global.
+ALTER TABLE rooms ADD COLUMN household_id UUID;
+ALTER TABLE shelving_units ADD COLUMN household_id UUID;
+ALTER TABLE shelves ADD COLUMN household_id UUID;
+ALTER TABLE containers ADD COLUMN household_id UUID;
+ALTER TABLE items ADD COLUMN household_id UUID;
+
+CREATE INDEX ASYNC idx_rooms_household ON rooms(household_id);
+CREATE INDEX ASYNC idx_shelving_units_household ON shelving_units(household_id);
+CREATE INDEX ASYNC idx_shelves_household ON shelves(household_id);
+CREATE INDEX ASYNC idx_containers_household ON containers(household_id);
+CREATE INDEX ASYNC idx_items_household ON items(household_id);
+
+-- Everything that existed before households goes into a default household (the id
+-- matches DEFAULT_HOUSEHOLD_ID in the application). The oldest user owns it and
+-- everyone else is a member.
+INSERT INTO households (id, name, created_by)
+VALUES ('00000000-0000-0000-0000-000000000001', 'Home', NULL);
+
+INSERT INTO household_memberships (household_id, user_id, role)
+SELECT
+    '00000000-0000-0000-0000-000000000001',
+    id,
+    CASE WHEN id = (SELECT id FROM users ORDER BY created_at, id LIMIT 1) THEN 'owner' ELSE 'member' END
+FROM users;
+
+UPDATE rooms SET household_id = '00000000-0000-0000-0000-000000000001' WHERE household_id IS NULL;
+UPDATE shelving_units SET household_id = '00000000-0000-0000-0000-000000000001' WHERE household_id IS NULL;
+UPDATE shelves SET household_id = '00000000-0000-0000-0000-000000000001' WHERE household_id IS NULL;
+UPDATE containers SET household_id = '00000000-0000-0000-0000-000000000001' WHERE household_id IS NULL;
+UPDATE items SET household_id = '00000000-0000-0000-0000-000000000001' WHERE household_id IS NULL;
@@ -0,0 +1,19 @@
+-- sqlx:no-transaction
+-- Owning household of each label batch. A label belongs to the household of its
+-- batch, so households only see and assign their own labels.
+ALTER TABLE label_batches ADD COLUMN household_id UUID;
+
+CREATE INDEX ASYNC idx_label_batches_household ON label_batches(household_id, created_at DESC);
+
+-- Batches go to the household their creator works in; those from before creators
+-- were recorded go to the default household, like the rest of the old inventory
+UPDATE label_batches
+SET household_id = (
+    SELECT household_id FROM household_memberships
+    WHERE user_id = label_batches.created_by
+    ORDER BY joined_at DESC
+    LIMIT 1
+)
+WHERE household_id IS NULL;
+
+UPDATE label_batches SET household_id = '00000000-0000-0000-0000-000000000001' WHERE household_id IS NULL;
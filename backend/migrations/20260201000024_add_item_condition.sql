@@ -0,0 +1,10 @@
+-- sqlx:no-transaction
+-- Physical condition of an item; NULL when it hasn't been recorded
+--
+-- DSQL rejects ADD CONSTRAINT on existing tables, so the migration filter skips the
+-- constraint there and the application-side check (Condition::from_str) applies on
+-- its own.
+ALTER TABLE items ADD COLUMN condition VARCHAR(20);
+
+ALTER TABLE items ADD CONSTRAINT items_condition_valid
+    CHECK (condition IN ('new', 'like_new', 'good', 'fair', 'poor') OR condition IS NULL);
@@ -0,0 +1,12 @@
+-- sqlx:no-transaction
+-- Warranty expiry reminders: when an item's warranty runs out, and whether its owner
+-- wants to hear about it
+--
+-- DSQL rejects ADD COLUMN ... DEFAULT, so reminder_enabled is added nullable and
+-- existing rows are backfilled here. The application reads a NULL as false.
+ALTER TABLE items ADD COLUMN warranty_expires_on DATE;
+ALTER TABLE items ADD COLUMN reminder_enabled BOOLEAN;
+
+UPDATE items SET reminder_enabled = false WHERE reminder_enabled IS NULL;
+
+CREATE INDEX ASYNC idx_items_warranty_expires_on ON items(warranty_expires_on);
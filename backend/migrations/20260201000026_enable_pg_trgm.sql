@@ -0,0 +1,7 @@
+-- sqlx:no-transaction
+-- pg_trgm provides similarity(), used by the duplicate check on item creation
+-- (POST /api/items?check_duplicates=true).
+--
+-- DSQL doesn't support extensions, so the migration filter skips this statement there
+-- and the duplicate check compares names in the application instead.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
@@ -0,0 +1,7 @@
+-- sqlx:no-transaction
+-- EXIF metadata read from photos: camera, capture time and orientation as JSON, and
+-- where the photo was taken. The JSON is stored as TEXT because DSQL has no JSON/JSONB
+-- column types.
+ALTER TABLE photos ADD COLUMN exif_data TEXT;
+ALTER TABLE photos ADD COLUMN lat DOUBLE PRECISION;
+ALTER TABLE photos ADD COLUMN lng DOUBLE PRECISION;
@@ -0,0 +1,15 @@
+-- sqlx:no-transaction
+-- When labels, contact submissions and entity tags last changed. DSQL has no triggers,
+-- so the application sets updated_at = NOW() in every INSERT and UPDATE (see
+-- create_triggers).
+--
+-- DSQL rejects ADD COLUMN ... DEFAULT, so each updated_at is added nullable and
+-- existing rows are backfilled here; new rows always get a value from the application.
+ALTER TABLE labels ADD COLUMN updated_at TIMESTAMPTZ;
+ALTER TABLE contact_submissions ADD COLUMN updated_at TIMESTAMPTZ;
+ALTER TABLE entity_tags ADD COLUMN updated_at TIMESTAMPTZ;
+
+-- Existing rows last changed no later than their newest known timestamp
+UPDATE labels SET updated_at = COALESCE(assigned_at, created_at);
+UPDATE contact_submissions SET updated_at = COALESCE(replied_at, created_at);
+UPDATE entity_tags SET updated_at = created_at;
@@ -0,0 +1,4 @@
+-- sqlx:no-transaction
+-- A room's floor plan image. It's a row in photos with entity_type 'room_floor_plan',
+-- kept apart from the room's regular photos.
+ALTER TABLE rooms ADD COLUMN floor_plan_photo_id UUID; -- References photos(id) - enforced in application
@@ -0,0 +1,9 @@
+-- sqlx:no-transaction
+-- Whether a user's profile, with their items, can be viewed without logging in
+-- (GET /api/users/:id/public). Profiles are private until the user opts in.
+--
+-- DSQL rejects ADD COLUMN ... DEFAULT, so is_public is added nullable and existing
+-- rows are backfilled here. The application reads a NULL as false.
+ALTER TABLE users ADD COLUMN is_public BOOLEAN;
+
+UPDATE users SET is_public = false WHERE is_public IS NULL;
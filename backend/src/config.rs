@@ -0,0 +1,217 @@
+use anyhow::Context;
+use axum::http::HeaderValue;
+use std::env;
+use std::str::FromStr;
+
+use crate::db::DbDialect;
+
+/// Settings read from the environment at startup.
+///
+/// S3 (`S3_*`/`AWS_*`) and vision (`VISION_BACKEND`, `ANTHROPIC_API_KEY`,
+/// `OPENAI_API_KEY`) settings are read by their services directly, since both
+/// are optional or have defaults.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// `APP_BASE_URL`: public frontend URL, defaults to `http://localhost:5173`
+    pub app_base_url: String,
+    /// `RECAPTCHA_SECRET_KEY` (required)
+    pub recaptcha_secret_key: String,
+    /// `RECAPTCHA_THRESHOLD`: minimum score to accept, defaults to 0.5
+    pub recaptcha_threshold: f64,
+    /// `GOOGLE_CLIENT_ID` (required)
+    pub google_client_id: String,
+    /// `GOOGLE_CLIENT_SECRET` (required)
+    pub google_client_secret: String,
+    /// `GOOGLE_REDIRECT_URL` (required)
+    pub google_redirect_url: String,
+    /// `RATE_LIMIT_CONTACT_RPM`: contact form submissions allowed per IP per minute,
+    /// per instance. Defaults to 5.
+    pub rate_limit_contact_rpm: u32,
+    /// `ADMIN_EMAIL`: the user with this email may use the admin-only endpoints, such as
+    /// webhooks and every user's items and stats. Optional.
+    pub admin_email: Option<String>,
+    /// `MAX_CONTAINER_DEPTH`: how many containers may be nested inside each other,
+    /// counting the one on the shelf as 1. Defaults to 10.
+    pub max_container_depth: i32,
+    /// `CORS_ALLOWED_ORIGINS`: comma-separated origins allowed to call the API.
+    /// Unset or `*` allows any origin, which is only accepted when `APP_ENV` is
+    /// `development` (the default outside Lambda).
+    pub cors_origins: CorsOrigins,
+    /// `DB_DIALECT`: `postgres` or `dsql`, see [`DbDialect::from_env`]
+    pub db_dialect: DbDialect,
+}
+
+impl Config {
+    pub fn from_env() -> anyhow::Result<Self> {
+        let required = |name: &str| {
+            env::var(name).with_context(|| format!("Missing {} environment variable", name))
+        };
+
+        let app_env = AppEnv::from_env()?;
+        let cors_origins =
+            CorsOrigins::resolve(env::var("CORS_ALLOWED_ORIGINS").ok().as_deref(), app_env)
+                .inspect_err(|e| tracing::error!("Refusing to start: {:#}", e))?;
+
+        Ok(Self {
+            app_base_url: env::var("APP_BASE_URL")
+                .unwrap_or_else(|_| "http://localhost:5173".to_string()),
+            recaptcha_secret_key: required("RECAPTCHA_SECRET_KEY")?,
+            recaptcha_threshold: env::var("RECAPTCHA_THRESHOLD")
+                .unwrap_or_else(|_| "0.5".to_string())
+                .parse::<f64>()
+                .context("RECAPTCHA_THRESHOLD must be a valid f64")?,
+            google_client_id: required("GOOGLE_CLIENT_ID")?,
+            google_client_secret: required("GOOGLE_CLIENT_SECRET")?,
+            google_redirect_url: required("GOOGLE_REDIRECT_URL")?,
+            rate_limit_contact_rpm: env::var("RATE_LIMIT_CONTACT_RPM")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse::<u32>()
+                .context("RATE_LIMIT_CONTACT_RPM must be a valid u32")?,
+            admin_email: env::var("ADMIN_EMAIL")
+                .ok()
+                .map(|email| email.trim().to_string())
+                .filter(|email| !email.is_empty()),
+            max_container_depth: env::var("MAX_CONTAINER_DEPTH")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse::<i32>()
+                .ok()
+                .filter(|depth| *depth > 0)
+                .context("MAX_CONTAINER_DEPTH must be a positive integer")?,
+            cors_origins,
+            db_dialect: DbDialect::from_env().map_err(anyhow::Error::msg)?,
+        })
+    }
+}
+
+/// Deployment environment, from `APP_ENV`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppEnv {
+    Development,
+    Production,
+}
+
+impl AppEnv {
+    /// `APP_ENV` when set, otherwise production in Lambda and development elsewhere
+    pub fn from_env() -> anyhow::Result<Self> {
+        match env::var("APP_ENV") {
+            Ok(app_env) => app_env.parse().map_err(anyhow::Error::msg),
+            Err(_) if env::var("AWS_LAMBDA_FUNCTION_NAME").is_ok() => Ok(AppEnv::Production),
+            Err(_) => Ok(AppEnv::Development),
+        }
+    }
+}
+
+impl FromStr for AppEnv {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "development" | "dev" => Ok(AppEnv::Development),
+            "production" | "prod" => Ok(AppEnv::Production),
+            other => Err(format!(
+                "Unknown APP_ENV '{}': expected development or production",
+                other
+            )),
+        }
+    }
+}
+
+/// Origins the CORS layer lets call the API
+#[derive(Debug, Clone, PartialEq)]
+pub enum CorsOrigins {
+    Any,
+    List(Vec<HeaderValue>),
+}
+
+impl CorsOrigins {
+    /// Parse `CORS_ALLOWED_ORIGINS`. An unset, empty or `*` value means any origin,
+    /// which production refuses: there every allowed origin must be listed.
+    pub fn resolve(raw: Option<&str>, app_env: AppEnv) -> anyhow::Result<Self> {
+        let origins: Vec<&str> = raw
+            .unwrap_or_default()
+            .split(',')
+            .map(|origin| origin.trim().trim_end_matches('/'))
+            .filter(|origin| !origin.is_empty())
+            .collect();
+
+        if origins.is_empty() || origins == ["*"] {
+            return match app_env {
+                AppEnv::Development => Ok(CorsOrigins::Any),
+                AppEnv::Production => Err(anyhow::anyhow!(
+                    "CORS_ALLOWED_ORIGINS must list the allowed origins when APP_ENV=production"
+                )),
+            };
+        }
+
+        origins
+            .into_iter()
+            .map(|origin| {
+                if origin == "*" {
+                    anyhow::bail!("CORS_ALLOWED_ORIGINS cannot mix '*' with specific origins");
+                }
+                if !(origin.starts_with("http://") || origin.starts_with("https://")) {
+                    anyhow::bail!(
+                        "Invalid origin '{}' in CORS_ALLOWED_ORIGINS: expected http(s)://host[:port]",
+                        origin
+                    );
+                }
+                HeaderValue::from_str(origin).with_context(|| {
+                    format!("Invalid origin '{}' in CORS_ALLOWED_ORIGINS", origin)
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map(CorsOrigins::List)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_env_from_str() {
+        assert_eq!("production".parse::<AppEnv>(), Ok(AppEnv::Production));
+        assert_eq!(" Dev ".parse::<AppEnv>(), Ok(AppEnv::Development));
+        assert!("staging".parse::<AppEnv>().is_err());
+    }
+
+    #[test]
+    fn test_cors_origins_any_only_in_development() {
+        for raw in [None, Some(""), Some("*"), Some(" * ")] {
+            assert_eq!(
+                CorsOrigins::resolve(raw, AppEnv::Development).unwrap(),
+                CorsOrigins::Any
+            );
+        }
+    }
+
+    #[test]
+    fn test_cors_origins_refuses_to_start_in_production_without_origins() {
+        for raw in [None, Some(""), Some("*")] {
+            let err = CorsOrigins::resolve(raw, AppEnv::Production).unwrap_err();
+            assert!(err.to_string().contains("CORS_ALLOWED_ORIGINS"));
+        }
+    }
+
+    #[test]
+    fn test_cors_origins_parses_list() {
+        let origins = CorsOrigins::resolve(
+            Some("https://inventory.example.com/, http://localhost:5173,,"),
+            AppEnv::Production,
+        )
+        .unwrap();
+        assert_eq!(
+            origins,
+            CorsOrigins::List(vec![
+                HeaderValue::from_static("https://inventory.example.com"),
+                HeaderValue::from_static("http://localhost:5173"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cors_origins_rejects_invalid_entries() {
+        assert!(CorsOrigins::resolve(Some("inventory.example.com"), AppEnv::Production).is_err());
+        assert!(CorsOrigins::resolve(Some("*, https://a.example"), AppEnv::Development).is_err());
+    }
+}
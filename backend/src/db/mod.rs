@@ -1,24 +1,43 @@
-use aws_config::{BehaviorVersion, Region};
+use aws_config::{BehaviorVersion, Region, SdkConfig};
 use aws_sdk_dsql::auth_token::{AuthTokenGenerator, Config};
 use sqlx::{
+    migrate::{MigrateError, Migration, Migrator},
     postgres::{PgConnectOptions, PgPoolOptions, PgSslMode},
     ConnectOptions, PgPool,
 };
+use std::borrow::Cow;
+use std::str::FromStr;
 use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
 
-/// Initialize database connection pool
-/// Compatible with both PostgreSQL (local) and Aurora DSQL (Lambda with IAM auth)
-pub async fn init_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
-    // Debug: Log environment variable check
-    let lambda_env = std::env::var("AWS_LAMBDA_FUNCTION_NAME");
-    tracing::info!("AWS_LAMBDA_FUNCTION_NAME check: {:?}", lambda_env);
+mod transaction;
 
-    // Check if we're running in Lambda (AWS environment with IAM role)
-    if lambda_env.is_ok() {
-        tracing::info!("✓ Running in Lambda - using IAM authentication for DSQL");
+pub use transaction::with_transaction;
 
-        // Parse database URL to extract connection parameters
-        // Format: postgresql://admin@host:port/database?sslmode=require
+/// DSQL IAM auth tokens expire after 15 minutes; refresh with room to spare
+const TOKEN_REFRESH_INTERVAL: Duration = Duration::from_secs(12 * 60);
+/// DSQL closes connections after an hour, so recycle them before that
+const DSQL_MAX_CONNECTION_LIFETIME: Duration = Duration::from_secs(50 * 60);
+
+/// Keeps a DSQL pool's IAM auth token fresh.
+///
+/// The token is only checked when a connection is opened, so connections that are
+/// already open keep working after it expires. New connections need a current token:
+/// the refresher regenerates it every 12 minutes and swaps it into the pool's connect
+/// options. Idle connections are closed after the same interval and reopened on
+/// demand with the new token.
+pub struct DsqlTokenRefresher {
+    signer: AuthTokenGenerator,
+    sdk_config: SdkConfig,
+    /// Connection options without a password
+    connect_options: PgConnectOptions,
+}
+
+impl DsqlTokenRefresher {
+    /// Build a refresher from a DSQL URL like `postgresql://admin@host:port/database`,
+    /// using the ambient AWS credentials (the Lambda execution role)
+    pub async fn from_database_url(database_url: &str) -> Result<Self, sqlx::Error> {
         let url = url::Url::parse(database_url).map_err(|e| {
             sqlx::Error::Configuration(format!("Invalid DATABASE_URL: {}", e).into())
         })?;
@@ -31,8 +50,6 @@ pub async fn init_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
         let database = url.path().trim_start_matches('/');
         let region = std::env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string());
 
-        tracing::info!("Generating IAM auth token for DSQL cluster: {}", host);
-
         // Load AWS config from environment (uses Lambda execution role)
         let sdk_config = aws_config::load_defaults(BehaviorVersion::latest()).await;
 
@@ -41,67 +58,926 @@ pub async fn init_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
             .hostname(host)
             .region(Region::new(region))
             .build()
-            .unwrap();
+            .map_err(|e| {
+                sqlx::Error::Configuration(format!("Invalid DSQL auth config: {}", e).into())
+            })?;
 
-        let signer = AuthTokenGenerator::new(dsql_config);
+        let connect_options = PgConnectOptions::new()
+            .host(host)
+            .port(port)
+            .database(database)
+            .username(username)
+            .ssl_mode(PgSslMode::VerifyFull)
+            // Disable statement logging for cleaner Lambda logs
+            .disable_statement_logging();
+
+        Ok(Self {
+            signer: AuthTokenGenerator::new(dsql_config),
+            sdk_config,
+            connect_options,
+        })
+    }
 
-        // Generate authentication token
-        let auth_token = signer
-            .db_connect_admin_auth_token(&sdk_config)
+    /// Connection options carrying a newly generated auth token
+    pub async fn connect_options(&self) -> Result<PgConnectOptions, sqlx::Error> {
+        let auth_token = self
+            .signer
+            .db_connect_admin_auth_token(&self.sdk_config)
             .await
             .map_err(|e| {
                 sqlx::Error::Configuration(format!("Failed to generate auth token: {}", e).into())
             })?;
 
-        // Convert AuthToken to String
-        let password_token = auth_token.to_string();
+        Ok(self
+            .connect_options
+            .clone()
+            .password(auth_token.to_string().as_str()))
+    }
 
-        tracing::info!("IAM auth token generated successfully");
+    /// Generate a new token and use it for the pool's future connections
+    pub async fn refresh(&self, pool: &PgPool) -> Result<(), sqlx::Error> {
+        pool.set_connect_options(self.connect_options().await?);
+        Ok(())
+    }
 
-        // Create connection options with IAM token
-        let mut connection_options = PgConnectOptions::new()
-            .host(host)
-            .port(port)
-            .database(database)
-            .username(username)
-            .password(password_token.as_str())
-            .ssl_mode(PgSslMode::VerifyFull);
+    /// Refresh the token in the background until the pool is closed
+    pub fn spawn(self, pool: PgPool) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(TOKEN_REFRESH_INTERVAL);
+            // A frozen Lambda container misses ticks; refresh once on thaw, not repeatedly
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // The first tick completes immediately and the pool's token is brand new
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                if pool.is_closed() {
+                    break;
+                }
+                match self.refresh(&pool).await {
+                    Ok(()) => tracing::info!("Refreshed DSQL IAM auth token"),
+                    // Keep the old token; open connections still work and we retry next tick
+                    Err(e) => tracing::error!("Failed to refresh DSQL IAM auth token: {}", e),
+                }
+            }
+        })
+    }
+}
 
-        // Disable statement logging for cleaner Lambda logs
-        connection_options = connection_options.disable_statement_logging();
+/// Connection pool settings, from `DB_*` environment variables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// `DB_MAX_CONNECTIONS`: 10 locally, 2 in Lambda, where an instance serves one
+    /// request at a time and DSQL's connection limit is shared by every instance
+    pub max_connections: u32,
+    /// `DB_MIN_CONNECTIONS`, defaults to 0
+    pub min_connections: u32,
+    /// `DB_ACQUIRE_TIMEOUT_SECS`, defaults to 5
+    pub acquire_timeout: Duration,
+    /// `DB_IDLE_TIMEOUT_SECS`. Defaults to the token refresh interval in Lambda so
+    /// idle connections reopen with a fresh token, and to sqlx's default locally.
+    pub idle_timeout: Option<Duration>,
+    /// `DB_MAX_LIFETIME_SECS`. Defaults to 50 minutes in Lambda, since DSQL closes
+    /// connections after an hour, and to sqlx's default locally.
+    pub max_lifetime: Option<Duration>,
+}
+
+impl PoolConfig {
+    pub fn from_env(in_lambda: bool) -> Result<Self, String> {
+        Self::resolve(in_lambda, |name| std::env::var(name).ok())
+    }
+
+    /// Settings from the variables `var` looks up, with defaults for the ones unset
+    pub fn resolve(in_lambda: bool, var: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let number = |name: &str| -> Result<Option<u64>, String> {
+            var(name)
+                .map(|value| {
+                    value
+                        .trim()
+                        .parse::<u64>()
+                        .map_err(|_| format!("{} must be a non-negative integer", name))
+                })
+                .transpose()
+        };
+        let count = |name: &str| -> Result<Option<u32>, String> {
+            number(name)?
+                .map(|n| u32::try_from(n).map_err(|_| format!("{} is too large", name)))
+                .transpose()
+        };
+        let secs = |name: &str| Ok::<_, String>(number(name)?.map(Duration::from_secs));
+
+        let max_connections =
+            count("DB_MAX_CONNECTIONS")?.unwrap_or(if in_lambda { 2 } else { 10 });
+        let min_connections = count("DB_MIN_CONNECTIONS")?.unwrap_or(0);
+        if max_connections == 0 {
+            return Err("DB_MAX_CONNECTIONS must be at least 1".to_string());
+        }
+        if min_connections > max_connections {
+            return Err(format!(
+                "DB_MIN_CONNECTIONS ({}) is more than DB_MAX_CONNECTIONS ({})",
+                min_connections, max_connections
+            ));
+        }
+
+        let (default_idle_timeout, default_max_lifetime) = if in_lambda {
+            (
+                Some(TOKEN_REFRESH_INTERVAL),
+                Some(DSQL_MAX_CONNECTION_LIFETIME),
+            )
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
+            max_connections,
+            min_connections,
+            acquire_timeout: secs("DB_ACQUIRE_TIMEOUT_SECS")?.unwrap_or(Duration::from_secs(5)),
+            idle_timeout: secs("DB_IDLE_TIMEOUT_SECS")?.or(default_idle_timeout),
+            max_lifetime: secs("DB_MAX_LIFETIME_SECS")?.or(default_max_lifetime),
+        })
+    }
+
+    /// Pool options with these settings. Unset timeouts keep sqlx's defaults.
+    pub fn pool_options(&self) -> PgPoolOptions {
+        let mut options = PgPoolOptions::new()
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .acquire_timeout(self.acquire_timeout);
+        if let Some(idle_timeout) = self.idle_timeout {
+            options = options.idle_timeout(idle_timeout);
+        }
+        if let Some(max_lifetime) = self.max_lifetime {
+            options = options.max_lifetime(max_lifetime);
+        }
+        options
+    }
+}
+
+/// Initialize database connection pool
+/// Compatible with both PostgreSQL (local) and Aurora DSQL (Lambda with IAM auth)
+pub async fn init_pool(database_url: &str) -> Result<PgPool, sqlx::Error> {
+    // Debug: Log environment variable check
+    let lambda_env = std::env::var("AWS_LAMBDA_FUNCTION_NAME");
+    tracing::info!("AWS_LAMBDA_FUNCTION_NAME check: {:?}", lambda_env);
+
+    let pool_config = PoolConfig::from_env(lambda_env.is_ok())
+        .map_err(|e| sqlx::Error::Configuration(e.into()))?;
+    tracing::info!(
+        max_connections = pool_config.max_connections,
+        min_connections = pool_config.min_connections,
+        acquire_timeout_secs = pool_config.acquire_timeout.as_secs(),
+        idle_timeout_secs = ?pool_config.idle_timeout.map(|d| d.as_secs()),
+        max_lifetime_secs = ?pool_config.max_lifetime.map(|d| d.as_secs()),
+        "Database pool configuration"
+    );
+
+    // Check if we're running in Lambda (AWS environment with IAM role)
+    if lambda_env.is_ok() {
+        tracing::info!("✓ Running in Lambda - using IAM authentication for DSQL");
+
+        // Format: postgresql://admin@host:port/database?sslmode=require
+        let refresher = DsqlTokenRefresher::from_database_url(database_url).await?;
+
+        tracing::info!("Generating IAM auth token for DSQL cluster");
+        let connection_options = refresher.connect_options().await?;
+        tracing::info!("IAM auth token generated successfully");
 
         // Create connection pool
-        PgPoolOptions::new()
-            .max_connections(10)
-            .acquire_timeout(Duration::from_secs(5))
+        let pool = pool_config
+            .pool_options()
             .connect_with(connection_options)
-            .await
+            .await?;
+
+        refresher.spawn(pool.clone());
+        Ok(pool)
     } else {
         tracing::info!("Running locally - using password-based authentication");
 
         // Local development - use standard connection string
-        PgPoolOptions::new()
-            .max_connections(10)
-            .acquire_timeout(Duration::from_secs(5))
-            .connect(database_url)
-            .await
+        pool_config.pool_options().connect(database_url).await
+    }
+}
+
+/// Database the migrations are applied to, from `DB_DIALECT` (`postgres` or `dsql`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbDialect {
+    Postgres,
+    Dsql,
+}
+
+impl DbDialect {
+    /// `DB_DIALECT` when set, otherwise DSQL in Lambda (where [`init_pool`] uses IAM
+    /// auth) and PostgreSQL everywhere else
+    pub fn from_env() -> Result<Self, String> {
+        match std::env::var("DB_DIALECT") {
+            Ok(dialect) => dialect.parse(),
+            Err(_) if std::env::var("AWS_LAMBDA_FUNCTION_NAME").is_ok() => Ok(DbDialect::Dsql),
+            Err(_) => Ok(DbDialect::Postgres),
+        }
+    }
+}
+
+impl FromStr for DbDialect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "postgres" | "postgresql" => Ok(DbDialect::Postgres),
+            "dsql" => Ok(DbDialect::Dsql),
+            other => Err(format!(
+                "Unknown DB_DIALECT '{}': expected postgres or dsql",
+                other
+            )),
+        }
+    }
+}
+
+/// What [`MigrationFilter`] does with one migration statement
+#[derive(Debug, PartialEq, Eq)]
+pub enum StatementAction {
+    Keep,
+    Rewrite(String),
+    /// Dropped, with the reason logged
+    Skip(&'static str),
+}
+
+/// Adapts migration SQL to the dialect it runs on.
+///
+/// The migrations are written for DSQL, whose `CREATE INDEX ASYNC` PostgreSQL
+/// doesn't accept, so on PostgreSQL `ASYNC` is removed. On DSQL, statements it
+/// doesn't support (sequences, extensions, `ALTER TABLE ... ADD CONSTRAINT`,
+/// advisory locks, and GIN, expression or partial indexes) are skipped with a
+/// warning, `CREATE INDEX [CONCURRENTLY]` becomes `CREATE INDEX ASYNC`, and
+/// `ALTER TABLE ... ADD COLUMN ... DEFAULT` is split into a nullable `ADD COLUMN`
+/// and an `UPDATE` backfilling the default (see [`split_column_default`]).
+#[derive(Debug, Clone, Copy)]
+pub struct MigrationFilter {
+    dialect: DbDialect,
+}
+
+impl MigrationFilter {
+    pub fn new(dialect: DbDialect) -> Self {
+        Self { dialect }
+    }
+
+    pub fn filter_statement(&self, statement: &str) -> StatementAction {
+        let code = strip_comments(statement);
+        let code = code.trim();
+        let words: Vec<(usize, String)> = leading_words(code, 4);
+        let word = |i: usize| words.get(i).map(|(_, w)| w.as_str()).unwrap_or("");
+        let upper = code
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_ascii_uppercase();
+
+        // CREATE [UNIQUE] INDEX [CONCURRENTLY | ASYNC] ...
+        let index_at = match (word(0), word(1), word(2)) {
+            ("CREATE", "INDEX", _) => Some(1),
+            ("CREATE", "UNIQUE", "INDEX") => Some(2),
+            _ => None,
+        };
+        if let Some(index_at) = index_at {
+            let (modifier_at, modifier) = match words.get(index_at + 1) {
+                Some((at, w)) if w == "CONCURRENTLY" || w == "ASYNC" => (Some(*at), w.as_str()),
+                _ => (None, ""),
+            };
+            let head_end = words[index_at].0 + "INDEX".len();
+            let head = &code[..head_end];
+            let rest = match modifier_at {
+                Some(at) => code[at + modifier.len()..].trim_start(),
+                None => code[head_end..].trim_start(),
+            };
+            if self.dialect == DbDialect::Dsql {
+                if let Some(reason) = unsupported_dsql_index(rest) {
+                    return StatementAction::Skip(reason);
+                }
+            }
+            return match (self.dialect, modifier) {
+                (DbDialect::Dsql, "ASYNC") | (DbDialect::Postgres, "" | "CONCURRENTLY") => {
+                    StatementAction::Keep
+                }
+                (DbDialect::Dsql, _) => {
+                    StatementAction::Rewrite(format!("{} ASYNC {}", head, rest))
+                }
+                (DbDialect::Postgres, _) => StatementAction::Rewrite(format!("{} {}", head, rest)),
+            };
+        }
+
+        if self.dialect == DbDialect::Postgres {
+            return StatementAction::Keep;
+        }
+        match (word(0), word(1)) {
+            ("CREATE", "SEQUENCE") => StatementAction::Skip("DSQL doesn't support sequences"),
+            ("CREATE", "EXTENSION") => StatementAction::Skip("DSQL doesn't support extensions"),
+            ("ALTER", "TABLE")
+                if upper.contains(" ADD CONSTRAINT ") || upper.contains(" ADD FOREIGN KEY") =>
+            {
+                StatementAction::Skip("DSQL doesn't support ALTER TABLE ... ADD CONSTRAINT")
+            }
+            ("ALTER", "TABLE") if upper.contains(" DEFAULT ") => match split_column_default(code) {
+                Some(rewritten) => StatementAction::Rewrite(rewritten),
+                // Left for DSQL to reject: dropping it would leave the column missing
+                None => StatementAction::Keep,
+            },
+            _ if upper.contains("PG_ADVISORY_") => {
+                StatementAction::Skip("DSQL doesn't support advisory locks")
+            }
+            _ => StatementAction::Keep,
+        }
+    }
+
+    /// The migration's SQL with each statement kept, rewritten or skipped
+    pub fn filter_sql(&self, version: i64, sql: &str) -> String {
+        let mut statements = Vec::new();
+        for statement in split_statements(sql) {
+            match self.filter_statement(statement) {
+                // Trailing whitespace stays: a final `--` comment must end before the `;`
+                StatementAction::Keep => statements.push(statement.trim_start().to_string()),
+                StatementAction::Rewrite(rewritten) => {
+                    tracing::debug!("Migration {}: rewrote {:?}", version, rewritten);
+                    statements.push(rewritten);
+                }
+                StatementAction::Skip(reason) => {
+                    tracing::warn!(
+                        "Migration {}: skipped statement ({}): {}",
+                        version,
+                        reason,
+                        strip_comments(statement).trim()
+                    );
+                }
+            }
+        }
+        if statements.is_empty() {
+            // sqlx still records the migration; give it something harmless to run
+            return "SELECT 1;".to_string();
+        }
+        statements.join(";\n\n") + ";"
+    }
+}
+
+/// Wraps a [`Migrator`] so its migrations are passed through a [`MigrationFilter`]
+/// before they run. Checksums are those of the files on disk, so rewriting doesn't
+/// invalidate migrations that are already applied.
+pub struct DialectMigrator {
+    migrator: Migrator,
+}
+
+impl DialectMigrator {
+    pub fn new(mut migrator: Migrator, dialect: DbDialect) -> Self {
+        let filter = MigrationFilter::new(dialect);
+        let migrations: Vec<Migration> = migrator
+            .migrations
+            .iter()
+            .map(|migration| Migration {
+                sql: Cow::Owned(filter.filter_sql(migration.version, &migration.sql)),
+                ..migration.clone()
+            })
+            .collect();
+        migrator.migrations = Cow::Owned(migrations);
+        // DSQL doesn't support pg_advisory_lock
+        migrator.set_locking(false);
+        Self { migrator }
+    }
+
+    pub async fn run(&self, pool: &PgPool) -> Result<(), MigrateError> {
+        self.migrator.run(pool).await
     }
 }
 
-/// Run database migrations
-/// All migrations are DSQL-compatible (Aurora DSQL is the primary deployment target)
-/// Note: Locking is disabled because DSQL doesn't support pg_advisory_lock
-pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
-    sqlx::migrate!("./migrations")
-        .set_locking(false)
+/// Run database migrations, adapted to `dialect`
+pub async fn run_migrations(pool: &PgPool, dialect: DbDialect) -> Result<(), MigrateError> {
+    DialectMigrator::new(sqlx::migrate!("./migrations"), dialect)
         .run(pool)
         .await
 }
 
+/// `ALTER TABLE t ADD [COLUMN] c type [NOT NULL] DEFAULT expr [NOT NULL]` as the
+/// statements DSQL accepts: `ALTER TABLE t ADD COLUMN c type` followed by
+/// `UPDATE t SET c = expr WHERE c IS NULL`. The column loses its `NOT NULL` and
+/// default, so rows inserted later without a value hold NULL.
+///
+/// `None` for anything else, e.g. several actions in one statement or a column with
+/// other constraints.
+fn split_column_default(code: &str) -> Option<String> {
+    let words = top_level_words(code)?;
+    let upper: Vec<String> = words.iter().map(|w| w.to_ascii_uppercase()).collect();
+    let is = |i: usize, word: &str| upper.get(i).is_some_and(|w| w == word);
+
+    let mut i = 2;
+    if is(i, "IF") && is(i + 1, "EXISTS") {
+        i += 2;
+    }
+    if is(i, "ONLY") {
+        i += 1;
+    }
+    let table = words.get(i)?;
+    i += 1;
+    if !is(i, "ADD") {
+        return None;
+    }
+    i += 1;
+    if is(i, "COLUMN") {
+        i += 1;
+    }
+    let if_not_exists = is(i, "IF") && is(i + 1, "NOT") && is(i + 2, "EXISTS");
+    if if_not_exists {
+        i += 3;
+    }
+    let column = words.get(i)?;
+    i += 1;
+
+    let default_at = (i..upper.len()).find(|&at| upper[at] == "DEFAULT")?;
+    // The type, then the default expression, each without NOT NULL / NULL
+    let mut column_type = Vec::new();
+    let mut default = Vec::new();
+    let mut at = i;
+    while at < upper.len() {
+        match upper[at].as_str() {
+            "NOT" if is(at + 1, "NULL") => at += 2,
+            "NULL" if at != default_at + 1 => at += 1,
+            "DEFAULT" => at += 1,
+            "CONSTRAINT" | "CHECK" | "REFERENCES" | "UNIQUE" | "PRIMARY" | "GENERATED"
+            | "COLLATE" => return None,
+            _ => {
+                if at < default_at {
+                    column_type.push(words[at]);
+                } else {
+                    default.push(words[at]);
+                }
+                at += 1;
+            }
+        }
+    }
+    if column_type.is_empty() || default.is_empty() {
+        return None;
+    }
+
+    Some(format!(
+        "ALTER TABLE {table} ADD COLUMN {if_not_exists}{column} {column_type};\n\n\
+         UPDATE {table} SET {column} = {default} WHERE {column} IS NULL",
+        if_not_exists = if if_not_exists { "IF NOT EXISTS " } else { "" },
+        column_type = column_type.join(" "),
+        default = default.join(" "),
+    ))
+}
+
+/// `code` split on whitespace outside parentheses and quotes, so `NUMERIC(10, 2)` and
+/// `'a b'` are one word each. `None` if it has a comma outside them, i.e. more than
+/// one action.
+fn top_level_words(code: &str) -> Option<Vec<&str>> {
+    let bytes = code.as_bytes();
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if depth == 0 && byte.is_ascii_whitespace() {
+            if let Some(word_start) = start.take() {
+                words.push(&code[word_start..i]);
+            }
+            i += 1;
+            continue;
+        }
+        start.get_or_insert(i);
+        match byte {
+            b'\'' | b'"' => {
+                i = skip_quoted(bytes, i);
+                continue;
+            }
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            b',' if depth == 0 => return None,
+            _ => {}
+        }
+        i += 1;
+    }
+    if let Some(word_start) = start {
+        words.push(&code[word_start..]);
+    }
+    Some(words)
+}
+
+/// Split SQL into statements on `;`, ignoring semicolons in string literals, quoted
+/// identifiers, comments and dollar-quoted bodies. Each statement keeps its leading
+/// comments; chunks with no code (e.g. trailing comments) are dropped.
+pub fn split_statements(sql: &str) -> Vec<&str> {
+    let bytes = sql.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' => i = skip_quoted(bytes, i),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = sql[i..].find('\n').map_or(bytes.len(), |end| i + end + 1);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = sql[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| i + 2 + end + 2);
+            }
+            b'$' => match dollar_tag(&sql[i..]) {
+                Some(tag) => {
+                    let body = i + tag.len();
+                    i = sql[body..]
+                        .find(tag)
+                        .map_or(bytes.len(), |end| body + end + tag.len());
+                }
+                None => i += 1,
+            },
+            b';' => {
+                statements.push(&sql[start..i]);
+                i += 1;
+                start = i;
+            }
+            _ => i += 1,
+        }
+    }
+    statements.push(&sql[start..]);
+
+    statements
+        .into_iter()
+        .filter(|statement| !strip_comments(statement).trim().is_empty())
+        .collect()
+}
+
+/// Index just past the quoted string or identifier starting at `start`. A doubled
+/// quote is an escaped quote.
+fn skip_quoted(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() {
+        if bytes[i] == quote {
+            if bytes.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// The `$tag$` opening a dollar-quoted string at the start of `sql`, if any
+fn dollar_tag(sql: &str) -> Option<&str> {
+    let end = sql[1..].find('$')? + 1;
+    let tag = &sql[1..end];
+    let valid = !tag.starts_with(|c: char| c.is_ascii_digit())
+        && tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then(|| &sql[..=end])
+}
+
+/// `statement` with `--` and `/* */` comments removed, leaving literals intact
+fn strip_comments(statement: &str) -> String {
+    let bytes = statement.as_bytes();
+    let mut out = String::with_capacity(statement.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' => {
+                let end = skip_quoted(bytes, i);
+                out.push_str(&statement[i..end]);
+                i = end;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = statement[i..].find('\n').map_or(bytes.len(), |end| i + end);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = statement[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| i + 2 + end + 2);
+                out.push(' ');
+            }
+            _ => {
+                let next = statement[i..]
+                    .find(['\'', '"', '-', '/'])
+                    .map_or(bytes.len(), |offset| (i + offset).max(i + 1));
+                out.push_str(&statement[i..next]);
+                i = next;
+            }
+        }
+    }
+    out
+}
+
+/// Why DSQL can't build an index, given the statement after `CREATE [UNIQUE] INDEX
+/// [CONCURRENTLY | ASYNC]`. DSQL only has B-tree indexes on plain columns.
+fn unsupported_dsql_index(rest: &str) -> Option<&'static str> {
+    let upper = rest.to_ascii_uppercase();
+    let open = upper.find('(')?;
+    let head: Vec<&str> = upper[..open].split_whitespace().collect();
+    let method = head
+        .iter()
+        .position(|w| *w == "USING")
+        .and_then(|at| head.get(at + 1));
+    if method.is_some_and(|method| *method != "BTREE") {
+        return Some("DSQL only supports B-tree indexes");
+    }
+
+    let mut depth = 0;
+    let close = upper[open..].find(|c| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        depth == 0
+    })? + open;
+    if upper[open + 1..close].contains('(') {
+        return Some("DSQL doesn't support expression indexes");
+    }
+    if upper[close..].split_whitespace().any(|w| w == "WHERE") {
+        return Some("DSQL doesn't support partial indexes");
+    }
+    None
+}
+
+/// The first `n` whitespace-separated words of `code`, uppercased, with their offsets
+fn leading_words(code: &str, n: usize) -> Vec<(usize, String)> {
+    let mut words = Vec::new();
+    let mut offset = 0;
+    for word in code.split_whitespace().take(n) {
+        let at = offset + code[offset..].find(word).unwrap_or(0);
+        words.push((at, word.to_ascii_uppercase()));
+        offset = at + word.len();
+    }
+    words
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn filter(dialect: DbDialect, statement: &str) -> StatementAction {
+        MigrationFilter::new(dialect).filter_statement(statement)
+    }
+
+    #[test]
+    fn test_pool_config_defaults() {
+        let local = PoolConfig::resolve(false, |_| None).unwrap();
+        assert_eq!(local.max_connections, 10);
+        assert_eq!(local.min_connections, 0);
+        assert_eq!(local.acquire_timeout, Duration::from_secs(5));
+        assert_eq!(local.idle_timeout, None);
+        assert_eq!(local.max_lifetime, None);
+
+        let lambda = PoolConfig::resolve(true, |_| None).unwrap();
+        assert_eq!(lambda.max_connections, 2);
+        assert_eq!(lambda.idle_timeout, Some(TOKEN_REFRESH_INTERVAL));
+        assert_eq!(lambda.max_lifetime, Some(DSQL_MAX_CONNECTION_LIFETIME));
+    }
+
+    #[test]
+    fn test_pool_config_from_vars() {
+        let vars = |name: &str| {
+            match name {
+                "DB_MAX_CONNECTIONS" => Some("4"),
+                "DB_MIN_CONNECTIONS" => Some(" 1 "),
+                "DB_ACQUIRE_TIMEOUT_SECS" => Some("3"),
+                "DB_IDLE_TIMEOUT_SECS" => Some("60"),
+                "DB_MAX_LIFETIME_SECS" => Some("600"),
+                _ => None,
+            }
+            .map(String::from)
+        };
+
+        assert_eq!(
+            PoolConfig::resolve(true, vars),
+            Ok(PoolConfig {
+                max_connections: 4,
+                min_connections: 1,
+                acquire_timeout: Duration::from_secs(3),
+                idle_timeout: Some(Duration::from_secs(60)),
+                max_lifetime: Some(Duration::from_secs(600)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_pool_config_rejects_invalid_values() {
+        let only = |name: &'static str, value: &'static str| {
+            move |var: &str| (var == name).then(|| value.to_string())
+        };
+
+        assert!(PoolConfig::resolve(false, only("DB_MAX_CONNECTIONS", "0")).is_err());
+        assert!(PoolConfig::resolve(false, only("DB_MAX_CONNECTIONS", "ten")).is_err());
+        assert!(PoolConfig::resolve(false, only("DB_MAX_CONNECTIONS", "-1")).is_err());
+        assert!(PoolConfig::resolve(false, only("DB_MIN_CONNECTIONS", "11")).is_err());
+        assert!(PoolConfig::resolve(false, only("DB_ACQUIRE_TIMEOUT_SECS", "1.5")).is_err());
+        assert!(PoolConfig::resolve(false, only("DB_MAX_CONNECTIONS", "99999999999")).is_err());
+    }
+
+    #[test]
+    fn test_db_dialect_from_str() {
+        assert_eq!("postgres".parse::<DbDialect>(), Ok(DbDialect::Postgres));
+        assert_eq!(" DSQL ".parse::<DbDialect>(), Ok(DbDialect::Dsql));
+        assert!("mysql".parse::<DbDialect>().is_err());
+    }
+
+    #[test]
+    fn test_split_statements() {
+        let sql = "-- header\nCREATE TABLE a (x TEXT DEFAULT ';');\n\
+                   /* ; */ INSERT INTO a VALUES ('it''s; fine');\n\
+                   CREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql;\n\
+                   -- trailing comment; not a statement\n";
+        let statements = split_statements(sql);
+
+        assert_eq!(statements.len(), 3);
+        assert!(statements[0].contains("DEFAULT ';'"));
+        assert!(statements[1].contains("'it''s; fine'"));
+        assert!(statements[2].ends_with("$body$ LANGUAGE sql"));
+    }
+
+    #[test]
+    fn test_strip_comments_keeps_literals() {
+        assert_eq!(
+            strip_comments("SELECT '--not a comment' -- a comment\n, 1").trim(),
+            "SELECT '--not a comment' \n, 1"
+        );
+        assert_eq!(strip_comments("/* x */SELECT 1").trim(), "SELECT 1");
+    }
+
+    #[test]
+    fn test_index_rewrites() {
+        let async_index = "CREATE INDEX ASYNC idx_a ON a(x)";
+        assert_eq!(
+            filter(DbDialect::Postgres, async_index),
+            StatementAction::Rewrite("CREATE INDEX idx_a ON a(x)".to_string())
+        );
+        assert_eq!(filter(DbDialect::Dsql, async_index), StatementAction::Keep);
+
+        assert_eq!(
+            filter(
+                DbDialect::Dsql,
+                "-- speeds up lookups\ncreate unique index concurrently idx_b ON b(y)"
+            ),
+            StatementAction::Rewrite("create unique index ASYNC idx_b ON b(y)".to_string())
+        );
+        assert_eq!(
+            filter(DbDialect::Dsql, "CREATE INDEX idx_c ON c(z)"),
+            StatementAction::Rewrite("CREATE INDEX ASYNC idx_c ON c(z)".to_string())
+        );
+        assert_eq!(
+            filter(DbDialect::Postgres, "CREATE INDEX idx_c ON c(z)"),
+            StatementAction::Keep
+        );
+    }
+
+    #[test]
+    fn test_dsql_skips_unsupported_indexes() {
+        for statement in [
+            "CREATE INDEX items_name_trgm_idx ON items USING gin(name gin_trgm_ops)",
+            "CREATE UNIQUE INDEX items_barcode_lower_idx ON items (LOWER(barcode))",
+            "CREATE UNIQUE INDEX idx_a ON a(x)\n    WHERE x IS NOT NULL",
+        ] {
+            assert!(
+                matches!(filter(DbDialect::Dsql, statement), StatementAction::Skip(_)),
+                "{}",
+                statement
+            );
+            assert_eq!(
+                filter(DbDialect::Postgres, statement),
+                StatementAction::Keep
+            );
+        }
+
+        assert_eq!(
+            filter(
+                DbDialect::Dsql,
+                "CREATE INDEX idx_a ON a USING btree (x) INCLUDE (y)"
+            ),
+            StatementAction::Rewrite(
+                "CREATE INDEX ASYNC idx_a ON a USING btree (x) INCLUDE (y)".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_dsql_skips_unsupported_statements() {
+        for statement in [
+            "CREATE SEQUENCE label_numbers",
+            "CREATE EXTENSION IF NOT EXISTS pg_trgm",
+            "ALTER TABLE items ADD CONSTRAINT fk_items_user FOREIGN KEY (user_id) REFERENCES users(id)",
+            "ALTER TABLE items\n  ADD CONSTRAINT items_quantity_non_negative CHECK (quantity >= 0)",
+            "SELECT pg_advisory_lock(1)",
+        ] {
+            assert!(
+                matches!(filter(DbDialect::Dsql, statement), StatementAction::Skip(_)),
+                "{}",
+                statement
+            );
+            assert_eq!(filter(DbDialect::Postgres, statement), StatementAction::Keep);
+        }
+
+        assert_eq!(
+            filter(
+                DbDialect::Dsql,
+                "ALTER TABLE items ADD COLUMN quantity INTEGER"
+            ),
+            StatementAction::Keep
+        );
+    }
+
+    #[test]
+    fn test_dsql_splits_add_column_default() {
+        assert_eq!(
+            filter(
+                DbDialect::Dsql,
+                "ALTER TABLE items ADD COLUMN quantity INTEGER NOT NULL DEFAULT 1"
+            ),
+            StatementAction::Rewrite(
+                "ALTER TABLE items ADD COLUMN quantity INTEGER;\n\n\
+                 UPDATE items SET quantity = 1 WHERE quantity IS NULL"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            filter(
+                DbDialect::Dsql,
+                "ALTER TABLE labels ADD updated_at TIMESTAMPTZ DEFAULT NOW() NOT NULL"
+            ),
+            StatementAction::Rewrite(
+                "ALTER TABLE labels ADD COLUMN updated_at TIMESTAMPTZ;\n\n\
+                 UPDATE labels SET updated_at = NOW() WHERE updated_at IS NULL"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            filter(
+                DbDialect::Dsql,
+                "ALTER TABLE items ADD COLUMN IF NOT EXISTS price NUMERIC(10, 2) DEFAULT 0.00"
+            ),
+            StatementAction::Rewrite(
+                "ALTER TABLE items ADD COLUMN IF NOT EXISTS price NUMERIC(10, 2);\n\n\
+                 UPDATE items SET price = 0.00 WHERE price IS NULL"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            filter(
+                DbDialect::Postgres,
+                "ALTER TABLE users ADD COLUMN is_public BOOLEAN NOT NULL DEFAULT false"
+            ),
+            StatementAction::Keep
+        );
+
+        // Forms it can't split are left for DSQL to reject
+        for statement in [
+            "ALTER TABLE a ADD COLUMN x INTEGER DEFAULT 1, ADD COLUMN y INTEGER DEFAULT 2",
+            "ALTER TABLE a ADD COLUMN x INTEGER DEFAULT 1 CHECK (x > 0)",
+            "ALTER TABLE a ALTER COLUMN x SET DEFAULT 1",
+        ] {
+            assert_eq!(
+                filter(DbDialect::Dsql, statement),
+                StatementAction::Keep,
+                "{}",
+                statement
+            );
+        }
+    }
+
+    #[test]
+    fn test_dsql_migrations_add_no_column_defaults() {
+        let migrator = DialectMigrator::new(sqlx::migrate!("./migrations"), DbDialect::Dsql);
+        for migration in migrator.migrator.iter() {
+            for statement in split_statements(&migration.sql) {
+                let code = strip_comments(statement).to_ascii_uppercase();
+                assert!(
+                    !(code.contains(" ADD ") && code.contains(" DEFAULT ")),
+                    "Migration {} adds a column with a default: {}",
+                    migration.version,
+                    statement.trim()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_filter_sql() {
+        let sql = "-- sqlx:no-transaction\n\
+                   CREATE TABLE a (id UUID PRIMARY KEY);\n\
+                   ALTER TABLE a ADD CONSTRAINT a_check CHECK (id IS NOT NULL);\n\
+                   CREATE INDEX ASYNC idx_a ON a(id);\n";
+
+        let postgres = MigrationFilter::new(DbDialect::Postgres).filter_sql(1, sql);
+        assert!(postgres.contains("CREATE INDEX idx_a ON a(id);"));
+        assert!(postgres.contains("ADD CONSTRAINT"));
+
+        let dsql = MigrationFilter::new(DbDialect::Dsql).filter_sql(1, sql);
+        assert!(dsql.contains("CREATE INDEX ASYNC idx_a"));
+        assert!(!dsql.contains("ADD CONSTRAINT"));
+
+        assert_eq!(
+            MigrationFilter::new(DbDialect::Dsql)
+                .filter_sql(1, "CREATE EXTENSION IF NOT EXISTS pg_trgm;"),
+            "SELECT 1;"
+        );
+    }
+
     #[tokio::test]
     #[ignore] // Only run when DATABASE_URL is set
     async fn test_database_connection() {
@@ -119,4 +995,34 @@ mod tests {
         assert!(result.is_ok(), "Failed to execute test query");
         assert_eq!(result.unwrap().0, 1);
     }
+
+    /// Needs a DSQL cluster in DATABASE_URL and AWS credentials allowed to connect as admin
+    #[cfg(feature = "dsql-integration")]
+    #[tokio::test]
+    async fn test_dsql_token_refresh_keeps_pool_usable() {
+        let database_url =
+            std::env::var("DATABASE_URL").expect("DATABASE_URL must be set for integration tests");
+        let refresher = DsqlTokenRefresher::from_database_url(&database_url)
+            .await
+            .unwrap();
+
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .connect_with(refresher.connect_options().await.unwrap())
+            .await
+            .unwrap();
+
+        refresher.refresh(&pool).await.unwrap();
+
+        // Force a new connection so it authenticates with the refreshed token
+        pool.close().await;
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .connect_with(pool.connect_options().as_ref().clone())
+            .await
+            .unwrap();
+
+        let result: (i32,) = sqlx::query_as("SELECT 1").fetch_one(&pool).await.unwrap();
+        assert_eq!(result.0, 1);
+    }
 }
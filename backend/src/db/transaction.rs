@@ -0,0 +1,136 @@
+use futures::future::{BoxFuture, FutureExt};
+use sqlx::{PgConnection, PgPool, Postgres, Transaction};
+use std::panic::AssertUnwindSafe;
+
+use crate::error::ApiError;
+
+/// Run `f` inside a transaction: commit when it returns `Ok`, roll back when it
+/// returns `Err` or panics.
+///
+/// A panic is reported as a 500 after the rollback, so the connection goes back to
+/// the pool clean instead of mid-transaction. The future may only borrow the
+/// connection, so `f` should `move` in what it needs (ids, payloads, `Arc`s).
+pub async fn with_transaction<F, T>(pool: &PgPool, f: F) -> Result<T, ApiError>
+where
+    F: for<'c> FnOnce(&'c mut PgConnection) -> BoxFuture<'c, Result<T, ApiError>>,
+{
+    let mut tx = pool.begin().await?;
+
+    let outcome = AssertUnwindSafe(f(&mut tx)).catch_unwind().await;
+
+    match outcome {
+        Ok(Ok(value)) => {
+            tx.commit().await?;
+            Ok(value)
+        }
+        Ok(Err(err)) => {
+            rollback(tx).await;
+            Err(err)
+        }
+        Err(panic) => {
+            rollback(tx).await;
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            tracing::error!("Transaction rolled back after a panic: {}", message);
+            Err(ApiError::InternalServer(
+                "Internal server error".to_string(),
+            ))
+        }
+    }
+}
+
+/// The original error is what the caller needs, so a failed rollback is only logged
+async fn rollback(tx: Transaction<'static, Postgres>) {
+    if let Err(e) = tx.rollback().await {
+        tracing::error!("Failed to roll back transaction: {e:?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_pool;
+    use uuid::Uuid;
+
+    async fn insert_room(conn: &mut PgConnection, id: Uuid) -> Result<(), ApiError> {
+        sqlx::query("INSERT INTO rooms (id, name, created_by) VALUES ($1, 'Transaction test', $2)")
+            .bind(id)
+            .bind(Uuid::new_v4())
+            .execute(&mut *conn)
+            .await?;
+        Ok(())
+    }
+
+    async fn room_exists(pool: &PgPool, id: Uuid) -> bool {
+        sqlx::query("SELECT id FROM rooms WHERE id = $1")
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_with_transaction_commits_on_ok() {
+        let pool = create_test_pool().await;
+        let id = Uuid::new_v4();
+
+        let result = with_transaction(&pool, move |conn| {
+            Box::pin(async move {
+                insert_room(conn, id).await?;
+                Ok(id)
+            })
+        })
+        .await;
+        let exists = room_exists(&pool, id).await;
+
+        sqlx::query("DELETE FROM rooms WHERE id = $1")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(result, Ok(id));
+        assert!(exists);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_with_transaction_rolls_back_on_error() {
+        let pool = create_test_pool().await;
+        let id = Uuid::new_v4();
+
+        let result: Result<(), ApiError> = with_transaction(&pool, move |conn| {
+            Box::pin(async move {
+                insert_room(conn, id).await?;
+                Err(ApiError::bad_request("Changed my mind"))
+            })
+        })
+        .await;
+
+        assert_eq!(result, Err(ApiError::bad_request("Changed my mind")));
+        assert!(!room_exists(&pool, id).await);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_with_transaction_rolls_back_on_panic() {
+        let pool = create_test_pool().await;
+        let id = Uuid::new_v4();
+
+        let result: Result<(), ApiError> = with_transaction(&pool, move |conn| {
+            Box::pin(async move {
+                insert_room(conn, id).await?;
+                panic!("Handler bug");
+            })
+        })
+        .await;
+
+        assert!(matches!(result, Err(ApiError::InternalServer(_))));
+        assert!(!room_exists(&pool, id).await);
+    }
+}
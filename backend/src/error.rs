@@ -3,160 +3,376 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Serialize;
 use serde_json::json;
+use std::fmt::Display;
 use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{LocationCounts, SimilarItem};
+
+/// Error returned by API handlers. Serializes as
+/// `{"code": "NOT_FOUND", "message": "Item with id ... not found", "details": null}`.
+///
+/// Helpers that still fail with a bare `StatusCode` convert with `?`, getting the
+/// status's reason phrase as the message.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ApiError {
+    #[error("{0}")]
+    NotFound(String),
 
-#[derive(Error, Debug)]
-#[allow(dead_code)] // Some variants will be used as we build out the API
-pub enum AppError {
-    #[error("Database error: {0}")]
-    Database(#[from] sqlx::Error),
+    #[error("{0}")]
+    BadRequest(String),
 
-    #[error("Internal server error: {0}")]
-    Internal(String),
+    #[error("{0}")]
+    Conflict(String),
 
-    #[error("Not found: {0}")]
-    NotFound(String),
+    /// 409 for a barcode already assigned to another item. Its id is returned in
+    /// `details.existing_item_id` when the item is in the caller's household.
+    #[error("Barcode is already assigned to another item")]
+    BarcodeConflict { existing_item_id: Option<Uuid> },
 
-    #[error("Validation error: {0}")]
-    Validation(String),
+    /// 409 for a shelving unit that already holds `max_shelves` shelves. Both counts
+    /// are returned in `details`.
+    #[error("Shelving unit is full ({current} of {max} shelves)")]
+    CapacityExceeded { current: i32, max: i32 },
 
-    #[error("Unauthorized")]
-    Unauthorized,
-}
+    /// 409 for an item named much like others in the same location, when the
+    /// create asked for a duplicate check. They're returned in `details.similar_items`.
+    #[error("Similarly named items already exist here")]
+    PossibleDuplicate { similar_items: Vec<SimilarItem> },
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            AppError::Database(ref e) => {
-                tracing::error!("Database error: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Database error occurred")
-            }
-            AppError::Internal(ref msg) => {
-                tracing::error!("Internal error: {}", msg);
-                (StatusCode::INTERNAL_SERVER_ERROR, msg.as_str())
-            }
-            AppError::NotFound(ref msg) => (StatusCode::NOT_FOUND, msg.as_str()),
-            AppError::Validation(ref msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
-            AppError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized"),
-        };
+    /// 409 for deleting a room, unit or shelf that still holds something, without
+    /// `?cascade=true`. What a cascade would delete is returned in `details.counts`.
+    #[error("{entity} is not empty; delete its contents first or pass cascade=true")]
+    HasChildren {
+        entity: &'static str,
+        counts: LocationCounts,
+    },
 
-        let body = Json(json!({
-            "error": error_message,
-        }));
+    #[error("{0}")]
+    Unauthorized(String),
 
-        (status, body).into_response()
-    }
-}
+    /// 401 telling the client to log in again because the OAuth token could not be
+    /// refreshed
+    #[error("{0}")]
+    SessionExpired(String),
 
-#[allow(dead_code)] // Will be used as we build out the API
-pub type Result<T> = std::result::Result<T, AppError>;
+    #[error("{0}")]
+    Forbidden(String),
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// 422 for well-formed requests that break a rule, e.g. nesting containers
+    /// too deep
+    #[error("{0}")]
+    UnprocessableEntity(String),
 
-    #[test]
-    fn test_app_error_display() {
-        let err = AppError::Internal("Test error".to_string());
-        assert_eq!(err.to_string(), "Internal server error: Test error");
+    /// 413 for an upload over the configured size limit
+    #[error("{0}")]
+    PayloadTooLarge(String),
 
-        let err = AppError::NotFound("Resource not found".to_string());
-        assert_eq!(err.to_string(), "Not found: Resource not found");
+    #[error("{0}")]
+    InternalServer(String),
 
-        let err = AppError::Validation("Invalid input".to_string());
-        assert_eq!(err.to_string(), "Validation error: Invalid input");
+    #[error("{0}")]
+    ServiceUnavailable(String),
+}
 
-        let err = AppError::Unauthorized;
-        assert_eq!(err.to_string(), "Unauthorized");
+impl ApiError {
+    /// `"<entity> with id <id> not found"`
+    pub fn not_found(entity: &str, id: impl Display) -> Self {
+        ApiError::NotFound(format!("{} with id {} not found", entity, id))
     }
 
-    #[test]
-    fn test_app_error_from_sqlx_error() {
-        // Test that we can convert from sqlx::Error
-        // We'll use a simple error case
-        let sqlx_err = sqlx::Error::PoolClosed;
-        let app_err: AppError = sqlx_err.into();
-
-        match app_err {
-            AppError::Database(_) => {}
-            _ => panic!("Expected Database variant"),
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        ApiError::BadRequest(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        ApiError::Conflict(message.into())
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            ApiError::Conflict(_)
+            | ApiError::BarcodeConflict { .. }
+            | ApiError::CapacityExceeded { .. }
+            | ApiError::PossibleDuplicate { .. }
+            | ApiError::HasChildren { .. } => StatusCode::CONFLICT,
+            ApiError::Unauthorized(_) | ApiError::SessionExpired(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::InternalServer(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 
-    #[tokio::test]
-    async fn test_app_error_into_response_internal() {
-        let err = AppError::Internal("Test internal error".to_string());
-        let response = err.into_response();
+    /// Machine-readable `code` field
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::NotFound(_) => "NOT_FOUND",
+            ApiError::BadRequest(_) => "BAD_REQUEST",
+            ApiError::Conflict(_) => "CONFLICT",
+            ApiError::BarcodeConflict { .. } => "BARCODE_CONFLICT",
+            ApiError::CapacityExceeded { .. } => "CAPACITY_EXCEEDED",
+            ApiError::PossibleDuplicate { .. } => "POSSIBLE_DUPLICATE",
+            ApiError::HasChildren { .. } => "HAS_CHILDREN",
+            ApiError::Unauthorized(_) => "UNAUTHORIZED",
+            ApiError::SessionExpired(_) => "SESSION_EXPIRED",
+            ApiError::Forbidden(_) => "FORBIDDEN",
+            ApiError::UnprocessableEntity(_) => "UNPROCESSABLE_ENTITY",
+            ApiError::PayloadTooLarge(_) => "PAYLOAD_TOO_LARGE",
+            ApiError::InternalServer(_) => "INTERNAL_SERVER_ERROR",
+            ApiError::ServiceUnavailable(_) => "SERVICE_UNAVAILABLE",
+        }
+    }
 
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    /// Structured `details` field, `null` for most errors
+    pub fn details(&self) -> serde_json::Value {
+        match self {
+            ApiError::BarcodeConflict {
+                existing_item_id: Some(existing_item_id),
+            } => json!({ "existing_item_id": existing_item_id }),
+            ApiError::CapacityExceeded { current, max } => {
+                json!({ "current": current, "max": max })
+            }
+            ApiError::PossibleDuplicate { similar_items } => {
+                json!({ "similar_items": similar_items })
+            }
+            ApiError::HasChildren { counts, .. } => json!({ "counts": counts }),
+            _ => serde_json::Value::Null,
+        }
+    }
+}
 
-        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+/// Body of every [`ApiError`] response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// e.g. `NOT_FOUND` or `BARCODE_CONFLICT`
+    pub code: String,
+    pub message: String,
+    /// Structured details, `null` for most errors
+    pub details: serde_json::Value,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = Json(ErrorResponse {
+            code: self.code().to_string(),
+            message: self.to_string(),
+            details: self.details(),
+        });
 
-        assert_eq!(json["error"], "Test internal error");
+        (self.status(), body).into_response()
     }
+}
 
-    #[tokio::test]
-    async fn test_app_error_into_response_not_found() {
-        let err = AppError::NotFound("Item not found".to_string());
-        let response = err.into_response();
+impl From<StatusCode> for ApiError {
+    fn from(status: StatusCode) -> Self {
+        let message = status.canonical_reason().unwrap_or("Error").to_string();
+        match status {
+            StatusCode::NOT_FOUND => ApiError::NotFound(message),
+            StatusCode::BAD_REQUEST => ApiError::BadRequest(message),
+            StatusCode::CONFLICT => ApiError::Conflict(message),
+            StatusCode::UNAUTHORIZED => ApiError::Unauthorized(message),
+            StatusCode::FORBIDDEN => ApiError::Forbidden(message),
+            StatusCode::UNPROCESSABLE_ENTITY => ApiError::UnprocessableEntity(message),
+            StatusCode::PAYLOAD_TOO_LARGE => ApiError::PayloadTooLarge(message),
+            StatusCode::SERVICE_UNAVAILABLE => ApiError::ServiceUnavailable(message),
+            status if status.is_client_error() => ApiError::BadRequest(message),
+            _ => ApiError::InternalServer(message),
+        }
+    }
+}
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+/// Database errors: a missing row is a 404 and a unique violation (SQLSTATE 23505) a
+/// 409, so handlers can propagate query errors with `?`. Anything else is logged and
+/// returned as an opaque 500.
+impl From<sqlx::Error> for ApiError {
+    fn from(err: sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::RowNotFound => ApiError::NotFound("Resource not found".to_string()),
+            sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+                tracing::warn!("Unique constraint violated: {:?}", err);
+                ApiError::Conflict("Resource already exists".to_string())
+            }
+            err => {
+                tracing::error!("Database error: {:?}", err);
+                ApiError::InternalServer("Database error occurred".to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    async fn api_error_body(err: ApiError) -> (StatusCode, serde_json::Value) {
+        let response = err.into_response();
+        let status = response.status();
         let body = axum::body::to_bytes(response.into_body(), usize::MAX)
             .await
             .unwrap();
-        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-
-        assert_eq!(json["error"], "Item not found");
+        (status, serde_json::from_slice(&body).unwrap())
     }
 
     #[tokio::test]
-    async fn test_app_error_into_response_validation() {
-        let err = AppError::Validation("Invalid name".to_string());
-        let response = err.into_response();
+    async fn test_api_error_into_response() {
+        let id = uuid::Uuid::nil();
+        let (status, json) = api_error_body(ApiError::not_found("Item", id)).await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(json["code"], "NOT_FOUND");
+        assert_eq!(
+            json["message"],
+            format!("Item with id {} not found", id).as_str()
+        );
+        assert!(json["details"].is_null());
+
+        let (status, json) = api_error_body(ApiError::conflict("Barcode in use")).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(json["code"], "CONFLICT");
+
+        let (status, json) =
+            api_error_body(ApiError::SessionExpired("Log in again".to_string())).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(json["code"], "SESSION_EXPIRED");
+
+        let (status, json) = api_error_body(ApiError::BarcodeConflict {
+            existing_item_id: Some(id),
+        })
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(json["code"], "BARCODE_CONFLICT");
+        assert_eq!(json["details"]["existing_item_id"], id.to_string().as_str());
+
+        // Another household's item isn't named
+        let (status, json) = api_error_body(ApiError::BarcodeConflict {
+            existing_item_id: None,
+        })
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(json["code"], "BARCODE_CONFLICT");
+        assert!(json["details"].is_null());
+
+        let (status, json) =
+            api_error_body(ApiError::CapacityExceeded { current: 4, max: 4 }).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(json["code"], "CAPACITY_EXCEEDED");
+        assert_eq!(json["details"]["current"], 4);
+        assert_eq!(json["details"]["max"], 4);
+
+        let (status, json) = api_error_body(ApiError::PossibleDuplicate {
+            similar_items: vec![SimilarItem {
+                id,
+                name: "Hammer".to_string(),
+                similarity: 0.75,
+            }],
+        })
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(json["code"], "POSSIBLE_DUPLICATE");
+        assert_eq!(json["details"]["similar_items"][0]["name"], "Hammer");
+        assert_eq!(json["details"]["similar_items"][0]["similarity"], 0.75);
+
+        let (status, json) = api_error_body(ApiError::HasChildren {
+            entity: "Room",
+            counts: LocationCounts {
+                rooms: 1,
+                units: 2,
+                ..Default::default()
+            },
+        })
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(json["code"], "HAS_CHILDREN");
+        assert_eq!(json["details"]["counts"]["units"], 2);
+        assert_eq!(json["details"]["counts"]["items"], 0);
+    }
 
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    #[test]
+    fn test_api_error_from_status_code() {
+        assert_eq!(
+            ApiError::from(StatusCode::NOT_FOUND),
+            ApiError::NotFound("Not Found".to_string())
+        );
+        assert_eq!(
+            ApiError::from(StatusCode::INTERNAL_SERVER_ERROR).status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            ApiError::from(StatusCode::SERVICE_UNAVAILABLE).code(),
+            "SERVICE_UNAVAILABLE"
+        );
+        assert_eq!(
+            ApiError::from(StatusCode::UNPROCESSABLE_ENTITY).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            ApiError::from(StatusCode::PAYLOAD_TOO_LARGE).status(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
 
-        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    /// Stand-in for a Postgres error with a given SQLSTATE
+    #[derive(Debug)]
+    struct FakeDatabaseError(&'static str);
 
-        assert_eq!(json["error"], "Invalid name");
+    impl std::fmt::Display for FakeDatabaseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "database error {}", self.0)
+        }
     }
 
-    #[tokio::test]
-    async fn test_app_error_into_response_unauthorized() {
-        let err = AppError::Unauthorized;
-        let response = err.into_response();
+    impl std::error::Error for FakeDatabaseError {}
 
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    impl sqlx::error::DatabaseError for FakeDatabaseError {
+        fn message(&self) -> &str {
+            "database error"
+        }
 
-        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        fn code(&self) -> Option<std::borrow::Cow<'_, str>> {
+            Some(self.0.into())
+        }
 
-        assert_eq!(json["error"], "Unauthorized");
+        fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+            self
+        }
+
+        fn kind(&self) -> sqlx::error::ErrorKind {
+            match self.0 {
+                "23505" => sqlx::error::ErrorKind::UniqueViolation,
+                _ => sqlx::error::ErrorKind::Other,
+            }
+        }
     }
 
-    #[tokio::test]
-    async fn test_app_error_into_response_database() {
-        let sqlx_err = sqlx::Error::PoolClosed;
-        let err: AppError = sqlx_err.into();
-        let response = err.into_response();
+    #[test]
+    fn test_api_error_from_database_error() {
+        let err: ApiError = sqlx::Error::RowNotFound.into();
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
 
-        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let err: ApiError = sqlx::Error::Database(Box::new(FakeDatabaseError("23505"))).into();
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        assert_eq!(err.code(), "CONFLICT");
 
-        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
-            .await
-            .unwrap();
-        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let err: ApiError = sqlx::Error::Database(Box::new(FakeDatabaseError("40001"))).into();
+        assert_eq!(err.status(), StatusCode::INTERNAL_SERVER_ERROR);
 
-        assert_eq!(json["error"], "Database error occurred");
+        let err: ApiError = sqlx::Error::PoolClosed.into();
+        assert_eq!(err.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.to_string(), "Database error occurred");
     }
 }
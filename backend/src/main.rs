@@ -1,12 +1,17 @@
 mod app;
+mod config;
 mod db;
 mod error;
 mod middleware;
 mod models;
 mod routes;
 mod services;
+#[cfg(test)]
+mod test_utils;
+mod utils;
 
 use std::env;
+use std::net::SocketAddr;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -33,15 +38,16 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::info!("Running database migrations...");
 
-    // Run migrations (includes conditional logic for PostgreSQL vs DSQL)
-    // Note: For DSQL, migrations may fail due to CREATE INDEX syntax differences
-    // In production, run migrations manually with ASYNC syntax first
-    match db::run_migrations(&pool).await {
+    // Migrations are adapted to the dialect: statements DSQL doesn't support are
+    // skipped there, and CREATE INDEX ASYNC becomes a plain CREATE INDEX on PostgreSQL
+    let dialect = db::DbDialect::from_env().map_err(anyhow::Error::msg)?;
+    tracing::info!("Database dialect: {:?}", dialect);
+    match db::run_migrations(&pool, dialect).await {
         Ok(_) => tracing::info!("✓ Migrations completed successfully"),
         Err(e) => {
             tracing::warn!("⚠ Migration warning: {}. Continuing startup...", e);
             tracing::warn!(
-                "  For DSQL: Ensure migrations were run manually with CREATE INDEX ASYNC syntax"
+                "  Statements skipped on DSQL (e.g. ADD CONSTRAINT) are logged above and may need applying by hand"
             );
         }
     }
@@ -72,7 +78,12 @@ async fn main() -> anyhow::Result<()> {
 
         tracing::info!("Server listening on http://0.0.0.0:3000");
 
-        axum::serve(listener, app).await?;
+        // Connection info gives the rate limiter the client address
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await?;
         Ok(())
     }
 }
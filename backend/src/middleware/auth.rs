@@ -1,32 +1,85 @@
 use axum::{
     async_trait,
-    extract::{FromRequestParts, Request},
-    http::{request::Parts, StatusCode},
+    extract::{FromRequestParts, Request, State},
+    http::request::Parts,
     middleware::Next,
     response::Response,
 };
+use chrono::Utc;
+use oauth2::{reqwest::async_http_client, RefreshToken};
+use std::sync::Arc;
 use tower_sessions::Session;
 use uuid::Uuid;
 
-use crate::routes::auth::UserSession;
+use crate::app::AppState;
+use crate::error::ApiError;
+use crate::routes::auth::{OAuthTokens, UserSession, OAUTH_TOKENS_SESSION_KEY, USER_SESSION_KEY};
+
+fn not_logged_in() -> ApiError {
+    ApiError::Unauthorized("Not logged in".to_string())
+}
+
+fn session_error(e: impl std::fmt::Debug) -> ApiError {
+    tracing::error!("Failed to read session: {:?}", e);
+    ApiError::InternalServer("Failed to read session".to_string())
+}
 
 pub async fn auth_guard(
     session: Session,
     request: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
-    let user: Option<UserSession> = session
-        .get("user")
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+) -> Result<Response, ApiError> {
+    let user: Option<UserSession> = session.get(USER_SESSION_KEY).await.map_err(session_error)?;
 
     if user.is_some() {
         Ok(next.run(request).await)
     } else {
-        Err(StatusCode::UNAUTHORIZED)
+        Err(not_logged_in())
     }
 }
 
+/// Refresh the Google access token when it is about to expire, so a valid session
+/// never carries a dead token. If it can't be refreshed the session is ended and the
+/// client gets 401 `SESSION_EXPIRED`. Sessions without tokens pass through untouched.
+pub async fn refresh_oauth_token(
+    State(state): State<Arc<AppState>>,
+    session: Session,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let tokens: Option<OAuthTokens> = session
+        .get(OAUTH_TOKENS_SESSION_KEY)
+        .await
+        .map_err(session_error)?;
+
+    if let Some(tokens) = tokens.filter(|tokens| tokens.needs_refresh(Utc::now())) {
+        let refreshed = match tokens.refresh_token.clone() {
+            Some(refresh_token) => state
+                .oauth_client
+                .exchange_refresh_token(&RefreshToken::new(refresh_token))
+                .request_async(async_http_client)
+                .await
+                .map(|token| OAuthTokens::from_response(&token, tokens.refresh_token, Utc::now()))
+                .map_err(|e| tracing::warn!("Failed to refresh OAuth token: {:?}", e))
+                .ok(),
+            None => None,
+        };
+
+        let Some(refreshed) = refreshed else {
+            session.flush().await.ok();
+            return Err(ApiError::SessionExpired(
+                "Session expired, please log in again".to_string(),
+            ));
+        };
+        session
+            .insert(OAUTH_TOKENS_SESSION_KEY, refreshed)
+            .await
+            .map_err(session_error)?;
+    }
+
+    Ok(next.run(request).await)
+}
+
 /// Extractor for authenticated user ID
 pub struct AuthUser(pub Uuid);
 
@@ -35,21 +88,19 @@ impl<S> FromRequestParts<S> for AuthUser
 where
     S: Send + Sync,
 {
-    type Rejection = StatusCode;
+    type Rejection = ApiError;
 
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
         let session = Session::from_request_parts(parts, state)
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            .map_err(session_error)?;
 
-        let user: Option<UserSession> = session
-            .get("user")
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let user: Option<UserSession> =
+            session.get(USER_SESSION_KEY).await.map_err(session_error)?;
 
         match user {
             Some(u) => Ok(AuthUser(u.user_id)),
-            None => Err(StatusCode::UNAUTHORIZED),
+            None => Err(not_logged_in()),
         }
     }
 }
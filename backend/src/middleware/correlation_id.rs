@@ -0,0 +1,178 @@
+//! Correlation ids tying together every log line of one HTTP request.
+//!
+//! Each request runs inside a `request` span carrying its `correlation_id`, so
+//! `tracing` events anywhere below the layer are printed with it. The id comes from
+//! the client's `X-Correlation-ID` header when it sends a usable one, otherwise a new
+//! UUID, and is echoed back in the response's `X-Correlation-ID`. Handlers can read it
+//! with `Extension<CorrelationId>`.
+
+use axum::{
+    extract::Request,
+    http::{HeaderMap, HeaderValue},
+    response::Response,
+};
+use futures::future::BoxFuture;
+use std::fmt;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::Instrument;
+use uuid::Uuid;
+
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+
+/// Longest client-supplied id that is kept; longer ones are replaced
+const MAX_ID_LEN: usize = 128;
+
+/// The current request's correlation id, in the request extensions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrelationId(pub String);
+
+impl fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl CorrelationId {
+    /// The client's id from `headers` if it is printable ASCII of a sane length,
+    /// otherwise a new UUID
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let supplied = headers
+            .get(CORRELATION_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|id| {
+                !id.is_empty() && id.len() <= MAX_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+            });
+
+        match supplied {
+            Some(id) => Self(id.to_string()),
+            None => Self(Uuid::new_v4().to_string()),
+        }
+    }
+}
+
+/// Layer giving every request a correlation id and a span to log under
+#[derive(Clone, Default)]
+pub struct CorrelationIdLayer;
+
+impl<S> Layer<S> for CorrelationIdLayer {
+    type Service = CorrelationIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CorrelationIdService { inner }
+    }
+}
+
+/// Service created by [`CorrelationIdLayer`]
+#[derive(Clone)]
+pub struct CorrelationIdService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request> for CorrelationIdService<S>
+where
+    S: Service<Request, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request) -> Self::Future {
+        let id = CorrelationId::from_headers(request.headers());
+        let span = tracing::info_span!(
+            "request",
+            correlation_id = %id,
+            method = %request.method(),
+            path = %request.uri().path(),
+        );
+        request.extensions_mut().insert(id.clone());
+
+        // Anything the inner service logs before returning its future belongs here too
+        let future = span.in_scope(|| self.inner.call(request));
+
+        Box::pin(
+            async move {
+                let mut response = future.await?;
+                // Validated as printable ASCII or generated, so always a valid value
+                if let Ok(value) = HeaderValue::from_str(&id.0) {
+                    response.headers_mut().insert(CORRELATION_ID_HEADER, value);
+                }
+                Ok(response)
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::get, Extension, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(|Extension(id): Extension<CorrelationId>| async move { id.0 }),
+            )
+            .layer(CorrelationIdLayer)
+    }
+
+    async fn send(header: Option<&str>) -> (String, String) {
+        let mut request = Request::builder().uri("/");
+        if let Some(value) = header {
+            request = request.header(CORRELATION_ID_HEADER, value);
+        }
+        let response = app()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let echoed = response.headers()[CORRELATION_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (echoed, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_client_correlation_id_is_kept() {
+        let (echoed, seen_by_handler) = send(Some("checkout-42")).await;
+
+        assert_eq!(echoed, "checkout-42");
+        assert_eq!(seen_by_handler, "checkout-42");
+    }
+
+    #[tokio::test]
+    async fn test_missing_correlation_id_is_generated() {
+        let (echoed, seen_by_handler) = send(None).await;
+
+        assert!(Uuid::parse_str(&echoed).is_ok());
+        assert_eq!(seen_by_handler, echoed);
+    }
+
+    #[test]
+    fn test_unusable_correlation_ids_are_replaced() {
+        let too_long = "a".repeat(MAX_ID_LEN + 1);
+        for supplied in ["", "   ", "has space", too_long.as_str()] {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                CORRELATION_ID_HEADER,
+                HeaderValue::from_str(supplied).unwrap(),
+            );
+
+            let id = CorrelationId::from_headers(&headers);
+            assert!(Uuid::parse_str(&id.0).is_ok(), "kept {:?}", supplied);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use axum::{
+    extract::Request,
+    handler::Handler,
+    middleware::{from_fn, Next},
+    response::Response,
+    routing::{patch, put, MethodRouter},
+};
+
+/// Route an update handler on `PATCH`, keeping the old `PUT` as a deprecated alias.
+/// The handlers are partial updates, which `PUT` never described correctly.
+pub fn patch_with_put_alias<H, T, S>(handler: H) -> MethodRouter<S>
+where
+    H: Handler<T, S>,
+    T: 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    patch(handler.clone()).merge(put(handler).layer(from_fn(warn_deprecated_put)))
+}
+
+/// Log each request still using the `PUT` alias so remaining callers can be found
+async fn warn_deprecated_put(req: Request, next: Next) -> Response {
+    tracing::warn!(
+        "PUT {} is deprecated; send PATCH for partial updates",
+        req.uri().path()
+    );
+    next.run(req).await
+}
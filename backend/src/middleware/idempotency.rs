@@ -0,0 +1,451 @@
+//! Replay of responses for requests carrying an `X-Idempotency-Key` header, so a
+//! client retrying a create after a dropped connection doesn't create it twice.
+//!
+//! The first request with a key claims it by inserting a row into `idempotency_keys`
+//! before the handler runs, and stores the response body once it succeeds. Failed
+//! requests delete the row so a retry runs again. A duplicate arriving while the
+//! first is still running gets 409; once the response is stored it is replayed.
+//! Claims are committed up front rather than held in an open transaction because
+//! Aurora DSQL doesn't block on conflicting writes, it fails them at commit.
+//!
+//! Expired keys, and claims abandoned by a request that never finished, are taken
+//! over under `SELECT ... FOR UPDATE` so only one request reruns them.
+//!
+//! Keys are per user, so one user's key can never replay another user's response.
+//!
+//! Only successful responses are stored, and replays are always 200 with a JSON
+//! body, so apply this to routes returning JSON.
+
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, Request},
+    http::{header, HeaderMap, Method, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Duration, Utc};
+use futures::future::BoxFuture;
+use sqlx::PgPool;
+use std::convert::Infallible;
+use std::task::{Context, Poll};
+use tower::{Layer, Service, ServiceExt};
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::middleware::auth::AuthUser;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "x-idempotency-key";
+
+/// Longest accepted header value
+const MAX_KEY_LEN: usize = 255;
+/// How long a response is replayed for
+const KEY_TTL_HOURS: i64 = 24;
+/// A claim with no response after this long belongs to a request that died
+const ABANDONED_CLAIM_MINUTES: i64 = 5;
+/// Expired keys deleted after each newly stored response
+const PRUNE_BATCH_SIZE: i64 = 100;
+
+const KEY_IN_USE: &str = "A request with this X-Idempotency-Key is still being processed";
+
+/// Layer adding idempotency keys to the routes it wraps
+#[derive(Clone)]
+pub struct IdempotencyLayer {
+    db: PgPool,
+}
+
+impl IdempotencyLayer {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+}
+
+impl<S> Layer<S> for IdempotencyLayer {
+    type Service = Idempotency<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Idempotency {
+            inner,
+            db: self.db.clone(),
+        }
+    }
+}
+
+/// Service created by [`IdempotencyLayer`]
+#[derive(Clone)]
+pub struct Idempotency<S> {
+    inner: S,
+    db: PgPool,
+}
+
+impl<S> Service<Request> for Idempotency<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Use the service that was polled ready, leaving a fresh clone in its place
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+        let db = self.db.clone();
+
+        Box::pin(async move {
+            let key = match idempotency_key(request.headers()) {
+                Ok(Some(key)) => key,
+                Ok(None) => return inner.oneshot(request).await,
+                Err(message) => return Ok(ApiError::bad_request(message).into_response()),
+            };
+
+            let (mut parts, body) = request.into_parts();
+            let user_id = match AuthUser::from_request_parts(&mut parts, &()).await {
+                Ok(AuthUser(user_id)) => user_id,
+                Err(e) => return Ok(e.into_response()),
+            };
+            let request = Request::from_parts(parts, body);
+            let key = scoped_key(user_id, request.method(), request.uri().path(), &key);
+
+            Ok(run_once(&db, inner, request, key)
+                .await
+                .unwrap_or_else(IntoResponse::into_response))
+        })
+    }
+}
+
+/// The trimmed header value, `None` when absent
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, String> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    let key = value
+        .to_str()
+        .map_err(|_| "X-Idempotency-Key must be ASCII".to_string())?
+        .trim();
+
+    if key.is_empty() {
+        return Err("X-Idempotency-Key must not be empty".to_string());
+    }
+    if key.len() > MAX_KEY_LEN {
+        return Err(format!(
+            "X-Idempotency-Key must be at most {} characters",
+            MAX_KEY_LEN
+        ));
+    }
+    Ok(Some(key.to_string()))
+}
+
+/// Keys only replay for the user and route they were first sent by and to
+fn scoped_key(user_id: Uuid, method: &Method, path: &str, key: &str) -> String {
+    format!("{} {} {} {}", user_id, method, path, key)
+}
+
+/// Whether a key's row can be taken over: its response has expired, or there is no
+/// response and the request that claimed it has been gone too long
+fn is_reusable(has_response: bool, created_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    let age = now - created_at;
+    if has_response {
+        age >= Duration::hours(KEY_TTL_HOURS)
+    } else {
+        age >= Duration::minutes(ABANDONED_CLAIM_MINUTES)
+    }
+}
+
+/// Unique violations, and DSQL's optimistic concurrency failures, mean another
+/// request got to the key first
+fn is_conflict(e: &sqlx::Error) -> bool {
+    matches!(
+        e,
+        sqlx::Error::Database(db) if matches!(db.code().as_deref(), Some("23505" | "40001"))
+    )
+}
+
+fn replay(body: String) -> Response {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json")],
+        body,
+    )
+        .into_response()
+}
+
+enum Claim {
+    /// This request runs the handler
+    Owned,
+    /// A stored response to return instead
+    Replay(String),
+}
+
+async fn claim(db: &PgPool, key: &str) -> Result<Claim, ApiError> {
+    let claim_error = |context: &str, e: sqlx::Error| -> ApiError {
+        if is_conflict(&e) {
+            return ApiError::conflict(KEY_IN_USE);
+        }
+        tracing::error!("Failed to {}: {:?}", context, e);
+        StatusCode::INTERNAL_SERVER_ERROR.into()
+    };
+
+    let inserted = sqlx::query(
+        "INSERT INTO idempotency_keys (key, created_at) VALUES ($1, NOW()) ON CONFLICT (key) DO NOTHING",
+    )
+    .bind(key)
+    .execute(db)
+    .await
+    .map_err(|e| claim_error("claim idempotency key", e))?
+    .rows_affected()
+        == 1;
+    if inserted {
+        return Ok(Claim::Owned);
+    }
+
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|e| claim_error("start idempotency key transaction", e))?;
+
+    let existing: Option<(Option<String>, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT response_body, created_at FROM idempotency_keys WHERE key = $1 FOR UPDATE",
+    )
+    .bind(key)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| claim_error("look up idempotency key", e))?;
+
+    // Pruned since the insert; the client can simply retry
+    let Some((response_body, created_at)) = existing else {
+        return Err(ApiError::conflict(KEY_IN_USE));
+    };
+
+    if !is_reusable(response_body.is_some(), created_at, Utc::now()) {
+        tx.rollback().await.ok();
+        return match response_body {
+            Some(body) => Ok(Claim::Replay(body)),
+            None => Err(ApiError::conflict(KEY_IN_USE)),
+        };
+    }
+
+    sqlx::query(
+        "UPDATE idempotency_keys SET response_body = NULL, created_at = NOW() WHERE key = $1",
+    )
+    .bind(key)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| claim_error("reuse idempotency key", e))?;
+    tx.commit()
+        .await
+        .map_err(|e| claim_error("reuse idempotency key", e))?;
+
+    Ok(Claim::Owned)
+}
+
+/// Run the request unless `key` already has a response, storing the response if it
+/// succeeds
+async fn run_once<S>(
+    db: &PgPool,
+    inner: S,
+    request: Request,
+    key: String,
+) -> Result<Response, ApiError>
+where
+    S: Service<Request, Response = Response, Error = Infallible> + Send,
+    S::Future: Send,
+{
+    if let Claim::Replay(body) = claim(db, &key).await? {
+        tracing::info!("Replaying response for idempotency key {}", key);
+        return Ok(replay(body));
+    }
+
+    let response = match inner.oneshot(request).await {
+        Ok(response) => response,
+        Err(never) => match never {},
+    };
+    if !response.status().is_success() {
+        release(db, &key).await;
+        return Ok(response);
+    }
+
+    // The handler's writes are committed, so from here on the response is returned
+    // even if it can't be stored
+    let (parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!("Failed to read response for idempotency key: {:?}", e);
+            release(db, &key).await;
+            return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
+        }
+    };
+
+    match std::str::from_utf8(&bytes) {
+        Ok(body) => {
+            let stored =
+                sqlx::query("UPDATE idempotency_keys SET response_body = $1 WHERE key = $2")
+                    .bind(body)
+                    .bind(&key)
+                    .execute(db)
+                    .await;
+            match stored {
+                Ok(_) => prune_expired(db.clone()),
+                Err(e) => tracing::error!("Failed to store idempotent response: {:?}", e),
+            }
+        }
+        Err(_) => {
+            tracing::warn!("Not storing non-UTF-8 response for idempotency key");
+            release(db, &key).await;
+        }
+    }
+
+    Ok(Response::from_parts(parts, Body::from(bytes)))
+}
+
+/// Drop a claim whose request failed, so a retry runs again
+async fn release(db: &PgPool, key: &str) {
+    if let Err(e) =
+        sqlx::query("DELETE FROM idempotency_keys WHERE key = $1 AND response_body IS NULL")
+            .bind(key)
+            .execute(db)
+            .await
+    {
+        tracing::error!("Failed to release idempotency key: {:?}", e);
+    }
+}
+
+/// Delete a batch of expired keys in the background
+fn prune_expired(db: PgPool) {
+    tokio::spawn(async move {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE key IN (
+                SELECT key FROM idempotency_keys
+                WHERE created_at < $1
+                LIMIT $2
+            )
+            "#,
+        )
+        .bind(Utc::now() - Duration::hours(KEY_TTL_HOURS))
+        .bind(PRUNE_BATCH_SIZE)
+        .execute(&db)
+        .await;
+
+        if let Err(e) = result {
+            tracing::warn!("Failed to prune expired idempotency keys: {:?}", e);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_pool;
+    use axum::{routing::post, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tower_sessions::{MemoryStore, Session, SessionManagerLayer};
+
+    use crate::routes::auth::{UserSession, USER_SESSION_KEY};
+
+    #[test]
+    fn test_idempotency_key_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(idempotency_key(&headers), Ok(None));
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, " abc-123 ".parse().unwrap());
+        assert_eq!(idempotency_key(&headers), Ok(Some("abc-123".to_string())));
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, "  ".parse().unwrap());
+        assert!(idempotency_key(&headers).is_err());
+
+        headers.insert(IDEMPOTENCY_KEY_HEADER, "a".repeat(256).parse().unwrap());
+        assert!(idempotency_key(&headers).is_err());
+    }
+
+    #[test]
+    fn test_keys_are_scoped_to_the_user_and_route() {
+        let user_id = Uuid::new_v4();
+        assert_ne!(
+            scoped_key(user_id, &Method::POST, "/api/items", "abc"),
+            scoped_key(user_id, &Method::POST, "/api/items/bulk", "abc")
+        );
+        assert_ne!(
+            scoped_key(user_id, &Method::POST, "/api/items", "abc"),
+            scoped_key(Uuid::new_v4(), &Method::POST, "/api/items", "abc")
+        );
+    }
+
+    #[test]
+    fn test_is_reusable() {
+        let now = Utc::now();
+        assert!(!is_reusable(true, now - Duration::hours(23), now));
+        assert!(is_reusable(true, now - Duration::hours(24), now));
+        // A claim still in progress
+        assert!(!is_reusable(false, now - Duration::minutes(1), now));
+        assert!(is_reusable(false, now - Duration::minutes(5), now));
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_duplicate_requests_are_replayed() {
+        let pool = create_test_pool().await;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handler_calls = calls.clone();
+        let user_id = Uuid::new_v4();
+        let app = Router::new()
+            .route(
+                "/api/items",
+                post(move || async move {
+                    let n = handler_calls.fetch_add(1, Ordering::SeqCst);
+                    axum::Json(serde_json::json!({ "call": n }))
+                }),
+            )
+            .route_layer(IdempotencyLayer::new(pool.clone()))
+            // Log every request in as the same user
+            .layer(axum::middleware::from_fn(
+                move |session: Session, request: Request, next: axum::middleware::Next| async move {
+                    let user = UserSession {
+                        user_id,
+                        email: "idempotency@example.com".to_string(),
+                        name: "Idempotency".to_string(),
+                        picture: None,
+                    };
+                    session.insert(USER_SESSION_KEY, user).await.unwrap();
+                    next.run(request).await
+                },
+            ))
+            .layer(SessionManagerLayer::new(MemoryStore::default()));
+
+        let key = Uuid::new_v4().to_string();
+        let request = || {
+            Request::builder()
+                .method("POST")
+                .uri("/api/items")
+                .header(IDEMPOTENCY_KEY_HEADER, &key)
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let first = app.clone().oneshot(request()).await.unwrap();
+        let second = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(second.status(), StatusCode::OK);
+        let first = axum::body::to_bytes(first.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let second = axum::body::to_bytes(second.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        sqlx::query("DELETE FROM idempotency_keys WHERE key = $1")
+            .bind(scoped_key(user_id, &Method::POST, "/api/items", &key))
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first, second);
+    }
+}
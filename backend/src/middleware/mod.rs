@@ -1 +1,5 @@
 pub mod auth;
+pub mod correlation_id;
+pub mod deprecation;
+pub mod idempotency;
+pub mod rate_limit;
@@ -0,0 +1,230 @@
+//! Per-IP rate limiting for public endpoints.
+//!
+//! Counts are kept in process memory, so the limit applies per instance. On Lambda
+//! every warm container keeps its own counts, and a client whose requests are spread
+//! across N containers can make up to N times the limit. Use this as burst
+//! protection in front of durable limits (the contact form also enforces an hourly
+//! limit from the database), not as a global quota.
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use dashmap::DashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Prune expired entries once this many IPs are being tracked
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Allows each IP `max_requests` per window, counted from its first request in the window
+pub struct RateLimiter {
+    max_requests: u32,
+    window: Duration,
+    hits: DashMap<IpAddr, (u32, Instant)>,
+}
+
+impl RateLimiter {
+    pub fn new(max_requests: u32, window: Duration) -> Self {
+        Self {
+            max_requests,
+            window,
+            hits: DashMap::new(),
+        }
+    }
+
+    pub fn per_minute(max_requests: u32) -> Self {
+        Self::new(max_requests, Duration::from_secs(60))
+    }
+
+    /// Record a request from `ip`. Returns how long to wait if it is over the limit.
+    pub fn check(&self, ip: IpAddr) -> Result<(), Duration> {
+        self.check_at(ip, Instant::now())
+    }
+
+    fn check_at(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        if self.hits.len() >= PRUNE_THRESHOLD {
+            self.hits
+                .retain(|_, (_, started)| now.duration_since(*started) < self.window);
+        }
+
+        let mut entry = self.hits.entry(ip).or_insert((0, now));
+        let (count, started) = entry.value_mut();
+
+        let elapsed = now.duration_since(*started);
+        if elapsed >= self.window {
+            *count = 0;
+            *started = now;
+        }
+
+        if *count >= self.max_requests {
+            return Err(self.window.saturating_sub(elapsed));
+        }
+
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// Client IP from the connection, or the last `X-Forwarded-For` hop when behind
+/// API Gateway/CloudFront (Lambda requests carry no socket address)
+fn client_ip(request: &Request) -> Option<IpAddr> {
+    if let Some(ConnectInfo(addr)) = request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        return Some(addr.ip());
+    }
+    forwarded_ip(request.headers())
+}
+
+/// The last `X-Forwarded-For` hop. API Gateway appends the address it received the
+/// request from to whatever header the client sent, so that entry is the only one the
+/// client can't forge; taking the first would let anyone dodge the limit by sending a
+/// different made-up address each time.
+fn forwarded_ip(headers: &HeaderMap) -> Option<IpAddr> {
+    headers
+        .get("x-forwarded-for")?
+        .to_str()
+        .ok()?
+        .rsplit(',')
+        .next()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Middleware rejecting requests over the limit with 429 and `Retry-After`.
+/// Requests with no identifiable client IP are let through.
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(ip) = client_ip(&request) else {
+        return next.run(request).await;
+    };
+
+    match limiter.check(ip) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            tracing::warn!("Rate limit exceeded for {} on {}", ip, request.uri().path());
+            // Round up so clients never retry before the window resets
+            let retry_after_secs =
+                retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after_secs.max(1).to_string())],
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::post, Router};
+    use tower::ServiceExt;
+
+    fn limited_app(limiter: RateLimiter) -> Router {
+        Router::new()
+            .route("/api/contact", post(|| async { StatusCode::CREATED }))
+            .route_layer(axum::middleware::from_fn_with_state(
+                Arc::new(limiter),
+                rate_limit,
+            ))
+    }
+
+    fn request_from(ip: &str) -> Request {
+        Request::builder()
+            .method("POST")
+            .uri("/api/contact")
+            .header("x-forwarded-for", format!("10.0.0.1, {}", ip))
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sixth_request_in_a_minute_is_rejected() {
+        let app = limited_app(RateLimiter::per_minute(5));
+
+        for _ in 0..5 {
+            let response = app
+                .clone()
+                .oneshot(request_from("203.0.113.7"))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::CREATED);
+        }
+
+        let response = app
+            .clone()
+            .oneshot(request_from("203.0.113.7"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=60).contains(&retry_after));
+
+        // Other clients are unaffected
+        let response = app.oneshot(request_from("198.51.100.2")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+    }
+
+    #[tokio::test]
+    async fn test_direct_requests_are_limited_by_socket_address() {
+        let app = limited_app(RateLimiter::per_minute(1));
+        // What `into_make_service_with_connect_info` attaches, with no X-Forwarded-For
+        let request = || {
+            let mut request = Request::builder()
+                .method("POST")
+                .uri("/api/contact")
+                .body(Body::empty())
+                .unwrap();
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from(([203, 0, 113, 7], 51000))));
+            request
+        };
+
+        let response = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let response = app.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[test]
+    fn test_window_resets() {
+        let limiter = RateLimiter::per_minute(1);
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        let start = Instant::now();
+
+        assert!(limiter.check_at(ip, start).is_ok());
+        assert_eq!(
+            limiter.check_at(ip, start + Duration::from_secs(20)),
+            Err(Duration::from_secs(40))
+        );
+        assert!(limiter
+            .check_at(ip, start + Duration::from_secs(60))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_forwarded_ip_uses_last_hop() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(forwarded_ip(&headers), None);
+
+        // A client can prepend anything; the proxy's entry is last
+        headers.insert("x-forwarded-for", "10.0.0.1, 203.0.113.7".parse().unwrap());
+        assert_eq!(forwarded_ip(&headers), "203.0.113.7".parse().ok());
+
+        headers.insert("x-forwarded-for", "not-an-ip".parse().unwrap());
+        assert_eq!(forwarded_ip(&headers), None);
+    }
+}
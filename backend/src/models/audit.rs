@@ -2,10 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use typeshare::typeshare;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct AuditLog {
     pub id: Uuid,
     pub entity_type: String,
@@ -18,7 +19,7 @@ pub struct AuditLog {
 }
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct AuditLogResponse {
     pub id: String,
     pub entity_type: String,
@@ -46,3 +47,19 @@ impl From<AuditLog> for AuditLogResponse {
         }
     }
 }
+
+/// Body of `DELETE /api/admin/audit-logs/purge`
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PurgeAuditLogsRequest {
+    /// Delete entries older than this many days; must be at least 1
+    pub older_than_days: i32,
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PurgeAuditLogsResponse {
+    pub deleted_count: u64,
+    /// Number of delete statements that removed rows
+    pub batches: i32,
+}
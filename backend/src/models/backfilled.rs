@@ -0,0 +1,65 @@
+use sqlx::postgres::{PgTypeInfo, PgValueRef};
+use sqlx::{Postgres, ValueRef};
+
+/// Columns added to existing tables after the fact. DSQL rejects
+/// `ADD COLUMN ... DEFAULT`, so they're added nullable and backfilled, and rows
+/// inserted without a value still hold NULL. These read NULL as the value the
+/// default would have given, e.g. `#[sqlx(try_from = "NullAsOne")]`.
+///
+/// A NULL quantity: one of the item
+pub struct NullAsOne(i32);
+
+impl sqlx::Type<Postgres> for NullAsOne {
+    fn type_info() -> PgTypeInfo {
+        <i32 as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <i32 as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for NullAsOne {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        if value.is_null() {
+            return Ok(NullAsOne(1));
+        }
+        Ok(NullAsOne(<i32 as sqlx::Decode<Postgres>>::decode(value)?))
+    }
+}
+
+impl From<NullAsOne> for i32 {
+    fn from(value: NullAsOne) -> Self {
+        value.0
+    }
+}
+
+/// A NULL flag: off
+pub struct NullAsFalse(bool);
+
+impl sqlx::Type<Postgres> for NullAsFalse {
+    fn type_info() -> PgTypeInfo {
+        <bool as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <bool as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for NullAsFalse {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        if value.is_null() {
+            return Ok(NullAsFalse(false));
+        }
+        Ok(NullAsFalse(<bool as sqlx::Decode<Postgres>>::decode(
+            value,
+        )?))
+    }
+}
+
+impl From<NullAsFalse> for bool {
+    fn from(value: NullAsFalse) -> Self {
+        value.0
+    }
+}
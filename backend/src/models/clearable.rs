@@ -0,0 +1,86 @@
+use serde::{Deserialize, Deserializer};
+
+/// A field in a partial update that distinguishes "omitted" from explicit `null`.
+///
+/// - field omitted in JSON -> `Keep` (leave the existing value alone)
+/// - field is `null` -> `Clear` (set the column to NULL)
+/// - field has a value -> `Set(value)`
+///
+/// Fields using this type must be annotated with `#[serde(default)]` so that an
+/// omitted field deserializes to `Keep`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Clearable<T> {
+    #[default]
+    Keep,
+    Clear,
+    Set(T),
+}
+
+impl<T> Clearable<T> {
+    /// Resolve the new value given the existing one
+    pub fn apply(self, existing: Option<T>) -> Option<T> {
+        match self {
+            Clearable::Keep => existing,
+            Clearable::Clear => None,
+            Clearable::Set(value) => Some(value),
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Clearable<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // Only called when the field is present, so null means Clear
+        Ok(match Option::<T>::deserialize(deserializer)? {
+            Some(value) => Clearable::Set(value),
+            None => Clearable::Clear,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct TestRequest {
+        #[serde(default)]
+        barcode: Clearable<String>,
+    }
+
+    #[test]
+    fn test_omitted_field_is_keep() {
+        let request: TestRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request.barcode, Clearable::Keep);
+    }
+
+    #[test]
+    fn test_null_field_is_clear() {
+        let request: TestRequest = serde_json::from_str(r#"{"barcode": null}"#).unwrap();
+        assert_eq!(request.barcode, Clearable::Clear);
+    }
+
+    #[test]
+    fn test_value_field_is_set() {
+        let request: TestRequest = serde_json::from_str(r#"{"barcode": "12345"}"#).unwrap();
+        assert_eq!(request.barcode, Clearable::Set("12345".to_string()));
+    }
+
+    #[test]
+    fn test_apply() {
+        let existing = Some("old".to_string());
+
+        assert_eq!(Clearable::Keep.apply(existing.clone()), existing);
+        assert_eq!(Clearable::<String>::Clear.apply(existing.clone()), None);
+        assert_eq!(
+            Clearable::Set("new".to_string()).apply(existing),
+            Some("new".to_string())
+        );
+        assert_eq!(Clearable::<String>::Keep.apply(None), None);
+    }
+}
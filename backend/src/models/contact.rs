@@ -1,11 +1,52 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::str::FromStr;
 use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::models::Clearable;
+
+/// Where a contact submission is in the admin's triage
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ContactStatus {
+    Unread,
+    Read,
+    Replied,
+    Archived,
+}
+
+impl ContactStatus {
+    /// Value stored in `contact_submissions.status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContactStatus::Unread => "unread",
+            ContactStatus::Read => "read",
+            ContactStatus::Replied => "replied",
+            ContactStatus::Archived => "archived",
+        }
+    }
+}
+
+impl FromStr for ContactStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unread" => Ok(ContactStatus::Unread),
+            "read" => Ok(ContactStatus::Read),
+            "replied" => Ok(ContactStatus::Replied),
+            "archived" => Ok(ContactStatus::Archived),
+            other => Err(format!("Unknown contact status: {}", other)),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct ContactSubmission {
     pub id: Uuid,
     pub name: String,
@@ -16,10 +57,16 @@ pub struct ContactSubmission {
     pub ip_address: Option<String>,
     pub user_agent: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub status: String,
+    pub admin_notes: Option<String>,
+    /// When the submission was first marked replied
+    pub replied_at: Option<DateTime<Utc>>,
+    /// When the admin last changed its status or notes
+    pub updated_at: DateTime<Utc>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateContactSubmissionRequest {
     pub name: String,
     pub email: String,
@@ -30,7 +77,7 @@ pub struct CreateContactSubmissionRequest {
 }
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ContactSubmissionResponse {
     pub id: Uuid,
     pub name: String,
@@ -39,10 +86,25 @@ pub struct ContactSubmissionResponse {
     pub message: String,
     pub item_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
+    pub status: ContactStatus,
+    pub admin_notes: Option<String>,
+    /// When the submission was first marked replied
+    pub replied_at: Option<DateTime<Utc>>,
+    /// When the admin last changed its status or notes
+    pub updated_at: DateTime<Utc>,
 }
 
 impl From<ContactSubmission> for ContactSubmissionResponse {
     fn from(submission: ContactSubmission) -> Self {
+        let status = submission.status.parse().unwrap_or_else(|e| {
+            tracing::warn!(
+                "Contact submission {} has invalid status: {}",
+                submission.id,
+                e
+            );
+            ContactStatus::Unread
+        });
+
         Self {
             id: submission.id,
             name: submission.name,
@@ -51,6 +113,94 @@ impl From<ContactSubmission> for ContactSubmissionResponse {
             message: submission.message,
             item_id: submission.item_id,
             created_at: submission.created_at,
+            status,
+            admin_notes: submission.admin_notes,
+            replied_at: submission.replied_at,
+            updated_at: submission.updated_at,
+        }
+    }
+}
+
+/// Filters for listing contact submissions, alongside the pagination parameters
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ContactSubmissionFilter {
+    #[param(inline)]
+    pub status: Option<ContactStatus>,
+    pub item_id: Option<Uuid>,
+}
+
+/// Body of `PATCH /api/contact/:id`. Omitted fields are left unchanged;
+/// `admin_notes: null` clears the notes.
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateContactSubmissionRequest {
+    pub status: Option<ContactStatus>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "string | null"))]
+    #[schema(value_type = Option<String>)]
+    pub admin_notes: Clearable<String>,
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UnreadCountResponse {
+    pub count: i32,
+}
+
+/// `replied_at` after a status change: stamped the first time a submission is marked
+/// replied and kept from then on, so archiving a replied submission doesn't lose it
+pub fn next_replied_at(
+    status: ContactStatus,
+    replied_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    match (status, replied_at) {
+        (_, Some(replied_at)) => Some(replied_at),
+        (ContactStatus::Replied, None) => Some(now),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_contact_status_round_trip() {
+        for status in [
+            ContactStatus::Unread,
+            ContactStatus::Read,
+            ContactStatus::Replied,
+            ContactStatus::Archived,
+        ] {
+            assert_eq!(status.as_str().parse::<ContactStatus>(), Ok(status));
+            assert_eq!(
+                serde_json::to_value(status).unwrap(),
+                serde_json::json!(status.as_str())
+            );
         }
+        assert!("spam".parse::<ContactStatus>().is_err());
+    }
+
+    #[test]
+    fn test_next_replied_at() {
+        let now = Utc::now();
+        let earlier = now - Duration::days(1);
+
+        assert_eq!(
+            next_replied_at(ContactStatus::Replied, None, now),
+            Some(now)
+        );
+        assert_eq!(
+            next_replied_at(ContactStatus::Replied, Some(earlier), now),
+            Some(earlier)
+        );
+        assert_eq!(
+            next_replied_at(ContactStatus::Archived, Some(earlier), now),
+            Some(earlier)
+        );
+        assert_eq!(next_replied_at(ContactStatus::Read, None, now), None);
     }
 }
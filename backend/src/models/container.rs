@@ -2,10 +2,13 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::models::TagResponse;
+
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Container {
     pub id: Uuid,
     pub shelf_id: Option<Uuid>,
@@ -16,28 +19,43 @@ pub struct Container {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: Uuid,
+    pub position: Option<i32>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateContainerRequest {
     pub shelf_id: Option<Uuid>,
     pub parent_container_id: Option<Uuid>,
     pub name: String,
     pub description: Option<String>,
+    /// Defaults to after the last container at the same location
+    pub position: Option<i32>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+/// Body of `PATCH /api/containers/:id`. Omitted fields are left unchanged.
 pub struct UpdateContainerRequest {
     pub name: Option<String>,
     pub description: Option<String>,
     pub shelf_id: Option<Uuid>,
     pub parent_container_id: Option<Uuid>,
+    pub position: Option<i32>,
+}
+
+/// Most containers a single reorder may name
+pub const MAX_REORDER_CONTAINERS: usize = 500;
+
+/// Body of `POST /api/containers/reorder`: sibling containers in their new order
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReorderContainersRequest {
+    pub container_ids: Vec<Uuid>,
 }
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ContainerResponse {
     pub id: Uuid,
     pub shelf_id: Option<Uuid>,
@@ -45,8 +63,18 @@ pub struct ContainerResponse {
     pub name: String,
     pub description: Option<String>,
     pub label_id: Option<Uuid>,
+    pub position: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Only with `?include_photos=true` on list endpoints
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_count: Option<i32>,
+    /// Presigned URL of the earliest photo, only with `?include_photos=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_photo_url: Option<String>,
+    /// Only with `?include_tags=true`; empty rather than absent when untagged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<TagResponse>>,
 }
 
 impl From<Container> for ContainerResponse {
@@ -58,12 +86,180 @@ impl From<Container> for ContainerResponse {
             name: container.name,
             description: container.description,
             label_id: container.label_id,
+            position: container.position,
             created_at: container.created_at,
             updated_at: container.updated_at,
+            photo_count: None,
+            primary_photo_url: None,
+            tags: None,
         }
     }
 }
 
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ContainerSearchQuery {
+    pub q: String,
+    pub limit: Option<i32>,
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ContainerSearchResult {
+    pub container: ContainerResponse,
+    /// Full-text relevance; 0 when the search fell back to substring matching
+    pub rank: f32,
+}
+
+/// Most containers `GET /api/containers/:id/subtree` returns
+pub const MAX_SUBTREE_NODES: usize = 500;
+
+#[typeshare]
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ContainerSubtreeQuery {
+    /// Return the subtree as a depth-first list instead of nested nodes
+    #[serde(default)]
+    pub flat: bool,
+}
+
+/// One container in a subtree, as read from the database
+#[derive(Debug, Clone, FromRow)]
+pub struct ContainerSubtreeRow {
+    pub id: Uuid,
+    pub parent_container_id: Option<Uuid>,
+    pub name: String,
+    pub item_count: i32,
+}
+
+/// A container with everything nested inside it
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ContainerTreeNode {
+    pub id: Uuid,
+    pub name: String,
+    /// Items directly in this container
+    pub item_count: i32,
+    /// Items in this container and every container nested inside it
+    pub total_item_count: i32,
+    #[schema(no_recursion)]
+    pub children: Vec<ContainerTreeNode>,
+}
+
+/// A container in the `?flat=true` form of a subtree
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FlatContainerTreeNode {
+    pub id: Uuid,
+    /// `None` only for the requested container
+    pub parent_container_id: Option<Uuid>,
+    pub name: String,
+    /// 0 for the requested container, 1 for its children, and so on
+    pub depth: i32,
+    pub item_count: i32,
+    pub total_item_count: i32,
+}
+
+/// `GET /api/containers/:id/subtree`: nested by default, a flat list with `?flat=true`
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum ContainerSubtreeResponse {
+    Tree(ContainerTreeNode),
+    Flat(Vec<FlatContainerTreeNode>),
+}
+
+impl ContainerTreeNode {
+    /// Assemble the tree rooted at `root_id` from subtree rows, keeping the rows'
+    /// order among siblings. Rows not connected to the root are ignored.
+    pub fn build(root_id: Uuid, rows: &[ContainerSubtreeRow]) -> Option<Self> {
+        let mut children: std::collections::HashMap<Uuid, Vec<&ContainerSubtreeRow>> =
+            std::collections::HashMap::new();
+        for row in rows.iter().filter(|row| row.id != root_id) {
+            if let Some(parent_id) = row.parent_container_id {
+                children.entry(parent_id).or_default().push(row);
+            }
+        }
+
+        fn node(
+            row: &ContainerSubtreeRow,
+            children: &std::collections::HashMap<Uuid, Vec<&ContainerSubtreeRow>>,
+        ) -> ContainerTreeNode {
+            let children: Vec<ContainerTreeNode> = children
+                .get(&row.id)
+                .map(|rows| rows.iter().map(|child| node(child, children)).collect())
+                .unwrap_or_default();
+            ContainerTreeNode {
+                id: row.id,
+                name: row.name.clone(),
+                item_count: row.item_count,
+                total_item_count: row.item_count
+                    + children
+                        .iter()
+                        .map(|child| child.total_item_count)
+                        .sum::<i32>(),
+                children,
+            }
+        }
+
+        rows.iter()
+            .find(|row| row.id == root_id)
+            .map(|root| node(root, &children))
+    }
+
+    /// Number of containers in the tree, including this one
+    pub fn node_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(ContainerTreeNode::node_count)
+            .sum::<usize>()
+    }
+
+    /// The tree as a list, parents before their children (depth-first)
+    pub fn flatten(&self) -> Vec<FlatContainerTreeNode> {
+        fn visit(
+            node: &ContainerTreeNode,
+            parent_container_id: Option<Uuid>,
+            depth: i32,
+            out: &mut Vec<FlatContainerTreeNode>,
+        ) {
+            out.push(FlatContainerTreeNode {
+                id: node.id,
+                parent_container_id,
+                name: node.name.clone(),
+                depth,
+                item_count: node.item_count,
+                total_item_count: node.total_item_count,
+            });
+            for child in &node.children {
+                visit(child, Some(node.id), depth + 1, out);
+            }
+        }
+
+        let mut out = Vec::with_capacity(self.node_count());
+        visit(self, None, 0, &mut out);
+        out
+    }
+}
+
+/// Turn free-text search input into a `to_tsquery` expression matching all words.
+///
+/// Returns `None` if the input is empty or contains anything other than letters,
+/// digits and whitespace, since tsquery operators (`&`, `|`, `!`, `:`, parentheses,
+/// quotes) would make `to_tsquery` fail on arbitrary user input.
+pub fn tsquery_from_search(search: &str) -> Option<String> {
+    let words: Vec<&str> = search.split_whitespace().collect();
+    if words.is_empty()
+        || !words
+            .iter()
+            .all(|word| word.chars().all(char::is_alphanumeric))
+    {
+        return None;
+    }
+    Some(words.join(" & "))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +276,7 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             created_by: Uuid::new_v4(),
+            position: Some(2),
         }
     }
 
@@ -94,6 +291,7 @@ mod tests {
         assert_eq!(response.name, container.name);
         assert_eq!(response.description, container.description);
         assert_eq!(response.label_id, container.label_id);
+        assert_eq!(response.position, container.position);
         assert_eq!(response.created_at, container.created_at);
         assert_eq!(response.updated_at, container.updated_at);
     }
@@ -122,6 +320,7 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             created_by: Uuid::new_v4(),
+            position: None,
         };
 
         let response: ContainerResponse = container.clone().into();
@@ -181,4 +380,103 @@ mod tests {
         assert_eq!(request.shelf_id, Some(shelf_id));
         assert_eq!(request.parent_container_id, Some(parent_id));
     }
+
+    #[test]
+    fn test_tsquery_from_search() {
+        assert_eq!(tsquery_from_search("garage"), Some("garage".to_string()));
+        assert_eq!(
+            tsquery_from_search("  christmas   lights "),
+            Some("christmas & lights".to_string())
+        );
+        assert_eq!(tsquery_from_search("box2"), Some("box2".to_string()));
+    }
+
+    #[test]
+    fn test_tsquery_from_search_rejects_operators() {
+        assert_eq!(tsquery_from_search(""), None);
+        assert_eq!(tsquery_from_search("   "), None);
+        assert_eq!(tsquery_from_search("tools & hardware"), None);
+        assert_eq!(tsquery_from_search("bin:*"), None);
+        assert_eq!(tsquery_from_search("(spare)"), None);
+        assert_eq!(tsquery_from_search("kid's toys"), None);
+    }
+
+    fn subtree_row(
+        id: Uuid,
+        parent: Option<Uuid>,
+        name: &str,
+        item_count: i32,
+    ) -> ContainerSubtreeRow {
+        ContainerSubtreeRow {
+            id,
+            parent_container_id: parent,
+            name: name.to_string(),
+            item_count,
+        }
+    }
+
+    #[test]
+    fn test_container_tree_build_sums_item_counts() {
+        let (root, a, b, a1) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let rows = vec![
+            subtree_row(root, Some(Uuid::new_v4()), "Tote", 1),
+            subtree_row(b, Some(root), "Bag B", 0),
+            subtree_row(a, Some(root), "Box A", 2),
+            subtree_row(a1, Some(a), "Pouch", 4),
+        ];
+
+        let tree = ContainerTreeNode::build(root, &rows).unwrap();
+
+        assert_eq!(tree.total_item_count, 7);
+        assert_eq!(tree.node_count(), 4);
+        let names: Vec<&str> = tree.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Bag B", "Box A"]);
+        assert_eq!(tree.children[1].total_item_count, 6);
+        assert_eq!(tree.children[1].children[0].id, a1);
+        assert!(tree.children[0].children.is_empty());
+    }
+
+    #[test]
+    fn test_container_tree_build_without_root() {
+        let rows = vec![subtree_row(Uuid::new_v4(), None, "Other", 0)];
+        assert_eq!(ContainerTreeNode::build(Uuid::new_v4(), &rows), None);
+    }
+
+    #[test]
+    fn test_container_tree_flatten_is_depth_first() {
+        let (root, a, b, a1) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+        let rows = vec![
+            subtree_row(root, None, "Tote", 0),
+            subtree_row(a, Some(root), "Box A", 1),
+            subtree_row(b, Some(root), "Bag B", 0),
+            subtree_row(a1, Some(a), "Pouch", 3),
+        ];
+
+        let flat = ContainerTreeNode::build(root, &rows).unwrap().flatten();
+
+        let order: Vec<(Uuid, Option<Uuid>, i32)> = flat
+            .iter()
+            .map(|node| (node.id, node.parent_container_id, node.depth))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (root, None, 0),
+                (a, Some(root), 1),
+                (a1, Some(a), 2),
+                (b, Some(root), 1)
+            ]
+        );
+        assert_eq!(flat[1].total_item_count, 4);
+    }
 }
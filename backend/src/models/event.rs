@@ -0,0 +1,19 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A recorded write to the inventory, streamed to `GET /api/events` subscribers
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct InventoryEvent {
+    /// The audit log action, e.g. `CREATE`, `UPDATE` or `MOVE`
+    pub event_type: String,
+    /// e.g. `item` or `container`
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    /// Household of the user who made the write; subscribers only see their own
+    pub household_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::str::FromStr;
+use typeshare::typeshare;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{Container, EntityTag, Item, Photo, Room, Shelf, ShelvingUnit, Tag};
+
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportStatus {
+    Pending,
+    Complete,
+    Failed,
+}
+
+impl ExportStatus {
+    /// Value stored in `exports.status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportStatus::Pending => "pending",
+            ExportStatus::Complete => "complete",
+            ExportStatus::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for ExportStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(ExportStatus::Pending),
+            "complete" => Ok(ExportStatus::Complete),
+            "failed" => Ok(ExportStatus::Failed),
+            other => Err(format!("Unknown export status: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct Export {
+    pub id: Uuid,
+    pub status: String,
+    pub download_url: Option<String>,
+    pub download_url_expires_at: Option<DateTime<Utc>>,
+    pub photo_count: Option<i32>,
+    pub error: Option<String>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExportResponse {
+    pub id: Uuid,
+    pub status: ExportStatus,
+    /// Presigned ZIP download, set once the export is complete
+    pub download_url: Option<String>,
+    pub download_url_expires_at: Option<DateTime<Utc>>,
+    pub photo_count: Option<i32>,
+    /// Why the export failed
+    pub error: Option<String>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<Export> for ExportResponse {
+    fn from(export: Export) -> Self {
+        let status = export.status.parse().unwrap_or_else(|e| {
+            tracing::warn!("Export {} has invalid status: {}", export.id, e);
+            ExportStatus::Failed
+        });
+
+        Self {
+            id: export.id,
+            status,
+            download_url: export.download_url,
+            download_url_expires_at: export.download_url_expires_at,
+            photo_count: export.photo_count,
+            error: export.error,
+            created_by: export.created_by,
+            created_at: export.created_at,
+            completed_at: export.completed_at,
+        }
+    }
+}
+
+/// Contents of `inventory.json` in an export archive. Trashed items are left out.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InventorySnapshot {
+    pub exported_at: DateTime<Utc>,
+    pub rooms: Vec<Room>,
+    pub shelving_units: Vec<ShelvingUnit>,
+    pub shelves: Vec<Shelf>,
+    pub containers: Vec<Container>,
+    pub items: Vec<Item>,
+    pub tags: Vec<Tag>,
+    pub entity_tags: Vec<EntityTag>,
+    /// Photo metadata; each file is in the archive at `photos/<s3_key>`
+    pub photos: Vec<Photo>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_status_round_trips() {
+        for status in [
+            ExportStatus::Pending,
+            ExportStatus::Complete,
+            ExportStatus::Failed,
+        ] {
+            assert_eq!(status.as_str().parse::<ExportStatus>(), Ok(status));
+            assert_eq!(
+                serde_json::to_value(status).unwrap(),
+                serde_json::json!(status.as_str())
+            );
+        }
+        assert!("done".parse::<ExportStatus>().is_err());
+    }
+}
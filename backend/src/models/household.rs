@@ -0,0 +1,135 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use std::str::FromStr;
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// The household everything created before households existed was moved into (see
+/// the create_households migration). Only the migration refers to it by value.
+#[allow(dead_code)]
+pub const DEFAULT_HOUSEHOLD_ID: Uuid = Uuid::from_u128(1);
+
+/// How long an invite link can be used
+pub const INVITE_TTL_DAYS: i64 = 7;
+
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Household {
+    pub id: Uuid,
+    pub name: String,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A user's place in a household
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HouseholdRole {
+    /// Can invite others
+    Owner,
+    Member,
+}
+
+impl HouseholdRole {
+    /// Value stored in `household_memberships.role`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HouseholdRole::Owner => "owner",
+            HouseholdRole::Member => "member",
+        }
+    }
+}
+
+impl FromStr for HouseholdRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "owner" => Ok(HouseholdRole::Owner),
+            "member" => Ok(HouseholdRole::Member),
+            other => Err(format!("Unknown household role: {}", other)),
+        }
+    }
+}
+
+/// Response of `POST /api/households/:id/invite`
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InviteResponse {
+    pub household_id: Uuid,
+    /// Pass to `GET /api/households/join?token=`. Only shown once.
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct JoinHouseholdQuery {
+    pub token: String,
+}
+
+/// Response of `GET /api/households/join`
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JoinHouseholdResponse {
+    pub household_id: Uuid,
+    pub role: HouseholdRole,
+}
+
+/// A fresh invite token (64 hex characters) and when it stops working
+pub fn new_invite_token(now: DateTime<Utc>) -> (String, DateTime<Utc>) {
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    (token, now + Duration::days(INVITE_TTL_DAYS))
+}
+
+/// Invites are looked up by the SHA-256 of their token, so a leaked table can't be
+/// used to join
+pub fn hash_invite_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.trim().as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_invite_token() {
+        let now = Utc::now();
+        let (token, expires_at) = new_invite_token(now);
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(expires_at, now + Duration::days(INVITE_TTL_DAYS));
+        assert_ne!(token, new_invite_token(now).0);
+    }
+
+    #[test]
+    fn test_hash_invite_token() {
+        let hash = hash_invite_token("abc");
+        assert_eq!(
+            hash,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(hash_invite_token(" abc\n"), hash);
+        assert_ne!(hash_invite_token("abd"), hash);
+    }
+
+    #[test]
+    fn test_household_role_round_trip() {
+        for role in [HouseholdRole::Owner, HouseholdRole::Member] {
+            assert_eq!(role.as_str().parse::<HouseholdRole>(), Ok(role));
+        }
+        assert!("admin".parse::<HouseholdRole>().is_err());
+    }
+
+    #[test]
+    fn test_default_household_id_matches_migration() {
+        assert_eq!(
+            DEFAULT_HOUSEHOLD_ID.to_string(),
+            "00000000-0000-0000-0000-000000000001"
+        );
+    }
+}
@@ -0,0 +1,332 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::models::{purchase_price_valid, ItemResponse};
+
+/// A single entity from Home Assistant's `GET /api/states` response
+#[typeshare]
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct HomeAssistantState {
+    pub entity_id: String,
+    #[allow(dead_code)] // Part of the payload; imports only need the entity's attributes
+    pub state: String,
+    #[serde(default)]
+    pub attributes: HomeAssistantAttributes,
+    /// Not part of the states API itself; exports that join the area registry put it here
+    pub area_id: Option<String>,
+}
+
+#[typeshare]
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct HomeAssistantAttributes {
+    pub friendly_name: Option<String>,
+    pub area_id: Option<String>,
+}
+
+impl HomeAssistantState {
+    /// The entity domain, e.g. `sensor` for `sensor.kitchen_temperature`
+    pub fn domain(&self) -> Option<&str> {
+        match self.entity_id.split_once('.') {
+            Some((domain, object_id)) if !domain.is_empty() && !object_id.is_empty() => {
+                Some(domain)
+            }
+            _ => None,
+        }
+    }
+
+    /// Item name: the friendly name if set, otherwise the entity ID
+    pub fn item_name(&self) -> String {
+        self.attributes
+            .friendly_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&self.entity_id)
+            .to_string()
+    }
+
+    /// Area ID from the top level or the attributes, whichever is present
+    pub fn area_id(&self) -> Option<&str> {
+        self.area_id
+            .as_deref()
+            .or(self.attributes.area_id.as_deref())
+            .filter(|area| !area.is_empty())
+    }
+}
+
+/// Convert a Home Assistant area ID into a room name (`living_room` -> `Living Room`)
+pub fn area_room_name(area_id: &str) -> String {
+    area_id
+        .split(['_', ' '])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[typeshare]
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct ImportResult {
+    pub rooms_created: i32,
+    pub items_created: i32,
+    pub tags_created: i32,
+    pub errors: Vec<String>,
+}
+
+/// Most rows accepted in one CSV import
+pub const MAX_CSV_IMPORT_ROWS: usize = 1000;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CsvImportQuery {
+    /// Stop at the first bad row and import nothing
+    #[serde(default)]
+    pub fail_fast: bool,
+}
+
+/// One data row of an item CSV. Empty cells read as `None`.
+#[derive(Debug, Deserialize)]
+pub struct CsvItemRow {
+    pub name: String,
+    pub description: Option<String>,
+    pub barcode: Option<String>,
+    pub barcode_type: Option<String>,
+    pub location_type: String,
+    pub location_id: String,
+    pub acquired_date: Option<String>,
+    pub purchase_price: Option<String>,
+}
+
+/// Where a CSV row's item goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CsvLocation {
+    Shelf(Uuid),
+    Container(Uuid),
+}
+
+/// A CSV row that passed validation, apart from checks against the database
+#[derive(Debug, PartialEq)]
+pub struct CsvItem {
+    pub name: String,
+    pub description: Option<String>,
+    pub barcode: Option<String>,
+    pub barcode_type: Option<String>,
+    pub location: CsvLocation,
+    pub acquired_date: Option<NaiveDate>,
+    pub purchase_price: Option<Decimal>,
+}
+
+impl CsvItemRow {
+    /// Check the row's fields, returning the message to report for the first bad one
+    pub fn validate(self) -> Result<CsvItem, String> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            return Err("name is required".to_string());
+        }
+
+        let location_id = Uuid::parse_str(self.location_id.trim())
+            .map_err(|_| format!("location_id {:?} is not a UUID", self.location_id))?;
+        let location = match self.location_type.trim() {
+            "shelf" => CsvLocation::Shelf(location_id),
+            "container" => CsvLocation::Container(location_id),
+            other => {
+                return Err(format!(
+                    "location_type must be shelf or container, not {:?}",
+                    other
+                ))
+            }
+        };
+
+        let acquired_date = self
+            .acquired_date
+            .map(|date| {
+                NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").map_err(|_| {
+                    format!(
+                        "acquired_date {:?} is not an ISO 8601 date (YYYY-MM-DD)",
+                        date
+                    )
+                })
+            })
+            .transpose()?;
+
+        let purchase_price = self
+            .purchase_price
+            .map(|price| {
+                Decimal::from_str(price.trim())
+                    .ok()
+                    .filter(|price| purchase_price_valid(Some(*price)))
+                    .ok_or_else(|| format!("purchase_price {:?} is not a valid amount", price))
+            })
+            .transpose()?;
+
+        Ok(CsvItem {
+            name,
+            description: self.description,
+            barcode: self.barcode,
+            barcode_type: self.barcode_type,
+            location,
+            acquired_date,
+            purchase_price,
+        })
+    }
+}
+
+/// A CSV row that wasn't imported
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CsvImportError {
+    /// Line in the file, counting the header as line 1
+    #[typeshare(serialized_as = "number")]
+    pub row: usize,
+    pub message: String,
+}
+
+/// Result of a CSV import, returned with 207 Multi-Status since rows succeed or fail
+/// individually. With `fail_fast`, `imported` is empty whenever `errors` isn't.
+#[typeshare]
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct CsvImportResponse {
+    pub imported: Vec<ItemResponse>,
+    pub errors: Vec<CsvImportError>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn csv_row() -> CsvItemRow {
+        CsvItemRow {
+            name: " Drill ".to_string(),
+            description: None,
+            barcode: Some("12345".to_string()),
+            barcode_type: None,
+            location_type: "shelf".to_string(),
+            location_id: Uuid::nil().to_string(),
+            acquired_date: Some("2024-03-01".to_string()),
+            purchase_price: Some("89.99".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_csv_row_validate() {
+        let item = csv_row().validate().unwrap();
+        assert_eq!(item.name, "Drill");
+        assert_eq!(item.location, CsvLocation::Shelf(Uuid::nil()));
+        assert_eq!(item.acquired_date, NaiveDate::from_ymd_opt(2024, 3, 1));
+        assert_eq!(item.purchase_price, Some(Decimal::new(8999, 2)));
+    }
+
+    #[test]
+    fn test_csv_row_validate_rejects_bad_fields() {
+        let mut row = csv_row();
+        row.location_type = "room".to_string();
+        assert!(row.validate().unwrap_err().contains("location_type"));
+
+        let mut row = csv_row();
+        row.location_id = "not-a-uuid".to_string();
+        assert!(row.validate().unwrap_err().contains("location_id"));
+
+        let mut row = csv_row();
+        row.acquired_date = Some("03/01/2024".to_string());
+        assert!(row.validate().unwrap_err().contains("acquired_date"));
+
+        let mut row = csv_row();
+        row.purchase_price = Some("-5".to_string());
+        assert!(row.validate().unwrap_err().contains("purchase_price"));
+
+        let mut row = csv_row();
+        row.name = "  ".to_string();
+        assert!(row.validate().is_err());
+    }
+
+    #[test]
+    fn test_csv_rows_deserialize_empty_cells_as_none() {
+        let data = "name,description,barcode,barcode_type,location_type,location_id,acquired_date,purchase_price\n\
+                    Drill,,,,container,00000000-0000-0000-0000-000000000000,,\n";
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+        let row: CsvItemRow = reader.deserialize().next().unwrap().unwrap();
+
+        assert_eq!(row.description, None);
+        assert_eq!(row.purchase_price, None);
+        assert_eq!(
+            row.validate().unwrap().location,
+            CsvLocation::Container(Uuid::nil())
+        );
+    }
+
+    fn state(entity_id: &str, friendly_name: Option<&str>) -> HomeAssistantState {
+        HomeAssistantState {
+            entity_id: entity_id.to_string(),
+            state: "on".to_string(),
+            attributes: HomeAssistantAttributes {
+                friendly_name: friendly_name.map(String::from),
+                area_id: None,
+            },
+            area_id: None,
+        }
+    }
+
+    #[test]
+    fn test_domain() {
+        assert_eq!(state("sensor.kitchen_temp", None).domain(), Some("sensor"));
+        assert_eq!(state("switch.porch", None).domain(), Some("switch"));
+        assert_eq!(state("invalid", None).domain(), None);
+        assert_eq!(state(".missing_domain", None).domain(), None);
+        assert_eq!(state("light.", None).domain(), None);
+    }
+
+    #[test]
+    fn test_item_name_prefers_friendly_name() {
+        assert_eq!(
+            state("light.porch", Some("Porch Light")).item_name(),
+            "Porch Light"
+        );
+        assert_eq!(state("light.porch", None).item_name(), "light.porch");
+        assert_eq!(state("light.porch", Some("  ")).item_name(), "light.porch");
+    }
+
+    #[test]
+    fn test_area_id_falls_back_to_attributes() {
+        let mut s = state("light.porch", None);
+        assert_eq!(s.area_id(), None);
+
+        s.attributes.area_id = Some("porch".to_string());
+        assert_eq!(s.area_id(), Some("porch"));
+
+        s.area_id = Some("garage".to_string());
+        assert_eq!(s.area_id(), Some("garage"));
+    }
+
+    #[test]
+    fn test_deserialize_states_api_format() {
+        let json = r#"{
+            "entity_id": "sensor.kitchen_temperature",
+            "state": "21.5",
+            "attributes": {"friendly_name": "Kitchen Temperature", "unit_of_measurement": "°C"},
+            "last_changed": "2024-01-01T00:00:00+00:00"
+        }"#;
+
+        let s: HomeAssistantState = serde_json::from_str(json).unwrap();
+        assert_eq!(s.item_name(), "Kitchen Temperature");
+        assert_eq!(s.domain(), Some("sensor"));
+        assert_eq!(s.area_id(), None);
+    }
+
+    #[test]
+    fn test_area_room_name() {
+        assert_eq!(area_room_name("living_room"), "Living Room");
+        assert_eq!(area_room_name("garage"), "Garage");
+        assert_eq!(area_room_name("kids__bedroom"), "Kids Bedroom");
+    }
+}
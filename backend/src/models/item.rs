@@ -1,11 +1,17 @@
 use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::postgres::{PgTypeInfo, PgValueRef};
+use sqlx::{FromRow, Postgres};
+use std::str::FromStr;
 use typeshare::typeshare;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::models::{Clearable, NullAsFalse, NullAsOne, TagResponse};
+
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Item {
     pub id: Uuid,
     pub shelf_id: Option<Uuid>,
@@ -20,13 +26,27 @@ pub struct Item {
     pub product_link: Option<String>,
     pub belongs_to_user_id: Option<Uuid>,
     pub acquired_date: Option<NaiveDate>,
+    #[sqlx(try_from = "NullAsOne")]
+    pub quantity: i32,
+    pub min_quantity: Option<i32>,
+    #[typeshare(serialized_as = "String")]
+    pub purchase_price: Option<Decimal>,
+    /// ISO 4217 code of `purchase_price`
+    pub currency: Option<String>,
+    pub condition: Option<Condition>,
+    pub warranty_expires_on: Option<NaiveDate>,
+    /// Whether the item shows up in `GET /api/items/expiring`
+    #[sqlx(try_from = "NullAsFalse")]
+    pub reminder_enabled: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: Uuid,
+    /// Set while the item is in the trash
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateItemRequest {
     pub shelf_id: Option<Uuid>,
     pub container_id: Option<Uuid>,
@@ -39,26 +59,89 @@ pub struct CreateItemRequest {
     pub product_link: Option<String>,
     pub belongs_to_user_id: Option<Uuid>,
     pub acquired_date: Option<NaiveDate>,
+    /// Defaults to 1
+    pub quantity: Option<i32>,
+    /// Items at or below this quantity are reported as low stock
+    pub min_quantity: Option<i32>,
+    /// Sent as a decimal string, e.g. "19.99"
+    #[typeshare(serialized_as = "String")]
+    pub purchase_price: Option<Decimal>,
+    /// ISO 4217 code; defaults to USD when a purchase price is given
+    pub currency: Option<String>,
+    /// One of `new`, `like_new`, `good`, `fair`, `poor`
+    pub condition: Option<String>,
+    pub warranty_expires_on: Option<NaiveDate>,
+    /// Defaults to false
+    pub reminder_enabled: Option<bool>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+/// Body of `PATCH /api/items/:id`. Omitted fields are left unchanged; optional
+/// fields sent as explicit `null` are cleared.
 pub struct UpdateItemRequest {
     pub name: Option<String>,
-    pub description: Option<String>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "string | null"))]
+    #[schema(value_type = Option<String>)]
+    pub description: Clearable<String>,
     pub shelf_id: Option<Uuid>,
     pub container_id: Option<Uuid>,
-    pub barcode: Option<String>,
-    pub barcode_type: Option<String>,
-    pub product_manual_s3_key: Option<String>,
-    pub receipt_s3_key: Option<String>,
-    pub product_link: Option<String>,
-    pub belongs_to_user_id: Option<Uuid>,
-    pub acquired_date: Option<NaiveDate>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "string | null"))]
+    #[schema(value_type = Option<String>)]
+    pub barcode: Clearable<String>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "string | null"))]
+    #[schema(value_type = Option<String>)]
+    pub barcode_type: Clearable<String>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "string | null"))]
+    #[schema(value_type = Option<String>)]
+    pub product_manual_s3_key: Clearable<String>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "string | null"))]
+    #[schema(value_type = Option<String>)]
+    pub receipt_s3_key: Clearable<String>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "string | null"))]
+    #[schema(value_type = Option<String>)]
+    pub product_link: Clearable<String>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "string | null"))]
+    #[schema(value_type = Option<Uuid>)]
+    pub belongs_to_user_id: Clearable<Uuid>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "NaiveDate | null"))]
+    #[schema(value_type = Option<NaiveDate>)]
+    pub acquired_date: Clearable<NaiveDate>,
+    pub quantity: Option<i32>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "number | null"))]
+    #[schema(value_type = Option<i32>)]
+    pub min_quantity: Clearable<i32>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "string | null"))]
+    #[schema(value_type = Option<Decimal>)]
+    pub purchase_price: Clearable<Decimal>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "string | null"))]
+    #[schema(value_type = Option<String>)]
+    pub currency: Clearable<String>,
+    #[serde(default)]
+    #[typeshare(typescript(type = "Condition | null"))]
+    #[schema(value_type = Option<String>)]
+    pub condition: Clearable<String>,
+    /// Can't be set to a date in the past
+    #[serde(default)]
+    #[typeshare(typescript(type = "NaiveDate | null"))]
+    #[schema(value_type = Option<NaiveDate>)]
+    pub warranty_expires_on: Clearable<NaiveDate>,
+    pub reminder_enabled: Option<bool>,
 }
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ItemResponse {
     pub id: Uuid,
     pub shelf_id: Option<Uuid>,
@@ -73,12 +156,33 @@ pub struct ItemResponse {
     pub product_link: Option<String>,
     pub belongs_to_user_id: Option<Uuid>,
     pub acquired_date: Option<NaiveDate>,
+    pub quantity: i32,
+    pub min_quantity: Option<i32>,
+    #[typeshare(serialized_as = "String")]
+    pub purchase_price: Option<Decimal>,
+    /// ISO 4217 code of `purchase_price`
+    pub currency: Option<String>,
+    pub condition: Option<Condition>,
+    pub warranty_expires_on: Option<NaiveDate>,
+    /// Whether the item shows up in `GET /api/items/expiring`
+    pub reminder_enabled: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set while the item is in the trash
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Only with `?include_photos=true` on list endpoints
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_count: Option<i32>,
+    /// Presigned URL of the earliest photo, only with `?include_photos=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_photo_url: Option<String>,
+    /// Only with `?include_tags=true`; empty rather than absent when untagged
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<TagResponse>>,
 }
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct PublicItemResponse {
     pub id: Uuid,
     pub name: String,
@@ -86,18 +190,265 @@ pub struct PublicItemResponse {
     pub product_link: Option<String>,
 }
 
+/// Most items returned by `GET /api/items/{id}/similar`
+pub const MAX_SIMILAR_ITEMS: i64 = 10;
+
+/// An item recommended alongside another for sharing its tags or, when that one is
+/// untagged, its container or shelf. Public, so no more than the public item view.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SimilarItemResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub product_link: Option<String>,
+    /// Tags it has in common with the item asked about; 0 for location matches
+    pub shared_tag_count: i32,
+}
+
+/// Whether a barcode is taken, for checking a scan before creating an item
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BarcodeCheckResponse {
+    pub exists: bool,
+    /// The item with the barcode, if it is in the caller's household
+    pub item_id: Option<Uuid>,
+}
+
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AdjustQuantityRequest {
+    /// Amount to add; negative to take items out
+    pub delta: i32,
+}
+
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TransferItemRequest {
+    pub new_owner_id: Uuid,
+}
+
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BulkCreateItemsRequest {
     pub items: Vec<CreateItemRequest>,
 }
 
 #[typeshare]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BulkCreateItemsResponse {
     pub items: Vec<ItemResponse>,
 }
 
+/// Names more similar than this to a new item's mark it as a possible duplicate
+pub const DUPLICATE_SIMILARITY_THRESHOLD: f32 = 0.7;
+
+/// A live item named like one being created in the same location, returned in the
+/// `details.similar_items` of a `POSSIBLE_DUPLICATE` error
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct SimilarItem {
+    pub id: Uuid,
+    pub name: String,
+    /// pg_trgm similarity of the names, from 0 to 1
+    pub similarity: f32,
+}
+
+// Similarities are never NaN, so ApiError can stay Eq
+impl Eq for SimilarItem {}
+
+/// Similarity of two names as pg_trgm's `similarity()` computes it: the trigrams
+/// the names share over all their distinct trigrams. Used where the database
+/// doesn't have pg_trgm (DSQL).
+pub fn name_similarity(a: &str, b: &str) -> f32 {
+    let (a, b) = (name_trigrams(a), name_trigrams(b));
+    let shared = a.intersection(&b).count();
+    let total = a.len() + b.len() - shared;
+    if total == 0 {
+        return 0.0;
+    }
+    shared as f32 / total as f32
+}
+
+/// pg_trgm's trigrams: each lowercased word, padded with two spaces in front and
+/// one behind. Anything but letters and digits separates words.
+fn name_trigrams(name: &str) -> std::collections::HashSet<[char; 3]> {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .flat_map(|word| {
+            let padded: Vec<char> = "  ".chars().chain(word.chars()).chain([' ']).collect();
+            padded
+                .windows(3)
+                .map(|w| [w[0], w[1], w[2]])
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Most items a single bulk delete may name
+pub const MAX_BULK_DELETE_ITEMS: usize = 500;
+
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkDeleteItemsRequest {
+    pub ids: Vec<Uuid>,
+}
+
+/// Best-effort result: each requested ID lands in exactly one list
+#[typeshare]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BulkDeleteItemsResponse {
+    pub deleted: Vec<Uuid>,
+    pub not_found: Vec<Uuid>,
+    /// Items that exist but that the user may not delete
+    pub forbidden: Vec<Uuid>,
+}
+
+impl BulkDeleteItemsResponse {
+    /// Split the requested IDs into those that were deleted, those the user may not
+    /// delete, and the rest, keeping request order and dropping duplicates
+    pub fn from_requested(requested: &[Uuid], deleted: &[Uuid], forbidden: &[Uuid]) -> Self {
+        let mut seen = std::collections::HashSet::new();
+        let mut response = Self {
+            deleted: Vec::new(),
+            not_found: Vec::new(),
+            forbidden: Vec::new(),
+        };
+        for id in requested.iter().copied().filter(|id| seen.insert(*id)) {
+            if deleted.contains(&id) {
+                response.deleted.push(id);
+            } else if forbidden.contains(&id) {
+                response.forbidden.push(id);
+            } else {
+                response.not_found.push(id);
+            }
+        }
+        response
+    }
+}
+
+/// Currency assumed for a purchase price sent without one
+pub const DEFAULT_CURRENCY: &str = "USD";
+
+/// Largest price a NUMERIC(12, 2) column holds, 9999999999.99 (`Decimal::new` isn't
+/// const, so it is spelled out as the 96-bit mantissa 999_999_999_999 and a scale of 2)
+const MAX_PURCHASE_PRICE: Decimal = Decimal::from_parts(0xD4A5_0FFF, 0xE8, 0, false, 2);
+
+/// Prices can't be negative or overflow the column
+pub fn purchase_price_valid(price: Option<Decimal>) -> bool {
+    price.is_none_or(|p| !p.is_sign_negative() && p <= MAX_PURCHASE_PRICE)
+}
+
+/// Normalize an ISO 4217 code to upper case, or `None` if it isn't three letters
+pub fn parse_currency(code: &str) -> Option<String> {
+    let code = code.trim();
+    (code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()))
+        .then(|| code.to_ascii_uppercase())
+}
+
+/// Physical condition of an item, stored as its snake_case name
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+    New,
+    LikeNew,
+    Good,
+    Fair,
+    Poor,
+}
+
+impl Condition {
+    pub const ALL: [Condition; 5] = [
+        Condition::New,
+        Condition::LikeNew,
+        Condition::Good,
+        Condition::Fair,
+        Condition::Poor,
+    ];
+
+    /// Value stored in `items.condition`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Condition::New => "new",
+            Condition::LikeNew => "like_new",
+            Condition::Good => "good",
+            Condition::Fair => "fair",
+            Condition::Poor => "poor",
+        }
+    }
+}
+
+impl FromStr for Condition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Condition::ALL
+            .into_iter()
+            .find(|condition| condition.as_str() == s)
+            .ok_or_else(|| format!("Unknown item condition: {}", s))
+    }
+}
+
+impl sqlx::Type<Postgres> for Condition {
+    fn type_info() -> PgTypeInfo {
+        <String as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <String as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+/// Read from the VARCHAR column; a value the CHECK constraint should have kept out
+/// is a decode error rather than a silent `None`
+impl<'r> sqlx::Decode<'r, Postgres> for Condition {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let value = <&str as sqlx::Decode<Postgres>>::decode(value)?;
+        Ok(value.parse()?)
+    }
+}
+
+/// Default and largest look-ahead of `GET /api/items/expiring`
+pub const DEFAULT_EXPIRING_WITHIN_DAYS: i32 = 30;
+pub const MAX_EXPIRING_WITHIN_DAYS: i32 = 3650;
+
+/// A warranty expiry being set must not already have passed; expiring today is fine
+pub fn warranty_expiry_valid(warranty_expires_on: Option<NaiveDate>, today: NaiveDate) -> bool {
+    warranty_expires_on.is_none_or(|date| date >= today)
+}
+
+/// Quantities can't be negative
+pub fn quantities_valid(quantity: Option<i32>, min_quantity: Option<i32>) -> bool {
+    quantity.is_none_or(|q| q >= 0) && min_quantity.is_none_or(|q| q >= 0)
+}
+
+impl Item {
+    /// Whether the quantity has dropped to the configured minimum
+    pub fn is_low_stock(&self) -> bool {
+        self.min_quantity
+            .is_some_and(|min_quantity| self.quantity <= min_quantity)
+    }
+
+    /// Whether `user_id` may transfer this item: its owner, or its creator if it has no owner
+    pub fn can_transfer(&self, user_id: Uuid) -> bool {
+        match self.belongs_to_user_id {
+            Some(owner_id) => owner_id == user_id,
+            None => self.created_by == user_id,
+        }
+    }
+
+    /// Whether `user_id` may change `belongs_to_user_id`: the item's owner or its creator
+    pub fn can_change_owner(&self, user_id: Uuid) -> bool {
+        self.belongs_to_user_id == Some(user_id) || self.created_by == user_id
+    }
+
+    /// Whether `user_id` may delete this item: only its creator
+    pub fn can_delete(&self, user_id: Uuid) -> bool {
+        self.created_by == user_id
+    }
+}
+
 impl From<Item> for ItemResponse {
     fn from(item: Item) -> Self {
         Self {
@@ -114,8 +465,19 @@ impl From<Item> for ItemResponse {
             product_link: item.product_link,
             belongs_to_user_id: item.belongs_to_user_id,
             acquired_date: item.acquired_date,
+            quantity: item.quantity,
+            min_quantity: item.min_quantity,
+            purchase_price: item.purchase_price,
+            currency: item.currency,
+            condition: item.condition,
+            warranty_expires_on: item.warranty_expires_on,
+            reminder_enabled: item.reminder_enabled,
             created_at: item.created_at,
             updated_at: item.updated_at,
+            deleted_at: item.deleted_at,
+            photo_count: None,
+            primary_photo_url: None,
+            tags: None,
         }
     }
 }
@@ -140,9 +502,17 @@ mod tests {
             product_link: None,
             belongs_to_user_id: None,
             acquired_date: None,
+            quantity: 1,
+            min_quantity: None,
+            purchase_price: None,
+            currency: None,
+            condition: None,
+            warranty_expires_on: None,
+            reminder_enabled: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             created_by: Uuid::new_v4(),
+            deleted_at: None,
         }
     }
 
@@ -192,9 +562,17 @@ mod tests {
             product_link: None,
             belongs_to_user_id: None,
             acquired_date: None,
+            quantity: 1,
+            min_quantity: None,
+            purchase_price: None,
+            currency: None,
+            condition: None,
+            warranty_expires_on: None,
+            reminder_enabled: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             created_by: Uuid::new_v4(),
+            deleted_at: None,
         };
 
         let response: ItemResponse = item.clone().into();
@@ -228,7 +606,45 @@ mod tests {
         let request: UpdateItemRequest = serde_json::from_str(json).unwrap();
 
         assert_eq!(request.name, Some("Updated Item Name".to_string()));
-        assert_eq!(request.description, None);
+        assert_eq!(request.description, Clearable::Keep);
+    }
+
+    #[test]
+    fn test_update_item_request_explicit_null_clears() {
+        let json = r#"{"barcode": null, "barcode_type": null, "description": "Kept"}"#;
+        let request: UpdateItemRequest = serde_json::from_str(json).unwrap();
+
+        assert_eq!(request.barcode, Clearable::Clear);
+        assert_eq!(request.barcode_type, Clearable::Clear);
+        assert_eq!(request.description, Clearable::Set("Kept".to_string()));
+        assert_eq!(request.product_link, Clearable::Keep);
+        assert_eq!(request.acquired_date, Clearable::Keep);
+    }
+
+    #[test]
+    fn test_warranty_expiry_valid() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 15).unwrap();
+
+        assert!(warranty_expiry_valid(None, today));
+        assert!(warranty_expiry_valid(Some(today), today));
+        assert!(warranty_expiry_valid(
+            NaiveDate::from_ymd_opt(2028, 1, 1),
+            today
+        ));
+        assert!(!warranty_expiry_valid(today.pred_opt(), today));
+    }
+
+    #[test]
+    fn test_condition_round_trip() {
+        for condition in Condition::ALL {
+            assert_eq!(condition.as_str().parse::<Condition>(), Ok(condition));
+            assert_eq!(
+                serde_json::to_value(condition).unwrap(),
+                serde_json::json!(condition.as_str())
+            );
+        }
+        assert!("mint".parse::<Condition>().is_err());
+        assert!("Good".parse::<Condition>().is_err());
     }
 
     #[test]
@@ -247,6 +663,22 @@ mod tests {
         assert_eq!(request.items[1].name, "Item 2");
     }
 
+    #[test]
+    fn test_bulk_delete_response_from_requested() {
+        let (a, b, c, d) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+
+        let response = BulkDeleteItemsResponse::from_requested(&[a, b, c, d, a], &[c, a], &[d]);
+
+        assert_eq!(response.deleted, vec![a, c]);
+        assert_eq!(response.not_found, vec![b]);
+        assert_eq!(response.forbidden, vec![d]);
+    }
+
     #[test]
     fn test_bulk_create_items_response() {
         let items = vec![create_test_item(), create_test_item()];
@@ -281,9 +713,17 @@ mod tests {
             product_link: None,
             belongs_to_user_id: None,
             acquired_date: None,
+            quantity: 1,
+            min_quantity: None,
+            purchase_price: None,
+            currency: None,
+            condition: None,
+            warranty_expires_on: None,
+            reminder_enabled: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             created_by: Uuid::new_v4(),
+            deleted_at: None,
         };
 
         let item_in_container = Item {
@@ -300,9 +740,17 @@ mod tests {
             product_link: None,
             belongs_to_user_id: None,
             acquired_date: None,
+            quantity: 1,
+            min_quantity: None,
+            purchase_price: None,
+            currency: None,
+            condition: None,
+            warranty_expires_on: None,
+            reminder_enabled: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             created_by: Uuid::new_v4(),
+            deleted_at: None,
         };
 
         assert!(item_on_shelf.shelf_id.is_some());
@@ -311,4 +759,126 @@ mod tests {
         assert!(item_in_container.shelf_id.is_none());
         assert!(item_in_container.container_id.is_some());
     }
+
+    #[test]
+    fn test_can_transfer_owned_item() {
+        let owner = Uuid::new_v4();
+        let mut item = create_test_item();
+        item.belongs_to_user_id = Some(owner);
+
+        assert!(item.can_transfer(owner));
+        // Once owned, the creator no longer controls the item
+        assert!(!item.can_transfer(item.created_by));
+        assert!(!item.can_transfer(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn test_can_transfer_unowned_item() {
+        let item = create_test_item();
+
+        assert!(item.can_transfer(item.created_by));
+        assert!(!item.can_transfer(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn test_can_change_owner() {
+        let owner = Uuid::new_v4();
+        let mut item = create_test_item();
+        item.belongs_to_user_id = Some(owner);
+
+        assert!(item.can_change_owner(owner));
+        assert!(item.can_change_owner(item.created_by));
+        assert!(!item.can_change_owner(Uuid::new_v4()));
+
+        item.belongs_to_user_id = None;
+        assert!(item.can_change_owner(item.created_by));
+        assert!(!item.can_change_owner(owner));
+    }
+
+    #[test]
+    fn test_can_delete() {
+        let owner = Uuid::new_v4();
+        let mut item = create_test_item();
+        item.belongs_to_user_id = Some(owner);
+
+        assert!(item.can_delete(item.created_by));
+        assert!(!item.can_delete(owner));
+        assert!(!item.can_delete(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn test_is_low_stock() {
+        let mut item = create_test_item();
+        item.quantity = 2;
+        assert!(!item.is_low_stock());
+
+        item.min_quantity = Some(1);
+        assert!(!item.is_low_stock());
+
+        item.min_quantity = Some(2);
+        assert!(item.is_low_stock());
+
+        item.quantity = 0;
+        assert!(item.is_low_stock());
+    }
+
+    #[test]
+    fn test_quantities_valid() {
+        assert!(quantities_valid(None, None));
+        assert!(quantities_valid(Some(0), Some(0)));
+        assert!(quantities_valid(Some(5), None));
+        assert!(!quantities_valid(Some(-1), None));
+        assert!(!quantities_valid(None, Some(-1)));
+    }
+
+    #[test]
+    fn test_purchase_price_valid() {
+        assert!(purchase_price_valid(None));
+        assert!(purchase_price_valid(Some(Decimal::ZERO)));
+        assert!(purchase_price_valid(Some(Decimal::new(1999, 2))));
+        assert_eq!(MAX_PURCHASE_PRICE, Decimal::new(999_999_999_999, 2));
+        assert!(purchase_price_valid(Some(MAX_PURCHASE_PRICE)));
+        assert!(!purchase_price_valid(Some(Decimal::new(-1, 2))));
+        assert!(!purchase_price_valid(Some(Decimal::new(10_000_000_000, 0))));
+    }
+
+    #[test]
+    fn test_parse_currency() {
+        assert_eq!(parse_currency("USD"), Some("USD".to_string()));
+        assert_eq!(parse_currency(" eur "), Some("EUR".to_string()));
+        assert_eq!(parse_currency("US"), None);
+        assert_eq!(parse_currency("US1"), None);
+        assert_eq!(parse_currency("USDX"), None);
+    }
+
+    #[test]
+    fn test_purchase_price_deserializes_from_string() {
+        let request: UpdateItemRequest =
+            serde_json::from_str(r#"{"purchase_price": "19.99", "currency": null}"#).unwrap();
+        assert_eq!(
+            request.purchase_price,
+            Clearable::Set(Decimal::new(1999, 2))
+        );
+        assert_eq!(request.currency, Clearable::Clear);
+    }
+
+    #[test]
+    fn test_adjust_quantity_request_deserialization() {
+        let request: AdjustQuantityRequest = serde_json::from_str(r#"{"delta": -1}"#).unwrap();
+        assert_eq!(request.delta, -1);
+    }
+
+    #[test]
+    fn test_name_similarity_matches_pg_trgm() {
+        // pg_trgm: SELECT similarity('word', 'words') = 0.571429
+        assert!((name_similarity("word", "words") - 4.0 / 7.0).abs() < 1e-6);
+        assert_eq!(name_similarity("Claw Hammer", "claw-hammer"), 1.0);
+        assert_eq!(name_similarity("Tape measure", "Socket set"), 0.0);
+        assert_eq!(name_similarity("", "!!"), 0.0);
+        assert!(
+            name_similarity("Phillips screwdriver", "Phillips screwdriver set")
+                > DUPLICATE_SIMILARITY_THRESHOLD
+        );
+        assert!(name_similarity("Claw hammer", "Hammer") < DUPLICATE_SIMILARITY_THRESHOLD);
+    }
 }
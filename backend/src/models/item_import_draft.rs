@@ -2,12 +2,17 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use typeshare::typeshare;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::models::ItemResponse;
 
+/// A draft imports into either a container or a shelf, never both
+pub const DRAFT_LOCATION_REQUIRED: &str =
+    "Exactly one of container_id or shelf_id must be provided";
+
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ItemImportDraftItem {
     pub name: String,
     pub description: Option<String>,
@@ -15,7 +20,7 @@ pub struct ItemImportDraftItem {
     pub barcode_type: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct ItemImportDraft {
     pub id: Uuid,
     pub container_id: Option<Uuid>,
@@ -31,21 +36,31 @@ pub struct ItemImportDraft {
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateItemImportDraftRequest {
-    pub container_id: Uuid,
+    pub container_id: Option<Uuid>,
+    pub shelf_id: Option<Uuid>,
     pub items: Vec<ItemImportDraftItem>,
     pub source_photo_ids: Vec<Uuid>,
 }
 
+impl CreateItemImportDraftRequest {
+    pub fn validate_location(&self) -> Result<(), &'static str> {
+        match (self.container_id, self.shelf_id) {
+            (Some(_), None) | (None, Some(_)) => Ok(()),
+            _ => Err(DRAFT_LOCATION_REQUIRED),
+        }
+    }
+}
+
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateItemImportDraftRequest {
     pub items: Vec<ItemImportDraftItem>,
 }
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct LocationUpdateProposal {
     pub description: Option<String>,
     pub tags: Option<Vec<String>>,
@@ -56,7 +71,7 @@ pub struct LocationUpdateProposal {
 pub type ContainerUpdateProposal = LocationUpdateProposal;
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ItemImportDraftResponse {
     pub id: Uuid,
     pub container_id: Option<Uuid>,
@@ -71,18 +86,26 @@ pub struct ItemImportDraftResponse {
 }
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct CommitItemImportDraftResponse {
     pub draft: ItemImportDraftResponse,
     pub created_items: Vec<ItemResponse>,
 }
 
+/// Most photos sent to the vision service in a single analysis
+pub const MAX_ANALYZE_PHOTOS: usize = 5;
+
+/// Longest hint passed along to the vision service, in characters
+pub const MAX_ANALYZE_HINT_CHARS: usize = 500;
+
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AnalyzePhotoRequest {
     pub container_id: Option<Uuid>,
     pub shelf_id: Option<Uuid>,
+    /// 1 to 5 photos, analyzed together
     pub photo_ids: Vec<Uuid>,
+    /// Context for the vision service, e.g. "camping gear"; at most 500 characters
     pub hint: Option<String>,
 }
 
@@ -90,7 +113,100 @@ impl AnalyzePhotoRequest {
     pub fn validate_location(&self) -> Result<(), &'static str> {
         match (self.container_id, self.shelf_id) {
             (Some(_), None) | (None, Some(_)) => Ok(()),
-            _ => Err("Exactly one of container_id or shelf_id must be provided"),
+            _ => Err(DRAFT_LOCATION_REQUIRED),
+        }
+    }
+
+    pub fn validate_photo_count(&self) -> Result<(), &'static str> {
+        match self.photo_ids.len() {
+            0 => Err("At least one photo_id is required"),
+            n if n > MAX_ANALYZE_PHOTOS => Err("At most 5 photo_ids can be analyzed at once"),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn validate_hint(&self) -> Result<(), &'static str> {
+        match &self.hint {
+            Some(hint) if hint.chars().count() > MAX_ANALYZE_HINT_CHARS => {
+                Err("hint must be at most 500 characters")
+            }
+            _ => Ok(()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze_request(photo_count: usize) -> AnalyzePhotoRequest {
+        AnalyzePhotoRequest {
+            container_id: None,
+            shelf_id: Some(Uuid::new_v4()),
+            photo_ids: (0..photo_count).map(|_| Uuid::new_v4()).collect(),
+            hint: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_photo_count() {
+        assert!(analyze_request(0).validate_photo_count().is_err());
+        assert!(analyze_request(1).validate_photo_count().is_ok());
+        assert!(analyze_request(MAX_ANALYZE_PHOTOS)
+            .validate_photo_count()
+            .is_ok());
+        assert!(analyze_request(MAX_ANALYZE_PHOTOS + 1)
+            .validate_photo_count()
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_location() {
+        assert!(analyze_request(1).validate_location().is_ok());
+
+        let mut request = analyze_request(1);
+        request.container_id = Some(Uuid::new_v4());
+        assert!(request.validate_location().is_err());
+
+        request.shelf_id = None;
+        request.container_id = None;
+        assert!(request.validate_location().is_err());
+    }
+
+    #[test]
+    fn test_create_request_validate_location() {
+        let request = |container_id, shelf_id| CreateItemImportDraftRequest {
+            container_id,
+            shelf_id,
+            items: Vec::new(),
+            source_photo_ids: Vec::new(),
+        };
+
+        assert!(request(Some(Uuid::new_v4()), None)
+            .validate_location()
+            .is_ok());
+        assert!(request(None, Some(Uuid::new_v4()))
+            .validate_location()
+            .is_ok());
+        assert_eq!(
+            request(None, None).validate_location(),
+            Err(DRAFT_LOCATION_REQUIRED)
+        );
+        assert_eq!(
+            request(Some(Uuid::new_v4()), Some(Uuid::new_v4())).validate_location(),
+            Err(DRAFT_LOCATION_REQUIRED)
+        );
+    }
+
+    #[test]
+    fn test_validate_hint() {
+        let mut request = analyze_request(1);
+        assert!(request.validate_hint().is_ok());
+
+        request.hint = Some("é".repeat(MAX_ANALYZE_HINT_CHARS));
+        assert!(request.validate_hint().is_ok());
+
+        request.hint = Some("a".repeat(MAX_ANALYZE_HINT_CHARS + 1));
+        assert!(request.validate_hint().is_err());
+    }
+}
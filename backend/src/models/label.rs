@@ -2,10 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use typeshare::typeshare;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Label {
     pub id: Uuid,
     pub number: i32,
@@ -15,17 +16,35 @@ pub struct Label {
     pub assigned_to_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub assigned_at: Option<DateTime<Utc>>,
+    /// When the label was last assigned or unassigned
+    pub updated_at: DateTime<Utc>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct GenerateLabelsRequest {
     pub count: i32,
-    pub template: Option<String>, // Default to "avery_18660"
+    /// "avery_18660" (default), "avery_5160", or the ID of a custom label template
+    pub template: Option<String>,
+    /// What the labels are for, e.g. "garage boxes"
+    pub purpose: Option<String>,
 }
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct LabelBatch {
+    pub id: Uuid,
+    pub label_count: i32,
+    pub template: String,
+    pub purpose: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// JSON of the sheet layout the batch was generated with
+    pub template_config: Option<String>,
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct GenerateLabelsResponse {
     pub batch_id: Uuid,
     pub labels: Vec<LabelResponse>,
@@ -33,7 +52,7 @@ pub struct GenerateLabelsResponse {
 }
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct LabelResponse {
     pub id: Uuid,
     pub number: i32,
@@ -43,6 +62,10 @@ pub struct LabelResponse {
     pub assigned_to_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub assigned_at: Option<DateTime<Utc>>,
+    /// When the label was last assigned or unassigned
+    pub updated_at: DateTime<Utc>,
+    /// Name of the entity the label is assigned to
+    pub label_name: Option<String>,
 }
 
 impl From<Label> for LabelResponse {
@@ -56,21 +79,202 @@ impl From<Label> for LabelResponse {
             assigned_to_id: label.assigned_to_id,
             created_at: label.created_at,
             assigned_at: label.assigned_at,
+            updated_at: label.updated_at,
+            label_name: None, // Will be set by route handler
         }
     }
 }
 
+/// Response of `GET /api/labels/:id/entity`: what a scanned label is stuck on
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LabelEntityResponse {
+    /// "room", "unit", "shelf", "container" or "item"
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    /// The entity's public view, not its full response: a `PublicItemResponse` for
+    /// items, a `PathNode` (kind, id and name) for rooms, units, shelves and containers
+    pub entity: serde_json::Value,
+}
+
+/// A label given to an item by `POST /api/containers/:id/assign-labels`
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ItemLabelAssignment {
+    pub item_id: Uuid,
+    pub label_id: Uuid,
+    pub label_number: i32,
+}
+
+/// Response of `POST /api/containers/:id/assign-labels`
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AssignContainerLabelsResponse {
+    pub assigned: Vec<ItemLabelAssignment>,
+    /// Items in the container that already had a label
+    #[typeshare(serialized_as = "number")]
+    pub skipped_already_labeled: i64,
+    /// Whether some unlabeled items were left without a label because too few
+    /// unassigned labels were available
+    pub insufficient_labels: bool,
+}
+
+impl AssignContainerLabelsResponse {
+    /// Give each unlabeled item the next free label, as `(id, number)` in number order
+    pub fn pair(
+        unlabeled_item_ids: &[Uuid],
+        free_labels: &[(Uuid, i32)],
+        skipped_already_labeled: i64,
+    ) -> Self {
+        let assigned = unlabeled_item_ids
+            .iter()
+            .zip(free_labels)
+            .map(
+                |(&item_id, &(label_id, label_number))| ItemLabelAssignment {
+                    item_id,
+                    label_id,
+                    label_number,
+                },
+            )
+            .collect();
+        Self {
+            assigned,
+            skipped_already_labeled,
+            insufficient_labels: free_labels.len() < unlabeled_item_ids.len(),
+        }
+    }
+}
+
+/// Most columns or rows a custom template may have
+pub const MAX_LABEL_TEMPLATE_CELLS_PER_SIDE: i32 = 100;
+
+/// A user-defined label sheet layout. Lengths are in millimetres.
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct CustomLabelTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub sheet_width_mm: f64,
+    pub sheet_height_mm: f64,
+    pub label_width_mm: f64,
+    pub label_height_mm: f64,
+    pub cols: i32,
+    pub rows: i32,
+    pub top_margin_mm: f64,
+    pub left_margin_mm: f64,
+    pub col_spacing_mm: f64,
+    pub row_spacing_mm: f64,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateLabelTemplateRequest {
+    pub name: String,
+    pub sheet_width_mm: f64,
+    pub sheet_height_mm: f64,
+    pub label_width_mm: f64,
+    pub label_height_mm: f64,
+    pub cols: i32,
+    pub rows: i32,
+    pub top_margin_mm: f64,
+    pub left_margin_mm: f64,
+    /// Gap between columns, defaults to 0
+    pub col_spacing_mm: Option<f64>,
+    /// Gap between rows, defaults to 0
+    pub row_spacing_mm: Option<f64>,
+}
+
+/// Body of `PATCH /api/label-templates/:id`. Omitted fields are left unchanged.
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateLabelTemplateRequest {
+    pub name: Option<String>,
+    pub sheet_width_mm: Option<f64>,
+    pub sheet_height_mm: Option<f64>,
+    pub label_width_mm: Option<f64>,
+    pub label_height_mm: Option<f64>,
+    pub cols: Option<i32>,
+    pub rows: Option<i32>,
+    pub top_margin_mm: Option<f64>,
+    pub left_margin_mm: Option<f64>,
+    pub col_spacing_mm: Option<f64>,
+    pub row_spacing_mm: Option<f64>,
+}
+
+/// Print a batch with each assigned label's entity name and location
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PrintLabelsWithNamesRequest {
+    pub batch_id: Uuid,
+    /// "avery_18660", "avery_5160" or a custom template ID. Defaults to the layout
+    /// the batch was generated with.
+    pub template: Option<String>,
+}
+
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AssignLabelRequest {
     pub assigned_to_type: String, // 'room', 'unit', 'shelf', 'container', 'item'
     pub assigned_to_id: Uuid,
 }
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BatchWithLabels {
     pub batch_id: Uuid,
     pub labels: Vec<LabelResponse>,
+    pub template: String,
+    pub purpose: Option<String>,
+    pub created_by: Option<Uuid>,
     pub created_at: DateTime<Utc>,
 }
+
+/// Lowest and highest label number in a batch
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct LabelRange {
+    pub from: i32,
+    pub to: i32,
+}
+
+/// Entry of `GET /api/labels/batches`
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LabelBatchSummary {
+    pub batch_id: Uuid,
+    /// Labels in the batch
+    #[typeshare(serialized_as = "number")]
+    pub count: i64,
+    /// When its first label was created
+    pub created_at: DateTime<Utc>,
+    pub label_range: LabelRange,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pair_container_labels() {
+        let items: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let labels: Vec<(Uuid, i32)> = (1..=2).map(|n| (Uuid::new_v4(), n)).collect();
+
+        let short = AssignContainerLabelsResponse::pair(&items, &labels, 4);
+        assert_eq!(short.assigned.len(), 2);
+        assert_eq!(short.assigned[0].item_id, items[0]);
+        assert_eq!(short.assigned[0].label_number, 1);
+        assert_eq!(short.assigned[1].label_id, labels[1].0);
+        assert_eq!(short.skipped_already_labeled, 4);
+        assert!(short.insufficient_labels);
+
+        let enough = AssignContainerLabelsResponse::pair(&items[..2], &labels, 0);
+        assert_eq!(enough.assigned.len(), 2);
+        assert!(!enough.insufficient_labels);
+
+        let none = AssignContainerLabelsResponse::pair(&[], &[], 1);
+        assert!(none.assigned.is_empty());
+        assert!(!none.insufficient_labels);
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::SearchResultKind;
+
+/// One step of a location breadcrumb
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct PathNode {
+    pub kind: SearchResultKind,
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LocationPathResponse {
+    /// From the room down to the requested entity, which is the last node
+    pub path: Vec<PathNode>,
+}
+
+/// Query of `DELETE` on rooms, shelving units and shelves
+#[typeshare]
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CascadeDeleteQuery {
+    /// Also delete everything inside: units, shelves, containers and items. Without
+    /// it, deleting a location that isn't empty fails with 409 `HAS_CHILDREN`.
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+/// Number of each kind of location, plus items, in a subtree
+#[typeshare]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct LocationCounts {
+    #[typeshare(serialized_as = "number")]
+    pub rooms: i64,
+    #[typeshare(serialized_as = "number")]
+    pub units: i64,
+    #[typeshare(serialized_as = "number")]
+    pub shelves: i64,
+    #[typeshare(serialized_as = "number")]
+    pub containers: i64,
+    /// Including items in the trash
+    #[typeshare(serialized_as = "number")]
+    pub items: i64,
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CascadeDeleteResponse {
+    /// The location itself and, with `cascade=true`, everything that was inside it
+    pub deleted: LocationCounts,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_node_serialization() {
+        let node = PathNode {
+            kind: SearchResultKind::Unit,
+            id: Uuid::nil(),
+            name: "Metal Shelves".to_string(),
+        };
+        let json = serde_json::to_value(&node).unwrap();
+        assert_eq!(json["kind"], "unit");
+        assert_eq!(json["name"], "Metal Shelves");
+    }
+}
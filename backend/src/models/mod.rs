@@ -1,32 +1,56 @@
 pub mod audit;
+pub mod backfilled;
+pub mod clearable;
 pub mod contact;
 pub mod container;
+pub mod event;
+pub mod export;
+pub mod household;
+pub mod import;
 pub mod item;
 pub mod item_import_draft;
 pub mod label;
+pub mod location;
 pub mod pagination;
 pub mod photo;
 pub mod room;
+pub mod search;
 pub mod shelf;
 pub mod shelving_unit;
+pub mod stats;
 pub mod tag;
 pub mod user;
+pub mod webhook;
 
 // Re-export types for convenience
 // Suppress unused warnings for now as these will be used when we add routes
 #[allow(unused_imports)]
 pub use audit::*;
 #[allow(unused_imports)]
+pub use backfilled::*;
+#[allow(unused_imports)]
+pub use clearable::*;
+#[allow(unused_imports)]
 pub use contact::*;
 #[allow(unused_imports)]
 pub use container::*;
 #[allow(unused_imports)]
+pub use event::*;
+#[allow(unused_imports)]
+pub use export::*;
+#[allow(unused_imports)]
+pub use household::*;
+#[allow(unused_imports)]
+pub use import::*;
+#[allow(unused_imports)]
 pub use item::*;
 #[allow(unused_imports)]
 pub use item_import_draft::*;
 #[allow(unused_imports)]
 pub use label::*;
 #[allow(unused_imports)]
+pub use location::*;
+#[allow(unused_imports)]
 pub use pagination::*;
 #[allow(unused_imports)]
 pub use pagination::*;
@@ -35,10 +59,16 @@ pub use photo::*;
 #[allow(unused_imports)]
 pub use room::*;
 #[allow(unused_imports)]
+pub use search::*;
+#[allow(unused_imports)]
 pub use shelf::*;
 #[allow(unused_imports)]
 pub use shelving_unit::*;
 #[allow(unused_imports)]
+pub use stats::*;
+#[allow(unused_imports)]
 pub use tag::*;
 #[allow(unused_imports)]
 pub use user::*;
+#[allow(unused_imports)]
+pub use webhook::*;
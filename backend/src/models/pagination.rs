@@ -1,21 +1,70 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PaginationQuery {
     pub limit: Option<i32>,
     pub offset: Option<i32>,
     pub search: Option<String>,
+    /// `next_cursor` from the previous page. Takes precedence over `offset` on
+    /// endpoints that support cursors.
+    pub cursor: Option<String>,
+    /// `GET /api/items` only: items acquired on or after this date
+    #[typeshare(serialized_as = "String")]
+    pub acquired_after: Option<NaiveDate>,
+    /// `GET /api/items` only: items acquired on or before this date
+    #[typeshare(serialized_as = "String")]
+    pub acquired_before: Option<NaiveDate>,
+    /// `GET /api/items` only: items created at or after this time (RFC 3339)
+    #[typeshare(serialized_as = "String")]
+    pub created_after: Option<DateTime<Utc>>,
+    /// `GET /api/items` only: items created at or before this time (RFC 3339)
+    #[typeshare(serialized_as = "String")]
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+impl PaginationQuery {
+    /// `search` as an ILIKE pattern matching it anywhere, or `None` when it's absent
+    /// or blank
+    pub fn search_pattern(&self) -> Option<String> {
+        self.search
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| format!("%{}%", s))
+    }
+
+    /// Reject a date filter whose range ends before it starts
+    pub fn validate_date_ranges(&self) -> Result<(), &'static str> {
+        if let (Some(after), Some(before)) = (self.acquired_after, self.acquired_before) {
+            if after > before {
+                return Err("acquired_after must not be later than acquired_before");
+            }
+        }
+        if let (Some(after), Some(before)) = (self.created_after, self.created_before) {
+            if after > before {
+                return Err("created_after must not be later than created_before");
+            }
+        }
+        Ok(())
+    }
 }
 
 #[typeshare]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct PaginatedResponse<T> {
     pub data: Vec<T>,
     pub total: i32,
     pub limit: i32,
     pub offset: i32,
+    /// Pass as `cursor` to fetch the next page. Absent on the last page and on
+    /// endpoints that only support offsets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 impl<T> PaginatedResponse<T> {
@@ -25,8 +74,14 @@ impl<T> PaginatedResponse<T> {
             total,
             limit,
             offset,
+            next_cursor: None,
         }
     }
+
+    pub fn with_next_cursor(mut self, next_cursor: Option<String>) -> Self {
+        self.next_cursor = next_cursor;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -39,6 +94,8 @@ mod tests {
             limit: None,
             offset: None,
             search: None,
+            cursor: None,
+            ..Default::default()
         };
 
         assert_eq!(query.limit, None);
@@ -51,12 +108,52 @@ mod tests {
             limit: Some(10),
             offset: Some(20),
             search: None,
+            cursor: None,
+            ..Default::default()
         };
 
         assert_eq!(query.limit, Some(10));
         assert_eq!(query.offset, Some(20));
     }
 
+    #[test]
+    fn test_pagination_query_search_pattern() {
+        let query = |search: Option<&str>| PaginationQuery {
+            search: search.map(str::to_string),
+            ..Default::default()
+        };
+
+        assert_eq!(query(None).search_pattern(), None);
+        assert_eq!(query(Some("  ")).search_pattern(), None);
+        assert_eq!(
+            query(Some(" garage ")).search_pattern(),
+            Some("%garage%".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pagination_query_validate_date_ranges() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2024, 5, d);
+        let acquired = |after, before| PaginationQuery {
+            acquired_after: after,
+            acquired_before: before,
+            ..Default::default()
+        };
+
+        assert!(acquired(day(1), day(31)).validate_date_ranges().is_ok());
+        assert!(acquired(day(1), day(1)).validate_date_ranges().is_ok());
+        assert!(acquired(day(2), None).validate_date_ranges().is_ok());
+        assert!(acquired(day(2), day(1)).validate_date_ranges().is_err());
+
+        let now = Utc::now();
+        let created = PaginationQuery {
+            created_after: Some(now),
+            created_before: Some(now - chrono::Duration::hours(1)),
+            ..Default::default()
+        };
+        assert!(created.validate_date_ranges().is_err());
+    }
+
     #[test]
     fn test_paginated_response_new() {
         let data = vec![1, 2, 3];
@@ -133,4 +230,25 @@ mod tests {
         assert_eq!(query.limit, Some(15));
         assert_eq!(query.offset, None);
     }
+
+    #[test]
+    fn test_pagination_query_deserialization_with_search() {
+        let json = r#"{"limit": 10, "search": "foo"}"#;
+        let query: PaginationQuery = serde_json::from_str(json).unwrap();
+
+        assert_eq!(query.limit, Some(10));
+        assert_eq!(query.offset, None);
+        assert_eq!(query.search, Some("foo".to_string()));
+    }
+
+    #[test]
+    fn test_next_cursor_only_serialized_when_set() {
+        let response = PaginatedResponse::new(vec![1], 1, 10, 0);
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("next_cursor").is_none());
+
+        let response = response.with_next_cursor(Some("abc".to_string()));
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["next_cursor"], "abc");
+    }
 }
@@ -1,11 +1,13 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::collections::HashMap;
 use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Photo {
     pub id: Uuid,
     pub entity_type: String,
@@ -18,10 +20,18 @@ pub struct Photo {
     pub height: Option<i32>,
     pub created_at: DateTime<Utc>,
     pub created_by: Uuid,
+    /// Perceptual hash as 16 hex digits, set in the background after creation
+    pub phash: Option<String>,
+    /// Camera, capture time and orientation read from the image's EXIF, as a JSON
+    /// object in a TEXT column
+    pub exif_data: Option<String>,
+    /// Where the photo was taken, from its EXIF GPS tags
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
 }
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PhotoResponse {
     pub id: String,
     pub entity_type: String,
@@ -33,10 +43,24 @@ pub struct PhotoResponse {
     pub width: Option<i32>,
     pub height: Option<i32>,
     pub created_at: String,
+    pub exif_data: Option<serde_json::Value>,
+    pub lat: Option<f64>,
+    pub lng: Option<f64>,
+}
+
+impl Photo {
+    /// The stored EXIF metadata. A malformed column reads as none.
+    pub fn exif(&self) -> Option<serde_json::Value> {
+        let exif_data = self.exif_data.as_deref()?;
+        serde_json::from_str(exif_data)
+            .inspect_err(|e| tracing::warn!("Photo {} has invalid exif_data: {}", self.id, e))
+            .ok()
+    }
 }
 
 impl From<Photo> for PhotoResponse {
     fn from(photo: Photo) -> Self {
+        let exif_data = photo.exif();
         // URLs will be generated by the API route
         Self {
             id: photo.id.to_string(),
@@ -49,12 +73,31 @@ impl From<Photo> for PhotoResponse {
             width: photo.width,
             height: photo.height,
             created_at: photo.created_at.to_rfc3339(),
+            exif_data,
+            lat: photo.lat,
+            lng: photo.lng,
         }
     }
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SimilarPhotosQuery {
+    /// Most bits a photo's hash may differ by, 0-64. Defaults to 10.
+    pub threshold: Option<u32>,
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SimilarPhotoResponse {
+    pub photo: PhotoResponse,
+    /// Bits the perceptual hashes differ in; 0 is a (near-)exact duplicate
+    pub distance: u32,
+}
+
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreatePhotoRequest {
     pub entity_type: String,
     pub entity_id: String,
@@ -67,9 +110,72 @@ pub struct CreatePhotoRequest {
 }
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PresignedUploadUrl {
     pub upload_url: String,
     pub s3_key: String,
-    pub expires_in: i32,
+    /// The content type the URL was signed for; the upload must use the same one
+    pub content_type: String,
+    /// Seconds until the upload URL expires
+    #[typeshare(serialized_as = "number")]
+    pub expires_in: u64,
+}
+
+/// Presigned POST for an item manual or receipt. Send a `multipart/form-data` POST
+/// to `upload_url` with every entry in `fields`, then the file as `file`.
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FileUploadResponse {
+    pub upload_url: String,
+    pub fields: HashMap<String, String>,
+    pub s3_key: String,
+    pub content_type: String,
+    /// Largest file S3 will accept, in bytes
+    #[typeshare(serialized_as = "number")]
+    pub max_bytes: u64,
+    /// Seconds until the upload expires
+    #[typeshare(serialized_as = "number")]
+    pub expires_in: u64,
+    /// Set when the request's `file_size_bytes` calls for a multipart upload; upload
+    /// the parts instead of POSTing to `upload_url`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multipart: Option<MultipartUploadResponse>,
+}
+
+/// A started multipart upload. PUT each `part_size` slice of the file (the last may
+/// be shorter) to the matching URL in `part_urls`, then send the `ETag` response
+/// headers to `POST /api/items/file-complete-multipart`.
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MultipartUploadResponse {
+    pub upload_id: String,
+    #[typeshare(serialized_as = "number")]
+    pub part_size: u64,
+    /// Part `n` goes to `part_urls[n - 1]`
+    pub part_urls: Vec<String>,
+}
+
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UploadedPart {
+    /// 1-based
+    pub part_number: i32,
+    pub etag: String,
+}
+
+/// Body of `POST /api/items/file-complete-multipart`
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CompleteMultipartUploadRequest {
+    pub s3_key: String,
+    pub upload_id: String,
+    pub parts: Vec<UploadedPart>,
+}
+
+/// Body of `POST /api/items/file-abort-multipart`
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AbortMultipartUploadRequest {
+    pub s3_key: String,
+    pub upload_id: String,
 }
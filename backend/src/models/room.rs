@@ -2,10 +2,14 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use typeshare::typeshare;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// `photos.entity_type` of room floor plans, which are kept out of the room's photos
+pub const FLOOR_PLAN_ENTITY_TYPE: &str = "room_floor_plan";
+
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Room {
     pub id: Uuid,
     pub name: String,
@@ -14,10 +18,12 @@ pub struct Room {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: Uuid,
+    /// Photo (with `entity_type` [`FLOOR_PLAN_ENTITY_TYPE`]) showing the room's layout
+    pub floor_plan_photo_id: Option<Uuid>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 #[allow(dead_code)] // Will be used when we implement room CRUD routes
 pub struct CreateRoomRequest {
     pub name: String,
@@ -25,15 +31,16 @@ pub struct CreateRoomRequest {
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 #[allow(dead_code)] // Will be used when we implement room CRUD routes
+/// Body of `PATCH /api/rooms/:id`. Omitted fields are left unchanged.
 pub struct UpdateRoomRequest {
     pub name: Option<String>,
     pub description: Option<String>,
 }
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct RoomResponse {
     pub id: Uuid,
     pub name: String,
@@ -41,6 +48,10 @@ pub struct RoomResponse {
     pub label_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub floor_plan_photo_id: Option<Uuid>,
+    /// Presigned download URL of the floor plan. Only set on single-room responses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floor_plan_url: Option<String>,
 }
 
 impl From<Room> for RoomResponse {
@@ -52,6 +63,17 @@ impl From<Room> for RoomResponse {
             label_id: room.label_id,
             created_at: room.created_at,
             updated_at: room.updated_at,
+            floor_plan_photo_id: room.floor_plan_photo_id,
+            floor_plan_url: None,
         }
     }
 }
+
+/// JSON body of `POST /api/rooms/:id/floor-plan`, using one of the room's photos as
+/// its floor plan. The endpoint also takes a `multipart/form-data` upload with the
+/// image as `file`.
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetFloorPlanRequest {
+    pub photo_id: Uuid,
+}
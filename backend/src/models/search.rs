@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    pub q: String,
+    /// Comma-separated kinds to search, e.g. `item,container`. Defaults to all kinds.
+    pub types: Option<String>,
+    pub limit: Option<i32>,
+}
+
+#[typeshare]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchResultKind {
+    Room,
+    Unit,
+    Shelf,
+    Container,
+    Item,
+}
+
+impl SearchResultKind {
+    pub const ALL: [SearchResultKind; 5] = [
+        SearchResultKind::Room,
+        SearchResultKind::Unit,
+        SearchResultKind::Shelf,
+        SearchResultKind::Container,
+        SearchResultKind::Item,
+    ];
+}
+
+impl FromStr for SearchResultKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "room" => Ok(SearchResultKind::Room),
+            "unit" => Ok(SearchResultKind::Unit),
+            "shelf" => Ok(SearchResultKind::Shelf),
+            "container" => Ok(SearchResultKind::Container),
+            "item" => Ok(SearchResultKind::Item),
+            other => Err(format!("Unknown search type: {}", other)),
+        }
+    }
+}
+
+/// Parse the `types` query parameter, defaulting to every kind
+pub fn parse_search_kinds(types: Option<&str>) -> Result<Vec<SearchResultKind>, String> {
+    let Some(types) = types.filter(|t| !t.trim().is_empty()) else {
+        return Ok(SearchResultKind::ALL.to_vec());
+    };
+
+    let mut kinds = Vec::new();
+    for kind in types.split(',').filter(|t| !t.trim().is_empty()) {
+        let kind = kind.parse::<SearchResultKind>()?;
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+    }
+    Ok(kinds)
+}
+
+#[typeshare]
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SearchResult {
+    pub kind: SearchResultKind,
+    pub id: Uuid,
+    pub name: String,
+    /// Breadcrumb of the enclosing locations, e.g. "Living Room > IKEA Kallax > Shelf 2".
+    /// Empty for rooms.
+    pub path: String,
+    /// 3 = exact name match, 2 = name prefix, 1 = name substring, 0 = other field matched
+    pub score: i32,
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SearchResponse {
+    pub results: Vec<SearchResult>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_kinds_defaults_to_all() {
+        assert_eq!(
+            parse_search_kinds(None).unwrap(),
+            SearchResultKind::ALL.to_vec()
+        );
+        assert_eq!(
+            parse_search_kinds(Some(" ")).unwrap(),
+            SearchResultKind::ALL.to_vec()
+        );
+    }
+
+    #[test]
+    fn test_parse_search_kinds() {
+        assert_eq!(
+            parse_search_kinds(Some("item, Container,item")).unwrap(),
+            vec![SearchResultKind::Item, SearchResultKind::Container]
+        );
+        assert!(parse_search_kinds(Some("item,garage")).is_err());
+    }
+
+    #[test]
+    fn test_search_result_kind_serialization() {
+        let json = serde_json::to_string(&SearchResultKind::Unit).unwrap();
+        assert_eq!(json, r#""unit""#);
+    }
+}
@@ -2,10 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Shelf {
     pub id: Uuid,
     pub shelving_unit_id: Uuid,
@@ -19,7 +20,7 @@ pub struct Shelf {
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateShelfRequest {
     pub shelving_unit_id: Uuid,
     pub name: String,
@@ -28,7 +29,8 @@ pub struct CreateShelfRequest {
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+/// Body of `PATCH /api/shelves/:id`. Omitted fields are left unchanged.
 pub struct UpdateShelfRequest {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -36,8 +38,25 @@ pub struct UpdateShelfRequest {
     pub shelving_unit_id: Option<Uuid>,
 }
 
+/// Body of `POST /api/units/:id/shelves/reorder`: every shelf of the unit, in its
+/// new order
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReorderShelvesRequest {
+    pub shelf_ids: Vec<Uuid>,
+}
+
+/// Whether to count each listed shelf's items and containers. Off by default since
+/// it costs two subqueries per shelf.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ShelfCountsQuery {
+    #[serde(default)]
+    pub include_counts: bool,
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ShelfResponse {
     pub id: Uuid,
     pub shelving_unit_id: Uuid,
@@ -47,6 +66,19 @@ pub struct ShelfResponse {
     pub label_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Only with `?include_photos=true` on list endpoints
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub photo_count: Option<i32>,
+    /// Presigned URL of the earliest photo, only with `?include_photos=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_photo_url: Option<String>,
+    /// Items directly on the shelf (not in its containers), only with
+    /// `?include_counts=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item_count: Option<i32>,
+    /// Containers directly on the shelf, only with `?include_counts=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_count: Option<i32>,
 }
 
 impl From<Shelf> for ShelfResponse {
@@ -60,6 +92,10 @@ impl From<Shelf> for ShelfResponse {
             label_id: shelf.label_id,
             created_at: shelf.created_at,
             updated_at: shelf.updated_at,
+            photo_count: None,
+            primary_photo_url: None,
+            item_count: None,
+            container_count: None,
         }
     }
 }
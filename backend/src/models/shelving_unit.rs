@@ -2,10 +2,13 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use typeshare::typeshare;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::models::Clearable;
+
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct ShelvingUnit {
     pub id: Uuid,
     pub room_id: Uuid,
@@ -15,34 +18,52 @@ pub struct ShelvingUnit {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub created_by: Uuid,
+    pub max_shelves: Option<i32>,
+    pub max_weight_kg: Option<f64>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 #[allow(dead_code)] // Will be used when we implement shelving unit CRUD routes
 pub struct CreateShelvingUnitRequest {
     pub room_id: Uuid,
     pub name: String,
     pub description: Option<String>,
+    /// Most shelves the unit holds; no limit when omitted
+    pub max_shelves: Option<i32>,
+    pub max_weight_kg: Option<f64>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 #[allow(dead_code)] // Will be used when we implement shelving unit CRUD routes
+/// Body of `PATCH /api/units/:id`. Omitted fields are left unchanged.
 pub struct UpdateShelvingUnitRequest {
     pub name: Option<String>,
     pub description: Option<String>,
     pub room_id: Option<Uuid>,
+    /// `null` removes the limit
+    #[serde(default)]
+    #[typeshare(typescript(type = "number | null"))]
+    #[schema(value_type = Option<i32>)]
+    pub max_shelves: Clearable<i32>,
+    /// `null` clears the weight
+    #[serde(default)]
+    #[typeshare(typescript(type = "number | null"))]
+    #[schema(value_type = Option<f64>)]
+    pub max_weight_kg: Clearable<f64>,
 }
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ShelvingUnitResponse {
     pub id: Uuid,
     pub room_id: Uuid,
     pub name: String,
     pub description: Option<String>,
     pub label_id: Option<Uuid>,
+    pub max_shelves: Option<i32>,
+    pub max_weight_kg: Option<f64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -55,8 +76,106 @@ impl From<ShelvingUnit> for ShelvingUnitResponse {
             name: unit.name,
             description: unit.description,
             label_id: unit.label_id,
+            max_shelves: unit.max_shelves,
+            max_weight_kg: unit.max_weight_kg,
             created_at: unit.created_at,
             updated_at: unit.updated_at,
         }
     }
 }
+
+/// Reject a capacity that isn't a positive shelf count or a positive, finite weight
+pub fn capacity_valid(max_shelves: Option<i32>, max_weight_kg: Option<f64>) -> bool {
+    max_shelves.is_none_or(|max| max > 0)
+        && max_weight_kg.is_none_or(|kg| kg.is_finite() && kg > 0.0)
+}
+
+/// Utilization at or above which adding a shelf is recorded as a capacity warning
+pub const CAPACITY_WARNING_PCT: f64 = 90.0;
+
+/// Response of `GET /api/units/:id/capacity`
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ShelvingUnitCapacity {
+    /// `None` when the unit has no limit
+    pub max_shelves: Option<i32>,
+    pub current_shelves: i32,
+    /// Share of `max_shelves` in use, `None` without a limit
+    pub utilization_pct: Option<f64>,
+}
+
+impl ShelvingUnitCapacity {
+    pub fn new(max_shelves: Option<i32>, current_shelves: i32) -> Self {
+        Self {
+            max_shelves,
+            current_shelves,
+            utilization_pct: max_shelves.map(|max| utilization_pct(current_shelves, max)),
+        }
+    }
+
+    /// No room for another shelf
+    pub fn is_full(&self) -> bool {
+        self.max_shelves
+            .is_some_and(|max| self.current_shelves >= max)
+    }
+
+    /// Whether adding one shelf takes utilization from below the warning level to
+    /// at or above it
+    pub fn crosses_warning_with_one_more(&self) -> bool {
+        self.max_shelves.is_some_and(|max| {
+            utilization_pct(self.current_shelves, max) < CAPACITY_WARNING_PCT
+                && utilization_pct(self.current_shelves + 1, max) >= CAPACITY_WARNING_PCT
+        })
+    }
+}
+
+fn utilization_pct(current: i32, max: i32) -> f64 {
+    // Rounded to one decimal so responses read 66.7 rather than 66.66666666666667
+    (current as f64 * 1000.0 / max as f64).round() / 10.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capacity_valid() {
+        assert!(capacity_valid(None, None));
+        assert!(capacity_valid(Some(5), Some(120.5)));
+        assert!(!capacity_valid(Some(0), None));
+        assert!(!capacity_valid(Some(-1), None));
+        assert!(!capacity_valid(None, Some(0.0)));
+        assert!(!capacity_valid(None, Some(f64::NAN)));
+    }
+
+    #[test]
+    fn test_shelving_unit_capacity() {
+        let capacity = ShelvingUnitCapacity::new(Some(4), 3);
+        assert_eq!(capacity.utilization_pct, Some(75.0));
+        assert!(!capacity.is_full());
+        assert_eq!(
+            ShelvingUnitCapacity::new(Some(3), 2).utilization_pct,
+            Some(66.7)
+        );
+
+        assert!(ShelvingUnitCapacity::new(Some(4), 4).is_full());
+        // Lowering the limit below the current count leaves the unit full
+        assert!(ShelvingUnitCapacity::new(Some(2), 3).is_full());
+
+        let unlimited = ShelvingUnitCapacity::new(None, 40);
+        assert_eq!(unlimited.utilization_pct, None);
+        assert!(!unlimited.is_full());
+    }
+
+    #[test]
+    fn test_capacity_warning_fires_once_when_crossing() {
+        // 8/10 -> 9/10 crosses 90%
+        assert!(ShelvingUnitCapacity::new(Some(10), 8).crosses_warning_with_one_more());
+        // Already at 90%
+        assert!(!ShelvingUnitCapacity::new(Some(10), 9).crosses_warning_with_one_more());
+        assert!(!ShelvingUnitCapacity::new(Some(10), 5).crosses_warning_with_one_more());
+        // 3/4 -> 4/4
+        assert!(ShelvingUnitCapacity::new(Some(4), 3).crosses_warning_with_one_more());
+        assert!(!ShelvingUnitCapacity::new(None, 100).crosses_warning_with_one_more());
+    }
+}
@@ -0,0 +1,137 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Counts for a room, or the whole inventory, through every level of the hierarchy.
+/// Trashed items are not counted.
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, FromRow, ToSchema)]
+pub struct InventoryStats {
+    pub shelving_unit_count: i32,
+    pub shelf_count: i32,
+    pub container_count: i32,
+    pub item_count: i32,
+    /// Photos of the room itself and of everything in it
+    pub photo_count: i32,
+    /// Distinct tags on the room and everything in it
+    pub tag_count: i32,
+    /// Sum of USD purchase prices, e.g. "123.45"; null when no item has one
+    #[typeshare(serialized_as = "String")]
+    pub total_value: Option<Decimal>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ValuationQuery {
+    /// ISO 4217 code to total; defaults to USD
+    pub currency: Option<String>,
+}
+
+/// Value of the items in one room
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, FromRow, ToSchema)]
+pub struct RoomValuation {
+    pub room_id: Uuid,
+    pub room_name: String,
+    #[typeshare(serialized_as = "String")]
+    pub value: Decimal,
+}
+
+/// Value of the items priced in one currency
+#[typeshare]
+#[derive(Debug, Clone, PartialEq, Serialize, FromRow, ToSchema)]
+pub struct CurrencyValuation {
+    pub currency: String,
+    #[typeshare(serialized_as = "String")]
+    pub total_value: Decimal,
+    pub item_count: i32,
+}
+
+/// Sum of purchase prices for items priced in `currency`. Items without a price, and
+/// trashed items, are left out. Decimals are serialized as strings, e.g. "1234.56".
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ValuationResponse {
+    #[typeshare(serialized_as = "String")]
+    pub total_value: Decimal,
+    pub currency: String,
+    pub item_count: i32,
+    pub by_room: Vec<RoomValuation>,
+    /// Totals per currency, only present when items are priced in more than one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub by_currency: Option<Vec<CurrencyValuation>>,
+}
+
+impl ValuationResponse {
+    /// Pick `currency` out of the per-currency totals
+    pub fn new(
+        currency: String,
+        by_currency: Vec<CurrencyValuation>,
+        by_room: Vec<RoomValuation>,
+    ) -> Self {
+        let (total_value, item_count) = by_currency
+            .iter()
+            .find(|totals| totals.currency == currency)
+            .map_or((Decimal::new(0, 2), 0), |totals| {
+                (totals.total_value, totals.item_count)
+            });
+
+        Self {
+            total_value,
+            currency,
+            item_count,
+            by_room,
+            by_currency: (by_currency.len() > 1).then_some(by_currency),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn totals(currency: &str, cents: i64, item_count: i32) -> CurrencyValuation {
+        CurrencyValuation {
+            currency: currency.to_string(),
+            total_value: Decimal::new(cents, 2),
+            item_count,
+        }
+    }
+
+    #[test]
+    fn test_single_currency_has_no_breakdown() {
+        let response =
+            ValuationResponse::new("USD".to_string(), vec![totals("USD", 123456, 3)], vec![]);
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["total_value"], "1234.56");
+        assert_eq!(json["item_count"], 3);
+        assert!(json.get("by_currency").is_none());
+    }
+
+    #[test]
+    fn test_mixed_currencies_include_breakdown() {
+        let response = ValuationResponse::new(
+            "EUR".to_string(),
+            vec![totals("EUR", 500, 1), totals("USD", 123456, 3)],
+            vec![],
+        );
+
+        assert_eq!(response.total_value, Decimal::new(500, 2));
+        assert_eq!(response.item_count, 1);
+        assert_eq!(response.by_currency.map(|b| b.len()), Some(2));
+    }
+
+    #[test]
+    fn test_unused_currency_is_zero() {
+        let response =
+            ValuationResponse::new("GBP".to_string(), vec![totals("USD", 100, 1)], vec![]);
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["total_value"], "0.00");
+        assert_eq!(json["item_count"], 0);
+    }
+}
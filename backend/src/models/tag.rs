@@ -2,10 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use typeshare::typeshare;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 #[allow(dead_code)]
 pub struct Tag {
     pub id: Uuid,
@@ -14,38 +15,176 @@ pub struct Tag {
 }
 
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 #[allow(dead_code)]
 pub struct EntityTag {
     pub entity_type: String,
     pub entity_id: Uuid,
     pub tag_id: Uuid,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 #[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[allow(dead_code)]
 pub struct TagResponse {
     pub id: Uuid,
     pub name: String,
     pub created_at: DateTime<Utc>,
+    /// Number of entities with the tag; only with `include_usage_count=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage_count: Option<i32>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TagListQuery {
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
+    /// Case-insensitive substring of the tag name
+    pub q: Option<String>,
+    /// Count each tag's entities, which costs a subquery per tag
+    pub include_usage_count: Option<bool>,
+}
+
+#[typeshare]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TagAutocompleteQuery {
+    pub q: String,
+    /// Defaults to 10, at most 50
+    pub limit: Option<i32>,
+}
+
+/// A typeahead suggestion
+#[typeshare]
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct TagSuggestion {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// Normalize a tag name: trim, collapse internal whitespace, and lowercase.
+/// `" Tools "`, `"TOOLS"`, and `"tools"` all normalize to `"tools"`.
+pub fn normalize_tag_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Key tags are compared on when looking for duplicates: the name lowercased with
+/// everything but letters and digits removed, so `"Power-Tools"` and `"power tools"`
+/// both become `"powertools"`
+pub fn tag_duplicate_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Tags whose names only differ in case, spacing or punctuation
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TagDuplicateGroup {
+    /// The `tag_duplicate_key` the tags share
+    pub normalized_name: String,
+    pub tags: Vec<TagResponse>,
+}
+
+impl TagDuplicateGroup {
+    /// Groups of two or more `tags` with the same key, ordered by key. Names without
+    /// any letters or digits are never grouped.
+    pub fn find(tags: Vec<TagResponse>) -> Vec<Self> {
+        let mut by_key: std::collections::BTreeMap<String, Vec<TagResponse>> =
+            std::collections::BTreeMap::new();
+        for tag in tags {
+            let key = tag_duplicate_key(&tag.name);
+            if !key.is_empty() {
+                by_key.entry(key).or_default().push(tag);
+            }
+        }
+
+        by_key
+            .into_iter()
+            .filter(|(_, tags)| tags.len() > 1)
+            .map(|(normalized_name, tags)| Self {
+                normalized_name,
+                tags,
+            })
+            .collect()
+    }
+}
+
+/// Response of `POST /api/tags/:id/merge-into/:target_id`
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TagMergeResponse {
+    /// Entities that had the merged tag; they all have the target tag now
+    #[typeshare(serialized_as = "number")]
+    pub merged_entity_count: i64,
+    /// The merged tag, unless another household still uses it
+    pub deleted_tag_id: Option<Uuid>,
+}
+
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateTagRequest {
     pub name: String,
 }
 
+/// Most tags `POST /api/tags/bulk` creates at once
+pub const MAX_BULK_CREATE_TAGS: usize = 50;
+
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkCreateTagsRequest {
+    /// Tag names; each entry may also be a comma-separated list, e.g. `"tools, hardware"`
+    pub names: Vec<String>,
+}
+
+impl BulkCreateTagsRequest {
+    /// The distinct normalized names, in request order. Blank entries are skipped; an
+    /// overlong name, no names at all or more than [`MAX_BULK_CREATE_TAGS`] is an error.
+    pub fn normalized_names(&self) -> Result<Vec<String>, &'static str> {
+        let mut names: Vec<String> = Vec::new();
+        for name in self.names.iter().flat_map(|entry| entry.split(',')) {
+            let name = normalize_tag_name(name);
+            if name.is_empty() || names.contains(&name) {
+                continue;
+            }
+            if name.len() > 100 {
+                return Err("Tag names must be 1-100 characters");
+            }
+            names.push(name);
+        }
+
+        match names.len() {
+            0 => Err("At least one tag name is required"),
+            n if n > MAX_BULK_CREATE_TAGS => Err("At most 50 tags can be created at once"),
+            _ => Ok(names),
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkCreateTagsResponse {
+    pub created: Vec<TagResponse>,
+    /// Requested tags that were already there, left unchanged
+    pub already_existed: Vec<TagResponse>,
+}
+
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateTagRequest {
     pub name: Option<String>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct AssignTagsRequest {
     pub entity_type: String,
     pub entity_id: Uuid,
@@ -53,19 +192,178 @@ pub struct AssignTagsRequest {
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BulkAssignTagsRequest {
     pub entity_type: String,
     pub entity_ids: Vec<Uuid>,
     pub tag_ids: Vec<Uuid>,
 }
 
+/// The tags of one entity, grouped from a single query over a whole page
+#[typeshare]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TagsSideload {
+    pub entity_id: Uuid,
+    pub tags: Vec<TagResponse>,
+}
+
+impl TagsSideload {
+    /// Group `(entity_id, tag)` rows into one sideload per requested entity, in
+    /// request order. Entities without tags get an empty list.
+    pub fn group(entity_ids: &[Uuid], rows: Vec<(Uuid, Tag)>) -> Vec<Self> {
+        let mut by_entity: std::collections::HashMap<Uuid, Vec<TagResponse>> =
+            std::collections::HashMap::new();
+        for (entity_id, tag) in rows {
+            by_entity
+                .entry(entity_id)
+                .or_default()
+                .push(TagResponse::from(tag));
+        }
+
+        entity_ids
+            .iter()
+            .map(|entity_id| Self {
+                entity_id: *entity_id,
+                tags: by_entity.remove(entity_id).unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
 impl From<Tag> for TagResponse {
     fn from(tag: Tag) -> Self {
         Self {
             id: tag.id,
             name: tag.name,
             created_at: tag.created_at,
+            usage_count: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_create_tags_normalized_names() {
+        let request = |names: &[&str]| BulkCreateTagsRequest {
+            names: names.iter().map(|name| name.to_string()).collect(),
+        };
+
+        assert_eq!(
+            request(&["Tools, hardware", " ELECTRICAL ", "tools", ""]).normalized_names(),
+            Ok(vec![
+                "tools".to_string(),
+                "hardware".to_string(),
+                "electrical".to_string()
+            ])
+        );
+        assert!(request(&[]).normalized_names().is_err());
+        assert!(request(&[" , "]).normalized_names().is_err());
+        assert!(request(&[&"x".repeat(101)]).normalized_names().is_err());
+
+        let fifty_one: Vec<String> = (0..=MAX_BULK_CREATE_TAGS)
+            .map(|i| format!("tag {i}"))
+            .collect();
+        let joined = fifty_one.join(",");
+        assert!(request(&[&joined]).normalized_names().is_err());
+        assert!(request(&[&fifty_one[1..].join(",")])
+            .normalized_names()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_normalize_tag_name_trims_and_lowercases() {
+        assert_eq!(normalize_tag_name(" Tools "), "tools");
+        assert_eq!(normalize_tag_name("TOOLS"), "tools");
+        assert_eq!(normalize_tag_name("tools"), "tools");
+    }
+
+    #[test]
+    fn test_normalize_tag_name_collapses_internal_whitespace() {
+        assert_eq!(normalize_tag_name("Power   Tools"), "power tools");
+        assert_eq!(normalize_tag_name("power\t\ntools"), "power tools");
+    }
+
+    #[test]
+    fn test_normalize_tag_name_empty() {
+        assert_eq!(normalize_tag_name(""), "");
+        assert_eq!(normalize_tag_name("   "), "");
+    }
+
+    #[test]
+    fn test_normalize_tag_name_preserves_punctuation() {
+        assert_eq!(normalize_tag_name("Office-Supplies"), "office-supplies");
+    }
+
+    #[test]
+    fn test_tag_duplicate_key_strips_case_spacing_and_punctuation() {
+        assert_eq!(tag_duplicate_key("Power-Tools"), "powertools");
+        assert_eq!(tag_duplicate_key(" power tools "), "powertools");
+        assert_eq!(tag_duplicate_key("Café #2"), "café2");
+        assert_eq!(tag_duplicate_key("!!!"), "");
+    }
+
+    #[test]
+    fn test_tag_duplicate_group_find() {
+        let response = |name: &str| TagResponse::from(tag(name));
+        let groups = TagDuplicateGroup::find(vec![
+            response("tools"),
+            response("Office Supplies"),
+            response("garage"),
+            response("office-supplies"),
+            response("TOOLS"),
+            response("#"),
+            response("?"),
+        ]);
+
+        let summary: Vec<(String, Vec<String>)> = groups
+            .into_iter()
+            .map(|group| {
+                let names = group.tags.into_iter().map(|t| t.name).collect();
+                (group.normalized_name, names)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "officesupplies".to_string(),
+                    vec!["Office Supplies".to_string(), "office-supplies".to_string()]
+                ),
+                (
+                    "tools".to_string(),
+                    vec!["tools".to_string(), "TOOLS".to_string()]
+                ),
+            ]
+        );
+    }
+
+    fn tag(name: &str) -> Tag {
+        Tag {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_tags_sideload_group_keeps_request_order_and_fills_empty() {
+        let (first, second, untagged) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let rows = vec![
+            (second, tag("garage")),
+            (first, tag("kitchen")),
+            (second, tag("tools")),
+        ];
+
+        let sideloads = TagsSideload::group(&[first, untagged, second], rows);
+
+        let ids: Vec<Uuid> = sideloads.iter().map(|s| s.entity_id).collect();
+        assert_eq!(ids, vec![first, untagged, second]);
+        let names = |s: &TagsSideload| s.tags.iter().map(|t| t.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&sideloads[0]), vec!["kitchen"]);
+        assert!(sideloads[1].tags.is_empty());
+        assert_eq!(names(&sideloads[2]), vec!["garage", "tools"]);
+    }
+}
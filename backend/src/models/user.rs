@@ -2,10 +2,13 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use typeshare::typeshare;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::{NullAsFalse, PublicItemResponse};
+
 #[typeshare]
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 #[allow(dead_code)] // Used in database schema and will be used for user management
 pub struct User {
     pub id: Uuid,
@@ -13,15 +16,101 @@ pub struct User {
     pub name: String,
     pub google_id: String,
     pub public_display_name: Option<String>,
+    /// Whether `GET /api/users/{id}/public` shows this user's profile
+    #[sqlx(try_from = "NullAsFalse")]
+    pub is_public: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 #[typeshare]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 #[allow(dead_code)] // Will be used when we implement user management
 pub struct CreateUserRequest {
     pub email: String,
     pub name: String,
     pub google_id: String,
 }
+
+/// Counts for the user stats route, within the caller's household. Trashed items
+/// are not counted.
+#[typeshare]
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct UserStats {
+    /// Items whose `belongs_to_user_id` is this user
+    pub items_owned: i32,
+    pub items_created: i32,
+    pub rooms_created: i32,
+    pub containers_created: i32,
+}
+
+/// Most items listed on a public profile
+pub const MAX_PUBLIC_PROFILE_ITEMS: i64 = 20;
+
+/// Longest `public_display_name` accepted
+pub const MAX_PUBLIC_DISPLAY_NAME_CHARS: usize = 255;
+
+/// A user's public profile, for anyone once they have set `is_public`
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicUserResponse {
+    pub id: Uuid,
+    pub public_display_name: Option<String>,
+    /// Live items the user created or owns
+    pub item_count: i32,
+    /// The most recently created of those items
+    pub public_items: Vec<PublicItemResponse>,
+}
+
+/// Body of `PUT /api/users/me/profile`, replacing both settings
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateProfileRequest {
+    /// Shown instead of the account name on public pages; blank or omitted clears it
+    #[serde(default)]
+    pub public_display_name: Option<String>,
+    pub is_public: bool,
+}
+
+impl UpdateProfileRequest {
+    /// The trimmed display name, `None` when blank
+    pub fn display_name(&self) -> Result<Option<String>, &'static str> {
+        let name = self
+            .public_display_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty());
+
+        match name {
+            Some(name) if name.chars().count() > MAX_PUBLIC_DISPLAY_NAME_CHARS => {
+                Err("public_display_name must be at most 255 characters")
+            }
+            name => Ok(name.map(str::to_string)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(name: Option<&str>) -> UpdateProfileRequest {
+        UpdateProfileRequest {
+            public_display_name: name.map(str::to_string),
+            is_public: true,
+        }
+    }
+
+    #[test]
+    fn test_update_profile_display_name() {
+        assert_eq!(
+            request(Some("  Tool Library ")).display_name(),
+            Ok(Some("Tool Library".to_string()))
+        );
+        assert_eq!(request(Some("   ")).display_name(), Ok(None));
+        assert_eq!(request(None).display_name(), Ok(None));
+
+        let too_long = "x".repeat(MAX_PUBLIC_DISPLAY_NAME_CHARS + 1);
+        assert!(request(Some(&too_long)).display_name().is_err());
+    }
+}
@@ -0,0 +1,274 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::net::{IpAddr, Ipv4Addr};
+use typeshare::typeshare;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Shortest accepted signing secret
+pub const MIN_WEBHOOK_SECRET_LEN: usize = 16;
+
+/// A webhook as stored; `event_types` is a JSON array in a TEXT column
+#[derive(Debug, Clone, FromRow)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub event_types: String,
+    pub active: bool,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    /// The stored event patterns. A malformed column matches nothing.
+    pub fn event_patterns(&self) -> Vec<String> {
+        serde_json::from_str(&self.event_types).unwrap_or_else(|e| {
+            tracing::warn!("Webhook {} has invalid event_types: {}", self.id, e);
+            Vec::new()
+        })
+    }
+
+    /// Whether an audit entry for `entity_type`/`action` should be delivered here
+    pub fn matches(&self, entity_type: &str, action: &str) -> bool {
+        self.active
+            && self
+                .event_patterns()
+                .iter()
+                .any(|pattern| event_matches(pattern, entity_type, action))
+    }
+}
+
+/// Match an `"<entity_type>.<action>"` pattern, where either side may be `*` and a
+/// bare `*` matches everything. Case-insensitive, so `item.create` matches the
+/// `CREATE` audit action.
+pub fn event_matches(pattern: &str, entity_type: &str, action: &str) -> bool {
+    let pattern = pattern.trim();
+    if pattern == "*" {
+        return true;
+    }
+    let Some((pattern_type, pattern_action)) = pattern.split_once('.') else {
+        return false;
+    };
+    (pattern_type == "*" || pattern_type.eq_ignore_ascii_case(entity_type))
+        && (pattern_action == "*" || pattern_action.eq_ignore_ascii_case(action))
+}
+
+/// Check the patterns in a create/update request
+pub fn validate_event_types(event_types: &[String]) -> Result<(), String> {
+    if event_types.is_empty() {
+        return Err("event_types must not be empty".to_string());
+    }
+    for pattern in event_types {
+        let pattern = pattern.trim();
+        if pattern != "*"
+            && !pattern
+                .split_once('.')
+                .is_some_and(|(t, a)| !t.is_empty() && !a.is_empty())
+        {
+            return Err(format!(
+                "Invalid event type '{}': expected <entity_type>.<action>",
+                pattern
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether a webhook may be delivered to `ip`: loopback, link-local, private and
+/// other non-routable addresses would let a webhook probe the server's own network
+pub fn is_public_webhook_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    // 100.64.0.0/10 is carrier-grade NAT, which `is_private` doesn't cover
+    let shared = ip.octets()[0] == 100 && (ip.octets()[1] & 0xC0) == 64;
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || shared)
+}
+
+/// Webhook URLs must be absolute http(s) URLs that don't point at loopback,
+/// link-local or private addresses. Hostnames are checked again once resolved, at
+/// delivery time.
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    match parsed.scheme() {
+        "http" | "https" => {}
+        scheme => return Err(format!("Unsupported webhook URL scheme: {}", scheme)),
+    }
+
+    let public = match parsed.host() {
+        Some(url::Host::Ipv4(ip)) => is_public_webhook_ip(IpAddr::V4(ip)),
+        Some(url::Host::Ipv6(ip)) => is_public_webhook_ip(IpAddr::V6(ip)),
+        Some(url::Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain != "localhost" && !domain.ends_with(".localhost")
+        }
+        None => return Err("Webhook URL has no host".to_string()),
+    };
+    if !public {
+        return Err(format!("Webhook URL points at a private address: {}", url));
+    }
+    Ok(())
+}
+
+#[typeshare]
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookResponse {
+    pub id: Uuid,
+    pub url: String,
+    /// Patterns like `item.create`, `container.*` or `*`
+    pub event_types: Vec<String>,
+    pub active: bool,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            event_types: webhook.event_patterns(),
+            id: webhook.id,
+            url: webhook.url,
+            active: webhook.active,
+            created_by: webhook.created_by,
+            created_at: webhook.created_at,
+            updated_at: webhook.updated_at,
+        }
+    }
+}
+
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    /// Key for the `X-Inventory-Signature` HMAC. Never returned by the API.
+    pub secret: String,
+    pub event_types: Vec<String>,
+    /// Defaults to true
+    pub active: Option<bool>,
+}
+
+#[typeshare]
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateWebhookRequest {
+    pub url: Option<String>,
+    pub secret: Option<String>,
+    pub event_types: Option<Vec<String>>,
+    pub active: Option<bool>,
+}
+
+/// One delivery attempt
+#[typeshare]
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub audit_log_id: Uuid,
+    /// `<entity_type>.<action>`, e.g. `item.CREATE`
+    pub event: String,
+    /// 1-based
+    pub attempt: i32,
+    pub succeeded: bool,
+    /// `None` when no response was received
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook(event_types: &str, active: bool) -> Webhook {
+        Webhook {
+            id: Uuid::new_v4(),
+            url: "https://example.com/hook".to_string(),
+            secret: "0123456789abcdef".to_string(),
+            event_types: event_types.to_string(),
+            active,
+            created_by: Uuid::new_v4(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_event_matches() {
+        assert!(event_matches("item.create", "item", "CREATE"));
+        assert!(event_matches("item.*", "item", "SOFT_DELETE"));
+        assert!(event_matches("*.delete", "container", "DELETE"));
+        assert!(event_matches("*", "room", "MOVE"));
+        assert!(!event_matches("item.create", "item", "UPDATE"));
+        assert!(!event_matches("item.create", "container", "CREATE"));
+        assert!(!event_matches("item", "item", "CREATE"));
+    }
+
+    #[test]
+    fn test_webhook_matches() {
+        let hook = webhook(r#"["item.create", "container.*"]"#, true);
+        assert!(hook.matches("item", "CREATE"));
+        assert!(hook.matches("container", "MOVE"));
+        assert!(!hook.matches("item", "DELETE"));
+
+        assert!(!webhook(r#"["*"]"#, false).matches("item", "CREATE"));
+        assert!(!webhook("not json", true).matches("item", "CREATE"));
+    }
+
+    #[test]
+    fn test_validate_event_types() {
+        assert!(validate_event_types(&["item.create".to_string(), "*".to_string()]).is_ok());
+        assert!(validate_event_types(&[]).is_err());
+        assert!(validate_event_types(&["item".to_string()]).is_err());
+        assert!(validate_event_types(&["item.".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_validate_webhook_url() {
+        assert!(validate_webhook_url("https://example.com/hook").is_ok());
+        assert!(validate_webhook_url("http://93.184.215.14/hook").is_ok());
+        assert!(validate_webhook_url("ftp://example.com").is_err());
+        assert!(validate_webhook_url("example.com/hook").is_err());
+
+        // Nothing on the server's own network
+        assert!(validate_webhook_url("http://localhost:8123/api/webhook/x").is_err());
+        assert!(validate_webhook_url("http://api.localhost./hook").is_err());
+        assert!(validate_webhook_url("http://127.0.0.1/hook").is_err());
+        assert!(validate_webhook_url("http://169.254.169.254/latest/meta-data").is_err());
+        assert!(validate_webhook_url("http://10.0.0.5/hook").is_err());
+        assert!(validate_webhook_url("http://192.168.1.20:8123/hook").is_err());
+        assert!(validate_webhook_url("http://100.64.0.1/hook").is_err());
+        assert!(validate_webhook_url("http://[::1]/hook").is_err());
+        assert!(validate_webhook_url("http://[fe80::1]/hook").is_err());
+        assert!(validate_webhook_url("http://[fd00::1]/hook").is_err());
+        assert!(validate_webhook_url("http://[::ffff:10.0.0.1]/hook").is_err());
+    }
+
+    #[test]
+    fn test_webhook_response_omits_secret() {
+        let json = serde_json::to_value(WebhookResponse::from(webhook(r#"["*"]"#, true))).unwrap();
+        assert!(json.get("secret").is_none());
+        assert_eq!(json["event_types"], serde_json::json!(["*"]));
+    }
+}
@@ -1,18 +1,22 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::Json,
     Router,
 };
 use serde::Deserialize;
 use std::sync::Arc;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 use crate::app::AppState;
-use crate::models::audit::AuditLogResponse;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::audit::{AuditLogResponse, PurgeAuditLogsRequest, PurgeAuditLogsResponse};
+use crate::routes::users::{ensure_admin, is_admin};
+use crate::services::household::HouseholdEntity;
 use chrono::{DateTime, Utc};
 use serde_json::Value as JsonValue;
-use sqlx::FromRow;
+use sqlx::{FromRow, Postgres, QueryBuilder};
 
 // Extended audit log with user name from join
 #[derive(Debug, FromRow)]
@@ -44,7 +48,8 @@ impl From<AuditLogWithUser> for AuditLogResponse {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct AuditLogsQuery {
     pub entity_type: Option<String>,
     pub entity_id: Option<Uuid>,
@@ -54,71 +59,116 @@ pub struct AuditLogsQuery {
     pub offset: Option<i64>,
 }
 
-/// Get audit logs with optional filters
-pub async fn get_audit_logs(
-    State(state): State<Arc<AppState>>,
-    Query(params): Query<AuditLogsQuery>,
-) -> Result<Json<Vec<AuditLogResponse>>, StatusCode> {
-    let limit = params.limit.unwrap_or(100).min(1000);
-    let offset = params.offset.unwrap_or(0);
-
-    let mut query = "SELECT * FROM audit_logs WHERE 1=1".to_string();
-    let mut bind_count = 1;
+/// Build the filtered audit log query, binding only the filters that are set
+fn audit_logs_query(
+    params: &AuditLogsQuery,
+    limit: i64,
+    offset: i64,
+) -> QueryBuilder<'_, Postgres> {
+    let mut query = QueryBuilder::new(
+        r#"
+        SELECT
+            al.*,
+            u.name as user_name
+        FROM audit_logs al
+        LEFT JOIN users u ON al.user_id = u.id
+        WHERE 1=1
+        "#,
+    );
 
-    if let Some(ref _entity_type) = params.entity_type {
-        query.push_str(&format!(" AND entity_type = ${}", bind_count));
-        bind_count += 1;
+    if let Some(ref entity_type) = params.entity_type {
+        query.push(" AND al.entity_type = ").push_bind(entity_type);
     }
-    if let Some(_entity_id) = params.entity_id {
-        query.push_str(&format!(" AND entity_id = ${}", bind_count));
-        bind_count += 1;
+    if let Some(entity_id) = params.entity_id {
+        query.push(" AND al.entity_id = ").push_bind(entity_id);
     }
-    if let Some(_user_id) = params.user_id {
-        query.push_str(&format!(" AND user_id = ${}", bind_count));
-        bind_count += 1;
+    if let Some(user_id) = params.user_id {
+        query.push(" AND al.user_id = ").push_bind(user_id);
     }
-    if let Some(ref _action) = params.action {
-        query.push_str(&format!(" AND action = ${}", bind_count));
-        bind_count += 1;
+    if let Some(ref action) = params.action {
+        query.push(" AND al.action = ").push_bind(action);
     }
 
-    query.push_str(" ORDER BY created_at DESC");
-    query.push_str(&format!(
-        " LIMIT ${} OFFSET ${}",
-        bind_count,
-        bind_count + 1
-    ));
+    query
+        .push(" ORDER BY al.created_at DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
 
-    // Query with user name join
-    let logs = sqlx::query_as::<_, AuditLogWithUser>(
-        r#"
-        SELECT 
-            al.*,
-            u.name as user_name
-        FROM audit_logs al
-        LEFT JOIN users u ON al.user_id = u.id
-        ORDER BY al.created_at DESC
-        LIMIT $1 OFFSET $2
-        "#,
+    query
+}
+
+/// Get audit logs with optional filters (admin only, since entries span households)
+#[utoipa::path(
+    get,
+    path = "/api/audit",
+    tag = "audit",
+    params(
+        AuditLogsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<AuditLogResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 403, description = "Not the admin", body = ErrorResponse)
     )
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch audit logs: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+)]
+pub async fn get_audit_logs(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<AuditLogsQuery>,
+) -> Result<Json<Vec<AuditLogResponse>>, ApiError> {
+    ensure_admin(&state, user_id).await?;
+
+    let limit = params.limit.unwrap_or(100).clamp(1, 1000);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let logs = audit_logs_query(&params, limit, offset)
+        .build_query_as::<AuditLogWithUser>()
+        .fetch_all(&state.db)
+        .await?;
 
     let responses: Vec<AuditLogResponse> = logs.into_iter().map(AuditLogResponse::from).collect();
     Ok(Json(responses))
 }
 
-/// Get audit logs for a specific entity
+/// Get audit logs for a specific entity. Entities outside the household scheme
+/// (labels, photos, templates, ...) are only shown to the admin; anyone else gets 404.
+#[utoipa::path(
+    get,
+    path = "/api/audit/entity/{entity_type}/{entity_id}",
+    tag = "audit",
+    params(
+        ("entity_type" = String, Path, description = "Entity type, e.g. `item`"),
+        ("entity_id" = Uuid, Path, description = "Entity id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<AuditLogResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Entity not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_audit_logs_by_entity(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path((entity_type, entity_id)): Path<(String, Uuid)>,
-) -> Result<Json<Vec<AuditLogResponse>>, StatusCode> {
+) -> Result<Json<Vec<AuditLogResponse>>, ApiError> {
+    // Locations and items must be in the user's household
+    match HouseholdEntity::from_entity_type(&entity_type) {
+        Some(entity) => {
+            state.authorize_entity(user_id, entity, entity_id).await?;
+        }
+        None if is_admin(&state, user_id).await? => {}
+        None => {
+            tracing::warn!(
+                "User {} requested the audit history of {} {}",
+                user_id,
+                entity_type,
+                entity_id
+            );
+            return Err(ApiError::not_found(&entity_type, entity_id));
+        }
+    }
+
     let logs = sqlx::query_as::<_, AuditLogWithUser>(
         r#"
         SELECT 
@@ -133,19 +183,84 @@ pub async fn get_audit_logs_by_entity(
     .bind(&entity_type)
     .bind(entity_id)
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch audit logs: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     let responses: Vec<AuditLogResponse> = logs.into_iter().map(AuditLogResponse::from).collect();
     Ok(Json(responses))
 }
 
+/// Rows removed per purge statement, so no single delete holds locks on (or
+/// exceeds DSQL's per-transaction row limit for) a large slice of the table
+const AUDIT_PURGE_BATCH_SIZE: i64 = 1000;
+
+/// Delete one batch of entries older than `$1` days. DSQL has no `DELETE ... LIMIT`,
+/// so the batch is picked by a subquery.
+const PURGE_AUDIT_LOGS_BATCH_SQL: &str = r#"
+    DELETE FROM audit_logs
+    WHERE id IN (
+        SELECT id FROM audit_logs
+        WHERE created_at < NOW() - INTERVAL '1 day' * $1
+        LIMIT $2
+    )
+"#;
+
+/// Delete audit log entries older than `older_than_days`, in batches until none
+/// are left (admin only)
+#[utoipa::path(
+    delete,
+    path = "/api/admin/audit-logs/purge",
+    tag = "audit",
+    request_body = PurgeAuditLogsRequest,
+    responses(
+        (status = 200, description = "OK", body = PurgeAuditLogsResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn purge_audit_logs(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Json(payload): Json<PurgeAuditLogsRequest>,
+) -> Result<Json<PurgeAuditLogsResponse>, ApiError> {
+    ensure_admin(&state, user_id).await?;
+
+    if payload.older_than_days < 1 {
+        return Err(ApiError::bad_request("older_than_days must be at least 1"));
+    }
+
+    let mut deleted_count: u64 = 0;
+    let mut batches: i32 = 0;
+    loop {
+        let result = sqlx::query(PURGE_AUDIT_LOGS_BATCH_SQL)
+            .bind(payload.older_than_days)
+            .bind(AUDIT_PURGE_BATCH_SIZE)
+            .execute(&state.db)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            break;
+        }
+        deleted_count += result.rows_affected();
+        batches += 1;
+    }
+
+    tracing::info!(
+        "User {} purged {} audit log entries older than {} days in {} batches",
+        user_id,
+        deleted_count,
+        payload.older_than_days,
+        batches
+    );
+
+    Ok(Json(PurgeAuditLogsResponse {
+        deleted_count,
+        batches,
+    }))
+}
+
 /// Create audit routes
 pub fn audit_routes() -> Router<Arc<AppState>> {
-    use axum::routing::get;
+    use axum::routing::{delete, get};
 
     Router::new()
         .route("/api/audit", get(get_audit_logs))
@@ -153,4 +268,172 @@ pub fn audit_routes() -> Router<Arc<AppState>> {
             "/api/audit/entity/:entity_type/:entity_id",
             get(get_audit_logs_by_entity),
         )
+        .route("/api/admin/audit-logs/purge", delete(purge_audit_logs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_pool, test_app_state};
+    use sqlx::PgPool;
+
+    #[test]
+    fn test_audit_logs_query_binds_only_active_filters() {
+        let params = AuditLogsQuery::default();
+        let query = audit_logs_query(&params, 100, 0);
+        let sql = query.sql();
+        assert!(!sql.contains("al.entity_type = $"));
+        assert!(sql.contains("LIMIT $1 OFFSET $2"));
+
+        let params = AuditLogsQuery {
+            entity_type: Some("item".to_string()),
+            action: Some("CREATE".to_string()),
+            ..Default::default()
+        };
+        let query = audit_logs_query(&params, 100, 0);
+        let sql = query.sql();
+        assert!(sql.contains("al.entity_type = $1"));
+        assert!(sql.contains("al.action = $2"));
+        assert!(!sql.contains("al.entity_id = $"));
+        // The users join compares al.user_id too, so only a bound filter counts
+        assert!(!sql.contains("al.user_id = $"));
+        assert!(sql.contains("LIMIT $3 OFFSET $4"));
+    }
+
+    async fn fetch(pool: &PgPool, params: &AuditLogsQuery) -> Vec<AuditLogWithUser> {
+        audit_logs_query(params, 1000, 0)
+            .build_query_as::<AuditLogWithUser>()
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_audit_log_filters_restrict_results() {
+        let pool = create_test_pool().await;
+        // Unique entity types keep this test's rows apart from everything else; the
+        // column holds 20 characters
+        let suffix = &Uuid::new_v4().simple().to_string()[..12];
+        let type_a = format!("test-a-{}", suffix);
+        let type_b = format!("test-b-{}", suffix);
+        let entity_1 = Uuid::new_v4();
+        let entity_2 = Uuid::new_v4();
+        let user_1 = Uuid::new_v4();
+        let user_2 = Uuid::new_v4();
+
+        let rows = [
+            (&type_a, entity_1, "CREATE", user_1),
+            (&type_a, entity_1, "UPDATE", user_2),
+            (&type_a, entity_2, "CREATE", user_2),
+            (&type_b, entity_2, "DELETE", user_1),
+        ];
+        for (entity_type, entity_id, action, user_id) in rows {
+            sqlx::query(
+                "INSERT INTO audit_logs (id, entity_type, entity_id, action, user_id) VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(entity_type)
+            .bind(entity_id)
+            .bind(action)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let by_type = fetch(
+            &pool,
+            &AuditLogsQuery {
+                entity_type: Some(type_a.clone()),
+                ..Default::default()
+            },
+        )
+        .await;
+        let by_entity = fetch(
+            &pool,
+            &AuditLogsQuery {
+                entity_id: Some(entity_2),
+                ..Default::default()
+            },
+        )
+        .await;
+        let by_user = fetch(
+            &pool,
+            &AuditLogsQuery {
+                user_id: Some(user_1),
+                ..Default::default()
+            },
+        )
+        .await;
+        let by_action = fetch(
+            &pool,
+            &AuditLogsQuery {
+                entity_id: Some(entity_1),
+                action: Some("UPDATE".to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
+        let combined = fetch(
+            &pool,
+            &AuditLogsQuery {
+                entity_type: Some(type_a.clone()),
+                entity_id: Some(entity_2),
+                user_id: Some(user_2),
+                action: Some("CREATE".to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        sqlx::query("DELETE FROM audit_logs WHERE entity_type = $1 OR entity_type = $2")
+            .bind(&type_a)
+            .bind(&type_b)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(by_type.len(), 3);
+        assert!(by_type.iter().all(|log| log.entity_type == type_a));
+
+        assert_eq!(by_entity.len(), 2);
+        assert!(by_entity.iter().all(|log| log.entity_id == entity_2));
+
+        assert_eq!(by_user.len(), 2);
+        assert!(by_user.iter().all(|log| log.user_id == Some(user_1)));
+
+        assert_eq!(by_action.len(), 1);
+        assert_eq!(by_action[0].action, "UPDATE");
+        assert_eq!(by_action[0].user_id, Some(user_2));
+
+        assert_eq!(combined.len(), 1);
+        assert_eq!(combined[0].entity_id, entity_2);
+        assert_eq!(combined[0].entity_type, type_a);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_audit_logs_hidden_from_non_admins() {
+        let pool = create_test_pool().await;
+        // No ADMIN_EMAIL is configured, so nobody is the admin
+        let state = test_app_state(pool);
+        let user_id = Uuid::new_v4();
+
+        let all = get_audit_logs(
+            State(state.clone()),
+            AuthUser(user_id),
+            Query(AuditLogsQuery::default()),
+        )
+        .await;
+        let label = get_audit_logs_by_entity(
+            State(state),
+            AuthUser(user_id),
+            Path(("label".to_string(), Uuid::new_v4())),
+        )
+        .await;
+
+        assert!(matches!(all, Err(ApiError::Forbidden(_))));
+        assert!(matches!(label, Err(ApiError::NotFound(_))));
+    }
 }
@@ -6,15 +6,31 @@ use axum::{
     routing::{get, post},
     Json, Router,
 };
-use oauth2::{reqwest::async_http_client, AuthorizationCode, CsrfToken, Scope, TokenResponse};
+use chrono::{DateTime, Duration, Utc};
+use oauth2::{
+    basic::BasicTokenType, reqwest::async_http_client, AuthorizationCode, CsrfToken, Scope,
+    TokenResponse,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tower_sessions::Session;
+use utoipa::{IntoParams, ToSchema};
 
 const AUTH_URL: &str = "https://www.googleapis.com/oauth2/v2/userinfo";
 
-#[derive(Debug, Deserialize)]
-struct AuthRequest {
+/// Session key holding the [`UserSession`]
+pub const USER_SESSION_KEY: &str = "user";
+/// Session key holding the [`OAuthTokens`]
+pub const OAUTH_TOKENS_SESSION_KEY: &str = "oauth_tokens";
+
+/// Google access tokens last an hour; assume that if the response doesn't say
+const DEFAULT_TOKEN_LIFETIME_SECS: i64 = 3600;
+/// Refresh the access token when it has less than this long left
+const TOKEN_REFRESH_WINDOW_SECS: i64 = 5 * 60;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuthRequest {
     code: String,
     state: String,
 }
@@ -27,7 +43,7 @@ struct GoogleUser {
     picture: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UserSession {
     pub user_id: uuid::Uuid,
     pub email: String,
@@ -35,6 +51,51 @@ pub struct UserSession {
     pub picture: Option<String>,
 }
 
+/// Google OAuth tokens, kept in the session separately from [`UserSession`] so they are
+/// never returned by `/api/auth/me`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthTokens {
+    pub access_token: String,
+    /// Only issued when the user consents to offline access
+    pub refresh_token: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl OAuthTokens {
+    /// Tokens from a code exchange or refresh. Google doesn't return a new refresh
+    /// token on refresh, so `previous_refresh_token` is kept in that case.
+    pub fn from_response(
+        token: &impl TokenResponse<BasicTokenType>,
+        previous_refresh_token: Option<String>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let lifetime = token
+            .expires_in()
+            .and_then(|expires_in| Duration::from_std(expires_in).ok())
+            .unwrap_or_else(|| Duration::seconds(DEFAULT_TOKEN_LIFETIME_SECS));
+
+        Self {
+            access_token: token.access_token().secret().clone(),
+            refresh_token: token
+                .refresh_token()
+                .map(|refresh_token| refresh_token.secret().clone())
+                .or(previous_refresh_token),
+            expires_at: now + lifetime,
+        }
+    }
+
+    /// Whether the access token expires within the refresh window
+    pub fn needs_refresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at - now < Duration::seconds(TOKEN_REFRESH_WINDOW_SECS)
+    }
+}
+
+/// The refresh token held in the session, if any
+async fn stored_refresh_token(session: &Session) -> Option<String> {
+    let tokens: Option<OAuthTokens> = session.get(OAUTH_TOKENS_SESSION_KEY).await.unwrap_or(None);
+    tokens.and_then(|tokens| tokens.refresh_token)
+}
+
 pub fn auth_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/api/auth/login", get(login_handler))
@@ -43,13 +104,31 @@ pub fn auth_routes() -> Router<Arc<AppState>> {
         .route("/api/auth/logout", post(logout_handler))
 }
 
-async fn login_handler(State(state): State<Arc<AppState>>, session: Session) -> impl IntoResponse {
-    let (auth_url, csrf_token) = state
+#[utoipa::path(
+    get,
+    path = "/api/auth/login",
+    tag = "auth",
+    responses(
+        (status = 303, description = "Redirect to Google sign-in")
+    ),
+    security(())
+)]
+pub async fn login_handler(
+    State(state): State<Arc<AppState>>,
+    session: Session,
+) -> impl IntoResponse {
+    let mut request = state
         .oauth_client
         .authorize_url(CsrfToken::new_random)
         .add_scope(Scope::new("email".to_string()))
         .add_scope(Scope::new("profile".to_string()))
-        .url();
+        // Google's equivalent of the offline_access scope: issue a refresh token
+        .add_extra_param("access_type", "offline");
+    // A refresh token is only sent on consent, so ask for consent while we don't have one
+    if stored_refresh_token(&session).await.is_none() {
+        request = request.add_extra_param("prompt", "consent");
+    }
+    let (auth_url, csrf_token) = request.url();
 
     // Store the csrf_token in the session to verify later
     session
@@ -60,7 +139,19 @@ async fn login_handler(State(state): State<Arc<AppState>>, session: Session) ->
     Redirect::to(auth_url.as_str())
 }
 
-async fn auth_callback(
+#[utoipa::path(
+    get,
+    path = "/api/auth/callback",
+    tag = "auth",
+    params(
+        AuthRequest
+    ),
+    responses(
+        (status = 303, description = "Redirect to the app once signed in")
+    ),
+    security(())
+)]
+pub async fn auth_callback(
     Query(query): Query<AuthRequest>,
     State(state): State<Arc<AppState>>,
     session: Session,
@@ -124,6 +215,14 @@ async fn auth_callback(
         }
     };
 
+    // Signing in again without consent brings no refresh token, so keep the one this
+    // session already holds for the same user
+    let previous_user: Option<UserSession> = session.get(USER_SESSION_KEY).await.unwrap_or(None);
+    let previous_refresh_token = match previous_user {
+        Some(previous) if previous.user_id == user_id => stored_refresh_token(&session).await,
+        _ => None,
+    };
+
     // Create session
     let user_session = UserSession {
         user_id,
@@ -133,23 +232,47 @@ async fn auth_callback(
     };
 
     session
-        .insert("user", user_session)
+        .insert(USER_SESSION_KEY, user_session)
         .await
         .expect("Failed to create session");
+    session
+        .insert(
+            OAUTH_TOKENS_SESSION_KEY,
+            OAuthTokens::from_response(&token, previous_refresh_token, Utc::now()),
+        )
+        .await
+        .expect("Failed to store OAuth tokens");
 
     // Redirect to frontend
     Redirect::to(&state.app_base_url).into_response()
 }
 
-async fn get_me_handler(session: Session) -> impl IntoResponse {
-    let user: Option<UserSession> = session.get("user").await.unwrap_or(None);
+#[utoipa::path(
+    get,
+    path = "/api/auth/me",
+    tag = "auth",
+    responses(
+        (status = 200, description = "The signed-in user", body = UserSession),
+        (status = 401, description = "Not logged in")
+    )
+)]
+pub async fn get_me_handler(session: Session) -> impl IntoResponse {
+    let user: Option<UserSession> = session.get(USER_SESSION_KEY).await.unwrap_or(None);
     match user {
         Some(u) => Json(u).into_response(),
         None => (StatusCode::UNAUTHORIZED, "Not logged in").into_response(),
     }
 }
 
-async fn logout_handler(session: Session) -> impl IntoResponse {
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Session cleared")
+    )
+)]
+pub async fn logout_handler(session: Session) -> impl IntoResponse {
     session.flush().await.ok();
     StatusCode::OK
 }
@@ -162,8 +285,8 @@ async fn upsert_user(pool: &sqlx::PgPool, google_user: &GoogleUser) -> anyhow::R
     }
     let row = sqlx::query_as::<_, UserId>(
         r#"
-        INSERT INTO users (id, email, name, google_id)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO users (id, email, name, google_id, is_public)
+        VALUES ($1, $2, $3, $4, false)
         ON CONFLICT (google_id)
         DO UPDATE SET name = $3, email = $2, updated_at = NOW()
         RETURNING id
@@ -178,3 +301,65 @@ async fn upsert_user(pool: &sqlx::PgPool, google_user: &GoogleUser) -> anyhow::R
 
     Ok(row.id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens_expiring_at(expires_at: DateTime<Utc>) -> OAuthTokens {
+        OAuthTokens {
+            access_token: "access".to_string(),
+            refresh_token: Some("refresh".to_string()),
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn test_needs_refresh_within_five_minutes_of_expiry() {
+        let now = Utc::now();
+        assert!(!tokens_expiring_at(now + Duration::minutes(30)).needs_refresh(now));
+        assert!(tokens_expiring_at(now + Duration::minutes(4)).needs_refresh(now));
+        assert!(tokens_expiring_at(now - Duration::minutes(1)).needs_refresh(now));
+    }
+
+    #[tokio::test]
+    async fn test_stored_refresh_token() {
+        let store = Arc::new(tower_sessions::MemoryStore::default());
+        let session = Session::new(None, store, None);
+        assert_eq!(stored_refresh_token(&session).await, None);
+
+        let mut tokens = tokens_expiring_at(Utc::now());
+        tokens.refresh_token = None;
+        session
+            .insert(OAUTH_TOKENS_SESSION_KEY, tokens.clone())
+            .await
+            .unwrap();
+        assert_eq!(stored_refresh_token(&session).await, None);
+
+        tokens.refresh_token = Some("refresh".to_string());
+        session
+            .insert(OAUTH_TOKENS_SESSION_KEY, tokens)
+            .await
+            .unwrap();
+        assert_eq!(
+            stored_refresh_token(&session).await.as_deref(),
+            Some("refresh")
+        );
+    }
+
+    #[test]
+    fn test_from_response_keeps_previous_refresh_token() {
+        let now = Utc::now();
+        let mut token = oauth2::basic::BasicTokenResponse::new(
+            oauth2::AccessToken::new("new-access".to_string()),
+            BasicTokenType::Bearer,
+            oauth2::EmptyExtraTokenFields {},
+        );
+        token.set_expires_in(Some(&std::time::Duration::from_secs(3599)));
+
+        let tokens = OAuthTokens::from_response(&token, Some("refresh".to_string()), now);
+        assert_eq!(tokens.access_token, "new-access");
+        assert_eq!(tokens.refresh_token.as_deref(), Some("refresh"));
+        assert_eq!(tokens.expires_at, now + Duration::seconds(3599));
+    }
+}
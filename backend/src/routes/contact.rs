@@ -1,26 +1,93 @@
 use axum::{
-    extract::{ConnectInfo, Query, State},
-    http::StatusCode,
-    response::Json,
+    extract::{ConnectInfo, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
     Router,
 };
+use chrono::{DateTime, Utc};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
 use crate::models::{
-    ContactSubmission, ContactSubmissionResponse, CreateContactSubmissionRequest,
-    PaginatedResponse, PaginationQuery,
+    next_replied_at, ContactStatus, ContactSubmission, ContactSubmissionFilter,
+    ContactSubmissionResponse, CreateContactSubmissionRequest, PaginatedResponse, PaginationQuery,
+    UnreadCountResponse, UpdateContactSubmissionRequest,
 };
+use crate::routes::users::ensure_admin;
+
+/// Maximum contact submissions allowed per IP address within the rate limit window
+const CONTACT_RATE_LIMIT_MAX: i64 = 5;
+/// Rate limit window in seconds (must match the INTERVAL in the rate limit query)
+const CONTACT_RATE_LIMIT_WINDOW_SECS: i64 = 3600;
+
+/// Check whether an IP has exceeded the contact submission rate limit.
+/// Returns the number of seconds until the next submission is allowed, if limited.
+///
+/// Counts are taken from contact_submissions itself rather than an in-memory map,
+/// since Lambda instances don't share memory and old rows age out of the window
+/// without needing a purge task.
+async fn contact_rate_limit_retry_after(
+    state: &AppState,
+    ip_address: &str,
+) -> Result<Option<i64>, ApiError> {
+    let (count, oldest): (i64, Option<DateTime<Utc>>) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*), MIN(created_at)
+        FROM contact_submissions
+        WHERE ip_address = $1 AND created_at > NOW() - INTERVAL '1 hour'
+        "#,
+    )
+    .bind(ip_address)
+    .fetch_one(&state.db)
+    .await?;
+
+    if count < CONTACT_RATE_LIMIT_MAX {
+        return Ok(None);
+    }
+
+    let retry_after = oldest
+        .map(|oldest| CONTACT_RATE_LIMIT_WINDOW_SECS - (Utc::now() - oldest).num_seconds())
+        .unwrap_or(CONTACT_RATE_LIMIT_WINDOW_SECS)
+        .max(1);
+
+    Ok(Some(retry_after))
+}
 
 /// Create a new contact submission (public endpoint with reCAPTCHA)
+#[utoipa::path(
+    post,
+    path = "/api/contact",
+    tag = "contact",
+    request_body = CreateContactSubmissionRequest,
+    responses(
+        (status = 200, description = "OK", body = ContactSubmissionResponse),
+        (status = 400, description = "Invalid submission or failed reCAPTCHA", body = ErrorResponse),
+        (status = 429, description = "Too many submissions; see `Retry-After`")
+    ),
+    security(())
+)]
 pub async fn create_contact_submission(
     State(state): State<Arc<AppState>>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     headers: axum::http::HeaderMap,
     Json(payload): Json<CreateContactSubmissionRequest>,
-) -> Result<Json<ContactSubmissionResponse>, StatusCode> {
+) -> Result<Response, ApiError> {
+    let ip_address = addr.ip().to_string();
+
+    // Enforce per-IP rate limit before doing any external verification
+    if let Some(retry_after) = contact_rate_limit_retry_after(&state, &ip_address).await? {
+        tracing::warn!("Contact submission rate limit exceeded for {}", ip_address);
+        return Ok((
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+        )
+            .into_response());
+    }
+
     // Verify reCAPTCHA token
     state
         .captcha
@@ -28,7 +95,7 @@ pub async fn create_contact_submission(
         .await
         .map_err(|e| {
             tracing::warn!("reCAPTCHA verification failed: {:?}", e);
-            StatusCode::BAD_REQUEST
+            ApiError::bad_request("reCAPTCHA verification failed")
         })?;
 
     // Extract user agent from headers
@@ -40,32 +107,29 @@ pub async fn create_contact_submission(
     // Validate email format (basic check)
     if !payload.email.contains('@') {
         tracing::warn!("Invalid email format: {}", payload.email);
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request("Invalid email address"));
     }
 
     // If item_id is provided, verify it exists
     if let Some(item_id) = payload.item_id {
-        let item_exists: bool =
-            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM items WHERE id = $1)")
-                .bind(item_id)
-                .fetch_one(&state.db)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to check item existence: {:?}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+        let item_exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM items WHERE id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(item_id)
+        .fetch_one(&state.db)
+        .await?;
 
         if !item_exists {
             tracing::warn!("Item not found: {}", item_id);
-            return Err(StatusCode::NOT_FOUND);
+            return Err(ApiError::not_found("Item", item_id));
         }
     }
 
     // Create contact submission
     let submission = sqlx::query_as::<_, ContactSubmission>(
         r#"
-        INSERT INTO contact_submissions (id, name, email, subject, message, item_id, ip_address, user_agent)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        INSERT INTO contact_submissions (id, name, email, subject, message, item_id, ip_address, user_agent, status, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW())
         RETURNING *
         "#,
     )
@@ -75,14 +139,11 @@ pub async fn create_contact_submission(
     .bind(&payload.subject)
     .bind(&payload.message)
     .bind(payload.item_id)
-    .bind(addr.ip().to_string())
+    .bind(&ip_address)
     .bind(user_agent)
+    .bind(ContactStatus::Unread.as_str())
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to create contact submission: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     tracing::info!(
         "Contact submission created: {} from {} ({})",
@@ -91,39 +152,65 @@ pub async fn create_contact_submission(
         submission.name
     );
 
-    Ok(Json(ContactSubmissionResponse::from(submission)))
+    Ok(Json(ContactSubmissionResponse::from(submission)).into_response())
 }
 
-/// List all contact submissions (protected endpoint)
+/// List contact submissions, newest first, optionally filtered by `status` and
+/// `item_id` (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/contact",
+    tag = "contact",
+    params(
+        PaginationQuery,
+        ContactSubmissionFilter
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ContactSubmissionResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 403, description = "Not the admin", body = ErrorResponse)
+    )
+)]
 pub async fn list_contact_submissions(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ContactSubmissionResponse>>, StatusCode> {
+    Query(filter): Query<ContactSubmissionFilter>,
+) -> Result<Json<PaginatedResponse<ContactSubmissionResponse>>, ApiError> {
+    ensure_admin(&state, user_id).await?;
+
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
+    let status = filter.status.map(|status| status.as_str());
 
     // Get total count
-    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM contact_submissions")
-        .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count contact submissions: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    let total: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM contact_submissions
+        WHERE ($1::TEXT IS NULL OR status = $1) AND ($2::UUID IS NULL OR item_id = $2)
+        "#,
+    )
+    .bind(status)
+    .bind(filter.item_id)
+    .fetch_one(&state.db)
+    .await?;
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
     // Get paginated submissions
     let submissions = sqlx::query_as::<_, ContactSubmission>(
-        "SELECT * FROM contact_submissions ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+        r#"
+        SELECT * FROM contact_submissions
+        WHERE ($1::TEXT IS NULL OR status = $1) AND ($2::UUID IS NULL OR item_id = $2)
+        ORDER BY created_at DESC
+        LIMIT $3 OFFSET $4
+        "#,
     )
+    .bind(status)
+    .bind(filter.item_id)
     .bind(limit)
     .bind(offset)
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch contact submissions: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     let responses: Vec<ContactSubmissionResponse> = submissions
         .into_iter()
@@ -135,13 +222,161 @@ pub async fn list_contact_submissions(
     )))
 }
 
+/// Update a submission's status and admin notes (admin only). Marking it replied
+/// stamps `replied_at` the first time.
+#[utoipa::path(
+    patch,
+    path = "/api/contact/{id}",
+    tag = "contact",
+    params(
+        ("id" = Uuid, Path, description = "Contact submission id")
+    ),
+    request_body = UpdateContactSubmissionRequest,
+    responses(
+        (status = 200, description = "OK", body = ContactSubmissionResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 403, description = "Not the admin", body = ErrorResponse),
+        (status = 404, description = "Contact submission not found", body = ErrorResponse)
+    )
+)]
+pub async fn update_contact_submission(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateContactSubmissionRequest>,
+) -> Result<Json<ContactSubmissionResponse>, ApiError> {
+    ensure_admin(&state, user_id).await?;
+
+    let existing =
+        sqlx::query_as::<_, ContactSubmission>("SELECT * FROM contact_submissions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Contact submission", id))?;
+
+    let status = payload
+        .status
+        .unwrap_or_else(|| existing.status.parse().unwrap_or(ContactStatus::Unread));
+    let admin_notes = payload.admin_notes.apply(existing.admin_notes);
+    let replied_at = next_replied_at(status, existing.replied_at, Utc::now());
+
+    let submission = sqlx::query_as::<_, ContactSubmission>(
+        r#"
+        UPDATE contact_submissions
+        SET status = $1, admin_notes = $2, replied_at = $3, updated_at = NOW()
+        WHERE id = $4
+        RETURNING *
+        "#,
+    )
+    .bind(status.as_str())
+    .bind(&admin_notes)
+    .bind(replied_at)
+    .bind(id)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(ContactSubmissionResponse::from(submission)))
+}
+
+/// Number of unread submissions, for the dashboard badge (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/contact/unread-count",
+    tag = "contact",
+    responses(
+        (status = 200, description = "OK", body = UnreadCountResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 403, description = "Not the admin", body = ErrorResponse)
+    )
+)]
+pub async fn get_unread_contact_count(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+) -> Result<Json<UnreadCountResponse>, ApiError> {
+    ensure_admin(&state, user_id).await?;
+
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM contact_submissions WHERE status = $1")
+            .bind(ContactStatus::Unread.as_str())
+            .fetch_one(&state.db)
+            .await?;
+
+    Ok(Json(UnreadCountResponse {
+        count: count.clamp(0, i32::MAX as i64) as i32,
+    }))
+}
+
 /// Create contact routes
-/// Note: POST /api/contact is public (no auth), GET /api/contact is protected
+/// Note: POST /api/contact is public (no auth), the rest are for the admin
 #[allow(dead_code)]
 pub fn contact_routes() -> Router<Arc<AppState>> {
-    use axum::routing::{get, post};
+    use axum::routing::{get, patch, post};
 
     Router::new()
         .route("/api/contact", post(create_contact_submission))
         .route("/api/contact", get(list_contact_submissions))
+        .route("/api/contact/unread-count", get(get_unread_contact_count))
+        .route("/api/contact/:id", patch(update_contact_submission))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Clearable;
+    use crate::test_utils::{create_test_pool, test_app_state};
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_contact_submissions_only_for_the_admin() {
+        let pool = create_test_pool().await;
+        // No ADMIN_EMAIL is configured, so nobody is the admin
+        let state = test_app_state(pool.clone());
+        let user_id = Uuid::new_v4();
+        let submission_id = Uuid::new_v4();
+
+        sqlx::query(
+            "INSERT INTO contact_submissions (id, name, email, subject, message, status) VALUES ($1, 'Sam', 'sam@example.com', 'Found your ladder', 'Hi', 'unread')",
+        )
+        .bind(submission_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let list = list_contact_submissions(
+            State(state.clone()),
+            AuthUser(user_id),
+            Query(PaginationQuery::default()),
+            Query(ContactSubmissionFilter::default()),
+        )
+        .await;
+        let update = update_contact_submission(
+            State(state.clone()),
+            AuthUser(user_id),
+            Path(submission_id),
+            Json(UpdateContactSubmissionRequest {
+                status: Some(ContactStatus::Archived),
+                admin_notes: Clearable::Set("Not mine".to_string()),
+            }),
+        )
+        .await;
+        let unread = get_unread_contact_count(State(state), AuthUser(user_id)).await;
+        let status: String =
+            sqlx::query_scalar("SELECT status FROM contact_submissions WHERE id = $1")
+                .bind(submission_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+
+        sqlx::query("DELETE FROM contact_submissions WHERE id = $1")
+            .bind(submission_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(matches!(list, Err(ApiError::Forbidden(_))));
+        assert!(matches!(update, Err(ApiError::Forbidden(_))));
+        assert!(matches!(unread, Err(ApiError::Forbidden(_))));
+        assert_eq!(status, "unread");
+    }
 }
@@ -1,6 +1,5 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::Json,
     Router,
 };
@@ -9,212 +8,417 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
 use crate::middleware::auth::AuthUser;
+use crate::middleware::deprecation::patch_with_put_alias;
 use crate::models::{
-    Container, ContainerResponse, CreateContainerRequest, PaginatedResponse, PaginationQuery,
-    UpdateContainerRequest,
+    tsquery_from_search, AssignContainerLabelsResponse, Container, ContainerResponse,
+    ContainerSearchQuery, ContainerSearchResult, ContainerSubtreeQuery, ContainerSubtreeResponse,
+    ContainerSubtreeRow, ContainerTreeNode, CreateContainerRequest, PaginatedResponse,
+    PaginationQuery, PhotoResponse, ReorderContainersRequest, UpdateContainerRequest,
+    MAX_REORDER_CONTAINERS, MAX_SUBTREE_NODES,
 };
+use crate::routes::photos::{attach_photo_summaries, fetch_entity_photos, IncludePhotosQuery};
+use crate::routes::tags::{attach_tags, IncludeTagsQuery};
+use crate::services::audit::Auditable;
+use crate::services::household::HouseholdEntity;
+use crate::services::r#move as move_service;
 
-/// Get all containers
+const CONTAINER_LOCATION_REQUIRED: &str =
+    "Exactly one of shelf_id or parent_container_id is required";
+
+/// Containers with an explicit position come first; unpositioned (NULL) ones sort last
+/// `search` is `$2`, as a pattern from `PaginationQuery::search_pattern`
+const LIST_CONTAINERS_BY_SHELF_SQL: &str = "SELECT * FROM containers WHERE shelf_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2) ORDER BY position ASC NULLS LAST, created_at LIMIT $3 OFFSET $4";
+const LIST_CONTAINERS_BY_PARENT_SQL: &str = "SELECT * FROM containers WHERE parent_container_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2) ORDER BY position ASC NULLS LAST, created_at LIMIT $3 OFFSET $4";
+
+/// Get all containers in the user's household
+#[utoipa::path(
+    get,
+    path = "/api/containers",
+    tag = "containers",
+    params(
+        PaginationQuery,
+        IncludePhotosQuery,
+        IncludeTagsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ContainerResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn list_containers(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ContainerResponse>>, StatusCode> {
+    Query(photos): Query<IncludePhotosQuery>,
+    Query(tags): Query<IncludeTagsQuery>,
+) -> Result<Json<PaginatedResponse<ContainerResponse>>, ApiError> {
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
+    let household_id = state.resolve_household(user_id).await?;
+    let search_pattern = params.search_pattern();
 
-    // Get total count
-    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM containers")
-        .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count containers: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    // Get total count with search filter
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM containers WHERE household_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2)",
+    )
+    .bind(household_id)
+    .bind(&search_pattern)
+    .fetch_one(&state.db)
+    .await?;
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
-    // Get paginated containers
+    // Get paginated containers with search filter
     let containers = sqlx::query_as::<_, Container>(
-        "SELECT * FROM containers ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+        "SELECT * FROM containers WHERE household_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2) ORDER BY created_at DESC LIMIT $3 OFFSET $4",
     )
+    .bind(household_id)
+    .bind(&search_pattern)
     .bind(limit)
     .bind(offset)
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch containers: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
-    let responses: Vec<ContainerResponse> = containers
+    let mut responses: Vec<ContainerResponse> = containers
         .into_iter()
         .map(ContainerResponse::from)
         .collect();
+    if photos.include_photos {
+        attach_photo_summaries(&state, "container", &mut responses).await?;
+    }
+    if tags.include_tags {
+        attach_tags(&state, "container", &mut responses).await?;
+    }
     Ok(Json(PaginatedResponse::new(
         responses, total, limit, offset,
     )))
 }
 
+#[derive(sqlx::FromRow)]
+struct ContainerSearchRow {
+    #[sqlx(flatten)]
+    container: Container,
+    rank: f32,
+}
+
+const SEARCH_CONTAINERS_FTS_SQL: &str = r#"
+    SELECT *, ts_rank(to_tsvector('english', name || ' ' || COALESCE(description, '')), to_tsquery('english', $1)) AS rank
+    FROM containers
+    WHERE household_id = $3
+      AND to_tsvector('english', name || ' ' || COALESCE(description, '')) @@ to_tsquery('english', $1)
+    ORDER BY rank DESC
+    LIMIT $2
+"#;
+
+const SEARCH_CONTAINERS_ILIKE_SQL: &str = r#"
+    SELECT *, 0::REAL AS rank
+    FROM containers
+    WHERE household_id = $3 AND (name ILIKE $1 OR description ILIKE $1)
+    ORDER BY name
+    LIMIT $2
+"#;
+
+/// Search containers by name and description, ranked by relevance
+///
+/// Input that `to_tsquery` can't parse safely falls back to an unranked `ILIKE` match.
+#[utoipa::path(
+    get,
+    path = "/api/containers/search",
+    tag = "containers",
+    params(
+        ContainerSearchQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<ContainerSearchResult>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn search_containers(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<ContainerSearchQuery>,
+) -> Result<Json<Vec<ContainerSearchResult>>, ApiError> {
+    let q = params.q.trim();
+    if q.is_empty() {
+        return Err(ApiError::bad_request("Search term must not be empty"));
+    }
+    let limit = params.limit.unwrap_or(50).clamp(1, 1000);
+    let household_id = state.resolve_household(user_id).await?;
+
+    let rows = match tsquery_from_search(q) {
+        Some(tsquery) => sqlx::query_as::<_, ContainerSearchRow>(SEARCH_CONTAINERS_FTS_SQL)
+            .bind(tsquery)
+            .bind(limit)
+            .bind(household_id),
+        None => sqlx::query_as::<_, ContainerSearchRow>(SEARCH_CONTAINERS_ILIKE_SQL)
+            .bind(format!("%{}%", q))
+            .bind(limit)
+            .bind(household_id),
+    }
+    .fetch_all(&state.db)
+    .await?;
+
+    let results = rows
+        .into_iter()
+        .map(|row| ContainerSearchResult {
+            container: ContainerResponse::from(row.container),
+            rank: row.rank,
+        })
+        .collect();
+    Ok(Json(results))
+}
+
 /// Get containers by shelf
+#[utoipa::path(
+    get,
+    path = "/api/shelves/{shelf_id}/containers",
+    tag = "containers",
+    params(
+        ("shelf_id" = Uuid, Path, description = "Shelf id"),
+        PaginationQuery,
+        IncludePhotosQuery,
+        IncludeTagsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ContainerResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelf not found", body = ErrorResponse)
+    )
+)]
 pub async fn list_containers_by_shelf(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(shelf_id): Path<Uuid>,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ContainerResponse>>, StatusCode> {
+    Query(photos): Query<IncludePhotosQuery>,
+    Query(tags): Query<IncludeTagsQuery>,
+) -> Result<Json<PaginatedResponse<ContainerResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Shelf, shelf_id)
+        .await?;
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
+    let search_pattern = params.search_pattern();
 
-    // Get total count
-    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM containers WHERE shelf_id = $1")
-        .bind(shelf_id)
-        .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count containers: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    let total = total.clamp(0, i32::MAX as i64) as i32;
-
-    // Get paginated containers
-    let containers = sqlx::query_as::<_, Container>(
-        "SELECT * FROM containers WHERE shelf_id = $1 ORDER BY created_at LIMIT $2 OFFSET $3",
+    // Get total count with search filter
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM containers WHERE shelf_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2)",
     )
     .bind(shelf_id)
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch containers: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .bind(&search_pattern)
+    .fetch_one(&state.db)
+    .await?;
+    let total = total.clamp(0, i32::MAX as i64) as i32;
+
+    // Get paginated containers with search filter
+    let containers = sqlx::query_as::<_, Container>(LIST_CONTAINERS_BY_SHELF_SQL)
+        .bind(shelf_id)
+        .bind(&search_pattern)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&state.db)
+        .await?;
 
-    let responses: Vec<ContainerResponse> = containers
+    let mut responses: Vec<ContainerResponse> = containers
         .into_iter()
         .map(ContainerResponse::from)
         .collect();
+    if photos.include_photos {
+        attach_photo_summaries(&state, "container", &mut responses).await?;
+    }
+    if tags.include_tags {
+        attach_tags(&state, "container", &mut responses).await?;
+    }
     Ok(Json(PaginatedResponse::new(
         responses, total, limit, offset,
     )))
 }
 
 /// Get containers by parent container
+#[utoipa::path(
+    get,
+    path = "/api/containers/{parent_id}/children",
+    tag = "containers",
+    params(
+        ("parent_id" = Uuid, Path, description = "Parent container id"),
+        PaginationQuery,
+        IncludePhotosQuery,
+        IncludeTagsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ContainerResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Container not found", body = ErrorResponse)
+    )
+)]
 pub async fn list_containers_by_parent(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(parent_id): Path<Uuid>,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ContainerResponse>>, StatusCode> {
+    Query(photos): Query<IncludePhotosQuery>,
+    Query(tags): Query<IncludeTagsQuery>,
+) -> Result<Json<PaginatedResponse<ContainerResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Container, parent_id)
+        .await?;
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
+    let search_pattern = params.search_pattern();
 
-    // Get total count
-    let total: i64 =
-        sqlx::query_scalar("SELECT COUNT(*) FROM containers WHERE parent_container_id = $1")
-            .bind(parent_id)
-            .fetch_one(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to count containers: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+    // Get total count with search filter
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM containers WHERE parent_container_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2)",
+    )
+    .bind(parent_id)
+    .bind(&search_pattern)
+    .fetch_one(&state.db)
+    .await?;
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
-    // Get paginated containers
-    let containers = sqlx::query_as::<_, Container>(
-        "SELECT * FROM containers WHERE parent_container_id = $1 ORDER BY created_at LIMIT $2 OFFSET $3"
-    )
+    // Get paginated containers with search filter
+    let containers = sqlx::query_as::<_, Container>(LIST_CONTAINERS_BY_PARENT_SQL)
         .bind(parent_id)
+        .bind(&search_pattern)
         .bind(limit)
         .bind(offset)
         .fetch_all(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch containers: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
-    let responses: Vec<ContainerResponse> = containers
+    let mut responses: Vec<ContainerResponse> = containers
         .into_iter()
         .map(ContainerResponse::from)
         .collect();
+    if photos.include_photos {
+        attach_photo_summaries(&state, "container", &mut responses).await?;
+    }
+    if tags.include_tags {
+        attach_tags(&state, "container", &mut responses).await?;
+    }
     Ok(Json(PaginatedResponse::new(
         responses, total, limit, offset,
     )))
 }
 
 /// Get a single container by ID
+#[utoipa::path(
+    get,
+    path = "/api/containers/{id}",
+    tag = "containers",
+    params(
+        ("id" = Uuid, Path, description = "Container id"),
+        IncludeTagsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = ContainerResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Container not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_container(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<ContainerResponse>, StatusCode> {
+    Query(tags): Query<IncludeTagsQuery>,
+) -> Result<Json<ContainerResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Container, id)
+        .await?;
     let container = sqlx::query_as::<_, Container>("SELECT * FROM containers WHERE id = $1")
         .bind(id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch container: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::not_found("Container", id))?;
 
-    Ok(Json(ContainerResponse::from(container)))
+    let mut response = ContainerResponse::from(container);
+    if tags.include_tags {
+        attach_tags(&state, "container", std::slice::from_mut(&mut response)).await?;
+    }
+    Ok(Json(response))
 }
 
 /// Create a new container
+#[utoipa::path(
+    post,
+    path = "/api/containers",
+    tag = "containers",
+    request_body = CreateContainerRequest,
+    responses(
+        (status = 200, description = "OK", body = ContainerResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn create_container(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Json(payload): Json<CreateContainerRequest>,
-) -> Result<Json<ContainerResponse>, StatusCode> {
+) -> Result<Json<ContainerResponse>, ApiError> {
     // Validate location constraint: exactly one of shelf_id or parent_container_id must be provided
     let (shelf_id, parent_container_id) = match (payload.shelf_id, payload.parent_container_id) {
         (Some(sid), None) => (Some(sid), None),
         (None, Some(pid)) => (None, Some(pid)),
         (Some(_), Some(_)) => {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(CONTAINER_LOCATION_REQUIRED));
         }
         (None, None) => {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(CONTAINER_LOCATION_REQUIRED));
         }
     };
 
-    // Verify location exists
+    let household_id = state.resolve_household(user_id).await?;
+
+    // Verify location exists in the user's household
     if let Some(sid) = shelf_id {
-        let shelf_exists = sqlx::query("SELECT id FROM shelves WHERE id = $1")
-            .bind(sid)
-            .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to verify shelf: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
-            .is_some();
+        let shelf_exists =
+            sqlx::query("SELECT id FROM shelves WHERE id = $1 AND household_id = $2")
+                .bind(sid)
+                .bind(household_id)
+                .fetch_optional(&state.db)
+                .await?
+                .is_some();
 
         if !shelf_exists {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::BadRequest(format!(
+                "Shelf with id {} does not exist",
+                sid
+            )));
         }
     }
 
     if let Some(pid) = parent_container_id {
-        let parent_exists = sqlx::query("SELECT id FROM containers WHERE id = $1")
-            .bind(pid)
-            .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to verify parent container: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
-            .is_some();
+        let parent_exists =
+            sqlx::query("SELECT id FROM containers WHERE id = $1 AND household_id = $2")
+                .bind(pid)
+                .bind(household_id)
+                .fetch_optional(&state.db)
+                .await?
+                .is_some();
 
         if !parent_exists {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::BadRequest(format!(
+                "Container with id {} does not exist",
+                pid
+            )));
         }
+
+        move_service::check_container_depth(&state.db, pid, None, state.max_container_depth)
+            .await?;
     }
 
+    // Auto-assign position if not provided
+    let position = match payload.position {
+        Some(pos) => pos,
+        None => {
+            move_service::next_container_position(&state.db, shelf_id, parent_container_id).await?
+        }
+    };
+
     let container = sqlx::query_as::<_, Container>(
         r#"
-        INSERT INTO containers (id, shelf_id, parent_container_id, name, description, created_by)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO containers (id, shelf_id, parent_container_id, name, description, position, created_by, household_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         RETURNING *
         "#,
     )
@@ -223,13 +427,11 @@ pub async fn create_container(
     .bind(parent_container_id)
     .bind(&payload.name)
     .bind(&payload.description)
+    .bind(position)
     .bind(user_id)
+    .bind(household_id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to create container: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     // Log audit
     state
@@ -242,75 +444,94 @@ pub async fn create_container(
 }
 
 /// Update a container
+#[utoipa::path(
+    patch,
+    path = "/api/containers/{id}",
+    tag = "containers",
+    params(
+        ("id" = Uuid, Path, description = "Container id")
+    ),
+    request_body = UpdateContainerRequest,
+    responses(
+        (status = 200, description = "OK", body = ContainerResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Container not found", body = ErrorResponse)
+    )
+)]
 pub async fn update_container(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateContainerRequest>,
-) -> Result<Json<ContainerResponse>, StatusCode> {
+) -> Result<Json<ContainerResponse>, ApiError> {
     // Check if container exists
-    let existing = sqlx::query_as::<_, Container>("SELECT * FROM containers WHERE id = $1")
-        .bind(id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch container: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+    let household_id = state.resolve_household(user_id).await?;
+
+    let existing = sqlx::query_as::<_, Container>(
+        "SELECT * FROM containers WHERE id = $1 AND household_id = $2",
+    )
+    .bind(id)
+    .bind(household_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Container", id))?;
 
     // Handle location changes
-    let (shelf_id, parent_container_id) =
-        if payload.shelf_id.is_some() || payload.parent_container_id.is_some() {
-            // New location provided - validate constraint
-            let new_shelf_id = payload.shelf_id.or(existing.shelf_id);
-            let new_parent_id = payload.parent_container_id.or(existing.parent_container_id);
-
-            match (new_shelf_id, new_parent_id) {
-                (Some(sid), None) => {
-                    // Verify shelf exists
-                    let shelf_exists = sqlx::query("SELECT id FROM shelves WHERE id = $1")
+    let (shelf_id, parent_container_id) = if payload.shelf_id.is_some()
+        || payload.parent_container_id.is_some()
+    {
+        // New location provided - validate constraint
+        let new_shelf_id = payload.shelf_id.or(existing.shelf_id);
+        let new_parent_id = payload.parent_container_id.or(existing.parent_container_id);
+
+        match (new_shelf_id, new_parent_id) {
+            (Some(sid), None) => {
+                // Verify shelf exists
+                let shelf_exists =
+                    sqlx::query("SELECT id FROM shelves WHERE id = $1 AND household_id = $2")
                         .bind(sid)
+                        .bind(household_id)
                         .fetch_optional(&state.db)
-                        .await
-                        .map_err(|e| {
-                            tracing::error!("Failed to verify shelf: {:?}", e);
-                            StatusCode::INTERNAL_SERVER_ERROR
-                        })?
+                        .await?
                         .is_some();
 
-                    if !shelf_exists {
-                        return Err(StatusCode::BAD_REQUEST);
-                    }
-                    (Some(sid), None)
+                if !shelf_exists {
+                    return Err(ApiError::BadRequest(format!(
+                        "Shelf with id {} does not exist",
+                        sid
+                    )));
                 }
-                (None, Some(pid)) => {
-                    // Verify parent exists and prevent circular reference
-                    if pid == id {
-                        return Err(StatusCode::BAD_REQUEST);
-                    }
-                    let parent_exists = sqlx::query("SELECT id FROM containers WHERE id = $1")
+                (Some(sid), None)
+            }
+            (None, Some(pid)) => {
+                // Verify parent exists and prevent circular reference
+                if pid == id {
+                    return Err(ApiError::bad_request("A container can't be its own parent"));
+                }
+                let parent_exists =
+                    sqlx::query("SELECT id FROM containers WHERE id = $1 AND household_id = $2")
                         .bind(pid)
+                        .bind(household_id)
                         .fetch_optional(&state.db)
-                        .await
-                        .map_err(|e| {
-                            tracing::error!("Failed to verify parent container: {:?}", e);
-                            StatusCode::INTERNAL_SERVER_ERROR
-                        })?
+                        .await?
                         .is_some();
 
-                    if !parent_exists {
-                        return Err(StatusCode::BAD_REQUEST);
-                    }
-                    (None, Some(pid))
+                if !parent_exists {
+                    return Err(ApiError::BadRequest(format!(
+                        "Container with id {} does not exist",
+                        pid
+                    )));
                 }
-                (Some(_), Some(_)) => return Err(StatusCode::BAD_REQUEST),
-                (None, None) => return Err(StatusCode::BAD_REQUEST),
+                (None, Some(pid))
             }
-        } else {
-            // No location change
-            (existing.shelf_id, existing.parent_container_id)
-        };
+            (Some(_), Some(_)) => return Err(ApiError::bad_request(CONTAINER_LOCATION_REQUIRED)),
+            (None, None) => return Err(ApiError::bad_request(CONTAINER_LOCATION_REQUIRED)),
+        }
+    } else {
+        // No location change
+        (existing.shelf_id, existing.parent_container_id)
+    };
 
     // Track changes for audit before consuming payload
     let mut changes = serde_json::Map::new();
@@ -340,7 +561,26 @@ pub async fn update_container(
     // Update fields if provided
     let name = payload.name.unwrap_or(existing.name.clone());
     let description = payload.description.or(existing.description.clone());
-    if shelf_id != existing.shelf_id || parent_container_id != existing.parent_container_id {
+    let location_changed =
+        shelf_id != existing.shelf_id || parent_container_id != existing.parent_container_id;
+    // A container moved without an explicit position goes after those already there
+    let position = match payload.position {
+        Some(pos) => Some(pos),
+        None if location_changed => Some(
+            move_service::next_container_position(&state.db, shelf_id, parent_container_id).await?,
+        ),
+        None => existing.position,
+    };
+    if position != existing.position {
+        changes.insert(
+            "position".to_string(),
+            serde_json::json!({
+                "from": existing.position,
+                "to": position
+            }),
+        );
+    }
+    if location_changed {
         changes.insert(
             "location".to_string(),
             serde_json::json!({
@@ -359,8 +599,8 @@ pub async fn update_container(
     let container = sqlx::query_as::<_, Container>(
         r#"
         UPDATE containers
-        SET name = $1, description = $2, shelf_id = $3, parent_container_id = $4, updated_at = NOW()
-        WHERE id = $5
+        SET name = $1, description = $2, shelf_id = $3, parent_container_id = $4, position = $5, updated_at = NOW()
+        WHERE id = $6
         RETURNING *
         "#,
     )
@@ -368,13 +608,10 @@ pub async fn update_container(
     .bind(&description)
     .bind(shelf_id)
     .bind(parent_container_id)
+    .bind(position)
     .bind(id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to update container: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     // Log audit
     if !changes.is_empty() {
@@ -394,39 +631,158 @@ pub async fn update_container(
     Ok(Json(ContainerResponse::from(container)))
 }
 
+/// Check a reorder names each container once, that they all exist, and that they
+/// are siblings: on the same shelf or inside the same parent container
+fn validate_reorder(requested: &[Uuid], found: &[Container]) -> Result<(), ApiError> {
+    let mut seen = std::collections::HashSet::new();
+    if let Some(duplicate) = requested.iter().find(|id| !seen.insert(**id)) {
+        return Err(ApiError::BadRequest(format!(
+            "Container {} is listed more than once",
+            duplicate
+        )));
+    }
+    if let Some(missing) = requested
+        .iter()
+        .find(|id| !found.iter().any(|container| container.id == **id))
+    {
+        return Err(ApiError::not_found("Container", *missing));
+    }
+
+    let first = &found[0];
+    if found.iter().any(|container| {
+        container.shelf_id != first.shelf_id
+            || container.parent_container_id != first.parent_container_id
+    }) {
+        return Err(ApiError::bad_request(
+            "All containers must share the same shelf_id or parent_container_id",
+        ));
+    }
+    Ok(())
+}
+
+/// Set the order of sibling containers: each gets its index in `container_ids` as
+/// its position. Containers at the same location that aren't listed keep theirs.
+#[utoipa::path(
+    post,
+    path = "/api/containers/reorder",
+    tag = "containers",
+    request_body = ReorderContainersRequest,
+    responses(
+        (status = 200, description = "OK", body = Vec<ContainerResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn reorder_containers(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Json(payload): Json<ReorderContainersRequest>,
+) -> Result<Json<Vec<ContainerResponse>>, ApiError> {
+    let ids = &payload.container_ids;
+    if ids.is_empty() || ids.len() > MAX_REORDER_CONTAINERS {
+        return Err(ApiError::bad_request(format!(
+            "Between 1 and {} container_ids are required",
+            MAX_REORDER_CONTAINERS
+        )));
+    }
+
+    let household_id = state.resolve_household(user_id).await?;
+    let existing = sqlx::query_as::<_, Container>(
+        "SELECT * FROM containers WHERE id = ANY($1) AND household_id = $2",
+    )
+    .bind(ids)
+    .bind(household_id)
+    .fetch_all(&state.db)
+    .await?;
+    validate_reorder(ids, &existing)?;
+
+    let mut tx = state.db.begin().await?;
+
+    let mut containers = Vec::with_capacity(ids.len());
+    for (index, id) in ids.iter().enumerate() {
+        let container = sqlx::query_as::<_, Container>(
+            "UPDATE containers SET position = $1, updated_at = NOW() WHERE id = $2 RETURNING *",
+        )
+        .bind(index as i32)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        containers.push(container);
+    }
+
+    tx.commit().await?;
+
+    for container in &containers {
+        let before = existing
+            .iter()
+            .find(|c| c.id == container.id)
+            .and_then(|c| c.position);
+        if before != container.position {
+            state
+                .audit
+                .log_update(
+                    "container",
+                    container.id,
+                    Some(user_id),
+                    serde_json::json!({
+                        "position": { "from": before, "to": container.position }
+                    }),
+                    None,
+                )
+                .await
+                .ok();
+        }
+    }
+
+    Ok(Json(
+        containers
+            .into_iter()
+            .map(ContainerResponse::from)
+            .collect(),
+    ))
+}
+
 /// Delete a container
+#[utoipa::path(
+    delete,
+    path = "/api/containers/{id}",
+    tag = "containers",
+    params(
+        ("id" = Uuid, Path, description = "Container id")
+    ),
+    responses(
+        (status = 200, description = "OK"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Container not found", body = ErrorResponse)
+    )
+)]
 pub async fn delete_container(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Container, id)
+        .await?;
     // Check if container has any nested containers
     let nested_count: i64 =
         sqlx::query_scalar("SELECT COUNT(*) FROM containers WHERE parent_container_id = $1")
             .bind(id)
             .fetch_one(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to check for nested containers: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .await?;
 
     if nested_count > 0 {
-        return Err(StatusCode::CONFLICT);
+        return Err(ApiError::conflict("Container has nested containers"));
     }
 
     // Check if container has any items
     let item_count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items WHERE container_id = $1")
         .bind(id)
         .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to check for items: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
     if item_count > 0 {
-        return Err(StatusCode::CONFLICT);
+        return Err(ApiError::conflict("Container still holds items"));
     }
 
     // Log audit before deletion
@@ -438,34 +794,260 @@ pub async fn delete_container(
     let result = sqlx::query("DELETE FROM containers WHERE id = $1")
         .bind(id)
         .execute(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete container: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
     if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found("Container", id));
     }
 
     Ok(Json(json!({ "message": "Container deleted successfully" })))
 }
 
+/// Get photos for a container
+#[utoipa::path(
+    get,
+    path = "/api/containers/{id}/photos",
+    tag = "containers",
+    params(
+        ("id" = Uuid, Path, description = "Container id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<PhotoResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Container not found", body = ErrorResponse)
+    )
+)]
+pub async fn list_container_photos(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<PhotoResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Container, id)
+        .await?;
+    let photos = fetch_entity_photos(&state, "container", id).await?;
+    Ok(Json(photos))
+}
+
+/// A container and everything nested in it, breadth first, with each container's
+/// own (non-trashed) item count. `$2` caps how many levels below `$1` are walked,
+/// `$3` the number of rows.
+const CONTAINER_SUBTREE_SQL: &str = r#"
+    WITH RECURSIVE subtree AS (
+        SELECT id, parent_container_id, name, position, created_at, 0 AS depth
+        FROM containers
+        WHERE id = $1
+        UNION ALL
+        SELECT c.id, c.parent_container_id, c.name, c.position, c.created_at, s.depth + 1
+        FROM containers c
+        JOIN subtree s ON c.parent_container_id = s.id
+        WHERE s.depth < $2
+    )
+    SELECT
+        s.id,
+        s.parent_container_id,
+        s.name,
+        (
+            SELECT COUNT(*)::INT FROM items i
+            WHERE i.container_id = s.id AND i.deleted_at IS NULL
+        ) AS item_count
+    FROM subtree s
+    ORDER BY s.depth, s.position ASC NULLS LAST, s.created_at, s.id
+    LIMIT $3
+"#;
+
+/// Get a container with everything nested inside it, in one request. Walks at most
+/// `MAX_CONTAINER_DEPTH` levels and rejects subtrees of more than 500 containers
+/// with 422.
+#[utoipa::path(
+    get,
+    path = "/api/containers/{id}/subtree",
+    tag = "containers",
+    params(
+        ("id" = Uuid, Path, description = "Container id"),
+        ContainerSubtreeQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = ContainerSubtreeResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Container not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_container_subtree(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<ContainerSubtreeQuery>,
+) -> Result<Json<ContainerSubtreeResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Container, id)
+        .await?;
+    let rows = sqlx::query_as::<_, ContainerSubtreeRow>(CONTAINER_SUBTREE_SQL)
+        .bind(id)
+        .bind(state.max_container_depth)
+        .bind(MAX_SUBTREE_NODES as i64 + 1)
+        .fetch_all(&state.db)
+        .await?;
+
+    if rows.len() > MAX_SUBTREE_NODES {
+        return Err(ApiError::UnprocessableEntity(format!(
+            "Container {} holds more than {} nested containers",
+            id, MAX_SUBTREE_NODES
+        )));
+    }
+
+    let tree =
+        ContainerTreeNode::build(id, &rows).ok_or_else(|| ApiError::not_found("Container", id))?;
+
+    Ok(Json(if params.flat {
+        ContainerSubtreeResponse::Flat(tree.flatten())
+    } else {
+        ContainerSubtreeResponse::Tree(tree)
+    }))
+}
+
+/// Give every item directly in a container that has no label the next unassigned
+/// label of the household, lowest number first. Labels are claimed with `FOR UPDATE SKIP LOCKED`, so
+/// concurrent requests never hand out the same label. When labels run out the items
+/// that got one keep it and `insufficient_labels` is set.
+#[utoipa::path(
+    post,
+    path = "/api/containers/{id}/assign-labels",
+    tag = "containers",
+    params(
+        ("id" = Uuid, Path, description = "Container id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = AssignContainerLabelsResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Container not found", body = ErrorResponse)
+    )
+)]
+pub async fn assign_container_labels(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<AssignContainerLabelsResponse>, ApiError> {
+    let household_id = state
+        .authorize_entity(user_id, HouseholdEntity::Container, id)
+        .await?;
+
+    let mut tx = state.db.begin().await?;
+
+    // An item counts as labeled through either side of the assignment
+    let items: Vec<(Uuid, bool)> = sqlx::query_as(
+        r#"
+        SELECT i.id, (i.label_id IS NOT NULL OR EXISTS (
+            SELECT 1 FROM labels l WHERE l.assigned_to_type = 'item' AND l.assigned_to_id = i.id
+        )) AS labeled
+        FROM items i
+        WHERE i.container_id = $1 AND i.deleted_at IS NULL
+        ORDER BY i.name, i.id
+        FOR UPDATE OF i
+        "#,
+    )
+    .bind(id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let unlabeled: Vec<Uuid> = items
+        .iter()
+        .filter(|(_, labeled)| !labeled)
+        .map(|(item_id, _)| *item_id)
+        .collect();
+    let skipped = (items.len() - unlabeled.len()) as i64;
+
+    let free_labels: Vec<(Uuid, i32)> = if unlabeled.is_empty() {
+        Vec::new()
+    } else {
+        sqlx::query_as(
+            r#"
+            SELECT id, number FROM labels
+            WHERE assigned_to_type IS NULL
+              AND batch_id IN (SELECT id FROM label_batches WHERE household_id = $2)
+            ORDER BY number ASC
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+            "#,
+        )
+        .bind(unlabeled.len() as i64)
+        .bind(household_id)
+        .fetch_all(&mut *tx)
+        .await?
+    };
+
+    let response = AssignContainerLabelsResponse::pair(&unlabeled, &free_labels, skipped);
+
+    for assignment in &response.assigned {
+        sqlx::query(
+            "UPDATE labels SET assigned_to_type = 'item', assigned_to_id = $1, assigned_at = NOW(), updated_at = NOW() WHERE id = $2",
+        )
+        .bind(assignment.item_id)
+        .bind(assignment.label_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("UPDATE items SET label_id = $1 WHERE id = $2")
+            .bind(assignment.label_id)
+            .bind(assignment.item_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    for assignment in &response.assigned {
+        state
+            .audit
+            .log_update(
+                "label",
+                assignment.label_id,
+                Some(user_id),
+                json!({
+                    "assigned_to_type": { "from": null, "to": "item" },
+                    "assigned_to_id": { "from": null, "to": assignment.item_id }
+                }),
+                None,
+            )
+            .await
+            .ok();
+    }
+
+    if response.insufficient_labels {
+        tracing::warn!(
+            "Container {} has {} unlabeled items but only {} labels were free",
+            id,
+            unlabeled.len(),
+            free_labels.len()
+        );
+    }
+
+    Ok(Json(response))
+}
+
 /// Create container routes
 pub fn container_routes() -> Router<Arc<AppState>> {
-    use axum::routing::get;
+    use axum::routing::{get, post};
 
     Router::new()
         .route(
             "/api/containers",
             get(list_containers).post(create_container),
         )
+        .route("/api/containers/reorder", post(reorder_containers))
         .route(
             "/api/containers/:id",
             get(get_container)
-                .put(update_container)
+                .merge(patch_with_put_alias(update_container))
                 .delete(delete_container),
         )
+        .route("/api/containers/search", get(search_containers))
+        .route("/api/containers/:id/photos", get(list_container_photos))
+        .route("/api/containers/:id/subtree", get(get_container_subtree))
+        .route(
+            "/api/containers/:id/assign-labels",
+            post(assign_container_labels),
+        )
         .route(
             "/api/shelves/:shelf_id/containers",
             get(list_containers_by_shelf),
@@ -475,3 +1057,239 @@ pub fn container_routes() -> Router<Arc<AppState>> {
             get(list_containers_by_parent),
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_pool, test_app_state};
+    use chrono::Utc;
+    use sqlx::PgPool;
+
+    fn container(shelf_id: Option<Uuid>, parent_container_id: Option<Uuid>) -> Container {
+        Container {
+            id: Uuid::new_v4(),
+            shelf_id,
+            parent_container_id,
+            name: "Box".to_string(),
+            description: None,
+            label_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: Uuid::new_v4(),
+            position: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_reorder_accepts_siblings() {
+        let shelf_id = Some(Uuid::new_v4());
+        let found = vec![container(shelf_id, None), container(shelf_id, None)];
+        let ids: Vec<Uuid> = found.iter().rev().map(|c| c.id).collect();
+
+        assert!(validate_reorder(&ids, &found).is_ok());
+    }
+
+    #[test]
+    fn test_validate_reorder_rejects_mixed_locations() {
+        let shelf_id = Some(Uuid::new_v4());
+        let found = vec![
+            container(shelf_id, None),
+            container(None, Some(Uuid::new_v4())),
+        ];
+        let ids: Vec<Uuid> = found.iter().map(|c| c.id).collect();
+
+        assert!(matches!(
+            validate_reorder(&ids, &found),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_reorder_rejects_missing_and_duplicate_ids() {
+        let found = vec![container(Some(Uuid::new_v4()), None)];
+
+        let missing = vec![found[0].id, Uuid::new_v4()];
+        assert!(matches!(
+            validate_reorder(&missing, &found),
+            Err(ApiError::NotFound(_))
+        ));
+
+        let duplicated = vec![found[0].id, found[0].id];
+        assert!(matches!(
+            validate_reorder(&duplicated, &found),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    /// Insert "Red bin", "Crate" (described as reddish) and "Blue bin" under one
+    /// shelf or parent container
+    async fn insert_search_fixtures(
+        pool: &PgPool,
+        household_id: Uuid,
+        user_id: Uuid,
+        shelf_id: Option<Uuid>,
+        parent_container_id: Option<Uuid>,
+    ) {
+        let containers = [
+            ("Red bin", None),
+            ("Crate", Some("Reddish plastic")),
+            ("Blue bin", None),
+        ];
+        for (name, description) in containers {
+            sqlx::query(
+                "INSERT INTO containers (id, shelf_id, parent_container_id, name, description, created_by, household_id) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(shelf_id)
+            .bind(parent_container_id)
+            .bind(name)
+            .bind(description)
+            .bind(user_id)
+            .bind(household_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+    }
+
+    fn search(term: &str) -> Query<PaginationQuery> {
+        Query(PaginationQuery {
+            search: Some(term.to_string()),
+            ..Default::default()
+        })
+    }
+
+    fn assert_red_matches(
+        everything: Result<Json<PaginatedResponse<ContainerResponse>>, ApiError>,
+        red: Result<Json<PaginatedResponse<ContainerResponse>>, ApiError>,
+    ) {
+        let everything = everything.unwrap().0;
+        assert_eq!(everything.total, 3);
+        assert_eq!(everything.data.len(), 3);
+
+        let red = red.unwrap().0;
+        let mut names: Vec<String> = red.data.into_iter().map(|c| c.name).collect();
+        names.sort();
+        assert_eq!(red.total, 2);
+        assert_eq!(names, vec!["Crate", "Red bin"]);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_list_containers_search_filters_name_and_description() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+        insert_search_fixtures(&pool, household_id, user_id, Some(Uuid::new_v4()), None).await;
+
+        let list = |term: &str| {
+            list_containers(
+                State(state.clone()),
+                AuthUser(user_id),
+                search(term),
+                Query(IncludePhotosQuery::default()),
+                Query(IncludeTagsQuery::default()),
+            )
+        };
+        let everything = list("").await;
+        let red = list("red").await;
+
+        sqlx::query("DELETE FROM containers WHERE household_id = $1")
+            .bind(household_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_red_matches(everything, red);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_list_containers_by_shelf_search_filters_name_and_description() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+        let shelf_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO shelves (id, shelving_unit_id, name, created_by, household_id) VALUES ($1, $2, 'Search shelf', $3, $4)",
+        )
+        .bind(shelf_id)
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(household_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        insert_search_fixtures(&pool, household_id, user_id, Some(shelf_id), None).await;
+
+        let list = |term: &str| {
+            list_containers_by_shelf(
+                State(state.clone()),
+                AuthUser(user_id),
+                Path(shelf_id),
+                search(term),
+                Query(IncludePhotosQuery::default()),
+                Query(IncludeTagsQuery::default()),
+            )
+        };
+        let everything = list("").await;
+        let red = list("RED").await;
+
+        sqlx::query("DELETE FROM containers WHERE shelf_id = $1")
+            .bind(shelf_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM shelves WHERE id = $1")
+            .bind(shelf_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_red_matches(everything, red);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_list_containers_by_parent_search_filters_name_and_description() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+        let parent_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO containers (id, shelf_id, name, created_by, household_id) VALUES ($1, $2, 'Search parent', $3, $4)",
+        )
+        .bind(parent_id)
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(household_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        insert_search_fixtures(&pool, household_id, user_id, None, Some(parent_id)).await;
+
+        let list = |term: &str| {
+            list_containers_by_parent(
+                State(state.clone()),
+                AuthUser(user_id),
+                Path(parent_id),
+                search(term),
+                Query(IncludePhotosQuery::default()),
+                Query(IncludeTagsQuery::default()),
+            )
+        };
+        let everything = list("").await;
+        let red = list("Red").await;
+
+        sqlx::query("DELETE FROM containers WHERE id = $1 OR parent_container_id = $1")
+            .bind(parent_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_red_matches(everything, red);
+    }
+}
@@ -0,0 +1,162 @@
+use axum::{
+    extract::State,
+    response::sse::{Event, KeepAlive, Sse},
+    Router,
+};
+use futures::Stream;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::InventoryEvent;
+
+/// How often an idle stream sends a comment, so proxies don't close it
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Stream inventory writes as server-sent events, one `data: {InventoryEvent}` per
+/// write recorded in the audit log by a member of the subscriber's household.
+///
+/// Needs a host that streams responses; through API Gateway on Lambda the response
+/// is buffered and never arrives.
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "events",
+    responses(
+        (status = 200, description = "`text/event-stream` of events", content_type = "text/event-stream", body = InventoryEvent),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn stream_events(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    tracing::debug!("User {} subscribed to inventory events", user_id);
+
+    Ok(
+        Sse::new(event_stream(state.events.subscribe(), household_id)).keep_alive(
+            KeepAlive::new()
+                .interval(HEARTBEAT_INTERVAL)
+                .text("heartbeat"),
+        ),
+    )
+}
+
+/// Events of `household_id` from `receiver` until its channel closes. A subscriber
+/// that falls behind skips the events it missed.
+fn event_stream(
+    receiver: broadcast::Receiver<InventoryEvent>,
+    household_id: Uuid,
+) -> impl Stream<Item = Result<Event, axum::Error>> {
+    futures::stream::unfold(receiver, move |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) if event.household_id == household_id => {
+                    return Some((Event::default().json_data(&event), receiver))
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Event subscriber lagged, skipped {} events", skipped);
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+/// Create event routes
+pub fn event_routes() -> Router<Arc<AppState>> {
+    use axum::routing::get;
+
+    Router::new().route("/api/events", get(stream_events))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::response::IntoResponse;
+    use chrono::Utc;
+
+    const HOUSEHOLD: Uuid = Uuid::from_u128(1);
+
+    fn event(event_type: &str) -> InventoryEvent {
+        household_event(event_type, HOUSEHOLD)
+    }
+
+    fn household_event(event_type: &str, household_id: Uuid) -> InventoryEvent {
+        InventoryEvent {
+            event_type: event_type.to_string(),
+            entity_type: "item".to_string(),
+            entity_id: Uuid::new_v4(),
+            household_id,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_are_sent_as_data_lines() {
+        let (sender, receiver) = broadcast::channel(4);
+        let (created, moved) = (event("CREATE"), event("MOVE"));
+        sender.send(created.clone()).unwrap();
+        sender.send(moved.clone()).unwrap();
+        drop(sender);
+
+        let response = Sse::new(event_stream(receiver, HOUSEHOLD)).into_response();
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        let expected = format!(
+            "data: {}\n\ndata: {}\n\n",
+            serde_json::to_string(&created).unwrap(),
+            serde_json::to_string(&moved).unwrap()
+        );
+        assert_eq!(String::from_utf8(body.to_vec()).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_skips_missed_events() {
+        let (sender, receiver) = broadcast::channel(1);
+        sender.send(event("CREATE")).unwrap();
+        let latest = event("DELETE");
+        sender.send(latest.clone()).unwrap();
+        drop(sender);
+
+        let response = Sse::new(event_stream(receiver, HOUSEHOLD)).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(body.to_vec()).unwrap(),
+            format!("data: {}\n\n", serde_json::to_string(&latest).unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_other_households_events_are_not_sent() {
+        let (sender, receiver) = broadcast::channel(4);
+        let own = event("CREATE");
+        sender
+            .send(household_event("CREATE", Uuid::from_u128(2)))
+            .unwrap();
+        sender.send(own.clone()).unwrap();
+        drop(sender);
+
+        let response = Sse::new(event_stream(receiver, HOUSEHOLD)).into_response();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(body.to_vec()).unwrap(),
+            format!("data: {}\n\n", serde_json::to_string(&own).unwrap())
+        );
+    }
+}
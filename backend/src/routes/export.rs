@@ -0,0 +1,91 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Router,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::{Export, ExportResponse, ExportStatus};
+use crate::services::audit::Auditable;
+use crate::services::export::ExportJob;
+
+/// Start exporting the household's whole inventory. Poll the returned export until it is complete
+/// to get its download URL.
+#[utoipa::path(
+    post,
+    path = "/api/export",
+    tag = "export",
+    responses(
+        (status = 202, description = "Export queued", body = ExportResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn create_export(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+) -> Result<(StatusCode, Json<ExportResponse>), ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+
+    let export = sqlx::query_as::<_, Export>(
+        "INSERT INTO exports (id, status, created_by) VALUES ($1, $2, $3) RETURNING *",
+    )
+    .bind(Uuid::new_v4())
+    .bind(ExportStatus::Pending.as_str())
+    .bind(user_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    ExportJob::new(state.db.clone(), state.s3.clone()).spawn(export.id, household_id);
+
+    state
+        .audit
+        .log_create("export", export.id, Some(user_id), None)
+        .await
+        .ok();
+
+    Ok((StatusCode::ACCEPTED, Json(ExportResponse::from(export))))
+}
+
+/// Get the status of an export the user started, and its download URL once complete
+#[utoipa::path(
+    get,
+    path = "/api/export/{id}",
+    tag = "export",
+    params(
+        ("id" = Uuid, Path, description = "Export id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = ExportResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Export not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_export(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ExportResponse>, ApiError> {
+    let export =
+        sqlx::query_as::<_, Export>("SELECT * FROM exports WHERE id = $1 AND created_by = $2")
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Export", id))?;
+
+    Ok(Json(ExportResponse::from(export)))
+}
+
+/// Create export routes
+pub fn export_routes() -> Router<Arc<AppState>> {
+    use axum::routing::{get, post};
+
+    Router::new()
+        .route("/api/export", post(create_export))
+        .route("/api/export/:id", get(get_export))
+}
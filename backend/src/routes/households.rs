@@ -0,0 +1,206 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    Router,
+};
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::{
+    hash_invite_token, new_invite_token, Household, HouseholdRole, InviteResponse,
+    JoinHouseholdQuery, JoinHouseholdResponse,
+};
+use crate::services::audit::Auditable;
+use crate::services::household as household_service;
+
+/// Get the household the user is working in
+#[utoipa::path(
+    get,
+    path = "/api/households/current",
+    tag = "households",
+    responses(
+        (status = 200, description = "OK", body = Household),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn get_current_household(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+) -> Result<Json<Household>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+
+    let household = sqlx::query_as::<_, Household>("SELECT * FROM households WHERE id = $1")
+        .bind(household_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Household", household_id))?;
+
+    Ok(Json(household))
+}
+
+/// Create a single-use invite link to a household. Only its owners can invite.
+#[utoipa::path(
+    post,
+    path = "/api/households/{id}/invite",
+    tag = "households",
+    params(
+        ("id" = Uuid, Path, description = "Household id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = InviteResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Household not found", body = ErrorResponse)
+    )
+)]
+pub async fn create_household_invite(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(household_id): Path<Uuid>,
+) -> Result<Json<InviteResponse>, ApiError> {
+    match household_service::membership_role(&state.db, household_id, user_id).await? {
+        Some(HouseholdRole::Owner) => {}
+        Some(HouseholdRole::Member) => {
+            tracing::warn!(
+                "User {} attempted to invite to household {} without owning it",
+                user_id,
+                household_id
+            );
+            return Err(ApiError::Forbidden(
+                "Only the household's owners can invite".to_string(),
+            ));
+        }
+        None => return Err(ApiError::not_found("Household", household_id)),
+    }
+
+    let invite_id = Uuid::new_v4();
+    let (token, expires_at) = new_invite_token(Utc::now());
+
+    sqlx::query(
+        r#"
+        INSERT INTO invites (id, household_id, token_hash, created_by, expires_at)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(invite_id)
+    .bind(household_id)
+    .bind(hash_invite_token(&token))
+    .bind(user_id)
+    .bind(expires_at)
+    .execute(&state.db)
+    .await?;
+
+    state
+        .audit
+        .log_create("invite", invite_id, Some(user_id), None)
+        .await
+        .ok();
+
+    Ok(Json(InviteResponse {
+        household_id,
+        token,
+        expires_at,
+    }))
+}
+
+/// Join the household an invite token was issued for, as a member. The user then
+/// works in that household. Joining a household one already belongs to leaves the
+/// invite unused.
+#[utoipa::path(
+    get,
+    path = "/api/households/join",
+    tag = "households",
+    params(
+        JoinHouseholdQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = JoinHouseholdResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn join_household(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<JoinHouseholdQuery>,
+) -> Result<Json<JoinHouseholdResponse>, ApiError> {
+    let invite: Option<(Uuid, Uuid, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT id, household_id, expires_at FROM invites WHERE token_hash = $1 AND accepted_at IS NULL",
+    )
+    .bind(hash_invite_token(&params.token))
+    .fetch_optional(&state.db)
+    .await?;
+
+    let Some((invite_id, household_id, expires_at)) = invite else {
+        tracing::warn!("User {} used an unknown or spent invite token", user_id);
+        return Err(ApiError::NotFound(
+            "Invite not found or already used".to_string(),
+        ));
+    };
+    if expires_at <= Utc::now() {
+        return Err(ApiError::bad_request("Invite has expired"));
+    }
+
+    if let Some(role) = household_service::membership_role(&state.db, household_id, user_id).await?
+    {
+        return Ok(Json(JoinHouseholdResponse { household_id, role }));
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Claim the invite; losing a race with another redemption leaves nothing to claim
+    let claimed = sqlx::query(
+        "UPDATE invites SET accepted_by = $1, accepted_at = NOW() WHERE id = $2 AND accepted_at IS NULL",
+    )
+    .bind(user_id)
+    .bind(invite_id)
+    .execute(&mut *tx)
+    .await?;
+    if claimed.rows_affected() == 0 {
+        return Err(ApiError::NotFound(
+            "Invite not found or already used".to_string(),
+        ));
+    }
+
+    sqlx::query(
+        "INSERT INTO household_memberships (household_id, user_id, role) VALUES ($1, $2, $3)",
+    )
+    .bind(household_id)
+    .bind(user_id)
+    .bind(HouseholdRole::Member.as_str())
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    state
+        .audit
+        .log_update(
+            "household",
+            household_id,
+            Some(user_id),
+            serde_json::json!({
+                "member_joined": { "user_id": user_id, "invite_id": invite_id }
+            }),
+            None,
+        )
+        .await
+        .ok();
+
+    Ok(Json(JoinHouseholdResponse {
+        household_id,
+        role: HouseholdRole::Member,
+    }))
+}
+
+/// Create household routes
+pub fn household_routes() -> Router<Arc<AppState>> {
+    use axum::routing::{get, post};
+
+    Router::new()
+        .route("/api/households/current", get(get_current_household))
+        .route("/api/households/join", get(join_household))
+        .route("/api/households/:id/invite", post(create_household_invite))
+}
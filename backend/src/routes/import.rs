@@ -0,0 +1,574 @@
+use axum::{
+    extract::{Multipart, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+    Router,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::{
+    area_room_name, normalize_tag_name, CsvImportError, CsvImportQuery, CsvImportResponse, CsvItem,
+    CsvItemRow, CsvLocation, HomeAssistantState, ImportResult, Item, ItemResponse,
+    DEFAULT_CURRENCY, MAX_CSV_IMPORT_ROWS,
+};
+use crate::routes::items::{barcode_conflict, barcode_owner};
+use crate::services::audit::{AuditAction, Auditable};
+
+/// Room used for entities that aren't assigned to a Home Assistant area
+const UNASSIGNED_ROOM_NAME: &str = "Unassigned";
+/// Shelving unit and shelf created in each room to hold imported entities,
+/// since items must live on a shelf or in a container
+const IMPORT_UNIT_NAME: &str = "Home Assistant";
+const IMPORT_SHELF_NAME: &str = "Devices";
+/// items.barcode is VARCHAR(50)
+const MAX_BARCODE_LEN: usize = 50;
+
+/// Find or create the room/unit/shelf in `household_id` that imported entities for an
+/// area land on. Newly created entities are appended to `created` for audit logging
+/// after commit.
+async fn ensure_import_shelf(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    room_name: &str,
+    user_id: Uuid,
+    household_id: Uuid,
+    created: &mut Vec<(&'static str, Uuid)>,
+) -> Result<Uuid, ApiError> {
+    let room_id: Option<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM rooms WHERE name = $1 AND household_id = $2 ORDER BY created_at LIMIT 1",
+    )
+    .bind(room_name)
+    .bind(household_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let room_id = match room_id {
+        Some(id) => id,
+        None => {
+            let id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO rooms (id, name, created_by, household_id) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(id)
+            .bind(room_name)
+            .bind(user_id)
+            .bind(household_id)
+            .execute(&mut **tx)
+            .await?;
+            created.push(("room", id));
+            id
+        }
+    };
+
+    let unit_id: Option<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM shelving_units WHERE room_id = $1 AND name = $2 ORDER BY created_at LIMIT 1",
+    )
+    .bind(room_id)
+    .bind(IMPORT_UNIT_NAME)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let unit_id = match unit_id {
+        Some(id) => id,
+        None => {
+            let id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO shelving_units (id, room_id, name, created_by, household_id) VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(id)
+            .bind(room_id)
+            .bind(IMPORT_UNIT_NAME)
+            .bind(user_id)
+            .bind(household_id)
+            .execute(&mut **tx)
+            .await?;
+            created.push(("unit", id));
+            id
+        }
+    };
+
+    let shelf_id: Option<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM shelves WHERE shelving_unit_id = $1 AND name = $2 ORDER BY created_at LIMIT 1",
+    )
+    .bind(unit_id)
+    .bind(IMPORT_SHELF_NAME)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    match shelf_id {
+        Some(id) => Ok(id),
+        None => {
+            let id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO shelves (id, shelving_unit_id, name, position, created_by, household_id) VALUES ($1, $2, $3, (SELECT COALESCE(MAX(position), 0) + 1 FROM shelves WHERE shelving_unit_id = $2), $4, $5)",
+            )
+            .bind(id)
+            .bind(unit_id)
+            .bind(IMPORT_SHELF_NAME)
+            .bind(user_id)
+            .bind(household_id)
+            .execute(&mut **tx)
+            .await?;
+            created.push(("shelf", id));
+            Ok(id)
+        }
+    }
+}
+
+/// Import entities from Home Assistant's states API
+#[utoipa::path(
+    post,
+    path = "/api/import/home-assistant",
+    tag = "import",
+    request_body = Vec<HomeAssistantState>,
+    responses(
+        (status = 200, description = "OK", body = ImportResult),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn import_home_assistant(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Json(entities): Json<Vec<HomeAssistantState>>,
+) -> Result<Json<ImportResult>, ApiError> {
+    let mut result = ImportResult::default();
+    let mut created: Vec<(&'static str, Uuid)> = Vec::new();
+    let mut shelves_by_room: HashMap<String, Uuid> = HashMap::new();
+    let mut tags_by_name: HashMap<String, Uuid> = HashMap::new();
+    let household_id = state.resolve_household(user_id).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    for entity in &entities {
+        let Some(domain) = entity.domain() else {
+            result
+                .errors
+                .push(format!("Invalid entity_id: {}", entity.entity_id));
+            continue;
+        };
+
+        if entity.entity_id.len() > MAX_BARCODE_LEN {
+            result.errors.push(format!(
+                "{}: entity_id longer than {} characters",
+                entity.entity_id, MAX_BARCODE_LEN
+            ));
+            continue;
+        }
+
+        let already_imported: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM items WHERE LOWER(barcode) = LOWER($1) AND deleted_at IS NULL)",
+        )
+        .bind(&entity.entity_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if already_imported {
+            result
+                .errors
+                .push(format!("{}: already imported", entity.entity_id));
+            continue;
+        }
+
+        let room_name = entity
+            .area_id()
+            .map(area_room_name)
+            .unwrap_or_else(|| UNASSIGNED_ROOM_NAME.to_string());
+
+        let shelf_id = match shelves_by_room.get(&room_name) {
+            Some(id) => *id,
+            None => {
+                let id =
+                    ensure_import_shelf(&mut tx, &room_name, user_id, household_id, &mut created)
+                        .await?;
+                shelves_by_room.insert(room_name, id);
+                id
+            }
+        };
+
+        let item_id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+            INSERT INTO items (id, shelf_id, name, barcode, barcode_type, created_by, household_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(item_id)
+        .bind(shelf_id)
+        .bind(entity.item_name())
+        .bind(&entity.entity_id)
+        .bind("home_assistant")
+        .bind(user_id)
+        .bind(household_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(barcode_conflict)?;
+        created.push(("item", item_id));
+        result.items_created += 1;
+
+        // Tag the item with its entity domain (sensor, switch, ...)
+        let tag_name = normalize_tag_name(domain);
+        let tag_id = match tags_by_name.get(&tag_name) {
+            Some(id) => *id,
+            None => {
+                let inserted: Option<Uuid> = sqlx::query_scalar(
+                    "INSERT INTO tags (id, name) VALUES ($1, $2) ON CONFLICT (name) DO NOTHING RETURNING id",
+                )
+                .bind(Uuid::new_v4())
+                .bind(&tag_name)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+                let id = match inserted {
+                    Some(id) => {
+                        created.push(("tag", id));
+                        result.tags_created += 1;
+                        id
+                    }
+                    None => {
+                        sqlx::query_scalar("SELECT id FROM tags WHERE name = $1")
+                            .bind(&tag_name)
+                            .fetch_one(&mut *tx)
+                            .await?
+                    }
+                };
+                tags_by_name.insert(tag_name, id);
+                id
+            }
+        };
+
+        sqlx::query(
+            "INSERT INTO entity_tags (entity_type, entity_id, tag_id, updated_at) VALUES ($1, $2, $3, NOW()) ON CONFLICT DO NOTHING",
+        )
+        .bind("item")
+        .bind(item_id)
+        .bind(tag_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    result.rooms_created = created
+        .iter()
+        .filter(|(entity_type, _)| *entity_type == "room")
+        .count() as i32;
+
+    for (entity_type, id) in &created {
+        state
+            .audit
+            .log_create(
+                entity_type,
+                *id,
+                Some(user_id),
+                Some(serde_json::json!({ "source": "home_assistant" })),
+            )
+            .await
+            .ok();
+    }
+
+    tracing::info!(
+        "Home Assistant import: {} rooms, {} items, {} tags created, {} errors",
+        result.rooms_created,
+        result.items_created,
+        result.tags_created,
+        result.errors.len()
+    );
+
+    Ok(Json(result))
+}
+
+/// The `file` field of a multipart upload
+async fn read_file_field(mut multipart: Multipart) -> Result<Vec<u8>, ApiError> {
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        tracing::warn!("Invalid multipart upload: {:?}", e);
+        ApiError::bad_request("Invalid multipart upload")
+    })? {
+        if field.name() == Some("file") {
+            let bytes = field.bytes().await.map_err(|e| {
+                tracing::warn!("Failed to read uploaded file: {:?}", e);
+                ApiError::bad_request("Failed to read uploaded file")
+            })?;
+            return Ok(bytes.to_vec());
+        }
+    }
+    Err(ApiError::bad_request("A file field is required"))
+}
+
+/// Columns a CSV import can't do without; the others may be left out
+const CSV_REQUIRED_COLUMNS: [&str; 3] = ["name", "location_type", "location_id"];
+
+/// A CSV row's file line number with its validated item or the error
+type ParsedCsvRow = (usize, Result<CsvItem, String>);
+
+/// Parse every row up front so a malformed file is rejected before anything is
+/// written.
+fn parse_csv(data: &[u8]) -> Result<Vec<ParsedCsvRow>, ApiError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(data);
+
+    let headers = reader
+        .headers()
+        .map_err(|e| ApiError::bad_request(format!("Invalid CSV header: {}", e)))?
+        .clone();
+    if let Some(missing) = CSV_REQUIRED_COLUMNS
+        .iter()
+        .find(|column| !headers.iter().any(|header| header == **column))
+    {
+        return Err(ApiError::bad_request(format!(
+            "CSV is missing the {} column",
+            missing
+        )));
+    }
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        if rows.len() == MAX_CSV_IMPORT_ROWS {
+            return Err(ApiError::bad_request(format!(
+                "At most {} rows can be imported at once",
+                MAX_CSV_IMPORT_ROWS
+            )));
+        }
+
+        let row = match record {
+            Ok(record) => (
+                record.position().map_or(0, |p| p.line() as usize),
+                record
+                    .deserialize::<CsvItemRow>(Some(&headers))
+                    .map_err(|e| e.to_string())
+                    .and_then(CsvItemRow::validate),
+            ),
+            Err(e) => (
+                e.position().map_or(0, |p| p.line() as usize),
+                Err(e.to_string()),
+            ),
+        };
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// Import items from a CSV upload (`file` field) with the columns name, description,
+/// barcode, barcode_type, location_type, location_id, acquired_date and
+/// purchase_price. Valid rows are imported and the rest reported, unless
+/// `?fail_fast=true`, in which case the first bad row cancels the whole import.
+/// Always answers 207 Multi-Status.
+#[utoipa::path(
+    post,
+    path = "/api/items/import-csv",
+    tag = "import",
+    params(
+        CsvImportQuery
+    ),
+    responses(
+        (status = 207, description = "Per-row results", body = CsvImportResponse),
+        (status = 400, description = "Missing or unreadable CSV file", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn import_items_csv(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<CsvImportQuery>,
+    multipart: Multipart,
+) -> Result<Response, ApiError> {
+    let data = read_file_field(multipart).await?;
+    let rows = parse_csv(&data)?;
+
+    let mut response = CsvImportResponse::default();
+    let mut locations: HashMap<CsvLocation, bool> = HashMap::new();
+    let household_id = state.resolve_household(user_id).await?;
+
+    let mut tx = state.db.begin().await?;
+
+    for (row, item) in rows {
+        // Everything that could fail is checked before the insert, since a failed
+        // statement would abort the transaction
+        let item = match item {
+            Ok(item) => item,
+            Err(message) => {
+                response.errors.push(CsvImportError { row, message });
+                if params.fail_fast {
+                    break;
+                }
+                continue;
+            }
+        };
+
+        let location_exists = match locations.get(&item.location) {
+            Some(exists) => *exists,
+            None => {
+                let query = match item.location {
+                    CsvLocation::Shelf(id) => {
+                        sqlx::query("SELECT id FROM shelves WHERE id = $1").bind(id)
+                    }
+                    CsvLocation::Container(id) => {
+                        sqlx::query("SELECT id FROM containers WHERE id = $1").bind(id)
+                    }
+                };
+                let exists = query.fetch_optional(&mut *tx).await?.is_some();
+                locations.insert(item.location, exists);
+                exists
+            }
+        };
+
+        let error = if !location_exists {
+            Some(match item.location {
+                CsvLocation::Shelf(id) => format!("Shelf with id {} does not exist", id),
+                CsvLocation::Container(id) => format!("Container with id {} does not exist", id),
+            })
+        } else if let Some(barcode) = &item.barcode {
+            // Sees rows imported earlier in this file too
+            barcode_owner(&mut *tx, barcode, None).await?.map(|owner| {
+                match owner.visible_id(Some(household_id)) {
+                    Some(id) => format!("Barcode {} is already assigned to item {}", barcode, id),
+                    None => format!("Barcode {} is already assigned to another item", barcode),
+                }
+            })
+        } else {
+            None
+        };
+        if let Some(message) = error {
+            response.errors.push(CsvImportError { row, message });
+            if params.fail_fast {
+                break;
+            }
+            continue;
+        }
+
+        let (shelf_id, container_id) = match item.location {
+            CsvLocation::Shelf(id) => (Some(id), None),
+            CsvLocation::Container(id) => (None, Some(id)),
+        };
+        let currency = item.purchase_price.map(|_| DEFAULT_CURRENCY);
+
+        let created = sqlx::query_as::<_, Item>(
+            r#"
+            INSERT INTO items (id, shelf_id, container_id, name, description, barcode,
+                              barcode_type, acquired_date, purchase_price, currency, created_by,
+                              household_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(shelf_id)
+        .bind(container_id)
+        .bind(&item.name)
+        .bind(&item.description)
+        .bind(&item.barcode)
+        .bind(&item.barcode_type)
+        .bind(item.acquired_date)
+        .bind(item.purchase_price)
+        .bind(currency)
+        .bind(user_id)
+        .bind(household_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(barcode_conflict)?;
+
+        response.imported.push(ItemResponse::from(created));
+    }
+
+    if params.fail_fast && !response.errors.is_empty() {
+        // Dropping the transaction rolls back the rows imported before the error
+        drop(tx);
+        response.imported.clear();
+        return Ok((StatusCode::MULTI_STATUS, Json(response)).into_response());
+    }
+
+    tx.commit().await?;
+
+    if !response.imported.is_empty() {
+        let item_ids: Vec<Uuid> = response.imported.iter().map(|item| item.id).collect();
+        state
+            .audit
+            .log_action(
+                "item_batch",
+                Uuid::new_v4(),
+                AuditAction::Create,
+                Some(user_id),
+                None,
+                Some(serde_json::json!({
+                    "source": "csv",
+                    "batch_size": item_ids.len(),
+                    "item_ids": item_ids,
+                    "errors": response.errors.len(),
+                })),
+            )
+            .await
+            .ok();
+    }
+
+    tracing::info!(
+        "CSV import: {} items created, {} rows rejected",
+        response.imported.len(),
+        response.errors.len()
+    );
+
+    Ok((StatusCode::MULTI_STATUS, Json(response)).into_response())
+}
+
+/// Create import routes
+pub fn import_routes() -> Router<Arc<AppState>> {
+    use axum::routing::post;
+
+    Router::new()
+        .route("/api/import/home-assistant", post(import_home_assistant))
+        .route("/api/items/import-csv", post(import_items_csv))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str =
+        "name,description,barcode,barcode_type,location_type,location_id,acquired_date,purchase_price";
+
+    #[test]
+    fn test_parse_csv_numbers_rows_by_line() {
+        let data = format!(
+            "{}\nDrill,,,,shelf,{},,\nSaw,,,,room,{},,\n",
+            HEADER,
+            Uuid::nil(),
+            Uuid::nil()
+        );
+        let rows = parse_csv(data.as_bytes()).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].0, 2);
+        assert!(rows[0].1.is_ok());
+        assert_eq!(rows[1].0, 3);
+        assert!(rows[1].1.is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_reports_short_rows() {
+        let data = format!("{}\nDrill,,,\n", HEADER);
+        let rows = parse_csv(data.as_bytes()).unwrap();
+
+        assert_eq!(rows[0].0, 2);
+        assert!(rows[0].1.is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_requires_location_columns() {
+        assert!(parse_csv(b"name,description\nDrill,cordless\n").is_err());
+        assert!(parse_csv(
+            format!(
+                "name,location_type,location_id\nDrill,shelf,{}\n",
+                Uuid::nil()
+            )
+            .as_bytes()
+        )
+        .unwrap()[0]
+            .1
+            .is_ok());
+    }
+}
@@ -1,64 +1,97 @@
 use axum::{
     extract::{Path, State},
     http::StatusCode,
-    response::{IntoResponse, Json},
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
+use futures::future::join_all;
 use serde_json::json;
+use sqlx::PgConnection;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::db::with_transaction;
+use crate::error::{ApiError, ErrorResponse};
 use crate::middleware::auth::AuthUser;
 use crate::models::{
-    AnalyzePhotoRequest, CommitItemImportDraftResponse, CreateItemImportDraftRequest,
-    CreateItemRequest, Item, ItemImportDraft, ItemImportDraftItem, ItemImportDraftResponse,
-    ItemResponse, LocationUpdateProposal, Photo, UpdateItemImportDraftRequest,
+    normalize_tag_name, AnalyzePhotoRequest, CommitItemImportDraftResponse,
+    CreateItemImportDraftRequest, CreateItemRequest, Item, ItemImportDraft, ItemImportDraftItem,
+    ItemImportDraftResponse, ItemResponse, LocationUpdateProposal, Photo,
+    UpdateItemImportDraftRequest, DRAFT_LOCATION_REQUIRED, MAX_ANALYZE_PHOTOS,
 };
+use crate::routes::items::barcode_conflict;
+use crate::services::audit::Auditable;
+use crate::services::household::{self as household_service, HouseholdEntity};
 use crate::services::vision::LocationType;
 
+const DRAFT_ALREADY_COMMITTED: &str = "Draft has already been committed";
+
+/// A draft, if its location is in the user's household. Drafts of other households are
+/// a 404, like missing ones.
+async fn fetch_authorized_draft(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<ItemImportDraft, ApiError> {
+    let draft =
+        sqlx::query_as::<_, ItemImportDraft>("SELECT * FROM item_import_drafts WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Item import draft", id))?;
+
+    let (entity, location_id) = match (draft.container_id, draft.shelf_id) {
+        (Some(container_id), _) => (HouseholdEntity::Container, container_id),
+        (_, Some(shelf_id)) => (HouseholdEntity::Shelf, shelf_id),
+        _ => return Err(ApiError::not_found("Item import draft", id)),
+    };
+    let household_id = state.resolve_household(user_id).await?;
+    household_service::ensure_in_household(&state.db, household_id, entity, location_id)
+        .await
+        .map_err(|e| match e {
+            ApiError::NotFound(_) => ApiError::not_found("Item import draft", id),
+            e => e,
+        })?;
+
+    Ok(draft)
+}
+
 async fn apply_tags(
-    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    conn: &mut PgConnection,
     entity_type: &str,
     entity_id: Uuid,
     tags: Vec<String>,
-) -> Result<(), StatusCode> {
+) -> Result<(), ApiError> {
     // Delete existing tags
     sqlx::query("DELETE FROM entity_tags WHERE entity_type = $1 AND entity_id = $2")
         .bind(entity_type)
         .bind(entity_id)
-        .execute(&mut **tx)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete existing tags: {e:?}");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .execute(&mut *conn)
+        .await?;
 
     // Insert new tags with upsert
     for tag_name in tags {
+        let tag_name = normalize_tag_name(&tag_name);
+        if tag_name.is_empty() {
+            continue;
+        }
+
         let tag_id: Uuid = sqlx::query_scalar(
             "INSERT INTO tags (id, name) VALUES ($1, $2) ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name RETURNING id"
         )
         .bind(Uuid::new_v4())
         .bind(&tag_name)
-        .fetch_one(&mut **tx)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to create/get tag: {e:?}");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .fetch_one(&mut *conn)
+        .await?;
 
-        sqlx::query("INSERT INTO entity_tags (entity_type, entity_id, tag_id) VALUES ($1, $2, $3)")
+        sqlx::query("INSERT INTO entity_tags (entity_type, entity_id, tag_id, updated_at) VALUES ($1, $2, $3, NOW())")
             .bind(entity_type)
             .bind(entity_id)
             .bind(tag_id)
-            .execute(&mut **tx)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to link tag to entity: {e:?}");
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .execute(&mut *conn)
+            .await?;
     }
 
     Ok(())
@@ -99,24 +132,46 @@ fn draft_to_response(draft: ItemImportDraft) -> Result<ItemImportDraftResponse,
     })
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/item-import-drafts",
+    tag = "item_import_drafts",
+    request_body = CreateItemImportDraftRequest,
+    responses(
+        (status = 200, description = "OK", body = ItemImportDraftResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn create_item_import_draft(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Json(payload): Json<CreateItemImportDraftRequest>,
-) -> Result<Json<ItemImportDraftResponse>, StatusCode> {
-    // Verify container exists
-    let container_exists = sqlx::query("SELECT id FROM containers WHERE id = $1")
-        .bind(payload.container_id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to verify container exists: {e:?}");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .is_some();
+) -> Result<Json<ItemImportDraftResponse>, ApiError> {
+    payload.validate_location().map_err(ApiError::bad_request)?;
+
+    // Verify location exists
+    let (table, location_id) = match (payload.container_id, payload.shelf_id) {
+        (Some(container_id), _) => ("containers", container_id),
+        (_, Some(shelf_id)) => ("shelves", shelf_id),
+        _ => return Err(ApiError::bad_request(DRAFT_LOCATION_REQUIRED)),
+    };
 
-    if !container_exists {
-        return Err(StatusCode::BAD_REQUEST);
+    let household_id = state.resolve_household(user_id).await?;
+    let location_exists = sqlx::query(&format!(
+        "SELECT id FROM {table} WHERE id = $1 AND household_id = $2"
+    ))
+    .bind(location_id)
+    .bind(household_id)
+    .fetch_optional(&state.db)
+    .await?
+    .is_some();
+
+    if !location_exists {
+        return Err(ApiError::bad_request(format!(
+            "Location with id {} does not exist",
+            location_id
+        )));
     }
 
     let proposed_items = serde_json::to_value(&payload.items).map_err(|e| {
@@ -134,27 +189,25 @@ pub async fn create_item_import_draft(
         INSERT INTO item_import_drafts (
             id,
             container_id,
+            shelf_id,
             status,
             proposed_items,
             source_photo_ids,
             created_by
         )
-        VALUES ($1, $2, $3, $4, $5, $6)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
         RETURNING *
         "#,
     )
     .bind(Uuid::new_v4())
     .bind(payload.container_id)
+    .bind(payload.shelf_id)
     .bind("draft")
     .bind(proposed_items)
     .bind(source_photo_ids)
     .bind(user_id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to create item import draft: {e:?}");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     state
         .audit
@@ -165,43 +218,54 @@ pub async fn create_item_import_draft(
     Ok(Json(draft_to_response(draft)?))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/item-import-drafts/{id}",
+    tag = "item_import_drafts",
+    params(
+        ("id" = Uuid, Path, description = "Draft id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = ItemImportDraftResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Draft not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_item_import_draft(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<ItemImportDraftResponse>, StatusCode> {
-    let draft =
-        sqlx::query_as::<_, ItemImportDraft>("SELECT * FROM item_import_drafts WHERE id = $1")
-            .bind(id)
-            .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to fetch item import draft: {e:?}");
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
-            .ok_or(StatusCode::NOT_FOUND)?;
+) -> Result<Json<ItemImportDraftResponse>, ApiError> {
+    let draft = fetch_authorized_draft(&state, user_id, id).await?;
 
     Ok(Json(draft_to_response(draft)?))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/item-import-drafts/{id}",
+    tag = "item_import_drafts",
+    params(
+        ("id" = Uuid, Path, description = "Draft id")
+    ),
+    request_body = UpdateItemImportDraftRequest,
+    responses(
+        (status = 200, description = "OK", body = ItemImportDraftResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Draft not found", body = ErrorResponse)
+    )
+)]
 pub async fn update_item_import_draft(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateItemImportDraftRequest>,
-) -> Result<Json<ItemImportDraftResponse>, StatusCode> {
-    let existing =
-        sqlx::query_as::<_, ItemImportDraft>("SELECT * FROM item_import_drafts WHERE id = $1")
-            .bind(id)
-            .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to fetch item import draft: {e:?}");
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
-            .ok_or(StatusCode::NOT_FOUND)?;
+) -> Result<Json<ItemImportDraftResponse>, ApiError> {
+    let existing = fetch_authorized_draft(&state, user_id, id).await?;
 
     if existing.status != "draft" {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request(DRAFT_ALREADY_COMMITTED));
     }
 
     let proposed_items = serde_json::to_value(&payload.items).map_err(|e| {
@@ -221,11 +285,7 @@ pub async fn update_item_import_draft(
     .bind(proposed_items)
     .bind(id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to update item import draft: {e:?}");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     state
         .audit
@@ -247,61 +307,55 @@ pub async fn update_item_import_draft(
     Ok(Json(draft_to_response(updated)?))
 }
 
-pub async fn commit_item_import_draft(
-    State(state): State<Arc<AppState>>,
-    AuthUser(user_id): AuthUser,
-    Path(id): Path<Uuid>,
-) -> Result<Json<CommitItemImportDraftResponse>, StatusCode> {
-    let mut tx = state.db.begin().await.map_err(|e| {
-        tracing::error!("Failed to start transaction: {e:?}");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-
+/// Create the draft's items, apply its location updates and mark it committed,
+/// returning the updated draft and the created items
+async fn commit_draft(
+    conn: &mut PgConnection,
+    id: Uuid,
+    user_id: Uuid,
+    household_id: Uuid,
+) -> Result<(ItemImportDraft, Vec<ItemResponse>), ApiError> {
     let draft = sqlx::query_as::<_, ItemImportDraft>(
         "SELECT * FROM item_import_drafts WHERE id = $1 FOR UPDATE",
     )
     .bind(id)
-    .fetch_optional(&mut *tx)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch item import draft: {e:?}");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?
-    .ok_or(StatusCode::NOT_FOUND)?;
+    .fetch_optional(&mut *conn)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Item import draft", id))?;
 
     if draft.status != "draft" {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request(DRAFT_ALREADY_COMMITTED));
     }
 
     // Verify location still exists
     if let Some(container_id) = draft.container_id {
-        let exists = sqlx::query("SELECT id FROM containers WHERE id = $1")
+        let exists = sqlx::query("SELECT id FROM containers WHERE id = $1 AND household_id = $2")
             .bind(container_id)
-            .fetch_optional(&mut *tx)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to verify container exists: {e:?}");
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
+            .bind(household_id)
+            .fetch_optional(&mut *conn)
+            .await?
             .is_some();
         if !exists {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(format!(
+                "Container with id {} no longer exists",
+                container_id
+            )));
         }
     } else if let Some(shelf_id) = draft.shelf_id {
-        let exists = sqlx::query("SELECT id FROM shelves WHERE id = $1")
+        let exists = sqlx::query("SELECT id FROM shelves WHERE id = $1 AND household_id = $2")
             .bind(shelf_id)
-            .fetch_optional(&mut *tx)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to verify shelf exists: {e:?}");
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
+            .bind(household_id)
+            .fetch_optional(&mut *conn)
+            .await?
             .is_some();
         if !exists {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(format!(
+                "Shelf with id {} no longer exists",
+                shelf_id
+            )));
         }
     } else {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request(DRAFT_LOCATION_REQUIRED));
     }
 
     let items: Vec<ItemImportDraftItem> = serde_json::from_value(draft.proposed_items.clone())
@@ -326,17 +380,13 @@ pub async fn commit_item_import_draft(
                 )
                 .bind(new_description)
                 .bind(container_id)
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to update container description: {e:?}");
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+                .execute(&mut *conn)
+                .await?;
             }
 
             // Handle tags if provided
             if let Some(ref tags) = location_updates.tags {
-                apply_tags(&mut tx, "container", container_id, tags.clone()).await?;
+                apply_tags(&mut *conn, "container", container_id, tags.clone()).await?;
             }
         } else if let Some(shelf_id) = draft.shelf_id {
             // Update shelf description if provided
@@ -346,17 +396,13 @@ pub async fn commit_item_import_draft(
                 )
                 .bind(new_description)
                 .bind(shelf_id)
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to update shelf description: {e:?}");
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+                .execute(&mut *conn)
+                .await?;
             }
 
             // Handle tags if provided
             if let Some(ref tags) = location_updates.tags {
-                apply_tags(&mut tx, "shelf", shelf_id, tags.clone()).await?;
+                apply_tags(&mut *conn, "shelf", shelf_id, tags.clone()).await?;
             }
         }
     }
@@ -375,12 +421,19 @@ pub async fn commit_item_import_draft(
             product_link: None,
             belongs_to_user_id: None,
             acquired_date: None,
+            quantity: None,
+            min_quantity: None,
+            purchase_price: None,
+            currency: None,
+            condition: None,
+            warranty_expires_on: None,
+            reminder_enabled: None,
         };
 
         let created = sqlx::query_as::<_, Item>(
             r#"
-            INSERT INTO items (id, shelf_id, container_id, name, description, barcode, barcode_type, created_by)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO items (id, shelf_id, container_id, name, description, barcode, barcode_type, created_by, household_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
             RETURNING *
             "#,
         )
@@ -392,12 +445,10 @@ pub async fn commit_item_import_draft(
         .bind(&create_req.barcode)
         .bind(&create_req.barcode_type)
         .bind(user_id)
-        .fetch_one(&mut *tx)
+        .bind(household_id)
+        .fetch_one(&mut *conn)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to create item from draft: {e:?}");
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(barcode_conflict)?;
 
         created_items.push(ItemResponse::from(created));
     }
@@ -413,17 +464,37 @@ pub async fn commit_item_import_draft(
     )
     .bind("committed")
     .bind(id)
-    .fetch_one(&mut *tx)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to mark draft committed: {e:?}");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .fetch_one(&mut *conn)
+    .await?;
 
-    tx.commit().await.map_err(|e| {
-        tracing::error!("Failed to commit transaction: {e:?}");
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    Ok((updated, created_items))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/item-import-drafts/{id}/commit",
+    tag = "item_import_drafts",
+    params(
+        ("id" = Uuid, Path, description = "Draft id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = CommitItemImportDraftResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Draft not found", body = ErrorResponse)
+    )
+)]
+pub async fn commit_item_import_draft(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CommitItemImportDraftResponse>, ApiError> {
+    fetch_authorized_draft(&state, user_id, id).await?;
+    let household_id = state.resolve_household(user_id).await?;
+
+    let (updated, created_items) = with_transaction(&state.db, move |conn| {
+        Box::pin(commit_draft(conn, id, user_id, household_id))
+    })
+    .await?;
 
     for item in &created_items {
         state
@@ -457,115 +528,126 @@ pub async fn commit_item_import_draft(
 }
 
 /// Analyze photos using AI and create an item import draft
+#[utoipa::path(
+    post,
+    path = "/api/item-import-drafts/analyze",
+    tag = "item_import_drafts",
+    request_body = AnalyzePhotoRequest,
+    responses(
+        (status = 200, description = "Draft created from the photo", body = ItemImportDraftResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Location or photo not found", body = ErrorResponse),
+        (status = 422, description = "Nothing recognizable in the photo"),
+        (status = 503, description = "Photo analysis is not configured")
+    )
+)]
 pub async fn analyze_photo_and_create_draft(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Json(payload): Json<AnalyzePhotoRequest>,
-) -> impl IntoResponse {
+) -> Result<Response, ApiError> {
     // Check if vision service is available
     let vision = match &state.vision {
         Some(v) => v,
         None => {
-            return (
+            return Ok((
                 StatusCode::SERVICE_UNAVAILABLE,
                 Json(serde_json::json!({
                     "error": "AI service unavailable",
-                    "message": "Vision analysis is not configured. Please set ANTHROPIC_API_KEY."
+                    "message": "Vision analysis is not configured. Please set ANTHROPIC_API_KEY, or OPENAI_API_KEY with VISION_BACKEND=openai."
                 })),
             )
-                .into_response();
+                .into_response());
         }
     };
 
     // Validate request
     if let Err(e) = payload.validate_location() {
-        return (StatusCode::BAD_REQUEST, Json(json!({"error": e}))).into_response();
+        return Ok((StatusCode::BAD_REQUEST, Json(json!({"error": e}))).into_response());
     }
 
-    if payload.photo_ids.is_empty() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({"error": "At least one photo_id required"})),
+    if let Err(e) = payload.validate_hint() {
+        return Ok((StatusCode::BAD_REQUEST, Json(json!({"error": e}))).into_response());
+    }
+
+    if let Err(e) = payload.validate_photo_count() {
+        return Ok((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(json!({
+                "error": e,
+                "field": "photo_ids",
+                "min": 1,
+                "max": MAX_ANALYZE_PHOTOS,
+                "received": payload.photo_ids.len(),
+            })),
         )
-            .into_response();
+            .into_response());
     }
 
-    // Verify location exists and determine type
+    let household_id = state.resolve_household(user_id).await?;
+
+    // Verify location exists in the household and determine type
     let location_type = if let Some(container_id) = payload.container_id {
-        let exists = match sqlx::query("SELECT id FROM containers WHERE id = $1")
+        let exists = sqlx::query("SELECT id FROM containers WHERE id = $1 AND household_id = $2")
             .bind(container_id)
+            .bind(household_id)
             .fetch_optional(&state.db)
-            .await
-        {
-            Ok(result) => result.is_some(),
-            Err(e) => {
-                tracing::error!("Failed to verify container exists: {e:?}");
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-            }
-        };
+            .await?
+            .is_some();
         if !exists {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({"error": "Container not found"})),
-            )
-                .into_response();
+            return Err(ApiError::not_found("Container", container_id));
         }
         LocationType::Container
     } else {
         let shelf_id = payload.shelf_id.unwrap();
-        let exists = match sqlx::query("SELECT id FROM shelves WHERE id = $1")
+        let exists = sqlx::query("SELECT id FROM shelves WHERE id = $1 AND household_id = $2")
             .bind(shelf_id)
+            .bind(household_id)
             .fetch_optional(&state.db)
-            .await
-        {
-            Ok(result) => result.is_some(),
-            Err(e) => {
-                tracing::error!("Failed to verify shelf exists: {e:?}");
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-            }
-        };
+            .await?
+            .is_some();
         if !exists {
-            return (
-                StatusCode::NOT_FOUND,
-                Json(json!({"error": "Shelf not found"})),
-            )
-                .into_response();
+            return Err(ApiError::not_found("Shelf", shelf_id));
         }
         LocationType::Shelf
     };
 
-    // Fetch and download all photos
-    let mut images: Vec<(Vec<u8>, String)> = Vec::new();
+    // Fetch all the household's photos, keeping the order they were sent in
+    let found = sqlx::query_as::<_, Photo>(&format!(
+        "SELECT * FROM photos p WHERE p.id = ANY($1) AND {}",
+        household_service::attached_in_household_sql("p", 2)
+    ))
+    .bind(&payload.photo_ids)
+    .bind(household_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let mut photos: Vec<Photo> = Vec::with_capacity(payload.photo_ids.len());
     for photo_id in &payload.photo_ids {
-        let photo = match sqlx::query_as::<_, Photo>("SELECT * FROM photos WHERE id = $1")
-            .bind(photo_id)
-            .fetch_optional(&state.db)
-            .await
-        {
-            Ok(Some(photo)) => photo,
-            Ok(None) => {
-                tracing::error!("Photo not found: {}", photo_id);
-                return (
-                    StatusCode::NOT_FOUND,
-                    Json(json!({"error": format!("Photo {} not found", photo_id)})),
-                )
-                    .into_response();
-            }
-            Err(e) => {
-                tracing::error!("Failed to fetch photo: {e:?}");
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-            }
-        };
+        match found.iter().find(|photo| photo.id == *photo_id) {
+            Some(photo) => photos.push(photo.clone()),
+            None => return Err(ApiError::not_found("Photo", photo_id)),
+        }
+    }
 
-        let image_bytes = match state.s3.get_object_bytes(&photo.s3_key).await {
-            Ok(bytes) => bytes,
+    // Download them from S3 in parallel
+    let downloads = join_all(
+        photos
+            .iter()
+            .map(|photo| state.s3.get_object_bytes(&photo.s3_key)),
+    )
+    .await;
+
+    let mut images: Vec<(Vec<u8>, String)> = Vec::with_capacity(photos.len());
+    for (photo, download) in photos.into_iter().zip(downloads) {
+        match download {
+            Ok(bytes) => images.push((bytes, photo.content_type)),
             Err(e) => {
-                tracing::error!("Failed to download photo from S3: {e:?}");
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                tracing::error!("Failed to download photo {} from S3: {e:?}", photo.id);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
             }
-        };
-
-        images.push((image_bytes, photo.content_type));
+        }
     }
 
     // Convert to references for vision service
@@ -596,14 +678,14 @@ pub async fn analyze_photo_and_create_draft(
                 StatusCode::INTERNAL_SERVER_ERROR
             };
 
-            return (
+            return Ok((
                 status,
                 Json(serde_json::json!({
                     "error": "AI analysis failed",
                     "message": error_message
                 })),
             )
-                .into_response();
+                .into_response());
         }
     };
 
@@ -613,34 +695,25 @@ pub async fn analyze_photo_and_create_draft(
     }
 
     // Create the draft
-    let proposed_items = match serde_json::to_value(&items) {
-        Ok(value) => value,
-        Err(e) => {
-            tracing::error!("Failed to serialize proposed items: {e:?}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-    };
+    let proposed_items = serde_json::to_value(&items).map_err(|e| {
+        tracing::error!("Failed to serialize proposed items: {e:?}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
     let proposed_location_updates = match location_updates {
-        Some(updates) => match serde_json::to_value(&updates) {
-            Ok(value) => Some(value),
-            Err(e) => {
-                tracing::error!("Failed to serialize location updates: {e:?}");
-                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-            }
-        },
+        Some(updates) => Some(serde_json::to_value(&updates).map_err(|e| {
+            tracing::error!("Failed to serialize location updates: {e:?}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?),
         None => None,
     };
 
-    let source_photo_ids = match serde_json::to_value(&payload.photo_ids) {
-        Ok(value) => value,
-        Err(e) => {
-            tracing::error!("Failed to serialize source_photo_ids: {e:?}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-    };
+    let source_photo_ids = serde_json::to_value(&payload.photo_ids).map_err(|e| {
+        tracing::error!("Failed to serialize source_photo_ids: {e:?}");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
-    let draft = match sqlx::query_as::<_, ItemImportDraft>(
+    let draft = sqlx::query_as::<_, ItemImportDraft>(
         r#"
         INSERT INTO item_import_drafts (
             id,
@@ -667,14 +740,7 @@ pub async fn analyze_photo_and_create_draft(
     .bind(source_photo_ids)
     .bind(user_id)
     .fetch_one(&state.db)
-    .await
-    {
-        Ok(draft) => draft,
-        Err(e) => {
-            tracing::error!("Failed to create item import draft: {e:?}");
-            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
-        }
-    };
+    .await?;
 
     state
         .audit
@@ -682,13 +748,7 @@ pub async fn analyze_photo_and_create_draft(
         .await
         .ok();
 
-    match draft_to_response(draft) {
-        Ok(response) => Json(response).into_response(),
-        Err(e) => {
-            tracing::error!("Failed to convert draft to response: {e:?}");
-            StatusCode::INTERNAL_SERVER_ERROR.into_response()
-        }
-    }
+    Ok(Json(draft_to_response(draft)?).into_response())
 }
 
 pub fn item_import_draft_routes() -> Router<Arc<AppState>> {
@@ -1,115 +1,519 @@
 use axum::{
+    body::Body,
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{header, StatusCode},
+    response::{Json, Response},
     Router,
 };
+use chrono::{NaiveDate, Utc};
+use futures::future::try_join_all;
+use futures::TryStreamExt;
+use rust_decimal::Decimal;
 use serde_json::json;
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::db::{with_transaction, DbDialect};
+use crate::error::{ApiError, ErrorResponse};
 use crate::middleware::auth::AuthUser;
+use crate::middleware::deprecation::patch_with_put_alias;
 use crate::models::{
-    BulkCreateItemsRequest, BulkCreateItemsResponse, CreateItemRequest, Item, ItemResponse,
-    PaginatedResponse, PaginationQuery, PublicItemResponse, UpdateItemRequest,
+    name_similarity, normalize_tag_name, parse_currency, purchase_price_valid, quantities_valid,
+    warranty_expiry_valid, AbortMultipartUploadRequest, AdjustQuantityRequest,
+    BarcodeCheckResponse, BulkCreateItemsRequest, BulkCreateItemsResponse, BulkDeleteItemsRequest,
+    BulkDeleteItemsResponse, Clearable, CompleteMultipartUploadRequest, Condition,
+    CreateItemRequest, FileUploadResponse, Item, ItemResponse, MultipartUploadResponse,
+    PaginatedResponse, PaginationQuery, Photo, PhotoResponse, PublicItemResponse, SimilarItem,
+    SimilarItemResponse, TransferItemRequest, UpdateItemRequest, DEFAULT_CURRENCY,
+    DEFAULT_EXPIRING_WITHIN_DAYS, DUPLICATE_SIMILARITY_THRESHOLD, MAX_BULK_DELETE_ITEMS,
+    MAX_EXPIRING_WITHIN_DAYS, MAX_SIMILAR_ITEMS,
 };
+use crate::routes::photos::{attach_photo_summaries, fetch_entity_photos, IncludePhotosQuery};
+use crate::routes::tags::{attach_tags, IncludeTagsQuery};
+use crate::services::audit::Auditable;
+use crate::services::household::{self as household_service, HouseholdEntity};
+use crate::services::s3::{multipart_plan, MAX_MULTIPART_PARTS, UPLOAD_URL_EXPIRES_IN_SECS};
+use crate::utils::{CsvEncoder, Cursor, CursorDecoder, CursorEncoder};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct FileUploadRequest {
     pub file_type: String, // "manual" or "receipt"
     pub content_type: String,
+    /// Size of the file; large files get a multipart upload
+    pub file_size_bytes: Option<u64>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct FileUploadResponse {
-    pub upload_url: String,
-    pub s3_key: String,
-}
-
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct FileDownloadRequest {
     pub s3_key: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct FileDownloadResponse {
     pub download_url: String,
 }
 
-/// Get all items
+/// Filters for `GET /api/items` beyond `search`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ItemFilterQuery {
+    /// Only items in this condition, e.g. `good`
+    pub condition: Option<String>,
+}
+
+/// Duplicate check on `POST /api/items`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DuplicateCheckQuery {
+    /// Refuse with `POSSIBLE_DUPLICATE` when items in the same location have a
+    /// similar name
+    #[serde(default)]
+    pub check_duplicates: bool,
+    /// Create anyway, skipping the duplicate check
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExpiringItemsQuery {
+    /// Days ahead to look, including today
+    pub within_days: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportItemsQuery {
+    pub search: Option<String>,
+    pub tag: Option<String>,
+}
+
+const ITEM_LOCATION_REQUIRED: &str = "Exactly one of shelf_id or container_id is required";
+const INVALID_QUANTITY: &str = "quantity and min_quantity must not be negative";
+const INVALID_PURCHASE_PRICE: &str = "purchase_price must be between 0 and 9999999999.99";
+const INVALID_CURRENCY: &str = "currency must be a three-letter ISO 4217 code";
+const ITEM_PERMISSION_DENIED: &str = "Only the item owner or creator may perform this action";
+const INVALID_CONDITION: &str = "condition must be one of new, like_new, good, fair, poor";
+
+/// Check a condition before it reaches the database, whose CHECK constraint would
+/// only produce an opaque 500
+fn parse_condition(condition: Option<&str>) -> Result<Option<Condition>, ApiError> {
+    condition
+        .map(|value| {
+            value.trim().parse().map_err(|e| {
+                tracing::warn!("{}", e);
+                ApiError::bad_request(INVALID_CONDITION)
+            })
+        })
+        .transpose()
+}
+
+/// Validate a purchase price and its currency, defaulting the currency to USD
+/// when only a price is given
+fn resolve_purchase_price(
+    price: Option<Decimal>,
+    currency: Option<String>,
+) -> Result<(Option<Decimal>, Option<String>), ApiError> {
+    if !purchase_price_valid(price) {
+        return Err(ApiError::bad_request(INVALID_PURCHASE_PRICE));
+    }
+    let currency = match currency {
+        Some(code) => {
+            Some(parse_currency(&code).ok_or_else(|| ApiError::bad_request(INVALID_CURRENCY))?)
+        }
+        None => price.map(|_| DEFAULT_CURRENCY.to_string()),
+    };
+    Ok((price, currency))
+}
+
+/// Rows buffered between the export query and the response body
+const EXPORT_BUFFER_ROWS: usize = 64;
+
+const EXPORT_CSV_HEADER: [&str; 12] = [
+    "id",
+    "name",
+    "description",
+    "barcode",
+    "barcode_type",
+    "location_type",
+    "location_id",
+    "location_name",
+    "room_name",
+    "acquired_date",
+    "product_link",
+    "tags",
+];
+
+/// Items with their location and room resolved. Items in nested containers get their
+/// room from the shelf holding the outermost container.
+const EXPORT_ITEMS_SQL: &str = r#"
+    WITH RECURSIVE container_roots AS (
+        SELECT id, shelf_id FROM containers WHERE parent_container_id IS NULL
+        UNION ALL
+        SELECT c.id, cr.shelf_id
+        FROM containers c
+        JOIN container_roots cr ON c.parent_container_id = cr.id
+    )
+    SELECT
+        i.id,
+        i.name,
+        i.description,
+        i.barcode,
+        i.barcode_type,
+        CASE WHEN i.container_id IS NOT NULL THEN 'container' ELSE 'shelf' END AS location_type,
+        COALESCE(i.container_id, i.shelf_id) AS location_id,
+        COALESCE(c.name, s.name) AS location_name,
+        r.name AS room_name,
+        i.acquired_date,
+        i.product_link,
+        (
+            SELECT string_agg(t.name, ';' ORDER BY t.name)
+            FROM entity_tags et
+            JOIN tags t ON t.id = et.tag_id
+            WHERE et.entity_type = 'item' AND et.entity_id = i.id
+        ) AS tags
+    FROM items i
+    LEFT JOIN containers c ON c.id = i.container_id
+    LEFT JOIN container_roots cr ON cr.id = i.container_id
+    LEFT JOIN shelves s ON s.id = COALESCE(i.shelf_id, cr.shelf_id)
+    LEFT JOIN shelving_units u ON u.id = s.shelving_unit_id
+    LEFT JOIN rooms r ON r.id = u.room_id
+    WHERE i.deleted_at IS NULL
+      AND i.household_id = $3
+      AND ($1::TEXT IS NULL OR i.name ILIKE $1 OR i.description ILIKE $1 OR i.barcode ILIKE $1)
+      AND ($2::TEXT IS NULL OR EXISTS (
+          SELECT 1
+          FROM entity_tags et
+          JOIN tags t ON t.id = et.tag_id
+          WHERE et.entity_type = 'item' AND et.entity_id = i.id AND t.name = $2
+      ))
+    ORDER BY i.created_at
+"#;
+
+#[derive(Debug, sqlx::FromRow)]
+struct ItemExportRow {
+    id: Uuid,
+    name: String,
+    description: Option<String>,
+    barcode: Option<String>,
+    barcode_type: Option<String>,
+    location_type: String,
+    location_id: Option<Uuid>,
+    location_name: Option<String>,
+    room_name: Option<String>,
+    acquired_date: Option<NaiveDate>,
+    product_link: Option<String>,
+    tags: Option<String>,
+}
+
+impl ItemExportRow {
+    fn into_record(self) -> [String; 12] {
+        [
+            self.id.to_string(),
+            self.name,
+            self.description.unwrap_or_default(),
+            self.barcode.unwrap_or_default(),
+            self.barcode_type.unwrap_or_default(),
+            self.location_type,
+            self.location_id
+                .map(|id| id.to_string())
+                .unwrap_or_default(),
+            self.location_name.unwrap_or_default(),
+            self.room_name.unwrap_or_default(),
+            self.acquired_date
+                .map(|date| date.to_string())
+                .unwrap_or_default(),
+            self.product_link.unwrap_or_default(),
+            self.tags.unwrap_or_default(),
+        ]
+    }
+}
+
+/// Barcode lookup is case-insensitive: Code128/Code39 barcodes can contain letters
+const GET_ITEM_BY_BARCODE_SQL: &str = "SELECT * FROM items WHERE LOWER(barcode) = LOWER($1) AND household_id = $2 AND deleted_at IS NULL";
+
+/// A live item using a barcode. Barcodes are unique across households, so this may
+/// be another household's item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, sqlx::FromRow)]
+pub(crate) struct BarcodeOwner {
+    pub id: Uuid,
+    pub household_id: Option<Uuid>,
+}
+
+impl BarcodeOwner {
+    /// The item's id if it is in `household_id`; other households only learn that
+    /// the barcode is taken
+    pub(crate) fn visible_id(self, household_id: Option<Uuid>) -> Option<Uuid> {
+        (household_id.is_some() && self.household_id == household_id).then_some(self.id)
+    }
+
+    /// 409 for a caller in `household_id`
+    pub(crate) fn conflict(self, household_id: Uuid) -> ApiError {
+        ApiError::BarcodeConflict {
+            existing_item_id: self.visible_id(Some(household_id)),
+        }
+    }
+}
+
+/// The item other than `exclude_id` using a barcode (case-insensitive), if any.
+/// Items in the trash don't count, but restoring one re-checks its barcode.
+/// Uniqueness is enforced here rather than with a unique index, which DSQL can't build
+/// on an expression or with a WHERE clause (see the add_items_barcode_unique_index
+/// migration).
+pub(crate) async fn barcode_owner<'e, E>(
+    executor: E,
+    barcode: &str,
+    exclude_id: Option<Uuid>,
+) -> Result<Option<BarcodeOwner>, ApiError>
+where
+    E: sqlx::PgExecutor<'e>,
+{
+    let owner = sqlx::query_as(
+        "SELECT id, household_id FROM items WHERE LOWER(barcode) = LOWER($1) AND deleted_at IS NULL AND ($2::uuid IS NULL OR id != $2) LIMIT 1",
+    )
+    .bind(barcode)
+    .bind(exclude_id)
+    .fetch_optional(executor)
+    .await?;
+    Ok(owner)
+}
+
+/// 409 when `err` violates the unique barcode index (PostgreSQL only), which catches
+/// saves racing past the barcode_owner check. The other item isn't known here, so no
+/// id is reported.
+pub(crate) fn barcode_conflict(err: sqlx::Error) -> ApiError {
+    match err {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+            ApiError::BarcodeConflict {
+                existing_item_id: None,
+            }
+        }
+        err => ApiError::from(err),
+    }
+}
+
+/// Conditions after the household in `GET /api/items`, shared by the page and its
+/// total so they always agree
+fn push_item_filters<'a>(
+    query: &mut QueryBuilder<'a, Postgres>,
+    search_pattern: Option<&'a str>,
+    condition: Option<Condition>,
+    dates: &PaginationQuery,
+) {
+    if let Some(pattern) = search_pattern {
+        query
+            .push(" AND (name ILIKE ")
+            .push_bind(pattern)
+            .push(" OR description ILIKE ")
+            .push_bind(pattern)
+            .push(" OR barcode ILIKE ")
+            .push_bind(pattern)
+            .push(")");
+    }
+    if let Some(condition) = condition {
+        query
+            .push(" AND condition = ")
+            .push_bind(condition.as_str());
+    }
+    if let Some(after) = dates.acquired_after {
+        query.push(" AND acquired_date >= ").push_bind(after);
+    }
+    if let Some(before) = dates.acquired_before {
+        query.push(" AND acquired_date <= ").push_bind(before);
+    }
+    if let Some(after) = dates.created_after {
+        query.push(" AND created_at >= ").push_bind(after);
+    }
+    if let Some(before) = dates.created_before {
+        query.push(" AND created_at <= ").push_bind(before);
+    }
+}
+
+/// Number of a household's live items matching the filters
+fn items_count_query<'a>(
+    household_id: Uuid,
+    search_pattern: Option<&'a str>,
+    condition: Option<Condition>,
+    dates: &PaginationQuery,
+) -> QueryBuilder<'a, Postgres> {
+    let mut query = QueryBuilder::new(
+        "SELECT COUNT(*) FROM items WHERE deleted_at IS NULL AND household_id = ",
+    );
+    query.push_bind(household_id);
+    push_item_filters(&mut query, search_pattern, condition, dates);
+    query
+}
+
+/// Page of a household's live items, newest first. With a cursor the page starts after
+/// it (keyset pagination) and `offset` is ignored; `id` breaks ties between rows created
+/// in the same instant so no row is skipped or repeated across pages.
+fn items_page_query<'a>(
+    household_id: Uuid,
+    search_pattern: Option<&'a str>,
+    condition: Option<Condition>,
+    dates: &PaginationQuery,
+    cursor: Option<&Cursor>,
+    limit: i32,
+    offset: i32,
+) -> QueryBuilder<'a, Postgres> {
+    let mut query =
+        QueryBuilder::new("SELECT * FROM items WHERE deleted_at IS NULL AND household_id = ");
+    query.push_bind(household_id);
+    push_item_filters(&mut query, search_pattern, condition, dates);
+
+    if let Some(cursor) = cursor {
+        query
+            .push(" AND (created_at, id) < (")
+            .push_bind(cursor.created_at)
+            .push(", ")
+            .push_bind(cursor.id)
+            .push(")");
+    }
+
+    query
+        .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+        .push_bind(limit);
+    if cursor.is_none() {
+        query.push(" OFFSET ").push_bind(offset);
+    }
+
+    query
+}
+
+/// Get all items in the user's household
+///
+/// Search uses `ILIKE '%term%'`, which can't use the B-tree index on `name` because of the
+/// leading wildcard. DSQL has no pg_trgm/GIN support, so search is a sequential scan and
+/// degrades as the items table grows (see the add_items_name_search_index migration).
+///
+/// Pass `cursor` (the previous page's `next_cursor`) instead of `offset` to page through
+/// large result sets without the cost of skipping rows. The `acquired_*` and `created_*`
+/// date filters are inclusive and combine with `search` and `condition`.
+#[utoipa::path(
+    get,
+    path = "/api/items",
+    tag = "items",
+    params(
+        PaginationQuery,
+        ItemFilterQuery,
+        IncludePhotosQuery,
+        IncludeTagsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ItemResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 422, description = "A date range ends before it starts", body = ErrorResponse)
+    )
+)]
 pub async fn list_items(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ItemResponse>>, StatusCode> {
+    Query(filter): Query<ItemFilterQuery>,
+    Query(photos): Query<IncludePhotosQuery>,
+    Query(tags): Query<IncludeTagsQuery>,
+) -> Result<Json<PaginatedResponse<ItemResponse>>, ApiError> {
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
+    let household_id = state.resolve_household(user_id).await?;
+    let condition = parse_condition(filter.condition.as_deref())?;
+
+    params
+        .validate_date_ranges()
+        .map_err(|e| ApiError::UnprocessableEntity(e.to_string()))?;
 
     // Build search condition if provided
     let search_pattern = params.search.as_ref().map(|s| format!("%{}%", s.trim()));
 
-    // Get total count with search filter
-    let total: i64 = if let Some(ref pattern) = search_pattern {
-        sqlx::query_scalar(
-            "SELECT COUNT(*) FROM items WHERE name ILIKE $1 OR description ILIKE $1 OR barcode ILIKE $1"
-        )
-        .bind(pattern)
+    // Get total count with the same filters as the page
+    let total: i64 = items_count_query(household_id, search_pattern.as_deref(), condition, &params)
+        .build_query_scalar::<i64>()
         .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count items with search: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-    } else {
-        sqlx::query_scalar("SELECT COUNT(*) FROM items")
-            .fetch_one(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to count items: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
-    };
+        .await?;
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
-    // Get paginated items with search filter
-    let items = if let Some(ref pattern) = search_pattern {
-        sqlx::query_as::<_, Item>(
-            "SELECT * FROM items WHERE name ILIKE $1 OR description ILIKE $1 OR barcode ILIKE $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3"
-        )
-        .bind(pattern)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&state.db)
-        .await
+    let cursor = params
+        .cursor
+        .as_deref()
+        .map(CursorDecoder::decode)
+        .transpose()
         .map_err(|e| {
-            tracing::error!("Failed to fetch items with search: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+            tracing::warn!("Invalid items cursor: {}", e);
+            StatusCode::BAD_REQUEST
+        })?;
+    // A cursor replaces the offset
+    let offset = if cursor.is_some() { 0 } else { offset };
+
+    // One extra row tells us whether there is a next page
+    let mut items = items_page_query(
+        household_id,
+        search_pattern.as_deref(),
+        condition,
+        &params,
+        cursor.as_ref(),
+        limit + 1,
+        offset,
+    )
+    .build_query_as::<Item>()
+    .fetch_all(&state.db)
+    .await?;
+
+    let next_cursor = if items.len() > limit as usize {
+        items.truncate(limit as usize);
+        items.last().map(|item| {
+            CursorEncoder::encode(&Cursor {
+                created_at: item.created_at,
+                id: item.id,
+            })
+        })
     } else {
-        sqlx::query_as::<_, Item>("SELECT * FROM items ORDER BY created_at DESC LIMIT $1 OFFSET $2")
-            .bind(limit)
-            .bind(offset)
-            .fetch_all(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to fetch items: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
+        None
     };
 
-    let responses: Vec<ItemResponse> = items.into_iter().map(ItemResponse::from).collect();
-    Ok(Json(PaginatedResponse::new(
-        responses, total, limit, offset,
-    )))
+    let mut responses: Vec<ItemResponse> = items.into_iter().map(ItemResponse::from).collect();
+    if photos.include_photos {
+        attach_photo_summaries(&state, "item", &mut responses).await?;
+    }
+    if tags.include_tags {
+        attach_tags(&state, "item", &mut responses).await?;
+    }
+    Ok(Json(
+        PaginatedResponse::new(responses, total, limit, offset).with_next_cursor(next_cursor),
+    ))
 }
 
 /// Get items by shelf
+#[utoipa::path(
+    get,
+    path = "/api/shelves/{shelf_id}/items",
+    tag = "items",
+    params(
+        ("shelf_id" = Uuid, Path, description = "Shelf id"),
+        PaginationQuery,
+        IncludePhotosQuery,
+        IncludeTagsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ItemResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelf not found", body = ErrorResponse)
+    )
+)]
 pub async fn list_items_by_shelf(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(shelf_id): Path<Uuid>,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ItemResponse>>, StatusCode> {
+    Query(photos): Query<IncludePhotosQuery>,
+    Query(tags): Query<IncludeTagsQuery>,
+) -> Result<Json<PaginatedResponse<ItemResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Shelf, shelf_id)
+        .await?;
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
 
@@ -119,70 +523,82 @@ pub async fn list_items_by_shelf(
     // Get total count with search filter
     let total: i64 = if let Some(ref pattern) = search_pattern {
         sqlx::query_scalar(
-            "SELECT COUNT(*) FROM items WHERE shelf_id = $1 AND (name ILIKE $2 OR description ILIKE $2 OR barcode ILIKE $2)"
+            "SELECT COUNT(*) FROM items WHERE shelf_id = $1 AND deleted_at IS NULL AND (name ILIKE $2 OR description ILIKE $2 OR barcode ILIKE $2)"
         )
         .bind(shelf_id)
         .bind(pattern)
         .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count items with search: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .await?
     } else {
-        sqlx::query_scalar("SELECT COUNT(*) FROM items WHERE shelf_id = $1")
+        sqlx::query_scalar("SELECT COUNT(*) FROM items WHERE shelf_id = $1 AND deleted_at IS NULL")
             .bind(shelf_id)
             .fetch_one(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to count items: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
+            .await?
     };
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
     // Get paginated items with search filter
     let items = if let Some(ref pattern) = search_pattern {
         sqlx::query_as::<_, Item>(
-            "SELECT * FROM items WHERE shelf_id = $1 AND (name ILIKE $2 OR description ILIKE $2 OR barcode ILIKE $2) ORDER BY created_at LIMIT $3 OFFSET $4"
+            "SELECT * FROM items WHERE shelf_id = $1 AND deleted_at IS NULL AND (name ILIKE $2 OR description ILIKE $2 OR barcode ILIKE $2) ORDER BY created_at LIMIT $3 OFFSET $4"
         )
         .bind(shelf_id)
         .bind(pattern)
         .bind(limit)
         .bind(offset)
         .fetch_all(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch items with search: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .await?
     } else {
         sqlx::query_as::<_, Item>(
-            "SELECT * FROM items WHERE shelf_id = $1 ORDER BY created_at LIMIT $2 OFFSET $3",
+            "SELECT * FROM items WHERE shelf_id = $1 AND deleted_at IS NULL ORDER BY created_at LIMIT $2 OFFSET $3",
         )
         .bind(shelf_id)
         .bind(limit)
         .bind(offset)
         .fetch_all(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch items: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .await?
     };
 
-    let responses: Vec<ItemResponse> = items.into_iter().map(ItemResponse::from).collect();
+    let mut responses: Vec<ItemResponse> = items.into_iter().map(ItemResponse::from).collect();
+    if photos.include_photos {
+        attach_photo_summaries(&state, "item", &mut responses).await?;
+    }
+    if tags.include_tags {
+        attach_tags(&state, "item", &mut responses).await?;
+    }
     Ok(Json(PaginatedResponse::new(
         responses, total, limit, offset,
     )))
 }
 
 /// Get items by container
+#[utoipa::path(
+    get,
+    path = "/api/containers/{container_id}/items",
+    tag = "items",
+    params(
+        ("container_id" = Uuid, Path, description = "Container id"),
+        PaginationQuery,
+        IncludePhotosQuery,
+        IncludeTagsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ItemResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Container not found", body = ErrorResponse)
+    )
+)]
 pub async fn list_items_by_container(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(container_id): Path<Uuid>,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ItemResponse>>, StatusCode> {
+    Query(photos): Query<IncludePhotosQuery>,
+    Query(tags): Query<IncludeTagsQuery>,
+) -> Result<Json<PaginatedResponse<ItemResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Container, container_id)
+        .await?;
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
 
@@ -192,138 +608,157 @@ pub async fn list_items_by_container(
     // Get total count with search filter
     let total: i64 = if let Some(ref pattern) = search_pattern {
         sqlx::query_scalar(
-            "SELECT COUNT(*) FROM items WHERE container_id = $1 AND (name ILIKE $2 OR description ILIKE $2 OR barcode ILIKE $2)"
+            "SELECT COUNT(*) FROM items WHERE container_id = $1 AND deleted_at IS NULL AND (name ILIKE $2 OR description ILIKE $2 OR barcode ILIKE $2)"
         )
         .bind(container_id)
         .bind(pattern)
         .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count items with search: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .await?
     } else {
-        sqlx::query_scalar("SELECT COUNT(*) FROM items WHERE container_id = $1")
-            .bind(container_id)
-            .fetch_one(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to count items: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM items WHERE container_id = $1 AND deleted_at IS NULL",
+        )
+        .bind(container_id)
+        .fetch_one(&state.db)
+        .await?
     };
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
     // Get paginated items with search filter
     let items = if let Some(ref pattern) = search_pattern {
         sqlx::query_as::<_, Item>(
-            "SELECT * FROM items WHERE container_id = $1 AND (name ILIKE $2 OR description ILIKE $2 OR barcode ILIKE $2) ORDER BY created_at LIMIT $3 OFFSET $4"
+            "SELECT * FROM items WHERE container_id = $1 AND deleted_at IS NULL AND (name ILIKE $2 OR description ILIKE $2 OR barcode ILIKE $2) ORDER BY created_at LIMIT $3 OFFSET $4"
         )
         .bind(container_id)
         .bind(pattern)
         .bind(limit)
         .bind(offset)
         .fetch_all(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch items with search: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .await?
     } else {
         sqlx::query_as::<_, Item>(
-            "SELECT * FROM items WHERE container_id = $1 ORDER BY created_at LIMIT $2 OFFSET $3",
+            "SELECT * FROM items WHERE container_id = $1 AND deleted_at IS NULL ORDER BY created_at LIMIT $2 OFFSET $3",
         )
         .bind(container_id)
         .bind(limit)
         .bind(offset)
         .fetch_all(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch items: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .await?
     };
 
-    let responses: Vec<ItemResponse> = items.into_iter().map(ItemResponse::from).collect();
+    let mut responses: Vec<ItemResponse> = items.into_iter().map(ItemResponse::from).collect();
+    if photos.include_photos {
+        attach_photo_summaries(&state, "item", &mut responses).await?;
+    }
+    if tags.include_tags {
+        attach_tags(&state, "item", &mut responses).await?;
+    }
     Ok(Json(PaginatedResponse::new(
         responses, total, limit, offset,
     )))
 }
 
 /// Get a single item by ID
+#[utoipa::path(
+    get,
+    path = "/api/items/{id}",
+    tag = "items",
+    params(
+        ("id" = Uuid, Path, description = "Item id"),
+        IncludeTagsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = ItemResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_item(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<ItemResponse>, StatusCode> {
-    let item = sqlx::query_as::<_, Item>("SELECT * FROM items WHERE id = $1")
-        .bind(id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch item: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    Ok(Json(ItemResponse::from(item)))
-}
+    Query(tags): Query<IncludeTagsQuery>,
+) -> Result<Json<ItemResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Item, id)
+        .await?;
+    let item =
+        sqlx::query_as::<_, Item>("SELECT * FROM items WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Item", id))?;
 
-/// Bulk create new items
-pub async fn bulk_create_items(
-    State(state): State<Arc<AppState>>,
-    AuthUser(user_id): AuthUser,
-    Json(payload): Json<BulkCreateItemsRequest>,
-) -> Result<Json<BulkCreateItemsResponse>, StatusCode> {
-    if payload.items.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+    let mut response = ItemResponse::from(item);
+    if tags.include_tags {
+        attach_tags(&state, "item", std::slice::from_mut(&mut response)).await?;
     }
+    Ok(Json(response))
+}
 
-    let mut tx = state.db.begin().await.map_err(|e| {
-        tracing::error!("Failed to start transaction for bulk item create: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-
-    let mut created_items: Vec<ItemResponse> = Vec::with_capacity(payload.items.len());
+/// Validate and insert `items` for `bulk_create_items`, failing on the first bad one
+async fn insert_bulk_items(
+    conn: &mut PgConnection,
+    items: Vec<CreateItemRequest>,
+    user_id: Uuid,
+    household_id: Uuid,
+) -> Result<Vec<ItemResponse>, ApiError> {
+    let mut created_items: Vec<ItemResponse> = Vec::with_capacity(items.len());
+
+    for item_req in items {
+        if !quantities_valid(item_req.quantity, item_req.min_quantity) {
+            return Err(ApiError::bad_request(INVALID_QUANTITY));
+        }
+        let (purchase_price, currency) =
+            resolve_purchase_price(item_req.purchase_price, item_req.currency.clone())?;
+        let condition = parse_condition(item_req.condition.as_deref())?;
 
-    for item_req in payload.items {
         // Validate location constraint: exactly one of shelf_id or container_id must be provided
         let (shelf_id, container_id) = match (item_req.shelf_id, item_req.container_id) {
             (Some(sid), None) => (Some(sid), None),
             (None, Some(cid)) => (None, Some(cid)),
-            (Some(_), Some(_)) => return Err(StatusCode::BAD_REQUEST),
-            (None, None) => return Err(StatusCode::BAD_REQUEST),
+            (Some(_), Some(_)) => return Err(ApiError::bad_request(ITEM_LOCATION_REQUIRED)),
+            (None, None) => return Err(ApiError::bad_request(ITEM_LOCATION_REQUIRED)),
         };
 
-        // Verify location exists
+        // Verify location exists in the household
         if let Some(sid) = shelf_id {
-            let shelf_exists = sqlx::query("SELECT id FROM shelves WHERE id = $1")
-                .bind(sid)
-                .fetch_optional(&mut *tx)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to verify shelf in bulk item create: {:?}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?
-                .is_some();
+            let shelf_exists =
+                sqlx::query("SELECT id FROM shelves WHERE id = $1 AND household_id = $2")
+                    .bind(sid)
+                    .bind(household_id)
+                    .fetch_optional(&mut *conn)
+                    .await?
+                    .is_some();
 
             if !shelf_exists {
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(ApiError::bad_request(format!(
+                    "Shelf with id {} does not exist",
+                    sid
+                )));
             }
         }
 
         if let Some(cid) = container_id {
-            let container_exists = sqlx::query("SELECT id FROM containers WHERE id = $1")
-                .bind(cid)
-                .fetch_optional(&mut *tx)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to verify container in bulk item create: {:?}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?
-                .is_some();
+            let container_exists =
+                sqlx::query("SELECT id FROM containers WHERE id = $1 AND household_id = $2")
+                    .bind(cid)
+                    .bind(household_id)
+                    .fetch_optional(&mut *conn)
+                    .await?
+                    .is_some();
 
             if !container_exists {
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(ApiError::bad_request(format!(
+                    "Container with id {} does not exist",
+                    cid
+                )));
+            }
+        }
+
+        if let Some(ref barcode) = item_req.barcode {
+            if let Some(owner) = barcode_owner(&mut *conn, barcode, None).await? {
+                return Err(owner.conflict(household_id));
             }
         }
 
@@ -331,8 +766,11 @@ pub async fn bulk_create_items(
             r#"
             INSERT INTO items (id, shelf_id, container_id, name, description, barcode, barcode_type,
                               product_manual_s3_key, receipt_s3_key, product_link,
-                              belongs_to_user_id, acquired_date, created_by)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                              belongs_to_user_id, acquired_date, quantity, min_quantity,
+                              purchase_price, currency, condition, warranty_expires_on,
+                              reminder_enabled, created_by, household_id)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19,
+                    $20, $21)
             RETURNING *
             "#,
         )
@@ -348,21 +786,61 @@ pub async fn bulk_create_items(
         .bind(&item_req.product_link)
         .bind(item_req.belongs_to_user_id)
         .bind(item_req.acquired_date)
+        .bind(item_req.quantity.unwrap_or(1))
+        .bind(item_req.min_quantity)
+        .bind(purchase_price)
+        .bind(&currency)
+        .bind(condition.as_ref().map(Condition::as_str))
+        .bind(item_req.warranty_expires_on)
+        .bind(item_req.reminder_enabled.unwrap_or(false))
         .bind(user_id)
-        .fetch_one(&mut *tx)
+        .bind(household_id)
+        .fetch_one(&mut *conn)
         .await
-        .map_err(|e| {
-            tracing::error!("Failed to create item in bulk create: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .map_err(barcode_conflict)?;
 
         created_items.push(ItemResponse::from(item));
     }
 
-    tx.commit().await.map_err(|e| {
-        tracing::error!("Failed to commit bulk item create: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    Ok(created_items)
+}
+
+/// Bulk create new items. With `?include_tags=true` every created item comes back
+/// with an (empty) `tags` list, so clients can treat the response like a list page.
+#[utoipa::path(
+    post,
+    path = "/api/items/bulk",
+    tag = "items",
+    params(
+        IncludeTagsQuery
+    ),
+    request_body = BulkCreateItemsRequest,
+    responses(
+        (status = 200, description = "OK", body = BulkCreateItemsResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn bulk_create_items(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(tags): Query<IncludeTagsQuery>,
+    Json(payload): Json<BulkCreateItemsRequest>,
+) -> Result<Json<BulkCreateItemsResponse>, ApiError> {
+    if payload.items.is_empty() {
+        return Err(ApiError::bad_request("No items provided"));
+    }
+    let household_id = state.resolve_household(user_id).await?;
+
+    let mut created_items = with_transaction(&state.db, move |conn| {
+        Box::pin(insert_bulk_items(
+            conn,
+            payload.items,
+            user_id,
+            household_id,
+        ))
+    })
+    .await?;
 
     for item in &created_items {
         state
@@ -372,77 +850,230 @@ pub async fn bulk_create_items(
             .ok();
     }
 
+    if tags.include_tags {
+        attach_tags(&state, "item", &mut created_items).await?;
+    }
+
     Ok(Json(BulkCreateItemsResponse {
         items: created_items,
     }))
 }
 
 /// Get item by barcode
+#[utoipa::path(
+    get,
+    path = "/api/items/barcode/{barcode}",
+    tag = "items",
+    params(
+        ("barcode" = String, Path, description = "Barcode")
+    ),
+    responses(
+        (status = 200, description = "OK", body = ItemResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn get_item_by_barcode(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(barcode): Path<String>,
-) -> Result<Json<ItemResponse>, StatusCode> {
-    let item = sqlx::query_as::<_, Item>("SELECT * FROM items WHERE barcode = $1")
+) -> Result<Json<ItemResponse>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    let item = sqlx::query_as::<_, Item>(GET_ITEM_BY_BARCODE_SQL)
         .bind(&barcode)
+        .bind(household_id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch item by barcode: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("Item with barcode {} not found", barcode)))?;
 
     Ok(Json(ItemResponse::from(item)))
 }
 
-/// Create a new item
+/// Whether a barcode is already assigned to a live item (no authentication required),
+/// so clients can warn before uploading photos for a new item. The item's id is only
+/// returned to members of its household.
+#[utoipa::path(
+    get,
+    path = "/api/items/barcode/{barcode}/check",
+    tag = "items",
+    params(
+        ("barcode" = String, Path, description = "Barcode")
+    ),
+    responses(
+        (status = 200, description = "OK", body = BarcodeCheckResponse)
+    ),
+    security(())
+)]
+pub async fn check_barcode(
+    State(state): State<Arc<AppState>>,
+    user: Option<AuthUser>,
+    Path(barcode): Path<String>,
+) -> Result<Json<BarcodeCheckResponse>, ApiError> {
+    let Some(owner) = barcode_owner(&state.db, &barcode, None).await? else {
+        return Ok(Json(BarcodeCheckResponse {
+            exists: false,
+            item_id: None,
+        }));
+    };
+
+    // Only signed-in callers have a household, and this never creates one
+    let household_id = match user {
+        Some(AuthUser(user_id)) => household_service::current_household(&state.db, user_id).await?,
+        None => None,
+    };
+
+    Ok(Json(BarcodeCheckResponse {
+        exists: true,
+        item_id: owner.visible_id(household_id),
+    }))
+}
+
+/// The five live items in a location whose names are most similar to `name`
+const SIMILAR_ITEMS_SQL: &str = r#"
+    SELECT id, name, similarity(name, $1) AS similarity
+    FROM items
+    WHERE deleted_at IS NULL
+      AND shelf_id IS NOT DISTINCT FROM $2
+      AND container_id IS NOT DISTINCT FROM $3
+    ORDER BY similarity DESC
+    LIMIT 5
+"#;
+
+/// Items in the location named more like `name` than the duplicate threshold, most
+/// similar first. DSQL has no pg_trgm, so there the names are compared here instead.
+async fn find_similar_items(
+    state: &AppState,
+    name: &str,
+    shelf_id: Option<Uuid>,
+    container_id: Option<Uuid>,
+) -> Result<Vec<SimilarItem>, ApiError> {
+    let candidates = match state.db_dialect {
+        DbDialect::Postgres => {
+            sqlx::query_as::<_, SimilarItem>(SIMILAR_ITEMS_SQL)
+                .bind(name)
+                .bind(shelf_id)
+                .bind(container_id)
+                .fetch_all(&state.db)
+                .await?
+        }
+        DbDialect::Dsql => {
+            let names: Vec<(Uuid, String)> = sqlx::query_as(
+                "SELECT id, name FROM items WHERE deleted_at IS NULL AND shelf_id IS NOT DISTINCT FROM $1 AND container_id IS NOT DISTINCT FROM $2",
+            )
+            .bind(shelf_id)
+            .bind(container_id)
+            .fetch_all(&state.db)
+            .await?;
+
+            let mut candidates: Vec<SimilarItem> = names
+                .into_iter()
+                .map(|(id, existing)| SimilarItem {
+                    similarity: name_similarity(name, &existing),
+                    id,
+                    name: existing,
+                })
+                .collect();
+            candidates.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+            candidates.truncate(5);
+            candidates
+        }
+    };
+
+    Ok(candidates
+        .into_iter()
+        .filter(|item| item.similarity > DUPLICATE_SIMILARITY_THRESHOLD)
+        .collect())
+}
+
+/// Create a new item. With `?check_duplicates=true`, refuses with 409
+/// `POSSIBLE_DUPLICATE` when items in the same location are named much like it;
+/// resend with `?force=true` to create it anyway.
+#[utoipa::path(
+    post,
+    path = "/api/items",
+    tag = "items",
+    params(
+        DuplicateCheckQuery
+    ),
+    request_body = CreateItemRequest,
+    responses(
+        (status = 200, description = "OK", body = ItemResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 409, description = "Barcode taken, or a possible duplicate", body = ErrorResponse)
+    )
+)]
 pub async fn create_item(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
+    Query(duplicates): Query<DuplicateCheckQuery>,
     Json(payload): Json<CreateItemRequest>,
-) -> Result<Json<ItemResponse>, StatusCode> {
+) -> Result<Json<ItemResponse>, ApiError> {
     // Validate location constraint: exactly one of shelf_id or container_id must be provided
+    if !quantities_valid(payload.quantity, payload.min_quantity) {
+        return Err(ApiError::bad_request(INVALID_QUANTITY));
+    }
+    let (purchase_price, currency) =
+        resolve_purchase_price(payload.purchase_price, payload.currency.clone())?;
+    let condition = parse_condition(payload.condition.as_deref())?;
+
     let (shelf_id, container_id) = match (payload.shelf_id, payload.container_id) {
         (Some(sid), None) => (Some(sid), None),
         (None, Some(cid)) => (None, Some(cid)),
         (Some(_), Some(_)) => {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(ITEM_LOCATION_REQUIRED));
         }
         (None, None) => {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(ITEM_LOCATION_REQUIRED));
         }
     };
 
-    // Verify location exists
+    let household_id = state.resolve_household(user_id).await?;
+
+    // Verify location exists in the user's household
     if let Some(sid) = shelf_id {
-        let shelf_exists = sqlx::query("SELECT id FROM shelves WHERE id = $1")
-            .bind(sid)
-            .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to verify shelf: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
-            .is_some();
+        let shelf_exists =
+            sqlx::query("SELECT id FROM shelves WHERE id = $1 AND household_id = $2")
+                .bind(sid)
+                .bind(household_id)
+                .fetch_optional(&state.db)
+                .await?
+                .is_some();
 
         if !shelf_exists {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(format!(
+                "Shelf with id {} does not exist",
+                sid
+            )));
         }
     }
 
     if let Some(cid) = container_id {
-        let container_exists = sqlx::query("SELECT id FROM containers WHERE id = $1")
-            .bind(cid)
-            .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to verify container: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
-            .is_some();
+        let container_exists =
+            sqlx::query("SELECT id FROM containers WHERE id = $1 AND household_id = $2")
+                .bind(cid)
+                .bind(household_id)
+                .fetch_optional(&state.db)
+                .await?
+                .is_some();
 
         if !container_exists {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(format!(
+                "Container with id {} does not exist",
+                cid
+            )));
+        }
+    }
+
+    if let Some(ref barcode) = payload.barcode {
+        if let Some(owner) = barcode_owner(&state.db, barcode, None).await? {
+            return Err(owner.conflict(household_id));
+        }
+    }
+    if duplicates.check_duplicates && !duplicates.force {
+        let similar_items =
+            find_similar_items(&state, &payload.name, shelf_id, container_id).await?;
+        if !similar_items.is_empty() {
+            return Err(ApiError::PossibleDuplicate { similar_items });
         }
     }
 
@@ -450,8 +1081,11 @@ pub async fn create_item(
         r#"
         INSERT INTO items (id, shelf_id, container_id, name, description, barcode, barcode_type,
                           product_manual_s3_key, receipt_s3_key, product_link,
-                          belongs_to_user_id, acquired_date, created_by)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                          belongs_to_user_id, acquired_date, quantity, min_quantity,
+                          purchase_price, currency, condition, warranty_expires_on,
+                          reminder_enabled, created_by, household_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19,
+                $20, $21)
         RETURNING *
         "#,
     )
@@ -467,13 +1101,34 @@ pub async fn create_item(
     .bind(&payload.product_link)
     .bind(payload.belongs_to_user_id)
     .bind(payload.acquired_date)
+    .bind(payload.quantity.unwrap_or(1))
+    .bind(payload.min_quantity)
+    .bind(purchase_price)
+    .bind(&currency)
+    .bind(condition.as_ref().map(Condition::as_str))
+    .bind(payload.warranty_expires_on)
+    .bind(payload.reminder_enabled.unwrap_or(false))
     .bind(user_id)
+    .bind(household_id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to create item: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await;
+
+    let item = match item {
+        Ok(item) => item,
+        // Only raised where the database has a unique barcode index; it catches two
+        // creates racing past the check above
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            let barcode = payload.barcode.as_deref().unwrap_or_default();
+            return Err(match barcode_owner(&state.db, barcode, None).await? {
+                Some(owner) => owner.conflict(household_id),
+                None => {
+                    tracing::error!("Failed to create item: {:?}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR.into()
+                }
+            });
+        }
+        Err(e) => return Err(e.into()),
+    };
 
     // Log audit
     state
@@ -485,23 +1140,39 @@ pub async fn create_item(
     Ok(Json(ItemResponse::from(item)))
 }
 
-/// Update an item
+/// Update an item. Only its owner or creator may change `belongs_to_user_id`.
+#[utoipa::path(
+    patch,
+    path = "/api/items/{id}",
+    tag = "items",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    request_body = UpdateItemRequest,
+    responses(
+        (status = 200, description = "OK", body = ItemResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    )
+)]
 pub async fn update_item(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateItemRequest>,
-) -> Result<Json<ItemResponse>, StatusCode> {
+) -> Result<Json<ItemResponse>, ApiError> {
+    let household_id = state
+        .authorize_entity(user_id, HouseholdEntity::Item, id)
+        .await?;
+
     // Check if item exists
-    let existing = sqlx::query_as::<_, Item>("SELECT * FROM items WHERE id = $1")
-        .bind(id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch item: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+    let existing =
+        sqlx::query_as::<_, Item>("SELECT * FROM items WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Item", id))?;
 
     // Handle location changes
     let (shelf_id, container_id) = if payload.shelf_id.is_some() || payload.container_id.is_some() {
@@ -512,150 +1183,247 @@ pub async fn update_item(
         match (new_shelf_id, new_container_id) {
             (Some(sid), None) => {
                 // Verify shelf exists
-                let shelf_exists = sqlx::query("SELECT id FROM shelves WHERE id = $1")
-                    .bind(sid)
-                    .fetch_optional(&state.db)
-                    .await
-                    .map_err(|e| {
-                        tracing::error!("Failed to verify shelf: {:?}", e);
-                        StatusCode::INTERNAL_SERVER_ERROR
-                    })?
-                    .is_some();
+                let shelf_exists =
+                    sqlx::query("SELECT id FROM shelves WHERE id = $1 AND household_id = $2")
+                        .bind(sid)
+                        .bind(household_id)
+                        .fetch_optional(&state.db)
+                        .await?
+                        .is_some();
 
                 if !shelf_exists {
-                    return Err(StatusCode::BAD_REQUEST);
+                    return Err(ApiError::bad_request(format!(
+                        "Shelf with id {} does not exist",
+                        sid
+                    )));
                 }
                 (Some(sid), None)
             }
             (None, Some(cid)) => {
                 // Verify container exists
-                let container_exists = sqlx::query("SELECT id FROM containers WHERE id = $1")
-                    .bind(cid)
-                    .fetch_optional(&state.db)
-                    .await
-                    .map_err(|e| {
-                        tracing::error!("Failed to verify container: {:?}", e);
-                        StatusCode::INTERNAL_SERVER_ERROR
-                    })?
-                    .is_some();
+                let container_exists =
+                    sqlx::query("SELECT id FROM containers WHERE id = $1 AND household_id = $2")
+                        .bind(cid)
+                        .bind(household_id)
+                        .fetch_optional(&state.db)
+                        .await?
+                        .is_some();
 
                 if !container_exists {
-                    return Err(StatusCode::BAD_REQUEST);
+                    return Err(ApiError::bad_request(format!(
+                        "Container with id {} does not exist",
+                        cid
+                    )));
                 }
                 (None, Some(cid))
             }
-            (Some(_), Some(_)) => return Err(StatusCode::BAD_REQUEST),
-            (None, None) => return Err(StatusCode::BAD_REQUEST),
+            (Some(_), Some(_)) => return Err(ApiError::bad_request(ITEM_LOCATION_REQUIRED)),
+            (None, None) => return Err(ApiError::bad_request(ITEM_LOCATION_REQUIRED)),
         }
     } else {
         // No location change
         (existing.shelf_id, existing.container_id)
     };
 
-    // Track changes for audit before consuming payload
-    let mut changes = serde_json::Map::new();
-    if let Some(ref new_name) = payload.name {
-        if new_name != &existing.name {
-            changes.insert(
-                "name".to_string(),
-                serde_json::json!({
-                    "from": &existing.name,
-                    "to": new_name
-                }),
-            );
-        }
+    // Resolve new values: omitted fields keep the existing value, explicit null clears
+    let name = payload.name.unwrap_or(existing.name.clone());
+    let description = payload.description.apply(existing.description.clone());
+    let barcode = payload.barcode.apply(existing.barcode.clone());
+    let barcode_type = payload.barcode_type.apply(existing.barcode_type.clone());
+    let product_manual_s3_key = payload
+        .product_manual_s3_key
+        .apply(existing.product_manual_s3_key.clone());
+    let receipt_s3_key = payload
+        .receipt_s3_key
+        .apply(existing.receipt_s3_key.clone());
+    let product_link = payload.product_link.apply(existing.product_link.clone());
+    let belongs_to_user_id = payload
+        .belongs_to_user_id
+        .apply(existing.belongs_to_user_id);
+    if belongs_to_user_id != existing.belongs_to_user_id && !existing.can_change_owner(user_id) {
+        tracing::warn!("User {} may not change the owner of item {}", user_id, id);
+        return Err(ApiError::Forbidden(ITEM_PERMISSION_DENIED.to_string()));
     }
-    if payload.description.is_some()
-        && payload.description.as_ref() != existing.description.as_ref()
-    {
-        changes.insert(
-            "description".to_string(),
+    let acquired_date = payload.acquired_date.apply(existing.acquired_date);
+    let quantity = payload.quantity.unwrap_or(existing.quantity);
+    let min_quantity = payload.min_quantity.apply(existing.min_quantity);
+
+    if !quantities_valid(Some(quantity), min_quantity) {
+        return Err(ApiError::bad_request(INVALID_QUANTITY));
+    }
+    let (purchase_price, currency) = resolve_purchase_price(
+        payload.purchase_price.apply(existing.purchase_price),
+        payload.currency.apply(existing.currency.clone()),
+    )?;
+    let condition = match payload.condition {
+        Clearable::Keep => existing.condition,
+        Clearable::Clear => None,
+        Clearable::Set(value) => parse_condition(Some(&value))?,
+    };
+    let warranty_expires_on = match payload.warranty_expires_on {
+        Clearable::Set(date) => {
+            if !warranty_expiry_valid(Some(date), Utc::now().date_naive()) {
+                return Err(ApiError::UnprocessableEntity(
+                    "warranty_expires_on must not be in the past".to_string(),
+                ));
+            }
+            Some(date)
+        }
+        other => other.apply(existing.warranty_expires_on),
+    };
+    let reminder_enabled = payload
+        .reminder_enabled
+        .unwrap_or(existing.reminder_enabled);
+
+    if let Some(ref new_barcode) = barcode {
+        if barcode != existing.barcode {
+            if let Some(owner) = barcode_owner(&state.db, new_barcode, Some(id)).await? {
+                return Err(owner.conflict(household_id));
+            }
+        }
+    }
+
+    // Track changes for audit
+    let mut changes = serde_json::Map::new();
+    if name != existing.name {
+        changes.insert(
+            "name".to_string(),
+            serde_json::json!({
+                "from": &existing.name,
+                "to": &name
+            }),
+        );
+    }
+    if description != existing.description {
+        changes.insert(
+            "description".to_string(),
             serde_json::json!({
                 "from": &existing.description,
-                "to": payload.description.as_ref()
+                "to": &description
             }),
         );
     }
-    if payload.barcode.is_some() && payload.barcode != existing.barcode {
+    if barcode != existing.barcode {
         changes.insert(
             "barcode".to_string(),
             serde_json::json!({
                 "from": &existing.barcode,
-                "to": &payload.barcode
+                "to": &barcode
             }),
         );
     }
-    if payload.barcode_type.is_some() && payload.barcode_type != existing.barcode_type {
+    if barcode_type != existing.barcode_type {
         changes.insert(
             "barcode_type".to_string(),
             serde_json::json!({
                 "from": &existing.barcode_type,
-                "to": &payload.barcode_type
+                "to": &barcode_type
             }),
         );
     }
-    if payload.product_manual_s3_key.is_some()
-        && payload.product_manual_s3_key != existing.product_manual_s3_key
-    {
+    if product_manual_s3_key != existing.product_manual_s3_key {
         changes.insert(
             "product_manual_s3_key".to_string(),
             serde_json::json!({
                 "from": &existing.product_manual_s3_key,
-                "to": &payload.product_manual_s3_key
+                "to": &product_manual_s3_key
             }),
         );
     }
-    if payload.receipt_s3_key.is_some() && payload.receipt_s3_key != existing.receipt_s3_key {
+    if receipt_s3_key != existing.receipt_s3_key {
         changes.insert(
             "receipt_s3_key".to_string(),
             serde_json::json!({
                 "from": &existing.receipt_s3_key,
-                "to": &payload.receipt_s3_key
+                "to": &receipt_s3_key
             }),
         );
     }
-    if payload.product_link.is_some() && payload.product_link != existing.product_link {
+    if product_link != existing.product_link {
         changes.insert(
             "product_link".to_string(),
             serde_json::json!({
                 "from": &existing.product_link,
-                "to": &payload.product_link
+                "to": &product_link
             }),
         );
     }
-    if payload.belongs_to_user_id.is_some()
-        && payload.belongs_to_user_id != existing.belongs_to_user_id
-    {
+    if belongs_to_user_id != existing.belongs_to_user_id {
         changes.insert(
             "belongs_to_user_id".to_string(),
             serde_json::json!({
                 "from": &existing.belongs_to_user_id,
-                "to": &payload.belongs_to_user_id
+                "to": &belongs_to_user_id
             }),
         );
     }
-    if payload.acquired_date.is_some() && payload.acquired_date != existing.acquired_date {
+    if acquired_date != existing.acquired_date {
         changes.insert(
             "acquired_date".to_string(),
             serde_json::json!({
                 "from": &existing.acquired_date,
-                "to": &payload.acquired_date
+                "to": &acquired_date
+            }),
+        );
+    }
+
+    if quantity != existing.quantity {
+        changes.insert(
+            "quantity".to_string(),
+            serde_json::json!({
+                "from": existing.quantity,
+                "to": quantity
+            }),
+        );
+    }
+    if min_quantity != existing.min_quantity {
+        changes.insert(
+            "min_quantity".to_string(),
+            serde_json::json!({
+                "from": existing.min_quantity,
+                "to": min_quantity
+            }),
+        );
+    }
+    if purchase_price != existing.purchase_price || currency != existing.currency {
+        changes.insert(
+            "purchase_price".to_string(),
+            serde_json::json!({
+                "from": { "amount": existing.purchase_price, "currency": existing.currency },
+                "to": { "amount": purchase_price, "currency": currency }
+            }),
+        );
+    }
+
+    if condition != existing.condition {
+        changes.insert(
+            "condition".to_string(),
+            serde_json::json!({
+                "from": existing.condition,
+                "to": condition
+            }),
+        );
+    }
+
+    if warranty_expires_on != existing.warranty_expires_on {
+        changes.insert(
+            "warranty_expires_on".to_string(),
+            serde_json::json!({
+                "from": existing.warranty_expires_on,
+                "to": warranty_expires_on
+            }),
+        );
+    }
+    if reminder_enabled != existing.reminder_enabled {
+        changes.insert(
+            "reminder_enabled".to_string(),
+            serde_json::json!({
+                "from": existing.reminder_enabled,
+                "to": reminder_enabled
             }),
         );
     }
 
-    // Update fields if provided
-    let name = payload.name.unwrap_or(existing.name.clone());
-    let description = payload.description.or(existing.description.clone());
-    let barcode = payload.barcode.or(existing.barcode.clone());
-    let barcode_type = payload.barcode_type.or(existing.barcode_type.clone());
-    let product_manual_s3_key = payload
-        .product_manual_s3_key
-        .or(existing.product_manual_s3_key.clone());
-    let receipt_s3_key = payload.receipt_s3_key.or(existing.receipt_s3_key.clone());
-    let product_link = payload.product_link.or(existing.product_link.clone());
-    let belongs_to_user_id = payload.belongs_to_user_id.or(existing.belongs_to_user_id);
-    let acquired_date = payload.acquired_date.or(existing.acquired_date);
     if shelf_id != existing.shelf_id || container_id != existing.container_id {
         changes.insert(
             "location".to_string(),
@@ -678,8 +1446,10 @@ pub async fn update_item(
         SET name = $1, description = $2, shelf_id = $3, container_id = $4,
             barcode = $5, barcode_type = $6,
             product_manual_s3_key = $7, receipt_s3_key = $8, product_link = $9,
-            belongs_to_user_id = $10, acquired_date = $11, updated_at = NOW()
-        WHERE id = $12
+            belongs_to_user_id = $10, acquired_date = $11, quantity = $12, min_quantity = $13,
+            purchase_price = $14, currency = $15, condition = $16,
+            warranty_expires_on = $17, reminder_enabled = $18, updated_at = NOW()
+        WHERE id = $19
         RETURNING *
         "#,
     )
@@ -694,13 +1464,17 @@ pub async fn update_item(
     .bind(&product_link)
     .bind(belongs_to_user_id)
     .bind(acquired_date)
+    .bind(quantity)
+    .bind(min_quantity)
+    .bind(purchase_price)
+    .bind(&currency)
+    .bind(condition.as_ref().map(Condition::as_str))
+    .bind(warranty_expires_on)
+    .bind(reminder_enabled)
     .bind(id)
     .fetch_one(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to update item: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .map_err(barcode_conflict)?;
 
     // Log audit
     if !changes.is_empty() {
@@ -720,41 +1494,658 @@ pub async fn update_item(
     Ok(Json(ItemResponse::from(item)))
 }
 
-/// Delete an item
+/// Move an item to the trash. It can be restored until it is purged. Only the item's
+/// creator may delete it.
+#[utoipa::path(
+    delete,
+    path = "/api/items/{id}",
+    tag = "items",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    responses(
+        (status = 200, description = "OK"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    )
+)]
 pub async fn delete_item(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    // Log audit before deletion
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Item, id)
+        .await?;
+    let existing =
+        sqlx::query_as::<_, Item>("SELECT * FROM items WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Item", id))?;
+
+    if !existing.can_delete(user_id) {
+        tracing::warn!("User {} may not delete item {}", user_id, id);
+        return Err(ApiError::Forbidden(ITEM_PERMISSION_DENIED.to_string()));
+    }
+
+    let result =
+        sqlx::query("UPDATE items SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .execute(&state.db)
+            .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::not_found("Item", id));
+    }
+
     state
         .audit
-        .log_delete("item", id, Some(user_id), None)
+        .log_soft_delete("item", id, Some(user_id), None)
         .await
         .ok();
 
-    let result = sqlx::query("DELETE FROM items WHERE id = $1")
-        .bind(id)
-        .execute(&state.db)
+    Ok(Json(json!({ "message": "Item moved to trash" })))
+}
+
+/// Permanently delete many items at once. Unlike bulk create this is best-effort:
+/// IDs that don't exist or that the user may not delete are reported back rather
+/// than failing the request.
+#[utoipa::path(
+    post,
+    path = "/api/items/bulk-delete",
+    tag = "items",
+    request_body = BulkDeleteItemsRequest,
+    responses(
+        (status = 207, description = "Per-item results", body = BulkDeleteItemsResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn bulk_delete_items(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Json(payload): Json<BulkDeleteItemsRequest>,
+) -> Result<(StatusCode, Json<BulkDeleteItemsResponse>), ApiError> {
+    if payload.ids.is_empty() || payload.ids.len() > MAX_BULK_DELETE_ITEMS {
+        return Err(ApiError::bad_request(format!(
+            "Between 1 and {} ids are required",
+            MAX_BULK_DELETE_ITEMS
+        )));
+    }
+
+    // Items of other households are reported as not found, like missing ones
+    let household_id = state.resolve_household(user_id).await?;
+    let found: Vec<(Uuid, Uuid)> =
+        sqlx::query_as("SELECT id, created_by FROM items WHERE id = ANY($1) AND household_id = $2")
+            .bind(&payload.ids)
+            .bind(household_id)
+            .fetch_all(&state.db)
+            .await?;
+
+    // Same rule as a single delete: only an item's creator may remove it
+    let (allowed, denied): (Vec<_>, Vec<_>) = found
+        .into_iter()
+        .partition(|(_, created_by)| *created_by == user_id);
+    let ids: Vec<Uuid> = allowed.into_iter().map(|(id, _)| id).collect();
+    let forbidden: Vec<Uuid> = denied.into_iter().map(|(id, _)| id).collect();
+
+    // Remove the items' photos from S3 first so a failure leaves nothing orphaned
+    let photos = sqlx::query_as::<_, Photo>(
+        "SELECT * FROM photos WHERE entity_type = 'item' AND entity_id = ANY($1)",
+    )
+    .bind(&ids)
+    .fetch_all(&state.db)
+    .await?;
+
+    let s3_keys = photos
+        .iter()
+        .flat_map(|photo| std::iter::once(&photo.s3_key).chain(&photo.thumbnail_s3_key));
+    try_join_all(s3_keys.map(|key| state.s3.delete_file(key)))
         .await
         .map_err(|e| {
-            tracing::error!("Failed to delete item: {:?}", e);
+            tracing::error!("Failed to delete item photos from S3: {:?}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
+    let deleted: Vec<Uuid> =
+        sqlx::query_scalar("DELETE FROM items WHERE id = ANY($1) RETURNING id")
+            .bind(&ids)
+            .fetch_all(&state.db)
+            .await?;
+
+    if !photos.is_empty() {
+        sqlx::query("DELETE FROM photos WHERE entity_type = 'item' AND entity_id = ANY($1)")
+            .bind(&deleted)
+            .execute(&state.db)
+            .await?;
+    }
+
+    // No foreign keys cascade on DSQL, so clear tags and labels like a location
+    // cascade does; the labels can be stuck on something else
+    sqlx::query("DELETE FROM entity_tags WHERE entity_type = 'item' AND entity_id = ANY($1)")
+        .bind(&deleted)
+        .execute(&state.db)
+        .await?;
+    sqlx::query(
+        r#"
+        UPDATE labels
+        SET assigned_to_type = NULL, assigned_to_id = NULL, assigned_at = NULL, updated_at = NOW()
+        WHERE assigned_to_type = 'item' AND assigned_to_id = ANY($1)
+        "#,
+    )
+    .bind(&deleted)
+    .execute(&state.db)
+    .await?;
+
+    for id in &deleted {
+        state
+            .audit
+            .log_delete("item", *id, Some(user_id), Some(json!({ "bulk": true })))
+            .await
+            .ok();
+    }
+
+    let response = BulkDeleteItemsResponse::from_requested(&payload.ids, &deleted, &forbidden);
+    tracing::info!(
+        "Bulk delete: {} items deleted, {} not found, {} forbidden",
+        response.deleted.len(),
+        response.not_found.len(),
+        response.forbidden.len()
+    );
+
+    Ok((StatusCode::MULTI_STATUS, Json(response)))
+}
+
+/// Get the household's items in the trash, most recently deleted first
+#[utoipa::path(
+    get,
+    path = "/api/items/trash",
+    tag = "items",
+    params(
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ItemResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn list_trashed_items(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<ItemResponse>>, ApiError> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 1000);
+    let offset = params.offset.unwrap_or(0).max(0);
+    let household_id = state.resolve_household(user_id).await?;
+
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM items WHERE deleted_at IS NOT NULL AND household_id = $1",
+    )
+    .bind(household_id)
+    .fetch_one(&state.db)
+    .await?;
+    let total = total.clamp(0, i32::MAX as i64) as i32;
+
+    let items = sqlx::query_as::<_, Item>(
+        "SELECT * FROM items WHERE deleted_at IS NOT NULL AND household_id = $1 ORDER BY deleted_at DESC LIMIT $2 OFFSET $3",
+    )
+    .bind(household_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    let responses: Vec<ItemResponse> = items.into_iter().map(ItemResponse::from).collect();
+    Ok(Json(PaginatedResponse::new(
+        responses, total, limit, offset,
+    )))
+}
+
+/// Restore an item from the trash
+#[utoipa::path(
+    post,
+    path = "/api/items/{id}/restore",
+    tag = "items",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = ItemResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    )
+)]
+pub async fn restore_item(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ItemResponse>, ApiError> {
+    let household_id = state
+        .authorize_entity(user_id, HouseholdEntity::Item, id)
+        .await?;
+    let trashed =
+        sqlx::query_as::<_, Item>("SELECT * FROM items WHERE id = $1 AND deleted_at IS NOT NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Item", id))?;
+
+    // Another item may have taken the barcode while this one was in the trash
+    if let Some(barcode) = trashed.barcode.as_deref() {
+        if let Some(owner) = barcode_owner(&state.db, barcode, Some(id)).await? {
+            return Err(owner.conflict(household_id));
+        }
+    }
+
+    let item = sqlx::query_as::<_, Item>(
+        "UPDATE items SET deleted_at = NULL, updated_at = NOW() WHERE id = $1 AND deleted_at IS NOT NULL RETURNING *",
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(barcode_conflict)?
+    .ok_or_else(|| ApiError::not_found("Item", id))?;
+
+    state
+        .audit
+        .log_restore(
+            "item",
+            id,
+            Some(user_id),
+            Some(json!({ "deleted_at": trashed.deleted_at })),
+        )
+        .await
+        .ok();
+
+    Ok(Json(ItemResponse::from(item)))
+}
+
+/// Permanently delete an item, whether or not it is in the trash
+#[utoipa::path(
+    delete,
+    path = "/api/items/{id}/purge",
+    tag = "items",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    responses(
+        (status = 204, description = "No Content"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    )
+)]
+pub async fn purge_item(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Item, id)
+        .await?;
+    let existing = sqlx::query_as::<_, Item>("SELECT * FROM items WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Item", id))?;
+
+    if !existing.can_delete(user_id) {
+        tracing::warn!("User {} may not purge item {}", user_id, id);
+        return Err(ApiError::Forbidden(ITEM_PERMISSION_DENIED.to_string()));
+    }
+
+    let result = sqlx::query("DELETE FROM items WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
     if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found("Item", id));
+    }
+
+    state
+        .audit
+        .log_delete("item", id, Some(user_id), None)
+        .await
+        .ok();
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Applies a quantity delta only if the result stays non-negative, so concurrent
+/// adjustments can't race past zero. A NULL quantity counts as 1.
+const ADJUST_ITEM_QUANTITY_SQL: &str = r#"
+    UPDATE items
+    SET quantity = COALESCE(quantity, 1) + $1, updated_at = NOW()
+    WHERE id = $2 AND deleted_at IS NULL AND COALESCE(quantity, 1) + $1 >= 0
+    RETURNING *
+"#;
+
+/// Increment or decrement an item's quantity
+#[utoipa::path(
+    patch,
+    path = "/api/items/{id}/quantity",
+    tag = "items",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    request_body = AdjustQuantityRequest,
+    responses(
+        (status = 200, description = "OK", body = ItemResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    )
+)]
+pub async fn adjust_item_quantity(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AdjustQuantityRequest>,
+) -> Result<Json<ItemResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Item, id)
+        .await?;
+    if payload.delta == 0 {
+        return Err(ApiError::bad_request("delta must not be zero"));
+    }
+
+    let item = sqlx::query_as::<_, Item>(ADJUST_ITEM_QUANTITY_SQL)
+        .bind(payload.delta)
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?;
+
+    let Some(item) = item else {
+        // Either the item doesn't exist or the delta would take it below zero
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM items WHERE id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+        return Err(if exists {
+            ApiError::bad_request("Quantity cannot go below zero")
+        } else {
+            ApiError::not_found("Item", id)
+        });
+    };
+
+    let low_stock = item.is_low_stock();
+    if low_stock {
+        tracing::info!(
+            "Item {} is low on stock: {} left (minimum {:?})",
+            id,
+            item.quantity,
+            item.min_quantity
+        );
+    }
+
+    state
+        .audit
+        .log_quantity_change(
+            "item",
+            id,
+            Some(user_id),
+            item.quantity - payload.delta,
+            item.quantity,
+            low_stock,
+        )
+        .await
+        .ok();
+
+    Ok(Json(ItemResponse::from(item)))
+}
+
+/// Get the household's items at or below their minimum quantity
+#[utoipa::path(
+    get,
+    path = "/api/items/low-stock",
+    tag = "items",
+    responses(
+        (status = 200, description = "OK", body = Vec<ItemResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn list_low_stock_items(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+) -> Result<Json<Vec<ItemResponse>>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+
+    let items = sqlx::query_as::<_, Item>(
+        "SELECT * FROM items WHERE deleted_at IS NULL AND household_id = $1 AND min_quantity IS NOT NULL AND COALESCE(quantity, 1) <= min_quantity ORDER BY name",
+    )
+    .bind(household_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(items.into_iter().map(ItemResponse::from).collect()))
+}
+
+/// Get the household's items with reminders on whose warranty runs out within
+/// `within_days` days (default 30), soonest first. Meant to be polled by a scheduled job.
+#[utoipa::path(
+    get,
+    path = "/api/items/expiring",
+    tag = "items",
+    params(
+        ExpiringItemsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<ItemResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn list_expiring_items(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<ExpiringItemsQuery>,
+) -> Result<Json<Vec<ItemResponse>>, ApiError> {
+    let within_days = params.within_days.unwrap_or(DEFAULT_EXPIRING_WITHIN_DAYS);
+    if !(0..=MAX_EXPIRING_WITHIN_DAYS).contains(&within_days) {
+        return Err(ApiError::bad_request(format!(
+            "within_days must be between 0 and {}",
+            MAX_EXPIRING_WITHIN_DAYS
+        )));
+    }
+    let household_id = state.resolve_household(user_id).await?;
+
+    let items = sqlx::query_as::<_, Item>(
+        r#"
+        SELECT * FROM items
+        WHERE deleted_at IS NULL AND household_id = $1 AND reminder_enabled = true
+          AND warranty_expires_on BETWEEN CURRENT_DATE AND CURRENT_DATE + $2::INTEGER
+        ORDER BY warranty_expires_on, name
+        "#,
+    )
+    .bind(household_id)
+    .bind(within_days)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(items.into_iter().map(ItemResponse::from).collect()))
+}
+
+/// Transfer an item to another member of its household
+#[utoipa::path(
+    post,
+    path = "/api/items/{id}/transfer",
+    tag = "items",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    request_body = TransferItemRequest,
+    responses(
+        (status = 200, description = "OK", body = ItemResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    )
+)]
+pub async fn transfer_item(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<TransferItemRequest>,
+) -> Result<Json<ItemResponse>, ApiError> {
+    let household_id = state
+        .authorize_entity(user_id, HouseholdEntity::Item, id)
+        .await?;
+    let existing =
+        sqlx::query_as::<_, Item>("SELECT * FROM items WHERE id = $1 AND deleted_at IS NULL")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Item", id))?;
+
+    if !existing.can_transfer(user_id) {
+        tracing::warn!("User {} may not transfer item {}", user_id, id);
+        return Err(ApiError::Forbidden(
+            "Only the item's owner, or its creator if unowned, can transfer it".to_string(),
+        ));
+    }
+
+    // An owner outside the household couldn't see the item
+    let owner_role =
+        household_service::membership_role(&state.db, household_id, payload.new_owner_id).await?;
+    if owner_role.is_none() {
+        tracing::warn!(
+            "Transfer target user {} is not in household {}",
+            payload.new_owner_id,
+            household_id
+        );
+        return Err(ApiError::bad_request(format!(
+            "User with id {} is not a member of this household",
+            payload.new_owner_id
+        )));
+    }
+
+    let item = sqlx::query_as::<_, Item>(
+        "UPDATE items SET belongs_to_user_id = $1, updated_at = NOW() WHERE id = $2 RETURNING *",
+    )
+    .bind(payload.new_owner_id)
+    .bind(id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if existing.belongs_to_user_id != item.belongs_to_user_id {
+        state
+            .audit
+            .log_update(
+                "item",
+                id,
+                Some(user_id),
+                json!({
+                    "belongs_to_user_id": {
+                        "from": existing.belongs_to_user_id,
+                        "to": item.belongs_to_user_id,
+                    }
+                }),
+                None,
+            )
+            .await
+            .ok();
     }
 
-    Ok(Json(json!({ "message": "Item deleted successfully" })))
+    Ok(Json(ItemResponse::from(item)))
+}
+
+/// Export the inventory as CSV
+///
+/// Rows are streamed from the database as they're read, so the whole table is never held
+/// in memory. Supports the same `search` filter as `list_items`, plus an exact `tag` filter.
+#[utoipa::path(
+    get,
+    path = "/api/items/export",
+    tag = "items",
+    params(
+        ExportItemsQuery
+    ),
+    responses(
+        (status = 200, description = "CSV file (`text/csv`)"),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn export_items_csv(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<ExportItemsQuery>,
+) -> Result<Response, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    let search_pattern = params
+        .search
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| format!("%{}%", s));
+    let tag = params
+        .tag
+        .as_deref()
+        .map(normalize_tag_name)
+        .filter(|t| !t.is_empty());
+
+    let (writer, encoder) = CsvEncoder::channel(EXPORT_BUFFER_ROWS);
+    let db = state.db.clone();
+
+    tokio::spawn(async move {
+        if !writer.write_record(&EXPORT_CSV_HEADER).await {
+            return;
+        }
+
+        let mut rows = sqlx::query_as::<_, ItemExportRow>(EXPORT_ITEMS_SQL)
+            .bind(search_pattern)
+            .bind(tag)
+            .bind(household_id)
+            .fetch(&db);
+
+        loop {
+            match rows.try_next().await {
+                Ok(Some(row)) => {
+                    if !writer.write_record(&row.into_record()).await {
+                        tracing::debug!("Client disconnected during item export");
+                        return;
+                    }
+                }
+                Ok(None) => return,
+                Err(e) => {
+                    // Headers are already sent; abort the body so the file isn't
+                    // saved as if it were complete
+                    tracing::error!("Failed to export items: {:?}", e);
+                    writer.abort(std::io::Error::other(e)).await;
+                    return;
+                }
+            }
+        }
+    });
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/csv; charset=utf-8")
+        .header(
+            header::CONTENT_DISPOSITION,
+            "attachment; filename=\"inventory.csv\"",
+        )
+        .body(Body::from_stream(encoder))
+        .unwrap())
 }
 
 /// Get presigned URL for file download
+#[utoipa::path(
+    post,
+    path = "/api/items/file-download-url",
+    tag = "items",
+    request_body = FileDownloadRequest,
+    responses(
+        (status = 200, description = "OK", body = FileDownloadResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn get_file_download_url(
     State(state): State<Arc<AppState>>,
     AuthUser(_user_id): AuthUser,
     Json(payload): Json<FileDownloadRequest>,
-) -> Result<Json<FileDownloadResponse>, StatusCode> {
+) -> Result<Json<FileDownloadResponse>, ApiError> {
     // Get presigned download URL
     let download_url = state
         .s3
@@ -769,15 +2160,27 @@ pub async fn get_file_download_url(
 }
 
 /// Get presigned URL for file upload (manual or receipt)
+#[utoipa::path(
+    post,
+    path = "/api/items/file-upload-url",
+    tag = "items",
+    request_body = FileUploadRequest,
+    responses(
+        (status = 200, description = "OK", body = FileUploadResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 413, description = "File over `MAX_MULTIPART_UPLOAD_BYTES`", body = ErrorResponse)
+    )
+)]
 pub async fn get_file_upload_url(
     State(state): State<Arc<AppState>>,
     AuthUser(_user_id): AuthUser,
     Json(payload): Json<FileUploadRequest>,
-) -> Result<Json<FileUploadResponse>, StatusCode> {
+) -> Result<Json<FileUploadResponse>, ApiError> {
     // Validate file type
     if payload.file_type != "manual" && payload.file_type != "receipt" {
         tracing::warn!("Invalid file type: {}", payload.file_type);
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request("file_type must be manual or receipt"));
     }
 
     // Generate S3 key
@@ -786,7 +2189,10 @@ pub async fn get_file_upload_url(
         ct if ct.starts_with("image/") => ct.strip_prefix("image/").unwrap_or("jpg"),
         _ => {
             tracing::warn!("Unsupported content type: {}", payload.content_type);
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::bad_request(format!(
+                "Unsupported content type: {}",
+                payload.content_type
+            )));
         }
     };
 
@@ -797,35 +2203,236 @@ pub async fn get_file_upload_url(
         file_extension
     );
 
-    // Get presigned upload URL
-    let upload_url = state
+    let multipart = match payload.file_size_bytes {
+        Some(size) if size > state.s3.max_multipart_upload_bytes() => {
+            return Err(ApiError::PayloadTooLarge(format!(
+                "file_size_bytes must be at most {}",
+                state.s3.max_multipart_upload_bytes()
+            )));
+        }
+        Some(size) if state.s3.needs_multipart(size) => {
+            let (part_size, part_count) = multipart_plan(size);
+            let upload = state
+                .s3
+                .generate_multipart_upload_urls(&s3_key, &payload.content_type, part_count as i32)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to start multipart upload: {:?}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+            Some(MultipartUploadResponse {
+                upload_id: upload.upload_id,
+                part_size,
+                part_urls: upload.part_urls,
+            })
+        }
+        _ => None,
+    };
+
+    // Presigned POST, so S3 enforces the size limit
+    let max_bytes = state.s3.max_upload_bytes();
+    let post = state
         .s3
-        .generate_presigned_upload_url_for_key(&s3_key, &payload.content_type)
+        .generate_presigned_upload_url_for_key(&s3_key, &payload.content_type, max_bytes)
         .await
         .map_err(|e| {
             tracing::error!("Failed to generate presigned upload URL: {:?}", e);
             StatusCode::INTERNAL_SERVER_ERROR
         })?;
 
-    Ok(Json(FileUploadResponse { upload_url, s3_key }))
+    Ok(Json(FileUploadResponse {
+        upload_url: post.url,
+        fields: post.fields,
+        s3_key,
+        content_type: payload.content_type,
+        max_bytes,
+        expires_in: UPLOAD_URL_EXPIRES_IN_SECS,
+        multipart,
+    }))
+}
+
+/// Multipart uploads can only target the keys handed out for item files
+fn ensure_item_file_key(s3_key: &str) -> Result<(), ApiError> {
+    if s3_key.starts_with("items/manual/") || s3_key.starts_with("items/receipt/") {
+        return Ok(());
+    }
+    tracing::warn!("Rejected multipart request for key {}", s3_key);
+    Err(ApiError::bad_request("s3_key is not an item file"))
+}
+
+/// Finish a multipart upload started by `POST /api/items/file-upload-url`.
+///
+/// Part URLs can't limit their size the way presigned POST does, so the assembled
+/// file is checked instead: one over `MAX_MULTIPART_UPLOAD_BYTES` is deleted and the
+/// request fails with 413.
+#[utoipa::path(
+    post,
+    path = "/api/items/file-complete-multipart",
+    tag = "items",
+    request_body = CompleteMultipartUploadRequest,
+    responses(
+        (status = 200, description = "OK"),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 413, description = "File over `MAX_MULTIPART_UPLOAD_BYTES`", body = ErrorResponse)
+    )
+)]
+pub async fn complete_multipart_file_upload(
+    State(state): State<Arc<AppState>>,
+    AuthUser(_user_id): AuthUser,
+    Json(payload): Json<CompleteMultipartUploadRequest>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    ensure_item_file_key(&payload.s3_key)?;
+
+    let max_part = MAX_MULTIPART_PARTS as i32;
+    if payload.parts.is_empty()
+        || payload
+            .parts
+            .iter()
+            .any(|part| !(1..=max_part).contains(&part.part_number) || part.etag.is_empty())
+    {
+        return Err(ApiError::bad_request(format!(
+            "parts must list part numbers between 1 and {} with their ETags",
+            max_part
+        )));
+    }
+
+    // S3 wants the parts in ascending order
+    let mut parts: Vec<(i32, String)> = payload
+        .parts
+        .into_iter()
+        .map(|part| (part.part_number, part.etag))
+        .collect();
+    parts.sort_by_key(|(part_number, _)| *part_number);
+    parts.dedup_by_key(|(part_number, _)| *part_number);
+
+    state
+        .s3
+        .complete_multipart_upload(&payload.s3_key, &payload.upload_id, &parts)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to complete multipart upload: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let size = state.s3.object_size(&payload.s3_key).await.map_err(|e| {
+        tracing::error!("Failed to check multipart upload size: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let max_bytes = state.s3.max_multipart_upload_bytes();
+    if size > max_bytes {
+        tracing::warn!(
+            "Deleting multipart upload {} of {} bytes, over the {} byte limit",
+            payload.s3_key,
+            size,
+            max_bytes
+        );
+        state.s3.delete_file(&payload.s3_key).await.map_err(|e| {
+            tracing::error!("Failed to delete oversized upload: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        return Err(ApiError::PayloadTooLarge(format!(
+            "File must be at most {} bytes",
+            max_bytes
+        )));
+    }
+
+    Ok(Json(serde_json::json!({ "s3_key": payload.s3_key })))
+}
+
+/// Abandon a multipart upload, freeing the parts already uploaded
+#[utoipa::path(
+    post,
+    path = "/api/items/file-abort-multipart",
+    tag = "items",
+    request_body = AbortMultipartUploadRequest,
+    responses(
+        (status = 204, description = "No Content"),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn abort_multipart_file_upload(
+    State(state): State<Arc<AppState>>,
+    AuthUser(_user_id): AuthUser,
+    Json(payload): Json<AbortMultipartUploadRequest>,
+) -> Result<StatusCode, ApiError> {
+    ensure_item_file_key(&payload.s3_key)?;
+
+    state
+        .s3
+        .abort_multipart_upload(&payload.s3_key, &payload.upload_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to abort multipart upload: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get photos for an item
+#[utoipa::path(
+    get,
+    path = "/api/items/{id}/photos",
+    tag = "items",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<PhotoResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    )
+)]
+pub async fn list_item_photos(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<PhotoResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Item, id)
+        .await?;
+    let photos = fetch_entity_photos(&state, "item", id).await?;
+    Ok(Json(photos))
 }
 
 /// Create item routes
 pub fn item_routes() -> Router<Arc<AppState>> {
-    use axum::routing::{get, post};
+    use axum::routing::{delete, get, patch, post};
 
     Router::new()
-        .route("/api/items", get(list_items).post(create_item))
+        // POST /api/items and /api/items/bulk are added in app.rs behind IdempotencyLayer
+        .route("/api/items", get(list_items))
         // Specific routes MUST come before parameterized routes
-        .route("/api/items/bulk", post(bulk_create_items))
+        .route("/api/items/bulk-delete", post(bulk_delete_items))
         .route("/api/items/file-upload-url", post(get_file_upload_url))
+        .route(
+            "/api/items/file-complete-multipart",
+            post(complete_multipart_file_upload),
+        )
+        .route(
+            "/api/items/file-abort-multipart",
+            post(abort_multipart_file_upload),
+        )
         .route("/api/items/file-download-url", post(get_file_download_url))
         .route("/api/items/barcode/:barcode", get(get_item_by_barcode))
+        .route("/api/items/export", get(export_items_csv))
+        .route("/api/items/low-stock", get(list_low_stock_items))
+        .route("/api/items/expiring", get(list_expiring_items))
+        .route("/api/items/trash", get(list_trashed_items))
         // Parameterized route comes last
         .route(
             "/api/items/:id",
-            get(get_item).put(update_item).delete(delete_item),
+            get(get_item)
+                .merge(patch_with_put_alias(update_item))
+                .delete(delete_item),
         )
+        .route("/api/items/:id/photos", get(list_item_photos))
+        .route("/api/items/:id/transfer", post(transfer_item))
+        .route("/api/items/:id/restore", post(restore_item))
+        .route("/api/items/:id/purge", delete(purge_item))
+        .route("/api/items/:id/quantity", patch(adjust_item_quantity))
         .route("/api/shelves/:shelf_id/items", get(list_items_by_shelf))
         .route(
             "/api/containers/:container_id/items",
@@ -835,10 +2442,34 @@ pub fn item_routes() -> Router<Arc<AppState>> {
 
 /// Get public item view (no authentication required)
 /// Returns limited information about an item for public viewing
+#[utoipa::path(
+    get,
+    path = "/api/items/{id}/public",
+    tag = "items",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = PublicItemResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    ),
+    security(())
+)]
 pub async fn get_item_public(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-) -> Result<Json<PublicItemResponse>, StatusCode> {
+) -> Result<Json<PublicItemResponse>, ApiError> {
+    public_item(&state.db, id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Item", id))
+}
+
+/// The public view of live item `id`, shown to anyone with its link or label
+pub(crate) async fn public_item(
+    db: &PgPool,
+    id: Uuid,
+) -> Result<Option<PublicItemResponse>, ApiError> {
     // Join items with users to get owner display name
     #[allow(clippy::type_complexity)]
     let result: Option<(Uuid, String, Option<String>, Option<String>, String)> = sqlx::query_as(
@@ -846,27 +2477,736 @@ pub async fn get_item_public(
         SELECT i.id, i.name, i.product_link, u.public_display_name, u.name as user_name
         FROM items i
         INNER JOIN users u ON i.created_by = u.id
-        WHERE i.id = $1
+        WHERE i.id = $1 AND i.deleted_at IS NULL
         "#,
     )
     .bind(id)
-    .fetch_optional(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch public item: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .fetch_optional(db)
+    .await?;
+
+    Ok(result.map(
+        |(item_id, name, product_link, public_display_name, user_name)| PublicItemResponse {
+            id: item_id,
+            name,
+            owner_display_name: public_display_name.unwrap_or(user_name),
+            product_link,
+        },
+    ))
+}
+
+/// Live items in the same household as `$1` sharing at least one of its tags, most
+/// shared first. `$2` is the household and `$3` the limit.
+const SIMILAR_BY_TAGS_SQL: &str = r#"
+    SELECT i.id, i.name, i.product_link, COUNT(et2.tag_id)::INT AS shared_tag_count
+    FROM entity_tags et1
+    JOIN entity_tags et2
+        ON et2.tag_id = et1.tag_id AND et2.entity_type = 'item' AND et2.entity_id != $1
+    JOIN items i ON i.id = et2.entity_id
+    WHERE et1.entity_type = 'item' AND et1.entity_id = $1
+      AND i.deleted_at IS NULL
+      AND i.household_id IS NOT DISTINCT FROM $2
+    GROUP BY i.id, i.name, i.product_link
+    ORDER BY shared_tag_count DESC, i.name
+    LIMIT $3
+"#;
+
+/// Live items other than `$1` in container `$2`, or on shelf `$3` when there is no
+/// container. `$4` is the limit.
+const SIMILAR_BY_LOCATION_SQL: &str = r#"
+    SELECT id, name, product_link, 0 AS shared_tag_count
+    FROM items
+    WHERE id != $1
+      AND deleted_at IS NULL
+      AND (container_id = $2 OR ($2::UUID IS NULL AND container_id IS NULL AND shelf_id = $3))
+    ORDER BY name
+    LIMIT $4
+"#;
+
+/// Items sharing the most tags with `id`, or stored next to it when it has no tags
+async fn find_items_like(state: &AppState, id: Uuid) -> Result<Vec<SimilarItemResponse>, ApiError> {
+    let (shelf_id, container_id, household_id): (Option<Uuid>, Option<Uuid>, Option<Uuid>) =
+        sqlx::query_as(
+            "SELECT shelf_id, container_id, household_id FROM items WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Item", id))?;
+
+    let tagged: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM entity_tags WHERE entity_type = 'item' AND entity_id = $1)",
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let similar = if tagged {
+        sqlx::query_as(SIMILAR_BY_TAGS_SQL)
+            .bind(id)
+            .bind(household_id)
+            .bind(MAX_SIMILAR_ITEMS)
+            .fetch_all(&state.db)
+            .await?
+    } else {
+        sqlx::query_as(SIMILAR_BY_LOCATION_SQL)
+            .bind(id)
+            .bind(container_id)
+            .bind(shelf_id)
+            .bind(MAX_SIMILAR_ITEMS)
+            .fetch_all(&state.db)
+            .await?
+    };
+
+    Ok(similar)
+}
+
+/// Items like this one (no authentication required): up to 10 sharing the most
+/// tags with it, or in the same container or shelf when it is untagged
+#[utoipa::path(
+    get,
+    path = "/api/items/{id}/similar",
+    tag = "items",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<SimilarItemResponse>),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    ),
+    security(())
+)]
+pub async fn get_similar_items(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<SimilarItemResponse>>, ApiError> {
+    Ok(Json(find_items_like(&state, id).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_pool, test_app_state};
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_get_item_by_barcode_is_case_insensitive() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let item_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+        let barcode = format!("ABC123-{}", &item_id.simple().to_string()[..8]);
+
+        sqlx::query(
+            "INSERT INTO items (id, shelf_id, name, barcode, barcode_type, created_by, household_id) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(item_id)
+        .bind(Uuid::new_v4())
+        .bind("Barcode test item")
+        .bind(&barcode)
+        .bind("CODE128")
+        .bind(Uuid::new_v4())
+        .bind(household_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let found = sqlx::query_as::<_, Item>(GET_ITEM_BY_BARCODE_SQL)
+            .bind(barcode.to_lowercase())
+            .bind(household_id)
+            .fetch_optional(&pool)
+            .await;
+        let in_use = barcode_owner(&pool, &barcode.to_lowercase(), None).await;
+        let in_use_excluding_self = barcode_owner(&pool, &barcode, Some(item_id)).await;
+        let anonymous_check =
+            check_barcode(State(state.clone()), None, Path(barcode.clone())).await;
+        let member_check = check_barcode(
+            State(state.clone()),
+            Some(AuthUser(user_id)),
+            Path(barcode.clone()),
+        )
+        .await;
+        let outsider_check = check_barcode(
+            State(state.clone()),
+            Some(AuthUser(Uuid::new_v4())),
+            Path(barcode.clone()),
+        )
+        .await;
+
+        sqlx::query("DELETE FROM items WHERE id = $1")
+            .bind(item_id)
+            .execute(&pool)
+            .await
+            .unwrap();
 
-    match result {
-        Some((item_id, name, product_link, public_display_name, user_name)) => {
-            let owner_display_name = public_display_name.unwrap_or(user_name);
-            Ok(Json(PublicItemResponse {
+        assert_eq!(found.unwrap().map(|item| item.id), Some(item_id));
+        assert_eq!(
+            in_use,
+            Ok(Some(BarcodeOwner {
                 id: item_id,
-                name,
-                owner_display_name,
-                product_link,
+                household_id: Some(household_id),
             }))
+        );
+        assert_eq!(in_use_excluding_self, Ok(None));
+
+        // Only the item's household learns which item has the barcode
+        let Json(anonymous_check) = anonymous_check.unwrap();
+        assert!(anonymous_check.exists);
+        assert_eq!(anonymous_check.item_id, None);
+        let Json(member_check) = member_check.unwrap();
+        assert_eq!(member_check.item_id, Some(item_id));
+        let Json(outsider_check) = outsider_check.unwrap();
+        assert!(outsider_check.exists);
+        assert_eq!(outsider_check.item_id, None);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_trashed_items_release_their_barcode() {
+        let pool = create_test_pool().await;
+        let item_id = Uuid::new_v4();
+        let household_id = Uuid::new_v4();
+        let barcode = format!("TRASH-{}", &item_id.simple().to_string()[..8]);
+
+        sqlx::query(
+            "INSERT INTO items (id, shelf_id, name, barcode, deleted_at, created_by, household_id) VALUES ($1, $2, $3, $4, NOW(), $5, $6)",
+        )
+        .bind(item_id)
+        .bind(Uuid::new_v4())
+        .bind("Trashed item")
+        .bind(&barcode)
+        .bind(Uuid::new_v4())
+        .bind(household_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let found = sqlx::query_as::<_, Item>(GET_ITEM_BY_BARCODE_SQL)
+            .bind(&barcode)
+            .bind(household_id)
+            .fetch_optional(&pool)
+            .await;
+        let in_use = barcode_owner(&pool, &barcode, None).await;
+
+        sqlx::query("DELETE FROM items WHERE id = $1")
+            .bind(item_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(found.unwrap().is_none());
+        assert_eq!(in_use, Ok(None));
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_find_similar_items_in_same_location() {
+        let pool = create_test_pool().await;
+        let container_id = Uuid::new_v4();
+        let mut item_ids = Vec::new();
+        for (name, location) in [
+            ("Phillips screwdriver", container_id),
+            ("Tape measure", container_id),
+            ("Phillips screwdriver", Uuid::new_v4()),
+        ] {
+            let id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO items (id, container_id, name, created_by) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(id)
+            .bind(location)
+            .bind(name)
+            .bind(Uuid::new_v4())
+            .execute(&pool)
+            .await
+            .unwrap();
+            item_ids.push(id);
+        }
+
+        let postgres = test_app_state(pool.clone());
+        let dsql = AppState {
+            db_dialect: DbDialect::Dsql,
+            ..Arc::into_inner(test_app_state(pool.clone())).unwrap()
+        };
+        let mut results = Vec::new();
+        for state in [&*postgres, &dsql] {
+            results.push(
+                find_similar_items(state, "Phillips screwdriver set", None, Some(container_id))
+                    .await,
+            );
+        }
+
+        sqlx::query("DELETE FROM items WHERE id = ANY($1)")
+            .bind(&item_ids)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        for similar in results {
+            let similar = similar.unwrap();
+            assert_eq!(similar.len(), 1);
+            assert_eq!(similar[0].id, item_ids[0]);
+            assert!(similar[0].similarity > DUPLICATE_SIMILARITY_THRESHOLD);
+        }
+    }
+
+    #[test]
+    fn test_item_date_filters_apply_to_page_and_count() {
+        let dates = PaginationQuery {
+            acquired_after: NaiveDate::from_ymd_opt(2024, 1, 1),
+            created_before: Some(Utc::now()),
+            ..Default::default()
+        };
+        let household_id = Uuid::new_v4();
+
+        let count = items_count_query(household_id, Some("%drill%"), None, &dates);
+        let page = items_page_query(household_id, Some("%drill%"), None, &dates, None, 10, 0);
+
+        for sql in [count.sql(), page.sql()] {
+            assert!(sql.contains("name ILIKE $2"));
+            assert!(sql.contains("AND acquired_date >= $5"));
+            assert!(sql.contains("AND created_at <= $6"));
+            assert!(!sql.contains("acquired_date <="));
+            assert!(!sql.contains("created_at >="));
+        }
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_similar_items_by_shared_tags_then_location() {
+        let pool = create_test_pool().await;
+        let shelf_id = Uuid::new_v4();
+        let item_ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let tag_ids: Vec<Uuid> = (0..2).map(|_| Uuid::new_v4()).collect();
+
+        for (id, name) in item_ids.iter().zip(["Drill", "Drill bits", "Saw", "Glue"]) {
+            sqlx::query(
+                "INSERT INTO items (id, shelf_id, name, created_by) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(id)
+            .bind(shelf_id)
+            .bind(name)
+            .bind(Uuid::new_v4())
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        for tag_id in &tag_ids {
+            sqlx::query("INSERT INTO tags (id, name) VALUES ($1, $2)")
+                .bind(tag_id)
+                .bind(format!("similar-test-{}", tag_id))
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        // The drill shares both tags with the bits and one with the saw; glue is untagged
+        for (item, tag) in [(0, 0), (0, 1), (1, 0), (1, 1), (2, 0)] {
+            sqlx::query(
+                "INSERT INTO entity_tags (entity_type, entity_id, tag_id, updated_at) VALUES ('item', $1, $2, NOW())",
+            )
+            .bind(item_ids[item])
+            .bind(tag_ids[tag])
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let state = test_app_state(pool.clone());
+        let by_tags = find_items_like(&state, item_ids[0]).await;
+        let by_location = find_items_like(&state, item_ids[3]).await;
+
+        sqlx::query("DELETE FROM entity_tags WHERE tag_id = ANY($1)")
+            .bind(&tag_ids)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM tags WHERE id = ANY($1)")
+            .bind(&tag_ids)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM items WHERE id = ANY($1)")
+            .bind(&item_ids)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let by_tags: Vec<(Uuid, i32)> = by_tags
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.id, item.shared_tag_count))
+            .collect();
+        assert_eq!(by_tags, vec![(item_ids[1], 2), (item_ids[2], 1)]);
+
+        let by_location = by_location.unwrap();
+        assert_eq!(by_location.len(), 3);
+        assert!(by_location.iter().all(|item| item.shared_tag_count == 0));
+        assert_eq!(by_location[0].name, "Drill");
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_adjust_quantity_blocks_negative_stock() {
+        let pool = create_test_pool().await;
+        let item_id = Uuid::new_v4();
+
+        sqlx::query(
+            "INSERT INTO items (id, shelf_id, name, quantity, min_quantity, created_by) VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(item_id)
+        .bind(Uuid::new_v4())
+        .bind("Quantity test item")
+        .bind(2)
+        .bind(1)
+        .bind(Uuid::new_v4())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let decremented = sqlx::query_as::<_, Item>(ADJUST_ITEM_QUANTITY_SQL)
+            .bind(-1)
+            .bind(item_id)
+            .fetch_optional(&pool)
+            .await;
+        let overdrawn = sqlx::query_as::<_, Item>(ADJUST_ITEM_QUANTITY_SQL)
+            .bind(-5)
+            .bind(item_id)
+            .fetch_optional(&pool)
+            .await;
+        let direct_update = sqlx::query("UPDATE items SET quantity = -1 WHERE id = $1")
+            .bind(item_id)
+            .execute(&pool)
+            .await;
+
+        sqlx::query("DELETE FROM items WHERE id = $1")
+            .bind(item_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let decremented = decremented.unwrap().unwrap();
+        assert_eq!(decremented.quantity, 1);
+        assert!(decremented.is_low_stock());
+        assert!(overdrawn.unwrap().is_none());
+
+        // The CHECK constraint rejects negative stock even outside the endpoint
+        let err = direct_update.unwrap_err();
+        let db_err = err.as_database_error().expect("Expected a database error");
+        assert_eq!(db_err.constraint(), Some("items_quantity_non_negative"));
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_item_without_backfilled_columns_reads_defaults() {
+        let pool = create_test_pool().await;
+        let item_id = Uuid::new_v4();
+
+        sqlx::query("INSERT INTO items (id, shelf_id, name, created_by) VALUES ($1, $2, $3, $4)")
+            .bind(item_id)
+            .bind(Uuid::new_v4())
+            .bind("Unquantified test item")
+            .bind(Uuid::new_v4())
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let fetched = sqlx::query_as::<_, Item>("SELECT * FROM items WHERE id = $1")
+            .bind(item_id)
+            .fetch_one(&pool)
+            .await;
+        let incremented = sqlx::query_as::<_, Item>(ADJUST_ITEM_QUANTITY_SQL)
+            .bind(2)
+            .bind(item_id)
+            .fetch_optional(&pool)
+            .await;
+
+        sqlx::query("DELETE FROM items WHERE id = $1")
+            .bind(item_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let fetched = fetched.unwrap();
+        assert_eq!(fetched.quantity, 1);
+        assert!(!fetched.reminder_enabled);
+        assert_eq!(incremented.unwrap().unwrap().quantity, 3);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_bulk_create_items_rolls_back_on_invalid_item() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let shelf_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        sqlx::query(
+            "INSERT INTO shelves (id, shelving_unit_id, name, created_by) VALUES ($1, $2, 'Bulk rollback shelf', $3)",
+        )
+        .bind(shelf_id)
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // The first item is fine; the second points at a shelf that doesn't exist
+        let payload: BulkCreateItemsRequest = serde_json::from_value(json!({
+            "items": [
+                { "shelf_id": shelf_id, "name": "Bulk rollback item" },
+                { "shelf_id": Uuid::new_v4(), "name": "Bulk rollback item" },
+            ]
+        }))
+        .unwrap();
+
+        let result = bulk_create_items(
+            State(state),
+            AuthUser(user_id),
+            Query(IncludeTagsQuery::default()),
+            Json(payload),
+        )
+        .await;
+        let persisted: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM items WHERE shelf_id = $1")
+            .bind(shelf_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("DELETE FROM shelves WHERE id = $1")
+            .bind(shelf_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(matches!(result, Err(ApiError::BadRequest(_))));
+        assert_eq!(persisted, 0);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_bulk_delete_items_reports_forbidden_items() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+        let own_item = Uuid::new_v4();
+        let other_item = Uuid::new_v4();
+        let missing_item = Uuid::new_v4();
+
+        for (id, created_by) in [(own_item, user_id), (other_item, Uuid::new_v4())] {
+            sqlx::query(
+                "INSERT INTO items (id, shelf_id, name, created_by, household_id) VALUES ($1, $2, 'Bulk delete item', $3, $4)",
+            )
+            .bind(id)
+            .bind(Uuid::new_v4())
+            .bind(created_by)
+            .bind(household_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        let (tag_id, label_id) = (Uuid::new_v4(), Uuid::new_v4());
+        sqlx::query(
+            "INSERT INTO entity_tags (entity_type, entity_id, tag_id, updated_at) VALUES ('item', $1, $2, NOW())",
+        )
+        .bind(own_item)
+        .bind(tag_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO labels (id, number, qr_data, assigned_to_type, assigned_to_id, updated_at)
+            VALUES ($1, (SELECT COALESCE(MAX(number), 0) + 1 FROM labels), $2, 'item', $3, NOW())
+            "#,
+        )
+        .bind(label_id)
+        .bind(format!("test/l/{}", label_id))
+        .bind(own_item)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let result = bulk_delete_items(
+            State(state),
+            AuthUser(user_id),
+            Json(BulkDeleteItemsRequest {
+                ids: vec![own_item, other_item, missing_item],
+            }),
+        )
+        .await;
+        let remaining: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM items WHERE id = ANY($1)")
+            .bind(vec![own_item, other_item])
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        let tag_left: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM entity_tags WHERE tag_id = $1)")
+                .bind(tag_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let label_assigned_to: Option<Uuid> =
+            sqlx::query_scalar("SELECT assigned_to_id FROM labels WHERE id = $1")
+                .bind(label_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+
+        sqlx::query("DELETE FROM items WHERE id = ANY($1)")
+            .bind(vec![own_item, other_item])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM labels WHERE id = $1")
+            .bind(label_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let (status, Json(response)) = result.unwrap();
+        assert_eq!(status, StatusCode::MULTI_STATUS);
+        assert_eq!(response.deleted, vec![own_item]);
+        assert_eq!(response.forbidden, vec![other_item]);
+        assert_eq!(response.not_found, vec![missing_item]);
+        assert_eq!(remaining, vec![other_item]);
+        assert!(!tag_left);
+        assert_eq!(label_assigned_to, None);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_transfer_item_only_within_household() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let (user_id, outsider_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let household_id = state.resolve_household(user_id).await.unwrap();
+        state.resolve_household(outsider_id).await.unwrap();
+        let item_id = Uuid::new_v4();
+
+        // The outsider is a real user, just not in the item's household
+        for (id, name) in [(user_id, "Pat"), (outsider_id, "Sam")] {
+            sqlx::query("INSERT INTO users (id, email, name, google_id) VALUES ($1, $2, $3, $4)")
+                .bind(id)
+                .bind(format!("{}@example.com", id))
+                .bind(name)
+                .bind(id.to_string())
+                .execute(&pool)
+                .await
+                .unwrap();
         }
-        None => Err(StatusCode::NOT_FOUND),
+
+        sqlx::query(
+            "INSERT INTO items (id, shelf_id, name, created_by, household_id) VALUES ($1, $2, 'Transfer item', $3, $4)",
+        )
+        .bind(item_id)
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(household_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let to_outsider = transfer_item(
+            State(state.clone()),
+            AuthUser(user_id),
+            Path(item_id),
+            Json(TransferItemRequest {
+                new_owner_id: outsider_id,
+            }),
+        )
+        .await;
+        let to_member = transfer_item(
+            State(state.clone()),
+            AuthUser(user_id),
+            Path(item_id),
+            Json(TransferItemRequest {
+                new_owner_id: user_id,
+            }),
+        )
+        .await;
+
+        sqlx::query("DELETE FROM items WHERE id = $1")
+            .bind(item_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+            .bind(vec![user_id, outsider_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(matches!(to_outsider, Err(ApiError::BadRequest(_))));
+        let Json(item) = to_member.unwrap();
+        assert_eq!(item.belongs_to_user_id, Some(user_id));
+    }
+
+    #[test]
+    fn test_barcode_owner_visible_id() {
+        let household_id = Uuid::new_v4();
+        let owner = BarcodeOwner {
+            id: Uuid::new_v4(),
+            household_id: Some(household_id),
+        };
+
+        assert_eq!(owner.visible_id(Some(household_id)), Some(owner.id));
+        assert_eq!(owner.visible_id(Some(Uuid::new_v4())), None);
+        assert_eq!(owner.visible_id(None), None);
+
+        // Items predating households belong to no one's
+        let unscoped = BarcodeOwner {
+            household_id: None,
+            ..owner
+        };
+        assert_eq!(unscoped.visible_id(None), None);
+        assert_eq!(
+            owner.conflict(Uuid::new_v4()),
+            ApiError::BarcodeConflict {
+                existing_item_id: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_purchase_price() {
+        let price = Some(Decimal::new(1999, 2));
+
+        assert_eq!(
+            resolve_purchase_price(price, None).unwrap(),
+            (price, Some("USD".to_string()))
+        );
+        assert_eq!(
+            resolve_purchase_price(price, Some("eur".to_string())).unwrap(),
+            (price, Some("EUR".to_string()))
+        );
+        assert_eq!(resolve_purchase_price(None, None).unwrap(), (None, None));
+        assert!(resolve_purchase_price(price, Some("EURO".to_string())).is_err());
+        assert!(resolve_purchase_price(Some(Decimal::new(-1, 0)), None).is_err());
+    }
+
+    #[test]
+    fn test_ensure_item_file_key() {
+        assert!(ensure_item_file_key("items/manual/a.pdf").is_ok());
+        assert!(ensure_item_file_key("items/receipt/b.png").is_ok());
+        assert!(ensure_item_file_key("photos/item/c.jpg").is_err());
+        assert!(ensure_item_file_key("items/other/d.pdf").is_err());
+    }
+
+    #[test]
+    fn test_parse_condition() {
+        assert_eq!(parse_condition(None).unwrap(), None);
+        assert_eq!(
+            parse_condition(Some("like_new")).unwrap(),
+            Some(Condition::LikeNew)
+        );
+        assert_eq!(
+            parse_condition(Some(" good ")).unwrap(),
+            Some(Condition::Good)
+        );
+        assert!(matches!(
+            parse_condition(Some("mint")),
+            Err(ApiError::BadRequest(msg)) if msg == INVALID_CONDITION
+        ));
     }
 }
@@ -0,0 +1,410 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    Router,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::{
+    CreateLabelTemplateRequest, CustomLabelTemplate, PaginatedResponse, PaginationQuery,
+    UpdateLabelTemplateRequest,
+};
+use crate::services::audit::Auditable;
+use crate::services::qr_pdf::LabelTemplateConfig;
+
+/// Reject blank or overlong names, and layouts whose labels don't fit on the sheet with 400
+fn validate_label_template(config: &LabelTemplateConfig) -> Result<(), ApiError> {
+    if config.name.trim().is_empty() {
+        return Err(ApiError::bad_request("name must not be empty"));
+    }
+    if config.name.chars().count() > 255 {
+        return Err(ApiError::bad_request("name must be at most 255 characters"));
+    }
+    config.validate().map_err(|e| {
+        tracing::warn!("Invalid label template: {}", e);
+        ApiError::bad_request(e)
+    })
+}
+
+/// A template created by `user_id`; anyone else's is a 404
+async fn fetch_label_template(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<CustomLabelTemplate, ApiError> {
+    sqlx::query_as::<_, CustomLabelTemplate>(
+        "SELECT * FROM label_templates WHERE id = $1 AND created_by = $2",
+    )
+    .bind(id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Label template", id))
+}
+
+/// Get the custom label templates the user created
+#[utoipa::path(
+    get,
+    path = "/api/label-templates",
+    tag = "label_templates",
+    params(
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<CustomLabelTemplate>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn list_label_templates(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<CustomLabelTemplate>>, ApiError> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 1000);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM label_templates WHERE created_by = $1")
+            .bind(user_id)
+            .fetch_one(&state.db)
+            .await?;
+    let total = total.clamp(0, i32::MAX as i64) as i32;
+
+    let templates = sqlx::query_as::<_, CustomLabelTemplate>(
+        "SELECT * FROM label_templates WHERE created_by = $1 ORDER BY name ASC, id ASC LIMIT $2 OFFSET $3",
+    )
+    .bind(user_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(
+        templates, total, limit, offset,
+    )))
+}
+
+/// Get a single custom label template by ID
+#[utoipa::path(
+    get,
+    path = "/api/label-templates/{id}",
+    tag = "label_templates",
+    params(
+        ("id" = Uuid, Path, description = "Label template id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = CustomLabelTemplate),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Label template not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_label_template(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CustomLabelTemplate>, ApiError> {
+    let template = fetch_label_template(&state, user_id, id).await?;
+    Ok(Json(template))
+}
+
+/// Create a custom label template
+#[utoipa::path(
+    post,
+    path = "/api/label-templates",
+    tag = "label_templates",
+    request_body = CreateLabelTemplateRequest,
+    responses(
+        (status = 200, description = "OK", body = CustomLabelTemplate),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn create_label_template(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Json(payload): Json<CreateLabelTemplateRequest>,
+) -> Result<Json<CustomLabelTemplate>, ApiError> {
+    let config = LabelTemplateConfig {
+        name: payload.name.trim().to_string(),
+        sheet_width_mm: payload.sheet_width_mm,
+        sheet_height_mm: payload.sheet_height_mm,
+        label_width_mm: payload.label_width_mm,
+        label_height_mm: payload.label_height_mm,
+        cols: payload.cols,
+        rows: payload.rows,
+        top_margin_mm: payload.top_margin_mm,
+        left_margin_mm: payload.left_margin_mm,
+        col_spacing_mm: payload.col_spacing_mm.unwrap_or(0.0),
+        row_spacing_mm: payload.row_spacing_mm.unwrap_or(0.0),
+    };
+    validate_label_template(&config)?;
+
+    let template = sqlx::query_as::<_, CustomLabelTemplate>(
+        r#"
+        INSERT INTO label_templates (
+            id, name, sheet_width_mm, sheet_height_mm, label_width_mm, label_height_mm,
+            cols, rows, top_margin_mm, left_margin_mm, col_spacing_mm, row_spacing_mm, created_by
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        RETURNING *
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(&config.name)
+    .bind(config.sheet_width_mm)
+    .bind(config.sheet_height_mm)
+    .bind(config.label_width_mm)
+    .bind(config.label_height_mm)
+    .bind(config.cols)
+    .bind(config.rows)
+    .bind(config.top_margin_mm)
+    .bind(config.left_margin_mm)
+    .bind(config.col_spacing_mm)
+    .bind(config.row_spacing_mm)
+    .bind(user_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    state
+        .audit
+        .log_create(
+            "label_template",
+            template.id,
+            Some(user_id),
+            serde_json::to_value(&config).ok(),
+        )
+        .await
+        .ok();
+
+    Ok(Json(template))
+}
+
+/// Update a custom label template. Batches already generated keep the layout
+/// they were printed with.
+#[utoipa::path(
+    patch,
+    path = "/api/label-templates/{id}",
+    tag = "label_templates",
+    params(
+        ("id" = Uuid, Path, description = "Label template id")
+    ),
+    request_body = UpdateLabelTemplateRequest,
+    responses(
+        (status = 200, description = "OK", body = CustomLabelTemplate),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Label template not found", body = ErrorResponse)
+    )
+)]
+pub async fn update_label_template(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateLabelTemplateRequest>,
+) -> Result<Json<CustomLabelTemplate>, ApiError> {
+    let existing = fetch_label_template(&state, user_id, id).await?;
+    let before = LabelTemplateConfig::from(&existing);
+
+    let config = LabelTemplateConfig {
+        name: payload
+            .name
+            .map(|name| name.trim().to_string())
+            .unwrap_or_else(|| before.name.clone()),
+        sheet_width_mm: payload.sheet_width_mm.unwrap_or(before.sheet_width_mm),
+        sheet_height_mm: payload.sheet_height_mm.unwrap_or(before.sheet_height_mm),
+        label_width_mm: payload.label_width_mm.unwrap_or(before.label_width_mm),
+        label_height_mm: payload.label_height_mm.unwrap_or(before.label_height_mm),
+        cols: payload.cols.unwrap_or(before.cols),
+        rows: payload.rows.unwrap_or(before.rows),
+        top_margin_mm: payload.top_margin_mm.unwrap_or(before.top_margin_mm),
+        left_margin_mm: payload.left_margin_mm.unwrap_or(before.left_margin_mm),
+        col_spacing_mm: payload.col_spacing_mm.unwrap_or(before.col_spacing_mm),
+        row_spacing_mm: payload.row_spacing_mm.unwrap_or(before.row_spacing_mm),
+    };
+    validate_label_template(&config)?;
+
+    let template = sqlx::query_as::<_, CustomLabelTemplate>(
+        r#"
+        UPDATE label_templates
+        SET name = $1, sheet_width_mm = $2, sheet_height_mm = $3, label_width_mm = $4,
+            label_height_mm = $5, cols = $6, rows = $7, top_margin_mm = $8,
+            left_margin_mm = $9, col_spacing_mm = $10, row_spacing_mm = $11
+        WHERE id = $12 AND created_by = $13
+        RETURNING *
+        "#,
+    )
+    .bind(&config.name)
+    .bind(config.sheet_width_mm)
+    .bind(config.sheet_height_mm)
+    .bind(config.label_width_mm)
+    .bind(config.label_height_mm)
+    .bind(config.cols)
+    .bind(config.rows)
+    .bind(config.top_margin_mm)
+    .bind(config.left_margin_mm)
+    .bind(config.col_spacing_mm)
+    .bind(config.row_spacing_mm)
+    .bind(id)
+    .bind(user_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if config != before {
+        state
+            .audit
+            .log_update(
+                "label_template",
+                id,
+                Some(user_id),
+                serde_json::json!({ "from": before, "to": config }),
+                None,
+            )
+            .await
+            .ok();
+    }
+
+    Ok(Json(template))
+}
+
+/// Delete a custom label template. Batches generated with it can still be
+/// reprinted from their stored layout.
+#[utoipa::path(
+    delete,
+    path = "/api/label-templates/{id}",
+    tag = "label_templates",
+    params(
+        ("id" = Uuid, Path, description = "Label template id")
+    ),
+    responses(
+        (status = 204, description = "No Content"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Label template not found", body = ErrorResponse)
+    )
+)]
+pub async fn delete_label_template(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    let result = sqlx::query("DELETE FROM label_templates WHERE id = $1 AND created_by = $2")
+        .bind(id)
+        .bind(user_id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::not_found("Label template", id));
+    }
+
+    state
+        .audit
+        .log_delete("label_template", id, Some(user_id), None)
+        .await
+        .ok();
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create label template routes
+pub fn label_template_routes() -> Router<Arc<AppState>> {
+    use axum::routing::get;
+
+    Router::new()
+        .route(
+            "/api/label-templates",
+            get(list_label_templates).post(create_label_template),
+        )
+        .route(
+            "/api/label-templates/:id",
+            get(get_label_template)
+                .patch(update_label_template)
+                .delete(delete_label_template),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_pool, test_app_state};
+
+    #[test]
+    fn test_validate_label_template_rejects_blank_name() {
+        let mut config = crate::services::qr_pdf::LabelTemplate::Avery18660.config();
+        assert!(validate_label_template(&config).is_ok());
+
+        config.name = "   ".to_string();
+        assert!(matches!(
+            validate_label_template(&config),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_label_templates_only_visible_to_their_creator() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let owner_id = Uuid::new_v4();
+        let other_id = Uuid::new_v4();
+
+        let Json(template) = create_label_template(
+            State(state.clone()),
+            AuthUser(owner_id),
+            Json(CreateLabelTemplateRequest {
+                name: "Pantry jars".to_string(),
+                sheet_width_mm: 215.9,
+                sheet_height_mm: 279.4,
+                label_width_mm: 50.0,
+                label_height_mm: 25.0,
+                cols: 3,
+                rows: 8,
+                top_margin_mm: 10.0,
+                left_margin_mm: 10.0,
+                col_spacing_mm: None,
+                row_spacing_mm: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        let get =
+            get_label_template(State(state.clone()), AuthUser(other_id), Path(template.id)).await;
+        let Json(list) = list_label_templates(
+            State(state.clone()),
+            AuthUser(other_id),
+            Query(PaginationQuery::default()),
+        )
+        .await
+        .unwrap();
+        let update = update_label_template(
+            State(state.clone()),
+            AuthUser(other_id),
+            Path(template.id),
+            Json(serde_json::from_value(serde_json::json!({ "name": "Mine now" })).unwrap()),
+        )
+        .await;
+        let delete =
+            delete_label_template(State(state.clone()), AuthUser(other_id), Path(template.id))
+                .await;
+        let own = get_label_template(State(state.clone()), AuthUser(owner_id), Path(template.id))
+            .await
+            .unwrap();
+
+        sqlx::query("DELETE FROM label_templates WHERE id = $1")
+            .bind(template.id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(matches!(get, Err(ApiError::NotFound(_))));
+        assert!(list.data.iter().all(|t| t.id != template.id));
+        assert!(matches!(update, Err(ApiError::NotFound(_))));
+        assert!(matches!(delete, Err(ApiError::NotFound(_))));
+        assert_eq!(own.name, "Pantry jars");
+    }
+}
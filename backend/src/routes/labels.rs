@@ -1,46 +1,217 @@
 use axum::{
     extract::{Path, Query, State},
     http::{header, StatusCode},
-    response::Response,
+    response::{IntoResponse, Response},
     Router,
 };
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::sync::Arc;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
 use crate::models::label::{BatchWithLabels, *};
-use crate::models::{PaginatedResponse, PaginationQuery};
-use crate::services::generate_label_pdf;
+use crate::models::{PaginatedResponse, PaginationQuery, PathNode, SearchResultKind};
+use crate::routes::items::public_item;
+use crate::routes::location::cached_location_path;
+use crate::services::audit::Auditable;
+use crate::services::household::HouseholdEntity;
+use crate::services::qr_pdf::{LabelTemplate, LabelTemplateConfig};
+use crate::services::{generate_label_pdf, generate_label_pdf_with_names};
+
+/// Layout for a template named in a request: a built-in Avery template, or the ID
+/// of one of the user's custom label templates
+pub(crate) async fn resolve_template(
+    state: &AppState,
+    user_id: Uuid,
+    template: &str,
+) -> Result<LabelTemplateConfig, ApiError> {
+    if let Ok(builtin) = template.parse::<LabelTemplate>() {
+        return Ok(builtin.config());
+    }
+    let Ok(id) = template.parse::<Uuid>() else {
+        tracing::warn!("Rejected unknown label template: {}", template);
+        return Err(ApiError::bad_request(format!(
+            "Unknown label template: {}",
+            template
+        )));
+    };
+
+    let custom = sqlx::query_as::<_, CustomLabelTemplate>(
+        "SELECT * FROM label_templates WHERE id = $1 AND created_by = $2",
+    )
+    .bind(id)
+    .bind(user_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Label template", id))?;
+
+    let config = LabelTemplateConfig::from(&custom);
+    // Checked on save too, but rows written by hand could still overflow the sheet
+    config.validate().map_err(ApiError::bad_request)?;
+    Ok(config)
+}
+
+/// Name of the entity each label in `$1` is assigned to, for the labels whose
+/// entity still exists in household `$2`
+const LABEL_NAMES_SQL: &str = r#"
+    SELECT labels.id, entities.name
+    FROM labels
+    JOIN (
+        SELECT 'room' AS entity_type, id, name FROM rooms WHERE household_id = $2
+        UNION ALL
+        SELECT 'unit', id, name FROM shelving_units WHERE household_id = $2
+        UNION ALL
+        SELECT 'shelf', id, name FROM shelves WHERE household_id = $2
+        UNION ALL
+        SELECT 'container', id, name FROM containers WHERE household_id = $2
+        UNION ALL
+        SELECT 'item', id, name FROM items WHERE deleted_at IS NULL AND household_id = $2
+    ) AS entities
+        ON entities.entity_type = labels.assigned_to_type AND entities.id = labels.assigned_to_id
+    WHERE labels.id = ANY($1)
+"#;
+
+/// Label `$1`, if its batch belongs to household `$2`
+const HOUSEHOLD_LABEL_SQL: &str = r#"
+    SELECT labels.*
+    FROM labels
+    JOIN label_batches ON label_batches.id = labels.batch_id
+    WHERE labels.id = $1 AND label_batches.household_id = $2
+"#;
+
+/// Label `id` of `household_id`. Another household's label is a 404, like a
+/// missing one.
+async fn fetch_household_label(
+    state: &AppState,
+    household_id: Uuid,
+    id: Uuid,
+) -> Result<Label, ApiError> {
+    sqlx::query_as::<_, Label>(HOUSEHOLD_LABEL_SQL)
+        .bind(id)
+        .bind(household_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Label", id))
+}
+
+/// Responses for `labels`, each with the name of the entity it is assigned to in
+/// `household_id`
+async fn label_responses(
+    state: &AppState,
+    household_id: Uuid,
+    labels: Vec<Label>,
+) -> Result<Vec<LabelResponse>, ApiError> {
+    let assigned: Vec<Uuid> = labels
+        .iter()
+        .filter(|label| label.assigned_to_id.is_some())
+        .map(|label| label.id)
+        .collect();
+    let mut names: HashMap<Uuid, String> = if assigned.is_empty() {
+        HashMap::new()
+    } else {
+        sqlx::query_as::<_, (Uuid, String)>(LABEL_NAMES_SQL)
+            .bind(&assigned)
+            .bind(household_id)
+            .fetch_all(&state.db)
+            .await?
+            .into_iter()
+            .collect()
+    };
+
+    Ok(labels
+        .into_iter()
+        .map(|label| {
+            let label_name = names.remove(&label.id);
+            LabelResponse {
+                label_name,
+                ..LabelResponse::from(label)
+            }
+        })
+        .collect())
+}
+
+/// Response for a single label, with the name of the entity it is assigned to
+async fn label_response(
+    state: &AppState,
+    household_id: Uuid,
+    label: Label,
+) -> Result<LabelResponse, ApiError> {
+    Ok(label_responses(state, household_id, vec![label])
+        .await?
+        .remove(0))
+}
 
 /// Generate a batch of labels
+#[utoipa::path(
+    post,
+    path = "/api/labels/generate",
+    tag = "labels",
+    request_body = GenerateLabelsRequest,
+    responses(
+        (status = 200, description = "OK", body = GenerateLabelsResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn generate_labels(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     axum::Json(payload): axum::Json<GenerateLabelsRequest>,
-) -> Result<axum::Json<GenerateLabelsResponse>, StatusCode> {
+) -> Result<axum::Json<GenerateLabelsResponse>, ApiError> {
     // Validate count
     if payload.count <= 0 || payload.count > 1000 {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request("count must be between 1 and 1000"));
     }
 
     // Use default template if not specified
-    let template = payload.template.as_deref().unwrap_or("avery_18660");
-    if template != "avery_18660" {
-        return Err(StatusCode::BAD_REQUEST);
-    }
+    let template = payload
+        .template
+        .as_deref()
+        .map(str::trim)
+        .unwrap_or(LabelTemplate::default().as_str())
+        .to_string();
+    let template_config = resolve_template(&state, user_id, &template).await?;
+    let template_config_json = serde_json::to_string(&template_config).map_err(|e| {
+        tracing::error!("Failed to serialize label template: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
 
-    // Generate batch ID
-    let batch_id = Uuid::new_v4();
+    let purpose = payload
+        .purpose
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+    let household_id = state.resolve_household(user_id).await?;
+
+    // Record the batch
+    let batch = sqlx::query_as::<_, LabelBatch>(
+        r#"
+        INSERT INTO label_batches (id, label_count, template, purpose, created_by, template_config, household_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(payload.count)
+    .bind(&template)
+    .bind(purpose)
+    .bind(user_id)
+    .bind(&template_config_json)
+    .bind(household_id)
+    .fetch_one(&state.db)
+    .await?;
+    let batch_id = batch.id;
 
     // Get the next label number
     // Use COALESCE to return 0 when there are no labels yet
     let max_number: i32 = sqlx::query_scalar("SELECT COALESCE(MAX(number), 0) FROM labels")
         .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to get max label number: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
     let start_number = max_number + 1;
 
@@ -56,8 +227,8 @@ pub async fn generate_labels(
 
         let label = sqlx::query_as::<_, Label>(
             r#"
-            INSERT INTO labels (id, number, qr_data, batch_id)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO labels (id, number, qr_data, batch_id, updated_at)
+            VALUES ($1, $2, $3, $4, NOW())
             RETURNING *
             "#,
         )
@@ -66,15 +237,26 @@ pub async fn generate_labels(
         .bind(&qr_data)
         .bind(batch_id)
         .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to create label: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
         labels.push(label);
     }
 
+    state
+        .audit
+        .log_create(
+            "label_batch",
+            batch_id,
+            Some(user_id),
+            Some(serde_json::json!({
+                "label_count": batch.label_count,
+                "template": batch.template,
+                "purpose": batch.purpose,
+            })),
+        )
+        .await
+        .ok();
+
     let response = GenerateLabelsResponse {
         batch_id,
         labels: labels.into_iter().map(LabelResponse::from).collect(),
@@ -85,51 +267,174 @@ pub async fn generate_labels(
 }
 
 /// Get a single label by ID
+#[utoipa::path(
+    get,
+    path = "/api/labels/{id}",
+    tag = "labels",
+    params(
+        ("id" = Uuid, Path, description = "Label id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = LabelResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Label not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_label(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<axum::Json<LabelResponse>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    let label = fetch_household_label(&state, household_id, id).await?;
+
+    Ok(axum::Json(
+        label_response(&state, household_id, label).await?,
+    ))
+}
+
+/// Resolve a scanned label to the entity it's assigned to (no authentication
+/// required), so the client can go straight to it. 404 when the label is unassigned
+/// or its entity is gone.
+///
+/// Anyone holding the label can call this, so `entity` is a public view rather than
+/// the entity's full response: a `PublicItemResponse` for items and a `PathNode`
+/// (kind, id and name) for rooms, units, shelves and containers. The client loads the
+/// full object from the entity's own endpoint.
+#[utoipa::path(
+    get,
+    path = "/api/labels/{id}/entity",
+    tag = "labels",
+    params(
+        ("id" = Uuid, Path, description = "Label id")
+    ),
+    responses(
+        (status = 200, description = "The assigned entity, in its public view", body = LabelEntityResponse),
+        (status = 404, description = "Label not found or not assigned", body = ErrorResponse)
+    ),
+    security(())
+)]
+pub async fn get_label_entity(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-) -> Result<axum::Json<LabelResponse>, StatusCode> {
+) -> Result<axum::Json<LabelEntityResponse>, ApiError> {
     let label = sqlx::query_as::<_, Label>("SELECT * FROM labels WHERE id = $1")
         .bind(id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch label: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::not_found("Label", id))?;
+
+    let (Some(entity_type), Some(entity_id)) = (label.assigned_to_type, label.assigned_to_id)
+    else {
+        return Err(ApiError::NotFound(format!(
+            "Label {} is not assigned",
+            label.number
+        )));
+    };
+    let kind = entity_type.parse::<SearchResultKind>().map_err(|_| {
+        tracing::warn!("Label {} has unknown type {}", id, entity_type);
+        ApiError::NotFound(format!("Label {} is not assigned", label.number))
+    })?;
+
+    // Anyone holding the label can ask, so only the public views go out
+    let entity = match kind {
+        SearchResultKind::Item => public_item(&state.db, entity_id)
+            .await?
+            .map(|item| serde_json::json!(item)),
+        _ => {
+            let query = format!(
+                "SELECT name FROM {} WHERE id = $1",
+                HouseholdEntity::from(kind).table()
+            );
+            sqlx::query_scalar::<_, String>(&query)
+                .bind(entity_id)
+                .fetch_optional(&state.db)
+                .await?
+                .map(|name| {
+                    serde_json::json!(PathNode {
+                        kind,
+                        id: entity_id,
+                        name,
+                    })
+                })
+        }
+    };
+    let entity = entity.ok_or_else(|| ApiError::not_found(&entity_type, entity_id))?;
+
+    Ok(axum::Json(LabelEntityResponse {
+        entity_type,
+        entity_id,
+        entity,
+    }))
+}
 
-    Ok(axum::Json(LabelResponse::from(label)))
+/// The user's household, after checking the entity a label is being put on is in it
+async fn authorize_label_target(
+    state: &AppState,
+    user_id: Uuid,
+    payload: &AssignLabelRequest,
+) -> Result<Uuid, ApiError> {
+    let entity = HouseholdEntity::from_entity_type(&payload.assigned_to_type)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    state
+        .authorize_entity(user_id, entity, payload.assigned_to_id)
+        .await
 }
 
 /// Assign a label to an entity
+#[utoipa::path(
+    post,
+    path = "/api/labels/{id}/assign",
+    tag = "labels",
+    params(
+        ("id" = Uuid, Path, description = "Label id")
+    ),
+    request_body = AssignLabelRequest,
+    responses(
+        (status = 200, description = "OK", body = LabelResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Label not found", body = ErrorResponse)
+    )
+)]
 pub async fn assign_label(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
     axum::Json(payload): axum::Json<AssignLabelRequest>,
-) -> Result<axum::Json<LabelResponse>, StatusCode> {
+) -> Result<axum::Json<LabelResponse>, ApiError> {
     // Validate assigned_to_type
     let valid_types = ["room", "unit", "shelf", "container", "item"];
     if !valid_types.contains(&payload.assigned_to_type.as_str()) {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::bad_request(format!(
+            "assigned_to_type must be one of {}",
+            valid_types.join(", ")
+        )));
     }
 
+    let household_id = authorize_label_target(&state, user_id, &payload).await?;
+
     // Check if label exists
-    let _existing = sqlx::query_as::<_, Label>("SELECT * FROM labels WHERE id = $1")
-        .bind(id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch label: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+    let existing = fetch_household_label(&state, household_id, id).await?;
+
+    // Items in the trash can't be labeled
+    if payload.assigned_to_type == "item" {
+        let item_exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM items WHERE id = $1 AND deleted_at IS NULL)",
+        )
+        .bind(payload.assigned_to_id)
+        .fetch_one(&state.db)
+        .await?;
+        if !item_exists {
+            return Err(ApiError::not_found("Item", payload.assigned_to_id));
+        }
+    }
 
     // Update label assignment
     let label = sqlx::query_as::<_, Label>(
         r#"
         UPDATE labels
-        SET assigned_to_type = $1, assigned_to_id = $2, assigned_at = NOW()
+        SET assigned_to_type = $1, assigned_to_id = $2, assigned_at = NOW(), updated_at = NOW()
         WHERE id = $3
         RETURNING *
         "#,
@@ -138,75 +443,237 @@ pub async fn assign_label(
     .bind(payload.assigned_to_id)
     .bind(id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to assign label: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
+
+    state
+        .audit
+        .log_update(
+            "label",
+            id,
+            Some(user_id),
+            serde_json::json!({
+                "assigned_to_type": {
+                    "from": existing.assigned_to_type,
+                    "to": label.assigned_to_type,
+                },
+                "assigned_to_id": {
+                    "from": existing.assigned_to_id,
+                    "to": label.assigned_to_id,
+                }
+            }),
+            None,
+        )
+        .await
+        .ok();
+
+    Ok(axum::Json(
+        label_response(&state, household_id, label).await?,
+    ))
+}
+
+/// Extra condition restricting label assignment to entities that aren't in the trash
+fn live_condition(table: &str) -> &'static str {
+    if table == "items" {
+        " AND deleted_at IS NULL"
+    } else {
+        ""
+    }
+}
+
+/// Table holding the `label_id` column for an assignable entity type
+fn label_table(entity_type: &str) -> Option<&'static str> {
+    match entity_type {
+        "room" => Some("rooms"),
+        "unit" => Some("shelving_units"),
+        "shelf" => Some("shelves"),
+        "container" => Some("containers"),
+        "item" => Some("items"),
+        _ => None,
+    }
+}
+
+/// Move a label to a different entity, detaching it from the one it was on
+#[utoipa::path(
+    put,
+    path = "/api/labels/{id}/assign",
+    tag = "labels",
+    params(
+        ("id" = Uuid, Path, description = "Label id")
+    ),
+    request_body = AssignLabelRequest,
+    responses(
+        (status = 200, description = "OK", body = LabelResponse),
+        (status = 204, description = "Label is already assigned to that entity"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Label not found", body = ErrorResponse)
+    )
+)]
+pub async fn reassign_label(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    axum::Json(payload): axum::Json<AssignLabelRequest>,
+) -> Result<Response, ApiError> {
+    let table = label_table(&payload.assigned_to_type).ok_or(StatusCode::BAD_REQUEST)?;
+    let household_id = authorize_label_target(&state, user_id, &payload).await?;
+
+    let existing = fetch_household_label(&state, household_id, id).await?;
+
+    // Already on this entity, nothing to do
+    if existing.assigned_to_type.as_deref() == Some(payload.assigned_to_type.as_str())
+        && existing.assigned_to_id == Some(payload.assigned_to_id)
+    {
+        return Ok(StatusCode::NO_CONTENT.into_response());
+    }
+
+    let mut tx = state.db.begin().await?;
+
+    // Point the new entity at the label
+    let live = live_condition(table);
+    let updated = sqlx::query(&format!(
+        "UPDATE {table} SET label_id = $1 WHERE id = $2{live}"
+    ))
+    .bind(id)
+    .bind(payload.assigned_to_id)
+    .execute(&mut *tx)
+    .await?;
+    if updated.rows_affected() == 0 {
+        return Err(ApiError::NotFound(format!(
+            "{} with id {} not found",
+            payload.assigned_to_type, payload.assigned_to_id
+        )));
+    }
+
+    // Detach the label from the entity it was previously on
+    if let (Some(old_type), Some(old_id)) = (&existing.assigned_to_type, existing.assigned_to_id) {
+        if let Some(old_table) = label_table(old_type) {
+            sqlx::query(&format!(
+                "UPDATE {old_table} SET label_id = NULL WHERE id = $1 AND label_id = $2"
+            ))
+            .bind(old_id)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    // An entity holds a single label, so any other label on it is now unassigned
+    sqlx::query(
+        r#"
+        UPDATE labels
+        SET assigned_to_type = NULL, assigned_to_id = NULL, assigned_at = NULL, updated_at = NOW()
+        WHERE assigned_to_type = $1 AND assigned_to_id = $2 AND id <> $3
+        "#,
+    )
+    .bind(&payload.assigned_to_type)
+    .bind(payload.assigned_to_id)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let label = sqlx::query_as::<_, Label>(
+        r#"
+        UPDATE labels
+        SET assigned_to_type = $1, assigned_to_id = $2, assigned_at = NOW(), updated_at = NOW()
+        WHERE id = $3
+        RETURNING *
+        "#,
+    )
+    .bind(&payload.assigned_to_type)
+    .bind(payload.assigned_to_id)
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
 
-    Ok(axum::Json(LabelResponse::from(label)))
+    state
+        .audit
+        .log_update(
+            "label",
+            id,
+            Some(user_id),
+            serde_json::json!({
+                "assigned_to_type": {
+                    "from": existing.assigned_to_type,
+                    "to": label.assigned_to_type,
+                },
+                "assigned_to_id": {
+                    "from": existing.assigned_to_id,
+                    "to": label.assigned_to_id,
+                }
+            }),
+            None,
+        )
+        .await
+        .ok();
+
+    Ok(axum::Json(label_response(&state, household_id, label).await?).into_response())
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PrintQuery {
     template: Option<String>,
 }
 
-/// List all batches with their labels
+/// List the household's batches with their labels
+#[utoipa::path(
+    get,
+    path = "/api/labels",
+    tag = "labels",
+    params(
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<BatchWithLabels>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn list_batches(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Query(params): Query<PaginationQuery>,
-) -> Result<axum::Json<PaginatedResponse<BatchWithLabels>>, StatusCode> {
+) -> Result<axum::Json<PaginatedResponse<BatchWithLabels>>, ApiError> {
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
+    let household_id = state.resolve_household(user_id).await?;
 
-    // Get total count of unique batches
-    let total: i64 = sqlx::query_scalar(
-        "SELECT COUNT(DISTINCT batch_id) FROM labels WHERE batch_id IS NOT NULL",
-    )
-    .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to count batches: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM label_batches WHERE household_id = $1")
+            .bind(household_id)
+            .fetch_one(&state.db)
+            .await?;
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
-    // Get paginated batch IDs
-    let batch_ids: Vec<Uuid> = sqlx::query_scalar(
-        "SELECT DISTINCT batch_id FROM labels WHERE batch_id IS NOT NULL ORDER BY batch_id DESC LIMIT $1 OFFSET $2"
+    // Get paginated batches, newest first
+    let label_batches = sqlx::query_as::<_, LabelBatch>(
+        "SELECT * FROM label_batches WHERE household_id = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3",
     )
+    .bind(household_id)
     .bind(limit)
     .bind(offset)
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch batch IDs: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     let mut batches = Vec::new();
-    for batch_id in batch_ids {
+    for batch in label_batches {
         // Get all labels for this batch
         let labels = sqlx::query_as::<_, Label>(
             "SELECT * FROM labels WHERE batch_id = $1 ORDER BY number ASC",
         )
-        .bind(batch_id)
+        .bind(batch.id)
         .fetch_all(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch labels for batch: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-
-        if !labels.is_empty() {
-            let created_at = labels[0].created_at;
-            batches.push(BatchWithLabels {
-                batch_id,
-                labels: labels.into_iter().map(LabelResponse::from).collect(),
-                created_at,
-            });
-        }
+        .await?;
+
+        batches.push(BatchWithLabels {
+            batch_id: batch.id,
+            labels: label_responses(&state, household_id, labels).await?,
+            template: batch.template,
+            purpose: batch.purpose,
+            created_by: batch.created_by,
+            created_at: batch.created_at,
+        });
     }
 
     Ok(axum::Json(PaginatedResponse::new(
@@ -214,32 +681,195 @@ pub async fn list_batches(
     )))
 }
 
-/// Generate PDF for a batch of labels
-pub async fn print_labels(
+/// Get a batch of the household and its labels, with the name of what each label is
+/// assigned to
+#[utoipa::path(
+    get,
+    path = "/api/labels/batch/{batchId}",
+    tag = "labels",
+    params(
+        ("batchId" = Uuid, Path, description = "Label batch id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = BatchWithLabels),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Label batch not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_batch(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(batch_id): Path<Uuid>,
-    Query(query): Query<PrintQuery>,
-) -> Result<Response, StatusCode> {
-    // Get all labels in the batch
+) -> Result<axum::Json<BatchWithLabels>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    let batch = sqlx::query_as::<_, LabelBatch>(
+        "SELECT * FROM label_batches WHERE id = $1 AND household_id = $2",
+    )
+    .bind(batch_id)
+    .bind(household_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Label batch", batch_id))?;
+
     let labels =
         sqlx::query_as::<_, Label>("SELECT * FROM labels WHERE batch_id = $1 ORDER BY number ASC")
             .bind(batch_id)
             .fetch_all(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to fetch labels: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .await?;
+
+    Ok(axum::Json(BatchWithLabels {
+        batch_id: batch.id,
+        labels: label_responses(&state, household_id, labels).await?,
+        template: batch.template,
+        purpose: batch.purpose,
+        created_by: batch.created_by,
+        created_at: batch.created_at,
+    }))
+}
+
+/// Every batch of the household that has labels, with how many and which numbers,
+/// newest first
+#[utoipa::path(
+    get,
+    path = "/api/labels/batches",
+    tag = "labels",
+    responses(
+        (status = 200, description = "OK", body = Vec<LabelBatchSummary>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn list_batch_summaries(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+) -> Result<axum::Json<Vec<LabelBatchSummary>>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    let rows = sqlx::query_as::<_, (Uuid, i64, DateTime<Utc>, i32, i32)>(
+        r#"
+        SELECT labels.batch_id, COUNT(*), MIN(labels.created_at), MIN(labels.number), MAX(labels.number)
+        FROM labels
+        JOIN label_batches ON label_batches.id = labels.batch_id
+        WHERE label_batches.household_id = $1
+        GROUP BY labels.batch_id
+        ORDER BY MIN(labels.created_at) DESC
+        "#,
+    )
+    .bind(household_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let summaries = rows
+        .into_iter()
+        .map(
+            |(batch_id, count, created_at, from, to)| LabelBatchSummary {
+                batch_id,
+                count,
+                created_at,
+                label_range: LabelRange { from, to },
+            },
+        )
+        .collect();
+
+    Ok(axum::Json(summaries))
+}
+
+/// Labels in a batch of `household_id`, in number order. 404 if the batch has none
+/// or belongs to another household.
+async fn fetch_batch_labels(
+    state: &AppState,
+    household_id: Uuid,
+    batch_id: Uuid,
+) -> Result<Vec<Label>, ApiError> {
+    let labels = sqlx::query_as::<_, Label>(
+        r#"
+        SELECT labels.*
+        FROM labels
+        JOIN label_batches ON label_batches.id = labels.batch_id
+        WHERE labels.batch_id = $1 AND label_batches.household_id = $2
+        ORDER BY labels.number ASC
+        "#,
+    )
+    .bind(batch_id)
+    .bind(household_id)
+    .fetch_all(&state.db)
+    .await?;
 
     if labels.is_empty() {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found("Label batch", batch_id));
     }
+    Ok(labels)
+}
 
-    // Use default template if not specified
-    let template = query.template.as_deref().unwrap_or("avery_18660");
-    if template != "avery_18660" {
-        return Err(StatusCode::BAD_REQUEST);
+/// The requested template, falling back to the layout the batch was generated with
+async fn print_template(
+    state: &AppState,
+    user_id: Uuid,
+    batch_id: Uuid,
+    requested: Option<String>,
+) -> Result<LabelTemplateConfig, ApiError> {
+    if let Some(name) = requested {
+        return resolve_template(state, user_id, name.trim()).await;
+    }
+
+    let batch = sqlx::query_as::<_, LabelBatch>("SELECT * FROM label_batches WHERE id = $1")
+        .bind(batch_id)
+        .fetch_optional(&state.db)
+        .await?;
+    let Some(batch) = batch else {
+        return Ok(LabelTemplate::default().config());
+    };
+
+    // Batches from before template_config was recorded only have the template name
+    match batch.template_config.as_deref().map(serde_json::from_str) {
+        Some(Ok(config)) => Ok(config),
+        Some(Err(e)) => {
+            tracing::warn!(
+                "Label batch {} has invalid template_config: {}",
+                batch.id,
+                e
+            );
+            resolve_template(state, user_id, &batch.template).await
+        }
+        None => resolve_template(state, user_id, &batch.template).await,
     }
+}
+
+/// PDF response with inline disposition to open in the browser
+fn pdf_response(batch_id: Uuid, pdf_bytes: Vec<u8>) -> Response {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/pdf")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("inline; filename=\"labels-{}.pdf\"", batch_id),
+        )
+        .body(axum::body::Body::from(pdf_bytes))
+        .unwrap()
+}
+
+/// Generate PDF for a batch of labels
+#[utoipa::path(
+    get,
+    path = "/api/labels/print/{batchId}",
+    tag = "labels",
+    params(
+        ("batchId" = Uuid, Path, description = "Label batch id"),
+        PrintQuery
+    ),
+    responses(
+        (status = 200, description = "Label sheet PDF (`application/pdf`)"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Label not found", body = ErrorResponse)
+    )
+)]
+pub async fn print_labels(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(batch_id): Path<Uuid>,
+    Query(query): Query<PrintQuery>,
+) -> Result<Response, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    let labels = fetch_batch_labels(&state, household_id, batch_id).await?;
+    let template = print_template(&state, user_id, batch_id, query.template).await?;
 
     // Prepare label data for PDF generation
     let label_data: Vec<(String, i32)> = labels
@@ -248,21 +878,89 @@ pub async fn print_labels(
         .collect();
 
     // Generate PDF
-    let pdf_bytes = generate_label_pdf(&label_data).map_err(|e| {
+    let pdf_bytes = generate_label_pdf(&label_data, &template).map_err(|e| {
         tracing::error!("Failed to generate PDF: {:?}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    // Return PDF response with inline disposition to open in browser
-    Ok(Response::builder()
-        .status(StatusCode::OK)
-        .header(header::CONTENT_TYPE, "application/pdf")
-        .header(
-            header::CONTENT_DISPOSITION,
-            format!("inline; filename=\"labels-{}.pdf\"", batch_id),
-        )
-        .body(axum::body::Body::from(pdf_bytes))
-        .unwrap())
+    Ok(pdf_response(batch_id, pdf_bytes))
+}
+
+/// Name and location (its parent's name, e.g. "Shelf 2") of the entity a label is
+/// assigned to. Unassigned labels, and labels whose entity is gone, get neither. An
+/// entity of another household is a 404.
+async fn label_name_and_location(
+    state: &AppState,
+    household_id: Uuid,
+    label: &Label,
+) -> Result<(Option<String>, Option<String>), ApiError> {
+    let (Some(entity_type), Some(entity_id)) = (&label.assigned_to_type, label.assigned_to_id)
+    else {
+        return Ok((None, None));
+    };
+    let Ok(kind) = entity_type.parse::<SearchResultKind>() else {
+        tracing::warn!("Label {} has unknown type {}", label.id, entity_type);
+        return Ok((None, None));
+    };
+
+    let owner: Option<Option<Uuid>> = sqlx::query_scalar(&format!(
+        "SELECT household_id FROM {} WHERE id = $1",
+        HouseholdEntity::from(kind).table()
+    ))
+    .bind(entity_id)
+    .fetch_optional(&state.db)
+    .await?;
+    match owner {
+        None => return Ok((None, None)),
+        Some(owner) if owner != Some(household_id) => {
+            return Err(ApiError::NotFound(format!(
+                "{} with id {} not found",
+                entity_type, entity_id
+            )));
+        }
+        Some(_) => {}
+    }
+
+    let mut path = cached_location_path(state, kind, entity_id).await?;
+
+    let name = path.pop().map(|node| node.name);
+    let location = path.pop().map(|node| node.name);
+    Ok((name, location))
+}
+
+/// Generate PDF for a batch of labels, printing what each label is assigned to
+#[utoipa::path(
+    post,
+    path = "/api/labels/print-with-names",
+    tag = "labels",
+    request_body = PrintLabelsWithNamesRequest,
+    responses(
+        (status = 200, description = "Label sheet PDF (`application/pdf`)"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Label batch or assigned entity not found", body = ErrorResponse)
+    )
+)]
+pub async fn print_labels_with_names(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    axum::Json(payload): axum::Json<PrintLabelsWithNamesRequest>,
+) -> Result<Response, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    let labels = fetch_batch_labels(&state, household_id, payload.batch_id).await?;
+    let template = print_template(&state, user_id, payload.batch_id, payload.template).await?;
+
+    let mut label_data = Vec::with_capacity(labels.len());
+    for label in &labels {
+        let (name, location) = label_name_and_location(&state, household_id, label).await?;
+        label_data.push((label.qr_data.clone(), label.number, name, location));
+    }
+
+    let pdf_bytes = generate_label_pdf_with_names(&label_data, &template).map_err(|e| {
+        tracing::error!("Failed to generate PDF: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(pdf_response(payload.batch_id, pdf_bytes))
 }
 
 /// Create label routes
@@ -272,7 +970,228 @@ pub fn label_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/api/labels/generate", post(generate_labels))
         .route("/api/labels/print/:batchId", get(print_labels))
-        .route("/api/labels/:id/assign", post(assign_label))
+        .route(
+            "/api/labels/print-with-names",
+            post(print_labels_with_names),
+        )
+        .route(
+            "/api/labels/:id/assign",
+            post(assign_label).put(reassign_label),
+        )
         .route("/api/labels", get(list_batches))
+        .route("/api/labels/batches", get(list_batch_summaries))
+        .route("/api/labels/batch/:batchId", get(get_batch))
         .route("/api/labels/:id", get(get_label))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_pool, test_app_state};
+
+    #[test]
+    fn test_label_table() {
+        assert_eq!(label_table("room"), Some("rooms"));
+        assert_eq!(label_table("unit"), Some("shelving_units"));
+        assert_eq!(label_table("shelf"), Some("shelves"));
+        assert_eq!(label_table("container"), Some("containers"));
+        assert_eq!(label_table("item"), Some("items"));
+        assert_eq!(label_table("labels; DROP TABLE items"), None);
+    }
+
+    #[test]
+    fn test_live_condition_only_filters_items() {
+        assert_eq!(live_condition("items"), " AND deleted_at IS NULL");
+        assert_eq!(live_condition("containers"), "");
+    }
+
+    /// First of a few free label numbers for a test. They're negative, which
+    /// `MAX(number) + 1` numbering never reaches, so tests don't race label generation.
+    fn test_label_number(seed: Uuid) -> i32 {
+        -2 - (seed.as_u128() % 1_000_000_000) as i32
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_get_label_entity_resolves_assigned_room() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let room_id = Uuid::new_v4();
+        let (assigned_id, unassigned_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        sqlx::query("INSERT INTO rooms (id, name, created_by) VALUES ($1, 'Label test room', $2)")
+            .bind(room_id)
+            .bind(Uuid::new_v4())
+            .execute(&pool)
+            .await
+            .unwrap();
+        let first_number = test_label_number(assigned_id);
+        for (number, (label_id, assigned_to_id)) in
+            (first_number..).zip([(assigned_id, Some(room_id)), (unassigned_id, None)])
+        {
+            sqlx::query(
+                r#"
+                INSERT INTO labels (id, number, qr_data, assigned_to_type, assigned_to_id, updated_at)
+                VALUES ($1, $2, $3, $4, $5, NOW())
+                "#,
+            )
+            .bind(label_id)
+            .bind(number)
+            .bind(format!("test/l/{}", label_id))
+            .bind(assigned_to_id.map(|_| "room"))
+            .bind(assigned_to_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let assigned = get_label_entity(State(state.clone()), Path(assigned_id)).await;
+        let unassigned = get_label_entity(State(state), Path(unassigned_id)).await;
+
+        sqlx::query("DELETE FROM labels WHERE id = ANY($1)")
+            .bind(vec![assigned_id, unassigned_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM rooms WHERE id = $1")
+            .bind(room_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let axum::Json(response) = assigned.unwrap();
+        assert_eq!(response.entity_type, "room");
+        assert_eq!(response.entity_id, room_id);
+        assert_eq!(response.entity["name"], "Label test room");
+        assert!(response.entity.get("created_by").is_none());
+        assert!(matches!(unassigned, Err(ApiError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_get_batch_names_assigned_labels() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let (batch_id, room_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (assigned_id, unassigned_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (user_id, outsider_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let household_id = state.resolve_household(user_id).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO rooms (id, name, created_by, household_id) VALUES ($1, 'Batch test room', $2, $3)",
+        )
+        .bind(room_id)
+        .bind(user_id)
+        .bind(household_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO label_batches (id, label_count, template, household_id) VALUES ($1, 2, 'avery_18660', $2)",
+        )
+        .bind(batch_id)
+        .bind(household_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let first_number = test_label_number(batch_id);
+        for (number, (label_id, assigned_to_id)) in
+            (first_number..).zip([(assigned_id, Some(room_id)), (unassigned_id, None)])
+        {
+            sqlx::query(
+                r#"
+                INSERT INTO labels (id, number, qr_data, batch_id, assigned_to_type, assigned_to_id, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, NOW())
+                "#,
+            )
+            .bind(label_id)
+            .bind(number)
+            .bind(format!("test/l/{}", label_id))
+            .bind(batch_id)
+            .bind(assigned_to_id.map(|_| "room"))
+            .bind(assigned_to_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let batch = get_batch(State(state.clone()), AuthUser(user_id), Path(batch_id)).await;
+        let summaries = list_batch_summaries(State(state.clone()), AuthUser(user_id)).await;
+        let missing = get_batch(
+            State(state.clone()),
+            AuthUser(user_id),
+            Path(Uuid::new_v4()),
+        )
+        .await;
+        let outsider_summaries =
+            list_batch_summaries(State(state.clone()), AuthUser(outsider_id)).await;
+        let outsider_label = get_label(
+            State(state.clone()),
+            AuthUser(outsider_id),
+            Path(assigned_id),
+        )
+        .await;
+        let outsider_batch =
+            get_batch(State(state.clone()), AuthUser(outsider_id), Path(batch_id)).await;
+        let outsider_print = print_labels_with_names(
+            State(state.clone()),
+            AuthUser(outsider_id),
+            axum::Json(PrintLabelsWithNamesRequest {
+                batch_id,
+                template: None,
+            }),
+        )
+        .await;
+
+        sqlx::query("DELETE FROM labels WHERE batch_id = $1")
+            .bind(batch_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM label_batches WHERE id = $1")
+            .bind(batch_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM rooms WHERE id = $1")
+            .bind(room_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let axum::Json(batch) = batch.unwrap();
+        assert_eq!(batch.labels.len(), 2);
+        assert_eq!(batch.labels[0].id, assigned_id);
+        assert_eq!(
+            batch.labels[0].label_name.as_deref(),
+            Some("Batch test room")
+        );
+        assert_eq!(batch.labels[1].label_name, None);
+
+        let axum::Json(summaries) = summaries.unwrap();
+        let summary = summaries
+            .iter()
+            .find(|summary| summary.batch_id == batch_id)
+            .expect("batch is listed");
+        assert_eq!(summary.count, 2);
+        assert_eq!(summary.label_range.to, summary.label_range.from + 1);
+        assert_eq!(summary.label_range.from, batch.labels[0].number);
+
+        assert!(matches!(missing, Err(ApiError::NotFound(_))));
+
+        // Other households see neither the batch nor its labels
+        let axum::Json(outsider_summaries) = outsider_summaries.unwrap();
+        assert!(outsider_summaries
+            .iter()
+            .all(|summary| summary.batch_id != batch_id));
+        assert_eq!(
+            outsider_label.unwrap_err(),
+            ApiError::not_found("Label", assigned_id)
+        );
+        assert_eq!(
+            outsider_batch.unwrap_err(),
+            ApiError::not_found("Label batch", batch_id)
+        );
+        assert!(matches!(outsider_print, Err(ApiError::NotFound(_))));
+    }
+}
@@ -0,0 +1,265 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json,
+    Router,
+};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::{LocationPathResponse, PathNode, SearchResultKind};
+
+/// Containers nest; stop walking up a (corrupt) cyclic hierarchy after this many steps
+const MAX_PATH_DEPTH: i32 = 32;
+
+/// Walk from an entity up to its room. `$1` is the entity kind, `$2` its id and `$3`
+/// the maximum depth. `nodes` gives every location's parent (a container's parent
+/// container wins over its shelf, as does an item's container) and is inlined, so
+/// each step is a primary key lookup. Rows come back root first.
+const LOCATION_PATH_SQL: &str = r#"
+    WITH RECURSIVE nodes AS NOT MATERIALIZED (
+        SELECT 'room'::TEXT AS kind, id, name, NULL::TEXT AS parent_kind, NULL::UUID AS parent_id
+        FROM rooms
+        UNION ALL
+        SELECT 'unit', id, name, 'room', room_id
+        FROM shelving_units
+        UNION ALL
+        SELECT 'shelf', id, name, 'unit', shelving_unit_id
+        FROM shelves
+        UNION ALL
+        SELECT 'container', id, name,
+               CASE WHEN parent_container_id IS NOT NULL THEN 'container' ELSE 'shelf' END,
+               COALESCE(parent_container_id, shelf_id)
+        FROM containers
+        UNION ALL
+        SELECT 'item', id, name,
+               CASE WHEN container_id IS NOT NULL THEN 'container' ELSE 'shelf' END,
+               COALESCE(container_id, shelf_id)
+        FROM items
+        WHERE deleted_at IS NULL
+    ),
+    path AS (
+        SELECT 0 AS depth, n.kind, n.id, n.name, n.parent_kind, n.parent_id
+        FROM nodes n
+        WHERE n.kind = $1 AND n.id = $2
+        UNION ALL
+        SELECT p.depth + 1, n.kind, n.id, n.name, n.parent_kind, n.parent_id
+        FROM path p
+        JOIN nodes n ON n.kind = p.parent_kind AND n.id = p.parent_id
+        WHERE p.depth < $3
+    )
+    SELECT kind, id, name FROM path ORDER BY depth DESC
+"#;
+
+/// Breadcrumb from the room down to the entity, empty if it doesn't exist
+pub async fn resolve_location_path(
+    db: &PgPool,
+    kind: SearchResultKind,
+    id: Uuid,
+) -> Result<Vec<PathNode>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, (String, Uuid, String)>(LOCATION_PATH_SQL)
+        .bind(kind_name(kind))
+        .bind(id)
+        .bind(MAX_PATH_DEPTH)
+        .fetch_all(db)
+        .await?;
+
+    if rows.len() > MAX_PATH_DEPTH as usize {
+        tracing::warn!("Location hierarchy too deep at {} {}", kind_name(kind), id);
+    }
+
+    rows.into_iter()
+        .map(|(kind, id, name)| {
+            let kind = kind
+                .parse::<SearchResultKind>()
+                .map_err(|e| sqlx::Error::Decode(e.into()))?;
+            Ok(PathNode { kind, id, name })
+        })
+        .collect()
+}
+
+/// The `kind` values used in `LOCATION_PATH_SQL`
+fn kind_name(kind: SearchResultKind) -> &'static str {
+    match kind {
+        SearchResultKind::Room => "room",
+        SearchResultKind::Unit => "unit",
+        SearchResultKind::Shelf => "shelf",
+        SearchResultKind::Container => "container",
+        SearchResultKind::Item => "item",
+    }
+}
+
+/// Breadcrumb for a room, unit, shelf, container or item, e.g.
+/// Garage > Metal Shelves > Shelf 3 > Tool Box. Paths are cached for a few seconds,
+/// so a move can take that long to show up.
+#[utoipa::path(
+    get,
+    path = "/api/location-path/{entity_type}/{entity_id}",
+    tag = "location",
+    params(
+        ("entity_type" = String, Path, description = "Entity type, e.g. `item`"),
+        ("entity_id" = Uuid, Path, description = "Entity id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = LocationPathResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Entity not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_location_path(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path((entity_type, entity_id)): Path<(String, Uuid)>,
+) -> Result<Json<LocationPathResponse>, ApiError> {
+    let kind = entity_type.parse::<SearchResultKind>().map_err(|e| {
+        tracing::warn!("Invalid location path entity type: {}", e);
+        StatusCode::BAD_REQUEST
+    })?;
+    state
+        .authorize_entity(user_id, kind.into(), entity_id)
+        .await?;
+
+    let path = cached_location_path(&state, kind, entity_id).await?;
+
+    if path.is_empty() {
+        return Err(ApiError::NotFound(format!(
+            "{} with id {} not found",
+            entity_type, entity_id
+        )));
+    }
+
+    Ok(Json(LocationPathResponse { path }))
+}
+
+/// [`resolve_location_path`] through the `location_paths` cache
+pub async fn cached_location_path(
+    state: &AppState,
+    kind: SearchResultKind,
+    id: Uuid,
+) -> Result<Vec<PathNode>, sqlx::Error> {
+    if let Some(path) = state.location_paths.get(&id) {
+        // Ids are unique across tables, but don't answer a room lookup with an item's path
+        if path.last().map(|node| node.kind) == Some(kind) {
+            return Ok(path);
+        }
+    }
+
+    let path = resolve_location_path(&state.db, kind, id).await?;
+    if !path.is_empty() {
+        state.location_paths.insert(id, path.clone());
+    }
+    Ok(path)
+}
+
+/// Create location routes
+pub fn location_routes() -> Router<Arc<AppState>> {
+    use axum::routing::get;
+
+    Router::new().route(
+        "/api/location-path/:entity_type/:entity_id",
+        get(get_location_path),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_pool;
+
+    #[test]
+    fn test_kind_name_round_trips() {
+        for kind in SearchResultKind::ALL {
+            assert_eq!(kind_name(kind).parse::<SearchResultKind>(), Ok(kind));
+        }
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_resolve_location_path_through_nested_containers() {
+        let pool = create_test_pool().await;
+        let user_id = Uuid::new_v4();
+        let (room_id, unit_id, shelf_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let (outer_id, inner_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        sqlx::query("INSERT INTO rooms (id, name, created_by) VALUES ($1, 'Garage', $2)")
+            .bind(room_id)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO shelving_units (id, room_id, name, created_by) VALUES ($1, $2, 'Metal Shelves', $3)",
+        )
+        .bind(unit_id)
+        .bind(room_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO shelves (id, shelving_unit_id, name, position, created_by) VALUES ($1, $2, 'Shelf 3', 3, $3)",
+        )
+        .bind(shelf_id)
+        .bind(unit_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO containers (id, shelf_id, name, created_by) VALUES ($1, $2, 'Tool Box', $3)",
+        )
+        .bind(outer_id)
+        .bind(shelf_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO containers (id, parent_container_id, name, created_by) VALUES ($1, $2, 'Bit Case', $3)",
+        )
+        .bind(inner_id)
+        .bind(outer_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let path = resolve_location_path(&pool, SearchResultKind::Container, inner_id).await;
+        let missing = resolve_location_path(&pool, SearchResultKind::Room, inner_id).await;
+
+        sqlx::query("DELETE FROM containers WHERE id = ANY($1)")
+            .bind(vec![inner_id, outer_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM shelves WHERE id = $1")
+            .bind(shelf_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM shelving_units WHERE id = $1")
+            .bind(unit_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM rooms WHERE id = $1")
+            .bind(room_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let path = path.unwrap();
+        let names: Vec<&str> = path.iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Garage", "Metal Shelves", "Shelf 3", "Tool Box", "Bit Case"]
+        );
+        assert_eq!(path[0].kind, SearchResultKind::Room);
+        assert_eq!(path[4].id, inner_id);
+        assert!(missing.unwrap().is_empty());
+    }
+}
@@ -2,28 +2,46 @@ pub mod audit;
 pub mod auth;
 pub mod contact;
 pub mod containers;
+pub mod events;
+pub mod export;
+pub mod households;
+pub mod import;
 pub mod item_import_drafts;
 pub mod items;
+pub mod label_templates;
 pub mod labels;
+pub mod location;
 pub mod r#move;
 pub mod photos;
 pub mod rooms;
+pub mod search;
 pub mod shelves;
 pub mod shelving_units;
+pub mod stats;
 pub mod tags;
 pub mod users;
+pub mod webhooks;
 
 // Re-export for convenience
 pub use audit::*;
 pub use auth::*;
 pub use containers::*;
+pub use events::*;
+pub use export::*;
+pub use households::*;
+pub use import::*;
 pub use item_import_drafts::*;
 pub use items::*;
+pub use label_templates::*;
 pub use labels::*;
+pub use location::*;
 pub use photos::*;
 pub use r#move::*;
 pub use rooms::*;
+pub use search::*;
 pub use shelves::*;
 pub use shelving_units::*;
+pub use stats::*;
 pub use tags::*;
 pub use users::*;
+pub use webhooks::*;
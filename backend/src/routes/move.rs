@@ -1,61 +1,113 @@
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
     response::Json,
     Router,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
 use crate::middleware::auth::AuthUser;
+use crate::routes::shelving_units::log_capacity_warning;
+use crate::services::audit::{AuditAction, Auditable};
+use crate::services::household::HouseholdEntity;
 use crate::services::r#move as move_service;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct MoveShelvingUnitRequest {
     pub target_room_id: Uuid,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct MoveShelfRequest {
     pub target_unit_id: Uuid,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct MoveContainerRequest {
     pub target_shelf_id: Option<Uuid>,
     pub target_parent_id: Option<Uuid>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct MoveItemRequest {
     pub target_shelf_id: Option<Uuid>,
     pub target_container_id: Option<Uuid>,
 }
 
-#[derive(Debug, Serialize)]
+/// Most items accepted by a bulk move
+pub const MAX_BULK_MOVE_ITEMS: usize = 100;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkMoveItemsRequest {
+    pub item_ids: Vec<Uuid>,
+    pub target_shelf_id: Option<Uuid>,
+    pub target_container_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct MoveResponse {
     pub message: String,
 }
 
+/// 404 unless each target given is in the user's household
+async fn authorize_target(
+    state: &AppState,
+    user_id: Uuid,
+    shelf_id: Option<Uuid>,
+    container_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    if let Some(shelf_id) = shelf_id {
+        state
+            .authorize_entity(user_id, HouseholdEntity::Shelf, shelf_id)
+            .await?;
+    }
+    if let Some(container_id) = container_id {
+        state
+            .authorize_entity(user_id, HouseholdEntity::Container, container_id)
+            .await?;
+    }
+    Ok(())
+}
+
 /// Move a shelving unit to a different room
+#[utoipa::path(
+    post,
+    path = "/api/units/{id}/move",
+    tag = "move",
+    params(
+        ("id" = Uuid, Path, description = "Shelving unit id")
+    ),
+    request_body = MoveShelvingUnitRequest,
+    responses(
+        (status = 200, description = "OK"),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelving unit not found", body = ErrorResponse)
+    )
+)]
 pub async fn move_shelving_unit(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(unit_id): Path<Uuid>,
     Json(payload): Json<MoveShelvingUnitRequest>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::ShelvingUnit, unit_id)
+        .await?;
+    state
+        .authorize_entity(user_id, HouseholdEntity::Room, payload.target_room_id)
+        .await?;
+
     // Get current location for audit
     let current: Option<(Uuid,)> =
         sqlx::query_as("SELECT room_id FROM shelving_units WHERE id = $1")
             .bind(unit_id)
             .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to get shelving unit location: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .await?;
 
     move_service::move_shelving_unit(&state.db, unit_id, payload.target_room_id).await?;
 
@@ -81,25 +133,57 @@ pub async fn move_shelving_unit(
 }
 
 /// Move a shelf to a different shelving unit
+#[utoipa::path(
+    post,
+    path = "/api/shelves/{id}/move",
+    tag = "move",
+    params(
+        ("id" = Uuid, Path, description = "Shelf id")
+    ),
+    request_body = MoveShelfRequest,
+    responses(
+        (status = 200, description = "OK", body = MoveResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelf not found", body = ErrorResponse)
+    )
+)]
 pub async fn move_shelf(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(shelf_id): Path<Uuid>,
     Json(payload): Json<MoveShelfRequest>,
-) -> Result<Json<MoveResponse>, StatusCode> {
+) -> Result<Json<MoveResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Shelf, shelf_id)
+        .await?;
+    state
+        .authorize_entity(
+            user_id,
+            HouseholdEntity::ShelvingUnit,
+            payload.target_unit_id,
+        )
+        .await?;
+
     // Get current location for audit
     let current: Option<(Uuid,)> =
         sqlx::query_as("SELECT shelving_unit_id FROM shelves WHERE id = $1")
             .bind(shelf_id)
             .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to get shelf location: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .await?;
+
+    // The target unit needs room, unless the shelf is already there
+    let capacity = match current {
+        Some((from_unit_id,)) if from_unit_id == payload.target_unit_id => None,
+        _ => Some(move_service::check_unit_capacity(&state.db, payload.target_unit_id).await?),
+    };
 
     move_service::move_shelf(&state.db, shelf_id, payload.target_unit_id).await?;
 
+    if let Some(capacity) = capacity {
+        log_capacity_warning(&state, payload.target_unit_id, user_id, &capacity).await;
+    }
+
     // Log audit
     if let Some((from_unit_id,)) = current {
         state
@@ -122,28 +206,51 @@ pub async fn move_shelf(
 }
 
 /// Move a container to a different location
+#[utoipa::path(
+    post,
+    path = "/api/containers/{id}/move",
+    tag = "move",
+    params(
+        ("id" = Uuid, Path, description = "Container id")
+    ),
+    request_body = MoveContainerRequest,
+    responses(
+        (status = 200, description = "OK", body = MoveResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Container not found", body = ErrorResponse)
+    )
+)]
 pub async fn move_container(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(container_id): Path<Uuid>,
     Json(payload): Json<MoveContainerRequest>,
-) -> Result<Json<MoveResponse>, StatusCode> {
+) -> Result<Json<MoveResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Container, container_id)
+        .await?;
+    authorize_target(
+        &state,
+        user_id,
+        payload.target_shelf_id,
+        payload.target_parent_id,
+    )
+    .await?;
+
     // Get current location for audit
     let current: Option<(Option<Uuid>, Option<Uuid>)> =
         sqlx::query_as("SELECT shelf_id, parent_container_id FROM containers WHERE id = $1")
             .bind(container_id)
             .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to get container location: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .await?;
 
     move_service::move_container(
         &state.db,
         container_id,
         payload.target_shelf_id,
         payload.target_parent_id,
+        state.max_container_depth,
     )
     .await?;
 
@@ -175,22 +282,45 @@ pub async fn move_container(
 }
 
 /// Move an item to a different location
+#[utoipa::path(
+    post,
+    path = "/api/items/{id}/move",
+    tag = "move",
+    params(
+        ("id" = Uuid, Path, description = "Item id")
+    ),
+    request_body = MoveItemRequest,
+    responses(
+        (status = 200, description = "OK", body = MoveResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Item not found", body = ErrorResponse)
+    )
+)]
 pub async fn move_item(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(item_id): Path<Uuid>,
     Json(payload): Json<MoveItemRequest>,
-) -> Result<Json<MoveResponse>, StatusCode> {
+) -> Result<Json<MoveResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Item, item_id)
+        .await?;
+    authorize_target(
+        &state,
+        user_id,
+        payload.target_shelf_id,
+        payload.target_container_id,
+    )
+    .await?;
+
     // Get current location for audit
-    let current: Option<(Option<Uuid>, Option<Uuid>)> =
-        sqlx::query_as("SELECT shelf_id, container_id FROM items WHERE id = $1")
-            .bind(item_id)
-            .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to get item location: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+    let current: Option<(Option<Uuid>, Option<Uuid>)> = sqlx::query_as(
+        "SELECT shelf_id, container_id FROM items WHERE id = $1 AND deleted_at IS NULL",
+    )
+    .bind(item_id)
+    .fetch_optional(&state.db)
+    .await?;
 
     move_service::move_item(
         &state.db,
@@ -227,6 +357,69 @@ pub async fn move_item(
     }))
 }
 
+/// Move up to [`MAX_BULK_MOVE_ITEMS`] items to one location, all or nothing. The
+/// batch is logged as a single `item_batch` audit entry listing the items.
+#[utoipa::path(
+    post,
+    path = "/api/items/bulk-move",
+    tag = "move",
+    request_body = BulkMoveItemsRequest,
+    responses(
+        (status = 200, description = "OK", body = MoveResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn bulk_move_items(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Json(mut payload): Json<BulkMoveItemsRequest>,
+) -> Result<Json<MoveResponse>, ApiError> {
+    if payload.item_ids.is_empty() || payload.item_ids.len() > MAX_BULK_MOVE_ITEMS {
+        return Err(ApiError::bad_request(format!(
+            "Between 1 and {} item_ids are required",
+            MAX_BULK_MOVE_ITEMS
+        )));
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    payload.item_ids.retain(|id| seen.insert(*id));
+
+    let household_id = state.resolve_household(user_id).await?;
+    move_service::move_items(
+        &state.db,
+        household_id,
+        &payload.item_ids,
+        payload.target_shelf_id,
+        payload.target_container_id,
+    )
+    .await?;
+
+    state
+        .audit
+        .log_action(
+            "item_batch",
+            Uuid::new_v4(),
+            AuditAction::Move,
+            Some(user_id),
+            None,
+            Some(serde_json::json!({
+                "batch_size": payload.item_ids.len(),
+                "item_ids": payload.item_ids,
+                "target": {
+                    "shelf_id": payload.target_shelf_id,
+                    "container_id": payload.target_container_id
+                }
+            })),
+        )
+        .await
+        .ok();
+
+    Ok(Json(MoveResponse {
+        message: format!("{} items moved successfully", payload.item_ids.len()),
+    }))
+}
+
 /// Create move routes
 pub fn move_routes() -> Router<Arc<AppState>> {
     use axum::routing::post;
@@ -236,4 +429,5 @@ pub fn move_routes() -> Router<Arc<AppState>> {
         .route("/api/shelves/:id/move", post(move_shelf))
         .route("/api/containers/:id/move", post(move_container))
         .route("/api/items/:id/move", post(move_item))
+        .route("/api/items/bulk-move", post(bulk_move_items))
 }
@@ -5,41 +5,111 @@ use axum::{
     routing::{get, post},
     Router,
 };
+use futures::future::try_join_all;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::sync::Arc;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
 use crate::middleware::auth::AuthUser;
-use crate::models::{CreatePhotoRequest, Photo, PhotoResponse, PresignedUploadUrl};
+use crate::models::{
+    ContainerResponse, CreatePhotoRequest, ItemResponse, Photo, PhotoResponse, PresignedUploadUrl,
+    ShelfResponse, SimilarPhotoResponse, SimilarPhotosQuery, FLOOR_PLAN_ENTITY_TYPE,
+};
+use crate::services::audit::Auditable;
+use crate::services::exif::{extract_photo_exif, has_exif};
+use crate::services::household::{self as household_service, HouseholdEntity};
+use crate::services::phash::{
+    closest_matches, compute_photo_phash, parse_phash, DEFAULT_SIMILARITY_THRESHOLD,
+};
+use crate::services::s3::UPLOAD_URL_EXPIRES_IN_SECS;
+use crate::services::thumbnail::generate_photo_thumbnail;
+use crate::utils::validate_photo_dimensions;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GetPhotosQuery {
     entity_type: String,
     entity_id: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UploadUrlRequest {
     content_type: String,
 }
 
+/// 404 unless the entity photos are (or will be) attached to is in the user's household
+async fn authorize_photo_entity(
+    state: &AppState,
+    user_id: Uuid,
+    entity_type: &str,
+    entity_id: Uuid,
+) -> Result<(), ApiError> {
+    let entity = HouseholdEntity::from_entity_type(entity_type)
+        .ok_or_else(|| ApiError::bad_request(format!("Unknown entity_type: {}", entity_type)))?;
+    state.authorize_entity(user_id, entity, entity_id).await?;
+    Ok(())
+}
+
+/// A photo, if it's attached to an entity of the user's household. Photos of other
+/// households are a 404, like missing ones.
+async fn fetch_authorized_photo(
+    state: &AppState,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<Photo, ApiError> {
+    let photo = sqlx::query_as::<_, Photo>("SELECT * FROM photos WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Photo", id))?;
+
+    let entity = HouseholdEntity::from_entity_type(&photo.entity_type)
+        .ok_or_else(|| ApiError::not_found("Photo", id))?;
+    let household_id = state.resolve_household(user_id).await?;
+    household_service::ensure_in_household(&state.db, household_id, entity, photo.entity_id)
+        .await
+        .map_err(|e| match e {
+            ApiError::NotFound(_) => ApiError::not_found("Photo", id),
+            e => e,
+        })?;
+
+    Ok(photo)
+}
+
 /// Get presigned URL for uploading a photo
+#[utoipa::path(
+    post,
+    path = "/api/photos/upload-url",
+    tag = "photos",
+    params(
+        GetPhotosQuery
+    ),
+    request_body = UploadUrlRequest,
+    responses(
+        (status = 200, description = "OK", body = PresignedUploadUrl),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn get_upload_url(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Query(params): Query<GetPhotosQuery>,
     Json(payload): Json<UploadUrlRequest>,
-) -> Result<Json<PresignedUploadUrl>, StatusCode> {
+) -> Result<Json<PresignedUploadUrl>, ApiError> {
     let entity_id = Uuid::parse_str(&params.entity_id).map_err(|_| {
         tracing::error!("Invalid entity_id: {}", params.entity_id);
         StatusCode::BAD_REQUEST
     })?;
-
-    let content_type = &payload.content_type;
+    authorize_photo_entity(&state, user_id, &params.entity_type, entity_id).await?;
 
     let (upload_url, s3_key) = state
         .s3
-        .generate_presigned_upload_url(&params.entity_type, entity_id, content_type)
+        .generate_presigned_upload_url(&params.entity_type, entity_id, &payload.content_type)
         .await
         .map_err(|e| {
             tracing::error!("Failed to generate presigned URL: {:?}", e);
@@ -49,78 +119,230 @@ pub async fn get_upload_url(
     Ok(Json(PresignedUploadUrl {
         upload_url,
         s3_key,
-        expires_in: 3600, // 1 hour
+        content_type: payload.content_type,
+        expires_in: UPLOAD_URL_EXPIRES_IN_SECS,
     }))
 }
 
-/// Get all photos for an entity
-pub async fn get_photos(
-    State(state): State<Arc<AppState>>,
-    Query(params): Query<GetPhotosQuery>,
-) -> Result<Json<Vec<PhotoResponse>>, StatusCode> {
-    let entity_id = Uuid::parse_str(&params.entity_id).map_err(|_| {
-        tracing::error!("Invalid entity_id: {}", params.entity_id);
-        StatusCode::BAD_REQUEST
-    })?;
-
+/// Fetch all photos for an entity with presigned download URLs
+pub async fn fetch_entity_photos(
+    state: &AppState,
+    entity_type: &str,
+    entity_id: Uuid,
+) -> Result<Vec<PhotoResponse>, ApiError> {
     let photos = sqlx::query_as::<_, Photo>(
         "SELECT * FROM photos WHERE entity_type = $1 AND entity_id = $2 ORDER BY created_at DESC",
     )
-    .bind(&params.entity_type)
+    .bind(entity_type)
     .bind(entity_id)
     .fetch_all(&state.db)
+    .await?;
+
+    // Generate presigned URLs for each photo
+    let mut responses = Vec::new();
+    for photo in photos {
+        responses.push(photo_with_urls(state, photo).await?);
+    }
+
+    Ok(responses)
+}
+
+/// A photo's response with presigned download URLs for the image and thumbnail
+async fn photo_with_urls(state: &AppState, photo: Photo) -> Result<PhotoResponse, ApiError> {
+    let url = state
+        .s3
+        .generate_presigned_download_url(&photo.s3_key)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to generate download URL: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let thumbnail_url = if let Some(ref thumb_key) = photo.thumbnail_s3_key {
+        Some(
+            state
+                .s3
+                .generate_presigned_download_url(thumb_key)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to generate thumbnail URL: {:?}", e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?,
+        )
+    } else {
+        None
+    };
+
+    let mut response: PhotoResponse = photo.into();
+    response.url = url;
+    response.thumbnail_url = thumbnail_url;
+    Ok(response)
+}
+
+/// `?include_photos=true` on list endpoints adds each entity's photo count and
+/// primary photo URL. Off by default since it costs an extra query and a presign
+/// per row.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IncludePhotosQuery {
+    #[serde(default)]
+    pub include_photos: bool,
+}
+
+/// List responses that can carry a photo summary
+pub trait PhotoSummaryTarget {
+    fn entity_id(&self) -> Uuid;
+    fn set_photo_summary(&mut self, photo_count: i32, primary_photo_url: Option<String>);
+}
+
+impl PhotoSummaryTarget for ItemResponse {
+    fn entity_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_photo_summary(&mut self, photo_count: i32, primary_photo_url: Option<String>) {
+        self.photo_count = Some(photo_count);
+        self.primary_photo_url = primary_photo_url;
+    }
+}
+
+impl PhotoSummaryTarget for ContainerResponse {
+    fn entity_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_photo_summary(&mut self, photo_count: i32, primary_photo_url: Option<String>) {
+        self.photo_count = Some(photo_count);
+        self.primary_photo_url = primary_photo_url;
+    }
+}
+
+impl PhotoSummaryTarget for ShelfResponse {
+    fn entity_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_photo_summary(&mut self, photo_count: i32, primary_photo_url: Option<String>) {
+        self.photo_count = Some(photo_count);
+        self.primary_photo_url = primary_photo_url;
+    }
+}
+
+/// Photo count and earliest photo per entity, for a whole page in one query
+const PHOTO_SUMMARIES_SQL: &str = r#"
+    SELECT DISTINCT ON (entity_id)
+        entity_id,
+        COUNT(*) OVER (PARTITION BY entity_id)::INT AS photo_count,
+        s3_key
+    FROM photos
+    WHERE entity_type = $1 AND entity_id = ANY($2)
+    ORDER BY entity_id, created_at, id
+"#;
+
+/// Fill in `photo_count` and `primary_photo_url` on a page of list responses.
+/// Entities without photos get a count of zero.
+pub async fn attach_photo_summaries<T: PhotoSummaryTarget>(
+    state: &AppState,
+    entity_type: &str,
+    responses: &mut [T],
+) -> Result<(), ApiError> {
+    if responses.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<Uuid> = responses
+        .iter()
+        .map(PhotoSummaryTarget::entity_id)
+        .collect();
+    let rows = sqlx::query_as::<_, (Uuid, i32, String)>(PHOTO_SUMMARIES_SQL)
+        .bind(entity_type)
+        .bind(&ids)
+        .fetch_all(&state.db)
+        .await?;
+
+    let urls = try_join_all(
+        rows.iter()
+            .map(|(_, _, s3_key)| state.s3.generate_presigned_download_url(s3_key)),
+    )
     .await
     .map_err(|e| {
-        tracing::error!("Failed to fetch photos: {:?}", e);
+        tracing::error!("Failed to generate primary photo URLs: {:?}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
-    // Generate presigned URLs for each photo
-    let mut responses = Vec::new();
-    for photo in photos {
-        let url = state
-            .s3
-            .generate_presigned_download_url(&photo.s3_key)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to generate download URL: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
-
-        let thumbnail_url = if let Some(ref thumb_key) = photo.thumbnail_s3_key {
-            Some(
-                state
-                    .s3
-                    .generate_presigned_download_url(thumb_key)
-                    .await
-                    .map_err(|e| {
-                        tracing::error!("Failed to generate thumbnail URL: {:?}", e);
-                        StatusCode::INTERNAL_SERVER_ERROR
-                    })?,
-            )
-        } else {
-            None
-        };
+    let mut summaries: HashMap<Uuid, (i32, String)> = rows
+        .into_iter()
+        .zip(urls)
+        .map(|((entity_id, photo_count, _), url)| (entity_id, (photo_count, url)))
+        .collect();
 
-        let mut response: PhotoResponse = photo.into();
-        response.url = url;
-        response.thumbnail_url = thumbnail_url;
-        responses.push(response);
+    for response in responses.iter_mut() {
+        match summaries.remove(&response.entity_id()) {
+            Some((photo_count, url)) => response.set_photo_summary(photo_count, Some(url)),
+            None => response.set_photo_summary(0, None),
+        }
     }
 
+    Ok(())
+}
+
+/// Get all photos for an entity
+#[utoipa::path(
+    get,
+    path = "/api/photos",
+    tag = "photos",
+    params(
+        GetPhotosQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<PhotoResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn get_photos(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<GetPhotosQuery>,
+) -> Result<Json<Vec<PhotoResponse>>, ApiError> {
+    let entity_id = Uuid::parse_str(&params.entity_id).map_err(|_| {
+        tracing::error!("Invalid entity_id: {}", params.entity_id);
+        StatusCode::BAD_REQUEST
+    })?;
+    authorize_photo_entity(&state, user_id, &params.entity_type, entity_id).await?;
+
+    let responses = fetch_entity_photos(&state, &params.entity_type, entity_id).await?;
     Ok(Json(responses))
 }
 
 /// Create a photo record after successful upload to S3
+#[utoipa::path(
+    post,
+    path = "/api/photos",
+    tag = "photos",
+    request_body = CreatePhotoRequest,
+    responses(
+        (status = 200, description = "OK", body = PhotoResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn create_photo(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Json(payload): Json<CreatePhotoRequest>,
-) -> Result<Json<PhotoResponse>, StatusCode> {
+) -> Result<Json<PhotoResponse>, ApiError> {
     let entity_id = Uuid::parse_str(&payload.entity_id).map_err(|_| {
         tracing::error!("Invalid entity_id: {}", payload.entity_id);
-        StatusCode::BAD_REQUEST
+        ApiError::bad_request(format!("Invalid entity_id: {}", payload.entity_id))
     })?;
+    authorize_photo_entity(&state, user_id, &payload.entity_type, entity_id).await?;
+
+    validate_photo_dimensions(
+        Some(payload.file_size.into()),
+        payload.width,
+        payload.height,
+    )
+    .inspect_err(|e| tracing::warn!("Invalid photo metadata: {}", e))?;
 
     let photo = sqlx::query_as::<_, Photo>(
         r#"
@@ -140,11 +362,44 @@ pub async fn create_photo(
     .bind(payload.height)
     .bind(user_id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to create photo: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
+
+    // Hash the image in the background so near-duplicates can be found later
+    if photo.content_type.starts_with("image/") {
+        let state = state.clone();
+        let photo = photo.clone();
+        tokio::spawn(async move {
+            if let Err(e) = compute_photo_phash(&state.db, &state.s3, &photo).await {
+                tracing::warn!("Failed to hash photo {}: {:?}", photo.id, e);
+            }
+        });
+    }
+
+    // Read the camera's EXIF (capture time, position) in the background
+    if has_exif(&photo.content_type) {
+        let state = state.clone();
+        let photo = photo.clone();
+        tokio::spawn(async move {
+            if let Err(e) = extract_photo_exif(&state.db, &state.s3, &photo).await {
+                tracing::warn!("Failed to read EXIF of photo {}: {:?}", photo.id, e);
+            }
+        });
+    }
+
+    // Build a thumbnail in the background unless the client uploaded one
+    if photo.thumbnail_s3_key.is_none() && photo.content_type.starts_with("image/") {
+        let state = state.clone();
+        let photo = photo.clone();
+        tokio::spawn(async move {
+            if let Err(e) = generate_photo_thumbnail(&state.db, &state.s3, &photo).await {
+                tracing::warn!(
+                    "Failed to generate thumbnail for photo {}: {:?}",
+                    photo.id,
+                    e
+                );
+            }
+        });
+    }
 
     // Generate presigned URLs
     let url = state
@@ -179,20 +434,26 @@ pub async fn create_photo(
 }
 
 /// Delete a photo
+#[utoipa::path(
+    delete,
+    path = "/api/photos/{id}",
+    tag = "photos",
+    params(
+        ("id" = Uuid, Path, description = "Photo id")
+    ),
+    responses(
+        (status = 200, description = "OK"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Photo not found", body = ErrorResponse)
+    )
+)]
 pub async fn delete_photo(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     // Get photo to find S3 keys
-    let photo = sqlx::query_as::<_, Photo>("SELECT * FROM photos WHERE id = $1")
-        .bind(id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch photo: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+    let photo = fetch_authorized_photo(&state, user_id, id).await?;
 
     // Delete from S3
     state.s3.delete_file(&photo.s3_key).await.map_err(|e| {
@@ -208,35 +469,61 @@ pub async fn delete_photo(
     let result = sqlx::query("DELETE FROM photos WHERE id = $1")
         .bind(id)
         .execute(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete photo: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
     if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found("Photo", id));
     }
 
+    // A deleted floor plan leaves its room without one
+    if photo.entity_type == FLOOR_PLAN_ENTITY_TYPE {
+        sqlx::query(
+            "UPDATE rooms SET floor_plan_photo_id = NULL, updated_at = NOW() WHERE floor_plan_photo_id = $1",
+        )
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+    }
+
+    state
+        .audit
+        .log_delete(
+            "photo",
+            id,
+            Some(user_id),
+            Some(serde_json::json!({
+                "entity_type": photo.entity_type,
+                "entity_id": photo.entity_id,
+            })),
+        )
+        .await
+        .ok();
+
     Ok(Json(
         serde_json::json!({ "message": "Photo deleted successfully" }),
     ))
 }
 
 /// Get a single photo by ID
+#[utoipa::path(
+    get,
+    path = "/api/photos/{id}",
+    tag = "photos",
+    params(
+        ("id" = Uuid, Path, description = "Photo id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = PhotoResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Photo not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_photo(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<PhotoResponse>, StatusCode> {
-    let photo = sqlx::query_as::<_, Photo>("SELECT * FROM photos WHERE id = $1")
-        .bind(id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch photo: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+) -> Result<Json<PhotoResponse>, ApiError> {
+    let photo = fetch_authorized_photo(&state, user_id, id).await?;
 
     // Generate presigned URL
     let url = state
@@ -262,6 +549,7 @@ pub async fn get_photo(
     } else {
         None
     };
+    let exif_data = photo.exif();
 
     Ok(Json(PhotoResponse {
         id: photo.id.to_string(),
@@ -274,13 +562,185 @@ pub async fn get_photo(
         width: photo.width,
         height: photo.height,
         created_at: photo.created_at.to_rfc3339(),
+        exif_data,
+        lat: photo.lat,
+        lng: photo.lng,
     }))
 }
 
+/// Build (or rebuild) a photo's thumbnail from the full image: a JPEG fitting
+/// within 400x400, stored under `{entity_type}/{entity_id}/thumbs/`
+#[utoipa::path(
+    post,
+    path = "/api/photos/{id}/generate-thumbnail",
+    tag = "photos",
+    params(
+        ("id" = Uuid, Path, description = "Photo id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = PhotoResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Photo not found", body = ErrorResponse)
+    )
+)]
+pub async fn generate_thumbnail(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PhotoResponse>, ApiError> {
+    let photo = fetch_authorized_photo(&state, user_id, id).await?;
+
+    if !photo.content_type.starts_with("image/") {
+        return Err(ApiError::UnprocessableEntity(format!(
+            "Cannot make a thumbnail of a {} file",
+            photo.content_type
+        )));
+    }
+
+    generate_photo_thumbnail(&state.db, &state.s3, &photo)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to generate thumbnail for photo {}: {:?}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    get_photo(State(state), AuthUser(user_id), Path(id)).await
+}
+
+/// Read (or re-read) a photo's EXIF from the full image and store its camera, capture
+/// time, orientation and GPS position. A photo without EXIF is returned unchanged.
+#[utoipa::path(
+    post,
+    path = "/api/photos/{id}/extract-exif",
+    tag = "photos",
+    params(
+        ("id" = Uuid, Path, description = "Photo id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = PhotoResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Photo not found", body = ErrorResponse),
+        (status = 422, description = "Photo is not an image", body = ErrorResponse)
+    )
+)]
+pub async fn extract_exif(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PhotoResponse>, ApiError> {
+    let photo = fetch_authorized_photo(&state, user_id, id).await?;
+
+    if !photo.content_type.starts_with("image/") {
+        return Err(ApiError::UnprocessableEntity(format!(
+            "Cannot read EXIF from a {} file",
+            photo.content_type
+        )));
+    }
+
+    extract_photo_exif(&state.db, &state.s3, &photo)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read EXIF of photo {}: {:?}", id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    get_photo(State(state), AuthUser(user_id), Path(id)).await
+}
+
+/// Most photos returned by the similar photos endpoint
+const MAX_SIMILAR_PHOTOS: usize = 50;
+
+/// Photos that look like this one: those whose perceptual hash is within
+/// `threshold` bits of its hash, closest first
+#[utoipa::path(
+    get,
+    path = "/api/photos/{id}/similar",
+    tag = "photos",
+    params(
+        ("id" = Uuid, Path, description = "Photo id"),
+        SimilarPhotosQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<SimilarPhotoResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Photo not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_similar_photos(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<SimilarPhotosQuery>,
+) -> Result<Json<Vec<SimilarPhotoResponse>>, ApiError> {
+    let threshold = params.threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+    if threshold > 64 {
+        return Err(ApiError::bad_request("threshold must be between 0 and 64"));
+    }
+
+    let photo = fetch_authorized_photo(&state, user_id, id).await?;
+
+    let target = photo
+        .phash
+        .as_deref()
+        .and_then(parse_phash)
+        .ok_or_else(|| {
+            ApiError::UnprocessableEntity(format!("Photo {} has not been hashed yet", id))
+        })?;
+
+    let household_id = state.resolve_household(user_id).await?;
+    let hashes = sqlx::query_as::<_, (Uuid, String)>(&format!(
+        "SELECT p.id, p.phash FROM photos p WHERE p.phash IS NOT NULL AND p.id <> $1 AND {}",
+        household_service::attached_in_household_sql("p", 2)
+    ))
+    .bind(id)
+    .bind(household_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let matches = closest_matches(
+        target,
+        hashes
+            .into_iter()
+            .filter_map(|(photo_id, phash)| Some((photo_id, parse_phash(&phash)?))),
+        threshold,
+        MAX_SIMILAR_PHOTOS,
+    );
+    let ids: Vec<Uuid> = matches.iter().map(|(photo_id, _)| *photo_id).collect();
+
+    let mut photos: HashMap<Uuid, Photo> =
+        sqlx::query_as::<_, Photo>("SELECT * FROM photos WHERE id = ANY($1)")
+            .bind(&ids)
+            .fetch_all(&state.db)
+            .await?
+            .into_iter()
+            .map(|photo| (photo.id, photo))
+            .collect();
+
+    let mut responses = Vec::with_capacity(matches.len());
+    for (photo_id, distance) in matches {
+        // Deleted between the two queries
+        let Some(photo) = photos.remove(&photo_id) else {
+            continue;
+        };
+        responses.push(SimilarPhotoResponse {
+            photo: photo_with_urls(&state, photo).await?,
+            distance,
+        });
+    }
+
+    Ok(Json(responses))
+}
+
 /// Create photo routes
 pub fn photo_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/api/photos/upload-url", post(get_upload_url))
         .route("/api/photos", get(get_photos).post(create_photo))
         .route("/api/photos/:id", get(get_photo).delete(delete_photo))
+        .route("/api/photos/:id/similar", get(get_similar_photos))
+        .route(
+            "/api/photos/:id/generate-thumbnail",
+            post(generate_thumbnail),
+        )
+        .route("/api/photos/:id/extract-exif", post(extract_exif))
 }
@@ -1,49 +1,112 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{DefaultBodyLimit, FromRequest, Multipart, Path, Query, Request, State},
+    http::{header::CONTENT_TYPE, StatusCode},
     response::Json,
     Router,
 };
 use serde_json::json;
+use sqlx::FromRow;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
 use crate::middleware::auth::AuthUser;
+use crate::middleware::deprecation::patch_with_put_alias;
 use crate::models::{
-    CreateRoomRequest, PaginatedResponse, PaginationQuery, Room, RoomResponse, UpdateRoomRequest,
+    CascadeDeleteQuery, CascadeDeleteResponse, CreateRoomRequest, PaginatedResponse,
+    PaginationQuery, Photo, PhotoResponse, Room, RoomResponse, SetFloorPlanRequest,
+    UpdateRoomRequest, FLOOR_PLAN_ENTITY_TYPE,
 };
+use crate::routes::photos::fetch_entity_photos;
+use crate::services::audit::Auditable;
+use crate::services::cascade::{delete_location, CascadeRoot};
+use crate::services::household::HouseholdEntity;
+use crate::services::s3::photo_s3_key;
 
-/// Get all rooms
+/// Largest floor plan image accepted as an upload
+const MAX_FLOOR_PLAN_BYTES: usize = 20 * 1024 * 1024;
+
+/// A room with the S3 key of its floor plan, if it has one
+#[derive(FromRow)]
+struct RoomWithFloorPlan {
+    #[sqlx(flatten)]
+    room: Room,
+    floor_plan_s3_key: Option<String>,
+}
+
+const GET_ROOM_WITH_FLOOR_PLAN_SQL: &str = r#"
+    SELECT r.*, p.s3_key AS floor_plan_s3_key
+    FROM rooms r
+    LEFT JOIN photos p ON p.id = r.floor_plan_photo_id
+    WHERE r.id = $1
+"#;
+
+/// `room` as a response, with a presigned URL for the floor plan stored at
+/// `floor_plan_s3_key`
+async fn room_response(
+    state: &AppState,
+    room: Room,
+    floor_plan_s3_key: Option<&str>,
+) -> Result<RoomResponse, ApiError> {
+    let mut response = RoomResponse::from(room);
+    if let Some(s3_key) = floor_plan_s3_key {
+        let url = state
+            .s3
+            .generate_presigned_download_url(s3_key)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to generate floor plan URL: {:?}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        response.floor_plan_url = Some(url);
+    }
+    Ok(response)
+}
+
+/// Get all rooms in the user's household
+#[utoipa::path(
+    get,
+    path = "/api/rooms",
+    tag = "rooms",
+    params(
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<RoomResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn list_rooms(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<RoomResponse>>, StatusCode> {
+) -> Result<Json<PaginatedResponse<RoomResponse>>, ApiError> {
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
+    let household_id = state.resolve_household(user_id).await?;
+    let search_pattern = params.search_pattern();
 
-    // Get total count
-    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rooms")
-        .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count rooms: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    // Get total count with search filter
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM rooms WHERE household_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2)",
+    )
+    .bind(household_id)
+    .bind(&search_pattern)
+    .fetch_one(&state.db)
+    .await?;
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
-    // Get paginated rooms
+    // Get paginated rooms with search filter
     let rooms = sqlx::query_as::<_, Room>(
-        "SELECT * FROM rooms ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+        "SELECT * FROM rooms WHERE household_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2) ORDER BY created_at DESC LIMIT $3 OFFSET $4",
     )
+    .bind(household_id)
+    .bind(&search_pattern)
     .bind(limit)
     .bind(offset)
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch rooms: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     let responses: Vec<RoomResponse> = rooms.into_iter().map(RoomResponse::from).collect();
     Ok(Json(PaginatedResponse::new(
@@ -52,33 +115,60 @@ pub async fn list_rooms(
 }
 
 /// Get a single room by ID
+#[utoipa::path(
+    get,
+    path = "/api/rooms/{id}",
+    tag = "rooms",
+    params(
+        ("id" = Uuid, Path, description = "Room id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = RoomResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Room not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_room(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<RoomResponse>, StatusCode> {
-    let room = sqlx::query_as::<_, Room>("SELECT * FROM rooms WHERE id = $1")
+) -> Result<Json<RoomResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Room, id)
+        .await?;
+    let row = sqlx::query_as::<_, RoomWithFloorPlan>(GET_ROOM_WITH_FLOOR_PLAN_SQL)
         .bind(id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch room: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::not_found("Room", id))?;
 
-    Ok(Json(RoomResponse::from(room)))
+    let response = room_response(&state, row.room, row.floor_plan_s3_key.as_deref()).await?;
+    Ok(Json(response))
 }
 
 /// Create a new room
+#[utoipa::path(
+    post,
+    path = "/api/rooms",
+    tag = "rooms",
+    request_body = CreateRoomRequest,
+    responses(
+        (status = 200, description = "OK", body = RoomResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn create_room(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Json(payload): Json<CreateRoomRequest>,
-) -> Result<Json<RoomResponse>, StatusCode> {
+) -> Result<Json<RoomResponse>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+
     let room = sqlx::query_as::<_, Room>(
         r#"
-        INSERT INTO rooms (id, name, description, created_by)
-        VALUES ($1, $2, $3, $4)
+        INSERT INTO rooms (id, name, description, created_by, household_id)
+        VALUES ($1, $2, $3, $4, $5)
         RETURNING *
         "#,
     )
@@ -86,12 +176,9 @@ pub async fn create_room(
     .bind(&payload.name)
     .bind(&payload.description)
     .bind(user_id)
+    .bind(household_id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to create room: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     // Log audit
     state
@@ -104,22 +191,36 @@ pub async fn create_room(
 }
 
 /// Update a room
+#[utoipa::path(
+    patch,
+    path = "/api/rooms/{id}",
+    tag = "rooms",
+    params(
+        ("id" = Uuid, Path, description = "Room id")
+    ),
+    request_body = UpdateRoomRequest,
+    responses(
+        (status = 200, description = "OK", body = RoomResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Room not found", body = ErrorResponse)
+    )
+)]
 pub async fn update_room(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateRoomRequest>,
-) -> Result<Json<RoomResponse>, StatusCode> {
+) -> Result<Json<RoomResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Room, id)
+        .await?;
     // Check if room exists
     let existing = sqlx::query_as::<_, Room>("SELECT * FROM rooms WHERE id = $1")
         .bind(id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch room: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::not_found("Room", id))?;
 
     // Track changes for audit before consuming payload
     let mut changes = serde_json::Map::new();
@@ -162,11 +263,7 @@ pub async fn update_room(
     .bind(&description)
     .bind(id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to update room: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     // Log audit
     if !changes.is_empty() {
@@ -186,44 +283,531 @@ pub async fn update_room(
     Ok(Json(RoomResponse::from(room)))
 }
 
-/// Delete a room
+/// Delete a room; with `?cascade=true` everything inside it is deleted too
+#[utoipa::path(
+    delete,
+    path = "/api/rooms/{id}",
+    tag = "rooms",
+    params(
+        ("id" = Uuid, Path, description = "Room id"),
+        CascadeDeleteQuery
+    ),
+    responses(
+        (status = 200, description = "What was deleted", body = CascadeDeleteResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 403, description = "Room contains items someone else created", body = ErrorResponse),
+        (status = 404, description = "Room not found", body = ErrorResponse),
+        (status = 409, description = "Room is not empty and cascade wasn't requested", body = ErrorResponse),
+        (status = 422, description = "Room holds too much to delete in one transaction", body = ErrorResponse)
+    )
+)]
 pub async fn delete_room(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    // Log audit before deletion
+    Query(params): Query<CascadeDeleteQuery>,
+) -> Result<Json<CascadeDeleteResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Room, id)
+        .await?;
+    let deleted = delete_location(
+        &state.db,
+        &state.s3,
+        user_id,
+        CascadeRoot::Room(id),
+        params.cascade,
+    )
+    .await?;
+
+    let metadata = params.cascade.then(|| json!({ "cascade": deleted }));
     state
         .audit
-        .log_delete("room", id, Some(user_id), None)
+        .log_delete("room", id, Some(user_id), metadata)
         .await
         .ok();
 
-    let result = sqlx::query("DELETE FROM rooms WHERE id = $1")
-        .bind(id)
-        .execute(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete room: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
+    Ok(Json(CascadeDeleteResponse { deleted }))
+}
+
+/// Get photos for a room
+#[utoipa::path(
+    get,
+    path = "/api/rooms/{id}/photos",
+    tag = "rooms",
+    params(
+        ("id" = Uuid, Path, description = "Room id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<PhotoResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Room not found", body = ErrorResponse)
+    )
+)]
+pub async fn list_room_photos(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<PhotoResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Room, id)
+        .await?;
+    let photos = fetch_entity_photos(&state, "room", id).await?;
+    Ok(Json(photos))
+}
+
+/// Store an uploaded floor plan image from the `file` field of `multipart`
+async fn upload_floor_plan(
+    state: &AppState,
+    user_id: Uuid,
+    room_id: Uuid,
+    mut multipart: Multipart,
+) -> Result<Photo, ApiError> {
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        tracing::warn!("Invalid multipart upload: {:?}", e);
+        ApiError::bad_request("Invalid multipart upload")
+    })? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let content_type = field.content_type().unwrap_or_default().to_string();
+        if !content_type.starts_with("image/") {
+            return Err(ApiError::bad_request("Floor plan must be an image"));
+        }
+        let bytes = field.bytes().await.map_err(|e| {
+            tracing::warn!("Failed to read uploaded file: {:?}", e);
+            ApiError::bad_request("Failed to read uploaded file")
         })?;
+        // The body limit keeps this far below i32::MAX
+        let file_size = bytes.len() as i32;
+
+        let s3_key = photo_s3_key(FLOOR_PLAN_ENTITY_TYPE, room_id, &content_type);
+        state
+            .s3
+            .put_object_bytes(&s3_key, bytes.to_vec(), &content_type)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to upload floor plan: {:?}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
 
-    if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        let photo = sqlx::query_as::<_, Photo>(
+            r#"
+            INSERT INTO photos (id, entity_type, entity_id, s3_key, content_type, file_size, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(FLOOR_PLAN_ENTITY_TYPE)
+        .bind(room_id)
+        .bind(&s3_key)
+        .bind(&content_type)
+        .bind(file_size)
+        .bind(user_id)
+        .fetch_one(&state.db)
+        .await?;
+
+        return Ok(photo);
     }
 
-    Ok(Json(json!({ "message": "Room deleted successfully" })))
+    Err(ApiError::bad_request("A file field is required"))
+}
+
+/// Turn one of the room's photos into its floor plan
+async fn adopt_floor_plan(
+    state: &AppState,
+    room_id: Uuid,
+    photo_id: Uuid,
+) -> Result<Photo, ApiError> {
+    let photo = sqlx::query_as::<_, Photo>("SELECT * FROM photos WHERE id = $1")
+        .bind(photo_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Photo", photo_id))?;
+
+    let is_room_photo = photo.entity_type == "room" || photo.entity_type == FLOOR_PLAN_ENTITY_TYPE;
+    if !is_room_photo || photo.entity_id != room_id {
+        return Err(ApiError::bad_request(format!(
+            "Photo with id {} is not a photo of this room",
+            photo_id
+        )));
+    }
+
+    let photo =
+        sqlx::query_as::<_, Photo>("UPDATE photos SET entity_type = $1 WHERE id = $2 RETURNING *")
+            .bind(FLOOR_PLAN_ENTITY_TYPE)
+            .bind(photo_id)
+            .fetch_one(&state.db)
+            .await?;
+
+    Ok(photo)
+}
+
+/// Delete a floor plan that's been replaced or removed, with its files. Failures are
+/// only logged: the room no longer points at it either way.
+async fn delete_floor_plan_photo(state: &AppState, photo_id: Uuid) {
+    let deleted = sqlx::query_as::<_, Photo>("DELETE FROM photos WHERE id = $1 RETURNING *")
+        .bind(photo_id)
+        .fetch_optional(&state.db)
+        .await;
+
+    match deleted {
+        Ok(Some(photo)) => {
+            if let Err(e) = state.s3.delete_file(&photo.s3_key).await {
+                tracing::warn!("Failed to delete floor plan {} from S3: {:?}", photo_id, e);
+            }
+            if let Some(ref thumb_key) = photo.thumbnail_s3_key {
+                state.s3.delete_file(thumb_key).await.ok(); // Ignore errors for thumbnails
+            }
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!("Failed to delete floor plan {}: {:?}", photo_id, e),
+    }
+}
+
+/// Set a room's floor plan, replacing (and deleting) any previous one. Send either a
+/// `multipart/form-data` upload with the image as `file`, or JSON naming one of the
+/// room's photos, which then moves out of the room's photos.
+#[utoipa::path(
+    post,
+    path = "/api/rooms/{id}/floor-plan",
+    tag = "rooms",
+    params(
+        ("id" = Uuid, Path, description = "Room id")
+    ),
+    request_body(
+        content = SetFloorPlanRequest,
+        description = "JSON with an existing photo id, or a multipart upload with the image as `file`"
+    ),
+    responses(
+        (status = 200, description = "OK", body = RoomResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Room or photo not found", body = ErrorResponse)
+    )
+)]
+pub async fn set_room_floor_plan(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    request: Request,
+) -> Result<Json<RoomResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Room, id)
+        .await?;
+    let existing = sqlx::query_as::<_, Room>("SELECT * FROM rooms WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Room", id))?;
+
+    let is_upload = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("multipart/form-data"));
+
+    let photo = if is_upload {
+        let multipart = Multipart::from_request(request, &state)
+            .await
+            .map_err(|e| ApiError::bad_request(e.body_text()))?;
+        upload_floor_plan(&state, user_id, id, multipart).await?
+    } else {
+        let Json(payload) = Json::<SetFloorPlanRequest>::from_request(request, &state)
+            .await
+            .map_err(|e| ApiError::bad_request(e.body_text()))?;
+        adopt_floor_plan(&state, id, payload.photo_id).await?
+    };
+
+    let room = sqlx::query_as::<_, Room>(
+        r#"
+        UPDATE rooms
+        SET floor_plan_photo_id = $1, updated_at = NOW()
+        WHERE id = $2
+        RETURNING *
+        "#,
+    )
+    .bind(photo.id)
+    .bind(id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if let Some(previous_id) = existing.floor_plan_photo_id.filter(|&p| p != photo.id) {
+        delete_floor_plan_photo(&state, previous_id).await;
+    }
+
+    state
+        .audit
+        .log_update(
+            "room",
+            id,
+            Some(user_id),
+            json!({
+                "floor_plan_photo_id": {
+                    "from": existing.floor_plan_photo_id,
+                    "to": photo.id,
+                }
+            }),
+            None,
+        )
+        .await
+        .ok();
+
+    Ok(Json(
+        room_response(&state, room, Some(&photo.s3_key)).await?,
+    ))
+}
+
+/// Remove a room's floor plan, deleting its photo
+#[utoipa::path(
+    delete,
+    path = "/api/rooms/{id}/floor-plan",
+    tag = "rooms",
+    params(
+        ("id" = Uuid, Path, description = "Room id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = RoomResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Room not found or has no floor plan", body = ErrorResponse)
+    )
+)]
+pub async fn remove_room_floor_plan(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RoomResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Room, id)
+        .await?;
+    let existing = sqlx::query_as::<_, Room>("SELECT * FROM rooms WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Room", id))?;
+
+    let photo_id = existing
+        .floor_plan_photo_id
+        .ok_or_else(|| ApiError::NotFound(format!("Room with id {} has no floor plan", id)))?;
+
+    let room = sqlx::query_as::<_, Room>(
+        r#"
+        UPDATE rooms
+        SET floor_plan_photo_id = NULL, updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .fetch_one(&state.db)
+    .await?;
+
+    delete_floor_plan_photo(&state, photo_id).await;
+
+    state
+        .audit
+        .log_update(
+            "room",
+            id,
+            Some(user_id),
+            json!({
+                "floor_plan_photo_id": {
+                    "from": photo_id,
+                    "to": null,
+                }
+            }),
+            None,
+        )
+        .await
+        .ok();
+
+    Ok(Json(RoomResponse::from(room)))
 }
 
 /// Create room routes
 pub fn room_routes() -> Router<Arc<AppState>> {
     #[allow(unused_imports)]
-    use axum::routing::{delete, get, post, put};
+    use axum::routing::{delete, get, post};
 
     Router::new()
         .route("/api/rooms", get(list_rooms).post(create_room))
         .route(
             "/api/rooms/:id",
-            get(get_room).put(update_room).delete(delete_room),
+            get(get_room)
+                .merge(patch_with_put_alias(update_room))
+                .delete(delete_room),
+        )
+        .route("/api/rooms/:id/photos", get(list_room_photos))
+        .route(
+            "/api/rooms/:id/floor-plan",
+            post(set_room_floor_plan)
+                .delete(remove_room_floor_plan)
+                .layer(DefaultBodyLimit::max(MAX_FLOOR_PLAN_BYTES)),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_pool, test_app_state};
+    use axum::body::Body;
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_list_rooms_search_filters_name_and_description() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+
+        let rooms = [
+            ("Garage", None),
+            ("Attic", Some("Above the garage")),
+            ("Kitchen", None),
+        ];
+        for (name, description) in rooms {
+            sqlx::query(
+                "INSERT INTO rooms (id, name, description, created_by, household_id) VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(name)
+            .bind(description)
+            .bind(user_id)
+            .bind(household_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let list = |search: Option<&str>| {
+            list_rooms(
+                State(state.clone()),
+                AuthUser(user_id),
+                Query(PaginationQuery {
+                    search: search.map(str::to_string),
+                    ..Default::default()
+                }),
+            )
+        };
+        let everything = list(Some("")).await;
+        let garage = list(Some("GARAGE")).await;
+
+        sqlx::query("DELETE FROM rooms WHERE household_id = $1")
+            .bind(household_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let everything = everything.unwrap().0;
+        assert_eq!(everything.total, 3);
+        assert_eq!(everything.data.len(), 3);
+
+        let garage = garage.unwrap().0;
+        let mut names: Vec<String> = garage.data.into_iter().map(|r| r.name).collect();
+        names.sort();
+        assert_eq!(garage.total, 2);
+        assert_eq!(names, vec!["Attic", "Garage"]);
+    }
+
+    fn floor_plan_request(photo_id: Uuid) -> Request {
+        axum::http::Request::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(json!({ "photo_id": photo_id }).to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_room_floor_plan_from_existing_photo() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let user_id = Uuid::new_v4();
+        let room_id = Uuid::new_v4();
+        let photo_id = Uuid::new_v4();
+        let other_photo_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO rooms (id, name, created_by, household_id) VALUES ($1, 'Floor plan room', $2, $3)",
+        )
+        .bind(room_id)
+        .bind(user_id)
+        .bind(household_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, entity_type, entity_id) in [
+            (photo_id, "room", room_id),
+            (other_photo_id, "item", Uuid::new_v4()),
+        ] {
+            sqlx::query(
+                "INSERT INTO photos (id, entity_type, entity_id, s3_key, content_type, file_size, created_by) VALUES ($1, $2, $3, $4, 'image/png', 1, $5)",
+            )
+            .bind(id)
+            .bind(entity_type)
+            .bind(entity_id)
+            .bind(format!("{}/{}/{}.png", entity_type, entity_id, id))
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let set = set_room_floor_plan(
+            State(state.clone()),
+            AuthUser(user_id),
+            Path(room_id),
+            floor_plan_request(photo_id),
         )
+        .await;
+        let fetched = get_room(State(state.clone()), AuthUser(user_id), Path(room_id)).await;
+        let room_photos =
+            list_room_photos(State(state.clone()), AuthUser(user_id), Path(room_id)).await;
+        let foreign = set_room_floor_plan(
+            State(state.clone()),
+            AuthUser(user_id),
+            Path(room_id),
+            floor_plan_request(other_photo_id),
+        )
+        .await;
+        let removed =
+            remove_room_floor_plan(State(state.clone()), AuthUser(user_id), Path(room_id)).await;
+        let photo_left: Option<Uuid> = sqlx::query_scalar("SELECT id FROM photos WHERE id = $1")
+            .bind(photo_id)
+            .fetch_optional(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("DELETE FROM photos WHERE id = ANY($1)")
+            .bind(vec![photo_id, other_photo_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM rooms WHERE id = $1")
+            .bind(room_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let set = set.unwrap().0;
+        assert_eq!(set.floor_plan_photo_id, Some(photo_id));
+        assert!(set.floor_plan_url.is_some());
+
+        let fetched = fetched.unwrap().0;
+        assert_eq!(fetched.floor_plan_photo_id, Some(photo_id));
+        assert!(fetched
+            .floor_plan_url
+            .unwrap()
+            .contains(&format!("room/{}/{}.png", room_id, photo_id)));
+
+        // The floor plan is no longer one of the room's regular photos
+        assert!(room_photos.unwrap().0.is_empty());
+        assert!(matches!(foreign, Err(ApiError::BadRequest(_))));
+
+        let removed = removed.unwrap().0;
+        assert_eq!(removed.floor_plan_photo_id, None);
+        assert_eq!(removed.floor_plan_url, None);
+        assert_eq!(photo_left, None);
+    }
 }
@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json,
+    Router,
+};
+use std::sync::Arc;
+
+use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::{parse_search_kinds, SearchQuery, SearchResponse};
+use crate::services::search;
+
+/// Search rooms, units, shelves, containers and items by name in one request
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "search",
+    params(
+        SearchQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = SearchResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn search_all(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<SearchQuery>,
+) -> Result<Json<SearchResponse>, ApiError> {
+    let term = params.q.trim();
+    if term.is_empty() {
+        return Err(ApiError::bad_request("Search term must not be empty"));
+    }
+
+    let kinds = parse_search_kinds(params.types.as_deref()).map_err(|e| {
+        tracing::warn!("Invalid search types: {}", e);
+        StatusCode::BAD_REQUEST
+    })?;
+    let limit = params.limit.unwrap_or(20).clamp(1, 100);
+
+    let household_id = state.resolve_household(user_id).await?;
+    let results = search::search(&state.db, household_id, term, &kinds, limit.into()).await?;
+
+    Ok(Json(SearchResponse { results }))
+}
+
+/// Create search routes
+pub fn search_routes() -> Router<Arc<AppState>> {
+    use axum::routing::get;
+
+    Router::new().route("/api/search", get(search_all))
+}
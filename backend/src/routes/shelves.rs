@@ -1,63 +1,155 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::Json,
     Router,
 };
 use serde_json::json;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
 use crate::middleware::auth::AuthUser;
+use crate::middleware::deprecation::patch_with_put_alias;
 use crate::models::{
-    CreateShelfRequest, PaginatedResponse, PaginationQuery, Shelf, ShelfResponse,
+    CascadeDeleteQuery, CascadeDeleteResponse, CreateShelfRequest, PaginatedResponse,
+    PaginationQuery, PhotoResponse, ReorderShelvesRequest, Shelf, ShelfCountsQuery, ShelfResponse,
     UpdateShelfRequest,
 };
+use crate::routes::photos::{attach_photo_summaries, fetch_entity_photos, IncludePhotosQuery};
+use crate::routes::shelving_units::log_capacity_warning;
+use crate::services::audit::Auditable;
+use crate::services::cascade::{delete_location, CascadeRoot};
+use crate::services::household::HouseholdEntity;
+use crate::services::r#move as move_service;
 
-/// Get all shelves
+/// Shelves with an explicit position come first; unpositioned (NULL) shelves sort last
+const LIST_SHELVES_BY_UNIT_SQL: &str = "SELECT * FROM shelves WHERE shelving_unit_id = $1 ORDER BY position ASC NULLS LAST, created_at LIMIT $2 OFFSET $3";
+
+/// Get all shelves in the user's household
+#[utoipa::path(
+    get,
+    path = "/api/shelves",
+    tag = "shelves",
+    params(
+        PaginationQuery,
+        IncludePhotosQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ShelfResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn list_shelves(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ShelfResponse>>, StatusCode> {
+    Query(photos): Query<IncludePhotosQuery>,
+) -> Result<Json<PaginatedResponse<ShelfResponse>>, ApiError> {
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
+    let household_id = state.resolve_household(user_id).await?;
+    let search_pattern = params.search_pattern();
 
-    // Get total count
-    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM shelves")
-        .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count shelves: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    // Get total count with search filter
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM shelves WHERE household_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2)",
+    )
+    .bind(household_id)
+    .bind(&search_pattern)
+    .fetch_one(&state.db)
+    .await?;
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
-    // Get paginated shelves
+    // Get paginated shelves with search filter
     let shelves = sqlx::query_as::<_, Shelf>(
-        "SELECT * FROM shelves ORDER BY shelving_unit_id, COALESCE(position, 0), created_at LIMIT $1 OFFSET $2",
+        "SELECT * FROM shelves WHERE household_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2) ORDER BY shelving_unit_id, position ASC NULLS LAST, created_at LIMIT $3 OFFSET $4",
     )
+    .bind(household_id)
+    .bind(&search_pattern)
     .bind(limit)
     .bind(offset)
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch shelves: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
-    let responses: Vec<ShelfResponse> = shelves.into_iter().map(ShelfResponse::from).collect();
+    let mut responses: Vec<ShelfResponse> = shelves.into_iter().map(ShelfResponse::from).collect();
+    if photos.include_photos {
+        attach_photo_summaries(&state, "shelf", &mut responses).await?;
+    }
     Ok(Json(PaginatedResponse::new(
         responses, total, limit, offset,
     )))
 }
 
+/// Live items and containers directly on each shelf in `$1`. The correlated counts
+/// lean on the `items(shelf_id)` and `containers(shelf_id)` indexes
+/// (idx_items_shelf_id, idx_containers_shelf_id); keep them if these tables change.
+const SHELF_COUNTS_SQL: &str = r#"
+    SELECT
+        s.id,
+        (SELECT COUNT(*) FROM items WHERE shelf_id = s.id AND deleted_at IS NULL)::INT,
+        (SELECT COUNT(*) FROM containers WHERE shelf_id = s.id)::INT
+    FROM shelves s
+    WHERE s.id = ANY($1)
+"#;
+
+/// Fill in `item_count` and `container_count` on a page of shelves
+async fn attach_shelf_counts(
+    state: &AppState,
+    responses: &mut [ShelfResponse],
+) -> Result<(), ApiError> {
+    if responses.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<Uuid> = responses.iter().map(|shelf| shelf.id).collect();
+    let mut counts: HashMap<Uuid, (i32, i32)> =
+        sqlx::query_as::<_, (Uuid, i32, i32)>(SHELF_COUNTS_SQL)
+            .bind(&ids)
+            .fetch_all(&state.db)
+            .await?
+            .into_iter()
+            .map(|(id, items, containers)| (id, (items, containers)))
+            .collect();
+
+    for response in responses.iter_mut() {
+        let (items, containers) = counts.remove(&response.id).unwrap_or_default();
+        response.item_count = Some(items);
+        response.container_count = Some(containers);
+    }
+
+    Ok(())
+}
+
 /// Get shelves by shelving unit
+#[utoipa::path(
+    get,
+    path = "/api/units/{unit_id}/shelves",
+    tag = "shelves",
+    params(
+        ("unit_id" = Uuid, Path, description = "Shelving unit id"),
+        PaginationQuery,
+        IncludePhotosQuery,
+        ShelfCountsQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ShelfResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelving unit not found", body = ErrorResponse)
+    )
+)]
 pub async fn list_shelves_by_unit(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(unit_id): Path<Uuid>,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ShelfResponse>>, StatusCode> {
+    Query(photos): Query<IncludePhotosQuery>,
+    Query(counts): Query<ShelfCountsQuery>,
+) -> Result<Json<PaginatedResponse<ShelfResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::ShelvingUnit, unit_id)
+        .await?;
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
 
@@ -65,71 +157,215 @@ pub async fn list_shelves_by_unit(
     let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM shelves WHERE shelving_unit_id = $1")
         .bind(unit_id)
         .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count shelves: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
     // Get paginated shelves
-    let shelves = sqlx::query_as::<_, Shelf>(
-        "SELECT * FROM shelves WHERE shelving_unit_id = $1 ORDER BY COALESCE(position, 0), created_at LIMIT $2 OFFSET $3"
-    )
+    let shelves = sqlx::query_as::<_, Shelf>(LIST_SHELVES_BY_UNIT_SQL)
         .bind(unit_id)
         .bind(limit)
         .bind(offset)
         .fetch_all(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch shelves: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
-    let responses: Vec<ShelfResponse> = shelves.into_iter().map(ShelfResponse::from).collect();
+    let mut responses: Vec<ShelfResponse> = shelves.into_iter().map(ShelfResponse::from).collect();
+    if photos.include_photos {
+        attach_photo_summaries(&state, "shelf", &mut responses).await?;
+    }
+    if counts.include_counts {
+        attach_shelf_counts(&state, &mut responses).await?;
+    }
     Ok(Json(PaginatedResponse::new(
         responses, total, limit, offset,
     )))
 }
 
+/// Check a reorder names every shelf of the unit exactly once. Shelves of another
+/// unit are a 422.
+fn validate_shelf_reorder(
+    unit_id: Uuid,
+    requested: &[Uuid],
+    unit_shelves: &[Shelf],
+) -> Result<(), ApiError> {
+    let mut seen = std::collections::HashSet::new();
+    if let Some(duplicate) = requested.iter().find(|id| !seen.insert(**id)) {
+        return Err(ApiError::BadRequest(format!(
+            "Shelf {} is listed more than once",
+            duplicate
+        )));
+    }
+    if let Some(foreign) = requested
+        .iter()
+        .find(|id| !unit_shelves.iter().any(|shelf| shelf.id == **id))
+    {
+        return Err(ApiError::UnprocessableEntity(format!(
+            "Shelf {} does not belong to shelving unit {}",
+            foreign, unit_id
+        )));
+    }
+    if requested.len() != unit_shelves.len() {
+        return Err(ApiError::BadRequest(format!(
+            "All {} shelves of the unit must be listed",
+            unit_shelves.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Renumber a unit's shelves 1, 2, 3, ... in the order given. The whole unit is
+/// renumbered in one transaction.
+#[utoipa::path(
+    post,
+    path = "/api/units/{unit_id}/shelves/reorder",
+    tag = "shelves",
+    params(
+        ("unit_id" = Uuid, Path, description = "Shelving unit id")
+    ),
+    request_body = ReorderShelvesRequest,
+    responses(
+        (status = 200, description = "The unit's shelves in their new order", body = Vec<ShelfResponse>),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelving unit not found", body = ErrorResponse),
+        (status = 422, description = "A shelf belongs to another unit", body = ErrorResponse)
+    )
+)]
+pub async fn reorder_unit_shelves(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(unit_id): Path<Uuid>,
+    Json(payload): Json<ReorderShelvesRequest>,
+) -> Result<Json<Vec<ShelfResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::ShelvingUnit, unit_id)
+        .await?;
+
+    let mut tx = state.db.begin().await?;
+
+    // Lock the unit's shelves so a concurrent reorder or move can't interleave
+    let existing =
+        sqlx::query_as::<_, Shelf>("SELECT * FROM shelves WHERE shelving_unit_id = $1 FOR UPDATE")
+            .bind(unit_id)
+            .fetch_all(&mut *tx)
+            .await?;
+    validate_shelf_reorder(unit_id, &payload.shelf_ids, &existing)?;
+
+    // Positions are unique within a unit and DSQL can't defer the check, so clear them
+    // all before handing out the new ones
+    sqlx::query("UPDATE shelves SET position = NULL WHERE shelving_unit_id = $1")
+        .bind(unit_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let mut shelves = Vec::with_capacity(payload.shelf_ids.len());
+    for (index, id) in payload.shelf_ids.iter().enumerate() {
+        let shelf = sqlx::query_as::<_, Shelf>(
+            "UPDATE shelves SET position = $1, updated_at = NOW() WHERE id = $2 RETURNING *",
+        )
+        .bind(index as i32 + 1)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        shelves.push(shelf);
+    }
+
+    tx.commit().await?;
+
+    for shelf in &shelves {
+        let before = existing
+            .iter()
+            .find(|s| s.id == shelf.id)
+            .and_then(|s| s.position);
+        if before != shelf.position {
+            state
+                .audit
+                .log_update(
+                    "shelf",
+                    shelf.id,
+                    Some(user_id),
+                    json!({ "position": { "from": before, "to": shelf.position } }),
+                    None,
+                )
+                .await
+                .ok();
+        }
+    }
+
+    Ok(Json(shelves.into_iter().map(ShelfResponse::from).collect()))
+}
+
 /// Get a single shelf by ID
+#[utoipa::path(
+    get,
+    path = "/api/shelves/{id}",
+    tag = "shelves",
+    params(
+        ("id" = Uuid, Path, description = "Shelf id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = ShelfResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelf not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_shelf(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<ShelfResponse>, StatusCode> {
+) -> Result<Json<ShelfResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Shelf, id)
+        .await?;
     let shelf = sqlx::query_as::<_, Shelf>("SELECT * FROM shelves WHERE id = $1")
         .bind(id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch shelf: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::not_found("Shelf", id))?;
 
     Ok(Json(ShelfResponse::from(shelf)))
 }
 
+/// 409 naming the position when `err` is a violation of the unique index on a unit's
+/// shelf positions
+fn shelf_position_conflict(err: sqlx::Error, position: Option<i32>) -> ApiError {
+    match err {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+            ApiError::conflict(format!(
+                "A shelf at position {} already exists in this unit",
+                position.unwrap_or_default()
+            ))
+        }
+        err => ApiError::from(err),
+    }
+}
+
 /// Create a new shelf
+#[utoipa::path(
+    post,
+    path = "/api/shelves",
+    tag = "shelves",
+    request_body = CreateShelfRequest,
+    responses(
+        (status = 200, description = "OK", body = ShelfResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn create_shelf(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Json(payload): Json<CreateShelfRequest>,
-) -> Result<Json<ShelfResponse>, StatusCode> {
-    // Verify shelving unit exists
-    let unit_exists = sqlx::query("SELECT id FROM shelving_units WHERE id = $1")
-        .bind(payload.shelving_unit_id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to verify shelving unit: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .is_some();
-
-    if !unit_exists {
-        return Err(StatusCode::BAD_REQUEST);
-    }
+) -> Result<Json<ShelfResponse>, ApiError> {
+    let household_id = state
+        .authorize_entity(
+            user_id,
+            HouseholdEntity::ShelvingUnit,
+            payload.shelving_unit_id,
+        )
+        .await?;
+
+    // Verify the shelving unit has room
+    let capacity = move_service::check_unit_capacity(&state.db, payload.shelving_unit_id).await?;
 
     // Auto-assign position if not provided
     let position = if let Some(pos) = payload.position {
@@ -139,19 +375,15 @@ pub async fn create_shelf(
             sqlx::query_scalar("SELECT MAX(position) FROM shelves WHERE shelving_unit_id = $1")
                 .bind(payload.shelving_unit_id)
                 .fetch_one(&state.db)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to get max position: {:?}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?;
+                .await?;
 
         Some(max_position.unwrap_or(0) + 1)
     };
 
     let shelf = sqlx::query_as::<_, Shelf>(
         r#"
-        INSERT INTO shelves (id, shelving_unit_id, name, description, position, created_by)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO shelves (id, shelving_unit_id, name, description, position, created_by, household_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
         RETURNING *
         "#,
     )
@@ -161,12 +393,10 @@ pub async fn create_shelf(
     .bind(&payload.description)
     .bind(position)
     .bind(user_id)
+    .bind(household_id)
     .fetch_one(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to create shelf: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .map_err(|e| shelf_position_conflict(e, position))?;
 
     // Log audit
     state
@@ -174,43 +404,57 @@ pub async fn create_shelf(
         .log_create("shelf", shelf.id, Some(user_id), None)
         .await
         .ok();
+    log_capacity_warning(&state, shelf.shelving_unit_id, user_id, &capacity).await;
 
     Ok(Json(ShelfResponse::from(shelf)))
 }
 
 /// Update a shelf
+#[utoipa::path(
+    patch,
+    path = "/api/shelves/{id}",
+    tag = "shelves",
+    params(
+        ("id" = Uuid, Path, description = "Shelf id")
+    ),
+    request_body = UpdateShelfRequest,
+    responses(
+        (status = 200, description = "OK", body = ShelfResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelf not found", body = ErrorResponse)
+    )
+)]
 pub async fn update_shelf(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateShelfRequest>,
-) -> Result<Json<ShelfResponse>, StatusCode> {
+) -> Result<Json<ShelfResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Shelf, id)
+        .await?;
     // Check if shelf exists
     let existing = sqlx::query_as::<_, Shelf>("SELECT * FROM shelves WHERE id = $1")
         .bind(id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch shelf: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    // If shelving_unit_id is provided, verify it exists
-    let shelving_unit_id = if let Some(new_unit_id) = payload.shelving_unit_id {
-        let unit_exists = sqlx::query("SELECT id FROM shelving_units WHERE id = $1")
-            .bind(new_unit_id)
-            .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to verify shelving unit: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
-            .is_some();
-
-        if !unit_exists {
-            return Err(StatusCode::BAD_REQUEST);
+        .await?
+        .ok_or_else(|| ApiError::not_found("Shelf", id))?;
+
+    // A shelf moving to another unit needs that unit to exist and have room
+    let moved_to = match payload.shelving_unit_id {
+        Some(new_unit_id) if new_unit_id != existing.shelving_unit_id => {
+            state
+                .authorize_entity(user_id, HouseholdEntity::ShelvingUnit, new_unit_id)
+                .await?;
+            Some((
+                new_unit_id,
+                move_service::check_unit_capacity(&state.db, new_unit_id).await?,
+            ))
         }
+        _ => None,
+    };
+    let shelving_unit_id = if let Some((new_unit_id, _)) = moved_to {
         new_unit_id
     } else {
         existing.shelving_unit_id
@@ -281,10 +525,7 @@ pub async fn update_shelf(
     .bind(id)
     .fetch_one(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to update shelf: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .map_err(|e| shelf_position_conflict(e, position))?;
 
     // Log audit
     if !changes.is_empty() {
@@ -300,49 +541,505 @@ pub async fn update_shelf(
             .await
             .ok();
     }
+    if let Some((new_unit_id, capacity)) = moved_to {
+        log_capacity_warning(&state, new_unit_id, user_id, &capacity).await;
+    }
 
     Ok(Json(ShelfResponse::from(shelf)))
 }
 
-/// Delete a shelf
+/// Delete a shelf; with `?cascade=true` everything inside it is deleted too
+#[utoipa::path(
+    delete,
+    path = "/api/shelves/{id}",
+    tag = "shelves",
+    params(
+        ("id" = Uuid, Path, description = "Shelf id"),
+        CascadeDeleteQuery
+    ),
+    responses(
+        (status = 200, description = "What was deleted", body = CascadeDeleteResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 403, description = "Shelf contains items someone else created", body = ErrorResponse),
+        (status = 404, description = "Shelf not found", body = ErrorResponse),
+        (status = 409, description = "Shelf is not empty and cascade wasn't requested", body = ErrorResponse),
+        (status = 422, description = "Shelf holds too much to delete in one transaction", body = ErrorResponse)
+    )
+)]
 pub async fn delete_shelf(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    // Log audit before deletion
+    Query(params): Query<CascadeDeleteQuery>,
+) -> Result<Json<CascadeDeleteResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Shelf, id)
+        .await?;
+    let deleted = delete_location(
+        &state.db,
+        &state.s3,
+        user_id,
+        CascadeRoot::Shelf(id),
+        params.cascade,
+    )
+    .await?;
+
+    let metadata = params.cascade.then(|| json!({ "cascade": deleted }));
     state
         .audit
-        .log_delete("shelf", id, Some(user_id), None)
+        .log_delete("shelf", id, Some(user_id), metadata)
         .await
         .ok();
 
-    let result = sqlx::query("DELETE FROM shelves WHERE id = $1")
-        .bind(id)
-        .execute(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete shelf: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-
-    if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
-    }
+    Ok(Json(CascadeDeleteResponse { deleted }))
+}
 
-    Ok(Json(json!({ "message": "Shelf deleted successfully" })))
+/// Get photos for a shelf
+#[utoipa::path(
+    get,
+    path = "/api/shelves/{id}/photos",
+    tag = "shelves",
+    params(
+        ("id" = Uuid, Path, description = "Shelf id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<PhotoResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelf not found", body = ErrorResponse)
+    )
+)]
+pub async fn list_shelf_photos(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<PhotoResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Shelf, id)
+        .await?;
+    let photos = fetch_entity_photos(&state, "shelf", id).await?;
+    Ok(Json(photos))
 }
 
 /// Create shelf routes
 pub fn shelf_routes() -> Router<Arc<AppState>> {
     #[allow(unused_imports)]
-    use axum::routing::{delete, get, post, put};
+    use axum::routing::{delete, get, post};
 
     Router::new()
         .route("/api/shelves", get(list_shelves).post(create_shelf))
         .route(
             "/api/shelves/:id",
-            get(get_shelf).put(update_shelf).delete(delete_shelf),
+            get(get_shelf)
+                .merge(patch_with_put_alias(update_shelf))
+                .delete(delete_shelf),
         )
+        .route("/api/shelves/:id/photos", get(list_shelf_photos))
         .route("/api/units/:unit_id/shelves", get(list_shelves_by_unit))
+        .route(
+            "/api/units/:unit_id/shelves/reorder",
+            post(reorder_unit_shelves),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_pool, test_app_state};
+    use axum::{http::StatusCode, response::IntoResponse};
+    use chrono::Utc;
+
+    fn shelf(unit_id: Uuid, position: Option<i32>) -> Shelf {
+        Shelf {
+            id: Uuid::new_v4(),
+            shelving_unit_id: unit_id,
+            name: "Shelf".to_string(),
+            description: None,
+            position,
+            label_id: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            created_by: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn test_validate_shelf_reorder_accepts_every_shelf_once() {
+        let unit_id = Uuid::new_v4();
+        let shelves = vec![shelf(unit_id, Some(1)), shelf(unit_id, Some(1))];
+        let ids: Vec<Uuid> = shelves.iter().rev().map(|s| s.id).collect();
+
+        assert!(validate_shelf_reorder(unit_id, &ids, &shelves).is_ok());
+    }
+
+    #[test]
+    fn test_validate_shelf_reorder_rejects_foreign_shelf() {
+        let unit_id = Uuid::new_v4();
+        let shelves = vec![shelf(unit_id, Some(1))];
+        let foreign = shelf(Uuid::new_v4(), Some(1));
+
+        assert!(matches!(
+            validate_shelf_reorder(unit_id, &[shelves[0].id, foreign.id], &shelves),
+            Err(ApiError::UnprocessableEntity(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_shelf_reorder_rejects_duplicate_and_incomplete_lists() {
+        let unit_id = Uuid::new_v4();
+        let shelves = vec![shelf(unit_id, Some(1)), shelf(unit_id, Some(2))];
+
+        assert!(matches!(
+            validate_shelf_reorder(unit_id, &[shelves[0].id, shelves[0].id], &shelves),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            validate_shelf_reorder(unit_id, &[shelves[1].id], &shelves),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_list_shelves_by_unit_sorts_null_positions_last() {
+        let pool = create_test_pool().await;
+        let unit_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+
+        // Inserted out of order; NULL positions must not mix with explicit position 0
+        let shelves = [
+            ("unpositioned-a", None),
+            ("second", Some(1)),
+            ("first", Some(0)),
+            ("unpositioned-b", None),
+        ];
+        for (name, position) in shelves {
+            sqlx::query(
+                "INSERT INTO shelves (id, shelving_unit_id, name, position, created_by, created_at) VALUES ($1, $2, $3, $4, $5, clock_timestamp())",
+            )
+            .bind(Uuid::new_v4())
+            .bind(unit_id)
+            .bind(name)
+            .bind(position)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let result = sqlx::query_as::<_, Shelf>(LIST_SHELVES_BY_UNIT_SQL)
+            .bind(unit_id)
+            .bind(50i64)
+            .bind(0i64)
+            .fetch_all(&pool)
+            .await;
+
+        sqlx::query("DELETE FROM shelves WHERE shelving_unit_id = $1")
+            .bind(unit_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let names: Vec<String> = result.unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(
+            names,
+            vec!["first", "second", "unpositioned-a", "unpositioned-b"]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_reorder_unit_shelves_swaps_positions() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let unit_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO shelving_units (id, room_id, name, created_by, household_id) VALUES ($1, $2, 'Reorder test unit', $3, $4)",
+        )
+        .bind(unit_id)
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(household_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // Reversing swaps positions the unique index won't let two shelves share
+        let mut shelf_ids = Vec::new();
+        for (name, position) in [("top", 1), ("middle", 2), ("bottom", 3)] {
+            let id = Uuid::new_v4();
+            sqlx::query(
+                "INSERT INTO shelves (id, shelving_unit_id, name, position, created_by) VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(id)
+            .bind(unit_id)
+            .bind(name)
+            .bind(position)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+            shelf_ids.push(id);
+        }
+        let foreign_shelf_id = Uuid::new_v4();
+        sqlx::query(
+            "INSERT INTO shelves (id, shelving_unit_id, name, position, created_by) VALUES ($1, $2, 'elsewhere', 1, $3)",
+        )
+        .bind(foreign_shelf_id)
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let reversed: Vec<Uuid> = shelf_ids.iter().rev().copied().collect();
+        let reordered = reorder_unit_shelves(
+            State(state.clone()),
+            AuthUser(user_id),
+            Path(unit_id),
+            Json(ReorderShelvesRequest {
+                shelf_ids: reversed.clone(),
+            }),
+        )
+        .await;
+        let foreign = reorder_unit_shelves(
+            State(state),
+            AuthUser(user_id),
+            Path(unit_id),
+            Json(ReorderShelvesRequest {
+                shelf_ids: vec![shelf_ids[0], shelf_ids[1], foreign_shelf_id],
+            }),
+        )
+        .await;
+        let listed = sqlx::query_as::<_, Shelf>(LIST_SHELVES_BY_UNIT_SQL)
+            .bind(unit_id)
+            .bind(50i64)
+            .bind(0i64)
+            .fetch_all(&pool)
+            .await;
+
+        sqlx::query("DELETE FROM shelves WHERE shelving_unit_id = $1 OR id = $2")
+            .bind(unit_id)
+            .bind(foreign_shelf_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM shelving_units WHERE id = $1")
+            .bind(unit_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let Json(reordered) = reordered.unwrap();
+        let positions: Vec<(Uuid, Option<i32>)> =
+            reordered.iter().map(|s| (s.id, s.position)).collect();
+        assert_eq!(
+            positions,
+            vec![
+                (reversed[0], Some(1)),
+                (reversed[1], Some(2)),
+                (reversed[2], Some(3))
+            ]
+        );
+        assert!(matches!(foreign, Err(ApiError::UnprocessableEntity(_))));
+
+        // The rejected reorder left the new order alone
+        let listed: Vec<Uuid> = listed.unwrap().into_iter().map(|s| s.id).collect();
+        assert_eq!(listed, reversed);
+    }
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_attach_shelf_counts_skips_trashed_items() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let (unit_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (full_id, empty_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        for (id, name) in [(full_id, "full"), (empty_id, "empty")] {
+            sqlx::query(
+                "INSERT INTO shelves (id, shelving_unit_id, name, created_by) VALUES ($1, $2, $3, $4)",
+            )
+            .bind(id)
+            .bind(unit_id)
+            .bind(name)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        for deleted in [false, false, true] {
+            sqlx::query(
+                "INSERT INTO items (id, shelf_id, name, created_by, deleted_at) VALUES ($1, $2, 'Counted item', $3, CASE WHEN $4 THEN NOW() END)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(full_id)
+            .bind(user_id)
+            .bind(deleted)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO containers (id, shelf_id, name, created_by) VALUES ($1, $2, 'Counted box', $3)",
+        )
+        .bind(Uuid::new_v4())
+        .bind(full_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut responses: Vec<ShelfResponse> =
+            sqlx::query_as::<_, Shelf>(LIST_SHELVES_BY_UNIT_SQL)
+                .bind(unit_id)
+                .bind(50i64)
+                .bind(0i64)
+                .fetch_all(&pool)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(ShelfResponse::from)
+                .collect();
+        let attached = attach_shelf_counts(&state, &mut responses).await;
+
+        sqlx::query("DELETE FROM items WHERE shelf_id = $1")
+            .bind(full_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM containers WHERE shelf_id = $1")
+            .bind(full_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM shelves WHERE shelving_unit_id = $1")
+            .bind(unit_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        attached.unwrap();
+        let counts: HashMap<Uuid, (Option<i32>, Option<i32>)> = responses
+            .iter()
+            .map(|shelf| (shelf.id, (shelf.item_count, shelf.container_count)))
+            .collect();
+        assert_eq!(counts[&full_id], (Some(2), Some(1)));
+        assert_eq!(counts[&empty_id], (Some(0), Some(0)));
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_list_shelves_search_filters_name_and_description() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+
+        let shelves = [
+            ("Top shelf", None),
+            ("Bottom", Some("Tools, top-heavy ones")),
+            ("Middle", None),
+        ];
+        for (name, description) in shelves {
+            sqlx::query(
+                "INSERT INTO shelves (id, shelving_unit_id, name, description, created_by, household_id) VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(Uuid::new_v4())
+            .bind(name)
+            .bind(description)
+            .bind(user_id)
+            .bind(household_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let list = |search: Option<&str>| {
+            list_shelves(
+                State(state.clone()),
+                AuthUser(user_id),
+                Query(PaginationQuery {
+                    search: search.map(str::to_string),
+                    ..Default::default()
+                }),
+                Query(IncludePhotosQuery::default()),
+            )
+        };
+        let everything = list(Some("")).await;
+        let top = list(Some("Top")).await;
+
+        sqlx::query("DELETE FROM shelves WHERE household_id = $1")
+            .bind(household_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let everything = everything.unwrap().0;
+        assert_eq!(everything.total, 3);
+        assert_eq!(everything.data.len(), 3);
+
+        let top = top.unwrap().0;
+        let mut names: Vec<String> = top.data.into_iter().map(|s| s.name).collect();
+        names.sort();
+        assert_eq!(top.total, 2);
+        assert_eq!(names, vec!["Bottom", "Top shelf"]);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_create_shelf_at_taken_position_conflicts() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let unit_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+
+        sqlx::query(
+            "INSERT INTO shelving_units (id, room_id, name, created_by, household_id) VALUES ($1, $2, 'Position test unit', $3, $4)",
+        )
+        .bind(unit_id)
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(household_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let create = |name: &str| {
+            create_shelf(
+                State(state.clone()),
+                AuthUser(user_id),
+                Json(CreateShelfRequest {
+                    shelving_unit_id: unit_id,
+                    name: name.to_string(),
+                    description: None,
+                    position: Some(3),
+                }),
+            )
+        };
+        let first = create("taken").await;
+        let second = create("duplicate").await;
+
+        sqlx::query("DELETE FROM shelves WHERE shelving_unit_id = $1")
+            .bind(unit_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM shelving_units WHERE id = $1")
+            .bind(unit_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(first.is_ok());
+        let err = second.unwrap_err();
+        assert_eq!(
+            err,
+            ApiError::Conflict("A shelf at position 3 already exists in this unit".to_string())
+        );
+        assert_eq!(err.into_response().status(), StatusCode::CONFLICT);
+    }
 }
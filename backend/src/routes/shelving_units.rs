@@ -1,6 +1,5 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::Json,
     Router,
 };
@@ -9,42 +8,64 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
 use crate::middleware::auth::AuthUser;
+use crate::middleware::deprecation::patch_with_put_alias;
 use crate::models::{
-    CreateShelvingUnitRequest, PaginatedResponse, PaginationQuery, ShelvingUnit,
-    ShelvingUnitResponse, UpdateShelvingUnitRequest,
+    capacity_valid, CascadeDeleteQuery, CascadeDeleteResponse, CreateShelvingUnitRequest,
+    PaginatedResponse, PaginationQuery, ShelvingUnit, ShelvingUnitCapacity, ShelvingUnitResponse,
+    UpdateShelvingUnitRequest,
 };
+use crate::services::audit::Auditable;
+use crate::services::cascade::{delete_location, CascadeRoot};
+use crate::services::household::HouseholdEntity;
+use crate::services::r#move as move_service;
 
-/// Get all shelving units
+const INVALID_CAPACITY: &str = "max_shelves must be at least 1 and max_weight_kg must be positive";
+
+/// Get all shelving units in the user's household
+#[utoipa::path(
+    get,
+    path = "/api/units",
+    tag = "shelving_units",
+    params(
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ShelvingUnitResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn list_shelving_units(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ShelvingUnitResponse>>, StatusCode> {
+) -> Result<Json<PaginatedResponse<ShelvingUnitResponse>>, ApiError> {
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
+    let household_id = state.resolve_household(user_id).await?;
+    let search_pattern = params.search_pattern();
 
-    // Get total count
-    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM shelving_units")
-        .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count shelving units: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    // Get total count with search filter
+    let total: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM shelving_units WHERE household_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2)",
+    )
+    .bind(household_id)
+    .bind(&search_pattern)
+    .fetch_one(&state.db)
+    .await?;
     let total: i32 = total.clamp(0, i32::MAX as i64) as i32;
 
-    // Get paginated units
+    // Get paginated units with search filter
     let units = sqlx::query_as::<_, ShelvingUnit>(
-        "SELECT * FROM shelving_units ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+        "SELECT * FROM shelving_units WHERE household_id = $1 AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2) ORDER BY created_at DESC LIMIT $3 OFFSET $4",
     )
+    .bind(household_id)
+    .bind(&search_pattern)
     .bind(limit)
     .bind(offset)
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch shelving units: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     let responses: Vec<ShelvingUnitResponse> =
         units.into_iter().map(ShelvingUnitResponse::from).collect();
@@ -54,11 +75,29 @@ pub async fn list_shelving_units(
 }
 
 /// Get shelving units by room
+#[utoipa::path(
+    get,
+    path = "/api/rooms/{room_id}/units",
+    tag = "shelving_units",
+    params(
+        ("room_id" = Uuid, Path, description = "Room id"),
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ShelvingUnitResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Room not found", body = ErrorResponse)
+    )
+)]
 pub async fn list_shelving_units_by_room(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(room_id): Path<Uuid>,
     Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<ShelvingUnitResponse>>, StatusCode> {
+) -> Result<Json<PaginatedResponse<ShelvingUnitResponse>>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::Room, room_id)
+        .await?;
     let limit = params.limit.unwrap_or(50).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
 
@@ -66,11 +105,7 @@ pub async fn list_shelving_units_by_room(
     let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM shelving_units WHERE room_id = $1")
         .bind(room_id)
         .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count shelving units for room: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
     let total: i32 = total.clamp(0, i32::MAX as i64) as i32;
 
     // Get paginated units
@@ -81,11 +116,7 @@ pub async fn list_shelving_units_by_room(
     .bind(limit)
     .bind(offset)
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch shelving units for room: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     let responses: Vec<ShelvingUnitResponse> =
         units.into_iter().map(ShelvingUnitResponse::from).collect();
@@ -95,48 +126,142 @@ pub async fn list_shelving_units_by_room(
 }
 
 /// Get a single shelving unit by ID
+#[utoipa::path(
+    get,
+    path = "/api/units/{id}",
+    tag = "shelving_units",
+    params(
+        ("id" = Uuid, Path, description = "Shelving unit id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = ShelvingUnitResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelving unit not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_shelving_unit(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<ShelvingUnitResponse>, StatusCode> {
+) -> Result<Json<ShelvingUnitResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::ShelvingUnit, id)
+        .await?;
     let unit = sqlx::query_as::<_, ShelvingUnit>("SELECT * FROM shelving_units WHERE id = $1")
         .bind(id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch shelving unit: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::not_found("Shelving unit", id))?;
 
     Ok(Json(ShelvingUnitResponse::from(unit)))
 }
 
+/// How full a shelving unit is
+#[utoipa::path(
+    get,
+    path = "/api/units/{id}/capacity",
+    tag = "shelving_units",
+    params(
+        ("id" = Uuid, Path, description = "Shelving unit id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = ShelvingUnitCapacity),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelving unit not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_shelving_unit_capacity(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ShelvingUnitCapacity>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::ShelvingUnit, id)
+        .await?;
+    let capacity = move_service::shelving_unit_capacity(&state.db, id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Shelving unit", id))?;
+    Ok(Json(capacity))
+}
+
+/// Record an update on the shelving unit when the shelf just added to it (with
+/// `before` its capacity beforehand) takes it past 90% full
+pub(crate) async fn log_capacity_warning(
+    state: &AppState,
+    unit_id: Uuid,
+    user_id: Uuid,
+    before: &ShelvingUnitCapacity,
+) {
+    if !before.crosses_warning_with_one_more() {
+        return;
+    }
+
+    let after = ShelvingUnitCapacity::new(before.max_shelves, before.current_shelves + 1);
+    tracing::info!(
+        "Shelving unit {} is {}% full",
+        unit_id,
+        after.utilization_pct.unwrap_or_default()
+    );
+    state
+        .audit
+        .log_update(
+            "shelving_unit",
+            unit_id,
+            Some(user_id),
+            json!({
+                "capacity_warning": true,
+                "current_shelves": after.current_shelves,
+                "max_shelves": after.max_shelves,
+                "utilization_pct": after.utilization_pct,
+            }),
+            None,
+        )
+        .await
+        .ok();
+}
+
 /// Create a new shelving unit
+#[utoipa::path(
+    post,
+    path = "/api/units",
+    tag = "shelving_units",
+    request_body = CreateShelvingUnitRequest,
+    responses(
+        (status = 200, description = "OK", body = ShelvingUnitResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn create_shelving_unit(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Json(payload): Json<CreateShelvingUnitRequest>,
-) -> Result<Json<ShelvingUnitResponse>, StatusCode> {
-    // Verify room exists
-    let room_exists = sqlx::query("SELECT id FROM rooms WHERE id = $1")
+) -> Result<Json<ShelvingUnitResponse>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+
+    // Verify room exists in the user's household
+    let room_exists = sqlx::query("SELECT id FROM rooms WHERE id = $1 AND household_id = $2")
         .bind(payload.room_id)
+        .bind(household_id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to verify room: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .await?
         .is_some();
 
     if !room_exists {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::BadRequest(format!(
+            "Room with id {} does not exist",
+            payload.room_id
+        )));
+    }
+
+    if !capacity_valid(payload.max_shelves, payload.max_weight_kg) {
+        return Err(ApiError::bad_request(INVALID_CAPACITY));
     }
 
     let unit = sqlx::query_as::<_, ShelvingUnit>(
         r#"
-        INSERT INTO shelving_units (id, room_id, name, description, created_by)
-        VALUES ($1, $2, $3, $4, $5)
+        INSERT INTO shelving_units (id, room_id, name, description, max_shelves, max_weight_kg, created_by, household_id)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
         RETURNING *
         "#,
     )
@@ -144,13 +269,12 @@ pub async fn create_shelving_unit(
     .bind(payload.room_id)
     .bind(&payload.name)
     .bind(&payload.description)
+    .bind(payload.max_shelves)
+    .bind(payload.max_weight_kg)
     .bind(user_id)
+    .bind(household_id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to create shelving unit: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     // Log audit
     state
@@ -163,37 +287,53 @@ pub async fn create_shelving_unit(
 }
 
 /// Update a shelving unit
+#[utoipa::path(
+    patch,
+    path = "/api/units/{id}",
+    tag = "shelving_units",
+    params(
+        ("id" = Uuid, Path, description = "Shelving unit id")
+    ),
+    request_body = UpdateShelvingUnitRequest,
+    responses(
+        (status = 200, description = "OK", body = ShelvingUnitResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Shelving unit not found", body = ErrorResponse)
+    )
+)]
 pub async fn update_shelving_unit(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateShelvingUnitRequest>,
-) -> Result<Json<ShelvingUnitResponse>, StatusCode> {
+) -> Result<Json<ShelvingUnitResponse>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+
     // Check if shelving unit exists
-    let existing = sqlx::query_as::<_, ShelvingUnit>("SELECT * FROM shelving_units WHERE id = $1")
-        .bind(id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch shelving unit: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+    let existing = sqlx::query_as::<_, ShelvingUnit>(
+        "SELECT * FROM shelving_units WHERE id = $1 AND household_id = $2",
+    )
+    .bind(id)
+    .bind(household_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("Shelving unit", id))?;
 
-    // If room_id is provided, verify it exists
+    // If room_id is provided, verify it exists in the user's household
     let room_id = if let Some(new_room_id) = payload.room_id {
-        let room_exists = sqlx::query("SELECT id FROM rooms WHERE id = $1")
+        let room_exists = sqlx::query("SELECT id FROM rooms WHERE id = $1 AND household_id = $2")
             .bind(new_room_id)
+            .bind(household_id)
             .fetch_optional(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to verify room: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
+            .await?
             .is_some();
 
         if !room_exists {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(ApiError::BadRequest(format!(
+                "Room with id {} does not exist",
+                new_room_id
+            )));
         }
         new_room_id
     } else {
@@ -225,6 +365,24 @@ pub async fn update_shelving_unit(
         );
     }
 
+    let max_shelves = payload.max_shelves.apply(existing.max_shelves);
+    let max_weight_kg = payload.max_weight_kg.apply(existing.max_weight_kg);
+    if !capacity_valid(max_shelves, max_weight_kg) {
+        return Err(ApiError::bad_request(INVALID_CAPACITY));
+    }
+    if max_shelves != existing.max_shelves {
+        changes.insert(
+            "max_shelves".to_string(),
+            serde_json::json!({ "from": existing.max_shelves, "to": max_shelves }),
+        );
+    }
+    if max_weight_kg != existing.max_weight_kg {
+        changes.insert(
+            "max_weight_kg".to_string(),
+            serde_json::json!({ "from": existing.max_weight_kg, "to": max_weight_kg }),
+        );
+    }
+
     // Update fields if provided
     let name = payload.name.unwrap_or(existing.name.clone());
     let description = payload.description.or(existing.description.clone());
@@ -241,21 +399,20 @@ pub async fn update_shelving_unit(
     let unit = sqlx::query_as::<_, ShelvingUnit>(
         r#"
         UPDATE shelving_units
-        SET name = $1, description = $2, room_id = $3, updated_at = NOW()
-        WHERE id = $4
+        SET name = $1, description = $2, room_id = $3, max_shelves = $4, max_weight_kg = $5,
+            updated_at = NOW()
+        WHERE id = $6
         RETURNING *
         "#,
     )
     .bind(&name)
     .bind(&description)
     .bind(room_id)
+    .bind(max_shelves)
+    .bind(max_weight_kg)
     .bind(id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to update shelving unit: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     // Log audit
     if !changes.is_empty() {
@@ -275,41 +432,56 @@ pub async fn update_shelving_unit(
     Ok(Json(ShelvingUnitResponse::from(unit)))
 }
 
-/// Delete a shelving unit
+/// Delete a shelving unit; with `?cascade=true` everything inside it is deleted too
+#[utoipa::path(
+    delete,
+    path = "/api/units/{id}",
+    tag = "shelving_units",
+    params(
+        ("id" = Uuid, Path, description = "Shelving unit id"),
+        CascadeDeleteQuery
+    ),
+    responses(
+        (status = 200, description = "What was deleted", body = CascadeDeleteResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 403, description = "Shelving unit contains items someone else created", body = ErrorResponse),
+        (status = 404, description = "Shelving unit not found", body = ErrorResponse),
+        (status = 409, description = "Shelving unit is not empty and cascade wasn't requested", body = ErrorResponse),
+        (status = 422, description = "Shelving unit holds too much to delete in one transaction", body = ErrorResponse)
+    )
+)]
 pub async fn delete_shelving_unit(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
-    // Log audit before deletion
+    Query(params): Query<CascadeDeleteQuery>,
+) -> Result<Json<CascadeDeleteResponse>, ApiError> {
+    state
+        .authorize_entity(user_id, HouseholdEntity::ShelvingUnit, id)
+        .await?;
+    let deleted = delete_location(
+        &state.db,
+        &state.s3,
+        user_id,
+        CascadeRoot::ShelvingUnit(id),
+        params.cascade,
+    )
+    .await?;
+
+    let metadata = params.cascade.then(|| json!({ "cascade": deleted }));
     state
         .audit
-        .log_delete("shelving_unit", id, Some(user_id), None)
+        .log_delete("shelving_unit", id, Some(user_id), metadata)
         .await
         .ok();
 
-    let result = sqlx::query("DELETE FROM shelving_units WHERE id = $1")
-        .bind(id)
-        .execute(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete shelving unit: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-
-    if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
-    }
-
-    Ok(Json(
-        json!({ "message": "Shelving unit deleted successfully" }),
-    ))
+    Ok(Json(CascadeDeleteResponse { deleted }))
 }
 
 /// Create shelving unit routes
 pub fn shelving_unit_routes() -> Router<Arc<AppState>> {
     #[allow(unused_imports)]
-    use axum::routing::{delete, get, post, put};
+    use axum::routing::{delete, get, post};
 
     Router::new()
         .route(
@@ -319,11 +491,76 @@ pub fn shelving_unit_routes() -> Router<Arc<AppState>> {
         .route(
             "/api/units/:id",
             get(get_shelving_unit)
-                .put(update_shelving_unit)
+                .merge(patch_with_put_alias(update_shelving_unit))
                 .delete(delete_shelving_unit),
         )
+        .route("/api/units/:id/capacity", get(get_shelving_unit_capacity))
         .route(
             "/api/rooms/:room_id/units",
             get(list_shelving_units_by_room),
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_pool, test_app_state};
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_list_shelving_units_search_filters_name_and_description() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+
+        let units = [
+            ("Metal rack", None),
+            ("Bookcase", Some("Metal frame")),
+            ("Pantry", None),
+        ];
+        for (name, description) in units {
+            sqlx::query(
+                "INSERT INTO shelving_units (id, room_id, name, description, created_by, household_id) VALUES ($1, $2, $3, $4, $5, $6)",
+            )
+            .bind(Uuid::new_v4())
+            .bind(Uuid::new_v4())
+            .bind(name)
+            .bind(description)
+            .bind(user_id)
+            .bind(household_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let list = |search: Option<&str>| {
+            list_shelving_units(
+                State(state.clone()),
+                AuthUser(user_id),
+                Query(PaginationQuery {
+                    search: search.map(str::to_string),
+                    ..Default::default()
+                }),
+            )
+        };
+        let everything = list(Some("")).await;
+        let metal = list(Some("metal")).await;
+
+        sqlx::query("DELETE FROM shelving_units WHERE household_id = $1")
+            .bind(household_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let everything = everything.unwrap().0;
+        assert_eq!(everything.total, 3);
+        assert_eq!(everything.data.len(), 3);
+
+        let metal = metal.unwrap().0;
+        let mut names: Vec<String> = metal.data.into_iter().map(|u| u.name).collect();
+        names.sort();
+        assert_eq!(metal.total, 2);
+        assert_eq!(names, vec!["Bookcase", "Metal rack"]);
+    }
+}
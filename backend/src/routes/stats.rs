@@ -0,0 +1,290 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json,
+    Router,
+};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::{
+    parse_currency, CurrencyValuation, InventoryStats, RoomValuation, ValuationQuery,
+    ValuationResponse, DEFAULT_CURRENCY,
+};
+
+/// How long the whole-inventory stats are reused before they are recomputed
+const OVERVIEW_STATS_TTL: Duration = Duration::from_secs(60);
+
+/// Counts for the room in `$1`, or for all of household `$2` when `$1` is NULL, in one
+/// round trip. Containers are found by walking down from the shelves, so nested containers
+/// and the items in them are included. Returns no row when the room doesn't exist.
+/// Photos of shelving units are stored as `shelving_unit` while tags use `unit`.
+const INVENTORY_STATS_SQL: &str = r#"
+    WITH RECURSIVE
+    scoped_rooms AS (
+        SELECT id FROM rooms WHERE household_id = $2 AND ($1::UUID IS NULL OR id = $1)
+    ),
+    scoped_units AS (
+        SELECT u.id FROM shelving_units u JOIN scoped_rooms r ON r.id = u.room_id
+    ),
+    scoped_shelves AS (
+        SELECT s.id FROM shelves s JOIN scoped_units u ON u.id = s.shelving_unit_id
+    ),
+    scoped_containers AS (
+        SELECT c.id
+        FROM containers c
+        JOIN scoped_shelves s ON s.id = c.shelf_id
+        WHERE c.parent_container_id IS NULL
+        UNION ALL
+        SELECT c.id
+        FROM containers c
+        JOIN scoped_containers p ON c.parent_container_id = p.id
+    ),
+    scoped_items AS (
+        SELECT i.id, i.purchase_price, i.currency
+        FROM items i
+        WHERE i.deleted_at IS NULL
+          AND (i.shelf_id IN (SELECT id FROM scoped_shelves)
+               OR i.container_id IN (SELECT id FROM scoped_containers))
+    ),
+    entities AS (
+        SELECT 'room'::TEXT AS kind, id FROM scoped_rooms
+        UNION ALL SELECT 'unit', id FROM scoped_units
+        UNION ALL SELECT 'shelf', id FROM scoped_shelves
+        UNION ALL SELECT 'container', id FROM scoped_containers
+        UNION ALL SELECT 'item', id FROM scoped_items
+    )
+    SELECT
+        (SELECT COUNT(*)::INT FROM scoped_units) AS shelving_unit_count,
+        (SELECT COUNT(*)::INT FROM scoped_shelves) AS shelf_count,
+        (SELECT COUNT(*)::INT FROM scoped_containers) AS container_count,
+        (SELECT COUNT(*)::INT FROM scoped_items) AS item_count,
+        (
+            SELECT COUNT(*)::INT
+            FROM photos p
+            JOIN entities e ON e.id = p.entity_id
+             AND p.entity_type = CASE e.kind WHEN 'unit' THEN 'shelving_unit' ELSE e.kind END
+        ) AS photo_count,
+        (
+            SELECT COUNT(DISTINCT et.tag_id)::INT
+            FROM entity_tags et
+            JOIN entities e ON e.id = et.entity_id AND et.entity_type = e.kind
+        ) AS tag_count,
+        (
+            SELECT SUM(purchase_price)
+            FROM scoped_items
+            WHERE COALESCE(currency, 'USD') = 'USD'
+        ) AS total_value
+    WHERE $1::UUID IS NULL OR EXISTS (SELECT 1 FROM scoped_rooms)
+"#;
+
+/// Counts for one room and everything in it
+#[utoipa::path(
+    get,
+    path = "/api/rooms/{id}/stats",
+    tag = "stats",
+    params(
+        ("id" = Uuid, Path, description = "Room id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = InventoryStats),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Room not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_room_stats(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<InventoryStats>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    let stats = sqlx::query_as::<_, InventoryStats>(INVENTORY_STATS_SQL)
+        .bind(Some(id))
+        .bind(household_id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Room", id))?;
+
+    Ok(Json(stats))
+}
+
+/// Stats cached at `computed_at`, if they are still fresh at `now`
+fn fresh_stats(cached: &Option<(InventoryStats, Instant)>, now: Instant) -> Option<InventoryStats> {
+    cached
+        .as_ref()
+        .filter(|(_, computed_at)| now.saturating_duration_since(*computed_at) < OVERVIEW_STATS_TTL)
+        .map(|(stats, _)| stats.clone())
+}
+
+/// Counts for the household's whole inventory. Computed at most once a minute, so
+/// changes can take that long to show up.
+#[utoipa::path(
+    get,
+    path = "/api/stats/overview",
+    tag = "stats",
+    responses(
+        (status = 200, description = "OK", body = InventoryStats),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn get_overview_stats(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+) -> Result<Json<InventoryStats>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    let cached = state
+        .overview_stats
+        .read()
+        .await
+        .get(&household_id)
+        .cloned();
+    if let Some(stats) = fresh_stats(&cached, Instant::now()) {
+        return Ok(Json(stats));
+    }
+
+    let stats = sqlx::query_as::<_, InventoryStats>(INVENTORY_STATS_SQL)
+        .bind(None::<Uuid>)
+        .bind(household_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    state
+        .overview_stats
+        .write()
+        .await
+        .insert(household_id, (stats.clone(), Instant::now()));
+    Ok(Json(stats))
+}
+
+/// Purchase price totals per currency for household `$1`. Prices saved before the
+/// currency column was filled in count as USD.
+const VALUATION_BY_CURRENCY_SQL: &str = r#"
+    SELECT
+        COALESCE(currency, 'USD') AS currency,
+        SUM(purchase_price) AS total_value,
+        COUNT(*)::INT AS item_count
+    FROM items
+    WHERE purchase_price IS NOT NULL AND deleted_at IS NULL AND household_id = $1
+    GROUP BY 1
+    ORDER BY 1
+"#;
+
+/// Purchase price totals per room of household `$2` for the currency in `$1`. Items in nested containers
+/// get their room from the shelf holding the outermost container.
+const VALUATION_BY_ROOM_SQL: &str = r#"
+    WITH RECURSIVE container_roots AS (
+        SELECT id, shelf_id FROM containers WHERE parent_container_id IS NULL
+        UNION ALL
+        SELECT c.id, cr.shelf_id
+        FROM containers c
+        JOIN container_roots cr ON c.parent_container_id = cr.id
+    )
+    SELECT r.id AS room_id, r.name AS room_name, SUM(i.purchase_price) AS value
+    FROM items i
+    LEFT JOIN container_roots cr ON cr.id = i.container_id
+    JOIN shelves s ON s.id = COALESCE(i.shelf_id, cr.shelf_id)
+    JOIN shelving_units u ON u.id = s.shelving_unit_id
+    JOIN rooms r ON r.id = u.room_id
+    WHERE i.purchase_price IS NOT NULL
+      AND i.deleted_at IS NULL
+      AND i.household_id = $2
+      AND COALESCE(i.currency, 'USD') = $1
+    GROUP BY r.id, r.name
+    ORDER BY value DESC, r.name
+"#;
+
+/// Total purchase value of the inventory in one currency (`?currency=`, default USD),
+/// broken down by room
+#[utoipa::path(
+    get,
+    path = "/api/stats/valuation",
+    tag = "stats",
+    params(
+        ValuationQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = ValuationResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn get_valuation(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<ValuationQuery>,
+) -> Result<Json<ValuationResponse>, ApiError> {
+    let currency = match params.currency {
+        Some(code) => parse_currency(&code).ok_or_else(|| {
+            ApiError::bad_request("currency must be a three-letter ISO 4217 code")
+        })?,
+        None => DEFAULT_CURRENCY.to_string(),
+    };
+
+    let household_id = state.resolve_household(user_id).await?;
+
+    let by_currency = sqlx::query_as::<_, CurrencyValuation>(VALUATION_BY_CURRENCY_SQL)
+        .bind(household_id)
+        .fetch_all(&state.db)
+        .await?;
+
+    let by_room = sqlx::query_as::<_, RoomValuation>(VALUATION_BY_ROOM_SQL)
+        .bind(&currency)
+        .bind(household_id)
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(Json(ValuationResponse::new(currency, by_currency, by_room)))
+}
+
+/// Create stats routes
+pub fn stats_routes() -> Router<Arc<AppState>> {
+    use axum::routing::get;
+
+    Router::new()
+        .route("/api/rooms/:id/stats", get(get_room_stats))
+        .route("/api/stats/overview", get(get_overview_stats))
+        .route("/api/stats/valuation", get(get_valuation))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> InventoryStats {
+        InventoryStats {
+            shelving_unit_count: 1,
+            shelf_count: 2,
+            container_count: 3,
+            item_count: 4,
+            photo_count: 5,
+            tag_count: 6,
+            total_value: None,
+        }
+    }
+
+    #[test]
+    fn test_fresh_stats() {
+        let computed_at = Instant::now();
+        let cached = Some((stats(), computed_at));
+
+        assert_eq!(fresh_stats(&cached, computed_at), Some(stats()));
+        assert_eq!(
+            fresh_stats(
+                &cached,
+                computed_at + OVERVIEW_STATS_TTL - Duration::from_secs(1)
+            ),
+            Some(stats())
+        );
+        assert_eq!(fresh_stats(&cached, computed_at + OVERVIEW_STATS_TTL), None);
+        assert_eq!(fresh_stats(&None, computed_at), None);
+    }
+
+    #[test]
+    fn test_missing_total_value_serializes_as_null() {
+        let json = serde_json::to_value(stats()).unwrap();
+        assert!(json["total_value"].is_null());
+        assert_eq!(json["item_count"], 4);
+    }
+}
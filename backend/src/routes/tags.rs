@@ -1,99 +1,292 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::Json,
     Router,
 };
+use serde::Deserialize;
 use serde_json::json;
+use sqlx::{PgConnection, Postgres, QueryBuilder};
 use std::sync::Arc;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 use crate::app::AppState;
+use crate::db::with_transaction;
+use crate::error::{ApiError, ErrorResponse};
 use crate::middleware::auth::AuthUser;
 use crate::models::{
-    AssignTagsRequest, BulkAssignTagsRequest, CreateTagRequest, PaginatedResponse, PaginationQuery,
-    Tag, TagResponse, UpdateTagRequest,
+    normalize_tag_name, AssignTagsRequest, BulkAssignTagsRequest, BulkCreateTagsRequest,
+    BulkCreateTagsResponse, ContainerResponse, CreateTagRequest, ItemResponse, PaginatedResponse,
+    Tag, TagAutocompleteQuery, TagDuplicateGroup, TagListQuery, TagMergeResponse, TagResponse,
+    TagSuggestion, TagsSideload, UpdateTagRequest,
 };
+use crate::services::audit::Auditable;
+use crate::services::household::{self as household_service, HouseholdEntity};
 
-/// Get all tags
+/// Most suggestions returned by the autocomplete endpoint
+const MAX_AUTOCOMPLETE_LIMIT: i32 = 50;
+
+/// A tag with its optional usage count
+#[derive(sqlx::FromRow)]
+struct TagRow {
+    #[sqlx(flatten)]
+    tag: Tag,
+    usage_count: Option<i32>,
+}
+
+/// `?include_tags=true` embeds each entity's tags in item and container
+/// responses, saving a `GET /api/tags/entity/...` call per row. Off by default.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IncludeTagsQuery {
+    #[serde(default)]
+    pub include_tags: bool,
+}
+
+/// Responses that can carry their entity's tags
+pub trait TagsTarget {
+    fn entity_id(&self) -> Uuid;
+    fn set_tags(&mut self, tags: Vec<TagResponse>);
+}
+
+impl TagsTarget for ItemResponse {
+    fn entity_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_tags(&mut self, tags: Vec<TagResponse>) {
+        self.tags = Some(tags);
+    }
+}
+
+impl TagsTarget for ContainerResponse {
+    fn entity_id(&self) -> Uuid {
+        self.id
+    }
+
+    fn set_tags(&mut self, tags: Vec<TagResponse>) {
+        self.tags = Some(tags);
+    }
+}
+
+/// Every tag on a set of entities, in one round trip
+const ENTITY_TAGS_SQL: &str = r#"
+    SELECT et.entity_id, t.*
+    FROM entity_tags et
+    JOIN tags t ON t.id = et.tag_id
+    WHERE et.entity_type = $1 AND et.entity_id = ANY($2)
+    ORDER BY et.entity_id, t.name ASC
+"#;
+
+#[derive(sqlx::FromRow)]
+struct EntityTagRow {
+    entity_id: Uuid,
+    #[sqlx(flatten)]
+    tag: Tag,
+}
+
+/// Tags for each of `entity_ids`, in the same order, fetched with a single query
+pub async fn fetch_tags_sideload(
+    state: &AppState,
+    entity_type: &str,
+    entity_ids: &[Uuid],
+) -> Result<Vec<TagsSideload>, ApiError> {
+    if entity_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let rows = sqlx::query_as::<_, EntityTagRow>(ENTITY_TAGS_SQL)
+        .bind(entity_type)
+        .bind(entity_ids)
+        .fetch_all(&state.db)
+        .await?;
+
+    Ok(TagsSideload::group(
+        entity_ids,
+        rows.into_iter()
+            .map(|row| (row.entity_id, row.tag))
+            .collect(),
+    ))
+}
+
+/// Fill in `tags` on a page of responses. Untagged entities get an empty list.
+pub async fn attach_tags<T: TagsTarget>(
+    state: &AppState,
+    entity_type: &str,
+    responses: &mut [T],
+) -> Result<(), ApiError> {
+    let ids: Vec<Uuid> = responses.iter().map(TagsTarget::entity_id).collect();
+    let sideloads = fetch_tags_sideload(state, entity_type, &ids).await?;
+
+    for (response, sideload) in responses.iter_mut().zip(sideloads) {
+        response.set_tags(sideload.tags);
+    }
+
+    Ok(())
+}
+
+/// Get all tags, optionally filtered by `q`
+#[utoipa::path(
+    get,
+    path = "/api/tags",
+    tag = "tags",
+    params(
+        TagListQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<TagResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn list_tags(
     State(state): State<Arc<AppState>>,
-    Query(params): Query<PaginationQuery>,
-) -> Result<Json<PaginatedResponse<TagResponse>>, StatusCode> {
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<TagListQuery>,
+) -> Result<Json<PaginatedResponse<TagResponse>>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
     let limit = params.limit.unwrap_or(100).clamp(1, 1000);
     let offset = params.offset.unwrap_or(0).max(0);
+    let search_pattern = params
+        .q
+        .as_deref()
+        .map(normalize_tag_name)
+        .filter(|q| !q.is_empty())
+        .map(|q| format!("%{}%", q));
 
     // Get total count
-    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tags")
-        .fetch_one(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to count tags: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM tags WHERE ($1::TEXT IS NULL OR name ILIKE $1)")
+            .bind(&search_pattern)
+            .fetch_one(&state.db)
+            .await?;
     let total = total.clamp(0, i32::MAX as i64) as i32;
 
-    // Get paginated tags
-    let tags = sqlx::query_as::<_, Tag>("SELECT * FROM tags ORDER BY name ASC LIMIT $1 OFFSET $2")
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch tags: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    // Get paginated tags, counting only the household's uses
+    let rows = sqlx::query_as::<_, TagRow>(&format!(
+        r#"
+        SELECT t.*,
+               CASE WHEN $2 THEN
+                   (SELECT COUNT(*)::INT FROM entity_tags et WHERE et.tag_id = t.id AND {})
+               END AS usage_count
+        FROM tags t
+        WHERE ($1::TEXT IS NULL OR t.name ILIKE $1)
+        ORDER BY t.name ASC
+        LIMIT $3 OFFSET $4
+        "#,
+        household_service::attached_in_household_sql("et", 5)
+    ))
+    .bind(&search_pattern)
+    .bind(params.include_usage_count.unwrap_or(false))
+    .bind(limit)
+    .bind(offset)
+    .bind(household_id)
+    .fetch_all(&state.db)
+    .await?;
 
-    let responses: Vec<TagResponse> = tags.into_iter().map(TagResponse::from).collect();
+    let responses: Vec<TagResponse> = rows
+        .into_iter()
+        .map(|row| TagResponse {
+            usage_count: row.usage_count,
+            ..TagResponse::from(row.tag)
+        })
+        .collect();
     Ok(Json(PaginatedResponse::new(
         responses, total, limit, offset,
     )))
 }
 
+/// Tag names containing `q` for typeahead, names starting with it first
+#[utoipa::path(
+    get,
+    path = "/api/tags/autocomplete",
+    tag = "tags",
+    params(
+        TagAutocompleteQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<TagSuggestion>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn autocomplete_tags(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<TagAutocompleteQuery>,
+) -> Result<Json<Vec<TagSuggestion>>, ApiError> {
+    let limit = params.limit.unwrap_or(10).clamp(1, MAX_AUTOCOMPLETE_LIMIT);
+    let q = normalize_tag_name(&params.q);
+    if q.is_empty() {
+        return Ok(Json(Vec::new()));
+    }
+
+    let suggestions = sqlx::query_as::<_, TagSuggestion>(
+        r#"
+        SELECT id, name
+        FROM tags
+        WHERE name ILIKE '%' || $1 || '%'
+        ORDER BY name ILIKE $1 || '%' DESC, name ASC
+        LIMIT $2
+        "#,
+    )
+    .bind(&q)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(suggestions))
+}
+
 /// Get a single tag by ID
+#[utoipa::path(
+    get,
+    path = "/api/tags/{id}",
+    tag = "tags",
+    params(
+        ("id" = Uuid, Path, description = "Tag id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = TagResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Tag not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_tag(
     State(state): State<Arc<AppState>>,
     Path(id): Path<Uuid>,
-) -> Result<Json<TagResponse>, StatusCode> {
+) -> Result<Json<TagResponse>, ApiError> {
     let tag = sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE id = $1")
         .bind(id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch tag: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::not_found("Tag", id))?;
 
     Ok(Json(TagResponse::from(tag)))
 }
 
 /// Create a new tag
+#[utoipa::path(
+    post,
+    path = "/api/tags",
+    tag = "tags",
+    request_body = CreateTagRequest,
+    responses(
+        (status = 200, description = "OK", body = TagResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 409, description = "A tag with this name already exists", body = ErrorResponse)
+    )
+)]
 pub async fn create_tag(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Json(payload): Json<CreateTagRequest>,
-) -> Result<Json<TagResponse>, StatusCode> {
+) -> Result<Json<TagResponse>, ApiError> {
     // Validate tag name
-    let name = payload.name.trim();
+    let name = normalize_tag_name(&payload.name);
     if name.is_empty() || name.len() > 100 {
-        return Err(StatusCode::BAD_REQUEST);
-    }
-
-    // Check if tag with same name already exists
-    let existing = sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE LOWER(name) = LOWER($1)")
-        .bind(name)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to check existing tag: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-
-    if existing.is_some() {
-        return Err(StatusCode::CONFLICT);
+        return Err(ApiError::bad_request("Tag name must be 1-100 characters"));
     }
 
+    // The unique index on tags.name catches duplicates, including concurrent creates
     let tag = sqlx::query_as::<_, Tag>(
         r#"
         INSERT INTO tags (id, name)
@@ -102,13 +295,10 @@ pub async fn create_tag(
         "#,
     )
     .bind(Uuid::new_v4())
-    .bind(name)
+    .bind(&name)
     .fetch_one(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!("Failed to create tag: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .map_err(|e| tag_name_conflict(e, &name))?;
 
     // Log audit
     state
@@ -120,56 +310,156 @@ pub async fn create_tag(
     Ok(Json(TagResponse::from(tag)))
 }
 
+/// 409 naming the tag when `err` is a violation of the unique index on `tags.name`
+fn tag_name_conflict(err: sqlx::Error, name: &str) -> ApiError {
+    match err {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+            ApiError::conflict(format!("Tag '{}' already exists", name))
+        }
+        err => ApiError::from(err),
+    }
+}
+
+/// Insert the tags in `names` that don't exist yet and fetch the ones that do, both in
+/// the order of `names`
+async fn insert_missing_tags(
+    conn: &mut PgConnection,
+    names: &[String],
+) -> Result<(Vec<Tag>, Vec<Tag>), ApiError> {
+    let mut insert = QueryBuilder::<Postgres>::new("INSERT INTO tags (id, name) ");
+    insert.push_values(names, |mut row, name| {
+        row.push_bind(Uuid::new_v4()).push_bind(name);
+    });
+    insert.push(" ON CONFLICT (name) DO NOTHING RETURNING *");
+    let mut created = insert.build_query_as::<Tag>().fetch_all(&mut *conn).await?;
+
+    // Every name came back, so none of them existed before
+    let mut existing: Vec<Tag> = if created.len() == names.len() {
+        Vec::new()
+    } else {
+        let missing: Vec<String> = names
+            .iter()
+            .filter(|name| !created.iter().any(|tag| &tag.name == *name))
+            .cloned()
+            .collect();
+        sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE name = ANY($1)")
+            .bind(missing)
+            .fetch_all(&mut *conn)
+            .await?
+    };
+
+    let position = |tag: &Tag| names.iter().position(|name| *name == tag.name);
+    created.sort_by_key(position);
+    existing.sort_by_key(position);
+    Ok((created, existing))
+}
+
+/// Create several tags at once; names that are already tags are returned as they are
+#[utoipa::path(
+    post,
+    path = "/api/tags/bulk",
+    tag = "tags",
+    request_body = BulkCreateTagsRequest,
+    responses(
+        (status = 200, description = "OK", body = BulkCreateTagsResponse),
+        (status = 400, description = "No names, an invalid name or more than 50 tags", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn bulk_create_tags(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Json(payload): Json<BulkCreateTagsRequest>,
+) -> Result<Json<BulkCreateTagsResponse>, ApiError> {
+    let names = payload.normalized_names().map_err(ApiError::bad_request)?;
+
+    let (created, existing) = with_transaction(&state.db, move |conn| {
+        Box::pin(async move { insert_missing_tags(conn, &names).await })
+    })
+    .await?;
+
+    // One entry for the whole batch rather than one per tag
+    if !created.is_empty() {
+        let created_ids: Vec<Uuid> = created.iter().map(|tag| tag.id).collect();
+        state
+            .audit
+            .log_create(
+                "tag_batch",
+                Uuid::new_v4(),
+                Some(user_id),
+                Some(json!({
+                    "tag_ids": created_ids,
+                    "already_existed": existing.len(),
+                })),
+            )
+            .await
+            .ok();
+    }
+
+    Ok(Json(BulkCreateTagsResponse {
+        created: created.into_iter().map(TagResponse::from).collect(),
+        already_existed: existing.into_iter().map(TagResponse::from).collect(),
+    }))
+}
+
 /// Update a tag
+#[utoipa::path(
+    put,
+    path = "/api/tags/{id}",
+    tag = "tags",
+    params(
+        ("id" = Uuid, Path, description = "Tag id")
+    ),
+    request_body = UpdateTagRequest,
+    responses(
+        (status = 200, description = "OK", body = TagResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Tag not found", body = ErrorResponse)
+    )
+)]
 pub async fn update_tag(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateTagRequest>,
-) -> Result<Json<TagResponse>, StatusCode> {
+) -> Result<Json<TagResponse>, ApiError> {
     // Check if tag exists
     let existing = sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE id = $1")
         .bind(id)
         .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch tag: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
-        .ok_or(StatusCode::NOT_FOUND)?;
+        .await?
+        .ok_or_else(|| ApiError::not_found("Tag", id))?;
 
     // If name is being updated, validate it
     let name = if let Some(ref new_name) = payload.name {
-        let trimmed = new_name.trim();
-        if trimmed.is_empty() || trimmed.len() > 100 {
-            return Err(StatusCode::BAD_REQUEST);
+        let normalized = normalize_tag_name(new_name);
+        if normalized.is_empty() || normalized.len() > 100 {
+            return Err(ApiError::bad_request("Tag name must be 1-100 characters"));
         }
 
         // Check if another tag with same name exists
-        let conflict = sqlx::query_as::<_, Tag>(
-            "SELECT * FROM tags WHERE LOWER(name) = LOWER($1) AND id != $2",
-        )
-        .bind(trimmed)
-        .bind(id)
-        .fetch_optional(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to check tag name conflict: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        let conflict = sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE name = $1 AND id != $2")
+            .bind(&normalized)
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?;
 
         if conflict.is_some() {
-            return Err(StatusCode::CONFLICT);
+            return Err(ApiError::conflict(format!(
+                "Tag '{}' already exists",
+                normalized
+            )));
         }
 
-        trimmed.to_string()
+        normalized
     } else {
         existing.name.clone()
     };
 
     // Track changes for audit
     let mut changes = serde_json::Map::new();
-    if payload.name.is_some() && payload.name.as_ref() != Some(&existing.name) {
+    if name != existing.name {
         changes.insert(
             "name".to_string(),
             serde_json::json!({
@@ -190,11 +480,7 @@ pub async fn update_tag(
     .bind(&name)
     .bind(id)
     .fetch_one(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to update tag: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     // Log audit
     if !changes.is_empty() {
@@ -215,11 +501,24 @@ pub async fn update_tag(
 }
 
 /// Delete a tag
+#[utoipa::path(
+    delete,
+    path = "/api/tags/{id}",
+    tag = "tags",
+    params(
+        ("id" = Uuid, Path, description = "Tag id")
+    ),
+    responses(
+        (status = 200, description = "OK"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Tag not found", body = ErrorResponse)
+    )
+)]
 pub async fn delete_tag(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     // Log audit before deletion
     state
         .audit
@@ -230,29 +529,207 @@ pub async fn delete_tag(
     let result = sqlx::query("DELETE FROM tags WHERE id = $1")
         .bind(id)
         .execute(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to delete tag: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
     if result.rows_affected() == 0 {
-        return Err(StatusCode::NOT_FOUND);
+        return Err(ApiError::not_found("Tag", id));
     }
 
     Ok(Json(json!({ "message": "Tag deleted successfully" })))
 }
 
+/// Merge a tag into another: every entity of the user's household with the tag gets
+/// the target tag instead. Entities that already had both keep a single tag. Tags are
+/// shared between households, so the merged tag is only deleted once no other
+/// household uses it.
+#[utoipa::path(
+    post,
+    path = "/api/tags/{id}/merge-into/{target_id}",
+    tag = "tags",
+    params(
+        ("id" = Uuid, Path, description = "Tag to merge and delete"),
+        ("target_id" = Uuid, Path, description = "Tag to keep")
+    ),
+    responses(
+        (status = 200, description = "OK", body = TagMergeResponse),
+        (status = 400, description = "A tag can't be merged into itself", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Tag not found", body = ErrorResponse)
+    )
+)]
+pub async fn merge_tag(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path((source_id, target_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<TagMergeResponse>, ApiError> {
+    if source_id == target_id {
+        return Err(ApiError::bad_request("A tag can't be merged into itself"));
+    }
+
+    let household_id = state.resolve_household(user_id).await?;
+    let mut tx = state.db.begin().await?;
+
+    for id in [source_id, target_id] {
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM tags WHERE id = $1)")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if !exists {
+            return Err(ApiError::not_found("Tag", id));
+        }
+    }
+
+    // Copy the source tag's assignments in the household to the target, skipping
+    // entities that already have it, then drop the source's
+    sqlx::query(&format!(
+        r#"
+        INSERT INTO entity_tags (entity_type, entity_id, tag_id, updated_at)
+        SELECT et.entity_type, et.entity_id, $2, NOW() FROM entity_tags et
+        WHERE et.tag_id = $1 AND {}
+        ON CONFLICT DO NOTHING
+        "#,
+        household_service::attached_in_household_sql("et", 3)
+    ))
+    .bind(source_id)
+    .bind(target_id)
+    .bind(household_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let merged = sqlx::query(&format!(
+        "DELETE FROM entity_tags et WHERE et.tag_id = $1 AND {}",
+        household_service::attached_in_household_sql("et", 2)
+    ))
+    .bind(source_id)
+    .bind(household_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let deleted = sqlx::query(
+        "DELETE FROM tags WHERE id = $1 AND NOT EXISTS (SELECT 1 FROM entity_tags WHERE tag_id = $1)",
+    )
+    .bind(source_id)
+    .execute(&mut *tx)
+    .await?;
+    let deleted_tag_id = (deleted.rows_affected() > 0).then_some(source_id);
+
+    tx.commit().await?;
+
+    let merged_entity_count = merged.rows_affected() as i64;
+    if deleted_tag_id.is_some() {
+        state
+            .audit
+            .log_delete(
+                "tag",
+                source_id,
+                Some(user_id),
+                Some(json!({ "merged_into": target_id })),
+            )
+            .await
+            .ok();
+    }
+    state
+        .audit
+        .log_update(
+            "tag",
+            target_id,
+            Some(user_id),
+            json!({
+                "merged_from": source_id,
+                "merged_entity_count": merged_entity_count,
+            }),
+            None,
+        )
+        .await
+        .ok();
+
+    Ok(Json(TagMergeResponse {
+        merged_entity_count,
+        deleted_tag_id,
+    }))
+}
+
+/// Groups of tags whose names only differ in case, spacing or punctuation, as
+/// candidates for merging. Each tag comes with its usage count.
+#[utoipa::path(
+    get,
+    path = "/api/tags/duplicates",
+    tag = "tags",
+    responses(
+        (status = 200, description = "OK", body = Vec<TagDuplicateGroup>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn list_duplicate_tags(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+) -> Result<Json<Vec<TagDuplicateGroup>>, ApiError> {
+    let household_id = state.resolve_household(user_id).await?;
+    let rows = sqlx::query_as::<_, TagRow>(&format!(
+        r#"
+        SELECT t.*,
+               (SELECT COUNT(*)::INT FROM entity_tags et WHERE et.tag_id = t.id AND {}) AS usage_count
+        FROM tags t
+        ORDER BY t.name ASC
+        "#,
+        household_service::attached_in_household_sql("et", 1)
+    ))
+    .bind(household_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let tags = rows
+        .into_iter()
+        .map(|row| TagResponse {
+            usage_count: row.usage_count,
+            ..TagResponse::from(row.tag)
+        })
+        .collect();
+    Ok(Json(TagDuplicateGroup::find(tags)))
+}
+
+/// 404 unless the tagged entity is in household `household_id`
+async fn authorize_tagged(
+    state: &AppState,
+    household_id: Uuid,
+    entity_type: &str,
+    entity_id: Uuid,
+) -> Result<(), ApiError> {
+    let entity = HouseholdEntity::from_entity_type(entity_type)
+        .ok_or_else(|| ApiError::BadRequest(format!("Invalid entity type: {}", entity_type)))?;
+    household_service::ensure_in_household(&state.db, household_id, entity, entity_id).await
+}
+
 /// Get tags for a specific entity
+#[utoipa::path(
+    get,
+    path = "/api/tags/entity/{entity_type}/{entity_id}",
+    tag = "tags",
+    params(
+        ("entity_type" = String, Path, description = "Entity type, e.g. `item`"),
+        ("entity_id" = Uuid, Path, description = "Entity id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<TagResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Entity not found", body = ErrorResponse)
+    )
+)]
 pub async fn get_entity_tags(
     State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
     Path((entity_type, entity_id)): Path<(String, Uuid)>,
-) -> Result<Json<Vec<TagResponse>>, StatusCode> {
+) -> Result<Json<Vec<TagResponse>>, ApiError> {
     // Validate entity_type
     let valid_types = ["room", "unit", "shelf", "container", "item"];
     if !valid_types.contains(&entity_type.as_str()) {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::BadRequest(format!(
+            "Invalid entity type: {}",
+            entity_type
+        )));
     }
+    let household_id = state.resolve_household(user_id).await?;
+    authorize_tagged(&state, household_id, &entity_type, entity_id).await?;
 
     let tags = sqlx::query_as::<_, Tag>(
         r#"
@@ -265,65 +742,70 @@ pub async fn get_entity_tags(
     .bind(&entity_type)
     .bind(entity_id)
     .fetch_all(&state.db)
-    .await
-    .map_err(|e| {
-        tracing::error!("Failed to fetch entity tags: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    .await?;
 
     let responses: Vec<TagResponse> = tags.into_iter().map(TagResponse::from).collect();
     Ok(Json(responses))
 }
 
 /// Assign tags to an entity
+#[utoipa::path(
+    post,
+    path = "/api/tags/assign",
+    tag = "tags",
+    request_body = AssignTagsRequest,
+    responses(
+        (status = 200, description = "OK"),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Entity not found", body = ErrorResponse)
+    )
+)]
 pub async fn assign_tags(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Json(payload): Json<AssignTagsRequest>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     // Validate entity_type
     let valid_types = ["room", "unit", "shelf", "container", "item"];
     if !valid_types.contains(&payload.entity_type.as_str()) {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::BadRequest(format!(
+            "Invalid entity type: {}",
+            payload.entity_type
+        )));
     }
+    let household_id = state.resolve_household(user_id).await?;
+    authorize_tagged(
+        &state,
+        household_id,
+        &payload.entity_type,
+        payload.entity_id,
+    )
+    .await?;
 
     // Start transaction
-    let mut tx = state.db.begin().await.map_err(|e| {
-        tracing::error!("Failed to start transaction: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let mut tx = state.db.begin().await?;
 
     // Remove existing tags for this entity
     sqlx::query("DELETE FROM entity_tags WHERE entity_type = $1 AND entity_id = $2")
         .bind(&payload.entity_type)
         .bind(payload.entity_id)
         .execute(&mut *tx)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to remove existing tags: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
     // Insert new tags
     for tag_id in &payload.tag_ids {
         sqlx::query(
-            "INSERT INTO entity_tags (entity_type, entity_id, tag_id) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+            "INSERT INTO entity_tags (entity_type, entity_id, tag_id, updated_at) VALUES ($1, $2, $3, NOW()) ON CONFLICT DO NOTHING",
         )
         .bind(&payload.entity_type)
         .bind(payload.entity_id)
         .bind(tag_id)
         .execute(&mut *tx)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to assign tag: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
     }
 
-    tx.commit().await.map_err(|e| {
-        tracing::error!("Failed to commit transaction: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    tx.commit().await?;
 
     // Log audit
     state
@@ -344,22 +826,38 @@ pub async fn assign_tags(
 }
 
 /// Bulk assign tags to multiple entities
+#[utoipa::path(
+    post,
+    path = "/api/tags/bulk-assign",
+    tag = "tags",
+    request_body = BulkAssignTagsRequest,
+    responses(
+        (status = 200, description = "OK"),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Entity not found", body = ErrorResponse)
+    )
+)]
 pub async fn bulk_assign_tags(
     State(state): State<Arc<AppState>>,
     AuthUser(user_id): AuthUser,
     Json(payload): Json<BulkAssignTagsRequest>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<serde_json::Value>, ApiError> {
     // Validate entity_type
     let valid_types = ["room", "unit", "shelf", "container", "item"];
     if !valid_types.contains(&payload.entity_type.as_str()) {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(ApiError::BadRequest(format!(
+            "Invalid entity type: {}",
+            payload.entity_type
+        )));
+    }
+    let household_id = state.resolve_household(user_id).await?;
+    for entity_id in &payload.entity_ids {
+        authorize_tagged(&state, household_id, &payload.entity_type, *entity_id).await?;
     }
 
     // Start transaction
-    let mut tx = state.db.begin().await.map_err(|e| {
-        tracing::error!("Failed to start transaction: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    let mut tx = state.db.begin().await?;
 
     // For each entity, assign the tags
     for entity_id in &payload.entity_ids {
@@ -368,26 +866,18 @@ pub async fn bulk_assign_tags(
             .bind(&payload.entity_type)
             .bind(entity_id)
             .execute(&mut *tx)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to remove existing tags: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .await?;
 
         // Insert new tags
         for tag_id in &payload.tag_ids {
             sqlx::query(
-                "INSERT INTO entity_tags (entity_type, entity_id, tag_id) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+                "INSERT INTO entity_tags (entity_type, entity_id, tag_id, updated_at) VALUES ($1, $2, $3, NOW()) ON CONFLICT DO NOTHING",
             )
             .bind(&payload.entity_type)
             .bind(entity_id)
             .bind(tag_id)
             .execute(&mut *tx)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to assign tag: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .await?;
         }
 
         // Log audit for each entity
@@ -406,10 +896,7 @@ pub async fn bulk_assign_tags(
             .ok();
     }
 
-    tx.commit().await.map_err(|e| {
-        tracing::error!("Failed to commit transaction: {:?}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
+    tx.commit().await?;
 
     Ok(Json(json!({
         "message": format!("Tags assigned to {} entities", payload.entity_ids.len())
@@ -422,6 +909,10 @@ pub fn tag_routes() -> Router<Arc<AppState>> {
 
     Router::new()
         .route("/api/tags", get(list_tags).post(create_tag))
+        // Specific routes MUST come before parameterized routes
+        .route("/api/tags/bulk", post(bulk_create_tags))
+        .route("/api/tags/autocomplete", get(autocomplete_tags))
+        .route("/api/tags/duplicates", get(list_duplicate_tags))
         .route(
             "/api/tags/:id",
             get(get_tag).put(update_tag).delete(delete_tag),
@@ -432,4 +923,102 @@ pub fn tag_routes() -> Router<Arc<AppState>> {
         )
         .route("/api/tags/assign", post(assign_tags))
         .route("/api/tags/bulk-assign", post(bulk_assign_tags))
+        .route("/api/tags/:id/merge-into/:target_id", post(merge_tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_pool, test_app_state};
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_merge_tag_only_retags_own_household() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let (user_id, neighbor_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (room_id, neighbor_room_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (source_id, target_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        for (id, owner) in [(room_id, user_id), (neighbor_room_id, neighbor_id)] {
+            let household_id = state.resolve_household(owner).await.unwrap();
+            sqlx::query(
+                "INSERT INTO rooms (id, name, created_by, household_id) VALUES ($1, 'Merge test room', $2, $3)",
+            )
+            .bind(id)
+            .bind(owner)
+            .bind(household_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        for id in [source_id, target_id] {
+            sqlx::query("INSERT INTO tags (id, name) VALUES ($1, $2)")
+                .bind(id)
+                .bind(format!("merge-test-{}", id))
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        for room in [room_id, neighbor_room_id] {
+            sqlx::query(
+                "INSERT INTO entity_tags (entity_type, entity_id, tag_id, updated_at) VALUES ('room', $1, $2, NOW())",
+            )
+            .bind(room)
+            .bind(source_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let merged = merge_tag(
+            State(state.clone()),
+            AuthUser(user_id),
+            Path((source_id, target_id)),
+        )
+        .await;
+        let tags_after_merge: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            "SELECT entity_id, tag_id FROM entity_tags WHERE entity_id = ANY($1) ORDER BY tag_id = $2",
+        )
+        .bind(vec![room_id, neighbor_room_id])
+        .bind(target_id)
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let neighbor_merged = merge_tag(
+            State(state.clone()),
+            AuthUser(neighbor_id),
+            Path((source_id, target_id)),
+        )
+        .await;
+
+        sqlx::query("DELETE FROM entity_tags WHERE entity_id = ANY($1)")
+            .bind(vec![room_id, neighbor_room_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM tags WHERE id = ANY($1)")
+            .bind(vec![source_id, target_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM rooms WHERE id = ANY($1)")
+            .bind(vec![room_id, neighbor_room_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // The neighbor's room keeps the tag, so it isn't deleted yet
+        let Json(merged) = merged.unwrap();
+        assert_eq!(merged.merged_entity_count, 1);
+        assert_eq!(merged.deleted_tag_id, None);
+        assert_eq!(
+            tags_after_merge,
+            vec![(neighbor_room_id, source_id), (room_id, target_id)]
+        );
+
+        let Json(neighbor_merged) = neighbor_merged.unwrap();
+        assert_eq!(neighbor_merged.merged_entity_count, 1);
+        assert_eq!(neighbor_merged.deleted_tag_id, Some(source_id));
+    }
 }
@@ -1,25 +1,46 @@
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
+    extract::{Path, Query, State},
     response::Json,
     Router,
 };
 use serde::Deserialize;
+use serde_json::json;
 use std::sync::Arc;
+use utoipa::IntoParams;
+use uuid::Uuid;
 
 use crate::app::AppState;
-use crate::models::User;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::{
+    Item, ItemResponse, PaginatedResponse, PaginationQuery, PublicItemResponse, PublicUserResponse,
+    UpdateProfileRequest, User, UserStats, MAX_PUBLIC_PROFILE_ITEMS,
+};
+use crate::services::audit::Auditable;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct UsersQuery {
     pub search: Option<String>,
 }
 
 /// List all users (with optional search)
+#[utoipa::path(
+    get,
+    path = "/api/users",
+    tag = "users",
+    params(
+        UsersQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = Vec<User>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
 pub async fn list_users(
     State(state): State<Arc<AppState>>,
     Query(params): Query<UsersQuery>,
-) -> Result<Json<Vec<User>>, StatusCode> {
+) -> Result<Json<Vec<User>>, ApiError> {
     let users = if let Some(search) = params.search {
         let pattern = format!("%{}%", search.trim());
         sqlx::query_as::<_, User>(
@@ -27,27 +48,462 @@ pub async fn list_users(
         )
         .bind(&pattern)
         .fetch_all(&state.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to search users: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .await?
     } else {
         sqlx::query_as::<_, User>("SELECT * FROM users ORDER BY name LIMIT 100")
             .fetch_all(&state.db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to fetch users: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?
+            .await?
     };
 
     Ok(Json(users))
 }
 
+/// Whether `email` is the configured `ADMIN_EMAIL`
+fn is_admin_email(admin_email: Option<&str>, email: &str) -> bool {
+    admin_email.is_some_and(|admin| admin.eq_ignore_ascii_case(email.trim()))
+}
+
+/// Whether `user_id` is the user whose email matches `ADMIN_EMAIL`. Always false
+/// when no admin is configured.
+pub(crate) async fn is_admin(state: &AppState, user_id: Uuid) -> Result<bool, ApiError> {
+    let Some(admin_email) = state.admin_email.as_deref() else {
+        return Ok(false);
+    };
+
+    let email: Option<String> = sqlx::query_scalar("SELECT email FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_optional(&state.db)
+        .await?;
+    Ok(email.is_some_and(|email| is_admin_email(Some(admin_email), &email)))
+}
+
+/// Reject anyone but the `ADMIN_EMAIL` user with 403
+pub(crate) async fn ensure_admin(state: &AppState, user_id: Uuid) -> Result<(), ApiError> {
+    if is_admin(state, user_id).await? {
+        return Ok(());
+    }
+
+    tracing::warn!("User {} attempted an admin-only action", user_id);
+    Err(ApiError::Forbidden(
+        "Only the administrator can do this".to_string(),
+    ))
+}
+
+/// Users may only look at their own items and stats, unless they are the admin
+async fn ensure_can_view_user(
+    state: &AppState,
+    requester_id: Uuid,
+    target_id: Uuid,
+) -> Result<(), ApiError> {
+    if requester_id == target_id || is_admin(state, requester_id).await? {
+        return Ok(());
+    }
+
+    tracing::warn!(
+        "User {} may not view items or stats of user {}",
+        requester_id,
+        target_id
+    );
+    Err(ApiError::Forbidden(
+        "You can only view your own items and stats".to_string(),
+    ))
+}
+
+/// Items of the caller's household belonging to a user (`belongs_to_user_id`), newest
+/// first
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/items",
+    tag = "users",
+    params(
+        ("id" = Uuid, Path, description = "User id"),
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<ItemResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn list_user_items(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<ItemResponse>>, ApiError> {
+    ensure_can_view_user(&state, user_id, id).await?;
+
+    let limit = params.limit.unwrap_or(50).clamp(1, 1000);
+    let offset = params.offset.unwrap_or(0).max(0);
+    let search_pattern = params.search.as_ref().map(|s| format!("%{}%", s.trim()));
+    let household_id = state.resolve_household(user_id).await?;
+
+    let total: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM items
+        WHERE belongs_to_user_id = $1 AND household_id = $3 AND deleted_at IS NULL
+          AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2 OR barcode ILIKE $2)
+        "#,
+    )
+    .bind(id)
+    .bind(&search_pattern)
+    .bind(household_id)
+    .fetch_one(&state.db)
+    .await?;
+    let total = total.clamp(0, i32::MAX as i64) as i32;
+
+    let items = sqlx::query_as::<_, Item>(
+        r#"
+        SELECT * FROM items
+        WHERE belongs_to_user_id = $1 AND household_id = $5 AND deleted_at IS NULL
+          AND ($2::TEXT IS NULL OR name ILIKE $2 OR description ILIKE $2 OR barcode ILIKE $2)
+        ORDER BY created_at DESC, id DESC
+        LIMIT $3 OFFSET $4
+        "#,
+    )
+    .bind(id)
+    .bind(&search_pattern)
+    .bind(limit)
+    .bind(offset)
+    .bind(household_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let responses: Vec<ItemResponse> = items.into_iter().map(ItemResponse::from).collect();
+    Ok(Json(PaginatedResponse::new(
+        responses, total, limit, offset,
+    )))
+}
+
+/// `$1` is the user and `$2` the caller's household
+const USER_STATS_SQL: &str = r#"
+    SELECT
+        (SELECT COUNT(*)::INT FROM items WHERE belongs_to_user_id = $1 AND household_id = $2 AND deleted_at IS NULL) AS items_owned,
+        (SELECT COUNT(*)::INT FROM items WHERE created_by = $1 AND household_id = $2 AND deleted_at IS NULL) AS items_created,
+        (SELECT COUNT(*)::INT FROM rooms WHERE created_by = $1 AND household_id = $2) AS rooms_created,
+        (SELECT COUNT(*)::INT FROM containers WHERE created_by = $1 AND household_id = $2) AS containers_created
+"#;
+
+/// Counts of what a user owns and has created in the caller's household, in one
+/// round trip
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/stats",
+    tag = "users",
+    params(
+        ("id" = Uuid, Path, description = "User id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = UserStats),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 403, description = "Neither the user nor an admin", body = ErrorResponse)
+    )
+)]
+pub async fn get_user_stats(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UserStats>, ApiError> {
+    ensure_can_view_user(&state, user_id, id).await?;
+    let household_id = state.resolve_household(user_id).await?;
+
+    let stats = sqlx::query_as::<_, UserStats>(USER_STATS_SQL)
+        .bind(id)
+        .bind(household_id)
+        .fetch_one(&state.db)
+        .await?;
+
+    Ok(Json(stats))
+}
+
+/// Live items a user created or owns. `$1` is the user.
+const PUBLIC_ITEM_COUNT_SQL: &str = r#"
+    SELECT COUNT(*)::INT FROM items
+    WHERE (created_by = $1 OR belongs_to_user_id = $1) AND deleted_at IS NULL
+"#;
+
+/// The newest `$2` of those items, each with its creator's name as the public item
+/// view shows it
+const PUBLIC_ITEMS_SQL: &str = r#"
+    SELECT i.id, i.name, i.product_link, COALESCE(u.public_display_name, u.name) AS owner_display_name
+    FROM items i
+    INNER JOIN users u ON i.created_by = u.id
+    WHERE (i.created_by = $1 OR i.belongs_to_user_id = $1) AND i.deleted_at IS NULL
+    ORDER BY i.created_at DESC, i.id DESC
+    LIMIT $2
+"#;
+
+/// Public profile of a user who has opted in (no authentication required)
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/public",
+    tag = "users",
+    params(
+        ("id" = Uuid, Path, description = "User id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = PublicUserResponse),
+        (status = 404, description = "User not found or profile not public", body = ErrorResponse)
+    ),
+    security(())
+)]
+pub async fn get_public_user(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PublicUserResponse>, ApiError> {
+    // A private profile is indistinguishable from a missing user
+    let public_display_name: Option<String> = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT public_display_name FROM users WHERE id = $1 AND is_public",
+    )
+    .bind(id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("User", id))?;
+
+    let item_count: i32 = sqlx::query_scalar(PUBLIC_ITEM_COUNT_SQL)
+        .bind(id)
+        .fetch_one(&state.db)
+        .await?;
+
+    let public_items =
+        sqlx::query_as::<_, (Uuid, String, Option<String>, String)>(PUBLIC_ITEMS_SQL)
+            .bind(id)
+            .bind(MAX_PUBLIC_PROFILE_ITEMS)
+            .fetch_all(&state.db)
+            .await?
+            .into_iter()
+            .map(
+                |(id, name, product_link, owner_display_name)| PublicItemResponse {
+                    id,
+                    name,
+                    owner_display_name,
+                    product_link,
+                },
+            )
+            .collect();
+
+    Ok(Json(PublicUserResponse {
+        id,
+        public_display_name,
+        item_count,
+        public_items,
+    }))
+}
+
+/// Set the signed-in user's public display name and whether their profile is public
+#[utoipa::path(
+    put,
+    path = "/api/users/me/profile",
+    tag = "users",
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "OK", body = User),
+        (status = 400, description = "Display name too long", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn update_my_profile(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Json(payload): Json<UpdateProfileRequest>,
+) -> Result<Json<User>, ApiError> {
+    let public_display_name = payload.display_name().map_err(ApiError::bad_request)?;
+
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        UPDATE users
+        SET public_display_name = $2, is_public = $3, updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(user_id)
+    .bind(&public_display_name)
+    .bind(payload.is_public)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| ApiError::not_found("User", user_id))?;
+
+    state
+        .audit
+        .log_update(
+            "user",
+            user_id,
+            Some(user_id),
+            json!({
+                "public_display_name": public_display_name,
+                "is_public": payload.is_public,
+            }),
+            None,
+        )
+        .await
+        .ok();
+
+    Ok(Json(user))
+}
+
 /// Create user routes
 pub fn user_routes() -> Router<Arc<AppState>> {
-    use axum::routing::get;
+    use axum::routing::{get, put};
+
+    Router::new()
+        .route("/api/users", get(list_users))
+        .route("/api/users/me/profile", put(update_my_profile))
+        .route("/api/users/:id/items", get(list_user_items))
+        .route("/api/users/:id/stats", get(get_user_stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_test_pool, test_app_state};
+
+    #[test]
+    fn test_is_admin_email() {
+        assert!(is_admin_email(
+            Some("admin@example.com"),
+            "Admin@Example.com"
+        ));
+        assert!(!is_admin_email(
+            Some("admin@example.com"),
+            "someone@example.com"
+        ));
+        assert!(!is_admin_email(None, "admin@example.com"));
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_public_profile_only_once_opted_in() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let (user_id, friend_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (created_id, owned_id, trashed_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        // Sam predates is_public, which reads as private
+        for (id, name, is_public) in [(user_id, "Pat", Some(false)), (friend_id, "Sam", None)] {
+            sqlx::query(
+                "INSERT INTO users (id, email, name, google_id, is_public) VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(id)
+            .bind(format!("{}@example.com", id))
+            .bind(name)
+            .bind(id.to_string())
+            .bind(is_public)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        // One the user created, one someone else created for them, one in the trash
+        for (id, created_by, belongs_to, deleted) in [
+            (created_id, user_id, None, false),
+            (owned_id, friend_id, Some(user_id), false),
+            (trashed_id, user_id, None, true),
+        ] {
+            sqlx::query(
+                "INSERT INTO items (id, shelf_id, name, created_by, belongs_to_user_id, deleted_at) VALUES ($1, $2, 'Ladder', $3, $4, CASE WHEN $5 THEN NOW() END)",
+            )
+            .bind(id)
+            .bind(Uuid::new_v4())
+            .bind(created_by)
+            .bind(belongs_to)
+            .bind(deleted)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let private = get_public_user(State(state.clone()), Path(user_id)).await;
+        let updated = update_my_profile(
+            State(state.clone()),
+            AuthUser(user_id),
+            Json(UpdateProfileRequest {
+                public_display_name: Some(" Pat's Workshop ".to_string()),
+                is_public: true,
+            }),
+        )
+        .await;
+        let public = get_public_user(State(state.clone()), Path(user_id)).await;
+        let friend = get_public_user(State(state.clone()), Path(friend_id)).await;
+
+        sqlx::query("DELETE FROM items WHERE id = ANY($1)")
+            .bind(vec![created_id, owned_id, trashed_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = ANY($1)")
+            .bind(vec![user_id, friend_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(private.unwrap_err(), ApiError::not_found("User", user_id));
+        assert_eq!(friend.unwrap_err(), ApiError::not_found("User", friend_id));
+
+        let updated = updated.unwrap().0;
+        assert!(updated.is_public);
+        assert_eq!(
+            updated.public_display_name.as_deref(),
+            Some("Pat's Workshop")
+        );
+
+        let public = public.unwrap().0;
+        assert_eq!(
+            public.public_display_name.as_deref(),
+            Some("Pat's Workshop")
+        );
+        assert_eq!(public.item_count, 2);
+        let mut item_ids: Vec<Uuid> = public.public_items.iter().map(|item| item.id).collect();
+        item_ids.sort();
+        let mut expected = vec![created_id, owned_id];
+        expected.sort();
+        assert_eq!(item_ids, expected);
+        let owned = public.public_items.iter().find(|item| item.id == owned_id);
+        assert_eq!(owned.unwrap().owner_display_name, "Sam");
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_user_stats_count_only_the_callers_household() {
+        let pool = create_test_pool().await;
+        let state = test_app_state(pool.clone());
+        let user_id = Uuid::new_v4();
+        let household_id = state.resolve_household(user_id).await.unwrap();
+        let (own_item, other_item) = (Uuid::new_v4(), Uuid::new_v4());
+
+        sqlx::query("INSERT INTO users (id, email, name, google_id) VALUES ($1, $2, 'Pat', $3)")
+            .bind(user_id)
+            .bind(format!("{}@example.com", user_id))
+            .bind(user_id.to_string())
+            .execute(&pool)
+            .await
+            .unwrap();
+        for (id, household_id) in [(own_item, household_id), (other_item, Uuid::new_v4())] {
+            sqlx::query(
+                "INSERT INTO items (id, shelf_id, name, created_by, belongs_to_user_id, household_id) VALUES ($1, $2, 'Stats item', $3, $3, $4)",
+            )
+            .bind(id)
+            .bind(Uuid::new_v4())
+            .bind(user_id)
+            .bind(household_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let stats = get_user_stats(State(state), AuthUser(user_id), Path(user_id)).await;
+
+        sqlx::query("DELETE FROM items WHERE id = ANY($1)")
+            .bind(vec![own_item, other_item])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM users WHERE id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
 
-    Router::new().route("/api/users", get(list_users))
+        let Json(stats) = stats.unwrap();
+        assert_eq!(stats.items_owned, 1);
+        assert_eq!(stats.items_created, 1);
+    }
 }
@@ -0,0 +1,414 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::Json,
+    Router,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::app::AppState;
+use crate::error::{ApiError, ErrorResponse};
+use crate::middleware::auth::AuthUser;
+use crate::models::{
+    validate_event_types, validate_webhook_url, CreateWebhookRequest, PaginatedResponse,
+    PaginationQuery, UpdateWebhookRequest, Webhook, WebhookDelivery, WebhookResponse,
+    MIN_WEBHOOK_SECRET_LEN,
+};
+use crate::routes::users::ensure_admin;
+use crate::services::audit::Auditable;
+
+/// Reject invalid URLs, event patterns and secrets with 400
+fn validate_webhook_fields(
+    url: Option<&str>,
+    secret: Option<&str>,
+    event_types: Option<&[String]>,
+) -> Result<(), StatusCode> {
+    let check = || -> Result<(), String> {
+        if let Some(url) = url {
+            validate_webhook_url(url)?;
+        }
+        if let Some(event_types) = event_types {
+            validate_event_types(event_types)?;
+        }
+        if secret.is_some_and(|secret| secret.len() < MIN_WEBHOOK_SECRET_LEN) {
+            return Err(format!(
+                "secret must be at least {} characters",
+                MIN_WEBHOOK_SECRET_LEN
+            ));
+        }
+        Ok(())
+    };
+
+    check().map_err(|e| {
+        tracing::warn!("Invalid webhook: {}", e);
+        StatusCode::BAD_REQUEST
+    })
+}
+
+async fn fetch_webhook(state: &AppState, id: Uuid) -> Result<Webhook, ApiError> {
+    sqlx::query_as::<_, Webhook>("SELECT * FROM webhooks WHERE id = $1")
+        .bind(id)
+        .fetch_optional(&state.db)
+        .await?
+        .ok_or_else(|| ApiError::not_found("Webhook", id))
+}
+
+/// Get all webhooks (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    params(
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<WebhookResponse>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn list_webhooks(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Query(params): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<WebhookResponse>>, ApiError> {
+    ensure_admin(&state, user_id).await?;
+    let limit = params.limit.unwrap_or(50).clamp(1, 1000);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM webhooks")
+        .fetch_one(&state.db)
+        .await?;
+    let total = total.clamp(0, i32::MAX as i64) as i32;
+
+    let webhooks = sqlx::query_as::<_, Webhook>(
+        "SELECT * FROM webhooks ORDER BY created_at DESC LIMIT $1 OFFSET $2",
+    )
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    let responses: Vec<WebhookResponse> = webhooks.into_iter().map(WebhookResponse::from).collect();
+    Ok(Json(PaginatedResponse::new(
+        responses, total, limit, offset,
+    )))
+}
+
+/// Get a single webhook by ID (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Webhook id")
+    ),
+    responses(
+        (status = 200, description = "OK", body = WebhookResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse)
+    )
+)]
+pub async fn get_webhook(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    ensure_admin(&state, user_id).await?;
+    let webhook = fetch_webhook(&state, id).await?;
+    Ok(Json(WebhookResponse::from(webhook)))
+}
+
+/// Create a webhook (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 200, description = "OK", body = WebhookResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn create_webhook(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    ensure_admin(&state, user_id).await?;
+    validate_webhook_fields(
+        Some(&payload.url),
+        Some(&payload.secret),
+        Some(&payload.event_types),
+    )?;
+
+    let event_types = serde_json::to_string(&payload.event_types).map_err(|e| {
+        tracing::error!("Failed to serialize webhook event types: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let webhook = sqlx::query_as::<_, Webhook>(
+        r#"
+        INSERT INTO webhooks (id, url, secret, event_types, active, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(&payload.url)
+    .bind(&payload.secret)
+    .bind(&event_types)
+    .bind(payload.active.unwrap_or(true))
+    .bind(user_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    state
+        .audit
+        .log_create(
+            "webhook",
+            webhook.id,
+            Some(user_id),
+            Some(serde_json::json!({ "url": &webhook.url })),
+        )
+        .await
+        .ok();
+
+    Ok(Json(WebhookResponse::from(webhook)))
+}
+
+/// Update a webhook (admin only)
+#[utoipa::path(
+    put,
+    path = "/api/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Webhook id")
+    ),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "OK", body = WebhookResponse),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse)
+    )
+)]
+pub async fn update_webhook(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookResponse>, ApiError> {
+    ensure_admin(&state, user_id).await?;
+    validate_webhook_fields(
+        payload.url.as_deref(),
+        payload.secret.as_deref(),
+        payload.event_types.as_deref(),
+    )?;
+
+    let existing = fetch_webhook(&state, id).await?;
+
+    let url = payload.url.unwrap_or_else(|| existing.url.clone());
+    let active = payload.active.unwrap_or(existing.active);
+    let event_types = match &payload.event_types {
+        Some(event_types) => serde_json::to_string(event_types).map_err(|e| {
+            tracing::error!("Failed to serialize webhook event types: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?,
+        None => existing.event_types.clone(),
+    };
+
+    // Track changes for audit; the secret itself is never logged
+    let mut changes = serde_json::Map::new();
+    if url != existing.url {
+        changes.insert(
+            "url".to_string(),
+            serde_json::json!({ "from": &existing.url, "to": &url }),
+        );
+    }
+    if event_types != existing.event_types {
+        changes.insert(
+            "event_types".to_string(),
+            serde_json::json!({
+                "from": existing.event_patterns(),
+                "to": payload.event_types,
+            }),
+        );
+    }
+    if active != existing.active {
+        changes.insert(
+            "active".to_string(),
+            serde_json::json!({ "from": existing.active, "to": active }),
+        );
+    }
+    if payload.secret.is_some() {
+        changes.insert("secret".to_string(), serde_json::json!("rotated"));
+    }
+
+    let webhook = sqlx::query_as::<_, Webhook>(
+        r#"
+        UPDATE webhooks
+        SET url = $1, secret = $2, event_types = $3, active = $4, updated_at = NOW()
+        WHERE id = $5
+        RETURNING *
+        "#,
+    )
+    .bind(&url)
+    .bind(payload.secret.as_deref().unwrap_or(&existing.secret))
+    .bind(&event_types)
+    .bind(active)
+    .bind(id)
+    .fetch_one(&state.db)
+    .await?;
+
+    if !changes.is_empty() {
+        state
+            .audit
+            .log_update(
+                "webhook",
+                id,
+                Some(user_id),
+                serde_json::Value::Object(changes),
+                None,
+            )
+            .await
+            .ok();
+    }
+
+    Ok(Json(WebhookResponse::from(webhook)))
+}
+
+/// Delete a webhook (admin only). Its delivery log is kept.
+#[utoipa::path(
+    delete,
+    path = "/api/webhooks/{id}",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Webhook id")
+    ),
+    responses(
+        (status = 204, description = "No Content"),
+        (status = 401, description = "Not logged in", body = ErrorResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse)
+    )
+)]
+pub async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApiError> {
+    ensure_admin(&state, user_id).await?;
+    let result = sqlx::query("DELETE FROM webhooks WHERE id = $1")
+        .bind(id)
+        .execute(&state.db)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::not_found("Webhook", id));
+    }
+
+    state
+        .audit
+        .log_delete("webhook", id, Some(user_id), None)
+        .await
+        .ok();
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Delivery attempts for a webhook, newest first (admin only)
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/{id}/deliveries",
+    tag = "webhooks",
+    params(
+        ("id" = Uuid, Path, description = "Webhook id"),
+        PaginationQuery
+    ),
+    responses(
+        (status = 200, description = "OK", body = PaginatedResponse<WebhookDelivery>),
+        (status = 401, description = "Not logged in", body = ErrorResponse)
+    )
+)]
+pub async fn list_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    AuthUser(user_id): AuthUser,
+    Path(id): Path<Uuid>,
+    Query(params): Query<PaginationQuery>,
+) -> Result<Json<PaginatedResponse<WebhookDelivery>>, ApiError> {
+    ensure_admin(&state, user_id).await?;
+    let limit = params.limit.unwrap_or(50).clamp(1, 1000);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    fetch_webhook(&state, id).await?;
+
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM webhook_deliveries WHERE webhook_id = $1")
+            .bind(id)
+            .fetch_one(&state.db)
+            .await?;
+    let total = total.clamp(0, i32::MAX as i64) as i32;
+
+    let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+        r#"
+        SELECT * FROM webhook_deliveries
+        WHERE webhook_id = $1
+        ORDER BY created_at DESC, attempt DESC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(PaginatedResponse::new(
+        deliveries, total, limit, offset,
+    )))
+}
+
+/// Create webhook routes
+pub fn webhook_routes() -> Router<Arc<AppState>> {
+    use axum::routing::get;
+
+    Router::new()
+        .route("/api/webhooks", get(list_webhooks).post(create_webhook))
+        .route(
+            "/api/webhooks/:id",
+            get(get_webhook).put(update_webhook).delete(delete_webhook),
+        )
+        .route("/api/webhooks/:id/deliveries", get(list_webhook_deliveries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_webhook_fields() {
+        let events = vec!["item.*".to_string()];
+        assert!(validate_webhook_fields(
+            Some("https://example.com/hook"),
+            Some("0123456789abcdef"),
+            Some(&events),
+        )
+        .is_ok());
+        // Partial updates only validate what they change
+        assert!(validate_webhook_fields(None, None, None).is_ok());
+
+        assert_eq!(
+            validate_webhook_fields(None, Some("short"), None),
+            Err(StatusCode::BAD_REQUEST)
+        );
+        assert_eq!(
+            validate_webhook_fields(Some("not a url"), None, None),
+            Err(StatusCode::BAD_REQUEST)
+        );
+        assert_eq!(
+            validate_webhook_fields(None, None, Some(&[])),
+            Err(StatusCode::BAD_REQUEST)
+        );
+    }
+}
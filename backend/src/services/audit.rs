@@ -1,14 +1,24 @@
-use axum::http::StatusCode;
+use anyhow::Context;
+use chrono::Utc;
 use serde_json::Value;
 use sqlx::PgPool;
+use std::future::Future;
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
+use crate::models::InventoryEvent;
+use crate::services::household as household_service;
+use crate::services::webhook::{WebhookDispatcher, WebhookEvent};
+
 pub enum AuditAction {
     Create,
     Update,
     Delete,
     Move,
+    QuantityChange,
+    SoftDelete,
+    Restore,
 }
 
 impl std::fmt::Display for AuditAction {
@@ -18,22 +28,21 @@ impl std::fmt::Display for AuditAction {
             AuditAction::Update => "UPDATE",
             AuditAction::Delete => "DELETE",
             AuditAction::Move => "MOVE",
+            AuditAction::QuantityChange => "QUANTITY_CHANGE",
+            AuditAction::SoftDelete => "SOFT_DELETE",
+            AuditAction::Restore => "RESTORE",
         };
         write!(f, "{}", s)
     }
 }
 
-pub struct AuditService {
-    db: Arc<PgPool>,
-}
-
-impl AuditService {
-    pub fn new(db: Arc<PgPool>) -> Self {
-        Self { db }
-    }
-
+/// Something that records audit log entries.
+///
+/// Implementors only provide `log_action`; the per-action helpers are built on
+/// top of it so test doubles can capture every entry in one place.
+pub trait Auditable: Send + Sync {
     /// Log an action for an entity
-    pub async fn log_action(
+    fn log_action(
         &self,
         entity_type: &str,
         entity_id: Uuid,
@@ -41,38 +50,16 @@ impl AuditService {
         user_id: Option<Uuid>,
         changes: Option<Value>,
         metadata: Option<Value>,
-    ) -> Result<(), StatusCode> {
-        sqlx::query(
-            r#"
-            INSERT INTO audit_logs (id, entity_type, entity_id, action, user_id, changes, metadata)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
-            "#,
-        )
-        .bind(Uuid::new_v4())
-        .bind(entity_type)
-        .bind(entity_id)
-        .bind(action.to_string())
-        .bind(user_id)
-        .bind(changes)
-        .bind(metadata)
-        .execute(&*self.db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to log audit action: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-
-        Ok(())
-    }
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
 
     /// Log a create action
-    pub async fn log_create(
+    fn log_create(
         &self,
         entity_type: &str,
         entity_id: Uuid,
         user_id: Option<Uuid>,
         metadata: Option<Value>,
-    ) -> Result<(), StatusCode> {
+    ) -> impl Future<Output = anyhow::Result<()>> + Send {
         self.log_action(
             entity_type,
             entity_id,
@@ -81,18 +68,17 @@ impl AuditService {
             None,
             metadata,
         )
-        .await
     }
 
     /// Log an update action with changes
-    pub async fn log_update(
+    fn log_update(
         &self,
         entity_type: &str,
         entity_id: Uuid,
         user_id: Option<Uuid>,
         changes: Value,
         metadata: Option<Value>,
-    ) -> Result<(), StatusCode> {
+    ) -> impl Future<Output = anyhow::Result<()>> + Send {
         self.log_action(
             entity_type,
             entity_id,
@@ -101,17 +87,16 @@ impl AuditService {
             Some(changes),
             metadata,
         )
-        .await
     }
 
     /// Log a delete action
-    pub async fn log_delete(
+    fn log_delete(
         &self,
         entity_type: &str,
         entity_id: Uuid,
         user_id: Option<Uuid>,
         metadata: Option<Value>,
-    ) -> Result<(), StatusCode> {
+    ) -> impl Future<Output = anyhow::Result<()>> + Send {
         self.log_action(
             entity_type,
             entity_id,
@@ -120,11 +105,71 @@ impl AuditService {
             None,
             metadata,
         )
-        .await
+    }
+
+    /// Log moving an entity to the trash
+    fn log_soft_delete(
+        &self,
+        entity_type: &str,
+        entity_id: Uuid,
+        user_id: Option<Uuid>,
+        metadata: Option<Value>,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send {
+        self.log_action(
+            entity_type,
+            entity_id,
+            AuditAction::SoftDelete,
+            user_id,
+            None,
+            metadata,
+        )
+    }
+
+    /// Log restoring an entity from the trash
+    fn log_restore(
+        &self,
+        entity_type: &str,
+        entity_id: Uuid,
+        user_id: Option<Uuid>,
+        metadata: Option<Value>,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send {
+        self.log_action(
+            entity_type,
+            entity_id,
+            AuditAction::Restore,
+            user_id,
+            None,
+            metadata,
+        )
+    }
+
+    /// Log a stock quantity adjustment
+    fn log_quantity_change(
+        &self,
+        entity_type: &str,
+        entity_id: Uuid,
+        user_id: Option<Uuid>,
+        from_quantity: i32,
+        to_quantity: i32,
+        low_stock: bool,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send {
+        self.log_action(
+            entity_type,
+            entity_id,
+            AuditAction::QuantityChange,
+            user_id,
+            Some(serde_json::json!({
+                "quantity": { "from": from_quantity, "to": to_quantity },
+            })),
+            Some(serde_json::json!({
+                "delta": to_quantity - from_quantity,
+                "low_stock": low_stock,
+            })),
+        )
     }
 
     /// Log a move action
-    pub async fn log_move(
+    fn log_move(
         &self,
         entity_type: &str,
         entity_id: Uuid,
@@ -132,29 +177,126 @@ impl AuditService {
         from_location: Value,
         to_location: Value,
         metadata: Option<Value>,
-    ) -> Result<(), StatusCode> {
-        let move_metadata = serde_json::json!({
-            "from": from_location,
-            "to": to_location,
-        });
-
-        let combined_metadata = if let Some(meta) = metadata {
-            let mut map = meta.as_object().cloned().unwrap_or_default();
-            map.extend(move_metadata.as_object().unwrap().clone());
-            Some(Value::Object(map))
-        } else {
-            Some(move_metadata)
-        };
-
+    ) -> impl Future<Output = anyhow::Result<()>> + Send {
         self.log_action(
             entity_type,
             entity_id,
             AuditAction::Move,
             user_id,
             None,
-            combined_metadata,
+            Some(combine_move_metadata(from_location, to_location, metadata)),
         )
+    }
+}
+
+/// Merge the from/to locations of a move into any caller-supplied metadata
+fn combine_move_metadata(
+    from_location: Value,
+    to_location: Value,
+    metadata: Option<Value>,
+) -> Value {
+    let move_metadata = serde_json::json!({
+        "from": from_location,
+        "to": to_location,
+    });
+
+    if let Some(meta) = metadata {
+        let mut map = meta.as_object().cloned().unwrap_or_default();
+        map.extend(move_metadata.as_object().unwrap().clone());
+        Value::Object(map)
+    } else {
+        move_metadata
+    }
+}
+
+pub struct AuditService {
+    db: Arc<PgPool>,
+    webhooks: WebhookDispatcher,
+    /// Live feed for `GET /api/events`
+    events: broadcast::Sender<InventoryEvent>,
+}
+
+impl AuditService {
+    pub fn new(db: Arc<PgPool>, events: broadcast::Sender<InventoryEvent>) -> Self {
+        Self {
+            webhooks: WebhookDispatcher::new(db.clone()),
+            db,
+            events,
+        }
+    }
+
+    /// Household to stream an event by `user_id` to, skipping the lookup when no one
+    /// is subscribed. The write has already happened, so a failed lookup only drops
+    /// the event.
+    async fn event_household(&self, user_id: Option<Uuid>) -> Option<Uuid> {
+        if self.events.receiver_count() == 0 {
+            return None;
+        }
+        household_service::current_household(&self.db, user_id?)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to find household for event: {:?}", e))
+            .ok()
+            .flatten()
+    }
+}
+
+impl Auditable for AuditService {
+    async fn log_action(
+        &self,
+        entity_type: &str,
+        entity_id: Uuid,
+        action: AuditAction,
+        user_id: Option<Uuid>,
+        changes: Option<Value>,
+        metadata: Option<Value>,
+    ) -> anyhow::Result<()> {
+        let id = Uuid::new_v4();
+        let action = action.to_string();
+        sqlx::query(
+            r#"
+            INSERT INTO audit_logs (id, entity_type, entity_id, action, user_id, changes, metadata)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(id)
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(&action)
+        .bind(user_id)
+        .bind(&changes)
+        .bind(&metadata)
+        .execute(&*self.db)
         .await
+        .inspect_err(|e| tracing::error!("Failed to log audit action: {:?}", e))
+        .context("Failed to log audit action")?;
+
+        // Only entries that were actually recorded are sent to webhooks and event
+        // subscribers. Events are filed under the household the writer works in, and
+        // writes with no user behind them aren't streamed.
+        let occurred_at = Utc::now();
+        if let Some(household_id) = self.event_household(user_id).await {
+            self.events
+                .send(InventoryEvent {
+                    event_type: action.clone(),
+                    entity_type: entity_type.to_string(),
+                    entity_id,
+                    household_id,
+                    timestamp: occurred_at,
+                })
+                .ok();
+        }
+        self.webhooks.dispatch(WebhookEvent {
+            id,
+            entity_type: entity_type.to_string(),
+            entity_id,
+            action,
+            user_id,
+            changes,
+            metadata,
+            occurred_at,
+        });
+
+        Ok(())
     }
 }
 
@@ -168,6 +310,9 @@ mod tests {
         assert_eq!(AuditAction::Update.to_string(), "UPDATE");
         assert_eq!(AuditAction::Delete.to_string(), "DELETE");
         assert_eq!(AuditAction::Move.to_string(), "MOVE");
+        assert_eq!(AuditAction::QuantityChange.to_string(), "QUANTITY_CHANGE");
+        assert_eq!(AuditAction::SoftDelete.to_string(), "SOFT_DELETE");
+        assert_eq!(AuditAction::Restore.to_string(), "RESTORE");
     }
 
     #[test]
@@ -196,8 +341,9 @@ mod tests {
 
         // Test without existing metadata
         let combined_metadata = Some(move_metadata.clone());
-        assert!(combined_metadata.is_some());
-        let meta = combined_metadata.unwrap();
+        let Some(meta) = combined_metadata else {
+            panic!("move metadata should be kept when there is no other metadata");
+        };
         assert!(meta.get("from").is_some());
         assert!(meta.get("to").is_some());
 
@@ -220,7 +366,7 @@ mod tests {
     #[test]
     fn test_audit_action_all_variants() {
         // Ensure all variants are covered
-        let actions = vec![
+        let actions = [
             AuditAction::Create,
             AuditAction::Update,
             AuditAction::Delete,
@@ -265,4 +411,95 @@ mod tests {
         assert!(to_obj.contains_key("container_id"));
         assert!(to_obj.contains_key("container_name"));
     }
+
+    /// Entity type, entity id, action, changes and metadata of one recorded entry
+    type RecordedEntry = (String, Uuid, String, Option<Value>, Option<Value>);
+
+    /// Test double that records entries instead of writing to the database
+    #[derive(Default)]
+    struct RecordingAuditor {
+        entries: std::sync::Mutex<Vec<RecordedEntry>>,
+    }
+
+    impl Auditable for RecordingAuditor {
+        async fn log_action(
+            &self,
+            entity_type: &str,
+            entity_id: Uuid,
+            action: AuditAction,
+            _user_id: Option<Uuid>,
+            changes: Option<Value>,
+            metadata: Option<Value>,
+        ) -> anyhow::Result<()> {
+            self.entries.lock().unwrap().push((
+                entity_type.to_string(),
+                entity_id,
+                action.to_string(),
+                changes,
+                metadata,
+            ));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_helpers_delegate_to_log_action() {
+        let auditor = RecordingAuditor::default();
+        let id = Uuid::new_v4();
+        let changes = serde_json::json!({"name": {"from": "a", "to": "b"}});
+
+        auditor.log_create("item", id, None, None).await.unwrap();
+        auditor
+            .log_update("item", id, None, changes.clone(), None)
+            .await
+            .unwrap();
+        auditor.log_delete("item", id, None, None).await.unwrap();
+
+        let entries = auditor.entries.lock().unwrap();
+        let actions: Vec<&str> = entries.iter().map(|e| e.2.as_str()).collect();
+        assert_eq!(actions, vec!["CREATE", "UPDATE", "DELETE"]);
+        assert!(entries.iter().all(|e| e.0 == "item" && e.1 == id));
+        assert_eq!(entries[1].3, Some(changes));
+    }
+
+    #[tokio::test]
+    async fn test_trash_helpers_delegate_to_log_action() {
+        let auditor = RecordingAuditor::default();
+        let id = Uuid::new_v4();
+
+        auditor
+            .log_soft_delete("item", id, None, None)
+            .await
+            .unwrap();
+        auditor.log_restore("item", id, None, None).await.unwrap();
+
+        let entries = auditor.entries.lock().unwrap();
+        let actions: Vec<&str> = entries.iter().map(|e| e.2.as_str()).collect();
+        assert_eq!(actions, vec!["SOFT_DELETE", "RESTORE"]);
+        assert!(entries.iter().all(|e| e.3.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_log_move_merges_metadata() {
+        let auditor = RecordingAuditor::default();
+
+        auditor
+            .log_move(
+                "item",
+                Uuid::new_v4(),
+                None,
+                serde_json::json!({"shelf_id": "123"}),
+                serde_json::json!({"container_id": "456"}),
+                Some(serde_json::json!({"reason": "reorganization"})),
+            )
+            .await
+            .unwrap();
+
+        let entries = auditor.entries.lock().unwrap();
+        assert_eq!(entries[0].2, "MOVE");
+        let metadata = entries[0].4.as_ref().unwrap();
+        assert_eq!(metadata["from"]["shelf_id"], "123");
+        assert_eq!(metadata["to"]["container_id"], "456");
+        assert_eq!(metadata["reason"], "reorganization");
+    }
 }
@@ -0,0 +1,630 @@
+//! Deleting a room, shelving unit or shelf together with everything inside it.
+//!
+//! There are no foreign keys to cascade for us, so the subtree is collected first and
+//! deleted bottom up (items, containers, shelves, units, then the location itself) in
+//! one transaction, together with the photos, tags and label assignments of everything
+//! in it. The photos' S3 objects are removed once that transaction has committed.
+//!
+//! DSQL caps how many rows one transaction may modify (3,000 at the time of writing),
+//! so a subtree needing more than [`MAX_CASCADE_ROWS`] is refused rather than left to
+//! fail at commit.
+
+use futures::future::try_join_all;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::db::with_transaction;
+use crate::error::ApiError;
+use crate::models::{LocationCounts, FLOOR_PLAN_ENTITY_TYPE};
+use crate::services::s3::S3Service;
+
+/// Most rows a cascading delete touches in its transaction, within DSQL's limit
+const MAX_CASCADE_ROWS: i64 = 3000;
+
+/// Every container on the shelves in `$1`, however deeply nested. `UNION` rather than
+/// `UNION ALL` so a (corrupt) cyclic hierarchy still terminates.
+const SUBTREE_CONTAINERS_SQL: &str = r#"
+    WITH RECURSIVE subtree AS (
+        SELECT id FROM containers WHERE shelf_id = ANY($1)
+        UNION
+        SELECT c.id
+        FROM containers c
+        JOIN subtree s ON c.parent_container_id = s.id
+    )
+    SELECT id FROM subtree
+"#;
+
+/// The location a cascading delete starts from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CascadeRoot {
+    Room(Uuid),
+    ShelvingUnit(Uuid),
+    Shelf(Uuid),
+}
+
+impl CascadeRoot {
+    /// Name used in error messages
+    pub fn entity(&self) -> &'static str {
+        match self {
+            CascadeRoot::Room(_) => "Room",
+            CascadeRoot::ShelvingUnit(_) => "Shelving unit",
+            CascadeRoot::Shelf(_) => "Shelf",
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        match self {
+            CascadeRoot::Room(id) | CascadeRoot::ShelvingUnit(id) | CascadeRoot::Shelf(id) => *id,
+        }
+    }
+}
+
+/// SQL condition that a row's `type_col` and `id_col` point at an entity of the
+/// subtree, whose ids are bound as `$1` to `$5` by [`Subtree::entity_ids`]
+fn attached_to_subtree_sql(type_col: &str, id_col: &str) -> String {
+    format!(
+        r#"
+        ({type_col} IN ('room', '{floor_plan}') AND {id_col} = ANY($1))
+        OR ({type_col} IN ('unit', 'shelving_unit') AND {id_col} = ANY($2))
+        OR ({type_col} = 'shelf' AND {id_col} = ANY($3))
+        OR ({type_col} = 'container' AND {id_col} = ANY($4))
+        OR ({type_col} = 'item' AND {id_col} = ANY($5))
+        "#,
+        floor_plan = FLOOR_PLAN_ENTITY_TYPE,
+    )
+}
+
+/// Ids of the locations and items under (and including) a root
+#[derive(Debug, Default)]
+struct Subtree {
+    rooms: Vec<Uuid>,
+    units: Vec<Uuid>,
+    shelves: Vec<Uuid>,
+    containers: Vec<Uuid>,
+    items: Vec<Uuid>,
+    /// Items in the subtree the deleting user didn't create, and so may not delete
+    foreign_items: i64,
+    /// Photos, entity tags and labels attached to anything in the subtree
+    attached: i64,
+}
+
+impl Subtree {
+    fn counts(&self) -> LocationCounts {
+        LocationCounts {
+            rooms: self.rooms.len() as i64,
+            units: self.units.len() as i64,
+            shelves: self.shelves.len() as i64,
+            containers: self.containers.len() as i64,
+            items: self.items.len() as i64,
+        }
+    }
+
+    /// Rooms, units, shelves, containers and items, in the order of the parameters of
+    /// [`attached_to_subtree_sql`]
+    fn entity_ids(&self) -> [&Vec<Uuid>; 5] {
+        [
+            &self.rooms,
+            &self.units,
+            &self.shelves,
+            &self.containers,
+            &self.items,
+        ]
+    }
+
+    /// Rows the delete modifies
+    fn rows(&self) -> i64 {
+        let counts = self.counts();
+        counts.rooms
+            + counts.units
+            + counts.shelves
+            + counts.containers
+            + counts.items
+            + self.attached
+    }
+
+    /// Whether anything besides the root itself is in the subtree
+    fn has_children(&self, root: CascadeRoot) -> bool {
+        let own = match root {
+            CascadeRoot::Room(_) => self.units.len(),
+            CascadeRoot::ShelvingUnit(_) => self.shelves.len(),
+            CascadeRoot::Shelf(_) => 0,
+        };
+        own > 0 || !self.containers.is_empty() || !self.items.is_empty()
+    }
+}
+
+/// Collect the ids under `root`, 404 when it doesn't exist
+async fn collect_subtree(
+    conn: &mut PgConnection,
+    root: CascadeRoot,
+    user_id: Uuid,
+) -> Result<Subtree, ApiError> {
+    let table = match root {
+        CascadeRoot::Room(_) => "rooms",
+        CascadeRoot::ShelvingUnit(_) => "shelving_units",
+        CascadeRoot::Shelf(_) => "shelves",
+    };
+    let exists: bool = sqlx::query_scalar(&format!(
+        "SELECT EXISTS(SELECT 1 FROM {table} WHERE id = $1)"
+    ))
+    .bind(root.id())
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if !exists {
+        return Err(ApiError::not_found(root.entity(), root.id()));
+    }
+
+    let mut subtree = Subtree::default();
+
+    subtree.units = match root {
+        CascadeRoot::Room(id) => {
+            subtree.rooms = vec![id];
+            sqlx::query_scalar("SELECT id FROM shelving_units WHERE room_id = $1")
+                .bind(id)
+                .fetch_all(&mut *conn)
+                .await?
+        }
+        CascadeRoot::ShelvingUnit(id) => vec![id],
+        CascadeRoot::Shelf(_) => Vec::new(),
+    };
+
+    subtree.shelves = match root {
+        CascadeRoot::Shelf(id) => vec![id],
+        _ => {
+            sqlx::query_scalar("SELECT id FROM shelves WHERE shelving_unit_id = ANY($1)")
+                .bind(&subtree.units)
+                .fetch_all(&mut *conn)
+                .await?
+        }
+    };
+
+    subtree.containers = sqlx::query_scalar(SUBTREE_CONTAINERS_SQL)
+        .bind(&subtree.shelves)
+        .fetch_all(&mut *conn)
+        .await?;
+
+    // Trashed items go too; they could never be restored to a deleted location
+    let items: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        "SELECT id, created_by FROM items WHERE shelf_id = ANY($1) OR container_id = ANY($2)",
+    )
+    .bind(&subtree.shelves)
+    .bind(&subtree.containers)
+    .fetch_all(&mut *conn)
+    .await?;
+    subtree.foreign_items = items
+        .iter()
+        .filter(|(_, created_by)| *created_by != user_id)
+        .count() as i64;
+    subtree.items = items.into_iter().map(|(id, _)| id).collect();
+
+    let attached_sql = format!(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM photos WHERE {photos})
+            + (SELECT COUNT(*) FROM entity_tags WHERE {tags})
+            + (SELECT COUNT(*) FROM labels WHERE {labels})
+        "#,
+        photos = attached_to_subtree_sql("entity_type", "entity_id"),
+        tags = attached_to_subtree_sql("entity_type", "entity_id"),
+        labels = attached_to_subtree_sql("assigned_to_type", "assigned_to_id"),
+    );
+    let mut attached = sqlx::query_scalar(&attached_sql);
+    for ids in subtree.entity_ids() {
+        attached = attached.bind(ids);
+    }
+    subtree.attached = attached.fetch_one(&mut *conn).await?;
+
+    Ok(subtree)
+}
+
+/// Delete a collected subtree bottom up, returning what was actually deleted and the
+/// S3 keys of its photos
+async fn delete_subtree(
+    conn: &mut PgConnection,
+    subtree: &Subtree,
+) -> Result<(LocationCounts, Vec<String>), ApiError> {
+    let photos_sql = format!(
+        "DELETE FROM photos WHERE {} RETURNING s3_key, thumbnail_s3_key",
+        attached_to_subtree_sql("entity_type", "entity_id")
+    );
+    let mut photos = sqlx::query_as::<_, (String, Option<String>)>(&photos_sql);
+    for ids in subtree.entity_ids() {
+        photos = photos.bind(ids);
+    }
+    let s3_keys = photos
+        .fetch_all(&mut *conn)
+        .await?
+        .into_iter()
+        .flat_map(|(key, thumbnail_key)| std::iter::once(key).chain(thumbnail_key))
+        .collect();
+
+    let tags_sql = format!(
+        "DELETE FROM entity_tags WHERE {}",
+        attached_to_subtree_sql("entity_type", "entity_id")
+    );
+    let mut tags = sqlx::query(&tags_sql);
+    for ids in subtree.entity_ids() {
+        tags = tags.bind(ids);
+    }
+    tags.execute(&mut *conn).await?;
+
+    // Labels outlive what they were stuck on and can be assigned again
+    let labels_sql = format!(
+        r#"
+        UPDATE labels
+        SET assigned_to_type = NULL, assigned_to_id = NULL, assigned_at = NULL, updated_at = NOW()
+        WHERE {}
+        "#,
+        attached_to_subtree_sql("assigned_to_type", "assigned_to_id")
+    );
+    let mut labels = sqlx::query(&labels_sql);
+    for ids in subtree.entity_ids() {
+        labels = labels.bind(ids);
+    }
+    labels.execute(&mut *conn).await?;
+
+    let items = sqlx::query("DELETE FROM items WHERE id = ANY($1)")
+        .bind(&subtree.items)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+    let mut deleted = [0u64; 4];
+    for (count, (table, ids)) in deleted.iter_mut().zip([
+        ("containers", &subtree.containers),
+        ("shelves", &subtree.shelves),
+        ("shelving_units", &subtree.units),
+        ("rooms", &subtree.rooms),
+    ]) {
+        if ids.is_empty() {
+            continue;
+        }
+        *count = sqlx::query(&format!("DELETE FROM {table} WHERE id = ANY($1)"))
+            .bind(ids)
+            .execute(&mut *conn)
+            .await?
+            .rows_affected();
+    }
+    let [containers, shelves, units, rooms] = deleted;
+
+    let counts = LocationCounts {
+        rooms: rooms as i64,
+        units: units as i64,
+        shelves: shelves as i64,
+        containers: containers as i64,
+        items: items as i64,
+    };
+    Ok((counts, s3_keys))
+}
+
+/// Delete `root` for `user_id`. With `cascade` everything inside it goes too; without,
+/// a location that isn't empty is rejected with 409 `HAS_CHILDREN` and what a cascade
+/// would delete. Items someone else created make it a 403, as deleting them one by one
+/// would be, and a subtree too large for one transaction a 422. Returns what was
+/// deleted, the root included.
+pub async fn delete_location(
+    db: &PgPool,
+    s3: &S3Service,
+    user_id: Uuid,
+    root: CascadeRoot,
+    cascade: bool,
+) -> Result<LocationCounts, ApiError> {
+    let (deleted, s3_keys) = with_transaction(db, move |conn| {
+        Box::pin(async move {
+            let subtree = collect_subtree(conn, root, user_id).await?;
+
+            if !cascade && subtree.has_children(root) {
+                return Err(ApiError::HasChildren {
+                    entity: root.entity(),
+                    counts: subtree.counts(),
+                });
+            }
+            if subtree.foreign_items > 0 {
+                return Err(ApiError::Forbidden(format!(
+                    "{} contains {} items added by someone else, which only they may delete",
+                    root.entity(),
+                    subtree.foreign_items
+                )));
+            }
+            if subtree.rows() > MAX_CASCADE_ROWS {
+                return Err(ApiError::UnprocessableEntity(format!(
+                    "{} holds too much to delete at once; delete some of what is inside it first",
+                    root.entity()
+                )));
+            }
+
+            delete_subtree(conn, &subtree).await
+        })
+    })
+    .await?;
+
+    // The rows are gone, so a failure here only leaves unreferenced objects behind
+    if let Err(e) = try_join_all(s3_keys.iter().map(|key| s3.delete_file(key))).await {
+        tracing::warn!(
+            "Failed to delete photos of deleted {} {} from S3: {:?}",
+            root.entity(),
+            root.id(),
+            e
+        );
+    }
+
+    Ok(deleted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_pool;
+
+    #[test]
+    fn test_has_children_ignores_the_root() {
+        let id = Uuid::new_v4();
+
+        let empty_room = Subtree {
+            rooms: vec![id],
+            ..Default::default()
+        };
+        assert!(!empty_room.has_children(CascadeRoot::Room(id)));
+
+        let empty_unit = Subtree {
+            units: vec![id],
+            ..Default::default()
+        };
+        assert!(!empty_unit.has_children(CascadeRoot::ShelvingUnit(id)));
+
+        let shelf_with_items = Subtree {
+            shelves: vec![id],
+            items: vec![Uuid::new_v4(), Uuid::new_v4()],
+            ..Default::default()
+        };
+        assert!(shelf_with_items.has_children(CascadeRoot::Shelf(id)));
+
+        let unit_with_shelf = Subtree {
+            units: vec![id],
+            shelves: vec![Uuid::new_v4()],
+            ..Default::default()
+        };
+        assert!(unit_with_shelf.has_children(CascadeRoot::ShelvingUnit(id)));
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_delete_location_cascades_through_nested_containers() {
+        let pool = create_test_pool().await;
+        let user_id = Uuid::new_v4();
+        let (room_id, unit_id, shelf_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let (outer_id, inner_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (loose_item_id, boxed_item_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        sqlx::query("INSERT INTO rooms (id, name, created_by) VALUES ($1, 'Basement', $2)")
+            .bind(room_id)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO shelving_units (id, room_id, name, created_by) VALUES ($1, $2, 'Rack', $3)",
+        )
+        .bind(unit_id)
+        .bind(room_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO shelves (id, shelving_unit_id, name, position, created_by) VALUES ($1, $2, 'Top', 1, $3)",
+        )
+        .bind(shelf_id)
+        .bind(unit_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, parent_id) in [(outer_id, None), (inner_id, Some(outer_id))] {
+            sqlx::query(
+                "INSERT INTO containers (id, shelf_id, parent_container_id, name, created_by) VALUES ($1, $2, $3, 'Bin', $4)",
+            )
+            .bind(id)
+            .bind(parent_id.is_none().then_some(shelf_id))
+            .bind(parent_id)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO items (id, shelf_id, name, created_by) VALUES ($1, $2, 'Lamp', $3)",
+        )
+        .bind(loose_item_id)
+        .bind(shelf_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO items (id, container_id, name, created_by) VALUES ($1, $2, 'Fuse', $3)",
+        )
+        .bind(boxed_item_id)
+        .bind(inner_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // A photo, a tag and a label on things inside the room
+        let (photo_id, tag_id, label_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        sqlx::query(
+            "INSERT INTO photos (id, entity_type, entity_id, s3_key, content_type, file_size, created_by) VALUES ($1, 'item', $2, $3, 'image/jpeg', 1, $4)",
+        )
+        .bind(photo_id)
+        .bind(boxed_item_id)
+        .bind(format!("item/{}/photo.jpg", boxed_item_id))
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO entity_tags (entity_type, entity_id, tag_id, updated_at) VALUES ('container', $1, $2, NOW())",
+        )
+        .bind(inner_id)
+        .bind(tag_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO labels (id, number, qr_data, assigned_to_type, assigned_to_id, updated_at)
+            VALUES ($1, (SELECT COALESCE(MAX(number), 0) + 1 FROM labels), $2, 'unit', $3, NOW())
+            "#,
+        )
+        .bind(label_id)
+        .bind(format!("test/l/{}", label_id))
+        .bind(unit_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let s3 = S3Service::for_tests();
+        let refused = delete_location(&pool, &s3, user_id, CascadeRoot::Room(room_id), false).await;
+        let room_kept: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM rooms WHERE id = $1)")
+                .bind(room_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+
+        let cascaded = delete_location(&pool, &s3, user_id, CascadeRoot::Room(room_id), true).await;
+        let boxed_item_left: bool =
+            sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM items WHERE id = $1)")
+                .bind(boxed_item_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        let attached_left: (bool, bool) = sqlx::query_as(
+            r#"
+            SELECT
+                EXISTS(SELECT 1 FROM photos WHERE id = $1),
+                EXISTS(SELECT 1 FROM entity_tags WHERE tag_id = $2)
+            "#,
+        )
+        .bind(photo_id)
+        .bind(tag_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let label_assigned_to: Option<Uuid> =
+            sqlx::query_scalar("SELECT assigned_to_id FROM labels WHERE id = $1")
+                .bind(label_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+
+        sqlx::query("DELETE FROM labels WHERE id = $1")
+            .bind(label_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let everything = LocationCounts {
+            rooms: 1,
+            units: 1,
+            shelves: 1,
+            containers: 2,
+            items: 2,
+        };
+        assert_eq!(
+            refused,
+            Err(ApiError::HasChildren {
+                entity: "Room",
+                counts: everything,
+            })
+        );
+        assert!(room_kept);
+        assert_eq!(cascaded, Ok(everything));
+        assert!(!boxed_item_left);
+        assert_eq!(attached_left, (false, false));
+        assert_eq!(label_assigned_to, None);
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_delete_location_refuses_other_users_items() {
+        let pool = create_test_pool().await;
+        let (user_id, other_user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (shelf_id, item_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        sqlx::query(
+            "INSERT INTO shelves (id, shelving_unit_id, name, position, created_by) VALUES ($1, $2, 'Shared', 1, $3)",
+        )
+        .bind(shelf_id)
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO items (id, shelf_id, name, created_by) VALUES ($1, $2, 'Not mine', $3)",
+        )
+        .bind(item_id)
+        .bind(shelf_id)
+        .bind(other_user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let s3 = S3Service::for_tests();
+        let refused =
+            delete_location(&pool, &s3, user_id, CascadeRoot::Shelf(shelf_id), true).await;
+        let by_creator = delete_location(
+            &pool,
+            &s3,
+            other_user_id,
+            CascadeRoot::Shelf(shelf_id),
+            true,
+        )
+        .await;
+
+        assert!(matches!(refused, Err(ApiError::Forbidden(_))));
+        assert_eq!(
+            by_creator,
+            Ok(LocationCounts {
+                shelves: 1,
+                items: 1,
+                ..Default::default()
+            })
+        );
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_delete_location_empty_shelf_without_cascade() {
+        let pool = create_test_pool().await;
+        let shelf_id = Uuid::new_v4();
+
+        sqlx::query(
+            "INSERT INTO shelves (id, shelving_unit_id, name, position, created_by) VALUES ($1, $2, 'Spare', 1, $3)",
+        )
+        .bind(shelf_id)
+        .bind(Uuid::new_v4())
+        .bind(Uuid::new_v4())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let (s3, user_id) = (S3Service::for_tests(), Uuid::new_v4());
+        let deleted =
+            delete_location(&pool, &s3, user_id, CascadeRoot::Shelf(shelf_id), false).await;
+        let missing =
+            delete_location(&pool, &s3, user_id, CascadeRoot::Shelf(shelf_id), false).await;
+
+        assert_eq!(
+            deleted,
+            Ok(LocationCounts {
+                shelves: 1,
+                ..Default::default()
+            })
+        );
+        assert_eq!(missing, Err(ApiError::not_found("Shelf", shelf_id)));
+    }
+}
@@ -0,0 +1,261 @@
+use anyhow::Context;
+use exif::{In, Reader, Tag, Value};
+use serde::Serialize;
+use sqlx::PgPool;
+use std::io::Cursor;
+
+use crate::models::Photo;
+use crate::services::s3::S3Service;
+
+/// EXIF metadata kept for a photo. The GPS position goes in the photo's `lat` and
+/// `lng` columns and the rest is stored as its `exif_data`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PhotoExif {
+    pub camera_make: Option<String>,
+    pub camera_model: Option<String>,
+    /// `DateTimeOriginal` as `YYYY-MM-DDTHH:MM:SS`, in the camera's local time
+    pub captured_at: Option<String>,
+    /// EXIF orientation, 1-8: how the image must be rotated or flipped to show upright
+    pub orientation: Option<u32>,
+    #[serde(skip)]
+    pub lat: Option<f64>,
+    #[serde(skip)]
+    pub lng: Option<f64>,
+}
+
+/// Only JPEGs are read when a photo is created; other images can still be read on
+/// request
+pub fn has_exif(content_type: &str) -> bool {
+    matches!(content_type, "image/jpeg" | "image/jpg")
+}
+
+/// First string of an ASCII value, without padding
+fn ascii(value: &Value) -> Option<String> {
+    let Value::Ascii(parts) = value else {
+        return None;
+    };
+    let text = String::from_utf8_lossy(parts.first()?);
+    let text = text.trim_matches(char::from(0)).trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Degrees, minutes and seconds as decimal degrees
+fn dms_to_degrees(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+    degrees + minutes / 60.0 + seconds / 3600.0
+}
+
+/// A GPS coordinate from its degrees/minutes/seconds tag and hemisphere tag.
+/// `negative` is the hemisphere (`S` or `W`) that makes it negative.
+fn coordinate(
+    exif: &exif::Exif,
+    tag: Tag,
+    hemisphere_tag: Tag,
+    negative: &str,
+    limit: f64,
+) -> Option<f64> {
+    let Value::Rational(dms) = &exif.get_field(tag, In::PRIMARY)?.value else {
+        return None;
+    };
+    let [d, m, s] = dms.get(..3)? else {
+        return None;
+    };
+    let degrees = dms_to_degrees(d.to_f64(), m.to_f64(), s.to_f64());
+    let hemisphere = exif
+        .get_field(hemisphere_tag, In::PRIMARY)
+        .and_then(|field| ascii(&field.value));
+    let signed = if hemisphere.as_deref() == Some(negative) {
+        -degrees
+    } else {
+        degrees
+    };
+    (signed.is_finite() && signed.abs() <= limit).then_some(signed)
+}
+
+/// EXIF metadata of an encoded image, or `None` when it has none. CPU bound; run it
+/// on a blocking thread.
+pub fn read_exif(bytes: &[u8]) -> anyhow::Result<Option<PhotoExif>> {
+    let exif = match Reader::new().read_from_container(&mut Cursor::new(bytes)) {
+        Ok(exif) => exif,
+        Err(exif::Error::NotFound(_)) => return Ok(None),
+        Err(e) => return Err(e).context("Failed to read EXIF"),
+    };
+    let text = |tag| {
+        exif.get_field(tag, In::PRIMARY)
+            .and_then(|field| ascii(&field.value))
+    };
+
+    let captured_at = exif
+        .get_field(Tag::DateTimeOriginal, In::PRIMARY)
+        .and_then(|field| match &field.value {
+            Value::Ascii(parts) => exif::DateTime::from_ascii(parts.first()?).ok(),
+            _ => None,
+        })
+        .map(|dt| {
+            format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+                dt.year, dt.month, dt.day, dt.hour, dt.minute, dt.second
+            )
+        });
+
+    let metadata = PhotoExif {
+        camera_make: text(Tag::Make),
+        camera_model: text(Tag::Model),
+        captured_at,
+        orientation: exif
+            .get_field(Tag::Orientation, In::PRIMARY)
+            .and_then(|field| field.value.get_uint(0)),
+        lat: coordinate(&exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, "S", 90.0),
+        lng: coordinate(&exif, Tag::GPSLongitude, Tag::GPSLongitudeRef, "W", 180.0),
+    };
+    Ok((metadata != PhotoExif::default()).then_some(metadata))
+}
+
+/// Read a photo's EXIF from its full image in S3 and record it. Returns what was
+/// found; a photo without EXIF is left as it is.
+pub async fn extract_photo_exif(
+    db: &PgPool,
+    s3: &S3Service,
+    photo: &Photo,
+) -> anyhow::Result<Option<PhotoExif>> {
+    let original = s3.get_object_bytes(&photo.s3_key).await?;
+    let Some(metadata) = tokio::task::spawn_blocking(move || read_exif(&original)).await?? else {
+        return Ok(None);
+    };
+
+    sqlx::query("UPDATE photos SET exif_data = $1, lat = $2, lng = $3 WHERE id = $4")
+        .bind(serde_json::to_string(&metadata)?)
+        .bind(metadata.lat)
+        .bind(metadata.lng)
+        .bind(photo.id)
+        .execute(db)
+        .await?;
+
+    Ok(Some(metadata))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASCII: u16 = 2;
+    const SHORT: u16 = 3;
+    const LONG: u16 = 4;
+    const RATIONAL: u16 = 5;
+
+    /// A little-endian TIFF IFD starting at `start`: entries (tag, type, count,
+    /// value bytes) followed by the values that don't fit in an entry
+    fn ifd(start: u32, entries: &[(u16, u16, u32, Vec<u8>)]) -> Vec<u8> {
+        let mut out = (entries.len() as u16).to_le_bytes().to_vec();
+        let mut data: Vec<u8> = Vec::new();
+        let data_start = start + 2 + 12 * entries.len() as u32 + 4;
+        for (tag, kind, count, value) in entries {
+            out.extend(tag.to_le_bytes());
+            out.extend(kind.to_le_bytes());
+            out.extend(count.to_le_bytes());
+            if value.len() <= 4 {
+                let mut inline = value.clone();
+                inline.resize(4, 0);
+                out.extend(inline);
+            } else {
+                out.extend((data_start + data.len() as u32).to_le_bytes());
+                data.extend(value);
+            }
+        }
+        out.extend(0u32.to_le_bytes());
+        out.extend(data);
+        out
+    }
+
+    fn ascii_entry(tag: u16, text: &str) -> (u16, u16, u32, Vec<u8>) {
+        let mut bytes = text.as_bytes().to_vec();
+        bytes.push(0);
+        (tag, ASCII, bytes.len() as u32, bytes)
+    }
+
+    fn rationals_entry(tag: u16, values: [(u32, u32); 3]) -> (u16, u16, u32, Vec<u8>) {
+        let bytes = values
+            .iter()
+            .flat_map(|(num, denom)| num.to_le_bytes().into_iter().chain(denom.to_le_bytes()))
+            .collect();
+        (tag, RATIONAL, 3, bytes)
+    }
+
+    /// A JPEG holding only an EXIF segment: a phone camera's make, model,
+    /// orientation, capture time and position (37°46'30"N, 122°25'12"W)
+    fn jpeg_with_exif() -> Vec<u8> {
+        let exif_ifd = |start| ifd(start, &[ascii_entry(0x9003, "2024:05:01 10:30:00")]);
+        let gps_ifd = |start| {
+            ifd(
+                start,
+                &[
+                    ascii_entry(0x0001, "N"),
+                    rationals_entry(0x0002, [(37, 1), (46, 1), (30, 1)]),
+                    ascii_entry(0x0003, "W"),
+                    rationals_entry(0x0004, [(122, 1), (25, 1), (1200, 100)]),
+                ],
+            )
+        };
+        let ifd0 = |exif_at: u32, gps_at: u32| {
+            ifd(
+                8,
+                &[
+                    ascii_entry(0x010f, "Google"),
+                    ascii_entry(0x0110, "Pixel 8"),
+                    (0x0112, SHORT, 1, 6u16.to_le_bytes().to_vec()),
+                    (0x8769, LONG, 1, exif_at.to_le_bytes().to_vec()),
+                    (0x8825, LONG, 1, gps_at.to_le_bytes().to_vec()),
+                ],
+            )
+        };
+
+        // The IFDs' sizes don't depend on the offsets in them
+        let exif_at = 8 + ifd0(0, 0).len() as u32;
+        let gps_at = exif_at + exif_ifd(exif_at).len() as u32;
+        let mut tiff = b"II\x2a\x00\x08\x00\x00\x00".to_vec();
+        tiff.extend(ifd0(exif_at, gps_at));
+        tiff.extend(exif_ifd(exif_at));
+        tiff.extend(gps_ifd(gps_at));
+
+        let mut jpeg = vec![0xff, 0xd8, 0xff, 0xe1];
+        jpeg.extend(((2 + 6 + tiff.len()) as u16).to_be_bytes());
+        jpeg.extend(b"Exif\0\0");
+        jpeg.extend(tiff);
+        jpeg.extend([0xff, 0xd9]);
+        jpeg
+    }
+
+    #[test]
+    fn test_read_exif() {
+        let metadata = read_exif(&jpeg_with_exif()).unwrap().unwrap();
+
+        assert_eq!(metadata.camera_make.as_deref(), Some("Google"));
+        assert_eq!(metadata.camera_model.as_deref(), Some("Pixel 8"));
+        assert_eq!(metadata.captured_at.as_deref(), Some("2024-05-01T10:30:00"));
+        assert_eq!(metadata.orientation, Some(6));
+        assert!((metadata.lat.unwrap() - 37.775).abs() < 1e-9);
+        assert!((metadata.lng.unwrap() + 122.42).abs() < 1e-9);
+
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(json["camera_model"], "Pixel 8");
+        assert!(json.get("lat").is_none());
+    }
+
+    #[test]
+    fn test_read_exif_without_exif() {
+        // SOI, then straight to the end of the image
+        assert_eq!(read_exif(&[0xff, 0xd8, 0xff, 0xd9]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_dms_to_degrees() {
+        assert_eq!(dms_to_degrees(37.0, 46.0, 30.0), 37.775);
+        assert_eq!(dms_to_degrees(0.0, 0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_has_exif() {
+        assert!(has_exif("image/jpeg"));
+        assert!(!has_exif("image/png"));
+        assert!(!has_exif("application/pdf"));
+    }
+}
@@ -0,0 +1,255 @@
+//! Inventory export archives.
+//!
+//! An export is a ZIP holding `inventory.json` (an [`InventorySnapshot`] of one
+//! household) and each of its photos at `photos/<s3_key>`. It is built in a temporary file on a spawned task,
+//! uploaded to S3 and handed out as a presigned URL. Serializing the snapshot is the
+//! CPU-heavy part, so it runs on the blocking pool; photos are already compressed and
+//! are streamed into the archive uncompressed, so they never sit in memory whole. As
+//! with webhooks, a frozen Lambda container pauses the job until its next invocation.
+
+use async_zip::tokio::write::ZipFileWriter;
+use async_zip::{Compression, ZipEntryBuilder};
+use chrono::{Duration, Utc};
+use futures::AsyncWriteExt as _;
+use sqlx::PgPool;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt as _;
+use uuid::Uuid;
+
+use crate::models::{
+    Container, EntityTag, ExportStatus, InventorySnapshot, Item, Photo, Room, Shelf, ShelvingUnit,
+    Tag,
+};
+use crate::services::household::attached_in_household_sql;
+use crate::services::s3::{S3Service, DOWNLOAD_URL_EXPIRES_IN_SECS};
+
+const ZIP_CONTENT_TYPE: &str = "application/zip";
+
+/// Key the finished archive is uploaded to
+fn archive_s3_key(export_id: Uuid) -> String {
+    format!("exports/{}.zip", export_id)
+}
+
+/// Where the archive is built before upload (`/tmp` on Lambda)
+fn temp_archive_path(export_id: Uuid) -> PathBuf {
+    std::env::temp_dir().join(format!("export-{}.zip", export_id))
+}
+
+/// Path of a photo inside the archive
+fn photo_entry_name(s3_key: &str) -> String {
+    format!("photos/{}", s3_key.trim_start_matches('/'))
+}
+
+#[derive(Clone)]
+pub struct ExportJob {
+    db: PgPool,
+    s3: Arc<S3Service>,
+}
+
+impl ExportJob {
+    pub fn new(db: PgPool, s3: Arc<S3Service>) -> Self {
+        Self { db, s3 }
+    }
+
+    /// Build the archive of `household_id`'s inventory for a pending export in the
+    /// background, marking the export complete or failed when done
+    pub fn spawn(&self, export_id: Uuid, household_id: Uuid) {
+        let job = self.clone();
+        tokio::spawn(async move {
+            let path = temp_archive_path(export_id);
+            let result = job.run(export_id, household_id, &path).await;
+
+            if let Err(e) = tokio::fs::remove_file(&path).await {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    tracing::warn!("Failed to remove export file {}: {:?}", path.display(), e);
+                }
+            }
+
+            let finished = match result {
+                Ok(()) => Ok(()),
+                Err(e) => {
+                    tracing::error!("Export {} failed: {:?}", export_id, e);
+                    job.mark_failed(export_id, &e.to_string()).await
+                }
+            };
+            if let Err(e) = finished {
+                tracing::error!("Failed to record export {} result: {:?}", export_id, e);
+            }
+        });
+    }
+
+    async fn run(&self, export_id: Uuid, household_id: Uuid, path: &Path) -> anyhow::Result<()> {
+        let snapshot = self.snapshot(household_id).await?;
+        let photo_keys: Vec<String> = snapshot
+            .photos
+            .iter()
+            .map(|photo| photo.s3_key.clone())
+            .collect();
+
+        let json =
+            tokio::task::spawn_blocking(move || serde_json::to_vec_pretty(&snapshot)).await??;
+
+        let photo_count = self.write_archive(path, &json, &photo_keys).await?;
+
+        let s3_key = archive_s3_key(export_id);
+        self.s3
+            .put_object_from_path(&s3_key, path, ZIP_CONTENT_TYPE)
+            .await?;
+        let download_url = self.s3.generate_presigned_download_url(&s3_key).await?;
+        let expires_at = Utc::now() + Duration::seconds(DOWNLOAD_URL_EXPIRES_IN_SECS as i64);
+
+        sqlx::query(
+            r#"
+            UPDATE exports
+            SET status = $1, s3_key = $2, download_url = $3, download_url_expires_at = $4,
+                photo_count = $5, completed_at = NOW()
+            WHERE id = $6
+            "#,
+        )
+        .bind(ExportStatus::Complete.as_str())
+        .bind(&s3_key)
+        .bind(&download_url)
+        .bind(expires_at)
+        .bind(photo_count)
+        .bind(export_id)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(&self, export_id: Uuid, error: &str) -> anyhow::Result<()> {
+        sqlx::query(
+            "UPDATE exports SET status = $1, error = $2, completed_at = NOW() WHERE id = $3",
+        )
+        .bind(ExportStatus::Failed.as_str())
+        .bind(error)
+        .bind(export_id)
+        .execute(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    async fn snapshot(&self, household_id: Uuid) -> anyhow::Result<InventorySnapshot> {
+        Ok(InventorySnapshot {
+            exported_at: Utc::now(),
+            rooms: sqlx::query_as::<_, Room>(
+                "SELECT * FROM rooms WHERE household_id = $1 ORDER BY created_at",
+            )
+            .bind(household_id)
+            .fetch_all(&self.db)
+            .await?,
+            shelving_units: sqlx::query_as::<_, ShelvingUnit>(
+                "SELECT * FROM shelving_units WHERE household_id = $1 ORDER BY created_at",
+            )
+            .bind(household_id)
+            .fetch_all(&self.db)
+            .await?,
+            shelves: sqlx::query_as::<_, Shelf>(
+                "SELECT * FROM shelves WHERE household_id = $1 ORDER BY created_at",
+            )
+            .bind(household_id)
+            .fetch_all(&self.db)
+            .await?,
+            containers: sqlx::query_as::<_, Container>(
+                "SELECT * FROM containers WHERE household_id = $1 ORDER BY created_at",
+            )
+            .bind(household_id)
+            .fetch_all(&self.db)
+            .await?,
+            items: sqlx::query_as::<_, Item>(
+                "SELECT * FROM items WHERE household_id = $1 AND deleted_at IS NULL ORDER BY created_at",
+            )
+            .bind(household_id)
+            .fetch_all(&self.db)
+            .await?,
+            // Tags are shared, so only those the household uses
+            tags: sqlx::query_as::<_, Tag>(&format!(
+                "SELECT * FROM tags t WHERE EXISTS (SELECT 1 FROM entity_tags et WHERE et.tag_id = t.id AND {}) ORDER BY name",
+                attached_in_household_sql("et", 1)
+            ))
+            .bind(household_id)
+            .fetch_all(&self.db)
+            .await?,
+            entity_tags: sqlx::query_as::<_, EntityTag>(&format!(
+                "SELECT * FROM entity_tags et WHERE {} ORDER BY created_at",
+                attached_in_household_sql("et", 1)
+            ))
+            .bind(household_id)
+            .fetch_all(&self.db)
+            .await?,
+            photos: sqlx::query_as::<_, Photo>(&format!(
+                "SELECT * FROM photos p WHERE {} ORDER BY created_at",
+                attached_in_household_sql("p", 1)
+            ))
+            .bind(household_id)
+            .fetch_all(&self.db)
+            .await?,
+        })
+    }
+
+    /// Write the archive to `path`, returning how many photos made it in. Photos whose
+    /// object can't be fetched are skipped rather than failing the export.
+    async fn write_archive(
+        &self,
+        path: &Path,
+        inventory_json: &[u8],
+        photo_keys: &[String],
+    ) -> anyhow::Result<i32> {
+        let file = tokio::fs::File::create(path).await?;
+        let mut zip = ZipFileWriter::with_tokio(file);
+
+        zip.write_entry_whole(
+            ZipEntryBuilder::new("inventory.json".to_string().into(), Compression::Deflate),
+            inventory_json,
+        )
+        .await?;
+
+        let mut photo_count = 0;
+        for s3_key in photo_keys {
+            let mut body = match self.s3.get_object_stream(s3_key).await {
+                Ok(body) => body,
+                Err(e) => {
+                    tracing::warn!("Skipping photo {} in export: {:?}", s3_key, e);
+                    continue;
+                }
+            };
+
+            let mut entry = zip
+                .write_entry_stream(ZipEntryBuilder::new(
+                    photo_entry_name(s3_key).into(),
+                    Compression::Stored,
+                ))
+                .await?;
+            while let Some(chunk) = body.try_next().await? {
+                entry.write_all(&chunk).await?;
+            }
+            entry.close().await?;
+            photo_count += 1;
+        }
+
+        let mut file = zip.close().await?.into_inner();
+        file.flush().await?;
+        Ok(photo_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_paths() {
+        let id = Uuid::nil();
+        assert_eq!(
+            archive_s3_key(id),
+            "exports/00000000-0000-0000-0000-000000000000.zip"
+        );
+        assert_eq!(
+            photo_entry_name("item/123/photo.jpg"),
+            "photos/item/123/photo.jpg"
+        );
+        assert_eq!(photo_entry_name("/room/1.png"), "photos/room/1.png");
+    }
+}
@@ -0,0 +1,178 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::models::{HouseholdRole, SearchResultKind, FLOOR_PLAN_ENTITY_TYPE};
+
+/// The household `user_id` works in: the one they joined most recently, so accepting
+/// an invite switches them to the new household. A user without any membership (a
+/// new sign-up) gets a household of their own, which they own.
+pub async fn resolve_household(db: &PgPool, user_id: Uuid) -> Result<Uuid, ApiError> {
+    match current_household(db, user_id).await? {
+        Some(household_id) => Ok(household_id),
+        None => create_household(db, user_id, "Home").await,
+    }
+}
+
+/// The household `user_id` joined most recently, without creating one if they have none
+pub async fn current_household(db: &PgPool, user_id: Uuid) -> Result<Option<Uuid>, ApiError> {
+    let household_id = sqlx::query_scalar(
+        "SELECT household_id FROM household_memberships WHERE user_id = $1 ORDER BY joined_at DESC LIMIT 1",
+    )
+    .bind(user_id)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(household_id)
+}
+
+/// Create a household owned by `user_id`
+pub async fn create_household(db: &PgPool, user_id: Uuid, name: &str) -> Result<Uuid, ApiError> {
+    let household_id = Uuid::new_v4();
+
+    let mut tx = db.begin().await?;
+
+    sqlx::query("INSERT INTO households (id, name, created_by) VALUES ($1, $2, $3)")
+        .bind(household_id)
+        .bind(name)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        "INSERT INTO household_memberships (household_id, user_id, role) VALUES ($1, $2, $3)",
+    )
+    .bind(household_id)
+    .bind(user_id)
+    .bind(HouseholdRole::Owner.as_str())
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    tracing::info!("Created household {} for user {}", household_id, user_id);
+    Ok(household_id)
+}
+
+/// `user_id`'s role in `household_id`, or `None` if they aren't a member
+pub async fn membership_role(
+    db: &PgPool,
+    household_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<HouseholdRole>, ApiError> {
+    let role: Option<String> = sqlx::query_scalar(
+        "SELECT role FROM household_memberships WHERE household_id = $1 AND user_id = $2",
+    )
+    .bind(household_id)
+    .bind(user_id)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(role.map(|role| role.parse().unwrap_or(HouseholdRole::Member)))
+}
+
+/// Entities that belong to a household through their `household_id` column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HouseholdEntity {
+    Room,
+    ShelvingUnit,
+    Shelf,
+    Container,
+    Item,
+}
+
+impl HouseholdEntity {
+    pub(crate) fn table(self) -> &'static str {
+        match self {
+            HouseholdEntity::Room => "rooms",
+            HouseholdEntity::ShelvingUnit => "shelving_units",
+            HouseholdEntity::Shelf => "shelves",
+            HouseholdEntity::Container => "containers",
+            HouseholdEntity::Item => "items",
+        }
+    }
+
+    /// Name used in 404 messages
+    fn label(self) -> &'static str {
+        match self {
+            HouseholdEntity::Room => "Room",
+            HouseholdEntity::ShelvingUnit => "Shelving unit",
+            HouseholdEntity::Shelf => "Shelf",
+            HouseholdEntity::Container => "Container",
+            HouseholdEntity::Item => "Item",
+        }
+    }
+
+    /// From an entity type as stored on photos, labels and entity tags ("unit" and
+    /// "shelving_unit" both name a shelving unit, and a floor plan belongs to its room)
+    pub fn from_entity_type(entity_type: &str) -> Option<Self> {
+        match entity_type {
+            "room" | FLOOR_PLAN_ENTITY_TYPE => Some(HouseholdEntity::Room),
+            "unit" | "shelving_unit" => Some(HouseholdEntity::ShelvingUnit),
+            "shelf" => Some(HouseholdEntity::Shelf),
+            "container" => Some(HouseholdEntity::Container),
+            "item" => Some(HouseholdEntity::Item),
+            _ => None,
+        }
+    }
+}
+
+impl From<SearchResultKind> for HouseholdEntity {
+    fn from(kind: SearchResultKind) -> Self {
+        match kind {
+            SearchResultKind::Room => HouseholdEntity::Room,
+            SearchResultKind::Unit => HouseholdEntity::ShelvingUnit,
+            SearchResultKind::Shelf => HouseholdEntity::Shelf,
+            SearchResultKind::Container => HouseholdEntity::Container,
+            SearchResultKind::Item => HouseholdEntity::Item,
+        }
+    }
+}
+
+/// SQL condition that the row `alias`, with the `entity_type` and `entity_id` columns of
+/// photos and entity tags, is attached to an entity of the household bound as `$param`
+pub fn attached_in_household_sql(alias: &str, param: u8) -> String {
+    format!(
+        r#"
+        CASE
+            WHEN {alias}.entity_type IN ('room', '{floor_plan}') THEN
+                EXISTS (SELECT 1 FROM rooms WHERE id = {alias}.entity_id AND household_id = ${param})
+            WHEN {alias}.entity_type IN ('unit', 'shelving_unit') THEN
+                EXISTS (SELECT 1 FROM shelving_units WHERE id = {alias}.entity_id AND household_id = ${param})
+            WHEN {alias}.entity_type = 'shelf' THEN
+                EXISTS (SELECT 1 FROM shelves WHERE id = {alias}.entity_id AND household_id = ${param})
+            WHEN {alias}.entity_type = 'container' THEN
+                EXISTS (SELECT 1 FROM containers WHERE id = {alias}.entity_id AND household_id = ${param})
+            WHEN {alias}.entity_type = 'item' THEN
+                EXISTS (SELECT 1 FROM items WHERE id = {alias}.entity_id AND household_id = ${param})
+            ELSE FALSE
+        END
+        "#,
+        floor_plan = FLOOR_PLAN_ENTITY_TYPE,
+    )
+}
+
+/// 404 unless `id` is a `entity` of `household_id`. Another household's entity is
+/// reported exactly like a missing one, so ids can't be probed across households.
+pub async fn ensure_in_household(
+    db: &PgPool,
+    household_id: Uuid,
+    entity: HouseholdEntity,
+    id: Uuid,
+) -> Result<(), ApiError> {
+    let query = format!(
+        "SELECT EXISTS(SELECT 1 FROM {} WHERE id = $1 AND household_id = $2)",
+        entity.table()
+    );
+    let in_household: bool = sqlx::query_scalar(&query)
+        .bind(id)
+        .bind(household_id)
+        .fetch_one(db)
+        .await?;
+
+    if in_household {
+        Ok(())
+    } else {
+        Err(ApiError::not_found(entity.label(), id))
+    }
+}
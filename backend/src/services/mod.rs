@@ -1,10 +1,20 @@
 pub mod audit;
 pub mod captcha;
+pub mod cascade;
+pub mod exif;
+pub mod export;
+pub mod household;
 pub mod r#move;
+pub mod phash;
 pub mod qr_pdf;
 pub mod s3;
+pub mod search;
+pub mod thumbnail;
 pub mod vision;
+pub mod webhook;
 
-pub use captcha::CaptchaService;
-pub use qr_pdf::generate_label_pdf;
-pub use vision::VisionService;
+pub use captcha::{CaptchaService, CaptchaVerifier, NoOpCaptchaService};
+pub use qr_pdf::{generate_label_pdf, generate_label_pdf_with_names};
+#[cfg(test)]
+pub use vision::NoOpVisionService;
+pub use vision::{VisionBackendTrait, VisionService};
@@ -2,25 +2,96 @@ use axum::http::StatusCode;
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::error::ApiError;
+use crate::models::ShelvingUnitCapacity;
+
+/// How deep a container sits: 1 when it is on a shelf, its parent's depth + 1 when
+/// nested. `$1` is the container and `$2` caps the walk, so a (corrupt) cyclic
+/// hierarchy still terminates.
+const CONTAINER_DEPTH_SQL: &str = r#"
+    WITH RECURSIVE ancestors AS (
+        SELECT id, parent_container_id, 1 AS d FROM containers WHERE id = $1
+        UNION ALL
+        SELECT c.id, c.parent_container_id, a.d + 1
+        FROM containers c
+        JOIN ancestors a ON c.id = a.parent_container_id
+        WHERE a.d < $2
+    )
+    SELECT MAX(d) FROM ancestors
+"#;
+
+/// Levels of containers nested inside `$1`: 0 when it holds none. `$2` caps the walk.
+const CONTAINER_HEIGHT_SQL: &str = r#"
+    WITH RECURSIVE depth_check AS (
+        SELECT id, 0 AS d FROM containers WHERE id = $1
+        UNION ALL
+        SELECT c.id, dc.d + 1
+        FROM containers c
+        JOIN depth_check dc ON c.parent_container_id = dc.id
+        WHERE dc.d < $2
+    )
+    SELECT MAX(d) FROM depth_check
+"#;
+
+/// Depth of the deepest container once a container holding `height` levels of
+/// containers is put inside a parent at `parent_depth`
+fn nested_depth(parent_depth: i32, height: i32) -> i32 {
+    parent_depth + 1 + height
+}
+
+/// Reject with 422 when putting `container_id` (or a new, empty container when
+/// `None`) inside `parent_id` would nest containers more than `max_depth` deep
+pub async fn check_container_depth(
+    db: &PgPool,
+    parent_id: Uuid,
+    container_id: Option<Uuid>,
+    max_depth: i32,
+) -> Result<(), ApiError> {
+    // Walking one level past the limit is enough to tell it has been exceeded
+    let cap = max_depth.saturating_add(1);
+
+    let parent_depth: Option<i32> = sqlx::query_scalar(CONTAINER_DEPTH_SQL)
+        .bind(parent_id)
+        .bind(cap)
+        .fetch_one(db)
+        .await?;
+
+    let height = match container_id {
+        Some(id) => sqlx::query_scalar::<_, Option<i32>>(CONTAINER_HEIGHT_SQL)
+            .bind(id)
+            .bind(cap)
+            .fetch_one(db)
+            .await?
+            .unwrap_or(0),
+        None => 0,
+    };
+
+    let depth = nested_depth(parent_depth.unwrap_or(0), height);
+    if depth > max_depth {
+        return Err(ApiError::UnprocessableEntity(format!(
+            "Containers can be nested at most {} deep, this would nest them {} deep",
+            max_depth, depth
+        )));
+    }
+
+    Ok(())
+}
+
 /// Move a shelving unit to a different room
 pub async fn move_shelving_unit(
     db: &PgPool,
     unit_id: Uuid,
     target_room_id: Uuid,
-) -> Result<(), StatusCode> {
+) -> Result<(), ApiError> {
     // Verify target room exists
     let room_exists = sqlx::query("SELECT id FROM rooms WHERE id = $1")
         .bind(target_room_id)
         .fetch_optional(db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to verify room: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .await?
         .is_some();
 
     if !room_exists {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
 
     // Update shelving unit's room_id
@@ -28,96 +99,155 @@ pub async fn move_shelving_unit(
         .bind(target_room_id)
         .bind(unit_id)
         .execute(db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to move shelving unit: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+        .await?;
 
     Ok(())
 }
 
-/// Move a shelf to a different shelving unit
-pub async fn move_shelf(
+/// A shelving unit's shelf count against its `max_shelves`, or `None` if the unit
+/// doesn't exist
+pub async fn shelving_unit_capacity(
+    db: &PgPool,
+    unit_id: Uuid,
+) -> Result<Option<ShelvingUnitCapacity>, ApiError> {
+    let row: Option<(Option<i32>, i32)> = sqlx::query_as(
+        r#"
+        SELECT u.max_shelves, (SELECT COUNT(*)::INT FROM shelves s WHERE s.shelving_unit_id = u.id)
+        FROM shelving_units u
+        WHERE u.id = $1
+        "#,
+    )
+    .bind(unit_id)
+    .fetch_optional(db)
+    .await?;
+
+    Ok(row.map(|(max_shelves, current)| ShelvingUnitCapacity::new(max_shelves, current)))
+}
+
+/// Reject with 409 `CAPACITY_EXCEEDED` when `unit_id` has no room for another shelf.
+/// Returns the capacity before the shelf is added.
+pub async fn check_unit_capacity(
     db: &PgPool,
-    shelf_id: Uuid,
-    target_unit_id: Uuid,
-) -> Result<(), StatusCode> {
+    unit_id: Uuid,
+) -> Result<ShelvingUnitCapacity, ApiError> {
+    let capacity = shelving_unit_capacity(db, unit_id).await?.ok_or_else(|| {
+        ApiError::bad_request(format!("Shelving unit with id {} does not exist", unit_id))
+    })?;
+
+    if let Some(max) = capacity.max_shelves.filter(|_| capacity.is_full()) {
+        tracing::warn!(
+            "Shelving unit {} is full ({} of {} shelves)",
+            unit_id,
+            capacity.current_shelves,
+            max
+        );
+        return Err(ApiError::CapacityExceeded {
+            current: capacity.current_shelves,
+            max,
+        });
+    }
+
+    Ok(capacity)
+}
+
+/// Move a shelf to a different shelving unit, after its last shelf
+pub async fn move_shelf(db: &PgPool, shelf_id: Uuid, target_unit_id: Uuid) -> Result<(), ApiError> {
     // Verify target unit exists
     let unit_exists = sqlx::query("SELECT id FROM shelving_units WHERE id = $1")
         .bind(target_unit_id)
         .fetch_optional(db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to verify shelving unit: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?
+        .await?
         .is_some();
 
     if !unit_exists {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
 
-    // Update shelf's shelving_unit_id
-    sqlx::query("UPDATE shelves SET shelving_unit_id = $1, updated_at = NOW() WHERE id = $2")
-        .bind(target_unit_id)
-        .bind(shelf_id)
-        .execute(db)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to move shelf: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
+    // Update shelf's shelving_unit_id; its old position may be taken in the target unit
+    sqlx::query(
+        r#"
+        UPDATE shelves
+        SET shelving_unit_id = $1,
+            position = (SELECT COALESCE(MAX(position), 0) + 1 FROM shelves WHERE shelving_unit_id = $1),
+            updated_at = NOW()
+        WHERE id = $2
+        "#,
+    )
+    .bind(target_unit_id)
+    .bind(shelf_id)
+    .execute(db)
+    .await?;
 
     Ok(())
 }
 
-/// Move a container to a different location (shelf or parent container)
+/// Position after the last container on `shelf_id` or inside `parent_container_id`,
+/// for a container added or moved there
+pub async fn next_container_position(
+    db: &PgPool,
+    shelf_id: Option<Uuid>,
+    parent_container_id: Option<Uuid>,
+) -> Result<i32, ApiError> {
+    let max_position: Option<i32> = sqlx::query_scalar(
+        "SELECT MAX(position) FROM containers WHERE shelf_id = $1 OR parent_container_id = $2",
+    )
+    .bind(shelf_id)
+    .bind(parent_container_id)
+    .fetch_one(db)
+    .await?;
+
+    Ok(max_position.unwrap_or(0) + 1)
+}
+
+/// Clear `shelf_id` on every container nested at any depth below `$1`
+const CLEAR_DESCENDANT_SHELVES_SQL: &str = r#"
+    WITH RECURSIVE descendants AS (
+        SELECT id FROM containers WHERE parent_container_id = $1
+        UNION ALL
+        SELECT c.id
+        FROM containers c
+        JOIN descendants d ON c.parent_container_id = d.id
+    )
+    UPDATE containers SET shelf_id = NULL, updated_at = NOW()
+    WHERE id IN (SELECT id FROM descendants) AND shelf_id IS NOT NULL
+"#;
+
+/// Move a container to a different location (shelf or parent container), after the
+/// containers already there. Moves into a parent are limited to `max_depth` levels
+/// of nesting, see [`check_container_depth`].
 pub async fn move_container(
     db: &PgPool,
     container_id: Uuid,
     target_shelf_id: Option<Uuid>,
     target_parent_id: Option<Uuid>,
-) -> Result<(), StatusCode> {
+    max_depth: i32,
+) -> Result<(), ApiError> {
     // Validate location constraint
-    match (target_shelf_id, target_parent_id) {
+    let (shelf_id, parent_id, position) = match (target_shelf_id, target_parent_id) {
         (Some(sid), None) => {
             // Verify shelf exists
             let shelf_exists = sqlx::query("SELECT id FROM shelves WHERE id = $1")
                 .bind(sid)
                 .fetch_optional(db)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to verify shelf: {:?}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?
+                .await?
                 .is_some();
 
             if !shelf_exists {
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(StatusCode::BAD_REQUEST.into());
             }
 
             // Prevent moving container to itself
             if container_id == sid {
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(StatusCode::BAD_REQUEST.into());
             }
 
-            sqlx::query(
-                "UPDATE containers SET shelf_id = $1, parent_container_id = NULL, updated_at = NOW() WHERE id = $2"
-            )
-            .bind(sid)
-            .bind(container_id)
-            .execute(db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to move container: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            let position = next_container_position(db, Some(sid), None).await?;
+            (Some(sid), None, position)
         }
         (None, Some(pid)) => {
             // Verify parent exists and prevent circular references
             if container_id == pid {
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(StatusCode::BAD_REQUEST.into());
             }
 
             // Check for circular reference: ensure target parent is not a descendant
@@ -136,44 +266,51 @@ pub async fn move_container(
             .bind(container_id)
             .bind(pid)
             .fetch_one(db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to check circular reference: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .await?;
 
             if is_descendant {
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(StatusCode::BAD_REQUEST.into());
             }
 
             let parent_exists = sqlx::query("SELECT id FROM containers WHERE id = $1")
                 .bind(pid)
                 .fetch_optional(db)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to verify parent container: {:?}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?
+                .await?
                 .is_some();
 
             if !parent_exists {
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(StatusCode::BAD_REQUEST.into());
             }
 
-            sqlx::query(
-                "UPDATE containers SET shelf_id = NULL, parent_container_id = $1, updated_at = NOW() WHERE id = $2"
-            )
-            .bind(pid)
-            .bind(container_id)
-            .execute(db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to move container: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            check_container_depth(db, pid, Some(container_id), max_depth).await?;
+
+            let position = next_container_position(db, None, Some(pid)).await?;
+            (None, Some(pid), position)
         }
-        _ => return Err(StatusCode::BAD_REQUEST),
-    }
+        _ => return Err(StatusCode::BAD_REQUEST.into()),
+    };
+
+    let mut tx = db.begin().await?;
+
+    // Only the moved container changes place; its contents stay inside it
+    sqlx::query(
+        "UPDATE containers SET shelf_id = $1, parent_container_id = $2, position = $3, updated_at = NOW() WHERE id = $4",
+    )
+    .bind(shelf_id)
+    .bind(parent_id)
+    .bind(position)
+    .bind(container_id)
+    .execute(&mut *tx)
+    .await?;
+
+    // Nested containers sit in a container, not on a shelf, so none of them should
+    // still point at the shelf the moved container came from
+    sqlx::query(CLEAR_DESCENDANT_SHELVES_SQL)
+        .bind(container_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
 
     Ok(())
 }
@@ -184,7 +321,7 @@ pub async fn move_item(
     item_id: Uuid,
     target_shelf_id: Option<Uuid>,
     target_container_id: Option<Uuid>,
-) -> Result<(), StatusCode> {
+) -> Result<(), ApiError> {
     // Validate location constraint
     match (target_shelf_id, target_container_id) {
         (Some(sid), None) => {
@@ -192,15 +329,11 @@ pub async fn move_item(
             let shelf_exists = sqlx::query("SELECT id FROM shelves WHERE id = $1")
                 .bind(sid)
                 .fetch_optional(db)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to verify shelf: {:?}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?
+                .await?
                 .is_some();
 
             if !shelf_exists {
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(StatusCode::BAD_REQUEST.into());
             }
 
             sqlx::query(
@@ -209,26 +342,18 @@ pub async fn move_item(
             .bind(sid)
             .bind(item_id)
             .execute(db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to move item: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .await?;
         }
         (None, Some(cid)) => {
             // Verify container exists
             let container_exists = sqlx::query("SELECT id FROM containers WHERE id = $1")
                 .bind(cid)
                 .fetch_optional(db)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Failed to verify container: {:?}", e);
-                    StatusCode::INTERNAL_SERVER_ERROR
-                })?
+                .await?
                 .is_some();
 
             if !container_exists {
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(StatusCode::BAD_REQUEST.into());
             }
 
             sqlx::query(
@@ -237,14 +362,245 @@ pub async fn move_item(
             .bind(cid)
             .bind(item_id)
             .execute(db)
-            .await
-            .map_err(|e| {
-                tracing::error!("Failed to move item: {:?}", e);
-                StatusCode::INTERNAL_SERVER_ERROR
-            })?;
+            .await?;
+        }
+        _ => return Err(StatusCode::BAD_REQUEST.into()),
+    }
+
+    Ok(())
+}
+
+/// Requested ids missing from `found`, in request order without duplicates
+fn missing_ids(requested: &[Uuid], found: &[Uuid]) -> Vec<Uuid> {
+    let mut missing: Vec<Uuid> = Vec::new();
+    for id in requested {
+        if !found.contains(id) && !missing.contains(id) {
+            missing.push(*id);
+        }
+    }
+    missing
+}
+
+/// Move several of a household's items to one of its shelves or containers in a single
+/// transaction. Nothing moves if any item is missing (404) or the target is invalid (422).
+pub async fn move_items(
+    db: &PgPool,
+    household_id: Uuid,
+    item_ids: &[Uuid],
+    target_shelf_id: Option<Uuid>,
+    target_container_id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let target_sql = match (target_shelf_id, target_container_id) {
+        (Some(_), None) => "SELECT id FROM shelves WHERE id = $1 AND household_id = $2",
+        (None, Some(_)) => "SELECT id FROM containers WHERE id = $1 AND household_id = $2",
+        _ => {
+            return Err(ApiError::UnprocessableEntity(
+                "Exactly one of target_shelf_id or target_container_id is required".to_string(),
+            ))
         }
-        _ => return Err(StatusCode::BAD_REQUEST),
+    };
+    let target_id = target_shelf_id.or(target_container_id);
+
+    let mut tx = db.begin().await?;
+
+    let target_exists = sqlx::query(target_sql)
+        .bind(target_id)
+        .bind(household_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .is_some();
+    if !target_exists {
+        return Err(ApiError::UnprocessableEntity(format!(
+            "Target {} with id {} does not exist",
+            if target_shelf_id.is_some() {
+                "shelf"
+            } else {
+                "container"
+            },
+            target_id.unwrap_or_default()
+        )));
+    }
+
+    let found: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM items WHERE id = ANY($1) AND household_id = $2 AND deleted_at IS NULL FOR UPDATE",
+    )
+    .bind(item_ids)
+    .bind(household_id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let missing = missing_ids(item_ids, &found);
+    if !missing.is_empty() {
+        let ids: Vec<String> = missing.iter().map(Uuid::to_string).collect();
+        return Err(ApiError::NotFound(format!(
+            "Items not found: {}",
+            ids.join(", ")
+        )));
     }
 
+    sqlx::query(
+        "UPDATE items SET shelf_id = $1, container_id = $2, updated_at = NOW() WHERE id = ANY($3)",
+    )
+    .bind(target_shelf_id)
+    .bind(target_container_id)
+    .bind(item_ids)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_pool;
+
+    async fn container_location(pool: &PgPool, id: Uuid) -> (Option<Uuid>, Option<Uuid>) {
+        sqlx::query_as("SELECT shelf_id, parent_container_id FROM containers WHERE id = $1")
+            .bind(id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_nested_depth() {
+        // A new container directly inside one on a shelf
+        assert_eq!(nested_depth(1, 0), 2);
+        // A container holding two levels moved into one at depth 3
+        assert_eq!(nested_depth(3, 2), 6);
+    }
+
+    #[test]
+    fn test_missing_ids_keeps_request_order() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(missing_ids(&[c, a, b, c], &[a]), vec![c, b]);
+        assert!(missing_ids(&[a, b], &[b, a]).is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore] // Only run when DATABASE_URL is set
+    async fn test_move_container_clears_descendant_shelves() {
+        let pool = create_test_pool().await;
+        let user_id = Uuid::new_v4();
+        let (room_id, unit_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (old_shelf_id, new_shelf_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (outer_id, middle_id, inner_id, crate_id) = (
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+        );
+
+        sqlx::query("INSERT INTO rooms (id, name, created_by) VALUES ($1, 'Garage', $2)")
+            .bind(room_id)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO shelving_units (id, room_id, name, created_by) VALUES ($1, $2, 'Rack', $3)",
+        )
+        .bind(unit_id)
+        .bind(room_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (shelf_id, position) in [(old_shelf_id, 1), (new_shelf_id, 2)] {
+            sqlx::query(
+                "INSERT INTO shelves (id, shelving_unit_id, name, position, created_by) VALUES ($1, $2, 'Shelf', $3, $4)",
+            )
+            .bind(shelf_id)
+            .bind(unit_id)
+            .bind(position)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        // Three levels on the old shelf
+        for (id, parent_id) in [
+            (outer_id, None),
+            (middle_id, Some(outer_id)),
+            (inner_id, Some(middle_id)),
+        ] {
+            sqlx::query(
+                "INSERT INTO containers (id, shelf_id, parent_container_id, name, created_by) VALUES ($1, $2, $3, 'Box', $4)",
+            )
+            .bind(id)
+            .bind(parent_id.is_none().then_some(old_shelf_id))
+            .bind(parent_id)
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query(
+            "INSERT INTO containers (id, shelf_id, name, created_by) VALUES ($1, $2, 'Crate', $3)",
+        )
+        .bind(crate_id)
+        .bind(new_shelf_id)
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let to_shelf = move_container(&pool, outer_id, Some(new_shelf_id), None, 10).await;
+        let after_shelf_move = [
+            container_location(&pool, outer_id).await,
+            container_location(&pool, middle_id).await,
+            container_location(&pool, inner_id).await,
+        ];
+
+        let to_parent = move_container(&pool, outer_id, None, Some(crate_id), 10).await;
+        let after_parent_move = [
+            container_location(&pool, outer_id).await,
+            container_location(&pool, middle_id).await,
+            container_location(&pool, inner_id).await,
+        ];
+
+        sqlx::query("DELETE FROM containers WHERE id = ANY($1)")
+            .bind(vec![inner_id, middle_id, outer_id, crate_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM shelves WHERE id = ANY($1)")
+            .bind(vec![old_shelf_id, new_shelf_id])
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM shelving_units WHERE id = $1")
+            .bind(unit_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM rooms WHERE id = $1")
+            .bind(room_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert!(to_shelf.is_ok());
+        assert_eq!(
+            after_shelf_move,
+            [
+                (Some(new_shelf_id), None),
+                (None, Some(outer_id)),
+                (None, Some(middle_id)),
+            ]
+        );
+
+        assert!(to_parent.is_ok());
+        assert_eq!(
+            after_parent_move,
+            [
+                (None, Some(crate_id)),
+                (None, Some(outer_id)),
+                (None, Some(middle_id)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use anyhow::Context;
+use image::imageops::FilterType;
+use image::GrayImage;
+use sqlx::PgPool;
+use std::f64::consts::PI;
+
+use crate::models::Photo;
+use crate::services::s3::S3Service;
+
+/// Images are scaled to a square this many pixels on a side before the DCT
+const DCT_SIZE: usize = 32;
+/// The hash keeps this many of the lowest frequencies in each direction (8x8 = 64 bits)
+const HASH_SIZE: usize = 8;
+
+/// DCT-II basis, `[frequency][sample]`, for the frequencies the hash keeps
+fn dct_basis() -> [[f64; DCT_SIZE]; HASH_SIZE] {
+    let mut basis = [[0.0; DCT_SIZE]; HASH_SIZE];
+    for (k, row) in basis.iter_mut().enumerate() {
+        for (n, value) in row.iter_mut().enumerate() {
+            *value = ((2 * n + 1) as f64 * k as f64 * PI / (2 * DCT_SIZE) as f64).cos();
+        }
+    }
+    basis
+}
+
+/// 64-bit pHash of a 32x32 grayscale image: each of the 8x8 lowest DCT frequencies
+/// sets its bit when it is above the median of those frequencies (the DC term,
+/// which only reflects overall brightness, is left out of the median)
+fn hash_grayscale(image: &GrayImage) -> u64 {
+    let basis = dct_basis();
+    let pixel = |x: usize, y: usize| image.get_pixel(x as u32, y as u32)[0] as f64;
+
+    // Rows first, then columns: the 2D DCT is separable
+    let mut rows = [[0.0; HASH_SIZE]; DCT_SIZE];
+    for (y, row) in rows.iter_mut().enumerate() {
+        for (u, value) in row.iter_mut().enumerate() {
+            *value = (0..DCT_SIZE).map(|x| pixel(x, y) * basis[u][x]).sum();
+        }
+    }
+    let mut coefficients = [0.0; HASH_SIZE * HASH_SIZE];
+    for v in 0..HASH_SIZE {
+        for u in 0..HASH_SIZE {
+            coefficients[v * HASH_SIZE + u] = (0..DCT_SIZE).map(|y| rows[y][u] * basis[v][y]).sum();
+        }
+    }
+
+    let mut ac = coefficients[1..].to_vec();
+    ac.sort_by(f64::total_cmp);
+    let median = ac[ac.len() / 2];
+
+    coefficients
+        .iter()
+        .fold(0, |hash, &c| (hash << 1) | u64::from(c > median))
+}
+
+/// Perceptual hash of an encoded image. Near-duplicates (resized, recompressed,
+/// slightly brightened) differ in only a few bits. CPU bound; run it on a blocking
+/// thread.
+pub fn perceptual_hash(bytes: &[u8]) -> anyhow::Result<u64> {
+    let image = image::load_from_memory(bytes).context("Failed to decode image")?;
+    let gray = image
+        .resize_exact(DCT_SIZE as u32, DCT_SIZE as u32, FilterType::Triangle)
+        .to_luma8();
+    Ok(hash_grayscale(&gray))
+}
+
+/// Hashes at most this many bits apart count as the same picture unless the
+/// request picks another threshold
+pub const DEFAULT_SIMILARITY_THRESHOLD: u32 = 10;
+
+/// Number of bits two hashes differ in: 0 for the same image, around 32 for
+/// unrelated ones
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// The candidates within `threshold` bits of `target`, closest first, at most `limit`
+pub fn closest_matches<T>(
+    target: u64,
+    candidates: impl IntoIterator<Item = (T, u64)>,
+    threshold: u32,
+    limit: usize,
+) -> Vec<(T, u32)> {
+    let mut matches: Vec<(T, u32)> = candidates
+        .into_iter()
+        .map(|(candidate, hash)| (candidate, hamming_distance(target, hash)))
+        .filter(|(_, distance)| *distance <= threshold)
+        .collect();
+    matches.sort_by_key(|(_, distance)| *distance);
+    matches.truncate(limit);
+    matches
+}
+
+/// Hashes are stored as 16 hex digits
+pub fn format_phash(hash: u64) -> String {
+    format!("{:016x}", hash)
+}
+
+pub fn parse_phash(hex: &str) -> Option<u64> {
+    (hex.len() == 16)
+        .then(|| u64::from_str_radix(hex, 16).ok())
+        .flatten()
+}
+
+/// Hash a photo's full image from S3 and record it. Returns the stored hash.
+pub async fn compute_photo_phash(
+    db: &PgPool,
+    s3: &S3Service,
+    photo: &Photo,
+) -> anyhow::Result<String> {
+    let original = s3.get_object_bytes(&photo.s3_key).await?;
+    let hash = tokio::task::spawn_blocking(move || perceptual_hash(&original)).await??;
+    let phash = format_phash(hash);
+
+    sqlx::query("UPDATE photos SET phash = $1 WHERE id = $2")
+        .bind(&phash)
+        .bind(photo.id)
+        .execute(db)
+        .await?;
+
+    Ok(phash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbImage};
+    use std::io::Cursor;
+
+    fn png(image: &RgbImage) -> Vec<u8> {
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+        png
+    }
+
+    /// A diagonal gradient with a bright block in one corner
+    fn scene(width: u32, height: u32, brighten: u8) -> RgbImage {
+        RgbImage::from_fn(width, height, |x, y| {
+            let base = (x * 150 / width + y * 30 / height) as u8 + brighten;
+            let in_block = x < width / 3 && y < height / 3;
+            let value = if in_block { 255 } else { base };
+            image::Rgb([value, value, value])
+        })
+    }
+
+    #[test]
+    fn test_identical_images_hash_the_same() {
+        let a = perceptual_hash(&png(&scene(640, 480, 0))).unwrap();
+        let b = perceptual_hash(&png(&scene(640, 480, 0))).unwrap();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_near_duplicates_are_close() {
+        let original = perceptual_hash(&png(&scene(640, 480, 0))).unwrap();
+        let resized = perceptual_hash(&png(&scene(320, 240, 0))).unwrap();
+        let brighter = perceptual_hash(&png(&scene(640, 480, 20))).unwrap();
+        assert!(hamming_distance(original, resized) <= DEFAULT_SIMILARITY_THRESHOLD);
+        assert!(hamming_distance(original, brighter) <= DEFAULT_SIMILARITY_THRESHOLD);
+    }
+
+    #[test]
+    fn test_different_images_are_far_apart() {
+        let original = perceptual_hash(&png(&scene(640, 480, 0))).unwrap();
+        let stripes = RgbImage::from_fn(640, 480, |x, _| {
+            let value = if (x / 40) % 2 == 0 { 0 } else { 255 };
+            image::Rgb([value, value, value])
+        });
+        let other = perceptual_hash(&png(&stripes)).unwrap();
+        assert!(hamming_distance(original, other) > DEFAULT_SIMILARITY_THRESHOLD);
+    }
+
+    #[test]
+    fn test_closest_matches() {
+        let candidates = vec![
+            ("far", 0xffff),
+            ("same", 0b1),
+            ("near", 0b111),
+            ("also", 0b1011),
+        ];
+        let matches = closest_matches(0b1, candidates, 3, 10);
+        assert_eq!(matches, vec![("same", 0), ("near", 2), ("also", 2)]);
+
+        let candidates = vec![("same", 0b1), ("near", 0b111)];
+        assert_eq!(closest_matches(0b1, candidates, 3, 1), vec![("same", 0)]);
+    }
+
+    #[test]
+    fn test_phash_hex_round_trip() {
+        for hash in [0, 1, u64::MAX, 0x8000_0000_0000_0001] {
+            assert_eq!(parse_phash(&format_phash(hash)), Some(hash));
+        }
+        assert_eq!(format_phash(1), "0000000000000001");
+        assert_eq!(parse_phash("abc"), None);
+        assert_eq!(parse_phash("zzzzzzzzzzzzzzzz"), None);
+    }
+
+    #[test]
+    fn test_perceptual_hash_rejects_non_images() {
+        assert!(perceptual_hash(b"not an image").is_err());
+    }
+}
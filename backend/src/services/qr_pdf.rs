@@ -3,8 +3,14 @@ use ::image::ImageEncoder;
 use ::image::Rgb;
 use anyhow::{Context, Result};
 use printpdf::*;
+use qrcode::types::QrError;
 use qrcode::QrCode;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::io::{BufWriter, Write};
+use std::str::FromStr;
+
+use crate::models::{CustomLabelTemplate, MAX_LABEL_TEMPLATE_CELLS_PER_SIDE};
 
 /// Avery 18660 template specifications
 /// 1" x 2-5/8" labels, 30 labels per sheet (3 columns x 10 rows)
@@ -22,8 +28,8 @@ impl Avery18660 {
     pub const LABEL_WIDTH_INCHES: f32 = 2.625; // 2-5/8"
     pub const LABEL_HEIGHT_INCHES: f32 = 1.0;
     pub const LABELS_PER_ROW: usize = 3;
-    #[allow(dead_code)]
     pub const LABELS_PER_COLUMN: usize = 10;
+    #[allow(dead_code)]
     pub const LABELS_PER_SHEET: usize = 30;
     pub const HORIZONTAL_SPACING_INCHES: f32 = 0.125;
     pub const VERTICAL_SPACING_INCHES: f32 = 0.0; // Labels touch vertically
@@ -37,9 +43,306 @@ impl Avery18660 {
     pub const SHEET_HEIGHT_INCHES: f32 = 11.0;
 }
 
+/// Avery 5160 template specifications
+/// 1" x 2-5/8" labels, 30 labels per sheet (3 columns x 10 rows)
+/// Sheet size: 8.5" x 11" (US Letter)
+/// Label size: 2.625" x 1" (width x height)
+/// Horizontal spacing: 0.125" between columns
+/// Vertical spacing: 0" between rows
+/// Top margin: 0.5"
+/// Left margin: 0.1875"
+pub struct Avery5160;
+
+impl Avery5160 {
+    pub const LABEL_WIDTH_INCHES: f32 = 2.625;
+    pub const LABEL_HEIGHT_INCHES: f32 = 1.0;
+    pub const LABELS_PER_ROW: usize = 3;
+    pub const LABELS_PER_COLUMN: usize = 10;
+    #[allow(dead_code)]
+    pub const LABELS_PER_SHEET: usize = 30;
+    pub const HORIZONTAL_SPACING_INCHES: f32 = 0.125;
+    pub const VERTICAL_SPACING_INCHES: f32 = 0.0;
+    pub const TOP_MARGIN_INCHES: f32 = 0.5;
+    pub const LEFT_MARGIN_INCHES: f32 = 0.1875; // 3/16"
+    pub const SHEET_WIDTH_INCHES: f32 = 8.5;
+    pub const SHEET_HEIGHT_INCHES: f32 = 11.0;
+}
+
+/// Sheet layout in inches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LabelDimensions {
+    pub label_width: f32,
+    pub label_height: f32,
+    pub labels_per_row: usize,
+    pub labels_per_column: usize,
+    pub horizontal_spacing: f32,
+    pub vertical_spacing: f32,
+    pub top_margin: f32,
+    pub left_margin: f32,
+    pub sheet_width: f32,
+    pub sheet_height: f32,
+}
+
+impl LabelDimensions {
+    pub fn labels_per_sheet(&self) -> usize {
+        self.labels_per_row * self.labels_per_column
+    }
+
+    /// Bottom-left corner of the label at `index` on its sheet, in points
+    /// (PDF coordinates: bottom-left of the sheet is the origin)
+    pub fn label_origin_pt(&self, index: usize) -> (f32, f32) {
+        let row = index / self.labels_per_row;
+        let col = index % self.labels_per_row;
+
+        let x = self.left_margin + (col as f32) * (self.label_width + self.horizontal_spacing);
+        let y = self.sheet_height
+            - self.top_margin
+            - (row as f32) * (self.label_height + self.vertical_spacing)
+            - self.label_height;
+        (x * 72.0, y * 72.0)
+    }
+}
+
+/// A printable label sheet layout
+pub trait LabelSheet {
+    fn label_dimensions(&self) -> LabelDimensions;
+}
+
+impl LabelSheet for Avery18660 {
+    fn label_dimensions(&self) -> LabelDimensions {
+        LabelDimensions {
+            label_width: Self::LABEL_WIDTH_INCHES,
+            label_height: Self::LABEL_HEIGHT_INCHES,
+            labels_per_row: Self::LABELS_PER_ROW,
+            labels_per_column: Self::LABELS_PER_COLUMN,
+            horizontal_spacing: Self::HORIZONTAL_SPACING_INCHES,
+            vertical_spacing: Self::VERTICAL_SPACING_INCHES,
+            top_margin: Self::TOP_MARGIN_INCHES,
+            left_margin: Self::LEFT_MARGIN_INCHES,
+            sheet_width: Self::SHEET_WIDTH_INCHES,
+            sheet_height: Self::SHEET_HEIGHT_INCHES,
+        }
+    }
+}
+
+impl LabelSheet for Avery5160 {
+    fn label_dimensions(&self) -> LabelDimensions {
+        LabelDimensions {
+            label_width: Self::LABEL_WIDTH_INCHES,
+            label_height: Self::LABEL_HEIGHT_INCHES,
+            labels_per_row: Self::LABELS_PER_ROW,
+            labels_per_column: Self::LABELS_PER_COLUMN,
+            horizontal_spacing: Self::HORIZONTAL_SPACING_INCHES,
+            vertical_spacing: Self::VERTICAL_SPACING_INCHES,
+            top_margin: Self::TOP_MARGIN_INCHES,
+            left_margin: Self::LEFT_MARGIN_INCHES,
+            sheet_width: Self::SHEET_WIDTH_INCHES,
+            sheet_height: Self::SHEET_HEIGHT_INCHES,
+        }
+    }
+}
+
+/// Supported label templates, named as stored on label batches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LabelTemplate {
+    #[default]
+    Avery18660,
+    Avery5160,
+}
+
+impl LabelTemplate {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LabelTemplate::Avery18660 => "avery_18660",
+            LabelTemplate::Avery5160 => "avery_5160",
+        }
+    }
+
+    /// Human-readable name used as the PDF title
+    fn title(&self) -> &'static str {
+        match self {
+            LabelTemplate::Avery18660 => "Avery 18660 Labels",
+            LabelTemplate::Avery5160 => "Avery 5160 Labels",
+        }
+    }
+
+    /// The built-in layout as a [`LabelTemplateConfig`]
+    pub fn config(&self) -> LabelTemplateConfig {
+        let dimensions = self.label_dimensions();
+        LabelTemplateConfig {
+            name: self.title().to_string(),
+            sheet_width_mm: inches_to_mm(dimensions.sheet_width),
+            sheet_height_mm: inches_to_mm(dimensions.sheet_height),
+            label_width_mm: inches_to_mm(dimensions.label_width),
+            label_height_mm: inches_to_mm(dimensions.label_height),
+            cols: dimensions.labels_per_row as i32,
+            rows: dimensions.labels_per_column as i32,
+            top_margin_mm: inches_to_mm(dimensions.top_margin),
+            left_margin_mm: inches_to_mm(dimensions.left_margin),
+            col_spacing_mm: inches_to_mm(dimensions.horizontal_spacing),
+            row_spacing_mm: inches_to_mm(dimensions.vertical_spacing),
+        }
+    }
+}
+
+/// Rounded to the micrometre so the Avery layouts serialize as tidy numbers
+fn inches_to_mm(inches: f32) -> f64 {
+    (inches as f64 * 25.4 * 1000.0).round() / 1000.0
+}
+
+/// How far the last label may overhang the sheet, to absorb float rounding
+const FIT_TOLERANCE_MM: f64 = 0.01;
+
+/// The sheet layout a PDF is generated with, in millimetres. Built from a
+/// [`LabelTemplate`] or a custom template, and stored with each label batch as JSON
+/// so reprints use the same layout.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelTemplateConfig {
+    /// Used as the PDF title
+    pub name: String,
+    pub sheet_width_mm: f64,
+    pub sheet_height_mm: f64,
+    pub label_width_mm: f64,
+    pub label_height_mm: f64,
+    pub cols: i32,
+    pub rows: i32,
+    pub top_margin_mm: f64,
+    pub left_margin_mm: f64,
+    pub col_spacing_mm: f64,
+    pub row_spacing_mm: f64,
+}
+
+impl LabelTemplateConfig {
+    /// Check the lengths are sensible and every label cell lies on the sheet
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        let lengths = [
+            ("sheet_width_mm", self.sheet_width_mm),
+            ("sheet_height_mm", self.sheet_height_mm),
+            ("label_width_mm", self.label_width_mm),
+            ("label_height_mm", self.label_height_mm),
+            ("top_margin_mm", self.top_margin_mm),
+            ("left_margin_mm", self.left_margin_mm),
+            ("col_spacing_mm", self.col_spacing_mm),
+            ("row_spacing_mm", self.row_spacing_mm),
+        ];
+        for (field, value) in lengths {
+            if !value.is_finite() || value < 0.0 {
+                return Err(format!("{} must be a non-negative number", field));
+            }
+        }
+        for (field, value) in [
+            ("sheet_width_mm", self.sheet_width_mm),
+            ("sheet_height_mm", self.sheet_height_mm),
+            ("label_width_mm", self.label_width_mm),
+            ("label_height_mm", self.label_height_mm),
+        ] {
+            if value == 0.0 {
+                return Err(format!("{} must be greater than 0", field));
+            }
+        }
+        for (field, value) in [("cols", self.cols), ("rows", self.rows)] {
+            if !(1..=MAX_LABEL_TEMPLATE_CELLS_PER_SIDE).contains(&value) {
+                return Err(format!(
+                    "{} must be between 1 and {}",
+                    field, MAX_LABEL_TEMPLATE_CELLS_PER_SIDE
+                ));
+            }
+        }
+
+        let right_edge = self.left_margin_mm
+            + self.cols as f64 * self.label_width_mm
+            + (self.cols - 1) as f64 * self.col_spacing_mm;
+        if right_edge > self.sheet_width_mm + FIT_TOLERANCE_MM {
+            return Err(format!(
+                "Labels end {:.2}mm from the left edge, past the {:.2}mm wide sheet",
+                right_edge, self.sheet_width_mm
+            ));
+        }
+        let bottom_edge = self.top_margin_mm
+            + self.rows as f64 * self.label_height_mm
+            + (self.rows - 1) as f64 * self.row_spacing_mm;
+        if bottom_edge > self.sheet_height_mm + FIT_TOLERANCE_MM {
+            return Err(format!(
+                "Labels end {:.2}mm from the top edge, past the {:.2}mm tall sheet",
+                bottom_edge, self.sheet_height_mm
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl LabelSheet for LabelTemplateConfig {
+    fn label_dimensions(&self) -> LabelDimensions {
+        let inches = |mm: f64| (mm / 25.4) as f32;
+        LabelDimensions {
+            label_width: inches(self.label_width_mm),
+            label_height: inches(self.label_height_mm),
+            labels_per_row: self.cols.max(1) as usize,
+            labels_per_column: self.rows.max(1) as usize,
+            horizontal_spacing: inches(self.col_spacing_mm),
+            vertical_spacing: inches(self.row_spacing_mm),
+            top_margin: inches(self.top_margin_mm),
+            left_margin: inches(self.left_margin_mm),
+            sheet_width: inches(self.sheet_width_mm),
+            sheet_height: inches(self.sheet_height_mm),
+        }
+    }
+}
+
+impl From<&CustomLabelTemplate> for LabelTemplateConfig {
+    fn from(template: &CustomLabelTemplate) -> Self {
+        Self {
+            name: template.name.clone(),
+            sheet_width_mm: template.sheet_width_mm,
+            sheet_height_mm: template.sheet_height_mm,
+            label_width_mm: template.label_width_mm,
+            label_height_mm: template.label_height_mm,
+            cols: template.cols,
+            rows: template.rows,
+            top_margin_mm: template.top_margin_mm,
+            left_margin_mm: template.left_margin_mm,
+            col_spacing_mm: template.col_spacing_mm,
+            row_spacing_mm: template.row_spacing_mm,
+        }
+    }
+}
+
+impl LabelSheet for LabelTemplate {
+    fn label_dimensions(&self) -> LabelDimensions {
+        match self {
+            LabelTemplate::Avery18660 => Avery18660.label_dimensions(),
+            LabelTemplate::Avery5160 => Avery5160.label_dimensions(),
+        }
+    }
+}
+
+impl fmt::Display for LabelTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LabelTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "avery_18660" => Ok(LabelTemplate::Avery18660),
+            "avery_5160" => Ok(LabelTemplate::Avery5160),
+            other => Err(anyhow::anyhow!("Unknown label template: {}", other)),
+        }
+    }
+}
+
 /// Generate a QR code image from data
 pub fn generate_qr_code_image(data: &str, size_pixels: u32) -> Result<Vec<u8>> {
-    let qr = QrCode::new(data).context("Failed to generate QR code")?;
+    let qr = QrCode::new(data).map_err(|e| match e {
+        QrError::DataTooLong => anyhow::anyhow!(
+            "QR data too long: {} chars (max ~2953). Consider using a shorter URL.",
+            data.len()
+        ),
+        other => anyhow::Error::new(other).context("Failed to generate QR code"),
+    })?;
 
     let image = qr
         .render::<Rgb<u8>>()
@@ -60,18 +363,127 @@ pub fn generate_qr_code_image(data: &str, size_pixels: u32) -> Result<Vec<u8>> {
     Ok(buffer)
 }
 
-/// Generate a PDF with labels for Avery 18660 template
-pub fn generate_label_pdf(labels: &[(String, i32)], // (qr_data, number)
+/// QR code side: 0.85" leaves room for text on the right of a 1" tall label
+const QR_SIZE_PT: f32 = 0.85 * 72.0;
+/// Gap between the left edge of the label and the QR code
+const QR_LEFT_MARGIN_PT: f32 = 5.0;
+/// Gap between the QR code and the text
+const TEXT_SPACING_PT: f32 = 8.0;
+
+const NUMBER_FONT_SIZE: f32 = 12.0;
+const NAME_FONT_SIZE: f32 = 8.0;
+const LOCATION_FONT_SIZE: f32 = 7.0;
+/// Longest name and location printed, including the "..." of truncated text
+pub const MAX_LABEL_NAME_CHARS: usize = 20;
+pub const MAX_LABEL_LOCATION_CHARS: usize = 24;
+
+/// Shorten `text` to at most `max_chars` characters, ending in "..." if cut
+fn truncate_label_text(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    format!("{}...", kept.trim_end())
+}
+
+/// One line of text on a label
+#[derive(Debug, Clone, PartialEq)]
+struct LabelTextLine {
+    text: String,
+    font_size: f32,
+    italic: bool,
+    /// Baseline start, in points from the label's bottom-left corner
+    x_pt: f32,
+    y_pt: f32,
+}
+
+/// Lay out the number, and the name and location when given, to the right of the
+/// QR code. The lines are centered vertically as a block.
+fn label_text_lines(
+    number: i32,
+    name: Option<&str>,
+    location: Option<&str>,
+    label_height_pt: f32,
+) -> Vec<LabelTextLine> {
+    let x_pt = QR_LEFT_MARGIN_PT + QR_SIZE_PT + TEXT_SPACING_PT;
+    let number_text = format!("#{}", number);
+
+    let name = name
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| truncate_label_text(name, MAX_LABEL_NAME_CHARS));
+    let location = location
+        .filter(|location| !location.trim().is_empty())
+        .map(|location| truncate_label_text(location, MAX_LABEL_LOCATION_CHARS));
+
+    if name.is_none() && location.is_none() {
+        // Number only: baseline sits just below the middle so the text looks centered
+        return vec![LabelTextLine {
+            text: number_text,
+            font_size: NUMBER_FONT_SIZE,
+            italic: false,
+            x_pt,
+            y_pt: label_height_pt / 2.0 + NUMBER_FONT_SIZE * 0.35,
+        }];
+    }
+
+    let mut lines = vec![(number_text, NUMBER_FONT_SIZE, false)];
+    lines.extend(name.map(|name| (name, NAME_FONT_SIZE, false)));
+    lines.extend(location.map(|location| (location, LOCATION_FONT_SIZE, true)));
+
+    // Each line takes 1.2x its font size; glyphs sit in the top 0.8 of that
+    let block_height: f32 = lines.iter().map(|(_, size, _)| size * 1.2).sum();
+    let mut top = (label_height_pt + block_height) / 2.0;
+
+    lines
+        .into_iter()
+        .map(|(text, font_size, italic)| {
+            let y_pt = top - font_size;
+            top -= font_size * 1.2;
+            LabelTextLine {
+                text,
+                font_size,
+                italic,
+                x_pt,
+                y_pt,
+            }
+        })
+        .collect()
+}
+
+/// Generate a PDF with labels laid out for the given template
+pub fn generate_label_pdf(
+    labels: &[(String, i32)], // (qr_data, number)
+    template: &LabelTemplateConfig,
+) -> Result<Vec<u8>> {
+    let labels: Vec<(String, i32, Option<String>, Option<String>)> = labels
+        .iter()
+        .map(|(qr_data, number)| (qr_data.clone(), *number, None, None))
+        .collect();
+    generate_label_pdf_with_names(&labels, template)
+}
+
+/// Generate a PDF like [`generate_label_pdf`], printing each label's entity name
+/// (8pt, truncated to 20 characters) and location (7pt italic, e.g. "Shelf 2") under
+/// its number when present
+pub fn generate_label_pdf_with_names(
+    labels: &[(String, i32, Option<String>, Option<String>)], // (qr_data, number, name, location)
+    template: &LabelTemplateConfig,
 ) -> Result<Vec<u8>> {
     if labels.is_empty() {
         return Err(anyhow::anyhow!("No labels provided"));
     }
+    template
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid label template: {}", e))?;
+
+    let dimensions = template.label_dimensions();
 
     // Create PDF document
     let (doc, page1, layer1) = PdfDocument::new(
-        "Avery 18660 Labels",
-        Mm(Avery18660::SHEET_WIDTH_INCHES * 25.4),
-        Mm(Avery18660::SHEET_HEIGHT_INCHES * 25.4),
+        &template.name,
+        Mm(dimensions.sheet_width * 25.4),
+        Mm(dimensions.sheet_height * 25.4),
         "Layer 1",
     );
 
@@ -79,30 +491,26 @@ pub fn generate_label_pdf(labels: &[(String, i32)], // (qr_data, number)
     let mut current_layer = layer1;
 
     // Convert inches to points (1 inch = 72 points)
-    let label_width_pt = Avery18660::LABEL_WIDTH_INCHES * 72.0;
-    let label_height_pt = Avery18660::LABEL_HEIGHT_INCHES * 72.0;
-    let horizontal_spacing = Avery18660::HORIZONTAL_SPACING_INCHES * 72.0;
-    let _vertical_spacing = Avery18660::VERTICAL_SPACING_INCHES * 72.0;
-    let top_margin_pt = Avery18660::TOP_MARGIN_INCHES * 72.0;
-    let left_margin_pt = Avery18660::LEFT_MARGIN_INCHES * 72.0;
-    let sheet_height_pt = Avery18660::SHEET_HEIGHT_INCHES * 72.0;
+    let label_height_pt = dimensions.label_height * 72.0;
 
     // QR code size within label - make it larger since label is 2.625" wide
-    // Use 0.85" x 0.85" QR code to leave room for number text below (0.15" for text)
     let qr_size_pixels = 300; // Higher resolution for larger QR code at 300 DPI
-    let qr_size_pt = 0.85 * 72.0; // 0.85" square QR code
+    let qr_size_pt = QR_SIZE_PT;
 
     // Add Helvetica font for text (using regular Helvetica for closest match)
     let font = doc
         .add_builtin_font(BuiltinFont::Helvetica)
         .context("Failed to add font")?;
+    let italic_font = doc
+        .add_builtin_font(BuiltinFont::HelveticaOblique)
+        .context("Failed to add italic font")?;
 
-    for (sheet_idx, label_chunk) in labels.chunks(Avery18660::LABELS_PER_SHEET).enumerate() {
+    for (sheet_idx, label_chunk) in labels.chunks(dimensions.labels_per_sheet()).enumerate() {
         if sheet_idx > 0 {
             // Create new page for additional sheets
             let (page, layer) = doc.add_page(
-                Mm(Avery18660::SHEET_WIDTH_INCHES * 25.4),
-                Mm(Avery18660::SHEET_HEIGHT_INCHES * 25.4),
+                Mm(dimensions.sheet_width * 25.4),
+                Mm(dimensions.sheet_height * 25.4),
                 "Layer 1",
             );
             current_page = page;
@@ -111,20 +519,10 @@ pub fn generate_label_pdf(labels: &[(String, i32)], // (qr_data, number)
 
         let layer = doc.get_page(current_page).get_layer(current_layer);
 
-        for (label_idx, (qr_data, number)) in label_chunk.iter().enumerate() {
-            let row = label_idx / Avery18660::LABELS_PER_ROW;
-            let col = label_idx % Avery18660::LABELS_PER_ROW;
-
-            // Calculate label position (PDF coordinates: bottom-left is origin)
-            // For Avery 18660: labels are arranged in 3 columns, 10 rows
-            // Each label is 2.625" wide x 1" tall
-            // X position: left margin + (column * (label width + spacing))
-            let x = left_margin_pt + (col as f32) * (label_width_pt + horizontal_spacing);
-            // Y position: start from top of sheet, work downward
-            // Row 0 (top): y = sheet_height - top_margin - label_height
-            // Row 1: y = sheet_height - top_margin - (2 * label_height)
-            // Row n: y = sheet_height - top_margin - ((n + 1) * label_height)
-            let y = sheet_height_pt - top_margin_pt - ((row as f32 + 1.0) * label_height_pt);
+        for (label_idx, (qr_data, number, name, location)) in label_chunk.iter().enumerate() {
+            // Label position (PDF coordinates: bottom-left is origin), filling
+            // rows left to right from the top of the sheet down
+            let (x, y) = dimensions.label_origin_pt(label_idx);
 
             // Generate QR code image
             let qr_image_data = generate_qr_code_image(qr_data, qr_size_pixels)
@@ -157,8 +555,7 @@ pub fn generate_label_pdf(labels: &[(String, i32)], // (qr_data, number)
             // Place QR code on the left side of the label
             // Label is 2.625" wide, QR is 0.85" square
             // Leave some margin on the left, then QR code, then space for text on right
-            let qr_left_margin = 5.0; // 5pt margin from left edge
-            let qr_x = x + qr_left_margin;
+            let qr_x = x + QR_LEFT_MARGIN_PT;
             // Center QR code vertically in the label (label is 1" = 72pt tall)
             let qr_y = y + (label_height_pt - qr_size_pt) / 2.0;
 
@@ -190,27 +587,25 @@ pub fn generate_label_pdf(labels: &[(String, i32)], // (qr_data, number)
             // Add image to layer
             image.add_to_layer(layer.clone(), transform);
 
-            // Add label number text to the right of QR code
-            let number_text = format!("#{}", number);
-            let font_size = 12.0; // Slightly larger font for better visibility
-
-            // Position text to the right of QR code
-            // QR code starts at qr_x and is qr_size_pt wide
-            // Add some spacing between QR code and text
-            let text_spacing = 8.0; // 8pt spacing between QR code and text
-            let text_x_pt = qr_x + qr_size_pt + text_spacing;
-
-            // Center text vertically with QR code
-            // QR code is centered at: y + (label_height_pt - qr_size_pt) / 2.0
-            // Text baseline should align with QR code center
-            // Approximate: font_size * 0.7 gives approximate center alignment
-            let text_y_pt = y + (label_height_pt / 2.0) + (font_size * 0.35); // Center vertically
-
-            // Convert points to millimeters for use_text
-            let text_x_mm = text_x_pt / 72.0 * 25.4;
-            let text_y_mm = text_y_pt / 72.0 * 25.4;
-
-            layer.use_text(number_text, font_size, Mm(text_x_mm), Mm(text_y_mm), &font);
+            // Add the number, name and location to the right of the QR code
+            for line in label_text_lines(
+                *number,
+                name.as_deref(),
+                location.as_deref(),
+                label_height_pt,
+            ) {
+                // Convert points to millimeters for use_text
+                let text_x_mm = (x + line.x_pt) / 72.0 * 25.4;
+                let text_y_mm = (y + line.y_pt) / 72.0 * 25.4;
+                let line_font = if line.italic { &italic_font } else { &font };
+                layer.use_text(
+                    line.text,
+                    line.font_size,
+                    Mm(text_x_mm),
+                    Mm(text_y_mm),
+                    line_font,
+                );
+            }
         }
     }
 
@@ -316,10 +711,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_generate_qr_code_image_data_too_long() {
+        let too_long = "a".repeat(4000);
+        let result = generate_qr_code_image(&too_long, 200);
+
+        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "QR data too long: 4000 chars (max ~2953). Consider using a shorter URL."
+        );
+    }
+
     #[test]
     fn test_generate_label_pdf_empty_labels() {
         let labels: Vec<(String, i32)> = vec![];
-        let result = generate_label_pdf(&labels);
+        let result = generate_label_pdf(&labels, &LabelTemplate::Avery18660.config());
 
         assert!(result.is_err());
         assert!(result
@@ -331,7 +738,7 @@ mod tests {
     #[test]
     fn test_generate_label_pdf_single_label() {
         let labels = vec![("https://example.com/item/1".to_string(), 1)];
-        let result = generate_label_pdf(&labels);
+        let result = generate_label_pdf(&labels, &LabelTemplate::Avery18660.config());
 
         assert!(result.is_ok());
         let pdf_data = result.unwrap();
@@ -348,7 +755,7 @@ mod tests {
             ("https://example.com/item/2".to_string(), 2),
             ("https://example.com/item/3".to_string(), 3),
         ];
-        let result = generate_label_pdf(&labels);
+        let result = generate_label_pdf(&labels, &LabelTemplate::Avery18660.config());
 
         assert!(result.is_ok());
         let pdf_data = result.unwrap();
@@ -363,7 +770,7 @@ mod tests {
             .map(|i| (format!("https://example.com/item/{}", i), i))
             .collect();
 
-        let result = generate_label_pdf(&labels);
+        let result = generate_label_pdf(&labels, &LabelTemplate::Avery18660.config());
         assert!(result.is_ok());
         let pdf_data = result.unwrap();
         assert!(!pdf_data.is_empty());
@@ -377,7 +784,7 @@ mod tests {
             .map(|i| (format!("https://example.com/item/{}", i), i))
             .collect();
 
-        let result = generate_label_pdf(&labels);
+        let result = generate_label_pdf(&labels, &LabelTemplate::Avery18660.config());
         assert!(result.is_ok());
         let pdf_data = result.unwrap();
         assert!(!pdf_data.is_empty());
@@ -391,7 +798,7 @@ mod tests {
             .map(|i| (format!("https://example.com/item/{}", i), i))
             .collect();
 
-        let result = generate_label_pdf(&labels);
+        let result = generate_label_pdf(&labels, &LabelTemplate::Avery18660.config());
         assert!(result.is_ok());
         let pdf_data = result.unwrap();
         assert!(!pdf_data.is_empty());
@@ -408,7 +815,7 @@ mod tests {
             ),
             ("item with spaces and symbols !@#$%".to_string(), 2),
         ];
-        let result = generate_label_pdf(&labels);
+        let result = generate_label_pdf(&labels, &LabelTemplate::Avery18660.config());
 
         assert!(result.is_ok());
         let pdf_data = result.unwrap();
@@ -425,18 +832,292 @@ mod tests {
         let left_margin_pt = Avery18660::LEFT_MARGIN_INCHES * 72.0;
 
         // First label (row 0, col 0)
-        let x0 = left_margin_pt + (0 as f32) * (label_width_pt + horizontal_spacing);
+        let x0 = left_margin_pt + 0.0 * (label_width_pt + horizontal_spacing);
         assert_eq!(x0, left_margin_pt);
 
         // Second label in row (row 0, col 1)
-        let x1 = left_margin_pt + (1 as f32) * (label_width_pt + horizontal_spacing);
+        let x1 = left_margin_pt + 1.0 * (label_width_pt + horizontal_spacing);
         assert_eq!(x1, left_margin_pt + label_width_pt + horizontal_spacing);
 
         // Third label in row (row 0, col 2)
-        let x2 = left_margin_pt + (2 as f32) * (label_width_pt + horizontal_spacing);
+        let x2 = left_margin_pt + 2.0 * (label_width_pt + horizontal_spacing);
         assert_eq!(
             x2,
             left_margin_pt + 2.0 * (label_width_pt + horizontal_spacing)
         );
     }
+
+    /// The last label on a sheet must end inside the sheet, or it would print off the page
+    fn assert_last_label_fits(template: LabelTemplate) {
+        let dimensions = template.label_dimensions();
+        let (x, y) = dimensions.label_origin_pt(dimensions.labels_per_sheet() - 1);
+
+        let right_edge = x + dimensions.label_width * 72.0;
+        assert!(
+            right_edge <= dimensions.sheet_width * 72.0,
+            "{} label ends at {}pt, past the sheet edge",
+            template,
+            right_edge
+        );
+        assert!(
+            y >= 0.0,
+            "{} label starts below the sheet at {}pt",
+            template,
+            y
+        );
+    }
+
+    #[test]
+    fn test_avery18660_last_label_fits_on_sheet() {
+        assert_last_label_fits(LabelTemplate::Avery18660);
+    }
+
+    #[test]
+    fn test_avery5160_last_label_fits_on_sheet() {
+        assert_last_label_fits(LabelTemplate::Avery5160);
+    }
+
+    #[test]
+    fn test_avery5160_dimensions() {
+        let dimensions = LabelTemplate::Avery5160.label_dimensions();
+        assert_eq!(dimensions.labels_per_sheet(), Avery5160::LABELS_PER_SHEET);
+        assert_eq!(dimensions.left_margin, 0.1875);
+
+        // First label sits at the top-left margin
+        let (x, y) = dimensions.label_origin_pt(0);
+        assert_eq!(x, 0.1875 * 72.0);
+        assert_eq!(y, (11.0 - 0.5 - 1.0) * 72.0);
+    }
+
+    #[test]
+    fn test_label_template_from_str() {
+        assert_eq!(
+            "avery_18660".parse::<LabelTemplate>().unwrap(),
+            LabelTemplate::Avery18660
+        );
+        assert_eq!(
+            "avery_5160".parse::<LabelTemplate>().unwrap(),
+            LabelTemplate::Avery5160
+        );
+        assert!("avery_9999".parse::<LabelTemplate>().is_err());
+        assert_eq!(LabelTemplate::default().to_string(), "avery_18660");
+    }
+
+    #[test]
+    fn test_generate_label_pdf_avery5160() {
+        let labels: Vec<(String, i32)> = (1..=31)
+            .map(|i| (format!("https://example.com/item/{}", i), i))
+            .collect();
+
+        let pdf_data = generate_label_pdf(&labels, &LabelTemplate::Avery5160.config()).unwrap();
+        assert_eq!(&pdf_data[0..4], b"%PDF");
+    }
+
+    #[test]
+    fn test_builtin_template_configs_are_valid() {
+        for template in [LabelTemplate::Avery18660, LabelTemplate::Avery5160] {
+            let config = template.config();
+            assert_eq!(config.validate(), Ok(()), "{}", template);
+            assert_eq!(config.name, template.title());
+        }
+
+        let config = LabelTemplate::Avery18660.config();
+        assert_eq!(config.label_width_mm, 66.675);
+        assert_eq!(config.left_margin_mm, 4.826);
+        assert_eq!((config.cols, config.rows), (3, 10));
+    }
+
+    #[test]
+    fn test_template_config_dimensions_match_builtin() {
+        let builtin = LabelTemplate::Avery5160.label_dimensions();
+        let config = LabelTemplate::Avery5160.config().label_dimensions();
+
+        assert_eq!(config.labels_per_sheet(), builtin.labels_per_sheet());
+        for index in [0, 1, 29] {
+            let (bx, by) = builtin.label_origin_pt(index);
+            let (cx, cy) = config.label_origin_pt(index);
+            assert!((bx - cx).abs() < 0.01 && (by - cy).abs() < 0.01);
+        }
+    }
+
+    fn custom_config() -> LabelTemplateConfig {
+        LabelTemplateConfig {
+            name: "Custom 2x4".to_string(),
+            sheet_width_mm: 210.0,
+            sheet_height_mm: 297.0,
+            label_width_mm: 100.0,
+            label_height_mm: 70.0,
+            cols: 2,
+            rows: 4,
+            top_margin_mm: 8.0,
+            left_margin_mm: 4.0,
+            col_spacing_mm: 2.0,
+            row_spacing_mm: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_template_config_validate_fit() {
+        // 4 + 2 * 100 + 2 = 206mm wide, 8 + 4 * 70 = 288mm tall
+        assert_eq!(custom_config().validate(), Ok(()));
+
+        let too_wide = LabelTemplateConfig {
+            col_spacing_mm: 10.0,
+            ..custom_config()
+        };
+        assert!(too_wide.validate().unwrap_err().contains("210.00mm wide"));
+
+        let too_tall = LabelTemplateConfig {
+            rows: 5,
+            ..custom_config()
+        };
+        assert!(too_tall.validate().unwrap_err().contains("297.00mm tall"));
+    }
+
+    #[test]
+    fn test_template_config_validate_values() {
+        let no_columns = LabelTemplateConfig {
+            cols: 0,
+            ..custom_config()
+        };
+        assert!(no_columns.validate().is_err());
+
+        let negative_margin = LabelTemplateConfig {
+            top_margin_mm: -1.0,
+            ..custom_config()
+        };
+        assert!(negative_margin.validate().is_err());
+
+        let zero_height = LabelTemplateConfig {
+            label_height_mm: 0.0,
+            ..custom_config()
+        };
+        assert!(zero_height.validate().is_err());
+
+        let nan_width = LabelTemplateConfig {
+            sheet_width_mm: f64::NAN,
+            ..custom_config()
+        };
+        assert!(nan_width.validate().is_err());
+    }
+
+    #[test]
+    fn test_generate_label_pdf_custom_template() {
+        let labels: Vec<(String, i32)> = (1..=9)
+            .map(|i| (format!("https://example.com/item/{}", i), i))
+            .collect();
+
+        let pdf_data = generate_label_pdf(&labels, &custom_config()).unwrap();
+        assert_eq!(&pdf_data[0..4], b"%PDF");
+
+        let overflowing = LabelTemplateConfig {
+            rows: 5,
+            ..custom_config()
+        };
+        assert!(generate_label_pdf(&labels, &overflowing).is_err());
+    }
+
+    /// Text keeps this far from the right edge of the label
+    const TEXT_RIGHT_MARGIN_PT: f32 = 5.0;
+    /// Generous average Helvetica glyph width in ems
+    const APPROX_GLYPH_WIDTH_EM: f32 = 0.6;
+
+    #[test]
+    fn test_truncate_label_text() {
+        assert_eq!(truncate_label_text("  Drill  ", 20), "Drill");
+        assert_eq!(
+            truncate_label_text("Cordless Drill Driver Kit", 20),
+            "Cordless Drill Dr..."
+        );
+        assert_eq!(
+            truncate_label_text("Cordless Drill Driver Kit", 20)
+                .chars()
+                .count(),
+            20
+        );
+    }
+
+    #[test]
+    fn test_label_text_stays_within_avery18660_label() {
+        let dimensions = LabelTemplate::Avery18660.label_dimensions();
+        let label_width_pt = dimensions.label_width * 72.0;
+        let label_height_pt = dimensions.label_height * 72.0;
+        let long_name = "W".repeat(60);
+        let long_location = "M".repeat(60);
+
+        for (name, location) in [
+            (None, None),
+            (Some(long_name.as_str()), None),
+            (None, Some(long_location.as_str())),
+            (Some(long_name.as_str()), Some(long_location.as_str())),
+        ] {
+            let lines = label_text_lines(99999, name, location, label_height_pt);
+            assert_eq!(
+                lines.len(),
+                1 + name.is_some() as usize + location.is_some() as usize
+            );
+
+            for line in &lines {
+                let width =
+                    line.text.chars().count() as f32 * line.font_size * APPROX_GLYPH_WIDTH_EM;
+                assert!(
+                    line.x_pt >= QR_LEFT_MARGIN_PT + QR_SIZE_PT,
+                    "{:?} overlaps the QR code",
+                    line
+                );
+                assert!(
+                    line.x_pt + width <= label_width_pt - TEXT_RIGHT_MARGIN_PT,
+                    "{:?} runs past the right edge",
+                    line
+                );
+                // Descenders reach ~0.2em below the baseline, caps ~0.8em above
+                assert!(
+                    line.y_pt - line.font_size * 0.2 >= 0.0,
+                    "{:?} runs off the bottom",
+                    line
+                );
+                assert!(
+                    line.y_pt + line.font_size * 0.8 <= label_height_pt,
+                    "{:?} runs off the top",
+                    line
+                );
+            }
+
+            // Lines stack top to bottom without overlapping
+            for pair in lines.windows(2) {
+                assert!(pair[1].y_pt + pair[1].font_size * 0.8 <= pair[0].y_pt);
+            }
+        }
+    }
+
+    #[test]
+    fn test_label_text_lines_styles() {
+        let lines = label_text_lines(7, Some("Tool Box"), Some("Shelf 2"), 72.0);
+        assert_eq!(lines[0].text, "#7");
+        assert_eq!(lines[1].font_size, NAME_FONT_SIZE);
+        assert!(!lines[1].italic);
+        assert_eq!(lines[2].text, "Shelf 2");
+        assert_eq!(lines[2].font_size, LOCATION_FONT_SIZE);
+        assert!(lines[2].italic);
+
+        // Blank names are skipped rather than printed as an empty line
+        assert_eq!(label_text_lines(7, Some("  "), None, 72.0).len(), 1);
+    }
+
+    #[test]
+    fn test_generate_label_pdf_with_names() {
+        let labels = vec![
+            (
+                "https://example.com/item/1".to_string(),
+                1,
+                Some("Cordless Drill Driver Kit".to_string()),
+                Some("Shelf 2".to_string()),
+            ),
+            ("https://example.com/item/2".to_string(), 2, None, None),
+        ];
+
+        let pdf_data =
+            generate_label_pdf_with_names(&labels, &LabelTemplate::Avery18660.config()).unwrap();
+        assert_eq!(&pdf_data[0..4], b"%PDF");
+    }
 }
@@ -1,13 +1,131 @@
-use aws_sdk_s3::{presigning::PresigningConfig, Client as S3Client};
+use anyhow::Context;
+use aws_sdk_s3::{
+    config::{ProvideCredentials, SharedCredentialsProvider},
+    presigning::PresigningConfig,
+    primitives::ByteStream,
+    types::{CompletedMultipartUpload, CompletedPart},
+    Client as S3Client,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use moka::sync::Cache;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
 use std::env;
-use std::time::Duration;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// How long presigned upload URLs stay valid, in seconds
+pub const UPLOAD_URL_EXPIRES_IN_SECS: u64 = 3600;
+/// How long presigned download URLs stay valid, in seconds
+pub const DOWNLOAD_URL_EXPIRES_IN_SECS: u64 = 86400;
+/// A cached download URL is replaced this long before it expires, so whoever gets it
+/// always has at least an hour to use it
+const DOWNLOAD_URL_CACHE_MARGIN: Duration = Duration::from_secs(3600);
+/// Most presigned download URLs kept in memory
+const DOWNLOAD_URL_CACHE_CAPACITY: u64 = 10_000;
+/// Default for `MAX_UPLOAD_BYTES`: 50 MB
+const DEFAULT_MAX_UPLOAD_BYTES: u64 = 50 * 1024 * 1024;
+/// Default for `MULTIPART_THRESHOLD_BYTES`: 100 MB
+const DEFAULT_MULTIPART_THRESHOLD_BYTES: u64 = 100 * 1024 * 1024;
+/// Preferred size of each part of a multipart upload: 64 MiB
+const MULTIPART_PART_BYTES: u64 = 64 * 1024 * 1024;
+/// Most parts S3 accepts in one multipart upload
+pub const MAX_MULTIPART_PARTS: u64 = 10_000;
+/// Default for `MAX_MULTIPART_UPLOAD_BYTES`: 5 GiB
+const DEFAULT_MAX_MULTIPART_UPLOAD_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+/// Largest object S3 stores: 5 TiB
+const MAX_S3_OBJECT_BYTES: u64 = 5 * 1024 * 1024 * 1024 * 1024;
+
+/// Part size and number of parts for a multipart upload of `file_size` bytes: 64 MiB
+/// parts, made larger when that would need more than [`MAX_MULTIPART_PARTS`]
+pub fn multipart_plan(file_size: u64) -> (u64, u64) {
+    let part_size = MULTIPART_PART_BYTES.max(file_size.div_ceil(MAX_MULTIPART_PARTS));
+    (part_size, file_size.div_ceil(part_size).max(1))
+}
+
+/// A presigned POST upload: the file is sent as the last part of a
+/// `multipart/form-data` POST to `url`, after every entry in `fields`
+#[derive(Debug, Clone)]
+pub struct PresignedPost {
+    pub url: String,
+    pub fields: HashMap<String, String>,
+}
+
+/// Unique key for a new photo of an entity: `{entity_type}/{entity_id}/{uuid}.{ext}`
+pub fn photo_s3_key(entity_type: &str, entity_id: Uuid, content_type: &str) -> String {
+    let extension = match content_type {
+        ct if ct.starts_with("image/jpeg") => "jpg",
+        ct if ct.starts_with("image/png") => "png",
+        ct if ct.starts_with("image/webp") => "webp",
+        ct if ct.starts_with("image/gif") => "gif",
+        _ => "bin",
+    };
+    format!(
+        "{}/{}/{}.{}",
+        entity_type,
+        entity_id,
+        Uuid::new_v4(),
+        extension
+    )
+}
+
+/// A started multipart upload: PUT part `n` to `part_urls[n - 1]`, keeping each
+/// response's `ETag` for [`S3Service::complete_multipart_upload`]
+#[derive(Debug, Clone)]
+pub struct MultipartUpload {
+    pub upload_id: String,
+    pub part_urls: Vec<String>,
+}
+
+/// Lookups in the presigned download URL cache since startup, for `/health`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct UrlCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// Presigned download URLs by S3 key, each with when it stops being handed out (see
+/// [`download_url_freshness`]). Stale entries are replaced on their next lookup.
+fn download_url_cache() -> Cache<String, (String, Instant)> {
+    Cache::builder()
+        .max_capacity(DOWNLOAD_URL_CACHE_CAPACITY)
+        .build()
+}
+
+/// How long a download URL presigned now may be handed out: until
+/// [`DOWNLOAD_URL_CACHE_MARGIN`] before it stops working. A presigned URL dies with the
+/// credentials that signed it, so temporary credentials (e.g. a Lambda role's) that
+/// expire first cut it short. `None` when that leaves no time to cache it.
+fn download_url_freshness(credentials_expire_in: Option<Duration>) -> Option<Duration> {
+    let expires_in = Duration::from_secs(DOWNLOAD_URL_EXPIRES_IN_SECS);
+    let works_for = credentials_expire_in.map_or(expires_in, |left| left.min(expires_in));
+    works_for
+        .checked_sub(DOWNLOAD_URL_CACHE_MARGIN)
+        .filter(|fresh_for| !fresh_for.is_zero())
+}
+
 pub struct S3Service {
     client: S3Client,
+    /// Kept from construction to sign presigned POST policies; the client's own
+    /// config doesn't expose its provider
+    credentials: SharedCredentialsProvider,
     bucket: String,
-    #[allow(dead_code)]
     endpoint_url: Option<String>,
+    /// `MAX_UPLOAD_BYTES`: largest file accepted by presigned POST uploads
+    max_upload_bytes: u64,
+    /// `MULTIPART_THRESHOLD_BYTES`: files larger than this are uploaded in parts
+    multipart_threshold_bytes: u64,
+    /// `MAX_MULTIPART_UPLOAD_BYTES`: largest file accepted by multipart uploads
+    max_multipart_upload_bytes: u64,
+    download_urls: Cache<String, (String, Instant)>,
+    url_cache_hits: AtomicU64,
+    url_cache_misses: AtomicU64,
 }
 
 impl S3Service {
@@ -15,6 +133,25 @@ impl S3Service {
         let bucket = env::var("S3_BUCKET").unwrap_or_else(|_| "home-inventory-photos".to_string());
         let endpoint_url = env::var("S3_ENDPOINT").ok();
         let region = env::var("S3_REGION").unwrap_or_else(|_| "us-east-1".to_string());
+        let max_upload_bytes = match env::var("MAX_UPLOAD_BYTES") {
+            Ok(value) => value
+                .parse::<u64>()
+                .context("MAX_UPLOAD_BYTES must be a valid u64")?,
+            Err(_) => DEFAULT_MAX_UPLOAD_BYTES,
+        };
+        let multipart_threshold_bytes = match env::var("MULTIPART_THRESHOLD_BYTES") {
+            Ok(value) => value
+                .parse::<u64>()
+                .context("MULTIPART_THRESHOLD_BYTES must be a valid u64")?,
+            Err(_) => DEFAULT_MULTIPART_THRESHOLD_BYTES,
+        };
+        let max_multipart_upload_bytes = match env::var("MAX_MULTIPART_UPLOAD_BYTES") {
+            Ok(value) => value
+                .parse::<u64>()
+                .context("MAX_MULTIPART_UPLOAD_BYTES must be a valid u64")?
+                .min(MAX_S3_OBJECT_BYTES),
+            Err(_) => DEFAULT_MAX_MULTIPART_UPLOAD_BYTES,
+        };
 
         // Get credentials - support both S3_* and AWS_* env vars
         let access_key = env::var("S3_ACCESS_KEY")
@@ -30,28 +167,36 @@ impl S3Service {
             endpoint_url
         );
 
-        let client = if let Some(endpoint) = &endpoint_url {
+        let (client, credentials) = if let Some(endpoint) = &endpoint_url {
             // For MinIO/local S3, configure with custom endpoint and credentials
             tracing::info!("Using MinIO endpoint: {}", endpoint);
-            let credentials =
-                aws_sdk_s3::config::Credentials::new(&access_key, &secret_key, None, None, "minio");
+            let credentials = SharedCredentialsProvider::new(aws_sdk_s3::config::Credentials::new(
+                &access_key,
+                &secret_key,
+                None,
+                None,
+                "minio",
+            ));
 
             // MinIO requires path-style URLs (not virtual-hosted-style)
             let s3_config = aws_sdk_s3::Config::builder()
                 .behavior_version(aws_sdk_s3::config::BehaviorVersion::latest())
                 .endpoint_url(endpoint)
                 .region(aws_config::Region::new(region))
-                .credentials_provider(credentials)
+                .credentials_provider(credentials.clone())
                 .force_path_style(true) // Critical for MinIO compatibility
                 .build();
 
-            S3Client::from_conf(s3_config)
+            (S3Client::from_conf(s3_config), credentials)
         } else {
             // For AWS S3, use standard config
             let config = aws_config::defaults(aws_config::BehaviorVersion::latest())
                 .load()
                 .await;
-            S3Client::new(&config)
+            let credentials = config
+                .credentials_provider()
+                .context("AWS config has no credentials provider")?;
+            (S3Client::new(&config), credentials)
         };
 
         // Verify bucket exists (should be created by minio-init container)
@@ -71,11 +216,44 @@ impl S3Service {
 
         Ok(Self {
             client,
+            credentials,
             bucket,
             endpoint_url,
+            max_upload_bytes,
+            multipart_threshold_bytes,
+            max_multipart_upload_bytes,
+            download_urls: download_url_cache(),
+            url_cache_hits: AtomicU64::new(0),
+            url_cache_misses: AtomicU64::new(0),
         })
     }
 
+    /// Largest upload accepted by [`Self::generate_presigned_upload_url_for_key`] by default
+    pub fn max_upload_bytes(&self) -> u64 {
+        self.max_upload_bytes
+    }
+
+    /// Largest file accepted by multipart uploads
+    pub fn max_multipart_upload_bytes(&self) -> u64 {
+        self.max_multipart_upload_bytes
+    }
+
+    /// Check the bucket is reachable, as `new` does at startup
+    pub async fn check_bucket(&self) -> anyhow::Result<()> {
+        self.client
+            .head_bucket()
+            .bucket(&self.bucket)
+            .send()
+            .await?;
+        Ok(())
+    }
+
+    /// Whether a file of `file_size` bytes should be uploaded in parts: it is over
+    /// `MULTIPART_THRESHOLD_BYTES`, or too large for a single presigned POST
+    pub fn needs_multipart(&self, file_size: u64) -> bool {
+        file_size > self.multipart_threshold_bytes || file_size > self.max_upload_bytes
+    }
+
     /// Generate a presigned URL for uploading a file
     pub async fn generate_presigned_upload_url(
         &self,
@@ -83,16 +261,7 @@ impl S3Service {
         entity_id: Uuid,
         content_type: &str,
     ) -> anyhow::Result<(String, String)> {
-        // Generate unique S3 key
-        let file_id = Uuid::new_v4();
-        let extension = match content_type {
-            ct if ct.starts_with("image/jpeg") => "jpg",
-            ct if ct.starts_with("image/png") => "png",
-            ct if ct.starts_with("image/webp") => "webp",
-            ct if ct.starts_with("image/gif") => "gif",
-            _ => "bin",
-        };
-        let s3_key = format!("{}/{}/{}.{}", entity_type, entity_id, file_id, extension);
+        let s3_key = photo_s3_key(entity_type, entity_id, content_type);
 
         tracing::debug!(
             "Generating presigned URL for bucket: {}, key: {}",
@@ -101,7 +270,8 @@ impl S3Service {
         );
 
         // Generate presigned URL (valid for 1 hour)
-        let presigning_config = PresigningConfig::expires_in(Duration::from_secs(3600))?;
+        let presigning_config =
+            PresigningConfig::expires_in(Duration::from_secs(UPLOAD_URL_EXPIRES_IN_SECS))?;
 
         let presigned_request = self
             .client
@@ -120,29 +290,204 @@ impl S3Service {
         Ok((upload_url, s3_key))
     }
 
-    /// Generate a presigned URL for uploading a file with a specific key
+    /// Presigned POST upload for a specific key. Unlike a presigned PUT URL, the
+    /// signed policy carries a `content-length-range` condition, so S3 rejects files
+    /// larger than `max_bytes`.
     pub async fn generate_presigned_upload_url_for_key(
         &self,
         s3_key: &str,
         content_type: &str,
-    ) -> anyhow::Result<String> {
-        let presigning_config = PresigningConfig::expires_in(Duration::from_secs(3600))?;
+        max_bytes: u64,
+    ) -> anyhow::Result<PresignedPost> {
+        let credentials = self.credentials.provide_credentials().await?;
+        let region = self
+            .client
+            .config()
+            .region()
+            .map(|region| region.to_string())
+            .unwrap_or_else(|| "us-east-1".to_string());
 
-        let presigned_request = self
+        let now = Utc::now();
+        let date = now.format("%Y%m%d").to_string();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let expiration = now + chrono::Duration::seconds(UPLOAD_URL_EXPIRES_IN_SECS as i64);
+        let credential = format!(
+            "{}/{}/{}/s3/aws4_request",
+            credentials.access_key_id(),
+            date,
+            region
+        );
+
+        let mut fields = HashMap::from([
+            ("key".to_string(), s3_key.to_string()),
+            ("Content-Type".to_string(), content_type.to_string()),
+            (
+                "x-amz-algorithm".to_string(),
+                "AWS4-HMAC-SHA256".to_string(),
+            ),
+            ("x-amz-credential".to_string(), credential),
+            ("x-amz-date".to_string(), amz_date),
+        ]);
+        if let Some(token) = credentials.session_token() {
+            fields.insert("x-amz-security-token".to_string(), token.to_string());
+        }
+
+        let mut conditions = vec![
+            serde_json::json!({ "bucket": &self.bucket }),
+            serde_json::json!(["content-length-range", 0, max_bytes]),
+        ];
+        conditions.extend(
+            fields
+                .iter()
+                .map(|(name, value)| serde_json::json!({ name: value })),
+        );
+        let policy = STANDARD.encode(serde_json::to_vec(&serde_json::json!({
+            "expiration": expiration.format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string(),
+            "conditions": conditions,
+        }))?);
+
+        let signing_key = signing_key(credentials.secret_access_key(), &date, &region, "s3");
+        fields.insert(
+            "x-amz-signature".to_string(),
+            hex::encode(hmac_sha256(&signing_key, policy.as_bytes())),
+        );
+        fields.insert("policy".to_string(), policy);
+
+        // Path-style for MinIO, like the client itself
+        let url = match &self.endpoint_url {
+            Some(endpoint) => format!("{}/{}", endpoint.trim_end_matches('/'), self.bucket),
+            None => format!("https://{}.s3.{}.amazonaws.com", self.bucket, region),
+        };
+
+        Ok(PresignedPost { url, fields })
+    }
+
+    /// Start a multipart upload to `s3_key` and presign a PUT URL for each of its
+    /// `part_count` parts. Unlike presigned POST, part URLs carry no size limit.
+    pub async fn generate_multipart_upload_urls(
+        &self,
+        s3_key: &str,
+        content_type: &str,
+        part_count: i32,
+    ) -> anyhow::Result<MultipartUpload> {
+        let created = self
             .client
-            .put_object()
+            .create_multipart_upload()
             .bucket(&self.bucket)
             .key(s3_key)
             .content_type(content_type)
-            .presigned(presigning_config)
+            .send()
+            .await?;
+        let upload_id = created
+            .upload_id()
+            .context("S3 returned no multipart upload id")?
+            .to_string();
+
+        let presigning_config =
+            PresigningConfig::expires_in(Duration::from_secs(UPLOAD_URL_EXPIRES_IN_SECS))?;
+        let mut part_urls = Vec::with_capacity(part_count.max(0) as usize);
+        for part_number in 1..=part_count {
+            let presigned_request = self
+                .client
+                .upload_part()
+                .bucket(&self.bucket)
+                .key(s3_key)
+                .upload_id(&upload_id)
+                .part_number(part_number)
+                .presigned(presigning_config.clone())
+                .await?;
+            part_urls.push(presigned_request.uri().to_string());
+        }
+
+        Ok(MultipartUpload {
+            upload_id,
+            part_urls,
+        })
+    }
+
+    /// Assemble the uploaded parts, given as `(part_number, etag)` in ascending
+    /// part order, into the object at `s3_key`
+    pub async fn complete_multipart_upload(
+        &self,
+        s3_key: &str,
+        upload_id: &str,
+        parts: &[(i32, String)],
+    ) -> anyhow::Result<()> {
+        let parts = parts
+            .iter()
+            .map(|(part_number, etag)| {
+                CompletedPart::builder()
+                    .part_number(*part_number)
+                    .e_tag(etag)
+                    .build()
+            })
+            .collect();
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(s3_key)
+            .upload_id(upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
             .await?;
 
-        Ok(presigned_request.uri().to_string())
+        Ok(())
     }
 
-    /// Generate a presigned URL for downloading/viewing a file
+    /// Discard a multipart upload and any parts already stored for it
+    pub async fn abort_multipart_upload(
+        &self,
+        s3_key: &str,
+        upload_id: &str,
+    ) -> anyhow::Result<()> {
+        self.client
+            .abort_multipart_upload()
+            .bucket(&self.bucket)
+            .key(s3_key)
+            .upload_id(upload_id)
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Size in bytes of the object at `s3_key`
+    pub async fn object_size(&self, s3_key: &str) -> anyhow::Result<u64> {
+        let head = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(s3_key)
+            .send()
+            .await?;
+
+        Ok(head.content_length().unwrap_or(0).max(0) as u64)
+    }
+
+    /// Generate a presigned URL for downloading/viewing a file. URLs are reused from
+    /// the cache until an hour before they, or the credentials that signed them, expire.
     pub async fn generate_presigned_download_url(&self, s3_key: &str) -> anyhow::Result<String> {
-        let presigning_config = PresigningConfig::expires_in(Duration::from_secs(86400))?;
+        if let Some((url, fresh_until)) = self.download_urls.get(s3_key) {
+            if Instant::now() < fresh_until {
+                self.url_cache_hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(url);
+            }
+        }
+        self.url_cache_misses.fetch_add(1, Ordering::Relaxed);
+
+        let credentials = self.credentials.provide_credentials().await?;
+        let credentials_expire_in = credentials
+            .expiry()
+            .map(|expiry| expiry.duration_since(SystemTime::now()).unwrap_or_default());
+        let fresh_until = download_url_freshness(credentials_expire_in)
+            .map(|fresh_for| Instant::now() + fresh_for);
+        let presigning_config =
+            PresigningConfig::expires_in(Duration::from_secs(DOWNLOAD_URL_EXPIRES_IN_SECS))?;
 
         let presigned_request = self
             .client
@@ -152,7 +497,20 @@ impl S3Service {
             .presigned(presigning_config)
             .await?;
 
-        Ok(presigned_request.uri().to_string())
+        let url = presigned_request.uri().to_string();
+        if let Some(fresh_until) = fresh_until {
+            self.download_urls
+                .insert(s3_key.to_string(), (url.clone(), fresh_until));
+        }
+        Ok(url)
+    }
+
+    /// Hits and misses of the download URL cache so far
+    pub fn cache_stats(&self) -> UrlCacheStats {
+        UrlCacheStats {
+            hits: self.url_cache_hits.load(Ordering::Relaxed),
+            misses: self.url_cache_misses.load(Ordering::Relaxed),
+        }
     }
 
     /// Download file bytes from S3
@@ -169,6 +527,57 @@ impl S3Service {
         Ok(bytes)
     }
 
+    /// Stream an object's body without buffering it in memory
+    pub async fn get_object_stream(&self, s3_key: &str) -> anyhow::Result<ByteStream> {
+        let response = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(s3_key)
+            .send()
+            .await?;
+
+        Ok(response.body)
+    }
+
+    /// Upload a local file, streaming it from disk
+    pub async fn put_object_from_path(
+        &self,
+        s3_key: &str,
+        path: &Path,
+        content_type: &str,
+    ) -> anyhow::Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(s3_key)
+            .content_type(content_type)
+            .body(ByteStream::from_path(path).await?)
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Upload an in-memory object
+    pub async fn put_object_bytes(
+        &self,
+        s3_key: &str,
+        bytes: Vec<u8>,
+        content_type: &str,
+    ) -> anyhow::Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(s3_key)
+            .content_type(content_type)
+            .body(ByteStream::from(bytes))
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
     /// Delete a file from S3
     pub async fn delete_file(&self, s3_key: &str) -> anyhow::Result<()> {
         self.client
@@ -177,6 +586,7 @@ impl S3Service {
             .key(s3_key)
             .send()
             .await?;
+        self.download_urls.invalidate(s3_key);
 
         Ok(())
     }
@@ -191,3 +601,171 @@ impl S3Service {
         self.endpoint_url.as_deref()
     }
 }
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// SigV4 signing key for a day, region and service
+fn signing_key(secret_access_key: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let key = hmac_sha256(
+        format!("AWS4{}", secret_access_key).as_bytes(),
+        date.as_bytes(),
+    );
+    let key = hmac_sha256(&key, region.as_bytes());
+    let key = hmac_sha256(&key, service.as_bytes());
+    hmac_sha256(&key, b"aws4_request")
+}
+
+#[cfg(test)]
+impl S3Service {
+    /// Service pointed at a local MinIO-style endpoint with dummy credentials.
+    /// Presigning needs no network access, so this works without a server.
+    pub fn for_tests() -> Self {
+        let endpoint = "http://localhost:9000".to_string();
+        let credentials = SharedCredentialsProvider::new(aws_sdk_s3::config::Credentials::new(
+            "test", "test", None, None, "test",
+        ));
+        let s3_config = aws_sdk_s3::Config::builder()
+            .behavior_version(aws_sdk_s3::config::BehaviorVersion::latest())
+            .endpoint_url(&endpoint)
+            .region(aws_config::Region::new("us-east-1"))
+            .credentials_provider(credentials.clone())
+            .force_path_style(true)
+            .build();
+
+        Self {
+            client: S3Client::from_conf(s3_config),
+            credentials,
+            bucket: "test-bucket".to_string(),
+            endpoint_url: Some(endpoint),
+            max_upload_bytes: DEFAULT_MAX_UPLOAD_BYTES,
+            multipart_threshold_bytes: DEFAULT_MULTIPART_THRESHOLD_BYTES,
+            max_multipart_upload_bytes: DEFAULT_MAX_MULTIPART_UPLOAD_BYTES,
+            download_urls: download_url_cache(),
+            url_cache_hits: AtomicU64::new(0),
+            url_cache_misses: AtomicU64::new(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_key_matches_aws_example() {
+        // From the AWS Signature Version 4 documentation
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_photo_s3_key() {
+        let id = Uuid::new_v4();
+
+        let key = photo_s3_key("room_floor_plan", id, "image/png");
+        assert!(key.starts_with(&format!("room_floor_plan/{}/", id)));
+        assert!(key.ends_with(".png"));
+        assert!(photo_s3_key("item", id, "application/pdf").ends_with(".bin"));
+        assert_ne!(key, photo_s3_key("room_floor_plan", id, "image/png"));
+    }
+
+    #[test]
+    fn test_multipart_plan() {
+        const MIB: u64 = 1024 * 1024;
+
+        assert_eq!(multipart_plan(200 * MIB), (64 * MIB, 4));
+        assert_eq!(multipart_plan(64 * MIB), (64 * MIB, 1));
+        assert_eq!(multipart_plan(0), (64 * MIB, 1));
+
+        // Past 10,000 parts of 64 MiB the parts grow instead
+        let (part_size, parts) = multipart_plan(MAX_S3_OBJECT_BYTES);
+        assert!(part_size > 64 * MIB);
+        assert!(parts <= MAX_MULTIPART_PARTS);
+        assert!(part_size * parts >= MAX_S3_OBJECT_BYTES);
+    }
+
+    #[test]
+    fn test_needs_multipart() {
+        let s3 = S3Service::for_tests();
+        assert!(!s3.needs_multipart(10 * 1024 * 1024));
+        // Over MAX_UPLOAD_BYTES (50 MB) but under the 100 MB threshold
+        assert!(s3.needs_multipart(60 * 1024 * 1024));
+        assert!(s3.needs_multipart(6 * 1024 * 1024 * 1024));
+    }
+
+    #[tokio::test]
+    async fn test_presigned_post_limits_content_length() {
+        let s3 = S3Service::for_tests();
+        let post = s3
+            .generate_presigned_upload_url_for_key("items/manual/a.pdf", "application/pdf", 1024)
+            .await
+            .unwrap();
+
+        assert_eq!(post.url, "http://localhost:9000/test-bucket");
+        assert_eq!(post.fields["key"], "items/manual/a.pdf");
+        assert!(post.fields["x-amz-credential"].starts_with("test/"));
+        assert_eq!(post.fields["x-amz-signature"].len(), 64);
+
+        let policy: serde_json::Value =
+            serde_json::from_slice(&STANDARD.decode(&post.fields["policy"]).unwrap()).unwrap();
+        let conditions = policy["conditions"].as_array().unwrap();
+        assert!(conditions.contains(&serde_json::json!(["content-length-range", 0, 1024])));
+        assert!(conditions.contains(&serde_json::json!({ "Content-Type": "application/pdf" })));
+    }
+
+    #[tokio::test]
+    async fn test_download_urls_are_cached_until_near_expiry() {
+        let s3 = S3Service::for_tests();
+
+        let first = s3
+            .generate_presigned_download_url("photos/a.jpg")
+            .await
+            .unwrap();
+        let again = s3
+            .generate_presigned_download_url("photos/a.jpg")
+            .await
+            .unwrap();
+        s3.generate_presigned_download_url("photos/b.jpg")
+            .await
+            .unwrap();
+        assert_eq!(first, again);
+        assert_eq!(s3.cache_stats(), UrlCacheStats { hits: 1, misses: 2 });
+
+        // An entry within the margin of its expiry is presigned afresh
+        s3.download_urls.insert(
+            "photos/a.jpg".to_string(),
+            ("stale".to_string(), Instant::now()),
+        );
+        let renewed = s3
+            .generate_presigned_download_url("photos/a.jpg")
+            .await
+            .unwrap();
+        assert_ne!(renewed, "stale");
+        assert_eq!(s3.cache_stats(), UrlCacheStats { hits: 1, misses: 3 });
+    }
+
+    #[test]
+    fn test_download_url_freshness_is_capped_by_credentials() {
+        const HOUR: Duration = Duration::from_secs(3600);
+
+        // Long-lived credentials: the URL's own 24 hours, less the margin
+        assert_eq!(download_url_freshness(None), Some(23 * HOUR));
+        assert_eq!(download_url_freshness(Some(48 * HOUR)), Some(23 * HOUR));
+        // A session expiring sooner takes the URL with it
+        assert_eq!(download_url_freshness(Some(6 * HOUR)), Some(5 * HOUR));
+        assert_eq!(download_url_freshness(Some(HOUR)), None);
+        assert_eq!(download_url_freshness(Some(Duration::ZERO)), None);
+    }
+}
@@ -0,0 +1,333 @@
+use sqlx::{FromRow, PgPool};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::models::{SearchResult, SearchResultKind};
+
+/// Containers nest; stop walking up a (corrupt) cyclic hierarchy after this many steps
+const MAX_PATH_DEPTH: usize = 32;
+
+/// A search match plus the IDs of its direct parent location (at most one is set)
+#[derive(Debug, FromRow)]
+struct SearchRow {
+    id: Uuid,
+    name: String,
+    room_id: Option<Uuid>,
+    shelving_unit_id: Option<Uuid>,
+    shelf_id: Option<Uuid>,
+    container_id: Option<Uuid>,
+}
+
+impl SearchRow {
+    fn parent(&self) -> Option<Location> {
+        self.container_id
+            .map(Location::Container)
+            .or(self.shelf_id.map(Location::Shelf))
+            .or(self.shelving_unit_id.map(Location::Unit))
+            .or(self.room_id.map(Location::Room))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Location {
+    Room(Uuid),
+    Unit(Uuid),
+    Shelf(Uuid),
+    Container(Uuid),
+}
+
+/// Search query for each kind. `$1` is the `%term%` pattern, `$2` the exact term,
+/// `$3` the `term%` prefix pattern, `$4` the limit and `$5` the household. Best name matches come first
+/// so the per-kind limit keeps the most relevant rows.
+fn search_sql(kind: SearchResultKind) -> &'static str {
+    match kind {
+        SearchResultKind::Room => {
+            r#"
+            SELECT id, name, NULL::UUID AS room_id, NULL::UUID AS shelving_unit_id,
+                   NULL::UUID AS shelf_id, NULL::UUID AS container_id
+            FROM rooms
+            WHERE household_id = $5 AND (name ILIKE $1 OR description ILIKE $1)
+            ORDER BY LOWER(name) = LOWER($2) DESC, name ILIKE $3 DESC, name ILIKE $1 DESC, name
+            LIMIT $4
+            "#
+        }
+        SearchResultKind::Unit => {
+            r#"
+            SELECT id, name, room_id, NULL::UUID AS shelving_unit_id,
+                   NULL::UUID AS shelf_id, NULL::UUID AS container_id
+            FROM shelving_units
+            WHERE household_id = $5 AND (name ILIKE $1 OR description ILIKE $1)
+            ORDER BY LOWER(name) = LOWER($2) DESC, name ILIKE $3 DESC, name ILIKE $1 DESC, name
+            LIMIT $4
+            "#
+        }
+        SearchResultKind::Shelf => {
+            r#"
+            SELECT id, name, NULL::UUID AS room_id, shelving_unit_id,
+                   NULL::UUID AS shelf_id, NULL::UUID AS container_id
+            FROM shelves
+            WHERE household_id = $5 AND (name ILIKE $1 OR description ILIKE $1)
+            ORDER BY LOWER(name) = LOWER($2) DESC, name ILIKE $3 DESC, name ILIKE $1 DESC, name
+            LIMIT $4
+            "#
+        }
+        SearchResultKind::Container => {
+            r#"
+            SELECT id, name, NULL::UUID AS room_id, NULL::UUID AS shelving_unit_id,
+                   shelf_id, parent_container_id AS container_id
+            FROM containers
+            WHERE household_id = $5 AND (name ILIKE $1 OR description ILIKE $1)
+            ORDER BY LOWER(name) = LOWER($2) DESC, name ILIKE $3 DESC, name ILIKE $1 DESC, name
+            LIMIT $4
+            "#
+        }
+        SearchResultKind::Item => {
+            r#"
+            SELECT id, name, NULL::UUID AS room_id, NULL::UUID AS shelving_unit_id,
+                   shelf_id, container_id
+            FROM items
+            WHERE deleted_at IS NULL
+              AND household_id = $5
+              AND (name ILIKE $1 OR description ILIKE $1 OR barcode ILIKE $1)
+            ORDER BY LOWER(name) = LOWER($2) DESC, name ILIKE $3 DESC, name ILIKE $1 DESC, name
+            LIMIT $4
+            "#
+        }
+    }
+}
+
+/// Relevance of a name for a search term: 3 exact, 2 prefix, 1 substring, 0 otherwise
+/// (the match came from the description or barcode)
+pub fn relevance_score(name: &str, term: &str) -> i32 {
+    let name = name.to_lowercase();
+    let term = term.to_lowercase();
+    if name == term {
+        3
+    } else if name.starts_with(&term) {
+        2
+    } else if name.contains(&term) {
+        1
+    } else {
+        0
+    }
+}
+
+/// Resolves location breadcrumbs, caching each location looked up
+struct PathResolver<'a> {
+    db: &'a PgPool,
+    cache: HashMap<Location, Option<(String, Option<Location>)>>,
+}
+
+impl<'a> PathResolver<'a> {
+    fn new(db: &'a PgPool) -> Self {
+        Self {
+            db,
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Name and parent of a location, or `None` if it no longer exists
+    async fn lookup(
+        &mut self,
+        location: Location,
+    ) -> Result<Option<(String, Option<Location>)>, sqlx::Error> {
+        if let Some(entry) = self.cache.get(&location) {
+            return Ok(entry.clone());
+        }
+
+        let entry = match location {
+            Location::Room(id) => {
+                sqlx::query_scalar::<_, String>("SELECT name FROM rooms WHERE id = $1")
+                    .bind(id)
+                    .fetch_optional(self.db)
+                    .await?
+                    .map(|name| (name, None))
+            }
+            Location::Unit(id) => sqlx::query_as::<_, (String, Uuid)>(
+                "SELECT name, room_id FROM shelving_units WHERE id = $1",
+            )
+            .bind(id)
+            .fetch_optional(self.db)
+            .await?
+            .map(|(name, room_id)| (name, Some(Location::Room(room_id)))),
+            Location::Shelf(id) => sqlx::query_as::<_, (String, Uuid)>(
+                "SELECT name, shelving_unit_id FROM shelves WHERE id = $1",
+            )
+            .bind(id)
+            .fetch_optional(self.db)
+            .await?
+            .map(|(name, unit_id)| (name, Some(Location::Unit(unit_id)))),
+            Location::Container(id) => sqlx::query_as::<_, (String, Option<Uuid>, Option<Uuid>)>(
+                "SELECT name, shelf_id, parent_container_id FROM containers WHERE id = $1",
+            )
+            .bind(id)
+            .fetch_optional(self.db)
+            .await?
+            .map(|(name, shelf_id, parent_id)| {
+                let parent = parent_id
+                    .map(Location::Container)
+                    .or(shelf_id.map(Location::Shelf));
+                (name, parent)
+            }),
+        };
+
+        self.cache.insert(location, entry.clone());
+        Ok(entry)
+    }
+
+    /// Breadcrumb from the room down to `location`, e.g. "Garage > Metal Rack > Top Shelf"
+    async fn path(&mut self, location: Option<Location>) -> Result<String, sqlx::Error> {
+        let mut names = Vec::new();
+        let mut next = location;
+        while let Some(location) = next {
+            if names.len() >= MAX_PATH_DEPTH {
+                tracing::warn!("Location hierarchy too deep at {:?}", location);
+                break;
+            }
+            match self.lookup(location).await? {
+                Some((name, parent)) => {
+                    names.push(name);
+                    next = parent;
+                }
+                None => break,
+            }
+        }
+        names.reverse();
+        Ok(names.join(" > "))
+    }
+}
+
+/// Search one kind of entity and resolve each match's breadcrumb
+async fn search_kind(
+    db: &PgPool,
+    household_id: Uuid,
+    kind: SearchResultKind,
+    term: &str,
+    limit: i64,
+) -> Result<Vec<SearchResult>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, SearchRow>(search_sql(kind))
+        .bind(format!("%{}%", term))
+        .bind(term)
+        .bind(format!("{}%", term))
+        .bind(limit)
+        .bind(household_id)
+        .fetch_all(db)
+        .await?;
+
+    let mut resolver = PathResolver::new(db);
+    let mut results = Vec::with_capacity(rows.len());
+    for row in rows {
+        let path = resolver.path(row.parent()).await?;
+        results.push(SearchResult {
+            kind,
+            id: row.id,
+            score: relevance_score(&row.name, term),
+            name: row.name,
+            path,
+        });
+    }
+    Ok(results)
+}
+
+/// Search the requested entity kinds of a household in parallel, returning at most
+/// `limit` results ordered by relevance
+pub async fn search(
+    db: &PgPool,
+    household_id: Uuid,
+    term: &str,
+    kinds: &[SearchResultKind],
+    limit: i64,
+) -> Result<Vec<SearchResult>, sqlx::Error> {
+    let search_if_requested = |kind: SearchResultKind| async move {
+        if kinds.contains(&kind) {
+            search_kind(db, household_id, kind, term, limit).await
+        } else {
+            Ok(Vec::new())
+        }
+    };
+
+    let (rooms, units, shelves, containers, items) = tokio::join!(
+        search_if_requested(SearchResultKind::Room),
+        search_if_requested(SearchResultKind::Unit),
+        search_if_requested(SearchResultKind::Shelf),
+        search_if_requested(SearchResultKind::Container),
+        search_if_requested(SearchResultKind::Item),
+    );
+
+    let mut results = Vec::new();
+    for found in [rooms, units, shelves, containers, items] {
+        results.extend(found?);
+    }
+    sort_results(&mut results);
+    results.truncate(limit.max(0) as usize);
+    Ok(results)
+}
+
+/// Highest score first, then alphabetically by name
+fn sort_results(results: &mut [SearchResult]) {
+    results.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, score: i32) -> SearchResult {
+        SearchResult {
+            kind: SearchResultKind::Item,
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            path: String::new(),
+            score,
+        }
+    }
+
+    #[test]
+    fn test_relevance_score() {
+        assert_eq!(relevance_score("Drill", "drill"), 3);
+        assert_eq!(relevance_score("Drill Bits", "drill"), 2);
+        assert_eq!(relevance_score("Cordless Drill", "drill"), 1);
+        assert_eq!(relevance_score("Toolbox", "drill"), 0);
+    }
+
+    #[test]
+    fn test_sort_results() {
+        let mut results = vec![
+            result("Cordless Drill", 1),
+            result("drill bits", 2),
+            result("Drill", 3),
+            result("Drill Press", 2),
+        ];
+        sort_results(&mut results);
+
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Drill", "drill bits", "Drill Press", "Cordless Drill"]
+        );
+    }
+
+    #[test]
+    fn test_search_row_parent_prefers_innermost_location() {
+        let container_id = Uuid::new_v4();
+        let row = SearchRow {
+            id: Uuid::new_v4(),
+            name: "Hammer".to_string(),
+            room_id: None,
+            shelving_unit_id: None,
+            shelf_id: None,
+            container_id: Some(container_id),
+        };
+        assert_eq!(row.parent(), Some(Location::Container(container_id)));
+
+        let room_row = SearchRow {
+            container_id: None,
+            ..row
+        };
+        assert_eq!(room_row.parent(), None);
+    }
+}
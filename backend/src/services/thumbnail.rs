@@ -0,0 +1,117 @@
+use anyhow::Context;
+use image::codecs::jpeg::JpegEncoder;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::Photo;
+use crate::services::s3::S3Service;
+
+/// Thumbnails fit within a square this many pixels on a side
+pub const THUMBNAIL_MAX_DIMENSION: u32 = 400;
+
+const THUMBNAIL_JPEG_QUALITY: u8 = 80;
+
+/// Where a photo's thumbnail is stored, next to the photos of its entity
+pub fn thumbnail_key(entity_type: &str, entity_id: Uuid, photo_id: Uuid) -> String {
+    format!("{}/{}/thumbs/{}.jpg", entity_type, entity_id, photo_id)
+}
+
+/// Scale `width` x `height` down to fit within `max` x `max`, keeping the aspect
+/// ratio. Images that already fit are left at their size.
+fn fit_within(width: u32, height: u32, max: u32) -> (u32, u32) {
+    if width <= max && height <= max {
+        return (width, height);
+    }
+
+    let scale = max as f64 / width.max(height) as f64;
+    let scaled = |side: u32| ((side as f64 * scale).round() as u32).clamp(1, max);
+    (scaled(width), scaled(height))
+}
+
+/// Decode an image and re-encode it as a JPEG thumbnail. CPU bound; run it on a
+/// blocking thread.
+pub fn render_thumbnail(bytes: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let image = image::load_from_memory(bytes).context("Failed to decode image")?;
+    // JPEG has no alpha channel
+    let rgb = image.to_rgb8();
+    let (width, height) = fit_within(rgb.width(), rgb.height(), THUMBNAIL_MAX_DIMENSION);
+    let thumbnail = image::imageops::thumbnail(&rgb, width, height);
+
+    let mut jpeg = Vec::new();
+    JpegEncoder::new_with_quality(&mut jpeg, THUMBNAIL_JPEG_QUALITY)
+        .encode_image(&thumbnail)
+        .context("Failed to encode thumbnail")?;
+    Ok(jpeg)
+}
+
+/// Build a photo's thumbnail from the full image in S3, upload it and record its key.
+/// Returns the new key.
+pub async fn generate_photo_thumbnail(
+    db: &PgPool,
+    s3: &S3Service,
+    photo: &Photo,
+) -> anyhow::Result<String> {
+    let original = s3.get_object_bytes(&photo.s3_key).await?;
+    let jpeg = tokio::task::spawn_blocking(move || render_thumbnail(&original)).await??;
+
+    let key = thumbnail_key(&photo.entity_type, photo.entity_id, photo.id);
+    s3.put_object_bytes(&key, jpeg, "image/jpeg").await?;
+
+    sqlx::query("UPDATE photos SET thumbnail_s3_key = $1 WHERE id = $2")
+        .bind(&key)
+        .bind(photo.id)
+        .execute(db)
+        .await?;
+
+    // A thumbnail the client uploaded itself is now orphaned
+    if let Some(old_key) = photo.thumbnail_s3_key.as_deref().filter(|old| *old != key) {
+        if let Err(e) = s3.delete_file(old_key).await {
+            tracing::warn!("Failed to delete old thumbnail {}: {:?}", old_key, e);
+        }
+    }
+
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbaImage};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_fit_within() {
+        assert_eq!(fit_within(4000, 3000, 400), (400, 300));
+        assert_eq!(fit_within(3000, 4000, 400), (300, 400));
+        assert_eq!(fit_within(1000, 1000, 400), (400, 400));
+        assert_eq!(fit_within(200, 100, 400), (200, 100));
+        assert_eq!(fit_within(10000, 1, 400), (400, 1));
+    }
+
+    #[test]
+    fn test_thumbnail_key() {
+        let entity_id = Uuid::nil();
+        let photo_id = Uuid::from_u128(1);
+        assert_eq!(
+            thumbnail_key("item", entity_id, photo_id),
+            format!("item/{}/thumbs/{}.jpg", entity_id, photo_id)
+        );
+    }
+
+    #[test]
+    fn test_render_thumbnail() {
+        let mut png = Vec::new();
+        RgbaImage::new(800, 600)
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+
+        let jpeg = render_thumbnail(&png).unwrap();
+        let thumbnail = image::load_from_memory_with_format(&jpeg, ImageFormat::Jpeg).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (400, 300));
+    }
+
+    #[test]
+    fn test_render_thumbnail_rejects_non_images() {
+        assert!(render_thumbnail(b"not an image").is_err());
+    }
+}
@@ -1,13 +1,144 @@
+use axum::async_trait;
 use base64::{engine::general_purpose::STANDARD, Engine};
-use reqwest::Client;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Client, RequestBuilder, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::env;
+use std::str::FromStr;
+use std::time::Duration;
 
 use crate::models::{ItemImportDraftItem, LocationUpdateProposal};
 
+const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1/messages";
+const ANTHROPIC_MODEL: &str = "claude-sonnet-4-20250514";
+const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
+const OPENAI_MODEL: &str = "gpt-4o";
+
+/// Number of times a rate-limited request is retried before giving up
+const MAX_RETRIES: u32 = 3;
+/// Upper bound on how long we'll honor a server-provided Retry-After
+const MAX_RETRY_DELAY_SECS: u64 = 60;
+
+/// Identifies items in photos. Held by `AppState` as a trait object so handlers
+/// don't depend on which API is configured.
+#[async_trait]
+pub trait VisionBackendTrait: Send + Sync {
+    /// Check the API is reachable and accepts our key
+    async fn ping(&self) -> anyhow::Result<()>;
+
+    /// Items visible in `images` (bytes and media type), plus a suggested
+    /// description and tags for the container or shelf they were taken of
+    async fn analyze_image_for_items(
+        &self,
+        images: Vec<(&[u8], &str)>,
+        hint: Option<&str>,
+        location_type: LocationType,
+    ) -> anyhow::Result<(Vec<ItemImportDraftItem>, Option<LocationUpdateProposal>)>;
+}
+
+/// Which API analyzes photos, from `VISION_BACKEND`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisionBackend {
+    Anthropic,
+    OpenAI,
+}
+
+impl VisionBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VisionBackend::Anthropic => "anthropic",
+            VisionBackend::OpenAI => "openai",
+        }
+    }
+
+    /// Environment variable holding this backend's API key
+    pub fn api_key_var(&self) -> &'static str {
+        match self {
+            VisionBackend::Anthropic => "ANTHROPIC_API_KEY",
+            VisionBackend::OpenAI => "OPENAI_API_KEY",
+        }
+    }
+
+    fn api_url(&self) -> &'static str {
+        match self {
+            VisionBackend::Anthropic => ANTHROPIC_API_URL,
+            VisionBackend::OpenAI => OPENAI_API_URL,
+        }
+    }
+
+    fn model(&self) -> &'static str {
+        match self {
+            VisionBackend::Anthropic => ANTHROPIC_MODEL,
+            VisionBackend::OpenAI => OPENAI_MODEL,
+        }
+    }
+
+    /// Name used in log and error messages
+    fn label(&self) -> &'static str {
+        match self {
+            VisionBackend::Anthropic => "Anthropic",
+            VisionBackend::OpenAI => "OpenAI",
+        }
+    }
+}
+
+impl FromStr for VisionBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "anthropic" => Ok(VisionBackend::Anthropic),
+            "openai" => Ok(VisionBackend::OpenAI),
+            other => Err(format!(
+                "Unknown VISION_BACKEND '{}': expected anthropic or openai",
+                other
+            )),
+        }
+    }
+}
+
+/// Vision settings from the environment
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisionConfig {
+    pub backend: VisionBackend,
+    pub api_key: String,
+}
+
+impl VisionConfig {
+    /// `VISION_BACKEND` (`anthropic` by default, or `openai`) and the matching
+    /// `ANTHROPIC_API_KEY` or `OPENAI_API_KEY`
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::resolve(env::var("VISION_BACKEND").ok().as_deref(), |name| {
+            env::var(name).ok()
+        })
+    }
+
+    /// Pick the backend from `backend_var` and look its key up with `var`
+    pub fn resolve(
+        backend_var: Option<&str>,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> anyhow::Result<Self> {
+        let backend = match backend_var.map(str::trim).filter(|b| !b.is_empty()) {
+            Some(backend) => backend.parse().map_err(anyhow::Error::msg)?,
+            None => VisionBackend::Anthropic,
+        };
+        let api_key = var(backend.api_key_var())
+            .filter(|key| !key.trim().is_empty())
+            .ok_or_else(|| {
+                anyhow::anyhow!("{} environment variable not set", backend.api_key_var())
+            })?;
+
+        Ok(Self { backend, api_key })
+    }
+}
+
+/// Client for the configured vision API. Both backends get the same prompt and
+/// their answers are parsed the same way.
 pub struct VisionService {
     client: Client,
+    backend: VisionBackend,
     api_key: String,
+    api_url: String,
 }
 
 pub enum LocationType {
@@ -55,6 +186,47 @@ struct ResponseContent {
     text: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+struct OpenAiRequest {
+    model: String,
+    max_tokens: u32,
+    messages: Vec<OpenAiMessage>,
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAiMessage {
+    role: String,
+    content: Vec<OpenAiContent>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OpenAiContent {
+    ImageUrl { image_url: ImageUrl },
+    Text { text: String },
+}
+
+#[derive(Debug, Serialize)]
+struct ImageUrl {
+    /// A `data:` URL carrying the image inline
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiResponse {
+    choices: Vec<OpenAiChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiChoice {
+    message: OpenAiResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiResponseMessage {
+    content: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ParsedResponse {
     items: Vec<ParsedItem>,
@@ -71,16 +243,125 @@ struct ParsedItem {
 }
 
 impl VisionService {
+    /// Client for the backend `VISION_BACKEND` selects. Fails when that backend's
+    /// API key isn't set.
     pub fn new() -> anyhow::Result<Self> {
-        let api_key = env::var("ANTHROPIC_API_KEY")
-            .map_err(|_| anyhow::anyhow!("ANTHROPIC_API_KEY environment variable not set"))?;
+        Ok(Self::from_config(VisionConfig::from_env()?))
+    }
 
-        let client = Client::new();
+    pub fn from_config(config: VisionConfig) -> Self {
+        Self {
+            client: Client::new(),
+            backend: config.backend,
+            api_key: config.api_key,
+            api_url: config.backend.api_url().to_string(),
+        }
+    }
+
+    pub fn backend(&self) -> VisionBackend {
+        self.backend
+    }
 
-        Ok(Self { client, api_key })
+    /// A POST to the API with this backend's authentication headers
+    fn post(&self) -> RequestBuilder {
+        let request = self
+            .client
+            .post(&self.api_url)
+            .header("content-type", "application/json");
+        match self.backend {
+            VisionBackend::Anthropic => request
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", "2023-06-01"),
+            VisionBackend::OpenAI => request.bearer_auth(&self.api_key),
+        }
+    }
+
+    /// Request body with one user message: `images` followed by `prompt`
+    fn request_body(
+        &self,
+        images: Vec<(&[u8], &str)>,
+        prompt: String,
+        max_tokens: u32,
+    ) -> anyhow::Result<serde_json::Value> {
+        let model = self.backend.model().to_string();
+        let role = "user".to_string();
+        let body = match self.backend {
+            VisionBackend::Anthropic => {
+                let mut content: Vec<Content> = images
+                    .into_iter()
+                    .map(|(image_data, media_type)| Content::Image {
+                        source: ImageSource {
+                            source_type: "base64".to_string(),
+                            media_type: media_type.to_string(),
+                            data: base64_encode(image_data),
+                        },
+                    })
+                    .collect();
+                content.push(Content::Text { text: prompt });
+                serde_json::to_value(AnthropicRequest {
+                    model,
+                    max_tokens,
+                    messages: vec![Message { role, content }],
+                })?
+            }
+            VisionBackend::OpenAI => {
+                let mut content: Vec<OpenAiContent> = images
+                    .into_iter()
+                    .map(|(image_data, media_type)| OpenAiContent::ImageUrl {
+                        image_url: ImageUrl {
+                            url: format!(
+                                "data:{};base64,{}",
+                                media_type,
+                                base64_encode(image_data)
+                            ),
+                        },
+                    })
+                    .collect();
+                content.push(OpenAiContent::Text { text: prompt });
+                serde_json::to_value(OpenAiRequest {
+                    model,
+                    max_tokens,
+                    messages: vec![OpenAiMessage { role, content }],
+                })?
+            }
+        };
+        Ok(body)
+    }
+
+    /// The model's text answer from a successful response body
+    fn response_text(&self, body: &[u8]) -> anyhow::Result<String> {
+        let text = match self.backend {
+            VisionBackend::Anthropic => serde_json::from_slice::<AnthropicResponse>(body)?
+                .content
+                .into_iter()
+                .find_map(|c| c.text),
+            VisionBackend::OpenAI => serde_json::from_slice::<OpenAiResponse>(body)?
+                .choices
+                .into_iter()
+                .find_map(|choice| choice.message.content),
+        };
+        text.ok_or_else(|| anyhow::anyhow!("No text response from {}", self.backend.label()))
+    }
+}
+
+#[async_trait]
+impl VisionBackendTrait for VisionService {
+    /// A one-token request
+    async fn ping(&self) -> anyhow::Result<()> {
+        let request = self.request_body(Vec::new(), "ping".to_string(), 1)?;
+        let response = self.post().json(&request).send().await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "{} API returned {}",
+                self.backend.label(),
+                response.status()
+            ));
+        }
+        Ok(())
     }
 
-    pub async fn analyze_image_for_items(
+    async fn analyze_image_for_items(
         &self,
         images: Vec<(&[u8], &str)>,
         hint: Option<&str>,
@@ -92,74 +373,99 @@ impl VisionService {
             ));
         }
 
-        let mut content_blocks: Vec<Content> = Vec::new();
-
-        // Add all images as content blocks
-        for (image_data, media_type) in images {
-            let base64_image = base64_encode(image_data);
-            content_blocks.push(Content::Image {
-                source: ImageSource {
-                    source_type: "base64".to_string(),
-                    media_type: media_type.to_string(),
-                    data: base64_image,
-                },
-            });
-        }
-
-        // Add text prompt (with hint incorporated)
+        // Images first, then the text prompt (with hint incorporated)
         let prompt = build_prompt(hint, location_type);
-        content_blocks.push(Content::Text { text: prompt });
-
-        let request = AnthropicRequest {
-            model: "claude-sonnet-4-20250514".to_string(),
-            max_tokens: 4096,
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: content_blocks,
-            }],
+        let request = self.request_body(images, prompt, 4096)?;
+
+        let mut attempt = 0;
+        let response = loop {
+            let response = self.post().json(&request).send().await?;
+
+            if response.status() == StatusCode::TOO_MANY_REQUESTS && attempt < MAX_RETRIES {
+                let delay = retry_delay(response.headers(), attempt);
+                attempt += 1;
+                tracing::info!(
+                    "{} API rate limited, retrying in {:?} (attempt {}/{})",
+                    self.backend.label(),
+                    delay,
+                    attempt,
+                    MAX_RETRIES
+                );
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+
+            break response;
         };
 
-        let response = self
-            .client
-            .post("https://api.anthropic.com/v1/messages")
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
-
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
 
             // Parse error details for better handling
             let error_msg = if status.as_u16() == 429 {
-                "Rate limit exceeded. Please wait a moment and try again."
+                "Rate limit exceeded. Please wait a moment and try again.".to_string()
             } else if status.as_u16() == 401 {
-                "Invalid API key. Please check your ANTHROPIC_API_KEY configuration."
+                format!(
+                    "Invalid API key. Please check your {} configuration.",
+                    self.backend.api_key_var()
+                )
             } else if status.as_u16() == 400 {
                 "Invalid request. The image may be too large or in an unsupported format."
+                    .to_string()
             } else {
-                "Failed to analyze image. Please try again later."
+                "Failed to analyze image. Please try again later.".to_string()
             };
 
-            tracing::error!("Anthropic API error: {} - {}", status, body);
+            tracing::error!("{} API error: {} - {}", self.backend.label(), status, body);
             return Err(anyhow::anyhow!("{}", error_msg));
         }
 
-        let anthropic_response: AnthropicResponse = response.json().await?;
-
-        let text = anthropic_response
-            .content
-            .into_iter()
-            .find_map(|c| c.text)
-            .ok_or_else(|| anyhow::anyhow!("No text response from Anthropic"))?;
+        let body = response.bytes().await?;
+        let text = self.response_text(&body)?;
 
         parse_items_from_response(&text)
     }
 }
 
+/// Finds nothing in any photo, for tests that need a vision backend without an API
+/// key. Without one at all (`AppState::vision` is `None`) draft analysis returns 503.
+#[cfg(test)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOpVisionService;
+
+#[cfg(test)]
+#[async_trait]
+impl VisionBackendTrait for NoOpVisionService {
+    async fn ping(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn analyze_image_for_items(
+        &self,
+        _images: Vec<(&[u8], &str)>,
+        _hint: Option<&str>,
+        _location_type: LocationType,
+    ) -> anyhow::Result<(Vec<ItemImportDraftItem>, Option<LocationUpdateProposal>)> {
+        Ok((Vec::new(), None))
+    }
+}
+
+/// How long to wait before retrying a rate-limited request.
+/// Uses the Retry-After header (in seconds, capped at 60s) when present,
+/// otherwise falls back to exponential backoff (1s, 2s, 4s, ...).
+fn retry_delay(headers: &HeaderMap, attempt: u32) -> Duration {
+    let retry_after = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+
+    match retry_after {
+        Some(secs) => Duration::from_secs(secs.min(MAX_RETRY_DELAY_SECS)),
+        None => Duration::from_secs((1u64 << attempt.min(6)).min(MAX_RETRY_DELAY_SECS)),
+    }
+}
+
 fn build_prompt(hint: Option<&str>, location_type: LocationType) -> String {
     let location_name = match location_type {
         LocationType::Container => "container",
@@ -505,7 +811,7 @@ mod tests {
 
     #[test]
     fn test_item_import_draft_item_creation() {
-        let items = vec![ItemImportDraftItem {
+        let items = [ItemImportDraftItem {
             name: "Test Item".to_string(),
             description: Some("Description".to_string()),
             barcode: None,
@@ -517,4 +823,238 @@ mod tests {
         assert_eq!(items[0].barcode, None);
         assert_eq!(items[0].barcode_type, None);
     }
+
+    #[test]
+    fn test_retry_delay_uses_retry_after_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "5".parse().unwrap());
+
+        assert_eq!(retry_delay(&headers, 0), Duration::from_secs(5));
+        assert_eq!(retry_delay(&headers, 2), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_retry_delay_caps_retry_after_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+
+        assert_eq!(retry_delay(&headers, 0), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_retry_delay_falls_back_to_exponential_backoff() {
+        let headers = HeaderMap::new();
+
+        assert_eq!(retry_delay(&headers, 0), Duration::from_secs(1));
+        assert_eq!(retry_delay(&headers, 1), Duration::from_secs(2));
+        assert_eq!(retry_delay(&headers, 2), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_retry_delay_ignores_unparseable_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+
+        assert_eq!(retry_delay(&headers, 1), Duration::from_secs(2));
+    }
+
+    /// Spawn a mock Anthropic API that rate limits the first `rate_limited` requests
+    async fn spawn_mock_api(
+        rate_limited: usize,
+        retry_after: &'static str,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use axum::{http::StatusCode as AxumStatus, response::IntoResponse, routing::post};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+
+        let app = axum::Router::new().route(
+            "/v1/messages",
+            post(move || {
+                let counter = counter.clone();
+                async move {
+                    if counter.fetch_add(1, Ordering::SeqCst) < rate_limited {
+                        (
+                            AxumStatus::TOO_MANY_REQUESTS,
+                            [("retry-after", retry_after)],
+                            "",
+                        )
+                            .into_response()
+                    } else {
+                        axum::Json(serde_json::json!({
+                            "content": [{
+                                "type": "text",
+                                "text": r#"{"items": [{"name": "Hammer", "description": null}]}"#
+                            }]
+                        }))
+                        .into_response()
+                    }
+                }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        (format!("http://{}/v1/messages", addr), calls)
+    }
+
+    fn test_service(backend: VisionBackend, api_url: String) -> VisionService {
+        VisionService {
+            client: Client::new(),
+            backend,
+            api_key: "test-key".to_string(),
+            api_url,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_analyze_retries_after_rate_limit() {
+        let (url, calls) = spawn_mock_api(2, "0").await;
+        let service = test_service(VisionBackend::Anthropic, url);
+
+        let result = service
+            .analyze_image_for_items(
+                vec![(&b"image"[..], "image/jpeg")],
+                None,
+                LocationType::Container,
+            )
+            .await;
+
+        let (items, _) = result.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "Hammer");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_gives_up_after_max_retries() {
+        let (url, calls) = spawn_mock_api(usize::MAX, "0").await;
+        let service = test_service(VisionBackend::Anthropic, url);
+
+        let result = service
+            .analyze_image_for_items(
+                vec![(&b"image"[..], "image/jpeg")],
+                None,
+                LocationType::Shelf,
+            )
+            .await;
+
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Rate limit exceeded"));
+        assert_eq!(
+            calls.load(std::sync::atomic::Ordering::SeqCst),
+            MAX_RETRIES as usize + 1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_analyze_with_openai_backend() {
+        use axum::{http::HeaderMap as AxumHeaders, routing::post};
+
+        // Answers only requests shaped like a chat completion with an inline image
+        let app = axum::Router::new().route(
+            "/v1/chat/completions",
+            post(
+                |headers: AxumHeaders, axum::Json(body): axum::Json<serde_json::Value>| async move {
+                    assert_eq!(headers["authorization"], "Bearer test-key");
+                    assert_eq!(body["model"], OPENAI_MODEL);
+                    let content = &body["messages"][0]["content"];
+                    assert_eq!(content[0]["type"], "image_url");
+                    assert_eq!(
+                        content[0]["image_url"]["url"],
+                        "data:image/png;base64,aW1hZ2U="
+                    );
+                    assert_eq!(content[1]["type"], "text");
+
+                    axum::Json(serde_json::json!({
+                        "choices": [{
+                            "message": {
+                                "role": "assistant",
+                                "content": r#"{"items": [{"name": "Wrench", "description": "Adjustable"}], "location_tags": ["tools"]}"#
+                            }
+                        }]
+                    }))
+                },
+            ),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let service = test_service(
+            VisionBackend::OpenAI,
+            format!("http://{}/v1/chat/completions", addr),
+        );
+        let (items, proposal) = service
+            .analyze_image_for_items(
+                vec![(&b"image"[..], "image/png")],
+                None,
+                LocationType::Container,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "Wrench");
+        assert_eq!(proposal.unwrap().tags, Some(vec!["tools".to_string()]));
+    }
+
+    #[test]
+    fn test_vision_backend_from_str() {
+        assert_eq!(
+            "anthropic".parse::<VisionBackend>(),
+            Ok(VisionBackend::Anthropic)
+        );
+        assert_eq!(
+            " OpenAI ".parse::<VisionBackend>(),
+            Ok(VisionBackend::OpenAI)
+        );
+        for backend in [VisionBackend::Anthropic, VisionBackend::OpenAI] {
+            assert_eq!(backend.as_str().parse::<VisionBackend>(), Ok(backend));
+        }
+        assert!("gemini".parse::<VisionBackend>().is_err());
+    }
+
+    #[test]
+    fn test_vision_config_resolve() {
+        let keys = |name: &str| match name {
+            "ANTHROPIC_API_KEY" => Some("sk-ant".to_string()),
+            "OPENAI_API_KEY" => Some("sk-openai".to_string()),
+            _ => None,
+        };
+
+        let config = VisionConfig::resolve(None, keys).unwrap();
+        assert_eq!(config.backend, VisionBackend::Anthropic);
+        assert_eq!(config.api_key, "sk-ant");
+
+        let config = VisionConfig::resolve(Some("openai"), keys).unwrap();
+        assert_eq!(config.backend, VisionBackend::OpenAI);
+        assert_eq!(config.api_key, "sk-openai");
+
+        assert!(VisionConfig::resolve(Some("gemini"), keys).is_err());
+    }
+
+    #[test]
+    fn test_vision_config_requires_selected_backends_key() {
+        let anthropic_only = |name: &str| (name == "ANTHROPIC_API_KEY").then(|| "sk".to_string());
+
+        let err = VisionConfig::resolve(Some("openai"), anthropic_only).unwrap_err();
+        assert!(err.to_string().contains("OPENAI_API_KEY"));
+        assert!(VisionConfig::resolve(Some(" "), anthropic_only).is_ok());
+        assert!(VisionConfig::resolve(None, |_| Some("  ".to_string())).is_err());
+    }
 }
@@ -0,0 +1,246 @@
+//! Webhook delivery for audit log entries.
+//!
+//! Deliveries run on spawned tasks so handlers never wait on a subscriber. On Lambda a
+//! frozen container pauses in-flight deliveries until its next invocation, so retries
+//! there are best effort.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::models::{is_public_webhook_ip, Webhook};
+
+/// Header carrying `sha256=<hex HMAC-SHA256 of the body keyed by the webhook secret>`
+pub const SIGNATURE_HEADER: &str = "X-Inventory-Signature";
+/// Header carrying the event name, e.g. `item.CREATE`
+pub const EVENT_HEADER: &str = "X-Inventory-Event";
+
+const MAX_ATTEMPTS: u32 = 3;
+/// Wait before the first retry; doubles after each failed attempt
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON body POSTed to webhooks: the audit log entry that triggered it
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    /// Audit log entry id
+    pub id: Uuid,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub action: String,
+    pub user_id: Option<Uuid>,
+    pub changes: Option<Value>,
+    pub metadata: Option<Value>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl WebhookEvent {
+    /// `<entity_type>.<action>`, e.g. `item.CREATE`
+    pub fn name(&self) -> String {
+        format!("{}.{}", self.entity_type, self.action)
+    }
+}
+
+/// Signature header value for a body
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Resolve the webhook's host and refuse to deliver if any address is loopback,
+/// link-local or private, so a public hostname can't be pointed at the server's
+/// own network after the URL was validated
+async fn ensure_public_target(url: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| "Webhook URL has no host".to_string())?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let port = parsed.port_or_known_default().unwrap_or(443);
+
+    let addrs = tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| format!("Failed to resolve webhook host: {}", e))?;
+    for addr in addrs {
+        if !is_public_webhook_ip(addr.ip()) {
+            return Err(format!(
+                "Webhook host resolves to a private address: {}",
+                addr.ip()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Delay after failed attempt number `attempt` (1-based): 1s, 2s, 4s, ...
+fn backoff(attempt: u32) -> Duration {
+    INITIAL_BACKOFF * 2u32.pow(attempt.saturating_sub(1))
+}
+
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    db: Arc<PgPool>,
+    client: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    pub fn new(db: Arc<PgPool>) -> Self {
+        // Redirects aren't followed: a redirect could send the delivery to an address
+        // the URL checks would have rejected
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build webhook HTTP client");
+        Self { db, client }
+    }
+
+    /// Deliver an event to every matching active webhook in the background
+    pub fn dispatch(&self, event: WebhookEvent) {
+        let dispatcher = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = dispatcher.deliver_to_matching(&event).await {
+                tracing::error!("Failed to dispatch webhooks for {}: {:?}", event.name(), e);
+            }
+        });
+    }
+
+    async fn deliver_to_matching(&self, event: &WebhookEvent) -> anyhow::Result<()> {
+        let webhooks = sqlx::query_as::<_, Webhook>("SELECT * FROM webhooks WHERE active = TRUE")
+            .fetch_all(&*self.db)
+            .await?;
+
+        let matching: Vec<Webhook> = webhooks
+            .into_iter()
+            .filter(|webhook| webhook.matches(&event.entity_type, &event.action))
+            .collect();
+        if matching.is_empty() {
+            return Ok(());
+        }
+
+        let body = serde_json::to_vec(event)?;
+        futures::future::join_all(
+            matching
+                .iter()
+                .map(|webhook| self.deliver(webhook, event, &body)),
+        )
+        .await;
+        Ok(())
+    }
+
+    /// POST the body, retrying failures with exponential backoff. Every attempt is
+    /// recorded in webhook_deliveries.
+    async fn deliver(&self, webhook: &Webhook, event: &WebhookEvent, body: &[u8]) {
+        let signature = sign(&webhook.secret, body);
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            let (succeeded, status_code, error) = match ensure_public_target(&webhook.url).await {
+                Ok(()) => {
+                    let result = self
+                        .client
+                        .post(&webhook.url)
+                        .header(CONTENT_TYPE, "application/json")
+                        .header(SIGNATURE_HEADER, &signature)
+                        .header(EVENT_HEADER, event.name())
+                        .timeout(REQUEST_TIMEOUT)
+                        .body(body.to_vec())
+                        .send()
+                        .await;
+
+                    match result {
+                        Ok(response) if response.status().is_success() => {
+                            (true, Some(response.status().as_u16()), None)
+                        }
+                        Ok(response) => (
+                            false,
+                            Some(response.status().as_u16()),
+                            Some(format!("Webhook responded with {}", response.status())),
+                        ),
+                        Err(e) => (false, None, Some(e.to_string())),
+                    }
+                }
+                Err(e) => (false, None, Some(e)),
+            };
+
+            if let Err(e) = sqlx::query(
+                r#"
+                INSERT INTO webhook_deliveries
+                    (id, webhook_id, audit_log_id, event, attempt, succeeded, status_code, error)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(webhook.id)
+            .bind(event.id)
+            .bind(event.name())
+            .bind(attempt as i32)
+            .bind(succeeded)
+            .bind(status_code.map(i32::from))
+            .bind(&error)
+            .execute(&*self.db)
+            .await
+            {
+                tracing::error!("Failed to record webhook delivery: {:?}", e);
+            }
+
+            if succeeded {
+                return;
+            }
+            if attempt < MAX_ATTEMPTS {
+                tokio::time::sleep(backoff(attempt)).await;
+            }
+        }
+
+        tracing::warn!(
+            "Webhook {} gave up on {} after {} attempts",
+            webhook.id,
+            event.name(),
+            MAX_ATTEMPTS
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_matches_known_hmac() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_backoff_doubles() {
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(2), Duration::from_secs(2));
+        assert_eq!(backoff(3), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_event_name() {
+        let event = WebhookEvent {
+            id: Uuid::new_v4(),
+            entity_type: "item".to_string(),
+            entity_id: Uuid::new_v4(),
+            action: "CREATE".to_string(),
+            user_id: None,
+            changes: None,
+            metadata: None,
+            occurred_at: Utc::now(),
+        };
+        assert_eq!(event.name(), "item.CREATE");
+    }
+}
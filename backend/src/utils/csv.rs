@@ -0,0 +1,126 @@
+use axum::body::Bytes;
+use futures::Stream;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+
+/// Streams CSV rows produced by a background task as response body chunks.
+///
+/// Create one with [`CsvEncoder::channel`], hand the [`CsvWriter`] to the task
+/// producing rows, and use the encoder as the body stream. Dropping the writer
+/// ends the stream; [`CsvWriter::abort`] ends it with an error, so the response is
+/// cut off instead of looking complete.
+pub struct CsvEncoder {
+    rx: mpsc::Receiver<io::Result<Bytes>>,
+}
+
+/// Sending half of a [`CsvEncoder`]
+pub struct CsvWriter {
+    tx: mpsc::Sender<io::Result<Bytes>>,
+}
+
+impl CsvEncoder {
+    /// `buffer` is the number of rows that can be queued before the writer waits
+    pub fn channel(buffer: usize) -> (CsvWriter, CsvEncoder) {
+        let (tx, rx) = mpsc::channel(buffer);
+        (CsvWriter { tx }, CsvEncoder { rx })
+    }
+}
+
+impl Stream for CsvEncoder {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<io::Result<Bytes>>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl CsvWriter {
+    /// Send one CSV record. Returns `false` if the client has gone away.
+    pub async fn write_record<S: AsRef<str>>(&self, fields: &[S]) -> bool {
+        self.tx.send(Ok(encode_record(fields))).await.is_ok()
+    }
+
+    /// End the stream with `error`, aborting the response mid-body
+    pub async fn abort(self, error: io::Error) {
+        // Nothing left to tell a client that has already gone away
+        let _ = self.tx.send(Err(error)).await;
+    }
+}
+
+/// Encode fields as a CSV line (RFC 4180), quoting fields that need it
+pub fn encode_record<S: AsRef<str>>(fields: &[S]) -> Bytes {
+    let mut line = String::new();
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            line.push(',');
+        }
+        let field = field.as_ref();
+        if field.contains([',', '"', '\n', '\r']) {
+            line.push('"');
+            line.push_str(&field.replace('"', "\"\""));
+            line.push('"');
+        } else {
+            line.push_str(field);
+        }
+    }
+    line.push_str("\r\n");
+    Bytes::from(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    #[test]
+    fn test_encode_record_plain() {
+        assert_eq!(encode_record(&["a", "b", ""]), Bytes::from("a,b,\r\n"));
+    }
+
+    #[test]
+    fn test_encode_record_quotes_special_characters() {
+        assert_eq!(
+            encode_record(&["Drill, cordless", "12\" ruler", "line\nbreak"]),
+            Bytes::from("\"Drill, cordless\",\"12\"\" ruler\",\"line\nbreak\"\r\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encoder_streams_records_until_writer_dropped() {
+        let (writer, encoder) = CsvEncoder::channel(4);
+
+        tokio::spawn(async move {
+            assert!(writer.write_record(&["id", "name"]).await);
+            assert!(writer.write_record(&["1", "Hammer"]).await);
+        });
+
+        let chunks: Vec<Bytes> = encoder.map(Result::unwrap).collect().await;
+        assert_eq!(
+            chunks,
+            vec![Bytes::from("id,name\r\n"), Bytes::from("1,Hammer\r\n")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encoder_ends_with_error_when_aborted() {
+        let (writer, encoder) = CsvEncoder::channel(4);
+
+        tokio::spawn(async move {
+            assert!(writer.write_record(&["id", "name"]).await);
+            writer.abort(io::Error::other("connection reset")).await;
+        });
+
+        let chunks: Vec<io::Result<Bytes>> = encoder.collect().await;
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].as_ref().unwrap(), &Bytes::from("id,name\r\n"));
+        assert_eq!(
+            chunks[1].as_ref().unwrap_err().to_string(),
+            "connection reset"
+        );
+    }
+}
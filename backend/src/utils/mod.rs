@@ -0,0 +1,7 @@
+pub mod csv;
+pub mod pagination;
+pub mod validation;
+
+pub use csv::CsvEncoder;
+pub use pagination::{Cursor, CursorDecoder, CursorEncoder};
+pub use validation::validate_photo_dimensions;
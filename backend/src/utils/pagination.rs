@@ -0,0 +1,60 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Keyset position: the last row of a page ordered by `created_at DESC, id DESC`.
+/// The next page is the rows where `(created_at, id) < (cursor.created_at, cursor.id)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+/// Turns a [`Cursor`] into the opaque `next_cursor` string returned to clients:
+/// URL-safe base64 (no padding) of `{"created_at": "...", "id": "..."}`, so it can
+/// be passed back in a query string as is
+pub struct CursorEncoder;
+
+impl CursorEncoder {
+    pub fn encode(cursor: &Cursor) -> String {
+        let json = serde_json::to_vec(cursor).expect("Cursor serializes to JSON");
+        URL_SAFE_NO_PAD.encode(json)
+    }
+}
+
+/// Parses the `cursor` query parameter produced by [`CursorEncoder`]
+pub struct CursorDecoder;
+
+impl CursorDecoder {
+    pub fn decode(cursor: &str) -> Result<Cursor, String> {
+        let json = URL_SAFE_NO_PAD
+            .decode(cursor.trim())
+            .map_err(|e| format!("Invalid cursor encoding: {}", e))?;
+        serde_json::from_slice(&json).map_err(|e| format!("Invalid cursor: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor {
+            created_at: "2026-02-01T12:30:00.123456Z".parse().unwrap(),
+            id: Uuid::new_v4(),
+        };
+        let encoded = CursorEncoder::encode(&cursor);
+
+        assert!(!encoded.contains(['+', '/', '=']));
+        assert_eq!(CursorDecoder::decode(&encoded), Ok(cursor));
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert!(CursorDecoder::decode("not a cursor!").is_err());
+        // Valid base64, but not a cursor
+        assert!(CursorDecoder::decode(&URL_SAFE_NO_PAD.encode(r#"{"id": 1}"#)).is_err());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::error::ApiError;
+
+/// Largest width or height accepted for a photo, in pixels
+pub const MAX_PHOTO_DIMENSION: i32 = 20_000;
+
+/// Validate client-supplied photo metadata.
+///
+/// `file_size` must be positive, and `width`/`height` must be supplied together,
+/// each positive and no larger than `MAX_PHOTO_DIMENSION`.
+pub fn validate_photo_dimensions(
+    file_size: Option<i64>,
+    width: Option<i32>,
+    height: Option<i32>,
+) -> Result<(), ApiError> {
+    if let Some(size) = file_size {
+        if size <= 0 {
+            return Err(ApiError::bad_request("file_size must be positive"));
+        }
+    }
+
+    match (width, height) {
+        (None, None) => Ok(()),
+        (Some(width), Some(height)) => {
+            for (name, value) in [("width", width), ("height", height)] {
+                if value <= 0 || value > MAX_PHOTO_DIMENSION {
+                    return Err(ApiError::bad_request(format!(
+                        "{} must be between 1 and {}",
+                        name, MAX_PHOTO_DIMENSION
+                    )));
+                }
+            }
+            Ok(())
+        }
+        _ => Err(ApiError::bad_request(
+            "width and height must be provided together",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_photo_dimensions() {
+        assert!(validate_photo_dimensions(Some(1024), Some(800), Some(600)).is_ok());
+        assert!(validate_photo_dimensions(Some(1024), None, None).is_ok());
+        assert!(validate_photo_dimensions(None, None, None).is_ok());
+        assert!(validate_photo_dimensions(
+            None,
+            Some(MAX_PHOTO_DIMENSION),
+            Some(MAX_PHOTO_DIMENSION)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_invalid_file_size() {
+        assert!(validate_photo_dimensions(Some(0), None, None).is_err());
+        assert!(validate_photo_dimensions(Some(-1), None, None).is_err());
+    }
+
+    #[test]
+    fn test_invalid_dimensions() {
+        assert!(validate_photo_dimensions(None, Some(0), Some(600)).is_err());
+        assert!(validate_photo_dimensions(None, Some(800), Some(-600)).is_err());
+        assert!(validate_photo_dimensions(None, Some(MAX_PHOTO_DIMENSION + 1), Some(600)).is_err());
+    }
+
+    #[test]
+    fn test_width_and_height_required_together() {
+        let err = validate_photo_dimensions(None, Some(800), None).unwrap_err();
+        assert_eq!(
+            err,
+            ApiError::bad_request("width and height must be provided together")
+        );
+        assert!(validate_photo_dimensions(None, None, Some(600)).is_err());
+    }
+}
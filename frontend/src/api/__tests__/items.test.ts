@@ -134,11 +134,11 @@ describe('itemsApi', () => {
     it('should update an item', async () => {
       const id = 'item-1';
       const updateData = { name: 'Updated Item' };
-      mockedApiClient.put.mockResolvedValue({ data: mockItem });
+      mockedApiClient.patch.mockResolvedValue({ data: mockItem });
 
       const result = await itemsApi.update(id, updateData);
 
-      expect(mockedApiClient.put).toHaveBeenCalledWith(`/api/items/${id}`, updateData);
+      expect(mockedApiClient.patch).toHaveBeenCalledWith(`/api/items/${id}`, updateData);
       expect(result).toEqual(mockItem);
     });
   });
@@ -1,5 +1,9 @@
 import apiClient from './client';
-import type { AuditLogResponse } from '../types/generated';
+import type {
+  AuditLogResponse,
+  PurgeAuditLogsRequest,
+  PurgeAuditLogsResponse,
+} from '../types/generated';
 
 export interface AuditLogsQuery {
   entity_type?: string;
@@ -36,4 +40,13 @@ export const auditApi = {
     );
     return response.data;
   },
+
+  // Delete entries older than `older_than_days` (admin only)
+  purge: async (data: PurgeAuditLogsRequest): Promise<PurgeAuditLogsResponse> => {
+    const response = await apiClient.delete<PurgeAuditLogsResponse>(
+      '/api/admin/audit-logs/purge',
+      { data }
+    );
+    return response.data;
+  },
 };
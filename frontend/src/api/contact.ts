@@ -1,11 +1,19 @@
 import apiClient from './client';
 import type {
+  ContactStatus,
   ContactSubmissionResponse,
   CreateContactSubmissionRequest,
   PaginatedResponse,
   PaginationQuery,
+  UnreadCountResponse,
+  UpdateContactSubmissionRequest,
 } from '../types/generated';
 
+export interface ContactSubmissionListParams extends PaginationQuery {
+  status?: ContactStatus;
+  item_id?: string;
+}
+
 export const contactApi = {
   // Create a new contact submission (public endpoint)
   create: async (data: CreateContactSubmissionRequest): Promise<ContactSubmissionResponse> => {
@@ -13,12 +21,27 @@ export const contactApi = {
     return response.data;
   },
 
-  // List all contact submissions (protected endpoint)
-  getAll: async (params?: PaginationQuery): Promise<PaginatedResponse<ContactSubmissionResponse>> => {
+  // List contact submissions, optionally filtered by status or item (protected endpoint)
+  getAll: async (params?: ContactSubmissionListParams): Promise<PaginatedResponse<ContactSubmissionResponse>> => {
     const response = await apiClient.get<PaginatedResponse<ContactSubmissionResponse>>(
       '/api/contact',
       { params }
     );
     return response.data;
   },
+
+  // Update status and admin notes (protected endpoint)
+  update: async (
+    id: string,
+    data: UpdateContactSubmissionRequest
+  ): Promise<ContactSubmissionResponse> => {
+    const response = await apiClient.patch<ContactSubmissionResponse>(`/api/contact/${id}`, data);
+    return response.data;
+  },
+
+  // Number of unread submissions (protected endpoint)
+  getUnreadCount: async (): Promise<number> => {
+    const response = await apiClient.get<UnreadCountResponse>('/api/contact/unread-count');
+    return response.data.count;
+  },
 };
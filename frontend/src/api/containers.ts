@@ -1,8 +1,12 @@
 import apiClient from './client';
 import type {
+  AssignContainerLabelsResponse,
   ContainerResponse,
+  ContainerTreeNode,
+  FlatContainerTreeNode,
   CreateContainerRequest,
   UpdateContainerRequest,
+  ReorderContainersRequest,
   PaginatedResponse,
   PaginationQuery,
 } from '../types/generated';
@@ -54,13 +58,42 @@ export const containersApi = {
     id: string,
     data: UpdateContainerRequest
   ): Promise<ContainerResponse> => {
-    const response = await apiClient.put<ContainerResponse>(
+    const response = await apiClient.patch<ContainerResponse>(
       `/api/containers/${id}`,
       data
     );
     return response.data;
   },
 
+  // Get a container with everything nested inside it
+  getSubtree: async (id: string): Promise<ContainerTreeNode> => {
+    const response = await apiClient.get<ContainerTreeNode>(`/api/containers/${id}/subtree`);
+    return response.data;
+  },
+
+  // Get the same subtree as a depth-first list, e.g. for move target dropdowns
+  getSubtreeFlat: async (id: string): Promise<FlatContainerTreeNode[]> => {
+    const response = await apiClient.get<FlatContainerTreeNode[]>(
+      `/api/containers/${id}/subtree`,
+      { params: { flat: true } }
+    );
+    return response.data;
+  },
+
+  // Set the order of sibling containers; each gets its index as its position
+  reorder: async (data: ReorderContainersRequest): Promise<ContainerResponse[]> => {
+    const response = await apiClient.post<ContainerResponse[]>('/api/containers/reorder', data);
+    return response.data;
+  },
+
+  // Give each unlabeled item in the container the next free label
+  assignLabels: async (id: string): Promise<AssignContainerLabelsResponse> => {
+    const response = await apiClient.post<AssignContainerLabelsResponse>(
+      `/api/containers/${id}/assign-labels`
+    );
+    return response.data;
+  },
+
   // Delete a container
   delete: async (id: string): Promise<void> => {
     await apiClient.delete(`/api/containers/${id}`);
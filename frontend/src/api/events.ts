@@ -0,0 +1,14 @@
+import type { InventoryEvent } from '../types/generated';
+
+export const eventsApi = {
+  // Subscribe to inventory changes as they happen. Returns a function that closes
+  // the stream; EventSource reconnects on its own if the connection drops.
+  subscribe: (onEvent: (event: InventoryEvent) => void): (() => void) => {
+    const baseUrl = import.meta.env.VITE_API_URL || '';
+    const source = new EventSource(`${baseUrl}/api/events`, { withCredentials: true });
+    source.onmessage = (message) => {
+      onEvent(JSON.parse(message.data) as InventoryEvent);
+    };
+    return () => source.close();
+  },
+};
@@ -0,0 +1,16 @@
+import apiClient from './client';
+import type { ExportResponse } from '../types/generated';
+
+export const exportsApi = {
+  // Start exporting the whole inventory as a ZIP of JSON and photos
+  create: async (): Promise<ExportResponse> => {
+    const response = await apiClient.post<ExportResponse>('/api/export');
+    return response.data;
+  },
+
+  // Poll an export; download_url is set once it is complete
+  get: async (id: string): Promise<ExportResponse> => {
+    const response = await apiClient.get<ExportResponse>(`/api/export/${id}`);
+    return response.data;
+  },
+};
@@ -0,0 +1,30 @@
+import apiClient from './client';
+import type {
+  Household,
+  InviteResponse,
+  JoinHouseholdResponse,
+} from '../types/generated';
+
+export const householdsApi = {
+  // Get the household the current user works in
+  getCurrent: async (): Promise<Household> => {
+    const response = await apiClient.get<Household>('/api/households/current');
+    return response.data;
+  },
+
+  // Create a single-use invite link (owners only)
+  invite: async (householdId: string): Promise<InviteResponse> => {
+    const response = await apiClient.post<InviteResponse>(
+      `/api/households/${householdId}/invite`
+    );
+    return response.data;
+  },
+
+  // Join the household an invite token belongs to
+  join: async (token: string): Promise<JoinHouseholdResponse> => {
+    const response = await apiClient.get<JoinHouseholdResponse>('/api/households/join', {
+      params: { token },
+    });
+    return response.data;
+  },
+};
@@ -7,11 +7,23 @@ export { itemsApi } from './items';
 export { photosApi } from './photos';
 export { labelsApi } from './labels';
 export type { BatchWithLabels } from './labels';
+export { labelTemplatesApi } from './labelTemplates';
 export { tagsApi } from './tags';
 export { auditApi } from './audit';
 export type { AuditLogsQuery } from './audit';
 export { moveApi } from './move';
-export type { MoveShelfRequest, MoveContainerRequest, MoveItemRequest } from './move';
+export type {
+  MoveShelfRequest,
+  MoveContainerRequest,
+  MoveItemRequest,
+  BulkMoveItemsRequest,
+} from './move';
 export { itemImportDraftsApi } from './itemImportDrafts';
 export { contactApi } from './contact';
 export { usersApi } from './users';
+export { locationApi } from './location';
+export { webhooksApi } from './webhooks';
+export { exportsApi } from './exports';
+export { statsApi } from './stats';
+export { eventsApi } from './events';
+export { householdsApi } from './households';
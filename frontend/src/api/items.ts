@@ -1,21 +1,26 @@
 import apiClient from './client';
 import type {
+  Condition,
   ItemResponse,
   PublicItemResponse,
+  SimilarItemResponse,
+  BarcodeCheckResponse,
   CreateItemRequest,
   UpdateItemRequest,
   PaginatedResponse,
   PaginationQuery,
+  FileUploadResponse,
+  CompleteMultipartUploadRequest,
+  AbortMultipartUploadRequest,
+  BulkDeleteItemsResponse,
+  CsvImportResponse,
 } from '../types/generated';
 
-interface PresignedUploadUrl {
-  upload_url: string;
-  s3_key: string;
-}
-
 export const itemsApi = {
   // Get all items
-  getAll: async (params?: PaginationQuery): Promise<PaginatedResponse<ItemResponse>> => {
+  getAll: async (
+    params?: PaginationQuery & { condition?: Condition }
+  ): Promise<PaginatedResponse<ItemResponse>> => {
     const response = await apiClient.get<PaginatedResponse<ItemResponse>>('/api/items', { params });
     return response.data;
   },
@@ -52,9 +57,21 @@ export const itemsApi = {
     return response.data;
   },
 
-  // Create a new item
-  create: async (data: CreateItemRequest): Promise<ItemResponse> => {
-    const response = await apiClient.post<ItemResponse>('/api/items', data);
+  // Check whether a barcode is already assigned (no auth required)
+  checkBarcode: async (barcode: string): Promise<BarcodeCheckResponse> => {
+    const response = await apiClient.get<BarcodeCheckResponse>(
+      `/api/items/barcode/${encodeURIComponent(barcode)}/check`
+    );
+    return response.data;
+  },
+
+  // Create a new item. With check_duplicates, fails with 409 POSSIBLE_DUPLICATE (details.similar_items)
+  // when similarly named items share its location; force creates it anyway
+  create: async (
+    data: CreateItemRequest,
+    params?: { check_duplicates?: boolean; force?: boolean }
+  ): Promise<ItemResponse> => {
+    const response = await apiClient.post<ItemResponse>('/api/items', data, { params });
     return response.data;
   },
 
@@ -63,33 +80,105 @@ export const itemsApi = {
     id: string,
     data: UpdateItemRequest
   ): Promise<ItemResponse> => {
-    const response = await apiClient.put<ItemResponse>(`/api/items/${id}`, data);
+    const response = await apiClient.patch<ItemResponse>(`/api/items/${id}`, data);
     return response.data;
   },
 
-  // Delete an item
+  // Move an item to the trash
   delete: async (id: string): Promise<void> => {
     await apiClient.delete(`/api/items/${id}`);
   },
 
+  // Permanently delete several items; IDs that don't exist come back in not_found,
+  // and those the user may not delete in forbidden
+  bulkDelete: async (ids: string[]): Promise<BulkDeleteItemsResponse> => {
+    const response = await apiClient.post<BulkDeleteItemsResponse>('/api/items/bulk-delete', { ids });
+    return response.data;
+  },
+
+  // Get items whose warranty runs out soon, for those with reminders on
+  getExpiring: async (withinDays?: number): Promise<ItemResponse[]> => {
+    const response = await apiClient.get<ItemResponse[]>('/api/items/expiring', {
+      params: { within_days: withinDays },
+    });
+    return response.data;
+  },
+
+  // Get items in the trash
+  getTrash: async (params?: PaginationQuery): Promise<PaginatedResponse<ItemResponse>> => {
+    const response = await apiClient.get<PaginatedResponse<ItemResponse>>('/api/items/trash', { params });
+    return response.data;
+  },
+
+  // Restore an item from the trash
+  restore: async (id: string): Promise<ItemResponse> => {
+    const response = await apiClient.post<ItemResponse>(`/api/items/${id}/restore`);
+    return response.data;
+  },
+
+  // Permanently delete an item
+  purge: async (id: string): Promise<void> => {
+    await apiClient.delete(`/api/items/${id}/purge`);
+  },
+
+  // Import items from a CSV file. Responds 207 with per-row errors; with failFast the
+  // first bad row cancels the whole import.
+  importCsv: async (file: File, failFast = false): Promise<CsvImportResponse> => {
+    const form = new FormData();
+    form.append('file', file);
+    const response = await apiClient.post<CsvImportResponse>('/api/items/import-csv', form, {
+      params: failFast ? { fail_fast: true } : undefined,
+    });
+    return response.data;
+  },
+
   // Get public item view (no authentication required)
   getPublic: async (id: string): Promise<PublicItemResponse> => {
     const response = await apiClient.get<PublicItemResponse>(`/api/items/${id}/public`);
     return response.data;
   },
 
+  // Items sharing the most tags with this one, or stored next to it when untagged
+  getSimilar: async (id: string): Promise<SimilarItemResponse[]> => {
+    const response = await apiClient.get<SimilarItemResponse[]>(`/api/items/${id}/similar`);
+    return response.data;
+  },
+
   // Get presigned URL for file upload (manual or receipt)
   getFileUploadUrl: async (
     fileType: 'manual' | 'receipt',
-    contentType: string
-  ): Promise<PresignedUploadUrl> => {
-    const response = await apiClient.post<PresignedUploadUrl>(
+    contentType: string,
+    fileSizeBytes?: number
+  ): Promise<FileUploadResponse> => {
+    const response = await apiClient.post<FileUploadResponse>(
       `/api/items/file-upload-url`,
-      { file_type: fileType, content_type: contentType }
+      {
+        file_type: fileType,
+        content_type: contentType,
+        file_size_bytes: fileSizeBytes,
+      }
+    );
+    return response.data;
+  },
+
+  // Finish a multipart file upload
+  completeMultipartUpload: async (
+    request: CompleteMultipartUploadRequest
+  ): Promise<{ s3_key: string }> => {
+    const response = await apiClient.post<{ s3_key: string }>(
+      `/api/items/file-complete-multipart`,
+      request
     );
     return response.data;
   },
 
+  // Abandon a multipart file upload
+  abortMultipartUpload: async (
+    request: AbortMultipartUploadRequest
+  ): Promise<void> => {
+    await apiClient.post(`/api/items/file-abort-multipart`, request);
+  },
+
   // Get presigned URL for file download
   getFileDownloadUrl: async (s3Key: string): Promise<string> => {
     const response = await apiClient.post<{ download_url: string }>(
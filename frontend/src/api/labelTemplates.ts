@@ -0,0 +1,45 @@
+import apiClient from './client';
+import type {
+  CustomLabelTemplate,
+  CreateLabelTemplateRequest,
+  UpdateLabelTemplateRequest,
+  PaginatedResponse,
+  PaginationQuery,
+} from '../types/generated';
+
+export const labelTemplatesApi = {
+  // List all custom label templates
+  list: async (params?: PaginationQuery): Promise<PaginatedResponse<CustomLabelTemplate>> => {
+    const response = await apiClient.get<PaginatedResponse<CustomLabelTemplate>>(
+      '/api/label-templates',
+      { params }
+    );
+    return response.data;
+  },
+
+  // Get a single custom label template by ID
+  getById: async (id: string): Promise<CustomLabelTemplate> => {
+    const response = await apiClient.get<CustomLabelTemplate>(`/api/label-templates/${id}`);
+    return response.data;
+  },
+
+  // Create a custom label template
+  create: async (data: CreateLabelTemplateRequest): Promise<CustomLabelTemplate> => {
+    const response = await apiClient.post<CustomLabelTemplate>('/api/label-templates', data);
+    return response.data;
+  },
+
+  // Update a custom label template
+  update: async (id: string, data: UpdateLabelTemplateRequest): Promise<CustomLabelTemplate> => {
+    const response = await apiClient.patch<CustomLabelTemplate>(
+      `/api/label-templates/${id}`,
+      data
+    );
+    return response.data;
+  },
+
+  // Delete a custom label template
+  delete: async (id: string): Promise<void> => {
+    await apiClient.delete(`/api/label-templates/${id}`);
+  },
+};
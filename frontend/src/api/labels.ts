@@ -1,16 +1,22 @@
 import apiClient from './client';
 import type {
   LabelResponse,
+  LabelEntityResponse,
+  LabelBatchSummary,
   GenerateLabelsRequest,
   GenerateLabelsResponse,
   AssignLabelRequest,
   PaginatedResponse,
   PaginationQuery,
+  PrintLabelsWithNamesRequest,
 } from '../types/generated';
 
 export interface BatchWithLabels {
   batch_id: string;
   labels: LabelResponse[];
+  template: string;
+  purpose?: string;
+  created_by?: string;
   created_at: string;
 }
 
@@ -21,15 +27,16 @@ export const labelsApi = {
     return response.data;
   },
 
+  // Every batch with its label count and number range, newest first
+  listBatchSummaries: async (): Promise<LabelBatchSummary[]> => {
+    const response = await apiClient.get<LabelBatchSummary[]>('/api/labels/batches');
+    return response.data;
+  },
+
   // Get a single batch by ID
   getBatchById: async (batchId: string): Promise<BatchWithLabels> => {
-    // Find the batch from the list (we could add a dedicated endpoint later)
-    const batchesResponse = await labelsApi.listBatches({ limit: 1000 });
-    const batch = batchesResponse.data.find((b) => b.batch_id === batchId);
-    if (!batch) {
-      throw new Error(`Batch ${batchId} not found`);
-    }
-    return batch;
+    const response = await apiClient.get<BatchWithLabels>(`/api/labels/batch/${batchId}`);
+    return response.data;
   },
 
   // Generate a batch of labels
@@ -49,6 +56,12 @@ export const labelsApi = {
     return response.data;
   },
 
+  // Resolve a scanned label to the entity it's assigned to (works without logging in)
+  getEntity: async (id: string): Promise<LabelEntityResponse> => {
+    const response = await apiClient.get<LabelEntityResponse>(`/api/labels/${id}/entity`);
+    return response.data;
+  },
+
   // Assign a label to an entity
   assign: async (
     id: string,
@@ -61,6 +74,18 @@ export const labelsApi = {
     return response.data;
   },
 
+  // Move a label to a different entity. Resolves to null if it was already there
+  reassign: async (
+    id: string,
+    data: AssignLabelRequest
+  ): Promise<LabelResponse | null> => {
+    const response = await apiClient.put<LabelResponse | ''>(
+      `/api/labels/${id}/assign`,
+      data
+    );
+    return response.status === 204 ? null : (response.data as LabelResponse);
+  },
+
   // Download PDF for a batch of labels
   downloadPdf: async (batchId: string, template?: string): Promise<Blob> => {
     const params = template ? { template } : {};
@@ -70,4 +95,12 @@ export const labelsApi = {
     });
     return response.data;
   },
+
+  // Download PDF for a batch with each label's entity name and location printed on it
+  downloadPdfWithNames: async (request: PrintLabelsWithNamesRequest): Promise<Blob> => {
+    const response = await apiClient.post('/api/labels/print-with-names', request, {
+      responseType: 'blob',
+    });
+    return response.data;
+  },
 };
@@ -0,0 +1,12 @@
+import apiClient from './client';
+import type { LocationPathResponse, SearchResultKind } from '../types/generated';
+
+export const locationApi = {
+  // Breadcrumb from the room down to the entity
+  getPath: async (kind: SearchResultKind, id: string): Promise<LocationPathResponse> => {
+    const response = await apiClient.get<LocationPathResponse>(
+      `/api/location-path/${kind}/${id}`
+    );
+    return response.data;
+  },
+};
@@ -18,6 +18,12 @@ export interface MoveItemRequest {
   target_container_id?: string;
 }
 
+export interface BulkMoveItemsRequest {
+  item_ids: string[];
+  target_shelf_id?: string;
+  target_container_id?: string;
+}
+
 export interface MoveResponse {
   message: string;
 }
@@ -61,4 +67,10 @@ export const moveApi = {
     );
     return response.data;
   },
+
+  // Move up to 100 items to one location; nothing moves if any item is missing
+  bulkMoveItems: async (data: BulkMoveItemsRequest): Promise<MoveResponse> => {
+    const response = await apiClient.post<MoveResponse>('/api/items/bulk-move', data);
+    return response.data;
+  },
 };
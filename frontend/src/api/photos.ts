@@ -3,6 +3,8 @@ import type {
   PhotoResponse,
   CreatePhotoRequest,
   PresignedUploadUrl,
+  SimilarPhotoResponse,
+  SimilarPhotosQuery,
 } from '../types/generated';
 
 export const photosApi = {
@@ -42,6 +44,26 @@ export const photosApi = {
     return response.data;
   },
 
+  // Photos that look like this one, closest first
+  getSimilar: async (id: string, params?: SimilarPhotosQuery): Promise<SimilarPhotoResponse[]> => {
+    const response = await apiClient.get<SimilarPhotoResponse[]>(`/api/photos/${id}/similar`, {
+      params,
+    });
+    return response.data;
+  },
+
+  // Build (or rebuild) a photo's thumbnail on the server
+  generateThumbnail: async (id: string): Promise<PhotoResponse> => {
+    const response = await apiClient.post<PhotoResponse>(`/api/photos/${id}/generate-thumbnail`);
+    return response.data;
+  },
+
+  // Read (or re-read) a photo's EXIF metadata on the server
+  extractExif: async (id: string): Promise<PhotoResponse> => {
+    const response = await apiClient.post<PhotoResponse>(`/api/photos/${id}/extract-exif`);
+    return response.data;
+  },
+
   // Delete a photo
   delete: async (id: string): Promise<void> => {
     await apiClient.delete(`/api/photos/${id}`);
@@ -1,8 +1,10 @@
 import apiClient from './client';
 import type {
+  CascadeDeleteResponse,
   RoomResponse,
   CreateRoomRequest,
   UpdateRoomRequest,
+  SetFloorPlanRequest,
   PaginatedResponse,
   PaginationQuery,
 } from '../types/generated';
@@ -33,15 +35,39 @@ export const roomsApi = {
     id: string,
     data: UpdateRoomRequest
   ): Promise<RoomResponse> => {
-    const response = await apiClient.put<RoomResponse>(
+    const response = await apiClient.patch<RoomResponse>(
       `/api/rooms/${id}`,
       data
     );
     return response.data;
   },
 
-  // Delete a room
-  delete: async (id: string): Promise<void> => {
-    await apiClient.delete(`/api/rooms/${id}`);
+  // Delete a room; with cascade, everything inside it too
+  delete: async (id: string, cascade = false): Promise<CascadeDeleteResponse> => {
+    const response = await apiClient.delete<CascadeDeleteResponse>(`/api/rooms/${id}`, {
+      params: cascade ? { cascade: true } : undefined,
+    });
+    return response.data;
+  },
+
+  // Upload a floor plan image, replacing any previous one
+  uploadFloorPlan: async (id: string, file: File): Promise<RoomResponse> => {
+    const form = new FormData();
+    form.append('file', file);
+    const response = await apiClient.post<RoomResponse>(`/api/rooms/${id}/floor-plan`, form);
+    return response.data;
+  },
+
+  // Use one of the room's photos as its floor plan
+  setFloorPlanPhoto: async (id: string, photoId: string): Promise<RoomResponse> => {
+    const data: SetFloorPlanRequest = { photo_id: photoId };
+    const response = await apiClient.post<RoomResponse>(`/api/rooms/${id}/floor-plan`, data);
+    return response.data;
+  },
+
+  // Remove the floor plan, deleting its image
+  removeFloorPlan: async (id: string): Promise<RoomResponse> => {
+    const response = await apiClient.delete<RoomResponse>(`/api/rooms/${id}/floor-plan`);
+    return response.data;
   },
 };
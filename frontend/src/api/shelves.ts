@@ -1,8 +1,10 @@
 import apiClient from './client';
 import type {
+  CascadeDeleteResponse,
   ShelfResponse,
   CreateShelfRequest,
   UpdateShelfRequest,
+  ReorderShelvesRequest,
   PaginatedResponse,
   PaginationQuery,
 } from '../types/generated';
@@ -14,8 +16,11 @@ export const shelvesApi = {
     return response.data;
   },
 
-  // Get shelves by unit
-  getByUnit: async (unitId: string, params?: PaginationQuery): Promise<PaginatedResponse<ShelfResponse>> => {
+  // Get shelves by unit; include_counts adds each shelf's item and container counts
+  getByUnit: async (
+    unitId: string,
+    params?: PaginationQuery & { include_counts?: boolean }
+  ): Promise<PaginatedResponse<ShelfResponse>> => {
     const response = await apiClient.get<PaginatedResponse<ShelfResponse>>(
       `/api/units/${unitId}/shelves`,
       { params }
@@ -23,6 +28,15 @@ export const shelvesApi = {
     return response.data;
   },
 
+  // Renumber every shelf of a unit in the given order
+  reorderInUnit: async (unitId: string, data: ReorderShelvesRequest): Promise<ShelfResponse[]> => {
+    const response = await apiClient.post<ShelfResponse[]>(
+      `/api/units/${unitId}/shelves/reorder`,
+      data
+    );
+    return response.data;
+  },
+
   // Get a single shelf by ID
   getById: async (id: string): Promise<ShelfResponse> => {
     const response = await apiClient.get<ShelfResponse>(`/api/shelves/${id}`);
@@ -40,15 +54,18 @@ export const shelvesApi = {
     id: string,
     data: UpdateShelfRequest
   ): Promise<ShelfResponse> => {
-    const response = await apiClient.put<ShelfResponse>(
+    const response = await apiClient.patch<ShelfResponse>(
       `/api/shelves/${id}`,
       data
     );
     return response.data;
   },
 
-  // Delete a shelf
-  delete: async (id: string): Promise<void> => {
-    await apiClient.delete(`/api/shelves/${id}`);
+  // Delete a shelf; with cascade, everything inside it too
+  delete: async (id: string, cascade = false): Promise<CascadeDeleteResponse> => {
+    const response = await apiClient.delete<CascadeDeleteResponse>(`/api/shelves/${id}`, {
+      params: cascade ? { cascade: true } : undefined,
+    });
+    return response.data;
   },
 };
@@ -1,6 +1,8 @@
 import apiClient from './client';
 import type {
+  CascadeDeleteResponse,
   ShelvingUnitResponse,
+  ShelvingUnitCapacity,
   CreateShelvingUnitRequest,
   UpdateShelvingUnitRequest,
   PaginatedResponse,
@@ -32,6 +34,14 @@ export const shelvingUnitsApi = {
     return response.data;
   },
 
+  // Get how many shelves a unit holds against its limit
+  getCapacity: async (id: string): Promise<ShelvingUnitCapacity> => {
+    const response = await apiClient.get<ShelvingUnitCapacity>(
+      `/api/units/${id}/capacity`
+    );
+    return response.data;
+  },
+
   // Create a new shelving unit
   create: async (
     data: CreateShelvingUnitRequest
@@ -48,15 +58,18 @@ export const shelvingUnitsApi = {
     id: string,
     data: UpdateShelvingUnitRequest
   ): Promise<ShelvingUnitResponse> => {
-    const response = await apiClient.put<ShelvingUnitResponse>(
+    const response = await apiClient.patch<ShelvingUnitResponse>(
       `/api/units/${id}`,
       data
     );
     return response.data;
   },
 
-  // Delete a shelving unit
-  delete: async (id: string): Promise<void> => {
-    await apiClient.delete(`/api/units/${id}`);
+  // Delete a shelving unit; with cascade, everything inside it too
+  delete: async (id: string, cascade = false): Promise<CascadeDeleteResponse> => {
+    const response = await apiClient.delete<CascadeDeleteResponse>(`/api/units/${id}`, {
+      params: cascade ? { cascade: true } : undefined,
+    });
+    return response.data;
   },
 };
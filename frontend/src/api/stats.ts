@@ -0,0 +1,24 @@
+import apiClient from './client';
+import type { InventoryStats, ValuationResponse } from '../types/generated';
+
+export const statsApi = {
+  // Get counts for the whole inventory (cached on the server for a minute)
+  getOverview: async (): Promise<InventoryStats> => {
+    const response = await apiClient.get<InventoryStats>('/api/stats/overview');
+    return response.data;
+  },
+
+  // Get counts for one room and everything in it
+  getRoomStats: async (roomId: string): Promise<InventoryStats> => {
+    const response = await apiClient.get<InventoryStats>(`/api/rooms/${roomId}/stats`);
+    return response.data;
+  },
+
+  // Get the total purchase value of the inventory, by room, in one currency (default USD)
+  getValuation: async (currency?: string): Promise<ValuationResponse> => {
+    const response = await apiClient.get<ValuationResponse>('/api/stats/valuation', {
+      params: currency ? { currency } : undefined,
+    });
+    return response.data;
+  },
+};
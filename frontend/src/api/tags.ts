@@ -2,20 +2,33 @@ import apiClient from './client';
 import type {
   TagResponse,
   CreateTagRequest,
+  BulkCreateTagsRequest,
+  BulkCreateTagsResponse,
   UpdateTagRequest,
+  TagDuplicateGroup,
+  TagMergeResponse,
   AssignTagsRequest,
   BulkAssignTagsRequest,
   PaginatedResponse,
-  PaginationQuery,
+  TagListQuery,
+  TagSuggestion,
 } from '../types/generated';
 
 export const tagsApi = {
-  // List all tags
-  list: async (params?: PaginationQuery): Promise<PaginatedResponse<TagResponse>> => {
+  // List all tags, optionally filtered by name
+  list: async (params?: TagListQuery): Promise<PaginatedResponse<TagResponse>> => {
     const response = await apiClient.get<PaginatedResponse<TagResponse>>('/api/tags', { params });
     return response.data;
   },
 
+  // Tag name suggestions for typeahead
+  autocomplete: async (q: string, limit?: number): Promise<TagSuggestion[]> => {
+    const response = await apiClient.get<TagSuggestion[]>('/api/tags/autocomplete', {
+      params: { q, limit },
+    });
+    return response.data;
+  },
+
   // Get a single tag by ID
   getById: async (id: string): Promise<TagResponse> => {
     const response = await apiClient.get<TagResponse>(`/api/tags/${id}`);
@@ -28,6 +41,12 @@ export const tagsApi = {
     return response.data;
   },
 
+  // Create up to 50 tags at once; names that already exist come back in already_existed
+  bulkCreate: async (data: BulkCreateTagsRequest): Promise<BulkCreateTagsResponse> => {
+    const response = await apiClient.post<BulkCreateTagsResponse>('/api/tags/bulk', data);
+    return response.data;
+  },
+
   // Update a tag
   update: async (id: string, data: UpdateTagRequest): Promise<TagResponse> => {
     const response = await apiClient.put<TagResponse>(`/api/tags/${id}`, data);
@@ -39,6 +58,18 @@ export const tagsApi = {
     await apiClient.delete(`/api/tags/${id}`);
   },
 
+  // Move every use of a tag to another tag and delete it
+  mergeInto: async (id: string, targetId: string): Promise<TagMergeResponse> => {
+    const response = await apiClient.post<TagMergeResponse>(`/api/tags/${id}/merge-into/${targetId}`);
+    return response.data;
+  },
+
+  // Groups of tags whose names only differ in case, spacing or punctuation
+  getDuplicates: async (): Promise<TagDuplicateGroup[]> => {
+    const response = await apiClient.get<TagDuplicateGroup[]>('/api/tags/duplicates');
+    return response.data;
+  },
+
   // Get tags for a specific entity
   getEntityTags: async (entityType: string, entityId: string): Promise<TagResponse[]> => {
     const response = await apiClient.get<TagResponse[]>(
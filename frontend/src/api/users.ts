@@ -1,5 +1,13 @@
 import apiClient from './client';
-import type { User } from '../types/generated';
+import type {
+  ItemResponse,
+  PaginatedResponse,
+  PaginationQuery,
+  PublicUserResponse,
+  UpdateProfileRequest,
+  User,
+  UserStats,
+} from '../types/generated';
 
 export const usersApi = {
   // Get all users (with optional search)
@@ -9,4 +17,31 @@ export const usersApi = {
     });
     return response.data;
   },
+
+  // Get items belonging to a user (yourself, unless you are the admin)
+  getItems: async (userId: string, params?: PaginationQuery): Promise<PaginatedResponse<ItemResponse>> => {
+    const response = await apiClient.get<PaginatedResponse<ItemResponse>>(
+      `/api/users/${userId}/items`,
+      { params }
+    );
+    return response.data;
+  },
+
+  // Get item, room and container counts for a user
+  getStats: async (userId: string): Promise<UserStats> => {
+    const response = await apiClient.get<UserStats>(`/api/users/${userId}/stats`);
+    return response.data;
+  },
+
+  // Get a user's public profile (no authentication required; 404 unless public)
+  getPublic: async (userId: string): Promise<PublicUserResponse> => {
+    const response = await apiClient.get<PublicUserResponse>(`/api/users/${userId}/public`);
+    return response.data;
+  },
+
+  // Set your public display name and whether your profile is public
+  updateProfile: async (data: UpdateProfileRequest): Promise<User> => {
+    const response = await apiClient.put<User>('/api/users/me/profile', data);
+    return response.data;
+  },
 };
@@ -0,0 +1,54 @@
+import apiClient from './client';
+import type {
+  WebhookResponse,
+  CreateWebhookRequest,
+  UpdateWebhookRequest,
+  WebhookDelivery,
+  PaginatedResponse,
+  PaginationQuery,
+} from '../types/generated';
+
+export const webhooksApi = {
+  // List all webhooks
+  list: async (params?: PaginationQuery): Promise<PaginatedResponse<WebhookResponse>> => {
+    const response = await apiClient.get<PaginatedResponse<WebhookResponse>>('/api/webhooks', {
+      params,
+    });
+    return response.data;
+  },
+
+  // Get a single webhook by ID
+  getById: async (id: string): Promise<WebhookResponse> => {
+    const response = await apiClient.get<WebhookResponse>(`/api/webhooks/${id}`);
+    return response.data;
+  },
+
+  // Create a new webhook
+  create: async (data: CreateWebhookRequest): Promise<WebhookResponse> => {
+    const response = await apiClient.post<WebhookResponse>('/api/webhooks', data);
+    return response.data;
+  },
+
+  // Update a webhook
+  update: async (id: string, data: UpdateWebhookRequest): Promise<WebhookResponse> => {
+    const response = await apiClient.put<WebhookResponse>(`/api/webhooks/${id}`, data);
+    return response.data;
+  },
+
+  // Delete a webhook
+  delete: async (id: string): Promise<void> => {
+    await apiClient.delete(`/api/webhooks/${id}`);
+  },
+
+  // Delivery attempts for a webhook, newest first
+  getDeliveries: async (
+    id: string,
+    params?: PaginationQuery
+  ): Promise<PaginatedResponse<WebhookDelivery>> => {
+    const response = await apiClient.get<PaginatedResponse<WebhookDelivery>>(
+      `/api/webhooks/${id}/deliveries`,
+      { params }
+    );
+    return response.data;
+  },
+};
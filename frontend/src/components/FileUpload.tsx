@@ -1,4 +1,5 @@
 import { useState, useRef } from 'react';
+import type { FileUploadResponse } from '../types/generated';
 
 interface FileUploadProps {
   accept: string; // e.g., "application/pdf" or "application/pdf,image/*"
@@ -8,7 +9,7 @@ interface FileUploadProps {
   onUploadComplete: (s3Key: string) => void;
   onClear?: () => void;
   onError?: (error: Error) => void;
-  getUploadUrl: (contentType: string) => Promise<{ upload_url: string; s3_key: string }>;
+  getUploadUrl: (contentType: string) => Promise<FileUploadResponse>;
 }
 
 export default function FileUpload({
@@ -61,8 +62,16 @@ export default function FileUpload({
     setFileName(file.name);
 
     try {
-      // Step 1: Get presigned upload URL
-      const { upload_url, s3_key } = await getUploadUrl(file.type);
+      // Step 1: Get presigned upload (a POST whose policy limits the file size)
+      const { upload_url, fields, s3_key, max_bytes } = await getUploadUrl(file.type);
+      if (file.size > max_bytes) {
+        throw new Error(`File size must be less than ${Math.floor(max_bytes / (1024 * 1024))}MB`);
+      }
+
+      const formData = new FormData();
+      Object.entries(fields).forEach(([name, value]) => formData.append(name, value));
+      // S3 ignores form fields after the file
+      formData.append('file', file);
 
       // Step 2: Upload file directly to S3
       const xhr = new XMLHttpRequest();
@@ -93,9 +102,8 @@ export default function FileUpload({
           reject(new Error('Upload was aborted'));
         });
 
-        xhr.open('PUT', upload_url);
-        xhr.setRequestHeader('Content-Type', file.type);
-        xhr.send(file);
+        xhr.open('POST', upload_url);
+        xhr.send(formData);
       });
 
       // Step 3: Notify parent component
@@ -7,11 +7,11 @@ import type {
   AssignTagsRequest,
   BulkAssignTagsRequest,
   PaginatedResponse,
-  PaginationQuery,
+  TagListQuery,
 } from '../types/generated';
 
 // List all tags
-export const useTags = (params?: PaginationQuery) => {
+export const useTags = (params?: TagListQuery) => {
   return useQuery<PaginatedResponse<TagResponse>, Error>({
     queryKey: ['tags', params],
     queryFn: () => tagsApi.list(params),
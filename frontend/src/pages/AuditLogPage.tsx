@@ -112,6 +112,8 @@ export default function AuditLogPage() {
             <option value="UPDATE">Update</option>
             <option value="DELETE">Delete</option>
             <option value="MOVE">Move</option>
+            <option value="SOFT_DELETE">Move to trash</option>
+            <option value="RESTORE">Restore</option>
           </select>
         </div>
 
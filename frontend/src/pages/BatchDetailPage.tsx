@@ -33,10 +33,8 @@ export default function BatchDetailPage() {
     if (!batchId) return;
 
     try {
-      const blob = await downloadPdf.mutateAsync({
-        batchId,
-        template: 'avery_18660',
-      });
+      // Reprint on the template the batch was generated for
+      const blob = await downloadPdf.mutateAsync({ batchId });
 
       // Create object URL and open in browser
       const url = window.URL.createObjectURL(blob);
@@ -13,7 +13,8 @@ export interface ItemImportDraftItem {
 }
 
 export interface CreateItemImportDraftRequest {
-	container_id: string;
+	container_id?: string;
+	shelf_id?: string;
 	items: ItemImportDraftItem[];
 	source_photo_ids: string[];
 }
@@ -54,8 +55,25 @@ export interface ItemResponse {
 	product_link?: string;
 	belongs_to_user_id?: string;
 	acquired_date?: NaiveDate;
+	quantity: number;
+	min_quantity?: number;
+	purchase_price?: string;
+	/** ISO 4217 code of `purchase_price` */
+	currency?: string;
+	condition?: Condition;
+	warranty_expires_on?: NaiveDate;
+	/** Whether the item shows up in `GET /api/items/expiring` */
+	reminder_enabled: boolean;
 	created_at: Date;
 	updated_at: Date;
+	/** Set while the item is in the trash */
+	deleted_at?: Date;
+	/** Only with `?include_photos=true` on list endpoints */
+	photo_count?: number;
+	/** Presigned URL of the earliest photo, only with `?include_photos=true` */
+	primary_photo_url?: string;
+	/** Only with `?include_tags=true`; empty rather than absent when untagged */
+	tags?: TagResponse[];
 }
 
 export interface CommitItemImportDraftResponse {
@@ -66,7 +84,9 @@ export interface CommitItemImportDraftResponse {
 export interface AnalyzePhotoRequest {
 	container_id?: string;
 	shelf_id?: string;
+	/** 1 to 5 photos, analyzed together */
 	photo_ids: string[];
+	/** Context for the vision service, e.g. "camping gear"; at most 500 characters */
 	hint?: string;
 }
 
@@ -112,6 +132,7 @@ export interface CreateShelfRequest {
 	position?: number;
 }
 
+/** Body of `PATCH /api/shelves/:id`. Omitted fields are left unchanged. */
 export interface UpdateShelfRequest {
 	name?: string;
 	description?: string;
@@ -128,6 +149,17 @@ export interface ShelfResponse {
 	label_id?: string;
 	created_at: Date;
 	updated_at: Date;
+	/** Only with `?include_photos=true` on list endpoints */
+	photo_count?: number;
+	/** Presigned URL of the earliest photo, only with `?include_photos=true` */
+	primary_photo_url?: string;
+	/**
+	 * Items directly on the shelf (not in its containers), only with
+	 * `?include_counts=true`
+	 */
+	item_count?: number;
+	/** Containers directly on the shelf, only with `?include_counts=true` */
+	container_count?: number;
 }
 
 export interface User {
@@ -136,6 +168,8 @@ export interface User {
 	name: string;
 	google_id: string;
 	public_display_name?: string;
+	/** Whether `GET /api/users/{id}/public` shows this user's profile */
+	is_public: boolean;
 	created_at: Date;
 	updated_at: Date;
 }
@@ -156,6 +190,12 @@ export interface ContactSubmission {
 	ip_address?: string;
 	user_agent?: string;
 	created_at: Date;
+	status: string;
+	admin_notes?: string;
+	/** When the submission was first marked replied */
+	replied_at?: Date;
+	/** When the admin last changed its status or notes */
+	updated_at: Date;
 }
 
 export interface CreateContactSubmissionRequest {
@@ -175,6 +215,25 @@ export interface ContactSubmissionResponse {
 	message: string;
 	item_id?: string;
 	created_at: Date;
+	status: ContactStatus;
+	admin_notes?: string;
+	/** When the submission was first marked replied */
+	replied_at?: Date;
+	/** When the admin last changed its status or notes */
+	updated_at: Date;
+}
+
+/**
+ * Body of `PATCH /api/contact/:id`. Omitted fields are left unchanged;
+ * `admin_notes: null` clears the notes.
+ */
+export interface UpdateContactSubmissionRequest {
+	status?: ContactStatus;
+	admin_notes?: string | null;
+}
+
+export interface UnreadCountResponse {
+	count: number;
 }
 
 export interface Tag {
@@ -188,12 +247,36 @@ export interface EntityTag {
 	entity_id: string;
 	tag_id: string;
 	created_at: Date;
+	updated_at: Date;
 }
 
 export interface TagResponse {
 	id: string;
 	name: string;
 	created_at: Date;
+	/** Number of entities with the tag; only with `include_usage_count=true` */
+	usage_count?: number;
+}
+
+export interface TagListQuery {
+	limit?: number;
+	offset?: number;
+	/** Case-insensitive substring of the tag name */
+	q?: string;
+	/** Count each tag's entities, which costs a subquery per tag */
+	include_usage_count?: boolean;
+}
+
+export interface TagAutocompleteQuery {
+	q: string;
+	/** Defaults to 10, at most 50 */
+	limit?: number;
+}
+
+/** A typeahead suggestion */
+export interface TagSuggestion {
+	id: string;
+	name: string;
 }
 
 export interface CreateTagRequest {
@@ -230,9 +313,20 @@ export interface Item {
 	product_link?: string;
 	belongs_to_user_id?: string;
 	acquired_date?: NaiveDate;
+	quantity: number;
+	min_quantity?: number;
+	purchase_price?: string;
+	/** ISO 4217 code of `purchase_price` */
+	currency?: string;
+	condition?: Condition;
+	warranty_expires_on?: NaiveDate;
+	/** Whether the item shows up in `GET /api/items/expiring` */
+	reminder_enabled: boolean;
 	created_at: Date;
 	updated_at: Date;
 	created_by: string;
+	/** Set while the item is in the trash */
+	deleted_at?: Date;
 }
 
 export interface CreateItemRequest {
@@ -247,20 +341,45 @@ export interface CreateItemRequest {
 	product_link?: string;
 	belongs_to_user_id?: string;
 	acquired_date?: NaiveDate;
+	/** Defaults to 1 */
+	quantity?: number;
+	/** Items at or below this quantity are reported as low stock */
+	min_quantity?: number;
+	/** Sent as a decimal string, e.g. "19.99" */
+	purchase_price?: string;
+	/** ISO 4217 code; defaults to USD when a purchase price is given */
+	currency?: string;
+	/** One of `new`, `like_new`, `good`, `fair`, `poor` */
+	condition?: string;
+	warranty_expires_on?: NaiveDate;
+	/** Defaults to false */
+	reminder_enabled?: boolean;
 }
 
+/**
+ * Body of `PATCH /api/items/:id`. Omitted fields are left unchanged; optional
+ * fields sent as explicit `null` are cleared.
+ */
 export interface UpdateItemRequest {
 	name?: string;
-	description?: string;
+	description?: string | null;
 	shelf_id?: string;
 	container_id?: string;
-	barcode?: string;
-	barcode_type?: string;
-	product_manual_s3_key?: string;
-	receipt_s3_key?: string;
-	product_link?: string;
-	belongs_to_user_id?: string;
-	acquired_date?: NaiveDate;
+	barcode?: string | null;
+	barcode_type?: string | null;
+	product_manual_s3_key?: string | null;
+	receipt_s3_key?: string | null;
+	product_link?: string | null;
+	belongs_to_user_id?: string | null;
+	acquired_date?: NaiveDate | null;
+	quantity?: number;
+	min_quantity?: number | null;
+	purchase_price?: string | null;
+	currency?: string | null;
+	condition?: Condition | null;
+	/** Can't be set to a date in the past */
+	warranty_expires_on?: NaiveDate | null;
+	reminder_enabled?: boolean;
 }
 
 export interface PublicItemResponse {
@@ -270,6 +389,22 @@ export interface PublicItemResponse {
 	product_link?: string;
 }
 
+/** Whether a barcode is taken, for checking a scan before creating an item */
+export interface BarcodeCheckResponse {
+	exists: boolean;
+	/** The item with the barcode, if it is in the caller's household */
+	item_id?: string;
+}
+
+export interface AdjustQuantityRequest {
+	/** Amount to add; negative to take items out */
+	delta: number;
+}
+
+export interface TransferItemRequest {
+	new_owner_id: string;
+}
+
 export interface BulkCreateItemsRequest {
 	items: CreateItemRequest[];
 }
@@ -286,6 +421,8 @@ export interface Room {
 	created_at: Date;
 	updated_at: Date;
 	created_by: string;
+	/** Photo (with `entity_type` [`FLOOR_PLAN_ENTITY_TYPE`]) showing the room's layout */
+	floor_plan_photo_id?: string;
 }
 
 export interface CreateRoomRequest {
@@ -293,6 +430,7 @@ export interface CreateRoomRequest {
 	description?: string;
 }
 
+/** Body of `PATCH /api/rooms/:id`. Omitted fields are left unchanged. */
 export interface UpdateRoomRequest {
 	name?: string;
 	description?: string;
@@ -305,12 +443,28 @@ export interface RoomResponse {
 	label_id?: string;
 	created_at: Date;
 	updated_at: Date;
+	floor_plan_photo_id?: string;
+	/** Presigned download URL of the floor plan. Only set on single-room responses. */
+	floor_plan_url?: string;
 }
 
 export interface PaginationQuery {
 	limit?: number;
 	offset?: number;
 	search?: string;
+	/**
+	 * `next_cursor` from the previous page. Takes precedence over `offset` on
+	 * endpoints that support cursors.
+	 */
+	cursor?: string;
+	/** `GET /api/items` only: items acquired on or after this date */
+	acquired_after?: string;
+	/** `GET /api/items` only: items acquired on or before this date */
+	acquired_before?: string;
+	/** `GET /api/items` only: items created at or after this time (RFC 3339) */
+	created_after?: string;
+	/** `GET /api/items` only: items created at or before this time (RFC 3339) */
+	created_before?: string;
 }
 
 export interface PaginatedResponse<T> {
@@ -318,6 +472,11 @@ export interface PaginatedResponse<T> {
 	total: number;
 	limit: number;
 	offset: number;
+	/**
+	 * Pass as `cursor` to fetch the next page. Absent on the last page and on
+	 * endpoints that only support offsets.
+	 */
+	next_cursor?: string;
 }
 
 export interface ShelvingUnit {
@@ -329,18 +488,28 @@ export interface ShelvingUnit {
 	created_at: Date;
 	updated_at: Date;
 	created_by: string;
+	max_shelves?: number;
+	max_weight_kg?: number;
 }
 
 export interface CreateShelvingUnitRequest {
 	room_id: string;
 	name: string;
 	description?: string;
+	/** Most shelves the unit holds; no limit when omitted */
+	max_shelves?: number;
+	max_weight_kg?: number;
 }
 
+/** Body of `PATCH /api/units/:id`. Omitted fields are left unchanged. */
 export interface UpdateShelvingUnitRequest {
 	name?: string;
 	description?: string;
 	room_id?: string;
+	/** `null` removes the limit */
+	max_shelves?: number | null;
+	/** `null` clears the weight */
+	max_weight_kg?: number | null;
 }
 
 export interface ShelvingUnitResponse {
@@ -349,10 +518,21 @@ export interface ShelvingUnitResponse {
 	name: string;
 	description?: string;
 	label_id?: string;
+	max_shelves?: number;
+	max_weight_kg?: number;
 	created_at: Date;
 	updated_at: Date;
 }
 
+/** Response of `GET /api/units/:id/capacity` */
+export interface ShelvingUnitCapacity {
+	/** `None` when the unit has no limit */
+	max_shelves?: number;
+	current_shelves: number;
+	/** Share of `max_shelves` in use, `None` without a limit */
+	utilization_pct?: number;
+}
+
 export interface Label {
 	id: string;
 	number: number;
@@ -362,11 +542,27 @@ export interface Label {
 	assigned_to_id?: string;
 	created_at: Date;
 	assigned_at?: Date;
+	/** When the label was last assigned or unassigned */
+	updated_at: Date;
 }
 
 export interface GenerateLabelsRequest {
 	count: number;
+	/** "avery_18660" (default), "avery_5160", or the ID of a custom label template */
 	template?: string;
+	/** What the labels are for, e.g. "garage boxes" */
+	purpose?: string;
+}
+
+export interface LabelBatch {
+	id: string;
+	label_count: number;
+	template: string;
+	purpose?: string;
+	created_by?: string;
+	created_at: Date;
+	/** JSON of the sheet layout the batch was generated with */
+	template_config?: string;
 }
 
 export interface LabelResponse {
@@ -378,6 +574,10 @@ export interface LabelResponse {
 	assigned_to_id?: string;
 	created_at: Date;
 	assigned_at?: Date;
+	/** When the label was last assigned or unassigned */
+	updated_at: Date;
+	/** Name of the entity the label is assigned to */
+	label_name?: string;
 }
 
 export interface GenerateLabelsResponse {
@@ -394,6 +594,9 @@ export interface AssignLabelRequest {
 export interface BatchWithLabels {
 	batch_id: string;
 	labels: LabelResponse[];
+	template: string;
+	purpose?: string;
+	created_by?: string;
 	created_at: Date;
 }
 
@@ -409,6 +612,16 @@ export interface Photo {
 	height?: number;
 	created_at: Date;
 	created_by: string;
+	/** Perceptual hash as 16 hex digits, set in the background after creation */
+	phash?: string;
+	/**
+	 * Camera, capture time and orientation read from the image's EXIF, as a JSON
+	 * object in a TEXT column
+	 */
+	exif_data?: string;
+	/** Where the photo was taken, from its EXIF GPS tags */
+	lat?: number;
+	lng?: number;
 }
 
 export interface PhotoResponse {
@@ -422,6 +635,9 @@ export interface PhotoResponse {
 	width?: number;
 	height?: number;
 	created_at: string;
+	exif_data?: unknown;
+	lat?: number;
+	lng?: number;
 }
 
 export interface CreatePhotoRequest {
@@ -438,9 +654,54 @@ export interface CreatePhotoRequest {
 export interface PresignedUploadUrl {
 	upload_url: string;
 	s3_key: string;
+	/** The content type the URL was signed for; the upload must use the same one */
+	content_type: string;
+	/** Seconds until the upload URL expires */
 	expires_in: number;
 }
 
+/**
+ * Presigned POST for an item manual or receipt. Send a `multipart/form-data` POST
+ * to `upload_url` with every entry in `fields`, then the file as `file`.
+ */
+export interface FileUploadResponse {
+	upload_url: string;
+	fields: Record<string, string>;
+	s3_key: string;
+	content_type: string;
+	/** Largest file S3 will accept, in bytes */
+	max_bytes: number;
+	/** Seconds until the upload expires */
+	expires_in: number;
+	/** Set when the file is large enough to be uploaded in parts */
+	multipart?: MultipartUploadResponse;
+}
+
+/** Upload each part with PUT to its URL, in order, then complete the upload */
+export interface MultipartUploadResponse {
+	upload_id: string;
+	/** Bytes per part; the last part may be smaller */
+	part_size: number;
+	part_urls: string[];
+}
+
+export interface UploadedPart {
+	part_number: number;
+	/** ETag header returned by the part's PUT */
+	etag: string;
+}
+
+export interface CompleteMultipartUploadRequest {
+	s3_key: string;
+	upload_id: string;
+	parts: UploadedPart[];
+}
+
+export interface AbortMultipartUploadRequest {
+	s3_key: string;
+	upload_id: string;
+}
+
 export interface Container {
 	id: string;
 	shelf_id?: string;
@@ -451,6 +712,7 @@ export interface Container {
 	created_at: Date;
 	updated_at: Date;
 	created_by: string;
+	position?: number;
 }
 
 export interface CreateContainerRequest {
@@ -458,13 +720,22 @@ export interface CreateContainerRequest {
 	parent_container_id?: string;
 	name: string;
 	description?: string;
+	/** Defaults to after the last container at the same location */
+	position?: number;
 }
 
+/** Body of `PATCH /api/containers/:id`. Omitted fields are left unchanged. */
 export interface UpdateContainerRequest {
 	name?: string;
 	description?: string;
 	shelf_id?: string;
 	parent_container_id?: string;
+	position?: number;
+}
+
+/** Body of `POST /api/containers/reorder`: sibling containers in their new order */
+export interface ReorderContainersRequest {
+	container_ids: string[];
 }
 
 export interface ContainerResponse {
@@ -474,8 +745,565 @@ export interface ContainerResponse {
 	name: string;
 	description?: string;
 	label_id?: string;
+	position?: number;
 	created_at: Date;
 	updated_at: Date;
+	/** Only with `?include_photos=true` on list endpoints */
+	photo_count?: number;
+	/** Presigned URL of the earliest photo, only with `?include_photos=true` */
+	primary_photo_url?: string;
+	/** Only with `?include_tags=true`; empty rather than absent when untagged */
+	tags?: TagResponse[];
+}
+
+export interface ContainerSearchQuery {
+	q: string;
+	limit?: number;
+}
+
+export interface ContainerSearchResult {
+	container: ContainerResponse;
+	/** Full-text relevance; 0 when the search fell back to substring matching */
+	rank: number;
+}
+
+export interface HomeAssistantAttributes {
+	friendly_name?: string;
+	area_id?: string;
+}
+
+/** A single entity from Home Assistant's `GET /api/states` response */
+export interface HomeAssistantState {
+	entity_id: string;
+	state: string;
+	attributes: HomeAssistantAttributes;
+	/** Not part of the states API itself; exports that join the area registry put it here */
+	area_id?: string;
+}
+
+export interface ImportResult {
+	rooms_created: number;
+	items_created: number;
+	tags_created: number;
+	errors: string[];
+}
+
+export interface SearchQuery {
+	q: string;
+	/** Comma-separated kinds to search, e.g. `item,container`. Defaults to all kinds. */
+	types?: string;
+	limit?: number;
+}
+
+export interface SearchResult {
+	kind: SearchResultKind;
+	id: string;
+	name: string;
+	/**
+	 * Breadcrumb of the enclosing locations, e.g. "Living Room > IKEA Kallax > Shelf 2".
+	 * Empty for rooms.
+	 */
+	path: string;
+	/** 3 = exact name match, 2 = name prefix, 1 = name substring, 0 = other field matched */
+	score: number;
+}
+
+export interface SearchResponse {
+	results: SearchResult[];
+}
+
+export enum SearchResultKind {
+	Room = "room",
+	Unit = "unit",
+	Shelf = "shelf",
+	Container = "container",
+	Item = "item",
+}
+
+export interface BulkDeleteItemsRequest {
+	ids: string[];
+}
+
+/** Best-effort result: each requested ID lands in exactly one list */
+export interface BulkDeleteItemsResponse {
+	deleted: string[];
+	not_found: string[];
+	/** Items that exist but that the user may not delete */
+	forbidden: string[];
+}
+
+/** One step of a location breadcrumb */
+export interface PathNode {
+	kind: SearchResultKind;
+	id: string;
+	name: string;
+}
+
+export interface LocationPathResponse {
+	/** From the room down to the requested entity, which is the last node */
+	path: PathNode[];
+}
+
+export interface WebhookResponse {
+	id: string;
+	url: string;
+	/** Patterns like `item.create`, `container.*` or `*` */
+	event_types: string[];
+	active: boolean;
+	created_by: string;
+	created_at: Date;
+	updated_at: Date;
+}
+
+export interface CreateWebhookRequest {
+	url: string;
+	/** Key for the `X-Inventory-Signature` HMAC. Never returned by the API. */
+	secret: string;
+	event_types: string[];
+	/** Defaults to true */
+	active?: boolean;
+}
+
+export interface UpdateWebhookRequest {
+	url?: string;
+	secret?: string;
+	event_types?: string[];
+	active?: boolean;
+}
+
+/** One delivery attempt */
+export interface WebhookDelivery {
+	id: string;
+	webhook_id: string;
+	audit_log_id: string;
+	/** `<entity_type>.<action>`, e.g. `item.CREATE` */
+	event: string;
+	/** 1-based */
+	attempt: number;
+	succeeded: boolean;
+	/** `None` when no response was received */
+	status_code?: number;
+	error?: string;
+	created_at: Date;
+}
+
+/**
+ * Counts for the user stats route, within the caller's household. Trashed items
+ * are not counted.
+ */
+export interface UserStats {
+	/** Items whose `belongs_to_user_id` is this user */
+	items_owned: number;
+	items_created: number;
+	rooms_created: number;
+	containers_created: number;
+}
+
+/** Print a batch with each assigned label's entity name and location */
+/** A user-defined label sheet layout. Lengths are in millimetres. */
+export interface CustomLabelTemplate {
+	id: string;
+	name: string;
+	sheet_width_mm: number;
+	sheet_height_mm: number;
+	label_width_mm: number;
+	label_height_mm: number;
+	cols: number;
+	rows: number;
+	top_margin_mm: number;
+	left_margin_mm: number;
+	col_spacing_mm: number;
+	row_spacing_mm: number;
+	created_by?: string;
+	created_at: Date;
+}
+
+export interface CreateLabelTemplateRequest {
+	name: string;
+	sheet_width_mm: number;
+	sheet_height_mm: number;
+	label_width_mm: number;
+	label_height_mm: number;
+	cols: number;
+	rows: number;
+	top_margin_mm: number;
+	left_margin_mm: number;
+	/** Gap between columns, defaults to 0 */
+	col_spacing_mm?: number;
+	/** Gap between rows, defaults to 0 */
+	row_spacing_mm?: number;
+}
+
+/** Body of `PATCH /api/label-templates/:id`. Omitted fields are left unchanged. */
+export interface UpdateLabelTemplateRequest {
+	name?: string;
+	sheet_width_mm?: number;
+	sheet_height_mm?: number;
+	label_width_mm?: number;
+	label_height_mm?: number;
+	cols?: number;
+	rows?: number;
+	top_margin_mm?: number;
+	left_margin_mm?: number;
+	col_spacing_mm?: number;
+	row_spacing_mm?: number;
+}
+
+export interface PrintLabelsWithNamesRequest {
+	batch_id: string;
+	/**
+	 * "avery_18660", "avery_5160" or a custom template ID. Defaults to the layout
+	 * the batch was generated with.
+	 */
+	template?: string;
+}
+
+export enum ExportStatus {
+	Pending = "pending",
+	Complete = "complete",
+	Failed = "failed",
+}
+
+export interface ExportResponse {
+	id: string;
+	status: ExportStatus;
+	/** Presigned ZIP download, set once the export is complete */
+	download_url?: string;
+	download_url_expires_at?: Date;
+	photo_count?: number;
+	/** Why the export failed */
+	error?: string;
+	created_by: string;
+	created_at: Date;
+	completed_at?: Date;
+}
+
+/** Value of the items priced in one currency */
+export interface CurrencyValuation {
+	currency: string;
+	total_value: string;
+	item_count: number;
+}
+
+/** Value of the items in one room */
+export interface RoomValuation {
+	room_id: string;
+	room_name: string;
+	value: string;
+}
+
+/**
+ * Sum of purchase prices for items priced in `currency`. Items without a price, and
+ * trashed items, are left out. Decimals are serialized as strings, e.g. "1234.56".
+ */
+export interface ValuationResponse {
+	total_value: string;
+	currency: string;
+	item_count: number;
+	by_room: RoomValuation[];
+	/** Totals per currency, only present when items are priced in more than one */
+	by_currency?: CurrencyValuation[];
+}
+
+/**
+ * Counts for a room, or the whole inventory, through every level of the hierarchy.
+ * Trashed items are not counted.
+ */
+export interface InventoryStats {
+	shelving_unit_count: number;
+	shelf_count: number;
+	container_count: number;
+	item_count: number;
+	/** Photos of the room itself and of everything in it */
+	photo_count: number;
+	/** Distinct tags on the room and everything in it */
+	tag_count: number;
+	/** Sum of USD purchase prices, e.g. "123.45"; null when no item has one */
+	total_value?: string;
+}
+
+/** A CSV row that wasn't imported */
+export interface CsvImportError {
+	/** Line in the file, counting the header as line 1 */
+	row: number;
+	message: string;
+}
+
+/**
+ * Result of a CSV import, returned with 207 Multi-Status since rows succeed or fail
+ * individually. With `fail_fast`, `imported` is empty whenever `errors` isn't.
+ */
+export interface CsvImportResponse {
+	imported: ItemResponse[];
+	errors: CsvImportError[];
+}
+
+/** Where a contact submission is in the admin's triage */
+export enum ContactStatus {
+	Unread = "unread",
+	Read = "read",
+	Replied = "replied",
+	Archived = "archived",
+}
+
+/** The tags of one entity, grouped from a single query over a whole page */
+export interface TagsSideload {
+	entity_id: string;
+	tags: TagResponse[];
+}
+
+/** Body of `DELETE /api/admin/audit-logs/purge` */
+export interface PurgeAuditLogsRequest {
+	/** Delete entries older than this many days; must be at least 1 */
+	older_than_days: number;
+}
+
+export interface PurgeAuditLogsResponse {
+	deleted_count: number;
+	/** Number of delete statements that removed rows */
+	batches: number;
+}
+
+export interface ContainerSubtreeQuery {
+	/** Return the subtree as a depth-first list instead of nested nodes */
+	flat?: boolean;
+}
+
+/** A container with everything nested inside it */
+export interface ContainerTreeNode {
+	id: string;
+	name: string;
+	/** Items directly in this container */
+	item_count: number;
+	/** Items in this container and every container nested inside it */
+	total_item_count: number;
+	children: ContainerTreeNode[];
+}
+
+/** A container in the `?flat=true` form of a subtree */
+export interface FlatContainerTreeNode {
+	id: string;
+	/** `None` only for the requested container */
+	parent_container_id?: string;
+	name: string;
+	/** 0 for the requested container, 1 for its children, and so on */
+	depth: number;
+	item_count: number;
+	total_item_count: number;
+}
+
+export interface SimilarPhotosQuery {
+	/** Most bits a photo's hash may differ by, 0-64. Defaults to 10. */
+	threshold?: number;
+}
+
+export interface SimilarPhotoResponse {
+	photo: PhotoResponse;
+	/** Bits the perceptual hashes differ in; 0 is a (near-)exact duplicate */
+	distance: number;
+}
+
+export interface Household {
+	id: string;
+	name: string;
+	created_by?: string;
+	created_at: Date;
+}
+
+/** A user's place in a household */
+export enum HouseholdRole {
+	/** Can invite others */
+	Owner = "owner",
+	Member = "member",
+}
+
+/** Response of `POST /api/households/:id/invite` */
+export interface InviteResponse {
+	household_id: string;
+	/** Pass to `GET /api/households/join?token=`. Only shown once. */
+	token: string;
+	expires_at: Date;
+}
+
+/** Response of `GET /api/households/join` */
+export interface JoinHouseholdResponse {
+	household_id: string;
+	role: HouseholdRole;
+}
+
+/** Physical condition of an item, stored as its snake_case name */
+export enum Condition {
+	New = "new",
+	LikeNew = "like_new",
+	Good = "good",
+	Fair = "fair",
+	Poor = "poor",
+}
+
+/** A label given to an item by `POST /api/containers/:id/assign-labels` */
+export interface ItemLabelAssignment {
+	item_id: string;
+	label_id: string;
+	label_number: number;
+}
+
+/** Response of `POST /api/containers/:id/assign-labels` */
+export interface AssignContainerLabelsResponse {
+	assigned: ItemLabelAssignment[];
+	/** Items in the container that already had a label */
+	skipped_already_labeled: number;
+	/**
+	 * Whether some unlabeled items were left without a label because too few
+	 * unassigned labels were available
+	 */
+	insufficient_labels: boolean;
+}
+
+/**
+ * Body of `POST /api/units/:id/shelves/reorder`: every shelf of the unit, in its
+ * new order
+ */
+export interface ReorderShelvesRequest {
+	shelf_ids: string[];
+}
+
+/** Response of `GET /api/labels/:id/entity`: what a scanned label is stuck on */
+export interface LabelEntityResponse {
+	/** "room", "unit", "shelf", "container" or "item" */
+	entity_type: string;
+	entity_id: string;
+	/**
+	 * The entity's public view, not its full response: a `PublicItemResponse` for
+	 * items, a `PathNode` (kind, id and name) for rooms, units, shelves and containers
+	 */
+	entity: unknown;
+}
+
+/**
+ * A live item named like one being created in the same location, returned in the
+ * `details.similar_items` of a `POSSIBLE_DUPLICATE` error
+ */
+export interface SimilarItem {
+	id: string;
+	name: string;
+	/** pg_trgm similarity of the names, from 0 to 1 */
+	similarity: number;
+}
+
+/** A recorded write to the inventory, streamed to `GET /api/events` subscribers */
+export interface InventoryEvent {
+	/** The audit log action, e.g. `CREATE`, `UPDATE` or `MOVE` */
+	event_type: string;
+	/** e.g. `item` or `container` */
+	entity_type: string;
+	entity_id: string;
+	/** Household of the user who made the write; subscribers only see their own */
+	household_id: string;
+	timestamp: Date;
+}
+
+/** Lowest and highest label number in a batch */
+export interface LabelRange {
+	from: number;
+	to: number;
+}
+
+/** Entry of `GET /api/labels/batches` */
+export interface LabelBatchSummary {
+	batch_id: string;
+	/** Labels in the batch */
+	count: number;
+	/** When its first label was created */
+	created_at: Date;
+	label_range: LabelRange;
+}
+
+/** Tags whose names only differ in case, spacing or punctuation */
+export interface TagDuplicateGroup {
+	/** The `tag_duplicate_key` the tags share */
+	normalized_name: string;
+	tags: TagResponse[];
+}
+
+/** Response of `POST /api/tags/:id/merge-into/:target_id` */
+export interface TagMergeResponse {
+	/** Entities that had the merged tag; they all have the target tag now */
+	merged_entity_count: number;
+	/** The merged tag, unless another household still uses it */
+	deleted_tag_id?: string;
+}
+
+/**
+ * JSON body of `POST /api/rooms/:id/floor-plan`, using one of the room's photos as
+ * its floor plan. The endpoint also takes a `multipart/form-data` upload with the
+ * image as `file`.
+ */
+export interface SetFloorPlanRequest {
+	photo_id: string;
+}
+
+/** Query of `DELETE` on rooms, shelving units and shelves */
+export interface CascadeDeleteQuery {
+	/**
+	 * Also delete everything inside: units, shelves, containers and items. Without
+	 * it, deleting a location that isn't empty fails with 409 `HAS_CHILDREN`.
+	 */
+	cascade?: boolean;
+}
+
+/** Number of each kind of location, plus items, in a subtree */
+export interface LocationCounts {
+	rooms: number;
+	units: number;
+	shelves: number;
+	containers: number;
+	/** Including items in the trash */
+	items: number;
+}
+
+export interface CascadeDeleteResponse {
+	/** The location itself and, with `cascade=true`, everything that was inside it */
+	deleted: LocationCounts;
+}
+
+/**
+ * An item recommended alongside another for sharing its tags or, when that one is
+ * untagged, its container or shelf. Public, so no more than the public item view.
+ */
+export interface SimilarItemResponse {
+	id: string;
+	name: string;
+	product_link?: string;
+	/** Tags it has in common with the item asked about; 0 for location matches */
+	shared_tag_count: number;
+}
+
+/** A user's public profile, for anyone once they have set `is_public` */
+export interface PublicUserResponse {
+	id: string;
+	public_display_name?: string;
+	/** Live items the user created or owns */
+	item_count: number;
+	/** The most recently created of those items */
+	public_items: PublicItemResponse[];
+}
+
+/** Body of `PUT /api/users/me/profile`, replacing both settings */
+export interface UpdateProfileRequest {
+	/** Shown instead of the account name on public pages; blank or omitted clears it */
+	public_display_name?: string;
+	is_public: boolean;
+}
+
+export interface BulkCreateTagsRequest {
+	/** Tag names; each entry may also be a comma-separated list, e.g. `"tools, hardware"` */
+	names: string[];
+}
+
+export interface BulkCreateTagsResponse {
+	created: TagResponse[];
+	/** Requested tags that were already there, left unchanged */
+	already_existed: TagResponse[];
 }
 
 /**
@@ -485,7 +1313,7 @@ export interface ContainerResponse {
  * These functions allow for flexible encoding and decoding of data, ensuring that complex types are properly handled when converting between TS objects and JSON
  */
 export const ReviverFunc = (key: string, value: unknown): unknown => {
-    if (typeof value === "string" && /^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?Z$/.test(value) && (key === "assigned_at" || key === "created_at" || key === "updated_at")) {
+    if (typeof value === "string" && /^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?Z$/.test(value) && (key === "assigned_at" || key === "created_at" || key === "deleted_at" || key === "replied_at" || key === "updated_at")) {
         return new Date(value);
     }
     return value;
@@ -40,6 +40,8 @@ export function createLambdaFunctions(
           GOOGLE_CLIENT_SECRET: config.googleClientSecret,
           GOOGLE_REDIRECT_URL: pulumi.interpolate`https://${config.domainName}/api/auth/callback`,
           APP_BASE_URL: pulumi.interpolate`https://${config.domainName}`,
+          APP_ENV: "production",
+          CORS_ALLOWED_ORIGINS: pulumi.interpolate`https://${config.domainName}`,
           ANTHROPIC_API_KEY: config.anthropicApiKey,
           S3_BUCKET: storage.photoBucket.id,
           S3_REGION: "us-east-1",
@@ -64,6 +66,8 @@ export function createLambdaFunctions(
           GOOGLE_CLIENT_SECRET: config.googleClientSecret,
           GOOGLE_REDIRECT_URL: pulumi.interpolate`https://${config.domainName}/api/auth/callback`,
           APP_BASE_URL: pulumi.interpolate`https://${config.domainName}`,
+          APP_ENV: "production",
+          CORS_ALLOWED_ORIGINS: pulumi.interpolate`https://${config.domainName}`,
           ANTHROPIC_API_KEY: config.anthropicApiKey,
           S3_BUCKET: storage.photoBucketReplica.id,
           S3_REGION: "us-east-2",